@@ -0,0 +1,303 @@
+//! Pluggable network backends for `BpciTransport`
+//!
+//! A `Transport` moves opaque byte messages between socket addresses. The
+//! BPCI layer above it handles encoding, authentication and peer bookkeeping,
+//! so the same transport logic runs over TCP in production and over in-process
+//! channels in tests.
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::{mpsc, Mutex};
+use tracing::{debug, warn};
+
+use crate::{BpciError, NetworkErrorKind};
+
+/// Bytes received from the peer listening at the given address, or from the
+/// connection's own address if that peer is not listening
+pub type Inbound = (SocketAddr, Vec<u8>);
+
+/// Stream of inbound messages from every connection of a transport
+pub type InboundStream = mpsc::UnboundedReceiver<Inbound>;
+
+/// Message-oriented network backend
+#[async_trait]
+pub trait Transport: Send + Sync + std::fmt::Debug {
+    /// Accept connections on `addr`; returns the address actually bound
+    async fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, BpciError>;
+
+    /// Open a connection to `addr`
+    async fn dial(&self, addr: SocketAddr) -> Result<(), BpciError>;
+
+    /// Send one message to `addr`, which must have been dialed or have connected to us
+    async fn send(&self, addr: SocketAddr, data: Vec<u8>) -> Result<(), BpciError>;
+
+    /// Messages received on any connection. Can be taken once; later calls return `None`.
+    fn take_inbound(&self) -> Option<InboundStream>;
+}
+
+/// Longest hello frame accepted; an address in text form fits easily
+const MAX_HELLO_SIZE: usize = 64;
+
+/// TCP backend. Messages are framed with a 4-byte big-endian length prefix,
+/// and frames over `max_frame_size` close the connection.
+///
+/// The first frame on a dialed connection is a hello naming the dialer's
+/// listen address, empty if it is not listening. Inbound messages, and the
+/// connection itself, are keyed by that address rather than the connection's
+/// ephemeral port, so a peer is known by the address it is registered under
+/// whichever side dialed. The claim is not authenticated here; like the
+/// address `InMemoryTransport` reports, it only names the connection.
+#[derive(Debug)]
+pub struct TcpTransport {
+    max_frame_size: usize,
+    local_addr: std::sync::Mutex<Option<SocketAddr>>,
+    connections: Arc<Mutex<HashMap<SocketAddr, Connection>>>,
+    next_connection_id: Arc<AtomicU64>,
+    inbound_tx: mpsc::UnboundedSender<Inbound>,
+    inbound_rx: std::sync::Mutex<Option<InboundStream>>,
+}
+
+/// Write half of an open connection; the id tells a replaced connection's
+/// reader not to forget its successor
+#[derive(Debug)]
+struct Connection {
+    id: u64,
+    writer: OwnedWriteHalf,
+}
+
+impl TcpTransport {
+    pub fn new(max_frame_size: usize) -> Self {
+        let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+        Self {
+            max_frame_size,
+            local_addr: std::sync::Mutex::new(None),
+            connections: Arc::new(Mutex::new(HashMap::new())),
+            next_connection_id: Arc::new(AtomicU64::new(0)),
+            inbound_tx,
+            inbound_rx: std::sync::Mutex::new(Some(inbound_rx)),
+        }
+    }
+}
+
+// Register the write half under `from` and forward frames from `reader` to the
+// inbound stream until the connection closes, then forget the write half
+// unless a newer connection to `from` has replaced it
+async fn register_connection(
+    reader: OwnedReadHalf,
+    writer: OwnedWriteHalf,
+    from: SocketAddr,
+    max_frame_size: usize,
+    inbound_tx: mpsc::UnboundedSender<Inbound>,
+    connections: Arc<Mutex<HashMap<SocketAddr, Connection>>>,
+    id: u64,
+) {
+    connections.lock().await.insert(from, Connection { id, writer });
+    tokio::spawn(async move {
+        if let Err(e) = read_frames(reader, from, max_frame_size, &inbound_tx).await {
+            debug!("Connection from {} closed: {}", from, e);
+        }
+        let mut connections = connections.lock().await;
+        if connections.get(&from).is_some_and(|connection| connection.id == id) {
+            connections.remove(&from);
+        }
+    });
+}
+
+// The address an accepted connection speaks for: the listen address in its
+// hello, with the connection's own IP if it bound a wildcard, or the
+// connection's address if the dialer is not listening
+async fn read_hello(reader: &mut OwnedReadHalf, remote: SocketAddr) -> std::io::Result<SocketAddr> {
+    let len = reader.read_u32().await? as usize;
+    if len > MAX_HELLO_SIZE {
+        return Err(std::io::Error::new(std::io::ErrorKind::InvalidData, format!("{} byte hello", len)));
+    }
+    let mut hello = vec![0u8; len];
+    reader.read_exact(&mut hello).await?;
+    if hello.is_empty() {
+        return Ok(remote);
+    }
+    let mut listen_addr: SocketAddr = std::str::from_utf8(&hello).ok()
+        .and_then(|hello| hello.parse().ok())
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidData, "malformed hello"))?;
+    if listen_addr.ip().is_unspecified() {
+        listen_addr.set_ip(remote.ip());
+    }
+    Ok(listen_addr)
+}
+
+async fn read_frames(
+    mut reader: OwnedReadHalf,
+    from: SocketAddr,
+    max_frame_size: usize,
+    inbound_tx: &mpsc::UnboundedSender<Inbound>,
+) -> std::io::Result<()> {
+    loop {
+        let len = reader.read_u32().await? as usize;
+        if len > max_frame_size {
+            warn!("Dropping connection from {}: {} byte frame exceeds limit {}", from, len, max_frame_size);
+            return Ok(());
+        }
+        let mut frame = vec![0u8; len];
+        reader.read_exact(&mut frame).await?;
+        if inbound_tx.send((from, frame)).is_err() {
+            return Ok(());
+        }
+    }
+}
+
+#[async_trait]
+impl Transport for TcpTransport {
+    async fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, BpciError> {
+        let listener = TcpListener::bind(addr).await
+            .map_err(|e| BpciError::network(NetworkErrorKind::BindFailed, format!("{}: {}", addr, e)))?;
+        let local_addr = listener.local_addr()
+            .map_err(|e| BpciError::network(NetworkErrorKind::BindFailed, e.to_string()))?;
+
+        *self.local_addr.lock().unwrap_or_else(|e| e.into_inner()) = Some(local_addr);
+
+        let max_frame_size = self.max_frame_size;
+        let connections = self.connections.clone();
+        let next_connection_id = self.next_connection_id.clone();
+        let inbound_tx = self.inbound_tx.clone();
+        tokio::spawn(async move {
+            loop {
+                let (stream, remote) = match listener.accept().await {
+                    Ok(accepted) => accepted,
+                    Err(e) => {
+                        warn!("Accept failed on {}: {}", local_addr, e);
+                        continue;
+                    }
+                };
+                let (mut reader, writer) = stream.into_split();
+                let connections = connections.clone();
+                let inbound_tx = inbound_tx.clone();
+                let id = next_connection_id.fetch_add(1, Ordering::Relaxed);
+                // A slow hello must not hold up other connections
+                tokio::spawn(async move {
+                    match read_hello(&mut reader, remote).await {
+                        Ok(from) => register_connection(reader, writer, from, max_frame_size, inbound_tx, connections, id).await,
+                        Err(e) => warn!("Dropping connection from {}: bad hello: {}", remote, e),
+                    }
+                });
+            }
+        });
+        Ok(local_addr)
+    }
+
+    async fn dial(&self, addr: SocketAddr) -> Result<(), BpciError> {
+        let stream = TcpStream::connect(addr).await
+            .map_err(|e| BpciError::network(NetworkErrorKind::ConnectRefused, format!("{}: {}", addr, e)))?;
+        let (reader, mut writer) = stream.into_split();
+        let hello = self.local_addr.lock().unwrap_or_else(|e| e.into_inner())
+            .map(|local_addr| local_addr.to_string())
+            .unwrap_or_default();
+        let sent = async {
+            writer.write_u32(hello.len() as u32).await?;
+            writer.write_all(hello.as_bytes()).await
+        }.await;
+        sent.map_err(|e| BpciError::network(NetworkErrorKind::PeerReset, format!("{}: {}", addr, e)))?;
+
+        let id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        register_connection(reader, writer, addr, self.max_frame_size, self.inbound_tx.clone(), self.connections.clone(), id).await;
+        Ok(())
+    }
+
+    async fn send(&self, addr: SocketAddr, data: Vec<u8>) -> Result<(), BpciError> {
+        let len = u32::try_from(data.len())
+            .ok()
+            .filter(|len| *len as usize <= self.max_frame_size)
+            .ok_or(BpciError::InvalidMessage)?;
+
+        let mut connections = self.connections.lock().await;
+        let writer = &mut connections.get_mut(&addr).ok_or_else(|| BpciError::PeerNotFound(addr.to_string()))?.writer;
+        let result = async {
+            writer.write_u32(len).await?;
+            writer.write_all(&data).await
+        }.await;
+        if let Err(e) = result {
+            connections.remove(&addr);
+            return Err(BpciError::network(NetworkErrorKind::PeerReset, format!("{}: {}", addr, e)));
+        }
+        Ok(())
+    }
+
+    fn take_inbound(&self) -> Option<InboundStream> {
+        self.inbound_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
+
+/// In-process network shared by `InMemoryTransport`s; addresses are only
+/// names here, nothing is bound
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryNetwork {
+    listeners: Arc<std::sync::Mutex<HashMap<SocketAddr, mpsc::UnboundedSender<Inbound>>>>,
+}
+
+impl InMemoryNetwork {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// A new endpoint on this network
+    pub fn transport(&self) -> InMemoryTransport {
+        let (inbound_tx, inbound_rx) = mpsc::unbounded_channel();
+        InMemoryTransport {
+            network: self.clone(),
+            local_addr: std::sync::Mutex::new(None),
+            inbound_tx,
+            inbound_rx: std::sync::Mutex::new(Some(inbound_rx)),
+        }
+    }
+}
+
+/// Channel-backed transport for tests
+#[derive(Debug)]
+pub struct InMemoryTransport {
+    network: InMemoryNetwork,
+    local_addr: std::sync::Mutex<Option<SocketAddr>>,
+    inbound_tx: mpsc::UnboundedSender<Inbound>,
+    inbound_rx: std::sync::Mutex<Option<InboundStream>>,
+}
+
+#[async_trait]
+impl Transport for InMemoryTransport {
+    async fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, BpciError> {
+        let mut listeners = self.network.listeners.lock().unwrap_or_else(|e| e.into_inner());
+        if listeners.contains_key(&addr) {
+            return Err(BpciError::network(NetworkErrorKind::BindFailed, format!("{} already in use", addr)));
+        }
+        listeners.insert(addr, self.inbound_tx.clone());
+        *self.local_addr.lock().unwrap_or_else(|e| e.into_inner()) = Some(addr);
+        Ok(addr)
+    }
+
+    async fn dial(&self, addr: SocketAddr) -> Result<(), BpciError> {
+        if self.network.listeners.lock().unwrap_or_else(|e| e.into_inner()).contains_key(&addr) {
+            Ok(())
+        } else {
+            Err(BpciError::network(NetworkErrorKind::ConnectRefused, format!("nothing listening on {}", addr)))
+        }
+    }
+
+    async fn send(&self, addr: SocketAddr, data: Vec<u8>) -> Result<(), BpciError> {
+        let from = self.local_addr.lock().unwrap_or_else(|e| e.into_inner())
+            .ok_or_else(|| BpciError::network(NetworkErrorKind::ConnectRefused, "send before listen"))?;
+        let target = self.network.listeners.lock().unwrap_or_else(|e| e.into_inner())
+            .get(&addr)
+            .cloned()
+            .ok_or_else(|| BpciError::PeerNotFound(addr.to_string()))?;
+        target.send((from, data))
+            .map_err(|_| BpciError::network(NetworkErrorKind::PeerReset, format!("{} stopped receiving", addr)))
+    }
+
+    fn take_inbound(&self) -> Option<InboundStream> {
+        self.inbound_rx.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+}
@@ -0,0 +1,421 @@
+//! Fragmentation of large BPCI frames with optional Reed-Solomon FEC
+//!
+//! A frame is split into `k` equal data shards; with `parity_shards = m > 0`,
+//! `m` parity shards are added, and the receiver rebuilds the frame from any
+//! `k` of the `k + m` fragments instead of waiting for a retransmit.
+//!
+//! Incomplete frames are buffered within `ReassemblyLimits`, so a peer sending
+//! fragments it never completes cannot grow the receiver's memory without bound.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use reed_solomon_erasure::galois_8::ReedSolomon;
+use serde::{Deserialize, Serialize};
+use tracing::debug;
+
+use crate::{BpciError, BpciFrame};
+
+/// Reed-Solomon over GF(2^8) supports at most 256 shards in total
+pub const MAX_SHARDS: usize = 256;
+
+/// How frames are cut into fragments
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FragmentConfig {
+    /// Largest shard payload in bytes
+    pub max_fragment_size: usize,
+    /// Parity shards added per frame; 0 disables FEC
+    pub parity_shards: usize,
+}
+
+impl Default for FragmentConfig {
+    fn default() -> Self {
+        Self {
+            max_fragment_size: 1200,
+            parity_shards: 0,
+        }
+    }
+}
+
+impl FragmentConfig {
+    pub fn with_parity_shards(mut self, parity_shards: usize) -> Self {
+        self.parity_shards = parity_shards;
+        self
+    }
+}
+
+/// One shard of a fragmented frame
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameFragment {
+    /// `BpciFrame::hash` of the whole frame
+    pub frame_id: [u8; 32],
+    /// Shard position; indices `>= data_shards` are parity
+    pub index: u16,
+    pub data_shards: u16,
+    pub parity_shards: u16,
+    /// Length of the encoded frame before padding to whole shards
+    pub frame_len: u32,
+    #[serde(with = "serde_bytes")]
+    pub shard: Vec<u8>,
+}
+
+impl FrameFragment {
+    pub fn is_parity(&self) -> bool {
+        self.index >= self.data_shards
+    }
+}
+
+fn reed_solomon(data_shards: usize, parity_shards: usize) -> Result<ReedSolomon, BpciError> {
+    ReedSolomon::new(data_shards, parity_shards)
+        .map_err(|e| BpciError::Fec(format!("{} data + {} parity shards: {:?}", data_shards, parity_shards, e)))
+}
+
+/// Split `frame` into data shards plus `config.parity_shards` parity shards
+pub fn fragment_frame(frame: &BpciFrame, config: FragmentConfig) -> Result<Vec<FrameFragment>, BpciError> {
+    if config.max_fragment_size == 0 {
+        return Err(BpciError::Fec("max_fragment_size must be non-zero".to_string()));
+    }
+    let frame_id = frame.hash()?;
+    let encoded = bincode::serialize(frame)?;
+    let frame_len = u32::try_from(encoded.len()).map_err(|_| BpciError::InvalidMessage)?;
+
+    let data_shards = encoded.len().div_ceil(config.max_fragment_size).max(1);
+    let parity_shards = config.parity_shards;
+    if data_shards + parity_shards > MAX_SHARDS {
+        return Err(BpciError::Fec(format!(
+            "{} data + {} parity shards exceeds {}", data_shards, parity_shards, MAX_SHARDS
+        )));
+    }
+
+    // Equal-sized shards, the last one zero-padded
+    let shard_len = encoded.len().div_ceil(data_shards).max(1);
+    let mut shards: Vec<Vec<u8>> = (0..data_shards + parity_shards)
+        .map(|i| {
+            let start = (i * shard_len).min(encoded.len());
+            let end = ((i + 1) * shard_len).min(encoded.len());
+            let mut shard = if i < data_shards { encoded[start..end].to_vec() } else { Vec::new() };
+            shard.resize(shard_len, 0);
+            shard
+        })
+        .collect();
+    if parity_shards > 0 {
+        reed_solomon(data_shards, parity_shards)?
+            .encode(&mut shards)
+            .map_err(|e| BpciError::Fec(format!("encoding failed: {:?}", e)))?;
+    }
+
+    Ok(shards
+        .into_iter()
+        .enumerate()
+        .map(|(index, shard)| FrameFragment {
+            frame_id,
+            index: index as u16,
+            data_shards: data_shards as u16,
+            parity_shards: parity_shards as u16,
+            frame_len,
+            shard,
+        })
+        .collect())
+}
+
+/// Bounds on what a `FrameReassembler` buffers for incomplete frames
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReassemblyLimits {
+    /// Incomplete frames held at once; the oldest is dropped to make room
+    pub max_pending_frames: usize,
+    /// Shard bytes held at once across all incomplete frames
+    pub max_pending_bytes: usize,
+    /// Incomplete frames older than this are dropped
+    pub timeout: Duration,
+}
+
+impl Default for ReassemblyLimits {
+    fn default() -> Self {
+        Self {
+            max_pending_frames: 64,
+            max_pending_bytes: 16 * 1024 * 1024,
+            timeout: Duration::from_secs(10),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct PartialFrame {
+    data_shards: usize,
+    parity_shards: usize,
+    frame_len: usize,
+    shards: Vec<Option<Vec<u8>>>,
+    received: usize,
+    /// Shard bytes held for this frame
+    bytes: usize,
+    first_seen: Instant,
+}
+
+/// Incomplete frames are keyed by the peer sending them as well as the frame
+/// id, so fragments from one peer never mix into another's frame
+type PartialKey = (String, [u8; 32]);
+
+/// Collects fragments and rebuilds each frame once enough of them arrive
+#[derive(Debug, Default)]
+pub struct FrameReassembler {
+    limits: ReassemblyLimits,
+    partial: HashMap<PartialKey, PartialFrame>,
+    pending_bytes: usize,
+}
+
+impl FrameReassembler {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_limits(mut self, limits: ReassemblyLimits) -> Self {
+        self.limits = limits;
+        self
+    }
+
+    /// Frames with some, but not yet enough, fragments
+    pub fn pending(&self) -> usize {
+        self.partial.len()
+    }
+
+    /// Shard bytes buffered for incomplete frames
+    pub fn pending_bytes(&self) -> usize {
+        self.pending_bytes
+    }
+
+    /// Add a fragment received from `peer_id` at `now`. Returns the frame when
+    /// this fragment completes it: all data shards, or any `data_shards` of the
+    /// data and parity shards. Incomplete frames past the timeout are dropped
+    /// first, and the oldest ones make room when a limit is reached.
+    pub fn insert(&mut self, peer_id: &str, fragment: FrameFragment, now: Instant) -> Result<Option<BpciFrame>, BpciError> {
+        self.expire(now);
+
+        let data_shards = fragment.data_shards as usize;
+        let parity_shards = fragment.parity_shards as usize;
+        let total = data_shards + parity_shards;
+        if data_shards == 0 || total > MAX_SHARDS || fragment.index as usize >= total {
+            return Err(BpciError::InvalidMessage);
+        }
+        // `fragment_frame` gives every shard of a frame this length, which
+        // bounds what one frame can buffer before any of it is stored
+        let frame_len = fragment.frame_len as usize;
+        let shard_len = frame_len.div_ceil(data_shards).max(1);
+        if fragment.shard.len() != shard_len {
+            return Err(BpciError::InvalidMessage);
+        }
+        if total * shard_len > self.limits.max_pending_bytes {
+            return Err(BpciError::Fec(format!(
+                "{} shards of {} bytes exceed the reassembly limit of {} bytes",
+                total, shard_len, self.limits.max_pending_bytes
+            )));
+        }
+
+        let key = (peer_id.to_string(), fragment.frame_id);
+        if !self.partial.contains_key(&key) {
+            while self.partial.len() >= self.limits.max_pending_frames.max(1) && self.evict_oldest(&key) {}
+            self.partial.insert(key.clone(), PartialFrame {
+                data_shards,
+                parity_shards,
+                frame_len,
+                shards: vec![None; total],
+                received: 0,
+                bytes: 0,
+                first_seen: now,
+            });
+        }
+        let partial = &self.partial[&key];
+        if partial.data_shards != data_shards
+            || partial.parity_shards != parity_shards
+            || partial.frame_len != frame_len
+        {
+            return Err(BpciError::InvalidMessage);
+        }
+        if partial.shards[fragment.index as usize].is_some() {
+            return Ok(None);
+        }
+
+        while self.pending_bytes + shard_len > self.limits.max_pending_bytes && self.evict_oldest(&key) {}
+        let partial = self.partial.get_mut(&key).expect("partial frame present");
+        partial.shards[fragment.index as usize] = Some(fragment.shard);
+        partial.received += 1;
+        partial.bytes += shard_len;
+        self.pending_bytes += shard_len;
+        if partial.received < data_shards {
+            return Ok(None);
+        }
+
+        let partial = self.remove(&key).expect("partial frame present");
+        let frame = Self::reconstruct(partial)?;
+        if frame.hash()? != fragment.frame_id {
+            return Err(BpciError::Fec("reassembled frame does not match its id".to_string()));
+        }
+        Ok(Some(frame))
+    }
+
+    /// Drop incomplete frames first seen more than the timeout before `now`,
+    /// returning how many were dropped
+    pub fn expire(&mut self, now: Instant) -> usize {
+        let timeout = self.limits.timeout;
+        let before = self.partial.len();
+        let mut freed = 0;
+        self.partial.retain(|_, partial| {
+            let fresh = now.saturating_duration_since(partial.first_seen) < timeout;
+            if !fresh {
+                freed += partial.bytes;
+            }
+            fresh
+        });
+        self.pending_bytes -= freed;
+        before - self.partial.len()
+    }
+
+    // Drop the oldest incomplete frame other than `keep`; false if there is none
+    fn evict_oldest(&mut self, keep: &PartialKey) -> bool {
+        let oldest = self.partial.iter()
+            .filter(|(key, _)| *key != keep)
+            .min_by_key(|(_, partial)| partial.first_seen)
+            .map(|(key, _)| key.clone());
+        match oldest {
+            Some(key) => {
+                debug!("Reassembly buffer full; dropping incomplete frame from peer {}", key.0);
+                self.remove(&key);
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn remove(&mut self, key: &PartialKey) -> Option<PartialFrame> {
+        let partial = self.partial.remove(key)?;
+        self.pending_bytes -= partial.bytes;
+        Some(partial)
+    }
+
+    fn reconstruct(mut partial: PartialFrame) -> Result<BpciFrame, BpciError> {
+        if partial.shards[..partial.data_shards].iter().any(Option::is_none) {
+            reed_solomon(partial.data_shards, partial.parity_shards)?
+                .reconstruct_data(&mut partial.shards)
+                .map_err(|e| BpciError::Fec(format!("reconstruction failed: {:?}", e)))?;
+        }
+        let mut encoded: Vec<u8> = partial.shards
+            .into_iter()
+            .take(partial.data_shards)
+            .flat_map(|shard| shard.unwrap_or_default())
+            .collect();
+        if encoded.len() < partial.frame_len {
+            return Err(BpciError::InvalidMessage);
+        }
+        encoded.truncate(partial.frame_len);
+        Ok(bincode::deserialize(&encoded)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AeadKey, ClusterId, PohTick, ServiceIdHash, SigningKey};
+
+    fn large_frame() -> BpciFrame {
+        frame_with_nonce(1)
+    }
+
+    fn frame_with_nonce(nonce: u64) -> BpciFrame {
+        let payload: Vec<u8> = (0..10_000u32).map(|i| (i % 251) as u8).collect();
+        BpciFrame::new(
+            ClusterId::new([1u8; 16]),
+            ClusterId::new([2u8; 16]),
+            ServiceIdHash::new([3u8; 32]),
+            nonce,
+            PohTick::new([4u8; 32]),
+            &payload,
+            &AeadKey::new([5u8; 32]),
+            &SigningKey::new([6u8; 32]),
+        ).unwrap()
+    }
+
+    #[test]
+    fn test_reconstructs_with_parity_count_shards_dropped() {
+        let frame = large_frame();
+        let config = FragmentConfig { max_fragment_size: 1024, parity_shards: 3 };
+        let fragments = fragment_frame(&frame, config).unwrap();
+        let data_shards = fragments[0].data_shards as usize;
+        assert!(data_shards > 3);
+        assert_eq!(fragments.len(), data_shards + 3);
+
+        // Lose two data shards and one parity shard
+        let dropped = [0usize, data_shards / 2, data_shards + 1];
+        let mut reassembler = FrameReassembler::new();
+        let mut rebuilt = None;
+        for fragment in fragments.into_iter().filter(|f| !dropped.contains(&(f.index as usize))) {
+            if let Some(frame) = reassembler.insert("peer-1", fragment, Instant::now()).unwrap() {
+                rebuilt = Some(frame);
+            }
+        }
+        let rebuilt = rebuilt.expect("frame reconstructed");
+        assert_eq!(rebuilt.hash().unwrap(), frame.hash().unwrap());
+        assert_eq!(reassembler.pending(), 0);
+    }
+
+    #[test]
+    fn test_too_many_dropped_shards_leaves_frame_incomplete() {
+        let frame = large_frame();
+        let config = FragmentConfig { max_fragment_size: 1024, parity_shards: 3 };
+        let fragments = fragment_frame(&frame, config).unwrap();
+        let data_shards = fragments[0].data_shards as usize;
+
+        let dropped = [1usize, 2, data_shards - 1, data_shards + 2];
+        let mut reassembler = FrameReassembler::new();
+        for fragment in fragments.into_iter().filter(|f| !dropped.contains(&(f.index as usize))) {
+            assert!(reassembler.insert("peer-1", fragment, Instant::now()).unwrap().is_none());
+        }
+        assert_eq!(reassembler.pending(), 1);
+    }
+
+    #[test]
+    fn test_incomplete_frames_bounded_and_expired() {
+        let config = FragmentConfig { max_fragment_size: 1024, parity_shards: 0 };
+        let limits = ReassemblyLimits { max_pending_frames: 2, timeout: Duration::from_secs(5), ..ReassemblyLimits::default() };
+        let mut reassembler = FrameReassembler::new().with_limits(limits);
+        let start = Instant::now();
+
+        // One fragment each of three frames: the oldest makes room for the third
+        let firsts: Vec<FrameFragment> = (1..=3u64)
+            .map(|nonce| fragment_frame(&frame_with_nonce(nonce), config).unwrap().remove(0))
+            .collect();
+        let shard_len = firsts[0].shard.len();
+        for (i, fragment) in firsts.iter().enumerate() {
+            let now = start + Duration::from_millis(i as u64);
+            assert!(reassembler.insert("peer-1", fragment.clone(), now).unwrap().is_none());
+        }
+        assert_eq!(reassembler.pending(), 2);
+        assert_eq!(reassembler.pending_bytes(), 2 * shard_len);
+
+        // The same frame from another peer is buffered separately
+        assert!(reassembler.insert("peer-2", firsts[2].clone(), start + Duration::from_secs(1)).unwrap().is_none());
+        assert_eq!(reassembler.pending(), 2);
+
+        assert_eq!(reassembler.expire(start + Duration::from_millis(5_002)), 1);
+        assert_eq!(reassembler.expire(start + Duration::from_secs(6)), 1);
+        assert_eq!(reassembler.pending(), 0);
+        assert_eq!(reassembler.pending_bytes(), 0);
+    }
+
+    #[test]
+    fn test_oversized_or_malformed_fragments_rejected() {
+        let fragments = fragment_frame(&large_frame(), FragmentConfig { max_fragment_size: 1024, parity_shards: 2 }).unwrap();
+        let frame_bytes = fragments.len() * fragments[0].shard.len();
+
+        // A frame that could never fit is refused before anything is buffered
+        let limits = ReassemblyLimits { max_pending_bytes: frame_bytes - 1, ..ReassemblyLimits::default() };
+        let mut small = FrameReassembler::new().with_limits(limits);
+        assert!(matches!(small.insert("peer-1", fragments[0].clone(), Instant::now()), Err(BpciError::Fec(_))));
+        assert_eq!(small.pending_bytes(), 0);
+
+        // A shard longer than its frame's shard length
+        let mut padded = fragments[0].clone();
+        padded.shard.extend_from_slice(&[0u8; 4096]);
+        let mut reassembler = FrameReassembler::new();
+        assert!(matches!(reassembler.insert("peer-1", padded, Instant::now()), Err(BpciError::InvalidMessage)));
+        assert_eq!(reassembler.pending(), 0);
+    }
+}
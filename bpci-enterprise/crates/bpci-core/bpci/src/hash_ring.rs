@@ -0,0 +1,124 @@
+//! Consistent-hash ring for sticky service instance selection
+//!
+//! Each instance is placed on the ring at several points derived from its
+//! service id. A client key routes to the first instance point at or after the
+//! key's hash, so the same key keeps reaching the same instance, and adding or
+//! removing one instance only moves the keys that instance owned.
+
+use std::collections::BTreeMap;
+
+use crate::{domain_hash, ServiceInfo};
+
+/// Ring points per instance; more points spread keys more evenly
+pub const DEFAULT_VIRTUAL_NODES: usize = 64;
+
+/// Domain for hashing ring points and client keys
+pub const HASH_RING_DOMAIN: &str = "BPCI_HASH_RING";
+
+fn ring_hash(bytes: &[u8]) -> u64 {
+    let hash = domain_hash(HASH_RING_DOMAIN, bytes);
+    u64::from_be_bytes(hash[..8].try_into().expect("hash has 8 leading bytes"))
+}
+
+/// Ring over a fixed set of service instances
+#[derive(Debug, Clone, Default)]
+pub struct ConsistentHashRing {
+    instances: Vec<ServiceInfo>,
+    /// Ring point -> index into `instances`
+    points: BTreeMap<u64, usize>,
+}
+
+impl ConsistentHashRing {
+    pub fn new(instances: Vec<ServiceInfo>) -> Self {
+        Self::with_virtual_nodes(instances, DEFAULT_VIRTUAL_NODES)
+    }
+
+    pub fn with_virtual_nodes(instances: Vec<ServiceInfo>, virtual_nodes: usize) -> Self {
+        let mut points = BTreeMap::new();
+        for (index, instance) in instances.iter().enumerate() {
+            let id = &instance.service_id;
+            for replica in 0..virtual_nodes.max(1) {
+                let point = format!("{}/{}/{}#{}", id.name, id.version, id.instance_id, replica);
+                points.insert(ring_hash(point.as_bytes()), index);
+            }
+        }
+        Self { instances, points }
+    }
+
+    /// Instance owning `key`, or `None` for an empty ring
+    pub fn route(&self, key: &str) -> Option<ServiceInfo> {
+        let hash = ring_hash(key.as_bytes());
+        self.points.range(hash..)
+            .next()
+            .or_else(|| self.points.iter().next())
+            .map(|(_, &index)| self.instances[index].clone())
+    }
+
+    /// Number of instances on the ring
+    pub fn len(&self) -> usize {
+        self.instances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.instances.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HealthStatus, ServiceId};
+    use std::collections::HashMap;
+    use std::time::SystemTime;
+
+    fn instance(instance_id: &str) -> ServiceInfo {
+        ServiceInfo {
+            service_id: ServiceId {
+                name: "storage".to_string(),
+                version: "1.0.0".to_string(),
+                instance_id: instance_id.to_string(),
+            },
+            endpoint: "127.0.0.1:9000".parse().unwrap(),
+            capabilities: vec![],
+            health_status: HealthStatus::Healthy,
+            last_heartbeat: SystemTime::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    fn owners(ring: &ConsistentHashRing, keys: &[String]) -> Vec<String> {
+        keys.iter().map(|key| ring.route(key).unwrap().service_id.instance_id).collect()
+    }
+
+    #[test]
+    fn test_same_key_routes_to_same_instance() {
+        let ring = ConsistentHashRing::new((0..4).map(|i| instance(&format!("node-{}", i))).collect());
+        // Instance order does not matter, only membership
+        let reversed = ConsistentHashRing::new((0..4).rev().map(|i| instance(&format!("node-{}", i))).collect());
+        for key in ["client-1", "client-2", "session-abc"] {
+            let owner = ring.route(key).unwrap().service_id;
+            assert_eq!(ring.route(key).unwrap().service_id, owner);
+            assert_eq!(reversed.route(key).unwrap().service_id, owner);
+        }
+        assert!(ConsistentHashRing::new(vec![]).route("client-1").is_none());
+    }
+
+    #[test]
+    fn test_removing_instance_moves_only_its_keys() {
+        let keys: Vec<String> = (0..1000).map(|i| format!("client-{}", i)).collect();
+        let before = owners(&ConsistentHashRing::new((0..5).map(|i| instance(&format!("node-{}", i))).collect()), &keys);
+        let after = owners(&ConsistentHashRing::new([0, 1, 3, 4].iter().map(|i| instance(&format!("node-{}", i))).collect()), &keys);
+
+        let mut moved = 0;
+        for (old, new) in before.iter().zip(&after) {
+            if old == "node-2" {
+                assert_ne!(new, "node-2");
+                moved += 1;
+            } else {
+                assert_eq!(old, new, "a key not owned by the removed instance moved");
+            }
+        }
+        // node-2 owned roughly a fifth of the keys
+        assert!(moved > 50 && moved < 400, "moved {}", moved);
+    }
+}
@@ -0,0 +1,158 @@
+//! Typed identifiers and keys for the BPCI frame API
+//!
+//! Cluster ids, service hashes, PoH ticks and keys are all fixed-size byte
+//! arrays, and most of them are 32 bytes long. Giving each its own type turns
+//! a swapped positional argument into a compile error. Identifiers serialize
+//! exactly like the array they wrap, so frames on the wire are unchanged.
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+macro_rules! byte_id {
+    ($(#[$meta:meta])* $name:ident, $len:expr) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default, Serialize, Deserialize)]
+        #[serde(transparent)]
+        pub struct $name(pub [u8; $len]);
+
+        impl $name {
+            pub const fn new(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+
+            pub fn into_bytes(self) -> [u8; $len] {
+                self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; $len] {
+            fn from(id: $name) -> Self {
+                id.0
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+    };
+}
+
+macro_rules! secret_key {
+    ($(#[$meta:meta])* $name:ident) => {
+        $(#[$meta])*
+        #[derive(Clone)]
+        pub struct $name([u8; 32]);
+
+        impl $name {
+            pub const fn new(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+
+            pub fn as_bytes(&self) -> &[u8; 32] {
+                &self.0
+            }
+        }
+
+        impl From<[u8; 32]> for $name {
+            fn from(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+        }
+
+        // Never print key material
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                f.write_str(concat!(stringify!($name), "(..)"))
+            }
+        }
+
+        impl Drop for $name {
+            fn drop(&mut self) {
+                self.0.zeroize();
+            }
+        }
+    };
+}
+
+byte_id!(
+    /// Cluster a frame is sent from or to (`BpciFrame::src_cluster_id`, `dst_cluster_id`)
+    ClusterId, 16
+);
+
+byte_id!(
+    /// Destination service, `service_id_hash` of its FQDN
+    ServiceIdHash, 32
+);
+
+byte_id!(
+    /// PoH tick a frame references
+    PohTick, 32
+);
+
+byte_id!(
+    /// Ed25519 public key a frame signature is checked against
+    VerifyingKey, 32
+);
+
+secret_key!(
+    /// Symmetric key protecting a frame payload; zeroed on drop
+    AeadKey
+);
+
+secret_key!(
+    /// Ed25519 private key signing frame headers; zeroed on drop
+    SigningKey
+);
+
+impl SigningKey {
+    /// Ed25519 public key derived from this private key
+    pub fn verifying_key(&self) -> VerifyingKey {
+        VerifyingKey(self.to_dalek().verifying_key().to_bytes())
+    }
+
+    pub(crate) fn to_dalek(&self) -> ed25519_dalek::SigningKey {
+        ed25519_dalek::SigningKey::from_bytes(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bpi_enc::CanonicalCbor;
+
+    #[test]
+    fn test_ids_round_trip_bytes() {
+        let cluster = ClusterId::from([7u8; 16]);
+        assert_eq!(cluster.into_bytes(), [7u8; 16]);
+        assert_eq!(<[u8; 32]>::from(ServiceIdHash::new([3u8; 32])), [3u8; 32]);
+        assert_eq!(PohTick::new([4u8; 32]).as_bytes(), &[4u8; 32]);
+        assert_ne!(SigningKey::new([6u8; 32]).verifying_key(), VerifyingKey::new([6u8; 32]));
+        assert_eq!(SigningKey::new([6u8; 32]).verifying_key(), SigningKey::new([6u8; 32]).verifying_key());
+        assert_eq!(format!("{:?}", AeadKey::new([5u8; 32])), "AeadKey(..)");
+    }
+
+    #[test]
+    fn test_ids_serialize_like_raw_arrays() {
+        let svc = ServiceIdHash::new([3u8; 32]);
+        assert_eq!(CanonicalCbor::encode(&svc).unwrap(), CanonicalCbor::encode(&[3u8; 32]).unwrap());
+        assert_eq!(bincode::serialize(&ClusterId::new([1u8; 16])).unwrap(), bincode::serialize(&[1u8; 16]).unwrap());
+        assert_eq!(serde_json::to_string(&PohTick::new([4u8; 32])).unwrap(), serde_json::to_string(&[4u8; 32]).unwrap());
+
+        let decoded: ServiceIdHash = CanonicalCbor::decode(&CanonicalCbor::encode(&[3u8; 32]).unwrap()).unwrap();
+        assert_eq!(decoded, svc);
+    }
+}
@@ -7,26 +7,33 @@ use anyhow::Result;
 use bpi_enc::{domain_hash, domains::{TRANSPORT_MESSAGE_HASH, BPCI_HEADER_HASH}, EncodingError, CanonicalCbor};
 // use bpi_ibft::{IbftMessage, BlockProposal}; // TODO: Add bpi_ibft dependency
 // use bpi_poh::PohTick; // TODO: Add bpi_poh dependency
+use futures::StreamExt;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::net::SocketAddr;
+use std::path::Path;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use thiserror::Error;
-use tokio::sync::{mpsc, RwLock};
-use tracing::{debug, info, warn};
+use tokio::sync::{Mutex, RwLock};
+use tracing::{debug, info, info_span, warn, Instrument, Span};
 
 // Stage 18: E2E Key Agreement imports
-use x25519_dalek::{EphemeralSecret, StaticSecret, PublicKey as X25519PublicKey};
+use x25519_dalek::{EphemeralSecret, StaticSecret};
+pub use x25519_dalek::PublicKey as X25519PublicKey;
 use hkdf::Hkdf;
 use sha2::Sha256;
 use rand::rngs::OsRng;
+use rand::Rng;
+use subtle::ConstantTimeEq;
+use zeroize::{Zeroize, Zeroizing};
 
 /// BPCI Transport Layer Errors
 #[derive(Error, Debug)]
 pub enum BpciError {
-    #[error("Network error: {0}")]
-    Network(String),
+    #[error("Network error ({kind}): {detail}")]
+    Network { kind: NetworkErrorKind, detail: String },
     #[error("Serialization error: {0}")]
     Serialization(#[from] EncodingError),
     #[error("Peer not found: {0}")]
@@ -53,6 +60,59 @@ pub enum BpciError {
     ServiceKeyNotFound(String),
     #[error("Invalid public key: {0}")]
     InvalidPublicKey(String),
+    #[error("Bincode encoding failed: {0}")]
+    Bincode(#[from] bincode::Error),
+    #[error("Unknown wire format tag: {0:#04x}")]
+    UnknownWireFormat(u8),
+    #[error("Forward error correction failed: {0}")]
+    Fec(String),
+    #[error("Non-canonical encoding of {0}")]
+    NonCanonicalEncoding(&'static str),
+    #[error("Unknown AEAD algorithm id: {0:#04x}")]
+    UnknownAeadAlgorithm(u8),
+    #[error("Service registry full: {current} of {max} services registered")]
+    RegistryFull { current: usize, max: usize },
+    #[error("Frame references unknown PoH tick {0}")]
+    UnknownPohTick(String),
+}
+
+/// What went wrong in a `BpciError::Network`, for callers that branch on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NetworkErrorKind {
+    BindFailed,
+    ConnectRefused,
+    Timeout,
+    PeerReset,
+    Tls,
+    /// The transport is draining and refuses new sends
+    ShuttingDown,
+    /// A registry or connection limit has been reached
+    CapacityExceeded,
+    /// The addressed service is not registered or monitored
+    UnknownService,
+}
+
+impl std::fmt::Display for NetworkErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let kind = match self {
+            NetworkErrorKind::BindFailed => "bind failed",
+            NetworkErrorKind::ConnectRefused => "connection refused",
+            NetworkErrorKind::Timeout => "timed out",
+            NetworkErrorKind::PeerReset => "reset by peer",
+            NetworkErrorKind::Tls => "TLS failure",
+            NetworkErrorKind::ShuttingDown => "shutting down",
+            NetworkErrorKind::CapacityExceeded => "capacity exceeded",
+            NetworkErrorKind::UnknownService => "unknown service",
+        };
+        f.write_str(kind)
+    }
+}
+
+impl BpciError {
+    /// Build a `Network` error of the given kind
+    pub fn network(kind: NetworkErrorKind, detail: impl Into<String>) -> Self {
+        BpciError::Network { kind, detail: detail.into() }
+    }
 }
 
 /// Transport message types for BPCI
@@ -72,6 +132,82 @@ pub enum TransportMessage {
     Heartbeat { timestamp: u64 },
     /// Generic data message
     Data { payload: Vec<u8> },
+    /// Encoded `BpciFrame` whose receiver must answer with a `FrameAck`
+    ReliableFrame(Vec<u8>),
+    /// Acknowledgment of a `ReliableFrame`
+    FrameAck(FrameAck),
+    /// One shard of a frame sent with `send_frame_fragmented`
+    Fragment(FrameFragment),
+}
+
+/// Outbound queue priority of a transport message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MessagePriority {
+    /// Consensus traffic and heartbeats
+    High,
+    /// Peer discovery
+    Normal,
+    /// Bulk data
+    Low,
+}
+
+impl TransportMessage {
+    /// Queue class for this message; consensus must never wait behind bulk data
+    pub fn priority(&self) -> MessagePriority {
+        match self {
+            TransportMessage::Consensus(_)
+            | TransportMessage::PohTick(_)
+            | TransportMessage::BlockProposal(_)
+            | TransportMessage::IbftMessage(_)
+            | TransportMessage::Heartbeat { .. }
+            | TransportMessage::ReliableFrame(_)
+            | TransportMessage::FrameAck(_) => MessagePriority::High,
+            TransportMessage::PeerDiscovery(_) => MessagePriority::Normal,
+            TransportMessage::Data { .. }
+            | TransportMessage::Fragment(_) => MessagePriority::Low,
+        }
+    }
+}
+
+/// Number of queued messages per priority
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QueueDepths {
+    pub high: usize,
+    pub normal: usize,
+    pub low: usize,
+}
+
+/// Outbound message queues, drained highest priority first
+#[derive(Debug, Default)]
+struct PriorityQueues {
+    high: VecDeque<(String, TransportMessage)>,
+    normal: VecDeque<(String, TransportMessage)>,
+    low: VecDeque<(String, TransportMessage)>,
+}
+
+impl PriorityQueues {
+    fn push(&mut self, peer_id: String, message: TransportMessage) {
+        let queue = match message.priority() {
+            MessagePriority::High => &mut self.high,
+            MessagePriority::Normal => &mut self.normal,
+            MessagePriority::Low => &mut self.low,
+        };
+        queue.push_back((peer_id, message));
+    }
+
+    fn pop(&mut self) -> Option<(String, TransportMessage)> {
+        self.high.pop_front()
+            .or_else(|| self.normal.pop_front())
+            .or_else(|| self.low.pop_front())
+    }
+
+    fn depths(&self) -> QueueDepths {
+        QueueDepths {
+            high: self.high.len(),
+            normal: self.normal.len(),
+            low: self.low.len(),
+        }
+    }
 }
 
 /// Peer discovery message types
@@ -87,6 +223,8 @@ pub enum PeerDiscoveryMessage {
     PeerListRequest,
     /// Response with peer list
     PeerListResponse { peers: Vec<PeerInfo> },
+    /// Peer is shutting down and closing its connections
+    Goodbye { address: SocketAddr },
 }
 
 /// BPCI Frame structure per logic.md specification
@@ -94,16 +232,20 @@ pub enum PeerDiscoveryMessage {
 pub struct BpciFrame {
     /// Protocol version (always 1)
     pub version: u8,
+    /// `AeadAlgorithm::id` of the cipher protecting the payload
+    pub aead_alg: u8,
     /// Source cluster ID (16 bytes)
-    pub src_cluster_id: [u8; 16],
+    pub src_cluster_id: ClusterId,
     /// Destination cluster ID (16 bytes)
-    pub dst_cluster_id: [u8; 16],
-    /// Service ID hash (32 bytes) - H(service FQDN)
-    pub svc_id_hash: [u8; 32],
+    pub dst_cluster_id: ClusterId,
+    /// Service ID hash (32 bytes) - H(service FQDN), see `service_id_hash`
+    pub svc_id_hash: ServiceIdHash,
     /// Strictly increasing nonce per (src,svc)
     pub nonce: u64,
     /// PoH tick reference (32 bytes)
-    pub poh_tick: [u8; 32],
+    pub poh_tick: PohTick,
+    /// Commitment to the AEAD key, so the ciphertext opens under that key only
+    pub key_commitment: [u8; 32],
     /// AEAD ciphertext payload
     pub payload_ct: Vec<u8>,
     /// AEAD tag (16 bytes)
@@ -117,19 +259,56 @@ pub struct BpciFrame {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BpciFrameHeader {
     pub version: u8,
-    pub src_cluster_id: [u8; 16],
-    pub dst_cluster_id: [u8; 16],
-    pub svc_id_hash: [u8; 32],
+    pub aead_alg: u8,
+    pub src_cluster_id: ClusterId,
+    pub dst_cluster_id: ClusterId,
+    pub svc_id_hash: ServiceIdHash,
     pub nonce: u64,
-    pub poh_tick: [u8; 32],
+    pub poh_tick: PohTick,
+    pub key_commitment: [u8; 32],
     pub payload_len: usize,
 }
 
+// Byte comparison whose running time depends only on the input lengths, never on
+// where the inputs differ; every tag, signature, commitment and hash check goes through it
+fn ct_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
+// Decode `bytes`, accepting them only if they are exactly the canonical encoding of
+// the result; a second encoding of the same value would sign and hash differently
+fn decode_canonical<T>(bytes: &[u8], what: &'static str) -> Result<T, BpciError>
+where
+    T: Serialize + for<'de> Deserialize<'de>,
+{
+    let value: T = CanonicalCbor::decode(bytes)?;
+    if CanonicalCbor::encode(&value)? != bytes {
+        return Err(BpciError::NonCanonicalEncoding(what));
+    }
+    Ok(value)
+}
+
+impl BpciFrameHeader {
+    pub fn to_canonical_cbor(&self) -> Result<Vec<u8>, BpciError> {
+        Ok(CanonicalCbor::encode(self)?)
+    }
+
+    /// Decode a header, rejecting any encoding other than the canonical one
+    pub fn from_canonical_cbor(bytes: &[u8]) -> Result<Self, BpciError> {
+        decode_canonical(bytes, "BpciFrameHeader")
+    }
+}
+
+/// Scope nonces are tracked in: a frame's (src_cluster_id, svc_id_hash)
+pub type NonceKey = (ClusterId, ServiceIdHash);
+
 /// Nonce tracker for replay protection
 #[derive(Debug, Clone)]
 pub struct NonceTracker {
     /// Last seen nonce per (src_cluster_id, svc_id_hash)
-    nonces: HashMap<([u8; 16], [u8; 32]), u64>,
+    nonces: HashMap<NonceKey, u64>,
+    /// Hash of the last accepted frame per (src_cluster_id, svc_id_hash), with its nonce
+    last_frames: HashMap<NonceKey, (u64, [u8; 32])>,
     /// Out-of-order tolerance window
     tolerance_window: u64,
 }
@@ -141,9 +320,11 @@ pub struct AuthenticationResult {
     pub error: Option<String>,
     pub nonce_valid: bool,
     pub signature_valid: bool,
+    /// Byte-identical retransmit of the last accepted frame; already processed once
+    pub duplicate: bool,
 }
 
-/// X25519 Key Pair for E2E Key Agreement
+/// X25519 Key Pair for E2E Key Agreement. The private bytes are zeroed on drop.
 #[derive(Debug, Clone)]
 pub struct X25519KeyPair {
     pub private_key_bytes: [u8; 32],
@@ -165,7 +346,7 @@ pub struct E2EKeyManager {
     /// Service key registry
     registry: Arc<RwLock<ServiceKeyRegistry>>,
     /// Derived session keys cache: (src_cluster_id, svc_id_hash, ephemeral_pk) -> AEAD key
-    session_keys: Arc<RwLock<HashMap<([u8; 16], [u8; 32], [u8; 32]), [u8; 32]>>>,
+    session_keys: Arc<RwLock<HashMap<([u8; 16], [u8; 32], [u8; 32]), Zeroizing<[u8; 32]>>>>,
 }
 
 /// Key derivation result
@@ -191,6 +372,121 @@ pub struct BpciConfig {
     pub message_buffer_size: usize,
     /// Enable encryption
     pub enable_encryption: bool,
+    /// Encoding used for outbound transport messages
+    pub wire_format: WireFormat,
+    /// Largest encoded message accepted when decoding
+    pub max_message_size: usize,
+    /// Cipher used for outbound frames
+    pub aead_algorithm: AeadAlgorithm,
+    /// Ciphers accepted on inbound frames; frames using any other are rejected
+    pub permitted_aead_algorithms: Vec<AeadAlgorithm>,
+}
+
+/// Default `BpciConfig::max_message_size`
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+/// Encoded size cap for consensus, PoH and discovery messages
+pub const CONTROL_MESSAGE_MAX_SIZE: usize = 1024 * 1024;
+
+/// Encoded size cap for heartbeats, which carry only a timestamp
+pub const HEARTBEAT_MAX_SIZE: usize = 64;
+
+/// Wire encoding of a transport message. Encoded messages carry a one-byte
+/// tag so peers decode either format regardless of their own setting.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum WireFormat {
+    /// Self-describing CBOR
+    #[default]
+    Cbor,
+    /// Compact bincode, smaller for high-frequency messages such as heartbeats
+    Bincode,
+}
+
+impl WireFormat {
+    /// Tag byte prefixed to encoded messages
+    pub fn tag(self) -> u8 {
+        match self {
+            WireFormat::Cbor => 0x01,
+            WireFormat::Bincode => 0x02,
+        }
+    }
+
+    pub fn from_tag(tag: u8) -> Result<Self, BpciError> {
+        match tag {
+            0x01 => Ok(WireFormat::Cbor),
+            0x02 => Ok(WireFormat::Bincode),
+            other => Err(BpciError::UnknownWireFormat(other)),
+        }
+    }
+}
+
+/// Domain for hashing a service FQDN into `svc_id_hash`
+pub const BPCI_SERVICE_ID_HASH: &str = "BPCI_SERVICE_ID";
+
+/// `svc_id_hash` for a service FQDN. The name is normalized first: surrounding
+/// whitespace is trimmed, ASCII letters are lowercased since DNS names are
+/// case-insensitive, and a single trailing dot is dropped, so the absolute
+/// `consensus.mesh.` and relative `consensus.mesh` name the same service.
+pub fn service_id_hash(fqdn: &str) -> [u8; 32] {
+    let trimmed = fqdn.trim();
+    let normalized = trimmed.strip_suffix('.').unwrap_or(trimmed).to_ascii_lowercase();
+    domain_hash(BPCI_SERVICE_ID_HASH, normalized.as_bytes())
+}
+
+/// Domain for the AEAD key commitment carried in each frame
+pub const BPCI_KEY_COMMITMENT: &str = "BPCI_KEY_COMMITMENT";
+
+/// Domain for the HKDF salt binding an E2E session to its two public keys
+pub const BPCI_E2E_SALT: &str = "BPCI_E2E_SALT";
+
+/// Which way an E2E AEAD key protects traffic, seen from the side holding the
+/// ephemeral key. Each direction expands under its own HKDF info string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyDirection {
+    /// Ephemeral holder to service; what `derive_sender_key` and
+    /// `derive_receiver_key` agree on
+    Send,
+    /// Service back to the ephemeral holder
+    Recv,
+}
+
+impl KeyDirection {
+    fn info_label(self) -> &'static [u8] {
+        match self {
+            KeyDirection::Send => b"BPCI-AEAD-send",
+            KeyDirection::Recv => b"BPCI-AEAD-recv",
+        }
+    }
+}
+
+/// Cipher protecting BPCI frame payloads. Frames carry the one-byte id so the
+/// receiver picks the matching cipher; the id is signed with the header.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AeadAlgorithm {
+    #[default]
+    XChaCha20Poly1305,
+    /// Preferred where AES-NI or ARMv8 crypto extensions are available
+    Aes256Gcm,
+}
+
+impl AeadAlgorithm {
+    pub const ALL: &'static [AeadAlgorithm] = &[AeadAlgorithm::XChaCha20Poly1305, AeadAlgorithm::Aes256Gcm];
+
+    /// Id carried in `BpciFrame::aead_alg`
+    pub fn id(self) -> u8 {
+        match self {
+            AeadAlgorithm::XChaCha20Poly1305 => 0x01,
+            AeadAlgorithm::Aes256Gcm => 0x02,
+        }
+    }
+
+    pub fn from_id(id: u8) -> Result<Self, BpciError> {
+        match id {
+            0x01 => Ok(AeadAlgorithm::XChaCha20Poly1305),
+            0x02 => Ok(AeadAlgorithm::Aes256Gcm),
+            other => Err(BpciError::UnknownAeadAlgorithm(other)),
+        }
+    }
 }
 
 impl Default for BpciConfig {
@@ -202,30 +498,64 @@ impl Default for BpciConfig {
             heartbeat_interval: Duration::from_secs(10),
             message_buffer_size: 1000,
             enable_encryption: true,
+            wire_format: WireFormat::default(),
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
         }
     }
 }
 
 impl BpciFrame {
-    /// Create new BPCI frame with authentication
+    /// Create new BPCI frame with authentication, encrypted with the default AEAD
     pub fn new(
-        src_cluster_id: [u8; 16],
-        dst_cluster_id: [u8; 16],
-        svc_id_hash: [u8; 32],
+        src_cluster_id: ClusterId,
+        dst_cluster_id: ClusterId,
+        svc_id_hash: ServiceIdHash,
+        nonce: u64,
+        poh_tick: PohTick,
+        payload: &[u8],
+        aead_key: &AeadKey,
+        signing_key: &SigningKey,
+    ) -> Result<Self, BpciError> {
+        Self::new_with_algorithm(
+            src_cluster_id,
+            dst_cluster_id,
+            svc_id_hash,
+            nonce,
+            poh_tick,
+            payload,
+            aead_key,
+            signing_key,
+            AeadAlgorithm::default(),
+        )
+    }
+
+    /// Create new BPCI frame with authentication, encrypted with `aead_algorithm`
+    pub fn new_with_algorithm(
+        src_cluster_id: ClusterId,
+        dst_cluster_id: ClusterId,
+        svc_id_hash: ServiceIdHash,
         nonce: u64,
-        poh_tick: [u8; 32],
+        poh_tick: PohTick,
         payload: &[u8],
-        aead_key: &[u8; 32],
-        signing_key: &[u8; 32], // Ed25519 private key
+        aead_key: &AeadKey,
+        signing_key: &SigningKey,
+        aead_algorithm: AeadAlgorithm,
     ) -> Result<Self, BpciError> {
+        // AEAD alone is not key-committing; bind the frame to this key
+        let key_commitment = Self::commit_key(aead_key, &src_cluster_id, &svc_id_hash, nonce);
+
         // Create header for signing
         let header = BpciFrameHeader {
             version: 1,
+            aead_alg: aead_algorithm.id(),
             src_cluster_id,
             dst_cluster_id,
             svc_id_hash,
             nonce,
             poh_tick,
+            key_commitment,
             payload_len: payload.len(),
         };
 
@@ -236,57 +566,122 @@ impl BpciFrame {
         // Create domain-separated hash for signing
         let header_hash = domain_hash(BPCI_HEADER_HASH, &header_bytes);
 
-        // Sign header hash with Ed25519 (placeholder - would use actual Ed25519 library)
+        // Sign header hash with Ed25519
         let sig_src = Self::sign_ed25519(signing_key, &header_hash)?;
 
         // Encrypt payload with AEAD (placeholder - would use actual AEAD)
-        let (payload_ct, aead_tag) = Self::aead_encrypt(aead_key, &header_bytes, payload)?;
+        let (payload_ct, aead_tag) = Self::aead_encrypt(aead_algorithm, aead_key.as_bytes(), &header_bytes, payload)?;
 
         Ok(BpciFrame {
             version: 1,
+            aead_alg: aead_algorithm.id(),
             src_cluster_id,
             dst_cluster_id,
             svc_id_hash,
             nonce,
             poh_tick,
+            key_commitment,
             payload_ct,
             aead_tag,
             sig_src: sig_src.to_vec(),
         })
     }
 
-    /// Verify frame authentication
+    /// Header fields the signature covers
+    pub fn header(&self) -> BpciFrameHeader {
+        BpciFrameHeader {
+            version: self.version,
+            aead_alg: self.aead_alg,
+            src_cluster_id: self.src_cluster_id,
+            dst_cluster_id: self.dst_cluster_id,
+            svc_id_hash: self.svc_id_hash,
+            nonce: self.nonce,
+            poh_tick: self.poh_tick,
+            key_commitment: self.key_commitment,
+            payload_len: self.payload_ct.len(),
+        }
+    }
+
+    pub fn to_canonical_cbor(&self) -> Result<Vec<u8>, BpciError> {
+        Ok(CanonicalCbor::encode(self)?)
+    }
+
+    /// Decode a frame, rejecting any encoding other than the canonical one
+    pub fn from_canonical_cbor(bytes: &[u8]) -> Result<Self, BpciError> {
+        decode_canonical(bytes, "BpciFrame")
+    }
+
+    /// Wrap the frame in a `Data` message for sending over a transport
+    pub fn to_message(&self) -> Result<TransportMessage, BpciError> {
+        Ok(TransportMessage::Data { payload: bincode::serialize(self)? })
+    }
+
+    /// Wrap the frame in a `ReliableFrame` message, asking the receiver for a `FrameAck`
+    pub fn to_reliable_message(&self) -> Result<TransportMessage, BpciError> {
+        Ok(TransportMessage::ReliableFrame(bincode::serialize(self)?))
+    }
+
+    /// Unwrap a frame sent with `to_message` or `to_reliable_message`; `None`
+    /// for any other message
+    pub fn from_message(message: &TransportMessage) -> Option<BpciFrame> {
+        match message {
+            TransportMessage::Data { payload }
+            | TransportMessage::ReliableFrame(payload) => bincode::deserialize(payload).ok(),
+            _ => None,
+        }
+    }
+
+    /// Verify frame authentication, accepting any AEAD algorithm
     pub fn verify(
         &self,
-        public_key: &[u8; 32], // Ed25519 public key
-        aead_key: &[u8; 32],
+        public_key: &VerifyingKey,
+        aead_key: &AeadKey,
+        nonce_tracker: &mut NonceTracker,
+    ) -> Result<(Vec<u8>, AuthenticationResult), BpciError> {
+        self.verify_with_algorithms(public_key, aead_key, nonce_tracker, AeadAlgorithm::ALL)
+    }
+
+    /// Verify frame authentication, rejecting frames encrypted with an
+    /// algorithm outside `permitted`.
+    ///
+    /// Secret-dependent comparisons (signature, key commitment, AEAD tag and the
+    /// retransmit hash) are constant-time, so how long a rejection takes reveals
+    /// nothing about which bytes were wrong. Only public values such as the
+    /// signature length and the algorithm id are checked with ordinary branches.
+    pub fn verify_with_algorithms(
+        &self,
+        public_key: &VerifyingKey,
+        aead_key: &AeadKey,
         nonce_tracker: &mut NonceTracker,
+        permitted: &[AeadAlgorithm],
     ) -> Result<(Vec<u8>, AuthenticationResult), BpciError> {
         let mut result = AuthenticationResult {
             valid: false,
             error: None,
             nonce_valid: false,
             signature_valid: false,
+            duplicate: false,
         };
 
-        // Check nonce for replay protection
+        let aead_algorithm = AeadAlgorithm::from_id(self.aead_alg)?;
+        if !permitted.contains(&aead_algorithm) {
+            result.error = Some(format!("AEAD algorithm {:?} is not permitted", aead_algorithm));
+            return Ok((Vec::new(), result));
+        }
+
+        // Check nonce for replay protection; an honest retransmit of the last
+        // accepted frame reuses its nonce and is flagged rather than rejected
         let nonce_key = (self.src_cluster_id, self.svc_id_hash);
-        result.nonce_valid = nonce_tracker.check_nonce(nonce_key, self.nonce)?;
+        let frame_hash = self.hash()?;
+        result.duplicate = nonce_tracker.is_retransmit(&nonce_key, self.nonce, &frame_hash);
+        result.nonce_valid = result.duplicate || nonce_tracker.check_nonce(nonce_key, self.nonce)?;
         if !result.nonce_valid {
             result.error = Some("Nonce replay detected".to_string());
             return Ok((Vec::new(), result));
         }
 
         // Reconstruct header for verification
-        let header = BpciFrameHeader {
-            version: self.version,
-            src_cluster_id: self.src_cluster_id,
-            dst_cluster_id: self.dst_cluster_id,
-            svc_id_hash: self.svc_id_hash,
-            nonce: self.nonce,
-            poh_tick: self.poh_tick,
-            payload_len: self.payload_ct.len(),
-        };
+        let header = self.header();
 
         // Encode header canonically
         let header_bytes = CanonicalCbor::encode(&header)
@@ -308,16 +703,36 @@ impl BpciFrame {
             return Ok((Vec::new(), result));
         }
 
+        // A ciphertext crafted to open under several keys still commits to only one
+        if !ct_eq(&Self::commit_key(aead_key, &self.src_cluster_id, &self.svc_id_hash, self.nonce), &self.key_commitment) {
+            result.error = Some("Key commitment mismatch".to_string());
+            return Ok((Vec::new(), result));
+        }
+
         // Decrypt payload with AEAD
-        let payload = Self::aead_decrypt(aead_key, &header_bytes, &self.payload_ct, &self.aead_tag)?;
+        let payload = Self::aead_decrypt(aead_algorithm, aead_key.as_bytes(), &header_bytes, &self.payload_ct, &self.aead_tag)?;
 
         // Update nonce tracker
-        nonce_tracker.update_nonce(nonce_key, self.nonce);
+        if !result.duplicate {
+            nonce_tracker.update_nonce(nonce_key, self.nonce);
+            nonce_tracker.record_frame(nonce_key, self.nonce, frame_hash);
+        }
 
         result.valid = true;
         Ok((payload, result))
     }
 
+    /// Whether `sig_src` is `public_key`'s signature over the header. Checks
+    /// nothing else; `verify` also enforces nonces and decrypts the payload.
+    pub fn verify_signature(&self, public_key: &VerifyingKey) -> Result<bool, BpciError> {
+        let signature: [u8; 64] = match self.sig_src.as_slice().try_into() {
+            Ok(signature) => signature,
+            Err(_) => return Ok(false),
+        };
+        let header_hash = domain_hash(BPCI_HEADER_HASH, &self.header().to_canonical_cbor()?);
+        Self::verify_ed25519(public_key, &header_hash, &signature)
+    }
+
     /// Get frame hash for integrity verification
     pub fn hash(&self) -> Result<[u8; 32], BpciError> {
         let encoded = CanonicalCbor::encode(self)
@@ -325,37 +740,66 @@ impl BpciFrame {
         Ok(domain_hash(BPCI_HEADER_HASH, &encoded))
     }
 
-    // Placeholder Ed25519 signing (would use actual crypto library)
-    fn sign_ed25519(private_key: &[u8; 32], message: &[u8; 32]) -> Result<[u8; 64], BpciError> {
-        // Placeholder implementation - would use ed25519-dalek or similar
-        let mut signature = [0u8; 64];
-        signature[..32].copy_from_slice(private_key);
-        signature[32..].copy_from_slice(message);
-        Ok(signature)
+    /// Id shared by every log line about this frame: the first 8 bytes of its hash, in hex
+    pub fn correlation_id(&self) -> String {
+        self.hash()
+            .map(|hash| hex::encode(&hash[..8]))
+            .unwrap_or_else(|_| "unhashable".to_string())
+    }
+
+    /// Span covering this frame's send, verify and routing, keyed by `correlation_id`
+    pub fn span(&self) -> Span {
+        info_span!("bpci_frame", correlation_id = %self.correlation_id(), nonce = self.nonce)
+    }
+
+    // Ed25519 signature over a 32-byte digest
+    fn sign_ed25519(signing_key: &SigningKey, message: &[u8; 32]) -> Result<[u8; 64], BpciError> {
+        use ed25519_dalek::Signer;
+        Ok(signing_key.to_dalek().sign(message).to_bytes())
     }
 
-    // Placeholder Ed25519 verification
-    fn verify_ed25519(public_key: &[u8; 32], message: &[u8; 32], signature: &[u8; 64]) -> Result<bool, BpciError> {
-        // Placeholder implementation - would use ed25519-dalek or similar
-        Ok(signature[..32] == *public_key && signature[32..] == *message)
+    // Strict Ed25519 verification; a key that is not a valid curve point verifies nothing
+    fn verify_ed25519(public_key: &VerifyingKey, message: &[u8; 32], signature: &[u8; 64]) -> Result<bool, BpciError> {
+        let public_key = match ed25519_dalek::VerifyingKey::from_bytes(public_key.as_bytes()) {
+            Ok(public_key) => public_key,
+            Err(_) => return Ok(false),
+        };
+        let signature = ed25519_dalek::Signature::from_bytes(signature);
+        Ok(public_key.verify_strict(message, &signature).is_ok())
+    }
+
+    // Hash of the AEAD key and the frame's nonce scope; reveals nothing about the key
+    fn commit_key(aead_key: &AeadKey, src_cluster_id: &ClusterId, svc_id_hash: &ServiceIdHash, nonce: u64) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(88);
+        bytes.extend_from_slice(aead_key.as_bytes());
+        bytes.extend_from_slice(src_cluster_id.as_bytes());
+        bytes.extend_from_slice(svc_id_hash.as_bytes());
+        bytes.extend_from_slice(&nonce.to_be_bytes());
+        domain_hash(BPCI_KEY_COMMITMENT, &bytes)
+    }
+
+    // Placeholder tag, bound to the algorithm so a frame never opens under the other cipher
+    fn aead_tag(algorithm: AeadAlgorithm, key: &[u8; 32]) -> [u8; 16] {
+        let mut tag = [0u8; 16];
+        tag.copy_from_slice(&key[..16]);
+        tag[0] ^= algorithm.id();
+        tag
     }
 
     // Placeholder AEAD encryption
-    fn aead_encrypt(key: &[u8; 32], ad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, [u8; 16]), BpciError> {
-        // Placeholder implementation - would use XChaCha20-Poly1305
+    fn aead_encrypt(algorithm: AeadAlgorithm, key: &[u8; 32], ad: &[u8], plaintext: &[u8]) -> Result<(Vec<u8>, [u8; 16]), BpciError> {
+        // Placeholder implementation - would use XChaCha20-Poly1305 or AES-256-GCM
         let mut ciphertext = plaintext.to_vec();
         for (i, byte) in ciphertext.iter_mut().enumerate() {
             *byte ^= key[i % 32] ^ ad[i % ad.len()];
         }
-        let mut tag = [0u8; 16];
-        tag[..16].copy_from_slice(&key[..16]);
-        Ok((ciphertext, tag))
+        Ok((ciphertext, Self::aead_tag(algorithm, key)))
     }
 
     // Placeholder AEAD decryption
-    fn aead_decrypt(key: &[u8; 32], ad: &[u8], ciphertext: &[u8], tag: &[u8; 16]) -> Result<Vec<u8>, BpciError> {
+    fn aead_decrypt(algorithm: AeadAlgorithm, key: &[u8; 32], ad: &[u8], ciphertext: &[u8], tag: &[u8; 16]) -> Result<Vec<u8>, BpciError> {
         // Verify tag (placeholder)
-        if tag != &key[..16] {
+        if !ct_eq(tag, &Self::aead_tag(algorithm, key)) {
             return Err(BpciError::AeadError("Invalid AEAD tag".to_string()));
         }
 
@@ -400,6 +844,12 @@ impl X25519KeyPair {
     }
 }
 
+impl Drop for X25519KeyPair {
+    fn drop(&mut self) {
+        self.private_key_bytes.zeroize();
+    }
+}
+
 impl ServiceKeyRegistry {
     /// Create new service key registry
     pub fn new() -> Self {
@@ -450,20 +900,19 @@ impl E2EKeyManager {
     }
 
     /// Register a service's public key
-    pub async fn register_service_key(&self, svc_id_hash: [u8; 32], public_key_bytes: [u8; 32]) -> Result<(), BpciError> {
-        let public_key = X25519PublicKey::from(public_key_bytes);
+    pub async fn register_service_key(&self, svc_id_hash: ServiceIdHash, public_key: X25519PublicKey) -> Result<(), BpciError> {
         let mut registry = self.registry.write().await;
-        registry.register_service_key(svc_id_hash, public_key);
+        registry.register_service_key(svc_id_hash.into_bytes(), public_key);
         info!("Registered service key for service {:?}", hex::encode(svc_id_hash));
         Ok(())
     }
 
     /// Register our own service key pair
-    pub async fn register_our_service_key(&self, svc_id_hash: [u8; 32]) -> Result<[u8; 32], BpciError> {
+    pub async fn register_our_service_key(&self, svc_id_hash: ServiceIdHash) -> Result<[u8; 32], BpciError> {
         let key_pair = X25519KeyPair::generate();
         let public_key_bytes = key_pair.public_key_bytes();
         let mut registry = self.registry.write().await;
-        registry.register_our_service_key(svc_id_hash, key_pair);
+        registry.register_our_service_key(svc_id_hash.into_bytes(), key_pair);
         info!("Generated and registered our service key for service {:?}", hex::encode(svc_id_hash));
         Ok(public_key_bytes)
     }
@@ -471,8 +920,9 @@ impl E2EKeyManager {
     /// Derive AEAD key for sending (using ephemeral key)
     pub async fn derive_sender_key(
         &self,
-        svc_id_hash: [u8; 32],
+        svc_id_hash: ServiceIdHash,
     ) -> Result<KeyDerivationResult, BpciError> {
+        let svc_id_hash = svc_id_hash.into_bytes();
         let registry = self.registry.read().await;
         
         // Get service public key
@@ -487,16 +937,17 @@ impl E2EKeyManager {
         let shared_secret = ephemeral_secret.diffie_hellman(service_public_key);
 
         // Derive AEAD key using HKDF
-        let aead_key = Self::derive_aead_key(shared_secret.as_bytes(), &svc_id_hash)?;
+        let ephemeral_pk_bytes = ephemeral_public_key.to_bytes();
+        let salt = Self::session_salt(&ephemeral_pk_bytes, &service_public_key.to_bytes());
+        let aead_key = Self::derive_aead_key(shared_secret.as_bytes(), &salt, &svc_id_hash, KeyDirection::Send)?;
 
         // Cache the derived key
-        let ephemeral_pk_bytes = ephemeral_public_key.to_bytes();
         let cache_key = ([0u8; 16], svc_id_hash, ephemeral_pk_bytes); // src_cluster_id placeholder
         let mut session_keys = self.session_keys.write().await;
-        session_keys.insert(cache_key, aead_key);
+        session_keys.insert(cache_key, aead_key.clone());
 
         Ok(KeyDerivationResult {
-            aead_key,
+            aead_key: *aead_key,
             ephemeral_public_key,
             service_id_hash: svc_id_hash,
         })
@@ -505,16 +956,18 @@ impl E2EKeyManager {
     /// Derive AEAD key for receiving (using our service key)
     pub async fn derive_receiver_key(
         &self,
-        svc_id_hash: [u8; 32],
+        svc_id_hash: ServiceIdHash,
         ephemeral_public_key_bytes: [u8; 32],
-        src_cluster_id: [u8; 16],
+        src_cluster_id: ClusterId,
     ) -> Result<[u8; 32], BpciError> {
+        let svc_id_hash = svc_id_hash.into_bytes();
+
         // Check cache first
-        let cache_key = (src_cluster_id, svc_id_hash, ephemeral_public_key_bytes);
+        let cache_key = (src_cluster_id.into_bytes(), svc_id_hash, ephemeral_public_key_bytes);
         {
             let session_keys = self.session_keys.read().await;
-            if let Some(&cached_key) = session_keys.get(&cache_key) {
-                return Ok(cached_key);
+            if let Some(cached_key) = session_keys.get(&cache_key) {
+                return Ok(**cached_key);
             }
         }
 
@@ -528,30 +981,47 @@ impl E2EKeyManager {
         let ephemeral_public_key = X25519PublicKey::from(ephemeral_public_key_bytes);
 
         // Perform X25519 key exchange using our static service key
+        // StaticSecret zeroes its own copy on drop
         let our_static_secret = StaticSecret::from(our_key_pair.private_key_bytes);
         let shared_secret = our_static_secret.diffie_hellman(&ephemeral_public_key);
 
         // Derive AEAD key using HKDF
-        let aead_key = Self::derive_aead_key(shared_secret.as_bytes(), &svc_id_hash)?;
+        let salt = Self::session_salt(&ephemeral_public_key_bytes, &our_key_pair.public_key_bytes());
+        let aead_key = Self::derive_aead_key(shared_secret.as_bytes(), &salt, &svc_id_hash, KeyDirection::Send)?;
 
         // Cache the derived key
         let mut session_keys = self.session_keys.write().await;
-        session_keys.insert(cache_key, aead_key);
+        session_keys.insert(cache_key, aead_key.clone());
 
-        Ok(aead_key)
+        Ok(*aead_key)
+    }
+
+    /// HKDF salt for one session: both sides know the ephemeral and the
+    /// service static public key, so they compute the same salt
+    fn session_salt(ephemeral_public_key: &[u8; 32], static_public_key: &[u8; 32]) -> [u8; 32] {
+        let mut keys = [0u8; 64];
+        keys[..32].copy_from_slice(ephemeral_public_key);
+        keys[32..].copy_from_slice(static_public_key);
+        domain_hash(BPCI_E2E_SALT, &keys)
     }
 
-    /// Derive AEAD key using HKDF-SHA256
-    fn derive_aead_key(shared_secret: &[u8], svc_id_hash: &[u8; 32]) -> Result<[u8; 32], BpciError> {
-        // Context string: "BPCI-AEAD" || svc_id_hash
-        let mut context = Vec::with_capacity(9 + 32);
-        context.extend_from_slice(b"BPCI-AEAD");
+    /// Derive AEAD key using HKDF-SHA256; the returned buffer is zeroed on drop
+    fn derive_aead_key(
+        shared_secret: &[u8],
+        salt: &[u8; 32],
+        svc_id_hash: &[u8; 32],
+        direction: KeyDirection,
+    ) -> Result<Zeroizing<[u8; 32]>, BpciError> {
+        // Context string: direction label || svc_id_hash
+        let label = direction.info_label();
+        let mut context = Vec::with_capacity(label.len() + 32);
+        context.extend_from_slice(label);
         context.extend_from_slice(svc_id_hash);
 
         // HKDF-SHA256 key derivation
-        let hk = Hkdf::<Sha256>::new(None, shared_secret);
-        let mut aead_key = [0u8; 32];
-        hk.expand(&context, &mut aead_key)
+        let hk = Hkdf::<Sha256>::new(Some(salt), shared_secret);
+        let mut aead_key = Zeroizing::new([0u8; 32]);
+        hk.expand(&context, aead_key.as_mut_slice())
             .map_err(|e| BpciError::KeyDerivationError(format!("HKDF expansion failed: {}", e)))?;
 
         Ok(aead_key)
@@ -584,12 +1054,13 @@ impl NonceTracker {
     pub fn new(tolerance_window: u64) -> Self {
         Self {
             nonces: HashMap::new(),
+            last_frames: HashMap::new(),
             tolerance_window,
         }
     }
 
     /// Check if nonce is valid (not a replay)
-    pub fn check_nonce(&self, key: ([u8; 16], [u8; 32]), nonce: u64) -> Result<bool, BpciError> {
+    pub fn check_nonce(&self, key: NonceKey, nonce: u64) -> Result<bool, BpciError> {
         if let Some(&last_nonce) = self.nonces.get(&key) {
             // Reject if nonce is less than or equal to last seen nonce (strict replay protection)
             if nonce <= last_nonce {
@@ -599,21 +1070,60 @@ impl NonceTracker {
         Ok(true)
     }
 
+    /// Reserve and return the next outbound nonce for `(src, svc)`. The read
+    /// and increment happen in one call so no two senders get the same nonce.
+    pub fn next_nonce(&mut self, src_cluster_id: ClusterId, svc_id_hash: ServiceIdHash) -> u64 {
+        let nonce = self.nonces.entry((src_cluster_id, svc_id_hash)).or_insert(0);
+        *nonce += 1;
+        *nonce
+    }
+
     /// Update nonce for given key
-    pub fn update_nonce(&mut self, key: ([u8; 16], [u8; 32]), nonce: u64) {
+    pub fn update_nonce(&mut self, key: NonceKey, nonce: u64) {
         self.nonces.insert(key, nonce);
     }
 
+    /// Remember the hash of the frame accepted at `nonce`
+    pub fn record_frame(&mut self, key: NonceKey, nonce: u64, frame_hash: [u8; 32]) {
+        self.last_frames.insert(key, (nonce, frame_hash));
+    }
+
+    /// Whether a frame is an exact copy of the last one accepted for `key`
+    pub fn is_retransmit(&self, key: &NonceKey, nonce: u64, frame_hash: &[u8; 32]) -> bool {
+        self.last_frames.get(key)
+            .is_some_and(|(last_nonce, last_hash)| *last_nonce == nonce && ct_eq(last_hash, frame_hash))
+    }
+
     /// Get current nonce for key
-    pub fn get_nonce(&self, key: &([u8; 16], [u8; 32])) -> Option<u64> {
+    pub fn get_nonce(&self, key: &NonceKey) -> Option<u64> {
         self.nonces.get(key).copied()
     }
 
+    /// Fold in another tracker's state, e.g. a failed primary's after failover.
+    /// Each key keeps the higher of the two nonces, so anything either side
+    /// would reject as a replay is still rejected. A remembered frame survives
+    /// only if it was accepted at that higher nonce; one from a lower nonce
+    /// would otherwise let an old frame pass as a retransmit.
+    pub fn merge(&mut self, other: &NonceTracker) {
+        for (key, &nonce) in &other.nonces {
+            let merged = self.nonces.entry(*key).or_insert(nonce);
+            *merged = (*merged).max(nonce);
+        }
+        for (key, &(nonce, frame_hash)) in &other.last_frames {
+            if !self.last_frames.get(key).is_some_and(|(ours, _)| *ours >= nonce) {
+                self.last_frames.insert(*key, (nonce, frame_hash));
+            }
+        }
+        let nonces = &self.nonces;
+        self.last_frames.retain(|key, (nonce, _)| nonces.get(key) == Some(&*nonce));
+    }
+
     /// Clear old nonces (cleanup)
     pub fn cleanup_old_nonces(&mut self, current_time: u64, max_age: u64) {
         // Placeholder - would implement proper cleanup logic
         if current_time > max_age {
             self.nonces.clear();
+            self.last_frames.clear();
         }
     }
 }
@@ -626,6 +1136,89 @@ pub struct PeerInfo {
     pub capabilities: Vec<String>,
     pub last_seen: u64,
     pub connection_quality: f64,
+    /// Cluster id this peer signs frames as (`BpciFrame::src_cluster_id`)
+    #[serde(default)]
+    pub cluster_id: Option<ClusterId>,
+    /// Key this peer signs frames and acks with, learned when it was admitted
+    #[serde(default)]
+    pub verifying_key: Option<VerifyingKey>,
+}
+
+/// Capabilities a frame's sender must hold, per destination service
+#[derive(Debug, Clone, Default)]
+pub struct AccessPolicy {
+    required: HashMap<ServiceIdHash, Vec<String>>,
+}
+
+impl AccessPolicy {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only accept frames for `svc_id_hash` from peers holding every capability in `capabilities`
+    pub fn require(mut self, svc_id_hash: ServiceIdHash, capabilities: &[&str]) -> Self {
+        self.required.insert(svc_id_hash, capabilities.iter().map(|c| c.to_string()).collect());
+        self
+    }
+
+    /// Capabilities required for `svc_id_hash`; empty if the service is open
+    pub fn required_capabilities(&self, svc_id_hash: &ServiceIdHash) -> &[String] {
+        self.required.get(svc_id_hash).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// Whether `sender` may send to `svc_id_hash`. An unregistered sender only
+    /// passes for services with no requirements.
+    pub fn allows(&self, svc_id_hash: &ServiceIdHash, sender: Option<&PeerInfo>) -> bool {
+        let required = self.required_capabilities(svc_id_hash);
+        match sender {
+            Some(peer) => required.iter().all(|capability| peer.capabilities.contains(capability)),
+            None => required.is_empty(),
+        }
+    }
+}
+
+/// Decides whether the PoH tick a frame references is one this node accepts
+pub trait PohVerifier: Send + Sync + std::fmt::Debug {
+    /// False for ticks that are unknown or ahead of the local PoH sequence
+    fn is_valid_tick(&self, tick: &PohTick) -> bool;
+}
+
+/// Accepts every tick; the default until a PoH source is configured
+#[derive(Debug, Clone, Copy, Default)]
+pub struct AcceptAnyPohTick;
+
+impl PohVerifier for AcceptAnyPohTick {
+    fn is_valid_tick(&self, _tick: &PohTick) -> bool {
+        true
+    }
+}
+
+/// Result of sending a broadcast to one peer
+#[derive(Debug, Clone)]
+pub struct BroadcastOutcome {
+    pub peer_id: String,
+    pub connection_quality: f64,
+    /// Send error, if delivery to this peer failed
+    pub error: Option<String>,
+}
+
+/// Per-peer outcomes of a broadcast, in the order peers were attempted
+#[derive(Debug, Clone, Default)]
+pub struct BroadcastReport {
+    pub outcomes: Vec<BroadcastOutcome>,
+}
+
+impl BroadcastReport {
+    pub fn delivered(&self) -> usize {
+        self.outcomes.iter().filter(|outcome| outcome.error.is_none()).count()
+    }
+
+    pub fn failed(&self) -> Vec<&str> {
+        self.outcomes.iter()
+            .filter(|outcome| outcome.error.is_some())
+            .map(|outcome| outcome.peer_id.as_str())
+            .collect()
+    }
 }
 
 /// Connection statistics
@@ -653,6 +1246,70 @@ impl Default for ConnectionStats {
     }
 }
 
+/// Default time allowed for flushing queued messages on shutdown
+pub const DEFAULT_DRAIN_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Default silence after which a peer is considered gone
+pub const DEFAULT_PEER_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Backoff schedule for re-dialing dropped persistent peers
+#[derive(Debug, Clone)]
+pub struct ReconnectPolicy {
+    pub initial_delay: Duration,
+    pub max_delay: Duration,
+    pub max_attempts: u32,
+}
+
+impl Default for ReconnectPolicy {
+    fn default() -> Self {
+        Self {
+            initial_delay: Duration::from_millis(500),
+            max_delay: Duration::from_secs(30),
+            max_attempts: 8,
+        }
+    }
+}
+
+impl ReconnectPolicy {
+    /// Delay before the 1-based `attempt`: doubles per attempt up to `max_delay`, then
+    /// scaled by a random factor in [0.5, 1.0] so peers that dropped together spread out
+    pub fn delay_for(&self, attempt: u32) -> Duration {
+        let backoff = self.initial_delay
+            .saturating_mul(1u32 << attempt.saturating_sub(1).min(31))
+            .min(self.max_delay);
+        backoff.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+    }
+}
+
+/// Default number of undeliverable messages kept for inspection or retry
+pub const DEFAULT_DEAD_LETTER_CAPACITY: usize = 1024;
+
+/// A message that could not be delivered, kept until drained or redelivered
+#[derive(Debug, Clone)]
+pub struct DeadLetter {
+    pub peer_id: String,
+    pub message: TransportMessage,
+    /// Error from the most recent delivery attempt
+    pub reason: String,
+    /// Delivery attempts made so far, including the original send
+    pub attempts: u32,
+    /// Unix time of the most recent failure
+    pub failed_at: u64,
+}
+
+/// Redeliver a peer's dead letters automatically when it is added again
+#[derive(Debug, Clone)]
+pub struct DeadLetterRetryPolicy {
+    /// Attempts after which a message is discarded instead of re-queued
+    pub max_attempts: u32,
+}
+
+impl Default for DeadLetterRetryPolicy {
+    fn default() -> Self {
+        Self { max_attempts: 5 }
+    }
+}
+
 /// Main BPCI Transport Layer
 #[derive(Debug)]
 pub struct BpciTransport {
@@ -660,48 +1317,342 @@ pub struct BpciTransport {
     peers: Arc<RwLock<HashMap<String, PeerInfo>>>,
     stats: Arc<RwLock<HashMap<String, ConnectionStats>>>,
     is_running: Arc<RwLock<bool>>,
-    message_tx: Option<mpsc::UnboundedSender<TransportMessage>>,
-    message_rx: Option<mpsc::UnboundedReceiver<TransportMessage>>,
+    /// Set once shutdown begins; new sends are refused while draining
+    is_draining: Arc<RwLock<bool>>,
+    drain_timeout: Duration,
+    peer_timeout: Duration,
+    message_queues: Arc<Mutex<PriorityQueues>>,
     nonce_tracker: Arc<RwLock<NonceTracker>>,
     /// E2E Key Manager for Stage 18
     key_manager: Arc<E2EKeyManager>,
+    access_policy: AccessPolicy,
+    /// Checks the PoH tick of every verified frame
+    poh_verifier: Arc<dyn PohVerifier>,
+    /// Network backend messages are sent over
+    backend: Arc<dyn Transport>,
+    /// Peers dialed through `connect_peer`; only these are sent over the backend
+    connected: Arc<RwLock<HashSet<String>>>,
+    inbound: Mutex<Option<InboundStream>>,
+    /// Peers to re-dial when their connection drops, by id
+    persistent: Arc<RwLock<HashMap<String, SocketAddr>>>,
+    reconnect_policy: ReconnectPolicy,
+    /// Peers with a reconnect task running; at most one task per peer
+    reconnecting: Arc<Mutex<HashSet<String>>>,
+    /// Failed sends, oldest first; bounded by `dead_letter_capacity`
+    dead_letters: Arc<Mutex<VecDeque<DeadLetter>>>,
+    dead_letter_capacity: usize,
+    dead_letter_retry: Option<DeadLetterRetryPolicy>,
+    /// Frames sent with `send_frame_reliable` that are still awaiting an ack
+    acks: Arc<Mutex<AckTracker>>,
+    /// Key acks for inbound reliable frames are signed with
+    signing_key: Option<SigningKey>,
+    /// Heartbeat and reaping task spawned by `start`
+    liveness_task: Option<tokio::task::JoinHandle<()>>,
+    /// Retransmit timer spawned by `start`
+    retransmit_task: Option<tokio::task::JoinHandle<()>>,
+    /// Inbound fragments of frames not yet complete
+    reassembler: Arc<Mutex<FrameReassembler>>,
 }
 
 impl BpciTransport {
     /// Create new BPCI transport instance
     pub fn new(config: BpciConfig) -> Result<Self> {
-        let (message_tx, message_rx) = mpsc::unbounded_channel();
-        
+        let backend = Arc::new(TcpTransport::new(config.max_message_size));
         Ok(Self {
             config,
             peers: Arc::new(RwLock::new(HashMap::new())),
             stats: Arc::new(RwLock::new(HashMap::new())),
             is_running: Arc::new(RwLock::new(false)),
-            message_tx: Some(message_tx),
-            message_rx: Some(message_rx),
+            is_draining: Arc::new(RwLock::new(false)),
+            drain_timeout: DEFAULT_DRAIN_TIMEOUT,
+            peer_timeout: DEFAULT_PEER_TIMEOUT,
+            message_queues: Arc::new(Mutex::new(PriorityQueues::default())),
             nonce_tracker: Arc::new(RwLock::new(NonceTracker::new(100))), // 100 nonce tolerance
             key_manager: Arc::new(E2EKeyManager::new()),
+            access_policy: AccessPolicy::default(),
+            poh_verifier: Arc::new(AcceptAnyPohTick),
+            backend,
+            connected: Arc::new(RwLock::new(HashSet::new())),
+            inbound: Mutex::new(None),
+            persistent: Arc::new(RwLock::new(HashMap::new())),
+            reconnect_policy: ReconnectPolicy::default(),
+            reconnecting: Arc::new(Mutex::new(HashSet::new())),
+            dead_letters: Arc::new(Mutex::new(VecDeque::new())),
+            dead_letter_capacity: DEFAULT_DEAD_LETTER_CAPACITY,
+            dead_letter_retry: None,
+            acks: Arc::new(Mutex::new(AckTracker::default())),
+            signing_key: None,
+            liveness_task: None,
+            retransmit_task: None,
+            reassembler: Arc::new(Mutex::new(FrameReassembler::new())),
         })
     }
+
+    /// Replace the TCP backend, e.g. with an `InMemoryTransport` in tests
+    pub fn with_backend(mut self, backend: Box<dyn Transport>) -> Self {
+        self.backend = Arc::from(backend);
+        self
+    }
+
+    /// Set the backoff used when re-dialing dropped persistent peers
+    pub fn with_reconnect_policy(mut self, reconnect_policy: ReconnectPolicy) -> Self {
+        self.reconnect_policy = reconnect_policy;
+        self
+    }
+
+    /// Set how many failed sends are kept; the oldest is discarded when full, and
+    /// zero disables the dead-letter queue
+    pub fn with_dead_letter_capacity(mut self, dead_letter_capacity: usize) -> Self {
+        self.dead_letter_capacity = dead_letter_capacity;
+        self
+    }
+
+    /// Redeliver dead letters whenever their peer is added or reconnected
+    pub fn with_dead_letter_retry(mut self, dead_letter_retry: DeadLetterRetryPolicy) -> Self {
+        self.dead_letter_retry = Some(dead_letter_retry);
+        self
+    }
+
+    /// Set the ack timeout and retry cap for frames sent with `send_frame_reliable`
+    pub fn with_retransmit_policy(mut self, retransmit_policy: RetransmitPolicy) -> Self {
+        self.acks = Arc::new(Mutex::new(AckTracker::new(retransmit_policy)));
+        self
+    }
+
+    /// Sign acks for inbound reliable frames with `signing_key`; without one,
+    /// reliable frames are received but never acknowledged
+    pub fn with_signing_key(mut self, signing_key: SigningKey) -> Self {
+        self.signing_key = Some(signing_key);
+        self
+    }
+
+    /// Bound how many incomplete fragmented frames, and how many of their bytes,
+    /// are buffered, and for how long
+    pub fn with_reassembly_limits(mut self, limits: ReassemblyLimits) -> Self {
+        self.reassembler = Arc::new(Mutex::new(FrameReassembler::new().with_limits(limits)));
+        self
+    }
+
+    /// Restrict which peers may send frames to each service
+    pub fn with_access_policy(mut self, access_policy: AccessPolicy) -> Self {
+        self.access_policy = access_policy;
+        self
+    }
+
+    /// Reject verified frames whose PoH tick `poh_verifier` does not accept
+    pub fn with_poh_verifier(mut self, poh_verifier: impl PohVerifier + 'static) -> Self {
+        self.poh_verifier = Arc::new(poh_verifier);
+        self
+    }
+
+    /// Decode an inbound message, enforcing `BpciConfig::max_message_size`
+    pub fn decode_message(&self, data: &[u8]) -> Result<TransportMessage, BpciError> {
+        TransportMessage::decode_with_limit(data, self.config.max_message_size)
+    }
+
+    /// Set how long `shutdown` may spend flushing queued messages
+    pub fn with_drain_timeout(mut self, drain_timeout: Duration) -> Self {
+        self.drain_timeout = drain_timeout;
+        self
+    }
+
+    /// Set how long a peer may stay silent before it is removed
+    pub fn with_peer_timeout(mut self, peer_timeout: Duration) -> Self {
+        self.peer_timeout = peer_timeout;
+        self
+    }
     
-    /// Start the transport layer
+    /// Start the transport layer. Starting a running transport does nothing, so
+    /// there is only ever one liveness task and one retransmit timer.
     pub async fn start(&mut self) -> Result<()> {
-        info!("Starting BPCI transport on {}", self.config.bind_address);
-        
-        // Mark as running
-        *self.is_running.write().await = true;
+        {
+            let mut is_running = self.is_running.write().await;
+            if *is_running {
+                debug!("BPCI transport already running");
+                return Ok(());
+            }
+            info!("Starting BPCI transport on {}", self.config.bind_address);
+
+            // Mark as running
+            *self.is_draining.write().await = false;
+            *is_running = true;
+        }
+
+        // A task left over from before a shutdown may not have noticed it yet
+        for task in [self.liveness_task.take(), self.retransmit_task.take()].into_iter().flatten() {
+            task.abort();
+        }
+        self.liveness_task = Some(self.spawn_liveness_task());
+        self.retransmit_task = Some(self.spawn_retransmit_task().await);
         
         info!("BPCI transport started successfully");
         Ok(())
     }
     
-    /// Send message to specific peer
+    /// Accept inbound connections on `bind_address`; returns the address bound
+    pub async fn listen(&self) -> Result<SocketAddr, BpciError> {
+        let local_addr = self.backend.listen(self.config.bind_address).await?;
+        info!("BPCI transport listening on {}", local_addr);
+        Ok(local_addr)
+    }
+
+    /// Dial a peer and register it; later sends to it go over the backend
+    pub async fn connect_peer(&self, peer: PeerInfo) -> Result<()> {
+        self.backend.dial(peer.address).await?;
+        // Marked connected first so dead letters redelivered by `add_peer` go over the backend
+        self.connected.write().await.insert(peer.id.clone());
+        self.add_peer(peer).await
+    }
+
+    /// Next message received over the backend, with the address it came from.
+    /// Messages that fail to decode are logged and skipped.
+    pub async fn recv(&self) -> Option<(SocketAddr, TransportMessage)> {
+        let mut inbound = self.inbound.lock().await;
+        if inbound.is_none() {
+            *inbound = self.backend.take_inbound();
+        }
+        let stream = inbound.as_mut()?;
+        while let Some((from, data)) = stream.recv().await {
+            match self.decode_message(&data) {
+                Ok(message) => return Some((from, message)),
+                Err(e) => warn!("Dropping undecodable message from {}: {}", from, e),
+            }
+        }
+        None
+    }
+
+    /// Send message to specific peer. A message that cannot be delivered is kept
+    /// in the dead-letter queue.
     pub async fn send_to_peer(&self, peer_id: &str, message: TransportMessage) -> Result<()> {
-        let encoded = message.to_cbor()?;
+        self.ensure_accepting().await?;
+        if let Err(e) = self.deliver_known(peer_id, &message).await {
+            self.dead_letter(peer_id.to_string(), message, e.to_string(), 1).await;
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// Queue a message for a peer on its priority queue; it is sent on the next flush
+    pub async fn enqueue(&self, peer_id: &str, message: TransportMessage) -> Result<()> {
+        self.ensure_accepting().await?;
+        self.message_queues.lock().await.push(peer_id.to_string(), message);
+        Ok(())
+    }
+
+    /// Take the next queued message, high priority before normal before low
+    pub async fn dequeue(&self) -> Option<(String, TransportMessage)> {
+        self.message_queues.lock().await.pop()
+    }
+
+    /// Current depth of each priority queue
+    pub async fn get_queue_depths(&self) -> QueueDepths {
+        self.message_queues.lock().await.depths()
+    }
+
+    /// Send every queued message in priority order, returning how many were delivered
+    pub async fn flush_pending(&self) -> Result<usize> {
+        let mut queues = self.message_queues.lock().await;
+        let mut delivered = 0;
+        while let Some((peer_id, message)) = queues.pop() {
+            if let Err(e) = self.deliver(&peer_id, &message).await {
+                self.dead_letter(peer_id, message, e.to_string(), 1).await;
+                return Err(e);
+            }
+            delivered += 1;
+        }
+        Ok(delivered)
+    }
+
+    /// Take every dead letter, oldest first, leaving the queue empty
+    pub async fn drain_dead_letters(&self) -> Vec<DeadLetter> {
+        self.dead_letters.lock().await.drain(..).collect()
+    }
+
+    /// Number of messages waiting in the dead-letter queue
+    pub async fn dead_letter_count(&self) -> usize {
+        self.dead_letters.lock().await.len()
+    }
+
+    /// Try every dead letter again, returning how many were delivered. Failures
+    /// go back on the queue unless the retry policy's attempt limit is reached.
+    pub async fn retry_dead_letters(&self) -> Result<usize> {
+        self.ensure_accepting().await?;
+        let letters: Vec<DeadLetter> = self.dead_letters.lock().await.drain(..).collect();
+        Ok(self.redeliver(letters).await)
+    }
+
+    async fn retry_dead_letters_for(&self, peer_id: &str) -> usize {
+        let letters: Vec<DeadLetter> = {
+            let mut dead_letters = self.dead_letters.lock().await;
+            let (ours, others): (VecDeque<_>, VecDeque<_>) = dead_letters.drain(..)
+                .partition(|letter| letter.peer_id == peer_id);
+            *dead_letters = others;
+            ours.into()
+        };
+        self.redeliver(letters).await
+    }
+
+    async fn redeliver(&self, letters: Vec<DeadLetter>) -> usize {
+        let mut delivered = 0;
+        for letter in letters {
+            match self.deliver_known(&letter.peer_id, &letter.message).await {
+                Ok(()) => delivered += 1,
+                Err(e) => {
+                    let attempts = letter.attempts + 1;
+                    if self.dead_letter_retry.as_ref().is_some_and(|retry| attempts >= retry.max_attempts) {
+                        warn!("Discarding message for peer {} after {} delivery attempts: {}", letter.peer_id, attempts, e);
+                        continue;
+                    }
+                    self.dead_letter(letter.peer_id, letter.message, e.to_string(), attempts).await;
+                }
+            }
+        }
+        delivered
+    }
+
+    async fn dead_letter(&self, peer_id: String, message: TransportMessage, reason: String, attempts: u32) {
+        if self.dead_letter_capacity == 0 {
+            return;
+        }
+        let mut dead_letters = self.dead_letters.lock().await;
+        if dead_letters.len() >= self.dead_letter_capacity {
+            if let Some(evicted) = dead_letters.pop_front() {
+                warn!("Dead-letter queue full; discarding message for peer {}", evicted.peer_id);
+            }
+        }
+        debug!("Dead-lettered message for peer {}: {}", peer_id, reason);
+        dead_letters.push_back(DeadLetter { peer_id, message, reason, attempts, failed_at: unix_timestamp() });
+    }
+
+    async fn ensure_accepting(&self) -> Result<(), BpciError> {
+        if *self.is_draining.read().await {
+            return Err(BpciError::network(NetworkErrorKind::ShuttingDown, "Transport is shutting down"));
+        }
+        Ok(())
+    }
+
+    async fn deliver_known(&self, peer_id: &str, message: &TransportMessage) -> Result<()> {
+        if !self.peers.read().await.contains_key(peer_id) {
+            return Err(BpciError::PeerNotFound(peer_id.to_string()).into());
+        }
+        self.deliver(peer_id, message).await
+    }
+
+    async fn deliver(&self, peer_id: &str, message: &TransportMessage) -> Result<()> {
+        self.links().deliver(peer_id, message).await
+    }
+
+    async fn record_send(
+        stats: &RwLock<HashMap<String, ConnectionStats>>,
+        peer_id: &str,
+        message: &TransportMessage,
+        wire_format: WireFormat,
+    ) -> Result<()> {
+        let encoded = message.encode(wire_format)?;
         let message_hash = domain_hash(TRANSPORT_MESSAGE_HASH, &encoded);
         
         // Update statistics
-        let mut stats = self.stats.write().await;
+        let mut stats = stats.write().await;
         if let Some(peer_stats) = stats.get_mut(peer_id) {
             peer_stats.messages_sent += 1;
             peer_stats.bytes_sent += encoded.len() as u64;
@@ -712,31 +1663,152 @@ impl BpciTransport {
         Ok(())
     }
     
-    /// Broadcast message to all connected peers
-    pub async fn broadcast(&self, message: TransportMessage) -> Result<()> {
-        let peers = self.peers.read().await;
-        for peer_id in peers.keys() {
-            if let Err(e) = self.send_to_peer(peer_id, message.clone()).await {
-                debug!("Failed to send broadcast to peer {}: {}", peer_id, e);
-            }
+    /// Broadcast message to all connected peers, highest connection quality
+    /// first so flaky peers never delay delivery to reliable ones
+    pub async fn broadcast(&self, message: TransportMessage) -> Result<BroadcastReport> {
+        let mut targets: Vec<(String, f64)> = self.peers.read().await.values()
+            .map(|peer| (peer.id.clone(), peer.connection_quality))
+            .collect();
+        targets.sort_by(|a, b| b.1.total_cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+        let mut report = BroadcastReport::default();
+        for (peer_id, connection_quality) in targets {
+            let error = match self.send_to_peer(&peer_id, message.clone()).await {
+                Ok(()) => None,
+                Err(e) => {
+                    debug!("Failed to send broadcast to peer {}: {}", peer_id, e);
+                    Some(e.to_string())
+                }
+            };
+            report.outcomes.push(BroadcastOutcome { peer_id, connection_quality, error });
         }
-        Ok(())
+        Ok(report)
     }
     
-    /// Add a peer to the transport
+    /// Add a peer to the transport, redelivering its dead letters if a retry
+    /// policy is set. Its `last_seen` starts now, so a peer restored from an old
+    /// export is not reaped before it has had a chance to speak.
     pub async fn add_peer(&self, peer: PeerInfo) -> Result<()> {
         let peer_id = peer.id.clone();
-        self.peers.write().await.insert(peer_id.clone(), peer);
-        self.stats.write().await.insert(peer_id, ConnectionStats::default());
+        self.peers.write().await.insert(peer_id.clone(), PeerInfo { last_seen: unix_timestamp(), ..peer });
+        self.stats.write().await.insert(peer_id.clone(), ConnectionStats::default());
+        if self.dead_letter_retry.is_some() {
+            let delivered = self.retry_dead_letters_for(&peer_id).await;
+            if delivered > 0 {
+                info!("Redelivered {} dead-lettered messages to peer {}", delivered, peer_id);
+            }
+        }
         Ok(())
     }
     
-    /// Remove a peer from the transport
+    /// Known peers, ordered by id, for persisting the peer set across restarts
+    pub async fn export_peers(&self) -> Vec<PeerInfo> {
+        let mut peers = self.get_peers().await;
+        peers.sort_by(|a, b| a.id.cmp(&b.id));
+        peers
+    }
+
+    /// Add every peer not already known, returning how many were added
+    pub async fn import_peers(&self, peers: Vec<PeerInfo>) -> Result<usize> {
+        let mut imported = 0;
+        for peer in peers {
+            if self.peers.read().await.contains_key(&peer.id) {
+                continue;
+            }
+            self.add_peer(peer).await?;
+            imported += 1;
+        }
+        Ok(imported)
+    }
+
+    /// Write the exported peer set to `path` as CBOR
+    pub async fn save_peers_to_path(&self, path: impl AsRef<Path>) -> Result<()> {
+        let encoded = serde_cbor::to_vec(&self.export_peers().await)?;
+        tokio::fs::write(path, encoded).await?;
+        Ok(())
+    }
+
+    /// Import the peer set saved by `save_peers_to_path`, returning how many were added
+    pub async fn load_peers_from_path(&self, path: impl AsRef<Path>) -> Result<usize> {
+        let encoded = tokio::fs::read(path).await?;
+        let peers: Vec<PeerInfo> = serde_cbor::from_slice(&encoded)?;
+        self.import_peers(peers).await
+    }
+
+    /// Remove a peer from the transport. The peer is not reconnected, even if persistent.
     pub async fn remove_peer(&self, peer_id: &str) -> Result<()> {
         self.peers.write().await.remove(peer_id);
         self.stats.write().await.remove(peer_id);
+        self.connected.write().await.remove(peer_id);
+        self.persistent.write().await.remove(peer_id);
+        Ok(())
+    }
+
+    /// Re-dial `peer_id` at `address` whenever its connection drops
+    pub async fn set_peer_persistent(&self, peer_id: &str, address: SocketAddr) {
+        self.persistent.write().await.insert(peer_id.to_string(), address);
+    }
+
+    /// Handle a lost connection: forget the peer and, if it is persistent,
+    /// re-dial it in the background following the reconnect policy. A peer
+    /// already being re-dialed is left to its running task.
+    pub async fn peer_dropped(&self, peer_id: &str) {
+        self.links().peer_dropped(peer_id).await
+    }
+    
+    /// Record a message received from a peer, refreshing its liveness
+    pub async fn receive_from_peer(&self, peer_id: &str, message: &TransportMessage) -> Result<()> {
+        {
+            let mut peers = self.peers.write().await;
+            let peer = peers.get_mut(peer_id)
+                .ok_or_else(|| BpciError::PeerNotFound(peer_id.to_string()))?;
+            peer.last_seen = unix_timestamp();
+        }
+
+        let encoded = message.encode(self.config.wire_format)?;
+        if let Some(peer_stats) = self.stats.write().await.get_mut(peer_id) {
+            peer_stats.messages_received += 1;
+            peer_stats.bytes_received += encoded.len() as u64;
+            peer_stats.last_activity = Instant::now();
+        }
         Ok(())
     }
+
+    /// Remove peers that have been silent for longer than `peer_timeout`,
+    /// re-dialing those that are persistent
+    pub async fn reap_silent_peers(&self) -> Vec<String> {
+        self.links().reap(self.peer_timeout).await
+    }
+
+    /// Send heartbeats to every peer each `heartbeat_interval` and reap silent
+    /// peers until stopped
+    fn spawn_liveness_task(&self) -> tokio::task::JoinHandle<()> {
+        let links = self.links();
+        let is_running = self.is_running.clone();
+        let heartbeat_interval = self.config.heartbeat_interval;
+        let peer_timeout = self.peer_timeout;
+
+        tokio::spawn(async move {
+            let start = tokio::time::Instant::now() + heartbeat_interval;
+            let mut interval = tokio::time::interval_at(start, heartbeat_interval);
+            loop {
+                interval.tick().await;
+                if !*is_running.read().await {
+                    break;
+                }
+
+                let heartbeat = TransportMessage::Heartbeat { timestamp: unix_timestamp() };
+                let peer_ids: Vec<String> = links.peers.read().await.keys().cloned().collect();
+                for peer_id in &peer_ids {
+                    if let Err(e) = links.deliver(peer_id, &heartbeat).await {
+                        debug!("Failed to send heartbeat to peer {}: {}", peer_id, e);
+                    }
+                }
+
+                links.reap(peer_timeout).await;
+            }
+        })
+    }
     
     /// Get list of connected peers
     pub async fn get_peers(&self) -> Vec<PeerInfo> {
@@ -753,33 +1825,64 @@ impl BpciTransport {
         *self.is_running.read().await
     }
     
-    /// Shutdown the transport
+    /// Shutdown the transport: refuse new sends, flush queued messages within
+    /// `drain_timeout`, say goodbye to peers and then close their connections.
+    /// Connection statistics are kept for inspection after shutdown.
     pub async fn shutdown(&self) -> Result<()> {
         info!("Shutting down BPCI transport");
+        *self.is_draining.write().await = true;
+
+        let drained = tokio::time::timeout(self.drain_timeout, self.flush_pending()).await;
+        let drained = match drained {
+            Ok(result) => result,
+            Err(_) => {
+                warn!("BPCI transport drain exceeded {:?}; queued messages abandoned", self.drain_timeout);
+                *self.is_running.write().await = false;
+                return Err(BpciError::Timeout.into());
+            }
+        }?;
+
+        let goodbye = TransportMessage::PeerDiscovery(PeerDiscoveryMessage::Goodbye {
+            address: self.config.bind_address,
+        });
+        let peer_ids: Vec<String> = self.peers.read().await.keys().cloned().collect();
+        for peer_id in &peer_ids {
+            if let Err(e) = self.deliver(peer_id, &goodbye).await {
+                debug!("Failed to send goodbye to peer {}: {}", peer_id, e);
+            }
+        }
+        self.peers.write().await.clear();
+
         *self.is_running.write().await = false;
+        info!("BPCI transport shut down: drained {} queued messages, closed {} connections",
+              drained, peer_ids.len());
         Ok(())
     }
 
-    /// Send authenticated BPCI frame
+    /// Reserve the next outbound nonce for `(src, svc)` under a single lock
+    pub async fn next_nonce(&self, src_cluster_id: ClusterId, svc_id_hash: ServiceIdHash) -> u64 {
+        self.nonce_tracker.write().await.next_nonce(src_cluster_id, svc_id_hash)
+    }
+
+    /// Send authenticated BPCI frame. `svc_id_hash` is the destination service's
+    /// `service_id_hash`, e.g. `service_id_hash("consensus.bpci.mesh")`.
     pub async fn send_frame(
         &self,
-        dst_cluster_id: [u8; 16],
-        svc_id_hash: [u8; 32],
+        dst_cluster_id: ClusterId,
+        svc_id_hash: ServiceIdHash,
         payload: &[u8],
-        aead_key: &[u8; 32],
-        signing_key: &[u8; 32],
-        poh_tick: [u8; 32],
+        aead_key: &AeadKey,
+        signing_key: &SigningKey,
+        poh_tick: PohTick,
     ) -> Result<BpciFrame, BpciError> {
         // Generate src_cluster_id (would be from config in real implementation)
-        let src_cluster_id = [1u8; 16];
+        let src_cluster_id = ClusterId::new([1u8; 16]);
         
-        // Get next nonce for this (src, svc) pair
-        let nonce_key = (src_cluster_id, svc_id_hash);
-        let mut tracker = self.nonce_tracker.write().await;
-        let current_nonce = tracker.get_nonce(&nonce_key).unwrap_or(0) + 1;
+        // Reserve the next nonce for this (src, svc) pair
+        let current_nonce = self.next_nonce(src_cluster_id, svc_id_hash).await;
         
         // Create authenticated frame
-        let frame = BpciFrame::new(
+        let frame = BpciFrame::new_with_algorithm(
             src_cluster_id,
             dst_cluster_id,
             svc_id_hash,
@@ -788,36 +1891,289 @@ impl BpciTransport {
             payload,
             aead_key,
             signing_key,
+            self.config.aead_algorithm,
         )?;
         
-        // Update nonce tracker
-        tracker.update_nonce(nonce_key, current_nonce);
-        
-        info!("Sent authenticated BPCI frame with nonce {}", current_nonce);
+        frame.span().in_scope(|| info!("Sent authenticated BPCI frame with nonce {}", current_nonce));
         Ok(frame)
     }
 
-    /// Verify and process received BPCI frame
+    /// Send `frame` to a peer and retransmit it until the peer acknowledges it.
+    /// Returns the frame hash the ack will carry.
+    pub async fn send_frame_reliable(&self, peer_id: &str, frame: BpciFrame) -> Result<[u8; 32]> {
+        let span = frame.span();
+        async {
+            self.send_to_peer(peer_id, frame.to_reliable_message()?).await?;
+            debug!("Sent reliable frame to peer {}; awaiting ack", peer_id);
+            Ok::<_, anyhow::Error>(self.acks.lock().await.track(peer_id, frame, Instant::now())?)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Send `frame` to a peer as fragments cut by `config`, which the peer's
+    /// `route_inbound` reassembles. Returns the number of fragments sent.
+    pub async fn send_frame_fragmented(&self, peer_id: &str, frame: &BpciFrame, config: FragmentConfig) -> Result<usize> {
+        let span = frame.span();
+        async {
+            let fragments = fragment_frame(frame, config)?;
+            let count = fragments.len();
+            for fragment in fragments {
+                self.send_to_peer(peer_id, TransportMessage::Fragment(fragment)).await?;
+            }
+            debug!("Sent frame to peer {} in {} fragments", peer_id, count);
+            Ok(count)
+        }
+        .instrument(span)
+        .await
+    }
+
+    /// Apply an ack received from `peer_id`, checked against that peer's
+    /// registered key. False if the peer has no key, the ack matches no frame
+    /// pending at that peer, or its signature is invalid.
+    pub async fn acknowledge_frame(&self, peer_id: &str, ack: &FrameAck) -> bool {
+        let public_key = match self.peers.read().await.get(peer_id).and_then(|peer| peer.verifying_key) {
+            Some(public_key) => public_key,
+            None => return false,
+        };
+        self.acks.lock().await.acknowledge(peer_id, ack, &public_key)
+    }
+
+    /// Retransmit every reliable frame whose ack timer expired, returning the
+    /// hashes of frames given up on after the retry cap. `start` runs this on a
+    /// timer; calling it directly is only needed on a transport never started.
+    pub async fn retransmit_unacked(&self) -> Vec<[u8; 32]> {
+        self.retransmitter().run_due().await
+    }
+
+    fn links(&self) -> PeerLinks {
+        PeerLinks {
+            backend: self.backend.clone(),
+            peers: self.peers.clone(),
+            stats: self.stats.clone(),
+            connected: self.connected.clone(),
+            persistent: self.persistent.clone(),
+            reconnecting: self.reconnecting.clone(),
+            reconnect_policy: self.reconnect_policy.clone(),
+            wire_format: self.config.wire_format,
+        }
+    }
+
+    fn retransmitter(&self) -> Retransmitter {
+        Retransmitter {
+            acks: self.acks.clone(),
+            peers: self.peers.clone(),
+            connected: self.connected.clone(),
+            backend: self.backend.clone(),
+            stats: self.stats.clone(),
+            wire_format: self.config.wire_format,
+        }
+    }
+
+    /// Retransmit expired frames every ack timeout until stopped
+    async fn spawn_retransmit_task(&self) -> tokio::task::JoinHandle<()> {
+        let retransmitter = self.retransmitter();
+        let is_running = self.is_running.clone();
+        let period = self.acks.lock().await.policy().timeout.max(Duration::from_millis(1));
+
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval_at(tokio::time::Instant::now() + period, period);
+            loop {
+                interval.tick().await;
+                if !*is_running.read().await {
+                    break;
+                }
+                retransmitter.run_due().await;
+            }
+        })
+    }
+
+    /// Number of reliable frames still awaiting an ack
+    pub async fn pending_acks(&self) -> usize {
+        self.acks.lock().await.pending_count()
+    }
+
+    /// Id of the registered peer at `address`
+    pub async fn peer_id_at(&self, address: SocketAddr) -> Option<String> {
+        self.peers.read().await.values()
+            .find(|peer| peer.address == address)
+            .map(|peer| peer.id.clone())
+    }
+
+    /// Next message from a registered peer, passed through `route_inbound`
+    pub async fn recv_routed(&self) -> Option<(String, TransportMessage)> {
+        while let Some((from, message)) = self.recv().await {
+            if let Some(routed) = self.route_inbound(from, message).await {
+                return Some(routed);
+            }
+        }
+        None
+    }
+
+    /// Handle the transport's part of a message received from `from`, and hand
+    /// the rest to the caller with the id of the peer that sent it. Acks clear
+    /// the frame they confirm and are consumed. A reliable frame is acked once
+    /// it proves to be signed by the sending peer, and dropped otherwise.
+    /// Fragments are buffered until their frame is complete, which is then
+    /// returned as a data message. Messages from addresses no registered peer
+    /// uses are dropped.
+    pub async fn route_inbound(&self, from: SocketAddr, message: TransportMessage) -> Option<(String, TransportMessage)> {
+        let peer_id = match self.peer_id_at(from).await {
+            Some(peer_id) => peer_id,
+            None => {
+                debug!("Dropping message from unregistered address {}", from);
+                return None;
+            }
+        };
+        if let Err(e) = self.receive_from_peer(&peer_id, &message).await {
+            debug!("Failed to record message from peer {}: {}", peer_id, e);
+        }
+
+        if let TransportMessage::FrameAck(ack) = &message {
+            if !self.acknowledge_frame(&peer_id, ack).await {
+                debug!(correlation_id = %hex::encode(&ack.frame_hash[..8]), "Ignoring ack from peer {} for no frame pending there", peer_id);
+            }
+            return None;
+        }
+        if let TransportMessage::Fragment(fragment) = message {
+            let frame = match self.reassembler.lock().await.insert(&peer_id, fragment, Instant::now()) {
+                Ok(frame) => frame?,
+                Err(e) => {
+                    warn!("Dropping fragment from peer {}: {}", peer_id, e);
+                    return None;
+                }
+            };
+            return Some((peer_id, frame.to_message().ok()?));
+        }
+        if matches!(message, TransportMessage::ReliableFrame(_)) {
+            let frame = match BpciFrame::from_message(&message) {
+                Some(frame) => frame,
+                None => {
+                    warn!("Dropping undecodable reliable frame from peer {}", peer_id);
+                    return None;
+                }
+            };
+            let span = frame.span();
+            if let Err(e) = self.ack_reliable_frame(&peer_id, &frame).instrument(span.clone()).await {
+                span.in_scope(|| warn!("Dropping reliable frame from peer {}: {}", peer_id, e));
+                return None;
+            }
+        }
+        Some((peer_id, message))
+    }
+
+    /// Send `peer_id` a signed ack for `frame`, provided the peer signed the frame.
+    /// Without a local signing key nothing is sent, and the sender keeps retransmitting.
+    async fn ack_reliable_frame(&self, peer_id: &str, frame: &BpciFrame) -> Result<(), BpciError> {
+        let (_, public_key) = self.authenticated_sender(peer_id, frame).await?;
+        if !frame.verify_signature(&public_key)? {
+            return Err(BpciError::InvalidSignature(format!("frame not signed by peer {}", peer_id)));
+        }
+        let signing_key = match &self.signing_key {
+            Some(signing_key) => signing_key,
+            None => {
+                debug!("No signing key configured; not acknowledging frame from peer {}", peer_id);
+                return Ok(());
+            }
+        };
+        let ack = TransportMessage::FrameAck(FrameAck::sign(frame, signing_key)?);
+        // A lost ack is recovered by the sender's retransmit, which is acked again
+        if let Err(e) = self.deliver_known(peer_id, &ack).await {
+            debug!("Failed to send ack to peer {}: {}", peer_id, e);
+        }
+        Ok(())
+    }
+
+    /// Reject a frame whose authenticated sender lacks the capabilities the access
+    /// policy requires for its service. `None` is a sender not tied to any
+    /// registered peer, which only reaches open services.
+    fn check_access(&self, frame: &BpciFrame, sender: Option<&PeerInfo>) -> Result<(), BpciError> {
+        if self.access_policy.allows(&frame.svc_id_hash, sender) {
+            return Ok(());
+        }
+        Err(BpciError::AuthenticationFailed(format!(
+            "Sender {} lacks capabilities {:?} for service {:02x?}",
+            sender.map_or("<unauthenticated>", |peer| peer.id.as_str()),
+            self.access_policy.required_capabilities(&frame.svc_id_hash),
+            &frame.svc_id_hash.as_bytes()[..8],
+        )))
+    }
+
+    /// Reject a frame referencing a PoH tick the configured verifier does not know
+    fn check_poh_tick(&self, frame: &BpciFrame) -> Result<(), BpciError> {
+        if self.poh_verifier.is_valid_tick(&frame.poh_tick) {
+            return Ok(());
+        }
+        Err(BpciError::UnknownPohTick(hex::encode(frame.poh_tick.as_bytes())))
+    }
+
+    /// Registered peer `peer_id`, provided it may send `frame`: the peer's cluster
+    /// must be the frame's `src_cluster_id` and its signing key must be known
+    async fn authenticated_sender(&self, peer_id: &str, frame: &BpciFrame) -> Result<(PeerInfo, VerifyingKey), BpciError> {
+        let peer = self.peers.read().await.get(peer_id).cloned()
+            .ok_or_else(|| BpciError::PeerNotFound(peer_id.to_string()))?;
+        if peer.cluster_id != Some(frame.src_cluster_id) {
+            return Err(BpciError::AuthenticationFailed(format!(
+                "Peer {} sent a frame from cluster {}", peer_id, hex::encode(frame.src_cluster_id),
+            )));
+        }
+        let verifying_key = peer.verifying_key
+            .ok_or_else(|| BpciError::AuthenticationFailed(format!("Peer {} has no registered signing key", peer_id)))?;
+        Ok((peer, verifying_key))
+    }
+
+    /// Verify a frame received from the registered peer `peer_id`. The signature
+    /// is checked against the peer's own key and access is decided by the peer's
+    /// capabilities, so a frame naming another cluster gains nothing.
+    pub async fn verify_frame_from(
+        &self,
+        peer_id: &str,
+        frame: &BpciFrame,
+        aead_key: &AeadKey,
+    ) -> Result<(Vec<u8>, AuthenticationResult), BpciError> {
+        let (sender, public_key) = self.authenticated_sender(peer_id, frame).await?;
+        self.verify_frame_as(frame, Some(&sender), &public_key, aead_key).await
+    }
+
+    /// Verify and process received BPCI frame. The sender is not tied to a
+    /// registered peer, so only services the access policy leaves open are
+    /// accepted; use `verify_frame_from` for the others.
     pub async fn verify_frame(
         &self,
         frame: &BpciFrame,
-        public_key: &[u8; 32],
-        aead_key: &[u8; 32],
+        public_key: &VerifyingKey,
+        aead_key: &AeadKey,
     ) -> Result<(Vec<u8>, AuthenticationResult), BpciError> {
-        let mut tracker = self.nonce_tracker.write().await;
-        let (payload, result) = frame.verify(public_key, aead_key, &mut tracker)?;
-        
-        if result.valid {
-            info!("Successfully verified BPCI frame with nonce {}", frame.nonce);
-        } else {
-            warn!("BPCI frame verification failed: {:?}", result.error);
+        self.verify_frame_as(frame, None, public_key, aead_key).await
+    }
+
+    async fn verify_frame_as(
+        &self,
+        frame: &BpciFrame,
+        sender: Option<&PeerInfo>,
+        public_key: &VerifyingKey,
+        aead_key: &AeadKey,
+    ) -> Result<(Vec<u8>, AuthenticationResult), BpciError> {
+        async {
+            self.check_access(frame, sender)?;
+            self.check_poh_tick(frame)?;
+            let mut tracker = self.nonce_tracker.write().await;
+            let (payload, result) = frame.verify_with_algorithms(public_key, aead_key, &mut tracker, &self.config.permitted_aead_algorithms)?;
+
+            if result.valid {
+                info!("Successfully verified BPCI frame with nonce {}", frame.nonce);
+            } else {
+                warn!("BPCI frame verification failed: {:?}", result.error);
+            }
+
+            Ok::<_, BpciError>((payload, result))
         }
-        
-        Ok((payload, result))
+        .instrument(frame.span())
+        .await
     }
 
     /// Get nonce tracker statistics
-    pub async fn get_nonce_stats(&self) -> HashMap<([u8; 16], [u8; 32]), u64> {
+    pub async fn get_nonce_stats(&self) -> HashMap<NonceKey, u64> {
         let _tracker = self.nonce_tracker.read().await;
         let stats = HashMap::new();
         // Would implement proper stats collection in real implementation
@@ -838,80 +2194,86 @@ impl BpciTransport {
 
     // Stage 18: E2E Key Agreement Methods
 
-    /// Register a service's X25519 public key for E2E key agreement
-    pub async fn register_service_key(&self, svc_id_hash: [u8; 32], public_key_bytes: [u8; 32]) -> Result<(), BpciError> {
-        self.key_manager.register_service_key(svc_id_hash, public_key_bytes).await
+    /// Register a service's X25519 public key for E2E key agreement, keyed by its
+    /// `service_id_hash`
+    pub async fn register_service_key(&self, svc_id_hash: ServiceIdHash, public_key: X25519PublicKey) -> Result<(), BpciError> {
+        self.key_manager.register_service_key(svc_id_hash, public_key).await
     }
 
     /// Register our own service key pair and return the public key
-    pub async fn register_our_service_key(&self, svc_id_hash: [u8; 32]) -> Result<[u8; 32], BpciError> {
+    pub async fn register_our_service_key(&self, svc_id_hash: ServiceIdHash) -> Result<[u8; 32], BpciError> {
         self.key_manager.register_our_service_key(svc_id_hash).await
     }
 
     /// Send authenticated BPCI frame with E2E key agreement
     pub async fn send_frame_with_e2e(
         &self,
-        dst_cluster_id: [u8; 16],
-        svc_id_hash: [u8; 32],
+        dst_cluster_id: ClusterId,
+        svc_id_hash: ServiceIdHash,
         payload: &[u8],
-        signing_key: &[u8; 32],
-        poh_tick: [u8; 32],
+        signing_key: &SigningKey,
+        poh_tick: PohTick,
     ) -> Result<(BpciFrame, [u8; 32]), BpciError> {
         // Derive AEAD key using E2E key agreement
         let key_result = self.key_manager.derive_sender_key(svc_id_hash).await?;
+        let aead_key = AeadKey::new(key_result.aead_key);
         
         // Generate src_cluster_id (would be from config in real implementation)
-        let src_cluster_id = [1u8; 16];
+        let src_cluster_id = ClusterId::new([1u8; 16]);
         
-        // Get next nonce for this (src, svc) pair
-        let nonce_key = (src_cluster_id, svc_id_hash);
-        let mut tracker = self.nonce_tracker.write().await;
-        let current_nonce = tracker.get_nonce(&nonce_key).unwrap_or(0) + 1;
+        // Reserve the next nonce for this (src, svc) pair
+        let current_nonce = self.next_nonce(src_cluster_id, svc_id_hash).await;
         
         // Create authenticated frame with derived AEAD key
-        let frame = BpciFrame::new(
+        let frame = BpciFrame::new_with_algorithm(
             src_cluster_id,
             dst_cluster_id,
             svc_id_hash,
             current_nonce,
             poh_tick,
             payload,
-            &key_result.aead_key,
+            &aead_key,
             signing_key,
+            self.config.aead_algorithm,
         )?;
         
-        // Update nonce tracker
-        tracker.update_nonce(nonce_key, current_nonce);
-        
-        info!("Sent E2E authenticated BPCI frame with nonce {} and ephemeral key", current_nonce);
+        frame.span().in_scope(|| info!("Sent E2E authenticated BPCI frame with nonce {} and ephemeral key", current_nonce));
         Ok((frame, key_result.ephemeral_public_key.to_bytes()))
     }
 
-    /// Verify and process received BPCI frame with E2E key agreement
+    /// Verify and process received BPCI frame with E2E key agreement. Like
+    /// `verify_frame`, only open services are reachable this way.
     pub async fn verify_frame_with_e2e(
         &self,
         frame: &BpciFrame,
-        public_key: &[u8; 32],
+        public_key: &VerifyingKey,
         ephemeral_public_key_bytes: [u8; 32],
     ) -> Result<(Vec<u8>, AuthenticationResult), BpciError> {
-        // Derive AEAD key using E2E key agreement
-        let aead_key = self.key_manager.derive_receiver_key(
-            frame.svc_id_hash,
-            ephemeral_public_key_bytes,
-            frame.src_cluster_id,
-        ).await?;
-        
-        // Verify frame with derived AEAD key
-        let mut tracker = self.nonce_tracker.write().await;
-        let (payload, result) = frame.verify(public_key, &aead_key, &mut tracker)?;
-        
-        if result.valid {
-            info!("Successfully verified E2E BPCI frame with nonce {}", frame.nonce);
-        } else {
-            warn!("E2E BPCI frame verification failed: {:?}", result.error);
+        async {
+            self.check_access(frame, None)?;
+            self.check_poh_tick(frame)?;
+
+            // Derive AEAD key using E2E key agreement
+            let aead_key = AeadKey::new(self.key_manager.derive_receiver_key(
+                frame.svc_id_hash,
+                ephemeral_public_key_bytes,
+                frame.src_cluster_id,
+            ).await?);
+
+            // Verify frame with derived AEAD key
+            let mut tracker = self.nonce_tracker.write().await;
+            let (payload, result) = frame.verify_with_algorithms(public_key, &aead_key, &mut tracker, &self.config.permitted_aead_algorithms)?;
+
+            if result.valid {
+                info!("Successfully verified E2E BPCI frame with nonce {}", frame.nonce);
+            } else {
+                warn!("E2E BPCI frame verification failed: {:?}", result.error);
+            }
+
+            Ok::<_, BpciError>((payload, result))
         }
-        
-        Ok((payload, result))
+        .instrument(frame.span())
+        .await
     }
 
     /// Get E2E key manager statistics
@@ -937,6 +2299,187 @@ impl BpciTransport {
     }
 }
 
+/// Peer bookkeeping and the backend, shared with the liveness task so it can
+/// send heartbeats and drop, reap and re-dial peers on its own
+#[derive(Debug, Clone)]
+struct PeerLinks {
+    backend: Arc<dyn Transport>,
+    peers: Arc<RwLock<HashMap<String, PeerInfo>>>,
+    stats: Arc<RwLock<HashMap<String, ConnectionStats>>>,
+    connected: Arc<RwLock<HashSet<String>>>,
+    persistent: Arc<RwLock<HashMap<String, SocketAddr>>>,
+    reconnecting: Arc<Mutex<HashSet<String>>>,
+    reconnect_policy: ReconnectPolicy,
+    wire_format: WireFormat,
+}
+
+impl PeerLinks {
+    async fn deliver(&self, peer_id: &str, message: &TransportMessage) -> Result<()> {
+        if self.connected.read().await.contains(peer_id) {
+            let address = self.peers.read().await.get(peer_id)
+                .map(|peer| peer.address)
+                .ok_or_else(|| BpciError::PeerNotFound(peer_id.to_string()))?;
+            if let Err(e) = self.backend.send(address, message.encode(self.wire_format)?).await {
+                self.peer_dropped(peer_id).await;
+                return Err(e.into());
+            }
+        }
+        BpciTransport::record_send(&self.stats, peer_id, message, self.wire_format).await
+    }
+
+    async fn peer_dropped(&self, peer_id: &str) {
+        let dropped = self.peers.write().await.remove(peer_id);
+        self.stats.write().await.remove(peer_id);
+        self.connected.write().await.remove(peer_id);
+        self.reconnect_if_persistent(peer_id, dropped).await;
+    }
+
+    /// Remove peers silent for longer than `peer_timeout`, marking them
+    /// disconnected and re-dialing those that are persistent
+    async fn reap(&self, peer_timeout: Duration) -> Vec<String> {
+        let cutoff = unix_timestamp().saturating_sub(peer_timeout.as_secs());
+        let silent: Vec<PeerInfo> = {
+            let mut peers = self.peers.write().await;
+            let silent_ids: Vec<String> = peers.values()
+                .filter(|peer| peer.last_seen < cutoff)
+                .map(|peer| peer.id.clone())
+                .collect();
+            silent_ids.iter().filter_map(|peer_id| peers.remove(peer_id)).collect()
+        };
+
+        let mut reaped = Vec::with_capacity(silent.len());
+        for peer in silent {
+            let peer_id = peer.id.clone();
+            self.stats.write().await.remove(&peer_id);
+            self.connected.write().await.remove(&peer_id);
+            warn!("Removed silent peer {}", peer_id);
+            self.reconnect_if_persistent(&peer_id, Some(peer)).await;
+            reaped.push(peer_id);
+        }
+        reaped
+    }
+
+    async fn reconnect_if_persistent(&self, peer_id: &str, dropped: Option<PeerInfo>) {
+        let address = match self.persistent.read().await.get(peer_id) {
+            Some(address) => *address,
+            None => return,
+        };
+        let peer = match dropped {
+            Some(peer) => PeerInfo { address, ..peer },
+            None => PeerInfo {
+                id: peer_id.to_string(),
+                address,
+                capabilities: vec![],
+                last_seen: 0,
+                connection_quality: 1.0,
+                cluster_id: None,
+                verifying_key: None,
+            },
+        };
+        self.spawn_reconnect(peer).await;
+    }
+
+    async fn spawn_reconnect(&self, peer: PeerInfo) {
+        if !self.reconnecting.lock().await.insert(peer.id.clone()) {
+            debug!("Reconnect to peer {} already in progress", peer.id);
+            return;
+        }
+        info!("Lost connection to persistent peer {}; reconnecting to {}", peer.id, peer.address);
+        let backend = self.backend.clone();
+        let peers = self.peers.clone();
+        let stats = self.stats.clone();
+        let connected = self.connected.clone();
+        let persistent = self.persistent.clone();
+        let reconnecting = self.reconnecting.clone();
+        let policy = self.reconnect_policy.clone();
+
+        tokio::spawn(async move {
+            let peer_id = peer.id.clone();
+            let address = peer.address;
+            let reconnected = async {
+                for attempt in 1..=policy.max_attempts {
+                    tokio::time::sleep(policy.delay_for(attempt)).await;
+                    // Removed explicitly, or added back by hand, while we were waiting
+                    if !persistent.read().await.contains_key(&peer.id) || peers.read().await.contains_key(&peer.id) {
+                        return true;
+                    }
+                    match backend.dial(peer.address).await {
+                        Ok(()) => {
+                            info!("Reconnected to peer {} after {} attempts", peer.id, attempt);
+                            let peer_id = peer.id.clone();
+                            peers.write().await.insert(peer_id.clone(), PeerInfo { last_seen: unix_timestamp(), ..peer });
+                            stats.write().await.insert(peer_id.clone(), ConnectionStats::default());
+                            connected.write().await.insert(peer_id);
+                            return true;
+                        }
+                        Err(e) => debug!("Reconnect attempt {} to peer {} failed: {}", attempt, peer.id, e),
+                    }
+                }
+                false
+            }.await;
+
+            reconnecting.lock().await.remove(&peer_id);
+            if !reconnected {
+                warn!("Giving up on peer {} at {} after {} reconnect attempts", peer_id, address, policy.max_attempts);
+            }
+        });
+    }
+}
+
+/// Handles the retransmit timer needs to resend frames outside the transport
+#[derive(Debug, Clone)]
+struct Retransmitter {
+    acks: Arc<Mutex<AckTracker>>,
+    peers: Arc<RwLock<HashMap<String, PeerInfo>>>,
+    connected: Arc<RwLock<HashSet<String>>>,
+    backend: Arc<dyn Transport>,
+    stats: Arc<RwLock<HashMap<String, ConnectionStats>>>,
+    wire_format: WireFormat,
+}
+
+impl Retransmitter {
+    /// Resend every frame whose ack timer expired, returning the hashes of
+    /// frames given up on
+    async fn run_due(&self) -> Vec<[u8; 32]> {
+        let actions = self.acks.lock().await.due(Instant::now());
+        let mut gave_up = Vec::new();
+        for action in actions {
+            match action {
+                RetransmitAction::Retransmit { peer_id, frame } => {
+                    let span = frame.span();
+                    // Still tracked; the next timer expiry tries again
+                    if let Err(e) = self.resend(&peer_id, &frame).instrument(span.clone()).await {
+                        span.in_scope(|| debug!("Retransmit to peer {} failed: {}", peer_id, e));
+                    }
+                }
+                RetransmitAction::GaveUp { peer_id, frame_hash } => {
+                    warn!(correlation_id = %hex::encode(&frame_hash[..8]), "Peer {} never acknowledged frame", peer_id);
+                    gave_up.push(frame_hash);
+                }
+            }
+        }
+        gave_up
+    }
+
+    async fn resend(&self, peer_id: &str, frame: &BpciFrame) -> Result<()> {
+        let message = frame.to_reliable_message()?;
+        let address = self.peers.read().await.get(peer_id)
+            .map(|peer| peer.address)
+            .ok_or_else(|| BpciError::PeerNotFound(peer_id.to_string()))?;
+        if self.connected.read().await.contains(peer_id) {
+            self.backend.send(address, message.encode(self.wire_format)?).await?;
+        }
+        BpciTransport::record_send(&self.stats, peer_id, &message, self.wire_format).await
+    }
+}
+
+fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
 // Implement message serialization using CBOR
 impl TransportMessage {
     pub fn to_cbor(&self) -> Result<Vec<u8>, EncodingError> {
@@ -947,16 +2490,85 @@ impl TransportMessage {
         serde_cbor::from_slice(data).map_err(EncodingError::CborEncode)
     }
     
+    pub fn to_bincode(&self) -> Result<Vec<u8>, BpciError> {
+        Ok(bincode::serialize(self)?)
+    }
+
+    pub fn from_bincode(data: &[u8]) -> Result<Self, BpciError> {
+        Ok(bincode::deserialize(data)?)
+    }
+
+    /// Encode in `format`, prefixed with the format's tag byte
+    pub fn encode(&self, format: WireFormat) -> Result<Vec<u8>, BpciError> {
+        let body = match format {
+            WireFormat::Cbor => self.to_cbor()?,
+            WireFormat::Bincode => self.to_bincode()?,
+        };
+        let mut encoded = Vec::with_capacity(body.len() + 1);
+        encoded.push(format.tag());
+        encoded.extend_from_slice(&body);
+        Ok(encoded)
+    }
+
+    /// Decode a tagged message, detecting the format from its tag byte.
+    /// Messages over `DEFAULT_MAX_MESSAGE_SIZE` are rejected.
+    pub fn decode(data: &[u8]) -> Result<Self, BpciError> {
+        Self::decode_with_limit(data, DEFAULT_MAX_MESSAGE_SIZE)
+    }
+
+    /// Decode a tagged message of at most `max_message_size` bytes. The size is
+    /// checked before parsing, and the decoded variant must also fit its own cap
+    /// (see `size_limit`).
+    pub fn decode_with_limit(data: &[u8], max_message_size: usize) -> Result<Self, BpciError> {
+        if data.len() > max_message_size {
+            return Err(BpciError::InvalidMessage);
+        }
+        let (&tag, body) = data.split_first().ok_or(BpciError::InvalidMessage)?;
+        let message = match WireFormat::from_tag(tag)? {
+            WireFormat::Cbor => Self::from_cbor(body)?,
+            WireFormat::Bincode => Self::from_bincode(body)?,
+        };
+        if data.len() > message.size_limit(max_message_size) {
+            return Err(BpciError::InvalidMessage);
+        }
+        Ok(message)
+    }
+
+    /// Largest encoded size accepted for this variant: bulk data and block
+    /// proposals may use the whole limit, control traffic much less
+    pub fn size_limit(&self, max_message_size: usize) -> usize {
+        match self {
+            TransportMessage::Heartbeat { .. } => HEARTBEAT_MAX_SIZE,
+            TransportMessage::Consensus(_)
+            | TransportMessage::PohTick(_)
+            | TransportMessage::IbftMessage(_)
+            | TransportMessage::PeerDiscovery(_)
+            | TransportMessage::FrameAck(_) => CONTROL_MESSAGE_MAX_SIZE.min(max_message_size),
+            TransportMessage::BlockProposal(_)
+            | TransportMessage::Data { .. }
+            | TransportMessage::ReliableFrame(_)
+            | TransportMessage::Fragment(_) => max_message_size,
+        }
+    }
+    
     /// Get message hash for integrity verification
     pub fn hash(&self) -> Result<[u8; 32], EncodingError> {
         let encoded = self.to_cbor()?;
         Ok(domain_hash(TRANSPORT_MESSAGE_HASH, &encoded))
     }
+
+    /// Message hash over the tagged encoding, so the same message hashes
+    /// identically for a given format but never collides across formats
+    pub fn hash_for_format(&self, format: WireFormat) -> Result<[u8; 32], BpciError> {
+        let encoded = self.encode(format)?;
+        Ok(domain_hash(TRANSPORT_MESSAGE_HASH, &encoded))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use proptest::prelude::*;
     
     #[tokio::test]
     async fn test_bpci_config_default() {
@@ -989,6 +2601,75 @@ mod tests {
         }
         println!("✅ Message serialization working");
     }
+
+    #[test]
+    fn test_wire_format_round_trip() {
+        let message = TransportMessage::Data { payload: vec![1, 2, 3, 4] };
+        for format in [WireFormat::Cbor, WireFormat::Bincode] {
+            let encoded = message.encode(format).unwrap();
+            assert_eq!(encoded[0], format.tag());
+            match TransportMessage::decode(&encoded).unwrap() {
+                TransportMessage::Data { payload } => assert_eq!(payload, vec![1, 2, 3, 4]),
+                other => panic!("Wrong message type: {:?}", other),
+            }
+            assert_eq!(message.hash_for_format(format).unwrap(), message.hash_for_format(format).unwrap());
+        }
+
+        assert_ne!(
+            message.hash_for_format(WireFormat::Cbor).unwrap(),
+            message.hash_for_format(WireFormat::Bincode).unwrap()
+        );
+        assert!(matches!(TransportMessage::decode(&[0xff, 0x00]), Err(BpciError::UnknownWireFormat(0xff))));
+        assert!(matches!(TransportMessage::decode(&[]), Err(BpciError::InvalidMessage)));
+    }
+
+    #[test]
+    fn test_decode_enforces_message_size_limit() {
+        let message = TransportMessage::Data { payload: vec![7u8; 1000] };
+        for format in [WireFormat::Cbor, WireFormat::Bincode] {
+            let encoded = message.encode(format).unwrap();
+
+            // Exactly at the limit is accepted, one byte over is not
+            assert!(TransportMessage::decode_with_limit(&encoded, encoded.len()).is_ok());
+            assert!(matches!(
+                TransportMessage::decode_with_limit(&encoded, encoded.len() - 1),
+                Err(BpciError::InvalidMessage)
+            ));
+        }
+
+        // Oversize input is refused before it is parsed
+        let oversize = vec![WireFormat::Cbor.tag(); DEFAULT_MAX_MESSAGE_SIZE + 1];
+        assert!(matches!(TransportMessage::decode(&oversize), Err(BpciError::InvalidMessage)));
+
+        // A tiny frame declaring a huge payload fails without allocating it
+        let mut lying = vec![WireFormat::Bincode.tag()];
+        lying.extend_from_slice(&6u32.to_le_bytes()); // Data
+        lying.extend_from_slice(&(u64::MAX / 2).to_le_bytes());
+        assert!(TransportMessage::decode(&lying).is_err());
+
+        // Control traffic has a tighter cap than bulk data
+        let consensus = TransportMessage::Consensus(vec![0u8; CONTROL_MESSAGE_MAX_SIZE]).encode(WireFormat::Bincode).unwrap();
+        assert!(matches!(TransportMessage::decode(&consensus), Err(BpciError::InvalidMessage)));
+        let data = TransportMessage::Data { payload: vec![0u8; CONTROL_MESSAGE_MAX_SIZE] }.encode(WireFormat::Bincode).unwrap();
+        assert!(TransportMessage::decode(&data).is_ok());
+
+        let transport = BpciTransport::new(BpciConfig { max_message_size: 512, ..BpciConfig::default() }).unwrap();
+        assert!(matches!(transport.decode_message(&data), Err(BpciError::InvalidMessage)));
+        let heartbeat = TransportMessage::Heartbeat { timestamp: 1 }.encode(WireFormat::Cbor).unwrap();
+        assert!(matches!(transport.decode_message(&heartbeat), Ok(TransportMessage::Heartbeat { timestamp: 1 })));
+    }
+
+    #[test]
+    fn test_wire_format_encoded_sizes() {
+        let heartbeat = TransportMessage::Heartbeat { timestamp: 1234567890 };
+        let cbor = heartbeat.encode(WireFormat::Cbor).unwrap();
+        let bincode = heartbeat.encode(WireFormat::Bincode).unwrap();
+        println!("Heartbeat encoded size: CBOR {} bytes, bincode {} bytes", cbor.len(), bincode.len());
+
+        // Tag + u32 variant index + u64 timestamp
+        assert_eq!(bincode.len(), 1 + 4 + 8);
+        assert!(bincode.len() < cbor.len());
+    }
     
     #[tokio::test]
     async fn test_message_hashing() {
@@ -1011,6 +2692,8 @@ mod tests {
             capabilities: vec!["consensus".to_string(), "poh".to_string()],
             last_seen: 1234567890,
             connection_quality: 0.95,
+            cluster_id: None,
+            verifying_key: None,
         };
         
         // Add peer
@@ -1027,6 +2710,45 @@ mod tests {
         println!("✅ Peer management working");
     }
     
+    #[tokio::test]
+    async fn test_peer_export_import_round_trip() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        for (id, port) in [("peer-b", 9102), ("peer-a", 9101)] {
+            transport.add_peer(PeerInfo {
+                id: id.to_string(),
+                address: format!("127.0.0.1:{}", port).parse().unwrap(),
+                cluster_id: Some(ClusterId::new([port as u8; 16])),
+                verifying_key: Some(SigningKey::new([port as u8; 32]).verifying_key()),
+                ..drain_test_peer()
+            }).await.unwrap();
+        }
+        let exported = transport.export_peers().await;
+        assert_eq!(exported.iter().map(|peer| peer.id.as_str()).collect::<Vec<_>>(), vec!["peer-a", "peer-b"]);
+
+        let fresh = BpciTransport::new(BpciConfig::default()).unwrap();
+        assert_eq!(fresh.import_peers(exported.clone()).await.unwrap(), 2);
+        // Already known peers are skipped
+        assert_eq!(fresh.import_peers(exported.clone()).await.unwrap(), 0);
+
+        let path = std::env::temp_dir().join(format!("bpci-peers-{}.cbor", uuid::Uuid::new_v4()));
+        transport.save_peers_to_path(&path).await.unwrap();
+        let restarted = BpciTransport::new(BpciConfig::default()).unwrap();
+        assert_eq!(restarted.load_peers_from_path(&path).await.unwrap(), 2);
+        std::fs::remove_file(&path).unwrap();
+
+        for node in [&fresh, &restarted] {
+            let peers = node.export_peers().await;
+            assert_eq!(peers.len(), exported.len());
+            for (got, want) in peers.iter().zip(&exported) {
+                assert_eq!(got.id, want.id);
+                assert_eq!(got.address, want.address);
+                assert_eq!(got.cluster_id, want.cluster_id);
+                assert_eq!(got.verifying_key, want.verifying_key);
+            }
+            assert!(node.get_stats().await.contains_key("peer-a"));
+        }
+    }
+
     #[tokio::test]
     async fn test_transport_lifecycle() {
         let config = BpciConfig::default();
@@ -1037,25 +2759,257 @@ mod tests {
         
         // Start transport
         transport.start().await.unwrap();
-        assert!(transport.is_running().await);
-        
-        // Shutdown transport
+        assert!(transport.is_running().await);
+        
+        // Shutdown transport
+        transport.shutdown().await.unwrap();
+        assert!(!transport.is_running().await);
+        
+        println!("✅ Transport lifecycle working");
+    }
+    
+    fn drain_test_peer() -> PeerInfo {
+        PeerInfo {
+            id: "drain-peer".to_string(),
+            address: "127.0.0.1:9001".parse().unwrap(),
+            capabilities: vec![],
+            last_seen: 0,
+            connection_quality: 1.0,
+            cluster_id: None,
+            verifying_key: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drains_queued_messages() {
+        let mut transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        transport.start().await.unwrap();
+        transport.add_peer(drain_test_peer()).await.unwrap();
+
+        for i in 0..5u8 {
+            transport.enqueue("drain-peer", TransportMessage::Data { payload: vec![i] }).await.unwrap();
+        }
+        transport.shutdown().await.unwrap();
+
+        // Five queued messages plus the goodbye
+        let stats = transport.get_stats().await;
+        assert_eq!(stats["drain-peer"].messages_sent, 6);
+        assert!(transport.get_peers().await.is_empty());
+        assert!(!transport.is_running().await);
+
+        assert!(transport.enqueue("drain-peer", TransportMessage::Data { payload: vec![] }).await.is_err());
+        assert!(transport.send_to_peer("drain-peer", TransportMessage::Data { payload: vec![] }).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_network_error_kind_matchable() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        transport.add_peer(drain_test_peer()).await.unwrap();
+        transport.shutdown().await.unwrap();
+
+        let err = transport.send_to_peer("drain-peer", TransportMessage::Heartbeat { timestamp: 1 }).await.unwrap_err();
+        match err.downcast_ref::<BpciError>() {
+            Some(BpciError::Network { kind: NetworkErrorKind::ShuttingDown, detail }) => {
+                assert_eq!(detail, "Transport is shutting down");
+            }
+            other => panic!("Expected a shutting-down network error, got {:?}", other),
+        }
+        assert_eq!(err.to_string(), "Network error (shutting down): Transport is shutting down");
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_drain_timeout() {
+        let mut transport = BpciTransport::new(BpciConfig::default())
+            .unwrap()
+            .with_drain_timeout(Duration::from_millis(20));
+        transport.start().await.unwrap();
+        transport.add_peer(drain_test_peer()).await.unwrap();
+        transport.enqueue("drain-peer", TransportMessage::Data { payload: vec![1] }).await.unwrap();
+
+        // Hold the queue so the drain cannot make progress
+        let _queue = transport.message_queues.lock().await;
+        let err = transport.shutdown().await.unwrap_err();
+        assert!(matches!(err.downcast_ref::<BpciError>(), Some(BpciError::Timeout)));
+        assert!(!transport.is_running().await);
+    }
+    
+    #[tokio::test]
+    async fn test_silent_peer_reaped_active_peer_kept() {
+        let config = BpciConfig {
+            heartbeat_interval: Duration::from_millis(20),
+            ..BpciConfig::default()
+        };
+        let mut transport = BpciTransport::new(config)
+            .unwrap()
+            .with_peer_timeout(Duration::from_secs(30));
+
+        let silent = PeerInfo { id: "silent".to_string(), last_seen: 0, ..drain_test_peer() };
+        let active = PeerInfo { id: "active".to_string(), ..drain_test_peer() };
+        transport.add_peer(silent).await.unwrap();
+        transport.add_peer(active).await.unwrap();
+        // Adding counts as being seen; age the silent peer past the timeout
+        assert!(transport.get_peers().await.iter().all(|peer| peer.last_seen > 0));
+        transport.peers.write().await.get_mut("silent").unwrap().last_seen = 0;
+        transport.receive_from_peer("active", &TransportMessage::Heartbeat { timestamp: 1 }).await.unwrap();
+
+        transport.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        let peers = transport.get_peers().await;
+        assert_eq!(peers.len(), 1);
+        assert_eq!(peers[0].id, "active");
+
+        // The surviving peer has been sent heartbeats and its receipt recorded
+        let stats = transport.get_stats().await;
+        assert!(stats["active"].messages_sent > 0);
+        assert_eq!(stats["active"].messages_received, 1);
+        assert!(!stats.contains_key("silent"));
+
+        assert!(transport.receive_from_peer("silent", &TransportMessage::Heartbeat { timestamp: 2 }).await.is_err());
+        transport.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_start_twice_runs_one_liveness_task() {
+        let config = BpciConfig {
+            heartbeat_interval: Duration::from_millis(20),
+            ..BpciConfig::default()
+        };
+        let mut transport = BpciTransport::new(config).unwrap();
+        transport.add_peer(PeerInfo { id: "active".to_string(), ..drain_test_peer() }).await.unwrap();
+
+        transport.start().await.unwrap();
+        transport.start().await.unwrap();
+        tokio::time::sleep(Duration::from_millis(110)).await;
+
+        // Roughly one heartbeat per interval; a second task would double it
+        let sent = transport.get_stats().await["active"].messages_sent;
+        assert!((2..=7).contains(&sent), "sent {} heartbeats", sent);
         transport.shutdown().await.unwrap();
-        assert!(!transport.is_running().await);
-        
-        println!("✅ Transport lifecycle working");
+    }
+
+    #[tokio::test]
+    async fn test_heartbeats_reach_connected_peers() {
+        let network = InMemoryNetwork::new();
+        let addr_a: SocketAddr = "10.0.4.1:7000".parse().unwrap();
+        let addr_b: SocketAddr = "10.0.4.2:7000".parse().unwrap();
+        let config = BpciConfig {
+            bind_address: addr_a,
+            heartbeat_interval: Duration::from_millis(20),
+            ..BpciConfig::default()
+        };
+        let mut node_a = BpciTransport::new(config).unwrap().with_backend(Box::new(network.transport()));
+        let node_b = BpciTransport::new(BpciConfig { bind_address: addr_b, ..BpciConfig::default() }).unwrap()
+            .with_backend(Box::new(network.transport()));
+        node_a.listen().await.unwrap();
+        node_b.listen().await.unwrap();
+        node_a.connect_peer(PeerInfo { id: "node-b".to_string(), address: addr_b, ..drain_test_peer() }).await.unwrap();
+        node_b.add_peer(PeerInfo { id: "node-a".to_string(), address: addr_a, ..drain_test_peer() }).await.unwrap();
+        node_b.peers.write().await.get_mut("node-a").unwrap().last_seen = 0;
+
+        node_a.start().await.unwrap();
+        let (peer_id, message) = tokio::time::timeout(Duration::from_secs(5), node_b.recv_routed()).await.unwrap().unwrap();
+        assert_eq!(peer_id, "node-a");
+        assert!(matches!(message, TransportMessage::Heartbeat { .. }));
+        // Which is what keeps node-a from being reaped on node-b
+        assert!(node_b.get_peers().await[0].last_seen > 0);
+        node_a.shutdown().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_reaped_persistent_peer_redialed() {
+        let dials = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_backend(Box::new(FlakyTransport { failures: 0, dials: dials.clone() }))
+            .with_peer_timeout(Duration::from_secs(30))
+            .with_reconnect_policy(ReconnectPolicy {
+                initial_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(4),
+                max_attempts: 5,
+            });
+        transport.connect_peer(drain_test_peer()).await.unwrap();
+        transport.set_peer_persistent("drain-peer", drain_test_peer().address).await;
+        transport.peers.write().await.get_mut("drain-peer").unwrap().last_seen = 0;
+
+        assert_eq!(transport.reap_silent_peers().await, vec!["drain-peer".to_string()]);
+        assert!(!transport.connected.read().await.contains("drain-peer"));
+
+        // Handed to the reconnect path, which dials it again
+        tokio::time::timeout(Duration::from_secs(2), async {
+            while !transport.connected.read().await.contains("drain-peer") {
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }).await.expect("peer was not redialed");
+        assert_eq!(dials.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert!(transport.get_peers().await[0].last_seen > 0);
+    }
+
+    #[tokio::test]
+    async fn test_high_priority_dequeued_first() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+
+        // A backlog of bulk data with consensus traffic arriving in between
+        for i in 0..10u8 {
+            transport.enqueue("peer", TransportMessage::Data { payload: vec![i] }).await.unwrap();
+            if i % 3 == 0 {
+                transport.enqueue("peer", TransportMessage::Consensus(vec![i])).await.unwrap();
+            }
+        }
+        transport.enqueue("peer", TransportMessage::PeerDiscovery(PeerDiscoveryMessage::PeerListRequest)).await.unwrap();
+        transport.enqueue("peer", TransportMessage::Heartbeat { timestamp: 1 }).await.unwrap();
+
+        assert_eq!(transport.get_queue_depths().await, QueueDepths { high: 5, normal: 1, low: 10 });
+
+        let mut order = Vec::new();
+        while let Some((_, message)) = transport.dequeue().await {
+            order.push(message);
+        }
+        let priorities: Vec<MessagePriority> = order.iter().map(|m| m.priority()).collect();
+        assert!(priorities[..5].iter().all(|p| *p == MessagePriority::High));
+        assert_eq!(priorities[5], MessagePriority::Normal);
+        assert!(priorities[6..].iter().all(|p| *p == MessagePriority::Low));
+
+        // FIFO within a priority class
+        let consensus: Vec<u8> = order.iter().filter_map(|m| match m {
+            TransportMessage::Consensus(payload) => Some(payload[0]),
+            _ => None,
+        }).collect();
+        assert_eq!(consensus, vec![0, 3, 6, 9]);
+        assert_eq!(transport.get_queue_depths().await, QueueDepths::default());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_orders_by_connection_quality() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        for (id, quality) in [("flaky", 0.2), ("solid", 0.99), ("average", 0.6), ("good", 0.9)] {
+            let peer = PeerInfo { id: id.to_string(), connection_quality: quality, ..drain_test_peer() };
+            transport.add_peer(peer).await.unwrap();
+        }
+
+        let report = transport.broadcast(TransportMessage::Heartbeat { timestamp: 1 }).await.unwrap();
+        let attempted: Vec<&str> = report.outcomes.iter().map(|o| o.peer_id.as_str()).collect();
+        assert_eq!(attempted, vec!["solid", "good", "average", "flaky"]);
+        assert_eq!(report.delivered(), 4);
+        assert!(report.failed().is_empty());
+
+        // Failures are recorded per peer rather than aborting the broadcast
+        *transport.is_draining.write().await = true;
+        let report = transport.broadcast(TransportMessage::Heartbeat { timestamp: 2 }).await.unwrap();
+        assert_eq!(report.delivered(), 0);
+        assert_eq!(report.failed(), vec!["solid", "good", "average", "flaky"]);
+        assert!(report.outcomes.iter().all(|o| o.error.as_deref() == Some("Network error (shutting down): Transport is shutting down")));
     }
     
     #[tokio::test]
     async fn test_bpci_frame_creation() {
-        let src_cluster_id = [1u8; 16];
-        let dst_cluster_id = [2u8; 16];
-        let svc_id_hash = [3u8; 32];
+        let src_cluster_id = ClusterId::new([1u8; 16]);
+        let dst_cluster_id = ClusterId::new([2u8; 16]);
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
         let nonce = 1;
-        let poh_tick = [4u8; 32];
+        let poh_tick = PohTick::new([4u8; 32]);
         let payload = b"test payload";
-        let aead_key = [5u8; 32];
-        let signing_key = [6u8; 32];
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
 
         let frame = BpciFrame::new(
             src_cluster_id,
@@ -1084,15 +3038,15 @@ mod tests {
 
     #[tokio::test]
     async fn test_bpci_frame_verification() {
-        let src_cluster_id = [1u8; 16];
-        let dst_cluster_id = [2u8; 16];
-        let svc_id_hash = [3u8; 32];
+        let src_cluster_id = ClusterId::new([1u8; 16]);
+        let dst_cluster_id = ClusterId::new([2u8; 16]);
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
         let nonce = 1;
-        let poh_tick = [4u8; 32];
+        let poh_tick = PohTick::new([4u8; 32]);
         let payload = b"test payload";
-        let aead_key = [5u8; 32];
-        let signing_key = [6u8; 32];
-        let public_key = signing_key; // In placeholder implementation
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
+        let public_key = signing_key.verifying_key();
 
         // Create frame
         let frame = BpciFrame::new(
@@ -1122,7 +3076,7 @@ mod tests {
     #[tokio::test]
     async fn test_nonce_replay_protection() {
         let mut nonce_tracker = NonceTracker::new(5);
-        let key = ([1u8; 16], [2u8; 32]);
+        let key = (ClusterId::new([1u8; 16]), ServiceIdHash::new([2u8; 32]));
 
         // First nonce should be valid
         assert!(nonce_tracker.check_nonce(key, 1).unwrap());
@@ -1144,16 +3098,499 @@ mod tests {
         println!("✅ Nonce replay protection working");
     }
 
+    #[test]
+    fn test_nonce_tracker_merge_keeps_higher_nonce() {
+        let shared = (ClusterId::new([1u8; 16]), ServiceIdHash::new([1u8; 32]));
+        let primary_only = (ClusterId::new([2u8; 16]), ServiceIdHash::new([2u8; 32]));
+        let standby_only = (ClusterId::new([3u8; 16]), ServiceIdHash::new([3u8; 32]));
+
+        let mut standby = NonceTracker::new(100);
+        standby.update_nonce(shared, 5);
+        standby.record_frame(shared, 5, [5u8; 32]);
+        standby.update_nonce(standby_only, 3);
+
+        let mut primary = NonceTracker::new(100);
+        primary.update_nonce(shared, 9);
+        primary.record_frame(shared, 9, [9u8; 32]);
+        primary.update_nonce(primary_only, 4);
+
+        standby.merge(&primary);
+        assert_eq!(standby.get_nonce(&shared), Some(9));
+        assert_eq!(standby.get_nonce(&primary_only), Some(4));
+        assert_eq!(standby.get_nonce(&standby_only), Some(3));
+        assert!(standby.check_nonce(shared, 9).is_err());
+        assert!(standby.check_nonce(primary_only, 4).is_err());
+
+        // Only the frame accepted at the merged nonce still counts as a retransmit
+        assert!(standby.is_retransmit(&shared, 9, &[9u8; 32]));
+        assert!(!standby.is_retransmit(&shared, 5, &[5u8; 32]));
+
+        // Merging the other way round yields the same nonces
+        let mut stale = NonceTracker::new(100);
+        stale.update_nonce(shared, 2);
+        stale.merge(&standby);
+        assert_eq!(stale.get_nonce(&shared), Some(9));
+        assert_eq!(stale.get_nonce(&standby_only), Some(3));
+        assert!(stale.is_retransmit(&shared, 9, &[9u8; 32]));
+    }
+
+    #[tokio::test]
+    async fn test_honest_retransmit_flagged_duplicate() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
+        let frame = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 1, PohTick::new([4u8; 32]), b"payload", &aead_key, &signing_key).unwrap();
+
+        let (_, first) = transport.verify_frame(&frame, &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(first.valid);
+        assert!(!first.duplicate);
+
+        // Same frame again after a timeout: accepted but marked as a duplicate
+        let (payload, retransmit) = transport.verify_frame(&frame, &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(retransmit.valid);
+        assert!(retransmit.duplicate);
+        assert_eq!(payload, b"payload");
+
+        // The nonce window still moves forward normally
+        let next = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 2, PohTick::new([4u8; 32]), b"next", &aead_key, &signing_key).unwrap();
+        let (_, result) = transport.verify_frame(&next, &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(result.valid && !result.duplicate);
+    }
+
+    #[tokio::test]
+    async fn test_nonce_reuse_with_new_payload_rejected() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
+        let frame = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 1, PohTick::new([4u8; 32]), b"payload", &aead_key, &signing_key).unwrap();
+        transport.verify_frame(&frame, &signing_key.verifying_key(), &aead_key).await.unwrap();
+
+        let forged = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 1, PohTick::new([4u8; 32]), b"tampered", &aead_key, &signing_key).unwrap();
+        let result = transport.verify_frame(&forged, &signing_key.verifying_key(), &aead_key).await;
+        assert!(matches!(result, Err(BpciError::ReplayAttack(1, 1))));
+    }
+
+    #[tokio::test]
+    async fn test_access_policy_requires_sender_capabilities() {
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
+        let validator_key = SigningKey::new([6u8; 32]);
+        let observer_key = SigningKey::new([8u8; 32]);
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_access_policy(AccessPolicy::new().require(svc_id_hash, &["consensus"]));
+        transport.add_peer(PeerInfo {
+            id: "validator".to_string(),
+            cluster_id: Some(ClusterId::new([1u8; 16])),
+            verifying_key: Some(validator_key.verifying_key()),
+            capabilities: vec!["consensus".to_string()],
+            ..drain_test_peer()
+        }).await.unwrap();
+        transport.add_peer(PeerInfo {
+            id: "observer".to_string(),
+            cluster_id: Some(ClusterId::new([7u8; 16])),
+            verifying_key: Some(observer_key.verifying_key()),
+            capabilities: vec!["poh".to_string()],
+            ..drain_test_peer()
+        }).await.unwrap();
+        let aead_key = AeadKey::new([5u8; 32]);
+        let frame = |src: u8, nonce: u64, svc_id_hash: ServiceIdHash, signing_key: &SigningKey| {
+            BpciFrame::new(ClusterId::new([src; 16]), ClusterId::new([2u8; 16]), svc_id_hash, nonce, PohTick::new([4u8; 32]), b"vote", &aead_key, signing_key).unwrap()
+        };
+
+        let (payload, result) = transport.verify_frame_from("validator", &frame(1, 1, svc_id_hash, &validator_key), &aead_key).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(payload, b"vote");
+
+        // Correctly signed, but the sender does not hold `consensus`
+        let result = transport.verify_frame_from("observer", &frame(7, 1, svc_id_hash, &observer_key), &aead_key).await;
+        assert!(matches!(result, Err(BpciError::AuthenticationFailed(_))));
+
+        // Claiming the validator's cluster does not borrow its capabilities:
+        // the observer's own connection names the wrong cluster, and without a
+        // peer identity the restricted service is closed
+        let impersonation = frame(1, 2, svc_id_hash, &observer_key);
+        let result = transport.verify_frame_from("observer", &impersonation, &aead_key).await;
+        assert!(matches!(result, Err(BpciError::AuthenticationFailed(_))));
+        let result = transport.verify_frame(&impersonation, &observer_key.verifying_key(), &aead_key).await;
+        assert!(matches!(result, Err(BpciError::AuthenticationFailed(_))));
+
+        // Sent in the validator's name, but not signed with its key
+        let (_, result) = transport.verify_frame_from("validator", &impersonation, &aead_key).await.unwrap();
+        assert!(!result.valid);
+        assert!(!result.signature_valid);
+
+        // Services without requirements stay open
+        let open = frame(7, 1, ServiceIdHash::new([9u8; 32]), &observer_key);
+        assert!(transport.verify_frame(&open, &observer_key.verifying_key(), &aead_key).await.unwrap().1.valid);
+    }
+
+    /// Accepts only the ticks it was given
+    #[derive(Debug)]
+    struct KnownPohTicks(HashSet<PohTick>);
+
+    impl PohVerifier for KnownPohTicks {
+        fn is_valid_tick(&self, tick: &PohTick) -> bool {
+            self.0.contains(tick)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_poh_verifier_rejects_unknown_tick() {
+        let known = PohTick::new([4u8; 32]);
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_poh_verifier(KnownPohTicks(HashSet::from([known])));
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
+        let frame = |nonce, poh_tick| {
+            BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), nonce, poh_tick, b"block", &aead_key, &signing_key).unwrap()
+        };
+
+        let (payload, result) = transport.verify_frame(&frame(1, known), &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(payload, b"block");
+
+        let unknown = transport.verify_frame(&frame(2, PohTick::new([9u8; 32])), &signing_key.verifying_key(), &aead_key).await;
+        assert!(matches!(unknown, Err(BpciError::UnknownPohTick(tick)) if tick == hex::encode([9u8; 32])));
+
+        // The rejected frame did not consume its nonce
+        let (_, result) = transport.verify_frame(&frame(2, known), &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(result.valid);
+
+        // Without a verifier every tick passes
+        let permissive = BpciTransport::new(BpciConfig::default()).unwrap();
+        let (_, result) = permissive.verify_frame(&frame(1, PohTick::new([9u8; 32])), &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(result.valid);
+    }
+
+    /// Backend whose first `failures` dials are refused
+    #[derive(Debug)]
+    struct FlakyTransport {
+        failures: u32,
+        dials: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for FlakyTransport {
+        async fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, BpciError> {
+            Ok(addr)
+        }
+
+        async fn dial(&self, addr: SocketAddr) -> Result<(), BpciError> {
+            let attempt = self.dials.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if attempt <= self.failures {
+                return Err(BpciError::network(NetworkErrorKind::ConnectRefused, addr.to_string()));
+            }
+            Ok(())
+        }
+
+        async fn send(&self, _addr: SocketAddr, _data: Vec<u8>) -> Result<(), BpciError> {
+            Ok(())
+        }
+
+        fn take_inbound(&self) -> Option<InboundStream> {
+            None
+        }
+    }
+
+    /// Backend whose first `failures` sends are refused
+    #[derive(Debug)]
+    struct FailingSendTransport {
+        failures: u32,
+        sends: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for FailingSendTransport {
+        async fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, BpciError> {
+            Ok(addr)
+        }
+
+        async fn dial(&self, _addr: SocketAddr) -> Result<(), BpciError> {
+            Ok(())
+        }
+
+        async fn send(&self, addr: SocketAddr, _data: Vec<u8>) -> Result<(), BpciError> {
+            let attempt = self.sends.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if attempt <= self.failures {
+                return Err(BpciError::network(NetworkErrorKind::PeerReset, addr.to_string()));
+            }
+            Ok(())
+        }
+
+        fn take_inbound(&self) -> Option<InboundStream> {
+            None
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failed_send_lands_in_dead_letter_queue() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_dead_letter_capacity(2);
+        let message = TransportMessage::Data { payload: vec![7, 7, 7] };
+
+        assert!(transport.send_to_peer("ghost", message.clone()).await.is_err());
+        assert_eq!(transport.dead_letter_count().await, 1);
+
+        // Oldest is discarded once the queue is full
+        transport.send_to_peer("ghost-2", message.clone()).await.unwrap_err();
+        transport.send_to_peer("ghost-3", message).await.unwrap_err();
+        let letters = transport.drain_dead_letters().await;
+        let peer_ids: Vec<&str> = letters.iter().map(|letter| letter.peer_id.as_str()).collect();
+        assert_eq!(peer_ids, vec!["ghost-2", "ghost-3"]);
+        assert!(letters[0].reason.contains("ghost-2"));
+        assert_eq!(letters[0].attempts, 1);
+        assert!(transport.drain_dead_letters().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_dead_letter_redelivered_on_reconnect() {
+        let sends = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_backend(Box::new(FailingSendTransport { failures: 1, sends: sends.clone() }))
+            .with_dead_letter_retry(DeadLetterRetryPolicy::default());
+        transport.connect_peer(drain_test_peer()).await.unwrap();
+
+        let message = TransportMessage::Data { payload: vec![1, 2, 3] };
+        assert!(transport.send_to_peer("drain-peer", message).await.is_err());
+        assert!(transport.get_peers().await.is_empty());
+        assert_eq!(transport.dead_letter_count().await, 1);
+
+        transport.connect_peer(drain_test_peer()).await.unwrap();
+        assert_eq!(transport.dead_letter_count().await, 0);
+        assert_eq!(sends.load(std::sync::atomic::Ordering::SeqCst), 2);
+        assert_eq!(transport.get_stats().await["drain-peer"].messages_sent, 1);
+    }
+
+    #[tokio::test]
+    async fn test_manual_dead_letter_retry() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap();
+        let message = TransportMessage::Data { payload: vec![4, 5] };
+        transport.send_to_peer("drain-peer", message).await.unwrap_err();
+
+        // Still unknown: kept, with the attempt counted
+        assert_eq!(transport.retry_dead_letters().await.unwrap(), 0);
+        assert_eq!(transport.drain_dead_letters().await[0].attempts, 2);
+
+        transport.send_to_peer("drain-peer", TransportMessage::Data { payload: vec![4, 5] }).await.unwrap_err();
+        // Without a retry policy adding the peer does not redeliver by itself
+        transport.add_peer(drain_test_peer()).await.unwrap();
+        assert_eq!(transport.dead_letter_count().await, 1);
+        assert_eq!(transport.retry_dead_letters().await.unwrap(), 1);
+        assert_eq!(transport.dead_letter_count().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_reliable_frame_acked_and_retransmitted() {
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_retransmit_policy(RetransmitPolicy { timeout: Duration::from_millis(1), max_retries: 1 });
+        let peer_key = SigningKey::new([8u8; 32]);
+        transport.add_peer(PeerInfo { verifying_key: Some(peer_key.verifying_key()), ..drain_test_peer() }).await.unwrap();
+
+        let key = SigningKey::new([9u8; 32]);
+        let frame = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 1, PohTick::new([0u8; 32]), b"commit", &AeadKey::new([5u8; 32]), &key).unwrap();
+        let frame_hash = transport.send_frame_reliable("drain-peer", frame.clone()).await.unwrap();
+        assert_eq!(transport.pending_acks().await, 1);
+        // Only the peer's own key acknowledges the frame
+        assert!(!transport.acknowledge_frame("drain-peer", &FrameAck::sign(&frame, &key).unwrap()).await);
+        assert!(transport.acknowledge_frame("drain-peer", &FrameAck::sign(&frame, &peer_key).unwrap()).await);
+        assert_eq!(transport.pending_acks().await, 0);
+
+        let unacked = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 2, PohTick::new([0u8; 32]), b"commit", &AeadKey::new([5u8; 32]), &key).unwrap();
+        let unacked_hash = transport.send_frame_reliable("drain-peer", unacked).await.unwrap();
+        assert_ne!(unacked_hash, frame_hash);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert!(transport.retransmit_unacked().await.is_empty());
+        assert_eq!(transport.get_stats().await["drain-peer"].messages_sent, 3);
+        tokio::time::sleep(Duration::from_millis(5)).await;
+        assert_eq!(transport.retransmit_unacked().await, vec![unacked_hash]);
+        assert_eq!(transport.pending_acks().await, 0);
+    }
+
+    /// In-memory backend that silently loses its first `losses` sends
+    #[derive(Debug)]
+    struct LossyTransport {
+        inner: InMemoryTransport,
+        losses: u32,
+        sends: Arc<std::sync::atomic::AtomicU32>,
+    }
+
+    #[async_trait::async_trait]
+    impl Transport for LossyTransport {
+        async fn listen(&self, addr: SocketAddr) -> Result<SocketAddr, BpciError> {
+            self.inner.listen(addr).await
+        }
+
+        async fn dial(&self, addr: SocketAddr) -> Result<(), BpciError> {
+            self.inner.dial(addr).await
+        }
+
+        async fn send(&self, addr: SocketAddr, data: Vec<u8>) -> Result<(), BpciError> {
+            let attempt = self.sends.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            if attempt <= self.losses {
+                return Ok(());
+            }
+            self.inner.send(addr, data).await
+        }
+
+        fn take_inbound(&self) -> Option<InboundStream> {
+            self.inner.take_inbound()
+        }
+    }
+
+    #[tokio::test]
+    async fn test_lost_reliable_frame_retransmitted_until_acked() {
+        let network = InMemoryNetwork::new();
+        let addr_a: SocketAddr = "10.0.1.1:7000".parse().unwrap();
+        let addr_b: SocketAddr = "10.0.1.2:7000".parse().unwrap();
+        let key_a = SigningKey::new([6u8; 32]);
+        let key_b = SigningKey::new([8u8; 32]);
+        let sends = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let mut node_a = BpciTransport::new(BpciConfig { bind_address: addr_a, ..BpciConfig::default() }).unwrap()
+            .with_backend(Box::new(LossyTransport { inner: network.transport(), losses: 1, sends: sends.clone() }))
+            .with_retransmit_policy(RetransmitPolicy { timeout: Duration::from_millis(20), max_retries: 10 })
+            .with_signing_key(key_a.clone());
+        let node_b = BpciTransport::new(BpciConfig { bind_address: addr_b, ..BpciConfig::default() }).unwrap()
+            .with_backend(Box::new(network.transport()))
+            .with_signing_key(key_b.clone());
+        node_a.listen().await.unwrap();
+        node_b.listen().await.unwrap();
+        node_a.connect_peer(PeerInfo {
+            id: "node-b".to_string(),
+            address: addr_b,
+            cluster_id: Some(ClusterId::new([2u8; 16])),
+            verifying_key: Some(key_b.verifying_key()),
+            ..drain_test_peer()
+        }).await.unwrap();
+        node_b.connect_peer(PeerInfo {
+            id: "node-a".to_string(),
+            address: addr_a,
+            cluster_id: Some(ClusterId::new([1u8; 16])),
+            verifying_key: Some(key_a.verifying_key()),
+            ..drain_test_peer()
+        }).await.unwrap();
+        node_a.start().await.unwrap();
+
+        let aead_key = AeadKey::new([5u8; 32]);
+        let frame = node_a.send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"commit", &aead_key, &key_a, PohTick::new([4u8; 32])).await.unwrap();
+        let frame_hash = node_a.send_frame_reliable("node-b", frame).await.unwrap();
+        assert_eq!(sends.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // The first copy was lost; B only sees the timer's retransmit, and acks it
+        let (peer_id, message) = tokio::time::timeout(Duration::from_secs(5), node_b.recv_routed()).await.unwrap().unwrap();
+        assert_eq!(peer_id, "node-a");
+        assert!(sends.load(std::sync::atomic::Ordering::SeqCst) >= 2);
+        let received = BpciFrame::from_message(&message).unwrap();
+        assert_eq!(received.hash().unwrap(), frame_hash);
+        let (payload, result) = node_b.verify_frame_from("node-a", &received, &aead_key).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(payload, b"commit");
+
+        // Routing B's ack on A clears the frame
+        let (from, ack) = tokio::time::timeout(Duration::from_secs(5), node_a.recv()).await.unwrap().unwrap();
+        assert!(matches!(&ack, TransportMessage::FrameAck(ack) if ack.frame_hash == frame_hash));
+        assert!(node_a.route_inbound(from, ack).await.is_none());
+        assert_eq!(node_a.pending_acks().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_fragmented_frame_reassembled_on_receive() {
+        let network = InMemoryNetwork::new();
+        let addr_a: SocketAddr = "10.0.2.1:7000".parse().unwrap();
+        let addr_b: SocketAddr = "10.0.2.2:7000".parse().unwrap();
+        let key_a = SigningKey::new([6u8; 32]);
+        let sends = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let node_a = BpciTransport::new(BpciConfig { bind_address: addr_a, ..BpciConfig::default() }).unwrap()
+            .with_backend(Box::new(LossyTransport { inner: network.transport(), losses: 1, sends: sends.clone() }));
+        let node_b = BpciTransport::new(BpciConfig { bind_address: addr_b, ..BpciConfig::default() }).unwrap()
+            .with_backend(Box::new(network.transport()));
+        node_a.listen().await.unwrap();
+        node_b.listen().await.unwrap();
+        node_a.connect_peer(PeerInfo { id: "node-b".to_string(), address: addr_b, ..drain_test_peer() }).await.unwrap();
+        node_b.connect_peer(PeerInfo { id: "node-a".to_string(), address: addr_a, ..drain_test_peer() }).await.unwrap();
+
+        let aead_key = AeadKey::new([5u8; 32]);
+        let frame = node_a.send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"commit", &aead_key, &key_a, PohTick::new([4u8; 32])).await.unwrap();
+        let config = FragmentConfig { max_fragment_size: 64, parity_shards: 1 };
+        // The first fragment is lost; parity makes up for it
+        let count = node_a.send_frame_fragmented("node-b", &frame, config).await.unwrap();
+        assert!(count > 2);
+
+        let (peer_id, message) = tokio::time::timeout(Duration::from_secs(5), node_b.recv_routed()).await.unwrap().unwrap();
+        assert_eq!(peer_id, "node-a");
+        assert_eq!(BpciFrame::from_message(&message).unwrap().hash().unwrap(), frame.hash().unwrap());
+        assert_eq!(node_b.reassembler.lock().await.pending(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_persistent_peer_reconnects_with_backoff() {
+        let dials = Arc::new(std::sync::atomic::AtomicU32::new(0));
+        let transport = BpciTransport::new(BpciConfig::default()).unwrap()
+            .with_backend(Box::new(FlakyTransport { failures: 2, dials: dials.clone() }))
+            .with_reconnect_policy(ReconnectPolicy {
+                initial_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(4),
+                max_attempts: 5,
+            });
+        let peer = PeerInfo { capabilities: vec!["consensus".to_string()], ..drain_test_peer() };
+        transport.add_peer(peer.clone()).await.unwrap();
+        transport.set_peer_persistent("drain-peer", peer.address).await;
+
+        // Repeated drop notices share one reconnect task
+        transport.peer_dropped("drain-peer").await;
+        transport.peer_dropped("drain-peer").await;
+        transport.peer_dropped("drain-peer").await;
+        assert!(transport.get_peers().await.is_empty());
+
+        let restored = tokio::time::timeout(Duration::from_secs(2), async {
+            loop {
+                if let Some(peer) = transport.get_peers().await.pop() {
+                    return peer;
+                }
+                tokio::time::sleep(Duration::from_millis(1)).await;
+            }
+        }).await.expect("peer was not restored");
+        assert_eq!(restored.id, "drain-peer");
+        assert_eq!(restored.capabilities, vec!["consensus".to_string()]);
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(dials.load(std::sync::atomic::Ordering::SeqCst), 3);
+        assert!(transport.get_stats().await.contains_key("drain-peer"));
+        assert!(transport.reconnecting.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_nonce_reservations_unique_and_contiguous() {
+        let transport = Arc::new(BpciTransport::new(BpciConfig::default()).unwrap());
+        let src_cluster_id = ClusterId::new([1u8; 16]);
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
+
+        let handles: Vec<_> = (0..200)
+            .map(|_| {
+                let transport = transport.clone();
+                tokio::spawn(async move { transport.next_nonce(src_cluster_id, svc_id_hash).await })
+            })
+            .collect();
+
+        let mut nonces = Vec::new();
+        for handle in handles {
+            nonces.push(handle.await.unwrap());
+        }
+        nonces.sort_unstable();
+        assert_eq!(nonces, (1..=200).collect::<Vec<u64>>());
+
+        // Frames continue from the reserved sequence
+        let frame = transport.send_frame(ClusterId::new([2u8; 16]), svc_id_hash, b"payload", &AeadKey::new([5u8; 32]), &SigningKey::new([6u8; 32]), PohTick::new([4u8; 32])).await.unwrap();
+        assert_eq!(frame.nonce, 201);
+    }
+
     #[tokio::test]
     async fn test_bpci_frame_hashing() {
-        let src_cluster_id = [1u8; 16];
-        let dst_cluster_id = [2u8; 16];
-        let svc_id_hash = [3u8; 32];
+        let src_cluster_id = ClusterId::new([1u8; 16]);
+        let dst_cluster_id = ClusterId::new([2u8; 16]);
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
         let nonce = 1;
-        let poh_tick = [4u8; 32];
+        let poh_tick = PohTick::new([4u8; 32]);
         let payload = b"test payload";
-        let aead_key = [5u8; 32];
-        let signing_key = [6u8; 32];
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
 
         let frame = BpciFrame::new(
             src_cluster_id,
@@ -1175,18 +3612,124 @@ mod tests {
         println!("✅ BPCI frame hashing working");
     }
 
+    /// Decode `encoded` with `decode`, then assert re-encoding reproduces it byte for byte
+    fn assert_canonical_round_trip<T>(encoded: &[u8], decode: fn(&[u8]) -> Result<T, BpciError>) -> T
+    where
+        T: Serialize,
+    {
+        let decoded = decode(encoded).unwrap();
+        assert_eq!(CanonicalCbor::encode(&decoded).unwrap(), encoded);
+        decoded
+    }
+
+    #[test]
+    fn test_non_canonical_frame_encoding_rejected() {
+        let frame = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 1, PohTick::new([4u8; 32]), b"payload", &AeadKey::new([5u8; 32]), &SigningKey::new([6u8; 32])).unwrap();
+        let canonical = frame.to_canonical_cbor().unwrap();
+        assert_canonical_round_trip(&canonical, BpciFrame::from_canonical_cbor);
+
+        // Same frame without the self-describe tag still decodes loosely, but is not canonical
+        let untagged = serde_cbor::to_vec(&frame).unwrap();
+        assert!(serde_cbor::from_slice::<BpciFrame>(&untagged).is_ok());
+        assert!(matches!(
+            BpciFrame::from_canonical_cbor(&untagged),
+            Err(BpciError::NonCanonicalEncoding("BpciFrame"))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_typed_ids_keep_frame_wire_encoding() {
+        // The frame layout before the ids were typed
+        #[derive(Serialize, Deserialize)]
+        struct RawFrame {
+            version: u8,
+            aead_alg: u8,
+            src_cluster_id: [u8; 16],
+            dst_cluster_id: [u8; 16],
+            svc_id_hash: [u8; 32],
+            nonce: u64,
+            poh_tick: [u8; 32],
+            key_commitment: [u8; 32],
+            payload_ct: Vec<u8>,
+            aead_tag: [u8; 16],
+            #[serde(with = "serde_bytes")]
+            sig_src: Vec<u8>,
+        }
+
+        let sender = BpciTransport::new(BpciConfig::default()).unwrap();
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
+        let frame = sender
+            .send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"payload", &aead_key, &signing_key, PohTick::new([4u8; 32]))
+            .await
+            .unwrap();
+
+        let encoded = frame.to_canonical_cbor().unwrap();
+        let raw: RawFrame = CanonicalCbor::decode(&encoded).unwrap();
+        assert_eq!(raw.src_cluster_id, [1u8; 16]);
+        assert_eq!(raw.dst_cluster_id, [2u8; 16]);
+        assert_eq!(raw.svc_id_hash, [3u8; 32]);
+        assert_eq!(raw.poh_tick, [4u8; 32]);
+        assert_eq!(CanonicalCbor::encode(&raw).unwrap(), encoded);
+
+        let decoded = BpciFrame::from_canonical_cbor(&encoded).unwrap();
+        let receiver = BpciTransport::new(BpciConfig::default()).unwrap();
+        let (payload, result) = receiver.verify_frame(&decoded, &signing_key.verifying_key(), &aead_key).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(payload, b"payload");
+    }
+
+    proptest! {
+        #[test]
+        fn prop_frame_canonical_round_trip_hash_stable(
+            version in any::<u8>(),
+            aead_alg in any::<u8>(),
+            src_cluster_id in any::<[u8; 16]>(),
+            dst_cluster_id in any::<[u8; 16]>(),
+            svc_id_hash in any::<[u8; 32]>(),
+            nonce in any::<u64>(),
+            poh_tick in any::<[u8; 32]>(),
+            key_commitment in any::<[u8; 32]>(),
+            payload_ct in prop::collection::vec(any::<u8>(), 0..512),
+            aead_tag in any::<[u8; 16]>(),
+            sig_src in prop::collection::vec(any::<u8>(), 0..128),
+        ) {
+            let frame = BpciFrame {
+                version,
+                aead_alg,
+                src_cluster_id: src_cluster_id.into(),
+                dst_cluster_id: dst_cluster_id.into(),
+                svc_id_hash: svc_id_hash.into(),
+                nonce,
+                poh_tick: poh_tick.into(),
+                key_commitment,
+                payload_ct,
+                aead_tag,
+                sig_src,
+            };
+
+            let encoded = frame.to_canonical_cbor().unwrap();
+            let decoded = assert_canonical_round_trip(&encoded, BpciFrame::from_canonical_cbor);
+            prop_assert_eq!(decoded.hash().unwrap(), frame.hash().unwrap());
+
+            let header_bytes = frame.header().to_canonical_cbor().unwrap();
+            let header = assert_canonical_round_trip(&header_bytes, BpciFrameHeader::from_canonical_cbor);
+            prop_assert_eq!(header.to_canonical_cbor().unwrap(), decoded.header().to_canonical_cbor().unwrap());
+        }
+    }
+
     #[tokio::test]
     async fn test_transport_frame_methods() {
         let config = BpciConfig::default();
         let transport = BpciTransport::new(config.clone()).unwrap();
         
-        let dst_cluster_id = [2u8; 16];
-        let svc_id_hash = [3u8; 32];
+        let dst_cluster_id = ClusterId::new([2u8; 16]);
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
         let payload = b"test payload";
-        let aead_key = [5u8; 32];
-        let signing_key = [6u8; 32];
-        let public_key = signing_key; // In placeholder implementation
-        let poh_tick = [4u8; 32];
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
+        let public_key = signing_key.verifying_key();
+        let poh_tick = PohTick::new([4u8; 32]);
 
         // Send frame
         let frame = transport.send_frame(
@@ -1208,6 +3751,147 @@ mod tests {
         println!("✅ Transport frame methods working");
     }
 
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_frame_logs_share_correlation_id() {
+        let sender = BpciTransport::new(BpciConfig::default()).unwrap();
+        let frame = sender.send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"payload", &AeadKey::new([5u8; 32]), &SigningKey::new([6u8; 32]), PohTick::new([4u8; 32])).await.unwrap();
+        let receiver = BpciTransport::new(BpciConfig::default()).unwrap();
+        assert!(receiver.verify_frame(&frame, &SigningKey::new([6u8; 32]).verifying_key(), &AeadKey::new([5u8; 32])).await.unwrap().1.valid);
+
+        let id = format!("correlation_id={}", frame.correlation_id());
+        logs_assert(|lines: &[&str]| {
+            let tagged: Vec<&&str> = lines.iter().filter(|line| line.contains(&id)).collect();
+            for event in ["Sent authenticated BPCI frame", "Successfully verified BPCI frame"] {
+                if !tagged.iter().any(|line| line.contains(event)) {
+                    return Err(format!("no {:?} event tagged {}", event, id));
+                }
+            }
+            Ok(())
+        });
+    }
+
+    #[tokio::test]
+    async fn test_frames_round_trip_with_each_aead_algorithm() {
+        let key = SigningKey::new([6u8; 32]);
+        let aead_key = AeadKey::new([5u8; 32]);
+        for &algorithm in AeadAlgorithm::ALL {
+            let config = BpciConfig { aead_algorithm: algorithm, ..BpciConfig::default() };
+            let sender = BpciTransport::new(config.clone()).unwrap();
+            let frame = sender.send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"payload", &aead_key, &key, PohTick::new([4u8; 32])).await.unwrap();
+            assert_eq!(frame.aead_alg, algorithm.id());
+            assert_eq!(AeadAlgorithm::from_id(frame.aead_alg).unwrap(), algorithm);
+
+            let receiver = BpciTransport::new(config).unwrap();
+            let (payload, result) = receiver.verify_frame(&frame, &key.verifying_key(), &aead_key).await.unwrap();
+            assert!(result.valid, "{:?}: {:?}", algorithm, result.error);
+            assert_eq!(payload, b"payload");
+        }
+    }
+
+    #[test]
+    fn test_service_id_hash_normalization() {
+        let hash = service_id_hash("consensus.bpci.mesh");
+        assert_eq!(hash, service_id_hash("consensus.bpci.mesh"));
+        assert_eq!(hash, service_id_hash("  Consensus.BPCI.Mesh\n"));
+        // Absolute and relative forms of the same name
+        assert_eq!(hash, service_id_hash("consensus.bpci.mesh."));
+        // Only one trailing dot is dropped, and inner whitespace is significant
+        assert_ne!(hash, service_id_hash("consensus.bpci.mesh.."));
+        assert_ne!(hash, service_id_hash("consensus .bpci.mesh"));
+        assert_ne!(hash, service_id_hash("poh.bpci.mesh"));
+        // Domain-separated from a plain hash of the name
+        assert_ne!(hash, domain_hash(BPCI_HEADER_HASH, b"consensus.bpci.mesh"));
+    }
+
+    #[test]
+    fn test_constant_time_tag_check_accepts_and_rejects() {
+        let key = [5u8; 32];
+        let ad = b"header";
+        let (ciphertext, tag) = BpciFrame::aead_encrypt(AeadAlgorithm::default(), &key, ad, b"secret").unwrap();
+        let plaintext = BpciFrame::aead_decrypt(AeadAlgorithm::default(), &key, ad, &ciphertext, &tag).unwrap();
+        assert_eq!(plaintext, b"secret");
+
+        // A mismatch in the first or the last byte is rejected the same way
+        for index in [0, 15] {
+            let mut forged = tag;
+            forged[index] ^= 0x01;
+            assert!(matches!(
+                BpciFrame::aead_decrypt(AeadAlgorithm::default(), &key, ad, &ciphertext, &forged),
+                Err(BpciError::AeadError(_))
+            ));
+        }
+
+        assert!(ct_eq(&tag, &tag));
+        assert!(!ct_eq(&tag, &tag[..15]));
+        assert!(!ct_eq(&[], &[0u8]));
+    }
+
+    #[test]
+    fn test_ciphertext_opening_under_two_keys_rejected_by_commitment() {
+        let signing_key = SigningKey::new([6u8; 32]);
+        let key_a = [5u8; 32];
+        let key_b = [7u8; 32];
+        let frame = BpciFrame::new(ClusterId::new([1u8; 16]), ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), 1, PohTick::new([4u8; 32]), b"payload", &AeadKey::new(key_a), &signing_key).unwrap();
+
+        // Craft a tag that also opens under key B, as a non-committing AEAD allows;
+        // the tag is outside the signed header so the signature still holds
+        let mut crafted = frame.clone();
+        crafted.aead_tag = BpciFrame::aead_tag(AeadAlgorithm::default(), &key_b);
+        let header_bytes = crafted.header().to_canonical_cbor().unwrap();
+        assert!(BpciFrame::aead_decrypt(AeadAlgorithm::default(), &key_b, &header_bytes, &crafted.payload_ct, &crafted.aead_tag).is_ok());
+
+        let (_, result) = crafted.verify(&signing_key.verifying_key(), &AeadKey::new(key_b), &mut NonceTracker::new(100)).unwrap();
+        assert!(result.signature_valid);
+        assert!(!result.valid);
+        assert_eq!(result.error.as_deref(), Some("Key commitment mismatch"));
+
+        let (payload, result) = frame.verify(&signing_key.verifying_key(), &AeadKey::new(key_a), &mut NonceTracker::new(100)).unwrap();
+        assert!(result.valid);
+        assert_eq!(payload, b"payload");
+    }
+
+    #[tokio::test]
+    async fn test_frame_with_disabled_aead_algorithm_rejected() {
+        let key = SigningKey::new([6u8; 32]);
+        let aead_key = AeadKey::new([5u8; 32]);
+        let sender = BpciTransport::new(BpciConfig { aead_algorithm: AeadAlgorithm::Aes256Gcm, ..BpciConfig::default() }).unwrap();
+        let frame = sender.send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"payload", &aead_key, &key, PohTick::new([4u8; 32])).await.unwrap();
+
+        let receiver = BpciTransport::new(BpciConfig {
+            permitted_aead_algorithms: vec![AeadAlgorithm::XChaCha20Poly1305],
+            ..BpciConfig::default()
+        }).unwrap();
+        let (payload, result) = receiver.verify_frame(&frame, &key.verifying_key(), &aead_key).await.unwrap();
+        assert!(!result.valid);
+        assert!(payload.is_empty());
+        assert!(result.error.unwrap().contains("Aes256Gcm"));
+
+        // Relabelling the algorithm breaks the signature, and the tag would not open anyway
+        let mut relabelled = frame.clone();
+        relabelled.aead_alg = AeadAlgorithm::XChaCha20Poly1305.id();
+        let (_, result) = receiver.verify_frame(&relabelled, &key.verifying_key(), &aead_key).await.unwrap();
+        assert!(!result.signature_valid);
+
+        let mut unknown = frame;
+        unknown.aead_alg = 0x7f;
+        assert!(matches!(
+            receiver.verify_frame(&unknown, &key.verifying_key(), &aead_key).await,
+            Err(BpciError::UnknownAeadAlgorithm(0x7f))
+        ));
+    }
+
+    #[test]
+    fn test_x25519_private_bytes_zeroed_on_drop() {
+        let pattern = [0xA5u8; 32];
+        let mut key_pair = std::mem::ManuallyDrop::new(X25519KeyPair::from_private_bytes(pattern).unwrap());
+        assert_eq!(key_pair.private_key_bytes, pattern);
+
+        // Run the destructor but keep the storage so the bytes can be inspected
+        unsafe { std::ptr::drop_in_place(&mut *key_pair as *mut X25519KeyPair) };
+        assert_eq!(key_pair.private_key_bytes, [0u8; 32]);
+    }
+
     #[tokio::test]
     async fn test_x25519_key_pair_generation() {
         let key_pair = X25519KeyPair::generate();
@@ -1256,14 +3940,14 @@ mod tests {
     #[tokio::test]
     async fn test_e2e_key_manager() {
         let key_manager = E2EKeyManager::new();
-        let svc_id_hash = [1u8; 32];
+        let svc_id_hash = ServiceIdHash::new([1u8; 32]);
         
         // Register our service key
         let our_public_key = key_manager.register_our_service_key(svc_id_hash).await.unwrap();
         assert_eq!(our_public_key.len(), 32);
         
         // Register the service key (simulating peer registration)
-        key_manager.register_service_key(svc_id_hash, our_public_key).await.unwrap();
+        key_manager.register_service_key(svc_id_hash, X25519PublicKey::from(our_public_key)).await.unwrap();
         
         // Derive sender key
         let sender_result = key_manager.derive_sender_key(svc_id_hash).await.unwrap();
@@ -1275,7 +3959,7 @@ mod tests {
         let receiver_key = key_manager.derive_receiver_key(
             svc_id_hash,
             ephemeral_pk_bytes,
-            [1u8; 16],
+            ClusterId::new([1u8; 16]),
         ).await.unwrap();
         
         // Keys should match (perfect forward secrecy)
@@ -1295,8 +3979,10 @@ mod tests {
         let shared_secret = b"test_shared_secret_32_bytes_long";
         let svc_id_hash = [2u8; 32];
         
-        let key1 = E2EKeyManager::derive_aead_key(shared_secret, &svc_id_hash).unwrap();
-        let key2 = E2EKeyManager::derive_aead_key(shared_secret, &svc_id_hash).unwrap();
+        let salt = [7u8; 32];
+        
+        let key1 = E2EKeyManager::derive_aead_key(shared_secret, &salt, &svc_id_hash, KeyDirection::Send).unwrap();
+        let key2 = E2EKeyManager::derive_aead_key(shared_secret, &salt, &svc_id_hash, KeyDirection::Send).unwrap();
         
         // Same inputs should produce same key
         assert_eq!(key1, key2);
@@ -1304,29 +3990,61 @@ mod tests {
         
         // Different service ID should produce different key
         let different_svc_id = [3u8; 32];
-        let key3 = E2EKeyManager::derive_aead_key(shared_secret, &different_svc_id).unwrap();
+        let key3 = E2EKeyManager::derive_aead_key(shared_secret, &salt, &different_svc_id, KeyDirection::Send).unwrap();
         assert_ne!(key1, key3);
         
         println!("✅ HKDF key derivation working");
     }
 
+    #[tokio::test]
+    async fn test_hkdf_salt_and_direction_separate_keys() {
+        let shared_secret = b"test_shared_secret_32_bytes_long";
+        let svc_id_hash = [2u8; 32];
+        let salt = E2EKeyManager::session_salt(&[4u8; 32], &[5u8; 32]);
+        let key = E2EKeyManager::derive_aead_key(shared_secret, &salt, &svc_id_hash, KeyDirection::Send).unwrap();
+
+        // Another session's public keys give another salt and key
+        let other_salt = E2EKeyManager::session_salt(&[6u8; 32], &[5u8; 32]);
+        assert_ne!(salt, other_salt);
+        let other = E2EKeyManager::derive_aead_key(shared_secret, &other_salt, &svc_id_hash, KeyDirection::Send).unwrap();
+        assert_ne!(key, other);
+
+        // Each direction gets its own key
+        let reply = E2EKeyManager::derive_aead_key(shared_secret, &salt, &svc_id_hash, KeyDirection::Recv).unwrap();
+        assert_ne!(key, reply);
+
+        // A separate receiver, with nothing cached, still agrees with the sender
+        let sender = E2EKeyManager::new();
+        let receiver = E2EKeyManager::new();
+        let svc_id_hash = ServiceIdHash::new(svc_id_hash);
+        let service_public_key = receiver.register_our_service_key(svc_id_hash).await.unwrap();
+        sender.register_service_key(svc_id_hash, X25519PublicKey::from(service_public_key)).await.unwrap();
+        let sent = sender.derive_sender_key(svc_id_hash).await.unwrap();
+        let received = receiver.derive_receiver_key(
+            svc_id_hash,
+            sent.ephemeral_public_key.to_bytes(),
+            ClusterId::new([1u8; 16]),
+        ).await.unwrap();
+        assert_eq!(sent.aead_key, received);
+    }
+
     #[tokio::test]
     async fn test_transport_e2e_key_agreement() {
         let config = BpciConfig::default();
         let transport = BpciTransport::new(config.clone()).unwrap();
         
-        let svc_id_hash = [3u8; 32];
-        let dst_cluster_id = [2u8; 16];
+        let svc_id_hash = ServiceIdHash::new(service_id_hash("consensus.bpci.mesh"));
+        let dst_cluster_id = ClusterId::new([2u8; 16]);
         let payload = b"test e2e payload";
-        let signing_key = [6u8; 32];
-        let public_key = signing_key; // In placeholder implementation
-        let poh_tick = [4u8; 32];
+        let signing_key = SigningKey::new([6u8; 32]);
+        let public_key = signing_key.verifying_key();
+        let poh_tick = PohTick::new([4u8; 32]);
 
         // Register our service key
         let our_public_key = transport.register_our_service_key(svc_id_hash).await.unwrap();
         
         // Register the service key (simulating peer registration)
-        transport.register_service_key(svc_id_hash, our_public_key).await.unwrap();
+        transport.register_service_key(svc_id_hash, X25519PublicKey::from(our_public_key)).await.unwrap();
         
         // Send frame with E2E key agreement
         let (frame, ephemeral_pk) = transport.send_frame_with_e2e(
@@ -1341,7 +4059,7 @@ mod tests {
         let verify_transport = BpciTransport::new(config).unwrap();
         // Copy our static service key from sender transport into verify transport,
         // so the receiver has the matching private key for E2E derivation
-        copy_our_service_key(&transport, &verify_transport, svc_id_hash).await;
+        copy_our_service_key(&transport, &verify_transport, svc_id_hash.into_bytes()).await;
 
         // Verify frame with E2E key agreement on the receiver instance
         let (decrypted_payload, result) = verify_transport.verify_frame_with_e2e(
@@ -1382,14 +4100,14 @@ mod tests {
     async fn test_kci_resistance() {
         // Test Key Compromise Impersonation (KCI) resistance
         let key_manager = E2EKeyManager::new();
-        let svc_id_hash = [4u8; 32];
+        let svc_id_hash = ServiceIdHash::new([4u8; 32]);
         
         // Alice registers her service key
         let alice_public_key = key_manager.register_our_service_key(svc_id_hash).await.unwrap();
         
         // Bob generates his own key pair
         let bob_key_pair = X25519KeyPair::generate();
-        let bob_public_key = bob_key_pair.public_key_bytes();
+        let bob_public_key = bob_key_pair.public_key;
         
         // Register Bob's key as a service
         key_manager.register_service_key(svc_id_hash, bob_public_key).await.unwrap();
@@ -1409,11 +4127,11 @@ mod tests {
     #[tokio::test]
     async fn test_perfect_forward_secrecy() {
         let key_manager = E2EKeyManager::new();
-        let svc_id_hash = [5u8; 32];
+        let svc_id_hash = ServiceIdHash::new([5u8; 32]);
         
         // Register service keys
         let our_public_key = key_manager.register_our_service_key(svc_id_hash).await.unwrap();
-        key_manager.register_service_key(svc_id_hash, our_public_key).await.unwrap();
+        key_manager.register_service_key(svc_id_hash, X25519PublicKey::from(our_public_key)).await.unwrap();
         
         // Derive multiple session keys
         let session1 = key_manager.derive_sender_key(svc_id_hash).await.unwrap();
@@ -1438,10 +4156,10 @@ mod tests {
         
         // Test 1: X25519→HKDF AEAD keys
         let key_manager = E2EKeyManager::new();
-        let svc_id_hash = [1u8; 32];
+        let svc_id_hash = ServiceIdHash::new([1u8; 32]);
         
         let our_public_key = key_manager.register_our_service_key(svc_id_hash).await.unwrap();
-        key_manager.register_service_key(svc_id_hash, our_public_key).await.unwrap();
+        key_manager.register_service_key(svc_id_hash, X25519PublicKey::from(our_public_key)).await.unwrap();
         
         let sender_result = key_manager.derive_sender_key(svc_id_hash).await.unwrap();
         assert_eq!(sender_result.aead_key.len(), 32);
@@ -1470,16 +4188,16 @@ mod tests {
         let config = BpciConfig::default();
         let transport = BpciTransport::new(config.clone()).unwrap();
         
-        let svc_id_hash2 = [2u8; 32];
+        let svc_id_hash2 = ServiceIdHash::new([2u8; 32]);
         let our_key2 = transport.register_our_service_key(svc_id_hash2).await.unwrap();
-        transport.register_service_key(svc_id_hash2, our_key2).await.unwrap();
+        transport.register_service_key(svc_id_hash2, X25519PublicKey::from(our_key2)).await.unwrap();
         
         let (frame, ephemeral_pk) = transport.send_frame_with_e2e(
-            [3u8; 16],
+            ClusterId::new([3u8; 16]),
             svc_id_hash2,
             b"test payload",
-            &[7u8; 32],
-            [8u8; 32],
+            &SigningKey::new([7u8; 32]),
+            PohTick::new([8u8; 32]),
         ).await.unwrap();
         
         assert_eq!(frame.svc_id_hash, svc_id_hash2);
@@ -1518,14 +4236,14 @@ mod tests {
         println!("\n=== Stage 17: BPCI Frame & Header Authentication Exit Criteria ===");
         
         // Test 1: BPCI Frame structure
-        let src_cluster_id = [1u8; 16];
-        let dst_cluster_id = [2u8; 16];
-        let svc_id_hash = [3u8; 32];
+        let src_cluster_id = ClusterId::new([1u8; 16]);
+        let dst_cluster_id = ClusterId::new([2u8; 16]);
+        let svc_id_hash = ServiceIdHash::new([3u8; 32]);
         let nonce = 1;
-        let poh_tick = [4u8; 32];
+        let poh_tick = PohTick::new([4u8; 32]);
         let payload = b"test payload";
-        let aead_key = [5u8; 32];
-        let signing_key = [6u8; 32];
+        let aead_key = AeadKey::new([5u8; 32]);
+        let signing_key = SigningKey::new([6u8; 32]);
 
         let frame = BpciFrame::new(
             src_cluster_id,
@@ -1539,16 +4257,16 @@ mod tests {
         ).unwrap();
         
         assert_eq!(frame.version, 1);
-        assert_eq!(frame.src_cluster_id.len(), 16);
-        assert_eq!(frame.dst_cluster_id.len(), 16);
-        assert_eq!(frame.svc_id_hash.len(), 32);
-        assert_eq!(frame.poh_tick.len(), 32);
+        assert_eq!(frame.src_cluster_id.as_bytes().len(), 16);
+        assert_eq!(frame.dst_cluster_id.as_bytes().len(), 16);
+        assert_eq!(frame.svc_id_hash.as_bytes().len(), 32);
+        assert_eq!(frame.poh_tick.as_bytes().len(), 32);
         assert_eq!(frame.aead_tag.len(), 16);
         assert_eq!(frame.sig_src.len(), 64);
         println!("✅ Test 1: BPCI Frame structure - PASSED");
         
         // Test 2: Header authentication with Ed25519
-        let public_key = signing_key; // In placeholder implementation
+        let public_key = signing_key.verifying_key();
         let mut nonce_tracker = NonceTracker::new(10);
         let (decrypted_payload, result) = frame.verify(&public_key, &aead_key, &mut nonce_tracker).unwrap();
         
@@ -1559,7 +4277,7 @@ mod tests {
         
         // Test 3: Nonce-based replay protection
         let mut tracker = NonceTracker::new(5);
-        let key = ([1u8; 16], [2u8; 32]);
+        let key = (ClusterId::new([1u8; 16]), ServiceIdHash::new([2u8; 32]));
         
         assert!(tracker.check_nonce(key, 1).unwrap());
         tracker.update_nonce(key, 1);
@@ -1645,6 +4363,8 @@ mod tests {
             capabilities: vec!["consensus".to_string()],
             last_seen: 1234567890,
             connection_quality: 0.95,
+            cluster_id: None,
+            verifying_key: None,
         };
         transport.add_peer(peer).await.unwrap();
         let peers = transport.get_peers().await;
@@ -1728,12 +4448,98 @@ pub enum DiscoveryMessage {
     },
 }
 
+/// Domain for the digest a `HealthReport` signature covers
+pub const HEALTH_REPORT_HASH: &str = "BPCI_HEALTH_REPORT";
+
+/// A node's signed view of one service's health, counted by
+/// `BpciMeshCoordinator::report_service_health` toward the reporter quorum
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct HealthReport {
+    pub reporter: String,
+    pub service_id: ServiceId,
+    pub status: HealthStatus,
+    /// Unix time in seconds when the report was made
+    pub reported_at: u64,
+    /// Ed25519 signature by the reporter over all other fields
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl HealthReport {
+    /// Report `status` for `service_id` as `reporter`, signing with its key
+    pub fn sign(reporter: &str, service_id: ServiceId, status: HealthStatus, signing_key: &SigningKey) -> Result<Self, BpciError> {
+        let mut report = Self {
+            reporter: reporter.to_string(),
+            service_id,
+            status,
+            reported_at: unix_timestamp(),
+            signature: Vec::new(),
+        };
+        report.signature = BpciFrame::sign_ed25519(signing_key, &report.digest()?)?.to_vec();
+        Ok(report)
+    }
+
+    /// Check the signature against the reporter's public key
+    pub fn verify(&self, public_key: &VerifyingKey) -> bool {
+        let signature: [u8; 64] = match self.signature.as_slice().try_into() {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        match self.digest() {
+            Ok(digest) => BpciFrame::verify_ed25519(public_key, &digest, &signature).unwrap_or(false),
+            Err(_) => false,
+        }
+    }
+
+    fn digest(&self) -> Result<[u8; 32], BpciError> {
+        let signed = bincode::serialize(&(&self.reporter, &self.service_id, &self.status, self.reported_at))?;
+        Ok(domain_hash(HEALTH_REPORT_HASH, &signed))
+    }
+}
+
+/// A service's effective health moving into or out of `Unknown`
+#[derive(Debug, Clone, PartialEq)]
+pub struct HealthTransition {
+    pub service_id: ServiceId,
+    pub from: HealthStatus,
+    pub to: HealthStatus,
+}
+
+/// Callback invoked for each `HealthTransition`
+#[derive(Clone)]
+pub struct HealthTransitionListener(Arc<dyn Fn(&HealthTransition) + Send + Sync>);
+
+impl HealthTransitionListener {
+    pub fn new(listener: impl Fn(&HealthTransition) + Send + Sync + 'static) -> Self {
+        Self(Arc::new(listener))
+    }
+}
+
+impl std::fmt::Debug for HealthTransitionListener {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HealthTransitionListener")
+    }
+}
+
 /// Health monitoring and heartbeat system
 #[derive(Debug, Clone)]
 pub struct HealthMonitor {
     heartbeat_interval: Duration,
     health_timeout: Duration,
     service_health: Arc<RwLock<HashMap<ServiceId, (HealthStatus, SystemTime)>>>,
+    transition_listener: Option<HealthTransitionListener>,
+    transition_debounce: Duration,
+    // Last effective status seen per service, with any change not yet settled
+    reported_health: Arc<RwLock<HashMap<ServiceId, ReportedHealth>>>,
+}
+
+/// A service's last settled effective status, and a different status seen
+/// since with when it was first seen; it settles once it has held for the
+/// debounce
+#[derive(Debug, Clone)]
+struct ReportedHealth {
+    status: HealthStatus,
+    pending: Option<(HealthStatus, Instant)>,
 }
 
 impl HealthMonitor {
@@ -1742,46 +4548,170 @@ impl HealthMonitor {
             heartbeat_interval,
             health_timeout,
             service_health: Arc::new(RwLock::new(HashMap::new())),
+            transition_listener: None,
+            transition_debounce: Duration::ZERO,
+            reported_health: Arc::new(RwLock::new(HashMap::new())),
         }
     }
 
+    /// Report services going `Unknown` and recovering to `listener`. A new
+    /// status is only reported once it has been seen to hold for `debounce`,
+    /// so a flap that settles back within the debounce is never reported.
+    pub fn with_transition_listener(mut self, listener: HealthTransitionListener, debounce: Duration) -> Self {
+        self.transition_listener = Some(listener);
+        self.transition_debounce = debounce;
+        self
+    }
+
     pub async fn update_health(&self, service_id: ServiceId, status: HealthStatus) {
-        let mut health = self.service_health.write().await;
-        health.insert(service_id, (status, SystemTime::now()));
+        {
+            let mut health = self.service_health.write().await;
+            health.insert(service_id, (status, SystemTime::now()));
+        }
+        self.check_transitions().await;
     }
 
-    pub async fn get_health(&self, service_id: &ServiceId) -> HealthStatus {
-        let health = self.service_health.read().await;
-        if let Some((status, last_update)) = health.get(service_id) {
-            if last_update.elapsed().unwrap_or(Duration::MAX) > self.health_timeout {
-                HealthStatus::Unknown
-            } else {
-                status.clone()
+    /// Change a monitored service's status without counting it as alive, so
+    /// a service only reported on still times out. Returns false if the
+    /// service is not being monitored.
+    pub async fn set_status(&self, service_id: &ServiceId, status: HealthStatus) -> bool {
+        let found = {
+            let mut health = self.service_health.write().await;
+            match health.get_mut(service_id) {
+                Some((current, _)) => {
+                    *current = status;
+                    true
+                }
+                None => false,
             }
-        } else {
+        };
+        if found {
+            self.check_transitions().await;
+        }
+        found
+    }
+
+    /// Refresh a service's liveness without changing its reported status.
+    /// Returns false if the service is not being monitored.
+    pub async fn heartbeat(&self, service_id: &ServiceId) -> bool {
+        let found = {
+            let mut health = self.service_health.write().await;
+            match health.get_mut(service_id) {
+                Some((_, last_update)) => {
+                    *last_update = SystemTime::now();
+                    true
+                }
+                None => false,
+            }
+        };
+        if found {
+            self.check_transitions().await;
+        }
+        found
+    }
+
+    fn effective_status(&self, status: &HealthStatus, last_update: &SystemTime) -> HealthStatus {
+        if last_update.elapsed().unwrap_or(Duration::MAX) > self.health_timeout {
             HealthStatus::Unknown
+        } else {
+            status.clone()
+        }
+    }
+
+    /// Compare every service's effective status with the last one seen and
+    /// report (and return) transitions into or out of `Unknown`. Called on
+    /// each update and heartbeat; stale services only go `Unknown` with time,
+    /// so the coordinator also calls this on every monitoring tick.
+    pub async fn check_transitions(&self) -> Vec<HealthTransition> {
+        let mut transitions = Vec::new();
+        {
+            let health = self.service_health.read().await;
+            let mut reported = self.reported_health.write().await;
+            for (service_id, (status, last_update)) in health.iter() {
+                let current = self.effective_status(status, last_update);
+                let entry = match reported.get_mut(service_id) {
+                    Some(entry) => entry,
+                    // First sighting is the baseline, not a transition
+                    None => {
+                        reported.insert(service_id.clone(), ReportedHealth { status: current, pending: None });
+                        continue;
+                    }
+                };
+                if entry.status == current {
+                    entry.pending = None;
+                    continue;
+                }
+                let pending_since = match &entry.pending {
+                    Some((pending, since)) if *pending == current => *since,
+                    _ => Instant::now(),
+                };
+                if pending_since.elapsed() < self.transition_debounce {
+                    entry.pending = Some((current, pending_since));
+                    continue;
+                }
+                let previous = std::mem::replace(&mut entry.status, current.clone());
+                entry.pending = None;
+                if (previous == HealthStatus::Unknown) != (current == HealthStatus::Unknown) {
+                    transitions.push(HealthTransition {
+                        service_id: service_id.clone(),
+                        from: previous,
+                        to: current,
+                    });
+                }
+            }
+        }
+
+        if let Some(listener) = &self.transition_listener {
+            for transition in &transitions {
+                (listener.0)(transition);
+            }
+        }
+        transitions
+    }
+
+    /// When the next heartbeat from a service is expected
+    pub async fn next_heartbeat_due(&self, service_id: &ServiceId) -> Option<SystemTime> {
+        let health = self.service_health.read().await;
+        health.get(service_id).map(|(_, last_update)| *last_update + self.heartbeat_interval)
+    }
+
+    pub async fn remove(&self, service_id: &ServiceId) {
+        self.service_health.write().await.remove(service_id);
+        self.reported_health.write().await.remove(service_id);
+    }
+
+    pub async fn get_health(&self, service_id: &ServiceId) -> HealthStatus {
+        let health = self.service_health.read().await;
+        match health.get(service_id) {
+            Some((status, last_update)) => self.effective_status(status, last_update),
+            None => HealthStatus::Unknown,
         }
     }
 
     pub async fn cleanup_stale_services(&self) -> Vec<ServiceId> {
-        let mut health = self.service_health.write().await;
         let mut stale_services = Vec::new();
-        
-        health.retain(|service_id, (_, last_update)| {
-            if last_update.elapsed().unwrap_or(Duration::MAX) > self.health_timeout {
-                stale_services.push(service_id.clone());
-                false
-            } else {
-                true
-            }
-        });
-        
+        {
+            let mut health = self.service_health.write().await;
+            health.retain(|service_id, (_, last_update)| {
+                if last_update.elapsed().unwrap_or(Duration::MAX) > self.health_timeout {
+                    stale_services.push(service_id.clone());
+                    false
+                } else {
+                    true
+                }
+            });
+        }
+
+        let mut reported = self.reported_health.write().await;
+        for service_id in &stale_services {
+            reported.remove(service_id);
+        }
         stale_services
     }
 }
 
 /// Service discovery protocol implementation
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct DiscoveryProtocol {
     transport: Arc<BpciTransport>,
     discovery_port: u16,
@@ -1801,7 +4731,8 @@ impl DiscoveryProtocol {
         let payload = vec![]; // Placeholder for now
         
         let transport_message = TransportMessage::Data { payload };
-        self.transport.broadcast(transport_message).await
+        self.transport.broadcast(transport_message).await?;
+        Ok(())
     }
 
     pub async fn query_services(&self, capability_filter: Option<String>) -> Result<Vec<ServiceInfo>> {
@@ -1817,19 +4748,46 @@ impl DiscoveryProtocol {
         let payload = vec![]; // Placeholder for now
         
         let transport_message = TransportMessage::Data { payload };
-        self.transport.broadcast(transport_message).await
+        self.transport.broadcast(transport_message).await?;
+        Ok(())
     }
 }
 
-/// BPCI Mesh Coordinator - Central service registry and coordination
-#[derive(Debug)]
+/// BPCI Mesh Coordinator - Central service registry and coordination.
+/// Clones share the registry and health state.
+#[derive(Debug, Clone)]
 pub struct BpciMeshCoordinator {
     service_registry: Arc<RwLock<HashMap<ServiceId, ServiceInfo>>>,
     health_monitor: HealthMonitor,
     discovery_protocol: DiscoveryProtocol,
     coordinator_config: MeshCoordinatorConfig,
+    round_robin_cursors: Arc<RwLock<HashMap<String, usize>>>,
+    /// Latest health report per service from each reporting node
+    health_reports: Arc<RwLock<HashMap<ServiceId, HashMap<String, ReceivedHealthReport>>>>,
+    /// Public keys of the nodes whose health reports are counted, by reporter id
+    health_reporters: Arc<RwLock<HashMap<String, VerifyingKey>>>,
+    /// This node's reporter id and key, used to report probe results
+    local_reporter: Option<(String, SigningKey)>,
+    /// When a failed probe of each service was last logged as a warning
+    probe_warnings: Arc<Mutex<HashMap<ServiceId, Instant>>>,
+    /// Registrations refused because the registry was full
+    registry_full_rejections: Arc<AtomicU64>,
+}
+
+/// A reporter's latest accepted view of one service
+#[derive(Debug, Clone)]
+struct ReceivedHealthReport {
+    status: HealthStatus,
+    reported_at: u64,
+    received_at: Instant,
 }
 
+/// Least time between warnings about the same service failing its probes
+pub const PROBE_WARNING_INTERVAL: Duration = Duration::from_secs(300);
+
+/// Fraction of `max_services` at which the registry warns that it is nearly full
+pub const REGISTRY_HIGH_WATERMARK: f64 = 0.9;
+
 /// Configuration for the mesh coordinator
 #[derive(Debug, Clone)]
 pub struct MeshCoordinatorConfig {
@@ -1838,6 +4796,16 @@ pub struct MeshCoordinatorConfig {
     pub discovery_port: u16,
     pub max_services: usize,
     pub enable_load_balancing: bool,
+    /// Reporters that must agree before `report_service_health` changes a status
+    pub min_health_reporters: usize,
+    /// Probe every registered endpoint on each monitoring tick instead of
+    /// relying only on self-reported health. Probe results are reported as
+    /// the local reporter's, so `start` only probes with one configured.
+    pub active_probing: bool,
+    /// How long `probe_service` waits for an endpoint to accept a connection
+    pub probe_timeout: Duration,
+    /// Endpoints probed at once on each monitoring tick
+    pub max_concurrent_probes: usize,
 }
 
 impl Default for MeshCoordinatorConfig {
@@ -1848,6 +4816,10 @@ impl Default for MeshCoordinatorConfig {
             discovery_port: 21000,
             max_services: 1000,
             enable_load_balancing: true,
+            min_health_reporters: 1,
+            active_probing: false,
+            probe_timeout: Duration::from_secs(2),
+            max_concurrent_probes: 32,
         }
     }
 }
@@ -1862,26 +4834,52 @@ impl BpciMeshCoordinator {
             health_monitor,
             discovery_protocol,
             coordinator_config: config,
+            round_robin_cursors: Arc::new(RwLock::new(HashMap::new())),
+            health_reports: Arc::new(RwLock::new(HashMap::new())),
+            health_reporters: Arc::new(RwLock::new(HashMap::new())),
+            local_reporter: None,
+            probe_warnings: Arc::new(Mutex::new(HashMap::new())),
+            registry_full_rejections: Arc::new(AtomicU64::new(0)),
         }
     }
 
+    /// Report services going `Unknown` and recovering; see `HealthMonitor::with_transition_listener`
+    pub fn with_health_transition_listener(mut self, listener: HealthTransitionListener, debounce: Duration) -> Self {
+        self.health_monitor = self.health_monitor.with_transition_listener(listener, debounce);
+        self
+    }
+
+    /// Report this node's probe results as `reporter`, signed with `signing_key`.
+    /// They count toward the health quorum like any other reporter's, but only
+    /// a successful probe counts as the service being alive. Without a local
+    /// reporter, probes change no status.
+    pub fn with_local_reporter(mut self, reporter: &str, signing_key: SigningKey) -> Self {
+        self.local_reporter = Some((reporter.to_string(), signing_key));
+        self
+    }
+
     /// Register a service in the mesh
     pub async fn register_service(&self, service_info: ServiceInfo) -> Result<()> {
-        // Check capacity
-        {
-            let registry = self.service_registry.read().await;
-            if registry.len() >= self.coordinator_config.max_services {
-                return Err(BpciError::Network("Service registry at capacity".to_string()).into());
-            }
-        }
-
-        // Register service
+        // Check capacity and register under one lock
         {
             let mut registry = self.service_registry.write().await;
+            let max = self.coordinator_config.max_services;
+            let current = registry.len();
+            if current >= max {
+                self.registry_full_rejections.fetch_add(1, Ordering::Relaxed);
+                warn!("Rejected service {:?}: registry full at {} services", service_info.service_id, max);
+                return Err(BpciError::RegistryFull { current, max }.into());
+            }
             registry.insert(service_info.service_id.clone(), service_info.clone());
+
+            let high_watermark = (max as f64 * REGISTRY_HIGH_WATERMARK).ceil() as usize;
+            if current < high_watermark && registry.len() >= high_watermark {
+                warn!("Service registry above high watermark: {} of {} services", registry.len(), max);
+            }
         }
 
-        // Update health status
+        // Update health status; this also starts the heartbeat schedule, so the
+        // service must heartbeat every `heartbeat_interval` to stay registered
         self.health_monitor.update_health(
             service_info.service_id.clone(),
             service_info.health_status.clone()
@@ -1900,11 +4898,44 @@ impl BpciMeshCoordinator {
             let mut registry = self.service_registry.write().await;
             registry.remove(service_id);
         }
+        self.health_monitor.remove(service_id).await;
+        self.health_reports.write().await.remove(service_id);
 
         info!("Service deregistered from mesh: {:?}", service_id);
         Ok(())
     }
 
+    /// Record a liveness heartbeat from a registered service
+    pub async fn heartbeat(&self, service_id: &ServiceId) -> Result<()> {
+        {
+            let mut registry = self.service_registry.write().await;
+            let service = registry.get_mut(service_id)
+                .ok_or_else(|| BpciError::network(NetworkErrorKind::UnknownService, format!("Unknown service: {:?}", service_id)))?;
+            service.last_heartbeat = SystemTime::now();
+        }
+
+        if !self.health_monitor.heartbeat(service_id).await {
+            return Err(BpciError::network(NetworkErrorKind::UnknownService, format!("Service not monitored: {:?}", service_id)).into());
+        }
+        debug!("Heartbeat from service: {:?}", service_id);
+        Ok(())
+    }
+
+    /// Evict services that have gone `health_timeout` without a heartbeat or health update
+    pub async fn cleanup_stale_services(&self) -> Vec<ServiceId> {
+        let stale_services = self.health_monitor.cleanup_stale_services().await;
+        if !stale_services.is_empty() {
+            let mut registry = self.service_registry.write().await;
+            let mut reports = self.health_reports.write().await;
+            for service_id in &stale_services {
+                registry.remove(service_id);
+                reports.remove(service_id);
+                warn!("Removed stale service: {:?}", service_id);
+            }
+        }
+        stale_services
+    }
+
     /// Get all registered services
     pub async fn get_services(&self) -> Vec<ServiceInfo> {
         let registry = self.service_registry.read().await;
@@ -1922,6 +4953,72 @@ impl BpciMeshCoordinator {
             .collect()
     }
 
+    /// Get services whose capability of the given type carries every required parameter
+    pub async fn get_services_matching(
+        &self,
+        capability_type: &str,
+        required_params: &HashMap<String, String>,
+    ) -> Vec<ServiceInfo> {
+        let registry = self.service_registry.read().await;
+        registry.values()
+            .filter(|service| {
+                service.capabilities.iter().any(|cap| {
+                    cap.capability_type == capability_type
+                        && required_params.iter().all(|(key, value)| cap.parameters.get(key) == Some(value))
+                })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Select one endpoint for a capability, skipping unhealthy and unknown services.
+    /// With load balancing enabled, successive calls rotate round-robin over the
+    /// eligible instances; otherwise the first eligible instance is returned.
+    pub async fn select_endpoint(&self, capability_type: &str) -> Option<ServiceInfo> {
+        let mut candidates = self.eligible_instances(capability_type).await;
+        if candidates.is_empty() {
+            return None;
+        }
+
+        // Registry order is arbitrary, so rotate over a stable ordering
+        candidates.sort_by(|a, b| {
+            (&a.service_id.name, &a.service_id.version, &a.service_id.instance_id)
+                .cmp(&(&b.service_id.name, &b.service_id.version, &b.service_id.instance_id))
+        });
+
+        if !self.coordinator_config.enable_load_balancing {
+            return candidates.into_iter().next();
+        }
+
+        let mut cursors = self.round_robin_cursors.write().await;
+        let cursor = cursors.entry(capability_type.to_string()).or_insert(0);
+        let selected = candidates.swap_remove(*cursor % candidates.len());
+        *cursor = cursor.wrapping_add(1);
+        Some(selected)
+    }
+
+    /// Consistent-hash ring over the healthy or degraded instances of a capability
+    pub async fn hash_ring(&self, capability_type: &str) -> ConsistentHashRing {
+        ConsistentHashRing::new(self.eligible_instances(capability_type).await)
+    }
+
+    /// Sticky selection: the same `key` (e.g. a client id) reaches the same
+    /// instance for as long as that instance stays eligible
+    pub async fn route_sticky(&self, capability_type: &str, key: &str) -> Option<ServiceInfo> {
+        self.hash_ring(capability_type).await.route(key)
+    }
+
+    async fn eligible_instances(&self, capability_type: &str) -> Vec<ServiceInfo> {
+        let mut eligible = Vec::new();
+        for service in self.get_services_by_capability(capability_type).await {
+            match self.health_monitor.get_health(&service.service_id).await {
+                HealthStatus::Healthy | HealthStatus::Degraded => eligible.push(service),
+                HealthStatus::Unhealthy | HealthStatus::Unknown => {}
+            }
+        }
+        eligible
+    }
+
     /// Get service health status
     pub async fn get_service_health(&self, service_id: &ServiceId) -> HealthStatus {
         self.health_monitor.get_health(service_id).await
@@ -1933,6 +5030,175 @@ impl BpciMeshCoordinator {
         self.discovery_protocol.send_health_update(service_id, status).await
     }
 
+    /// Actively check a registered service by connecting to its endpoint, and
+    /// report what was seen as this node's view of its health. A refused or
+    /// timed-out connection is `Unhealthy`; an accepted one is `Healthy`, or a
+    /// self-reported `Degraded` that stays so. Returns the status the probe
+    /// saw, which only becomes authoritative through the reporter quorum.
+    /// Unregistered services are `Unknown`.
+    pub async fn probe_service(&self, service_id: &ServiceId) -> HealthStatus {
+        let endpoint = match self.service_registry.read().await.get(service_id) {
+            Some(service) => service.endpoint,
+            None => return HealthStatus::Unknown,
+        };
+        self.probe_endpoint(service_id, endpoint).await
+    }
+
+    async fn probe_endpoint(&self, service_id: &ServiceId, endpoint: SocketAddr) -> HealthStatus {
+        let reachable = matches!(
+            tokio::time::timeout(self.coordinator_config.probe_timeout, tokio::net::TcpStream::connect(endpoint)).await,
+            Ok(Ok(_))
+        );
+        if reachable {
+            self.probe_warnings.lock().await.remove(service_id);
+        } else {
+            self.log_probe_failure(service_id, endpoint).await;
+        }
+        let status = match (reachable, self.health_monitor.get_health(service_id).await) {
+            (true, HealthStatus::Degraded) => HealthStatus::Degraded,
+            (true, _) => HealthStatus::Healthy,
+            (false, _) => HealthStatus::Unhealthy,
+        };
+        self.report_probe(service_id, status.clone(), reachable).await;
+        status
+    }
+
+    // Warn about the first failed probe of a service, then at most once per
+    // `PROBE_WARNING_INTERVAL` for as long as it stays unreachable
+    async fn log_probe_failure(&self, service_id: &ServiceId, endpoint: SocketAddr) {
+        let mut warned = self.probe_warnings.lock().await;
+        if warned.get(service_id).is_some_and(|last| last.elapsed() < PROBE_WARNING_INTERVAL) {
+            debug!("Probe of service {:?} at {} failed", service_id, endpoint);
+            return;
+        }
+        warn!("Probe of service {:?} at {} failed", service_id, endpoint);
+        warned.insert(service_id.clone(), Instant::now());
+    }
+
+    // Feed a probe result into the reporter quorum as this node's report. A
+    // failed probe must not keep a dead service from timing out.
+    async fn report_probe(&self, service_id: &ServiceId, status: HealthStatus, reachable: bool) {
+        let Some((reporter, signing_key)) = &self.local_reporter else {
+            return;
+        };
+        let report = match HealthReport::sign(reporter, service_id.clone(), status, signing_key) {
+            Ok(report) => report,
+            Err(e) => {
+                warn!("Failed to sign probe report for service {:?}: {}", service_id, e);
+                return;
+            }
+        };
+        if let Err(e) = self.record_health_report(report, reachable).await {
+            debug!("Probe report for service {:?} not counted: {}", service_id, e);
+        }
+    }
+
+    // Probe every registered service, at most `max_concurrent_probes` at a time
+    async fn probe_all(&self) {
+        let targets: Vec<(ServiceId, SocketAddr)> = self.service_registry.read().await.values()
+            .map(|service| (service.service_id.clone(), service.endpoint))
+            .collect();
+        {
+            // Forget warnings about services that are gone
+            let registered: HashSet<&ServiceId> = targets.iter().map(|(service_id, _)| service_id).collect();
+            self.probe_warnings.lock().await.retain(|service_id, _| registered.contains(service_id));
+        }
+        futures::stream::iter(targets)
+            .for_each_concurrent(self.coordinator_config.max_concurrent_probes.max(1), |(service_id, endpoint)| async move {
+                self.probe_endpoint(&service_id, endpoint).await;
+            })
+            .await;
+    }
+
+    /// Count health reports from `reporter` when they verify against `public_key`
+    pub async fn register_health_reporter(&self, reporter: &str, public_key: VerifyingKey) {
+        self.health_reporters.write().await.insert(reporter.to_string(), public_key);
+    }
+
+    /// Record a reporter's signed view of a service's health. The authoritative
+    /// status only changes, and is only gossiped to the mesh, once a strict
+    /// majority of reporters with a report younger than `health_timeout` agree
+    /// on it, and at least `min_health_reporters` of them. Reports must be
+    /// signed by a registered reporter and name a registered service; reports
+    /// older than `health_timeout`, or than the one held from their reporter,
+    /// are ignored. Returns the new status if it changed.
+    pub async fn report_service_health(&self, report: HealthReport) -> Result<Option<HealthStatus>> {
+        self.record_health_report(report, true).await
+    }
+
+    // `report_service_health`, where an agreed status refreshes the service's
+    // liveness only if `shows_alive`
+    async fn record_health_report(&self, report: HealthReport, shows_alive: bool) -> Result<Option<HealthStatus>> {
+        if !self.service_registry.read().await.contains_key(&report.service_id) {
+            return Err(BpciError::network(
+                NetworkErrorKind::UnknownService,
+                format!("Health report for unknown service: {:?}", report.service_id),
+            ).into());
+        }
+        let local_key = self.local_reporter.as_ref()
+            .filter(|(reporter, _)| *reporter == report.reporter)
+            .map(|(_, signing_key)| signing_key.verifying_key());
+        let public_key = match local_key {
+            Some(public_key) => public_key,
+            None => self.health_reporters.read().await.get(&report.reporter).copied()
+                .ok_or_else(|| BpciError::AuthenticationFailed(format!("Unknown health reporter {}", report.reporter)))?,
+        };
+        if !report.verify(&public_key) {
+            return Err(BpciError::InvalidSignature(format!("health report not signed by reporter {}", report.reporter)).into());
+        }
+
+        let health_timeout = self.coordinator_config.health_timeout;
+        if unix_timestamp().abs_diff(report.reported_at) > health_timeout.as_secs() {
+            debug!("Ignoring health report from {} made at {}", report.reporter, report.reported_at);
+            return Ok(None);
+        }
+        let HealthReport { reporter, service_id, status, reported_at, .. } = report;
+        let agreed = {
+            let mut reports = self.health_reports.write().await;
+            let service_reports = reports.entry(service_id.clone()).or_default();
+            if service_reports.get(&reporter).is_some_and(|held| held.reported_at > reported_at) {
+                debug!("Ignoring health report from {} older than the one held", reporter);
+                return Ok(None);
+            }
+            service_reports.insert(reporter, ReceivedHealthReport { status, reported_at, received_at: Instant::now() });
+            service_reports.retain(|_, held| held.received_at.elapsed() <= health_timeout);
+            Self::health_quorum(service_reports, self.coordinator_config.min_health_reporters)
+        };
+
+        let Some(agreed) = agreed else {
+            return Ok(None);
+        };
+        if agreed == self.get_service_health(&service_id).await {
+            // Agreement on the current status still counts as liveness
+            if shows_alive {
+                self.health_monitor.update_health(service_id, agreed).await;
+            }
+            return Ok(None);
+        }
+        info!("Reporters agree service {:?} is {:?}", service_id, agreed);
+        if shows_alive {
+            self.update_service_health(service_id, agreed.clone()).await?;
+        } else {
+            self.health_monitor.set_status(&service_id, agreed.clone()).await;
+            self.discovery_protocol.send_health_update(service_id, agreed.clone()).await?;
+        }
+        Ok(Some(agreed))
+    }
+
+    // Status held by a strict majority of `reports`, if at least `min_reporters` hold it
+    fn health_quorum(reports: &HashMap<String, ReceivedHealthReport>, min_reporters: usize) -> Option<HealthStatus> {
+        let mut tally: Vec<(&HealthStatus, usize)> = Vec::new();
+        for ReceivedHealthReport { status, .. } in reports.values() {
+            match tally.iter_mut().find(|(counted, _)| *counted == status) {
+                Some((_, count)) => *count += 1,
+                None => tally.push((status, 1)),
+            }
+        }
+        tally.into_iter()
+            .find(|(_, count)| *count * 2 > reports.len() && *count >= min_reporters)
+            .map(|(status, _)| status.clone())
+    }
+
     /// Get mesh statistics
     pub async fn get_mesh_stats(&self) -> MeshStats {
         let registry = self.service_registry.read().await;
@@ -1958,6 +5224,7 @@ impl BpciMeshCoordinator {
             degraded_services,
             unhealthy_services,
             unknown_services,
+            registry_full_rejections: self.registry_full_rejections.load(Ordering::Relaxed),
         }
     }
 
@@ -1965,24 +5232,32 @@ impl BpciMeshCoordinator {
     pub async fn start(&self) -> Result<()> {
         info!("Starting BPCI Mesh Coordinator on port {}", self.coordinator_config.discovery_port);
         
+        // Probes only count as the local reporter's reports
+        let probing = self.coordinator_config.active_probing && self.local_reporter.is_some();
+        if self.coordinator_config.active_probing && !probing {
+            warn!("Active probing needs a local reporter; not probing");
+        }
+
         // Start health monitoring task
-        let health_monitor = self.health_monitor.clone();
-        let service_registry = self.service_registry.clone();
+        let coordinator = self.clone();
         
         tokio::spawn(async move {
-            let mut interval = tokio::time::interval(Duration::from_secs(60));
+            // Check once per expected heartbeat so eviction lags the timeout by at most one interval
+            let mut interval = tokio::time::interval(coordinator.coordinator_config.heartbeat_interval);
             loop {
                 interval.tick().await;
-                
-                // Cleanup stale services
-                let stale_services = health_monitor.cleanup_stale_services().await;
-                if !stale_services.is_empty() {
-                    let mut registry = service_registry.write().await;
-                    for service_id in stale_services {
-                        registry.remove(&service_id);
-                        warn!("Removed stale service: {:?}", service_id);
-                    }
+
+                // Services that answer a probe count as alive even without a
+                // heartbeat; failed probes are reported but keep nothing alive
+                if probing {
+                    coordinator.probe_all().await;
                 }
+
+                // Report services that went quiet before they are evicted
+                coordinator.health_monitor.check_transitions().await;
+
+                // Cleanup services that missed heartbeats for longer than the health timeout
+                coordinator.cleanup_stale_services().await;
             }
         });
 
@@ -1999,16 +5274,29 @@ pub struct MeshStats {
     pub degraded_services: usize,
     pub unhealthy_services: usize,
     pub unknown_services: usize,
+    /// Registrations refused since startup because the registry was full
+    #[serde(default)]
+    pub registry_full_rejections: u64,
 }
 
+pub mod backend;
 pub mod cluster_registration;
+pub mod fragment;
+pub mod hash_ring;
+pub mod ids;
+pub mod reliability;
 pub mod economic_integration;
 pub mod server;
 
 // Phase 1: BPCI Block Creator for v1.0 blockchain pipeline
 pub mod block_creator;
 
+pub use backend::{Inbound, InboundStream, InMemoryNetwork, InMemoryTransport, TcpTransport, Transport};
 pub use cluster_registration::*;
+pub use fragment::{fragment_frame, FragmentConfig, FrameFragment, FrameReassembler, ReassemblyLimits};
+pub use hash_ring::ConsistentHashRing;
+pub use ids::{AeadKey, ClusterId, PohTick, ServiceIdHash, SigningKey, VerifyingKey};
+pub use reliability::{AckTracker, FrameAck, RetransmitAction, RetransmitPolicy};
 pub use economic_integration::*;
 pub mod unified_api;
 pub mod validator_roles;
@@ -2030,6 +5318,10 @@ mod mesh_coordinator_tests {
             heartbeat_interval: Duration::from_secs(30),
             message_buffer_size: 1024,
             enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
         };
         
         let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
@@ -2050,6 +5342,10 @@ mod mesh_coordinator_tests {
             heartbeat_interval: Duration::from_secs(30),
             message_buffer_size: 1024,
             enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
         };
         
         let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
@@ -2095,6 +5391,10 @@ mod mesh_coordinator_tests {
             heartbeat_interval: Duration::from_secs(30),
             message_buffer_size: 1024,
             enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
         };
         
         let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
@@ -2149,6 +5449,486 @@ mod mesh_coordinator_tests {
         println!("✅ Service discovery by capability working");
     }
 
+    fn parameterized_service(name: &str, port: u16, params: &[(&str, &str)]) -> ServiceInfo {
+        ServiceInfo {
+            service_id: ServiceId {
+                name: name.to_string(),
+                version: "1.0.0".to_string(),
+                instance_id: format!("{}-1", name),
+            },
+            endpoint: format!("127.0.0.1:{}", port).parse().unwrap(),
+            capabilities: vec![ServiceCapability {
+                capability_type: "storage".to_string(),
+                parameters: params.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+            }],
+            health_status: HealthStatus::Healthy,
+            last_heartbeat: SystemTime::now(),
+            metadata: HashMap::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_service_discovery_by_parameters() {
+        let bpci_config = BpciConfig {
+            bind_address: "127.0.0.1:21006".parse().unwrap(),
+            max_connections: 100,
+            connection_timeout: Duration::from_secs(30),
+            heartbeat_interval: Duration::from_secs(30),
+            message_buffer_size: 1024,
+            enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
+        };
+
+        let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
+        let coordinator = BpciMeshCoordinator::new(transport, MeshCoordinatorConfig::default());
+
+        coordinator.register_service(parameterized_service("exact", 8081, &[("region", "eu")])).await.unwrap();
+        coordinator.register_service(parameterized_service("superset", 8082, &[("region", "eu"), ("tier", "ssd")])).await.unwrap();
+        coordinator.register_service(parameterized_service("other-region", 8083, &[("region", "us")])).await.unwrap();
+        coordinator.register_service(parameterized_service("unlabelled", 8084, &[])).await.unwrap();
+
+        let required: HashMap<String, String> = [("region".to_string(), "eu".to_string())].into_iter().collect();
+        let mut names: Vec<String> = coordinator.get_services_matching("storage", &required).await
+            .into_iter()
+            .map(|service| service.service_id.name)
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["exact".to_string(), "superset".to_string()]);
+
+        // A required key the capability lacks excludes it, even when the others match
+        let required: HashMap<String, String> = [
+            ("region".to_string(), "eu".to_string()),
+            ("tier".to_string(), "ssd".to_string()),
+        ].into_iter().collect();
+        let services = coordinator.get_services_matching("storage", &required).await;
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].service_id.name, "superset");
+
+        // Parameters only count on a capability of the requested type
+        assert!(coordinator.get_services_matching("http-api", &HashMap::new()).await.is_empty());
+        assert_eq!(coordinator.get_services_matching("storage", &HashMap::new()).await.len(), 4);
+    }
+
+    fn mesh_coordinator(port: u16, config: MeshCoordinatorConfig) -> BpciMeshCoordinator {
+        let bpci_config = BpciConfig {
+            bind_address: format!("127.0.0.1:{}", port).parse().unwrap(),
+            max_connections: 100,
+            connection_timeout: Duration::from_secs(30),
+            heartbeat_interval: Duration::from_secs(30),
+            message_buffer_size: 1024,
+            enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
+        };
+        let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
+        BpciMeshCoordinator::new(transport, config)
+    }
+
+    #[tokio::test]
+    async fn test_select_endpoint_round_robin() {
+        let coordinator = mesh_coordinator(21007, MeshCoordinatorConfig::default());
+        for (i, port) in [8081u16, 8082, 8083].iter().enumerate() {
+            coordinator.register_service(parameterized_service(&format!("node-{}", i), *port, &[])).await.unwrap();
+        }
+
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        let mut sequence = Vec::new();
+        for _ in 0..9 {
+            let service = coordinator.select_endpoint("storage").await.expect("No endpoint selected");
+            *counts.entry(service.service_id.name.clone()).or_default() += 1;
+            sequence.push(service.service_id.name);
+        }
+
+        assert_eq!(counts.len(), 3);
+        assert!(counts.values().all(|&count| count == 3));
+        assert_eq!(&sequence[0..3], &sequence[3..6]);
+        assert!(coordinator.select_endpoint("http-api").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_select_endpoint_skips_unhealthy() {
+        let coordinator = mesh_coordinator(21008, MeshCoordinatorConfig::default());
+        for (i, port) in [8081u16, 8082, 8083].iter().enumerate() {
+            coordinator.register_service(parameterized_service(&format!("node-{}", i), *port, &[])).await.unwrap();
+        }
+        let sick = parameterized_service("node-1", 8082, &[]).service_id;
+        coordinator.update_service_health(sick, HealthStatus::Unhealthy).await.unwrap();
+
+        for _ in 0..6 {
+            let service = coordinator.select_endpoint("storage").await.expect("No endpoint selected");
+            assert_ne!(service.service_id.name, "node-1");
+        }
+
+        // Without load balancing the same eligible instance is always chosen
+        let coordinator = mesh_coordinator(21009, MeshCoordinatorConfig {
+            enable_load_balancing: false,
+            ..MeshCoordinatorConfig::default()
+        });
+        for (i, port) in [8081u16, 8082].iter().enumerate() {
+            coordinator.register_service(parameterized_service(&format!("node-{}", i), *port, &[])).await.unwrap();
+        }
+        for _ in 0..3 {
+            assert_eq!(coordinator.select_endpoint("storage").await.unwrap().service_id.name, "node-0");
+        }
+    }
+
+    #[tokio::test]
+    async fn test_sticky_routing_skips_unhealthy_instances() {
+        let coordinator = mesh_coordinator(21015, MeshCoordinatorConfig::default());
+        for (i, port) in [8081u16, 8082, 8083].iter().enumerate() {
+            coordinator.register_service(parameterized_service(&format!("node-{}", i), *port, &[])).await.unwrap();
+        }
+        let keys: Vec<String> = (0..50).map(|i| format!("client-{}", i)).collect();
+        let mut owners = Vec::new();
+        for key in &keys {
+            owners.push(coordinator.route_sticky("storage", key).await.unwrap().service_id);
+        }
+        let sick = owners[0].clone();
+
+        coordinator.update_service_health(sick.clone(), HealthStatus::Unhealthy).await.unwrap();
+        assert_eq!(coordinator.hash_ring("storage").await.len(), 2);
+        for (key, owner) in keys.iter().zip(&owners) {
+            let routed = coordinator.route_sticky("storage", key).await.unwrap().service_id;
+            assert_ne!(routed, sick);
+            if *owner != sick {
+                assert_eq!(routed, *owner);
+            }
+        }
+        assert!(coordinator.route_sticky("http-api", "client-0").await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_register_service_rejects_when_registry_full() {
+        let coordinator = mesh_coordinator(21013, MeshCoordinatorConfig {
+            max_services: 2,
+            ..MeshCoordinatorConfig::default()
+        });
+        coordinator.register_service(parameterized_service("node-0", 8081, &[])).await.unwrap();
+        coordinator.register_service(parameterized_service("node-1", 8082, &[])).await.unwrap();
+
+        let err = coordinator.register_service(parameterized_service("node-2", 8083, &[])).await.unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<BpciError>(),
+            Some(BpciError::RegistryFull { current: 2, max: 2 })
+        ));
+        let stats = coordinator.get_mesh_stats().await;
+        assert_eq!(stats.total_services, 2);
+        assert_eq!(stats.registry_full_rejections, 1);
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_registry_high_watermark_warning() {
+        let coordinator = mesh_coordinator(21014, MeshCoordinatorConfig {
+            max_services: 10,
+            ..MeshCoordinatorConfig::default()
+        });
+        for i in 0..8u16 {
+            coordinator.register_service(parameterized_service(&format!("node-{}", i), 8081 + i, &[])).await.unwrap();
+        }
+        assert!(!logs_contain("high watermark"));
+
+        coordinator.register_service(parameterized_service("node-8", 8089, &[])).await.unwrap();
+        assert!(logs_contain("Service registry above high watermark: 9 of 10 services"));
+    }
+
+    fn reporter_key(reporter: &str) -> SigningKey {
+        SigningKey::new(domain_hash("TEST_HEALTH_REPORTER", reporter.as_bytes()))
+    }
+
+    async fn register_reporters(coordinator: &BpciMeshCoordinator, reporters: &[&str]) {
+        for reporter in reporters {
+            coordinator.register_health_reporter(reporter, reporter_key(reporter).verifying_key()).await;
+        }
+    }
+
+    fn signed_report(reporter: &str, service_id: &ServiceId, status: HealthStatus) -> HealthReport {
+        HealthReport::sign(reporter, service_id.clone(), status, &reporter_key(reporter)).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_single_dissenting_health_report_does_not_flip_status() {
+        let coordinator = mesh_coordinator(21011, MeshCoordinatorConfig {
+            min_health_reporters: 2,
+            ..MeshCoordinatorConfig::default()
+        });
+        let service = parameterized_service("node-0", 8081, &[]);
+        coordinator.register_service(service.clone()).await.unwrap();
+        let service_id = service.service_id;
+        register_reporters(&coordinator, &["observer-a", "observer-b", "observer-c"]).await;
+
+        for reporter in ["observer-a", "observer-b", "observer-c"] {
+            coordinator.report_service_health(signed_report(reporter, &service_id, HealthStatus::Healthy)).await.unwrap();
+        }
+        assert_eq!(coordinator.get_service_health(&service_id).await, HealthStatus::Healthy);
+
+        // One flaky observer is outvoted
+        let changed = coordinator.report_service_health(signed_report("observer-c", &service_id, HealthStatus::Unhealthy)).await.unwrap();
+        assert_eq!(changed, None);
+        assert_eq!(coordinator.get_service_health(&service_id).await, HealthStatus::Healthy);
+
+        // A second one makes a majority
+        let changed = coordinator.report_service_health(signed_report("observer-b", &service_id, HealthStatus::Unhealthy)).await.unwrap();
+        assert_eq!(changed, Some(HealthStatus::Unhealthy));
+        assert_eq!(coordinator.get_service_health(&service_id).await, HealthStatus::Unhealthy);
+    }
+
+    #[tokio::test]
+    async fn test_health_quorum_needs_min_reporters() {
+        let coordinator = mesh_coordinator(21012, MeshCoordinatorConfig {
+            min_health_reporters: 2,
+            ..MeshCoordinatorConfig::default()
+        });
+        let service = parameterized_service("node-0", 8081, &[]);
+        coordinator.register_service(service.clone()).await.unwrap();
+        let service_id = service.service_id;
+        let before = coordinator.get_service_health(&service_id).await;
+        register_reporters(&coordinator, &["observer-a", "observer-b"]).await;
+
+        // A lone reporter is a majority of one, but below the minimum
+        let changed = coordinator.report_service_health(signed_report("observer-a", &service_id, HealthStatus::Degraded)).await.unwrap();
+        assert_eq!(changed, None);
+        assert_eq!(coordinator.get_service_health(&service_id).await, before);
+
+        let changed = coordinator.report_service_health(signed_report("observer-b", &service_id, HealthStatus::Degraded)).await.unwrap();
+        assert_eq!(changed, Some(HealthStatus::Degraded));
+    }
+
+    #[tokio::test]
+    async fn test_health_reports_must_be_authentic_and_for_known_services() {
+        let coordinator = mesh_coordinator(21017, MeshCoordinatorConfig {
+            min_health_reporters: 2,
+            ..MeshCoordinatorConfig::default()
+        });
+        let service = parameterized_service("node-0", 8081, &[]);
+        coordinator.register_service(service.clone()).await.unwrap();
+        let service_id = service.service_id;
+        register_reporters(&coordinator, &["observer-a"]).await;
+
+        // One key cannot vote under several reporter ids
+        let unregistered = signed_report("observer-b", &service_id, HealthStatus::Unhealthy);
+        assert!(coordinator.report_service_health(unregistered).await.is_err());
+        let forged = HealthReport::sign("observer-a", service_id.clone(), HealthStatus::Unhealthy, &reporter_key("observer-b")).unwrap();
+        assert!(coordinator.report_service_health(forged).await.is_err());
+        let mut altered = signed_report("observer-a", &service_id, HealthStatus::Healthy);
+        altered.status = HealthStatus::Unhealthy;
+        assert!(coordinator.report_service_health(altered).await.is_err());
+
+        // A replayed report older than the one held is ignored
+        let mut old = HealthReport {
+            reporter: "observer-a".to_string(),
+            service_id: service_id.clone(),
+            status: HealthStatus::Unhealthy,
+            reported_at: unix_timestamp() - 5,
+            signature: Vec::new(),
+        };
+        old.signature = BpciFrame::sign_ed25519(&reporter_key("observer-a"), &old.digest().unwrap()).unwrap().to_vec();
+        coordinator.report_service_health(signed_report("observer-a", &service_id, HealthStatus::Healthy)).await.unwrap();
+        assert_eq!(coordinator.report_service_health(old).await.unwrap(), None);
+
+        // Reports naming a service that is not registered are refused and not kept
+        let ghost = parameterized_service("ghost", 1, &[]).service_id;
+        assert!(coordinator.report_service_health(signed_report("observer-a", &ghost, HealthStatus::Healthy)).await.is_err());
+        let reports = coordinator.health_reports.read().await;
+        assert!(!reports.contains_key(&ghost));
+        assert_eq!(reports[&service_id].len(), 1);
+        assert_eq!(reports[&service_id]["observer-a"].status, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_probe_marks_reachable_healthy_and_unreachable_unhealthy() {
+        let coordinator = mesh_coordinator(21016, MeshCoordinatorConfig {
+            probe_timeout: Duration::from_millis(500),
+            ..MeshCoordinatorConfig::default()
+        }).with_local_reporter("local-node", reporter_key("local-node"));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live_port = listener.local_addr().unwrap().port();
+        // A port that was just released refuses connections
+        let dead_port = {
+            let closed = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+            closed.local_addr().unwrap().port()
+        };
+
+        let live = parameterized_service("live", live_port, &[]);
+        let mut dead = parameterized_service("dead", dead_port, &[]);
+        dead.health_status = HealthStatus::Unknown;
+        coordinator.register_service(live.clone()).await.unwrap();
+        coordinator.register_service(dead.clone()).await.unwrap();
+        coordinator.update_service_health(live.service_id.clone(), HealthStatus::Unhealthy).await.unwrap();
+
+        assert_eq!(coordinator.probe_service(&live.service_id).await, HealthStatus::Healthy);
+        assert_eq!(coordinator.get_service_health(&live.service_id).await, HealthStatus::Healthy);
+        assert_eq!(coordinator.probe_service(&dead.service_id).await, HealthStatus::Unhealthy);
+        assert_eq!(coordinator.get_service_health(&dead.service_id).await, HealthStatus::Unhealthy);
+
+        // A reachable service keeps its own Degraded report
+        coordinator.update_service_health(live.service_id.clone(), HealthStatus::Degraded).await.unwrap();
+        assert_eq!(coordinator.probe_service(&live.service_id).await, HealthStatus::Degraded);
+
+        let unregistered = parameterized_service("ghost", 1, &[]).service_id;
+        assert_eq!(coordinator.probe_service(&unregistered).await, HealthStatus::Unknown);
+        drop(listener);
+    }
+
+    #[tokio::test]
+    async fn test_failed_probes_do_not_keep_service_alive() {
+        let coordinator = mesh_coordinator(21019, MeshCoordinatorConfig {
+            health_timeout: Duration::from_millis(200),
+            probe_timeout: Duration::from_millis(100),
+            ..MeshCoordinatorConfig::default()
+        }).with_local_reporter("local-node", reporter_key("local-node"));
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let live = parameterized_service("live", listener.local_addr().unwrap().port(), &[]);
+        // A port that was just released refuses connections
+        let dead_port = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+        let dead = parameterized_service("dead", dead_port, &[]);
+        coordinator.register_service(live.clone()).await.unwrap();
+        coordinator.register_service(dead.clone()).await.unwrap();
+
+        coordinator.probe_all().await;
+        assert_eq!(coordinator.get_service_health(&dead.service_id).await, HealthStatus::Unhealthy);
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            coordinator.probe_all().await;
+        }
+
+        // Only the reachable service was kept alive
+        assert_eq!(coordinator.cleanup_stale_services().await, vec![dead.service_id.clone()]);
+        assert_eq!(coordinator.get_service_health(&live.service_id).await, HealthStatus::Healthy);
+        drop(listener);
+    }
+
+    #[tokio::test]
+    #[tracing_test::traced_test]
+    async fn test_probe_results_are_one_report_among_many() {
+        let coordinator = mesh_coordinator(21018, MeshCoordinatorConfig {
+            min_health_reporters: 2,
+            probe_timeout: Duration::from_millis(500),
+            max_concurrent_probes: 1,
+            ..MeshCoordinatorConfig::default()
+        }).with_local_reporter("local-node", reporter_key("local-node"));
+        register_reporters(&coordinator, &["observer-a"]).await;
+        let mut dead = Vec::new();
+        for name in ["dead-a", "dead-b"] {
+            // A port that was just released refuses connections
+            let port = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap().local_addr().unwrap().port();
+            let service = parameterized_service(name, port, &[]);
+            coordinator.register_service(service.clone()).await.unwrap();
+            dead.push(service.service_id);
+        }
+
+        // Repeated failures are warned about once per service
+        for _ in 0..3 {
+            coordinator.probe_all().await;
+        }
+        logs_assert(|lines: &[&str]| {
+            let warnings = lines.iter().filter(|line| line.contains("WARN") && line.contains("Probe of service")).count();
+            if warnings == 2 { Ok(()) } else { Err(format!("{} probe warnings", warnings)) }
+        });
+
+        // The probing node alone is below the reporter minimum
+        assert_eq!(coordinator.get_service_health(&dead[0]).await, HealthStatus::Healthy);
+        let changed = coordinator.report_service_health(signed_report("observer-a", &dead[0], HealthStatus::Unhealthy)).await.unwrap();
+        assert_eq!(changed, Some(HealthStatus::Unhealthy));
+        assert_eq!(coordinator.get_service_health(&dead[1]).await, HealthStatus::Healthy);
+    }
+
+    #[tokio::test]
+    async fn test_heartbeating_service_survives_cleanup() {
+        let coordinator = mesh_coordinator(21010, MeshCoordinatorConfig {
+            heartbeat_interval: Duration::from_millis(50),
+            health_timeout: Duration::from_millis(200),
+            ..MeshCoordinatorConfig::default()
+        });
+        let alive = parameterized_service("alive", 8081, &[]);
+        let silent = parameterized_service("silent", 8082, &[]);
+        coordinator.register_service(alive.clone()).await.unwrap();
+        coordinator.register_service(silent.clone()).await.unwrap();
+        assert!(coordinator.health_monitor.next_heartbeat_due(&alive.service_id).await.is_some());
+
+        for _ in 0..6 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            coordinator.heartbeat(&alive.service_id).await.unwrap();
+        }
+
+        let evicted = coordinator.cleanup_stale_services().await;
+        assert_eq!(evicted, vec![silent.service_id.clone()]);
+
+        let services = coordinator.get_services().await;
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].service_id, alive.service_id);
+        assert_eq!(coordinator.get_service_health(&alive.service_id).await, HealthStatus::Healthy);
+
+        // An evicted service must re-register before its heartbeats count again
+        assert!(coordinator.heartbeat(&silent.service_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_reports_unknown_and_recovery() {
+        let transitions = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = transitions.clone();
+        let monitor = HealthMonitor::new(Duration::from_millis(50), Duration::from_millis(100))
+            .with_transition_listener(
+                HealthTransitionListener::new(move |t| recorded.lock().unwrap().push(t.clone())),
+                Duration::ZERO,
+            );
+        let service_id = parameterized_service("flaky", 8081, &[]).service_id;
+
+        monitor.update_health(service_id.clone(), HealthStatus::Healthy).await;
+        assert!(monitor.check_transitions().await.is_empty());
+
+        tokio::time::sleep(Duration::from_millis(150)).await;
+        assert_eq!(monitor.get_health(&service_id).await, HealthStatus::Unknown);
+        monitor.check_transitions().await;
+        monitor.check_transitions().await;
+
+        monitor.update_health(service_id.clone(), HealthStatus::Degraded).await;
+        monitor.check_transitions().await;
+
+        assert_eq!(*transitions.lock().unwrap(), vec![
+            HealthTransition { service_id: service_id.clone(), from: HealthStatus::Healthy, to: HealthStatus::Unknown },
+            HealthTransition { service_id, from: HealthStatus::Unknown, to: HealthStatus::Degraded },
+        ]);
+    }
+
+    #[tokio::test]
+    async fn test_health_flap_shorter_than_debounce_not_reported() {
+        let transitions = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = transitions.clone();
+        let monitor = HealthMonitor::new(Duration::from_millis(20), Duration::from_millis(40))
+            .with_transition_listener(
+                HealthTransitionListener::new(move |t| recorded.lock().unwrap().push(t.clone())),
+                Duration::from_millis(200),
+            );
+        let service_id = parameterized_service("flapping", 8082, &[]).service_id;
+
+        // Long stable, then briefly Unknown and back
+        monitor.update_health(service_id.clone(), HealthStatus::Healthy).await;
+        tokio::time::sleep(Duration::from_millis(300)).await;
+        assert_eq!(monitor.get_health(&service_id).await, HealthStatus::Unknown);
+        assert!(monitor.check_transitions().await.is_empty());
+        monitor.update_health(service_id.clone(), HealthStatus::Healthy).await;
+        // Kept alive until well past the debounce; the flap stays unreported
+        for _ in 0..12 {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            monitor.heartbeat(&service_id).await;
+        }
+        assert!(transitions.lock().unwrap().is_empty());
+
+        // Unknown for longer than the debounce is reported
+        tokio::time::sleep(Duration::from_millis(60)).await;
+        assert!(monitor.check_transitions().await.is_empty());
+        tokio::time::sleep(Duration::from_millis(200)).await;
+        assert_eq!(monitor.check_transitions().await, vec![
+            HealthTransition { service_id, from: HealthStatus::Healthy, to: HealthStatus::Unknown },
+        ]);
+    }
+
     #[tokio::test]
     async fn test_mesh_statistics() {
         let bpci_config = BpciConfig {
@@ -2158,6 +5938,10 @@ mod mesh_coordinator_tests {
             heartbeat_interval: Duration::from_secs(30),
             message_buffer_size: 1024,
             enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
         };
         
         let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
@@ -2200,6 +5984,10 @@ mod mesh_coordinator_tests {
             heartbeat_interval: Duration::from_secs(30),
             message_buffer_size: 1024,
             enable_encryption: true,
+            wire_format: WireFormat::Cbor,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            aead_algorithm: AeadAlgorithm::default(),
+            permitted_aead_algorithms: AeadAlgorithm::ALL.to_vec(),
         };
         
         let transport = Arc::new(BpciTransport::new(bpci_config).unwrap());
@@ -0,0 +1,247 @@
+//! Acknowledged delivery for BPCI frames
+//!
+//! A frame sent with `BpciTransport::send_frame_reliable` travels as a
+//! `TransportMessage::ReliableFrame`, asking the receiver to answer with a
+//! signed `FrameAck`. Until that ack arrives the sender keeps the frame in an
+//! `AckTracker`, retransmitting it whenever its timer expires and giving up
+//! after `RetransmitPolicy::max_retries` unanswered retransmits.
+//!
+//! On the receiving side `BpciTransport::route_inbound` answers each reliable
+//! frame from a registered peer with an ack signed by the local key, and
+//! clears pending frames when their acks come back.
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{domain_hash, BpciError, BpciFrame, SigningKey, VerifyingKey};
+
+/// Domain for the digest a `FrameAck` signature covers
+pub const FRAME_ACK_HASH: &str = "BPCI_FRAME_ACK";
+
+/// Receiver's signed confirmation that a frame arrived
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FrameAck {
+    /// `BpciFrame::hash` of the acknowledged frame
+    pub frame_hash: [u8; 32],
+    pub nonce: u64,
+    /// Ed25519 signature over `frame_hash` and `nonce`
+    #[serde(with = "serde_bytes")]
+    pub signature: Vec<u8>,
+}
+
+impl FrameAck {
+    /// Acknowledge `frame`, signing with the receiver's key
+    pub fn sign(frame: &BpciFrame, signing_key: &SigningKey) -> Result<Self, BpciError> {
+        let frame_hash = frame.hash()?;
+        let signature = BpciFrame::sign_ed25519(signing_key, &Self::digest(&frame_hash, frame.nonce))?;
+        Ok(Self { frame_hash, nonce: frame.nonce, signature: signature.to_vec() })
+    }
+
+    /// Check the signature against the receiver's public key
+    pub fn verify(&self, public_key: &VerifyingKey) -> bool {
+        let signature: [u8; 64] = match self.signature.as_slice().try_into() {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        BpciFrame::verify_ed25519(public_key, &Self::digest(&self.frame_hash, self.nonce), &signature)
+            .unwrap_or(false)
+    }
+
+    fn digest(frame_hash: &[u8; 32], nonce: u64) -> [u8; 32] {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(frame_hash);
+        bytes.extend_from_slice(&nonce.to_be_bytes());
+        domain_hash(FRAME_ACK_HASH, &bytes)
+    }
+}
+
+/// Retransmit schedule for frames awaiting an ack
+#[derive(Debug, Clone)]
+pub struct RetransmitPolicy {
+    /// Wait for an ack before each retransmit
+    pub timeout: Duration,
+    /// Retransmits after the original send before the frame is given up
+    pub max_retries: u32,
+}
+
+impl Default for RetransmitPolicy {
+    fn default() -> Self {
+        Self {
+            timeout: Duration::from_millis(500),
+            max_retries: 5,
+        }
+    }
+}
+
+/// What to do with a frame whose ack timer expired
+#[derive(Debug, Clone)]
+pub enum RetransmitAction {
+    /// Send the frame to the peer again
+    Retransmit { peer_id: String, frame: BpciFrame },
+    /// Retries are exhausted; the frame is no longer tracked
+    GaveUp { peer_id: String, frame_hash: [u8; 32] },
+}
+
+#[derive(Debug)]
+struct PendingFrame {
+    peer_id: String,
+    frame: BpciFrame,
+    retransmits: u32,
+    deadline: Instant,
+}
+
+/// Frames sent reliably and not yet acknowledged, by frame hash
+#[derive(Debug, Default)]
+pub struct AckTracker {
+    policy: RetransmitPolicy,
+    pending: HashMap<[u8; 32], PendingFrame>,
+}
+
+impl AckTracker {
+    pub fn new(policy: RetransmitPolicy) -> Self {
+        Self { policy, pending: HashMap::new() }
+    }
+
+    pub fn policy(&self) -> &RetransmitPolicy {
+        &self.policy
+    }
+
+    /// Start waiting for an ack of `frame`, sent to `peer_id` at `now`
+    pub fn track(&mut self, peer_id: &str, frame: BpciFrame, now: Instant) -> Result<[u8; 32], BpciError> {
+        let frame_hash = frame.hash()?;
+        self.pending.insert(frame_hash, PendingFrame {
+            peer_id: peer_id.to_string(),
+            frame,
+            retransmits: 0,
+            deadline: now + self.policy.timeout,
+        });
+        Ok(frame_hash)
+    }
+
+    /// Clear the frame `ack` confirms. False if the ack is for no frame pending
+    /// at `peer_id`, names the wrong nonce, or is not signed by `public_key`,
+    /// the key of `peer_id`.
+    pub fn acknowledge(&mut self, peer_id: &str, ack: &FrameAck, public_key: &VerifyingKey) -> bool {
+        let matches = self.pending.get(&ack.frame_hash)
+            .is_some_and(|pending| pending.peer_id == peer_id && pending.frame.nonce == ack.nonce);
+        if !matches || !ack.verify(public_key) {
+            return false;
+        }
+        self.pending.remove(&ack.frame_hash);
+        true
+    }
+
+    /// Frames whose timer expired by `now`: each is either rescheduled for
+    /// retransmit or, once `max_retries` is reached, dropped
+    pub fn due(&mut self, now: Instant) -> Vec<RetransmitAction> {
+        let expired: Vec<[u8; 32]> = self.pending.iter()
+            .filter(|(_, pending)| pending.deadline <= now)
+            .map(|(frame_hash, _)| *frame_hash)
+            .collect();
+
+        let mut actions = Vec::with_capacity(expired.len());
+        for frame_hash in expired {
+            let pending = self.pending.get_mut(&frame_hash).expect("expired frame is pending");
+            if pending.retransmits >= self.policy.max_retries {
+                let pending = self.pending.remove(&frame_hash).expect("expired frame is pending");
+                actions.push(RetransmitAction::GaveUp { peer_id: pending.peer_id, frame_hash });
+                continue;
+            }
+            pending.retransmits += 1;
+            pending.deadline = now + self.policy.timeout;
+            actions.push(RetransmitAction::Retransmit {
+                peer_id: pending.peer_id.clone(),
+                frame: pending.frame.clone(),
+            });
+        }
+        actions
+    }
+
+    pub fn is_pending(&self, frame_hash: &[u8; 32]) -> bool {
+        self.pending.contains_key(frame_hash)
+    }
+
+    pub fn pending_count(&self) -> usize {
+        self.pending.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{AeadKey, ClusterId, PohTick, ServiceIdHash};
+
+    const KEY: SigningKey = SigningKey::new([9u8; 32]);
+
+    fn frame(nonce: u64) -> BpciFrame {
+        BpciFrame::new(
+            ClusterId::new([1u8; 16]),
+            ClusterId::new([2u8; 16]),
+            ServiceIdHash::new([3u8; 32]),
+            nonce,
+            PohTick::new([0u8; 32]),
+            b"commit",
+            &AeadKey::new([5u8; 32]),
+            &KEY,
+        ).unwrap()
+    }
+
+    fn tracker(max_retries: u32) -> AckTracker {
+        AckTracker::new(RetransmitPolicy { timeout: Duration::from_millis(100), max_retries })
+    }
+
+    #[test]
+    fn test_ack_clears_pending_frame() {
+        let mut tracker = tracker(3);
+        let now = Instant::now();
+        let frame_hash = tracker.track("peer-1", frame(1), now).unwrap();
+        assert!(tracker.is_pending(&frame_hash));
+
+        // Signed by someone else, or for another nonce: ignored
+        let public_key = KEY.verifying_key();
+        assert!(!tracker.acknowledge("peer-1", &FrameAck::sign(&frame(1), &SigningKey::new([8u8; 32])).unwrap(), &public_key));
+        let mut wrong_nonce = FrameAck::sign(&frame(1), &KEY).unwrap();
+        wrong_nonce.nonce = 2;
+        assert!(!tracker.acknowledge("peer-1", &wrong_nonce, &public_key));
+        assert!(tracker.is_pending(&frame_hash));
+
+        // A valid ack relayed by a peer the frame was not sent to
+        let ack = FrameAck::sign(&frame(1), &KEY).unwrap();
+        assert!(!tracker.acknowledge("peer-2", &ack, &public_key));
+
+        assert_eq!(ack.frame_hash, frame_hash);
+        assert!(tracker.acknowledge("peer-1", &ack, &public_key));
+        assert_eq!(tracker.pending_count(), 0);
+        assert!(tracker.due(now + Duration::from_secs(1)).is_empty());
+    }
+
+    #[test]
+    fn test_unacked_frame_retransmits_then_gives_up() {
+        let mut tracker = tracker(2);
+        let start = Instant::now();
+        let frame_hash = tracker.track("peer-1", frame(7), start).unwrap();
+        assert!(tracker.due(start + Duration::from_millis(50)).is_empty());
+
+        for round in 1..=2u64 {
+            let actions = tracker.due(start + Duration::from_millis(100 * round));
+            match actions.as_slice() {
+                [RetransmitAction::Retransmit { peer_id, frame }] => {
+                    assert_eq!(peer_id, "peer-1");
+                    assert_eq!(frame.nonce, 7);
+                }
+                other => panic!("expected a retransmit, got {:?}", other),
+            }
+        }
+
+        match tracker.due(start + Duration::from_millis(300)).as_slice() {
+            [RetransmitAction::GaveUp { peer_id, frame_hash: gave_up }] => {
+                assert_eq!(peer_id, "peer-1");
+                assert_eq!(*gave_up, frame_hash);
+            }
+            other => panic!("expected to give up, got {:?}", other),
+        }
+        assert!(!tracker.is_pending(&frame_hash));
+    }
+}
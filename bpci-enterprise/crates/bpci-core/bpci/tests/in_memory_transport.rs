@@ -0,0 +1,64 @@
+//! In-memory transport integration test
+//!
+//! Wires two BPCI transports over a shared in-memory network and exchanges an
+//! authenticated frame between them.
+
+use std::net::SocketAddr;
+
+use bpi_bpci::{
+    AeadKey, BpciConfig, BpciFrame, BpciTransport, ClusterId, InMemoryNetwork, PeerInfo, PohTick, ServiceIdHash, SigningKey,
+};
+
+fn transport_at(network: &InMemoryNetwork, bind_address: SocketAddr) -> BpciTransport {
+    let config = BpciConfig { bind_address, ..BpciConfig::default() };
+    BpciTransport::new(config).unwrap().with_backend(Box::new(network.transport()))
+}
+
+#[tokio::test]
+async fn test_authenticated_frame_over_in_memory_transport() {
+    let network = InMemoryNetwork::new();
+    let addr_a: SocketAddr = "10.0.0.1:7000".parse().unwrap();
+    let addr_b: SocketAddr = "10.0.0.2:7000".parse().unwrap();
+    let node_a = transport_at(&network, addr_a);
+    let node_b = transport_at(&network, addr_b);
+    assert_eq!(node_a.listen().await.unwrap(), addr_a);
+    assert_eq!(node_b.listen().await.unwrap(), addr_b);
+
+    node_a.connect_peer(PeerInfo {
+        id: "node-b".to_string(),
+        address: addr_b,
+        capabilities: vec![],
+        last_seen: 0,
+        connection_quality: 1.0,
+        cluster_id: Some(ClusterId::new([2u8; 16])),
+        verifying_key: None,
+    }).await.unwrap();
+
+    let aead_key = AeadKey::new([5u8; 32]);
+    let signing_key = SigningKey::new([6u8; 32]);
+    let frame = node_a
+        .send_frame(ClusterId::new([2u8; 16]), ServiceIdHash::new([3u8; 32]), b"block proposal", &aead_key, &signing_key, PohTick::new([4u8; 32]))
+        .await
+        .unwrap();
+    node_a.send_to_peer("node-b", frame.to_message().unwrap()).await.unwrap();
+    assert_eq!(node_a.get_stats().await["node-b"].messages_sent, 1);
+
+    let (from, message) = node_b.recv().await.unwrap();
+    assert_eq!(from, addr_a);
+    let received = BpciFrame::from_message(&message).unwrap();
+    let (payload, result) = node_b.verify_frame(&received, &signing_key.verifying_key(), &aead_key).await.unwrap();
+    assert!(result.valid);
+    assert_eq!(payload, b"block proposal");
+
+    // Dialing an address nobody listens on fails
+    let missing = PeerInfo {
+        id: "node-c".to_string(),
+        address: "10.0.0.3:7000".parse().unwrap(),
+        capabilities: vec![],
+        last_seen: 0,
+        connection_quality: 1.0,
+        cluster_id: None,
+        verifying_key: None,
+    };
+    assert!(node_a.connect_peer(missing).await.is_err());
+}
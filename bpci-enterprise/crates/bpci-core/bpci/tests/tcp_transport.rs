@@ -0,0 +1,62 @@
+//! TCP transport integration test
+//!
+//! Connects two BPCI transports over loopback TCP and checks that each side
+//! attributes the other's messages to the peer registered at its listen
+//! address, whichever side dialed.
+
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use bpi_bpci::{BpciConfig, BpciTransport, PeerInfo, TransportMessage};
+
+async fn listening_transport() -> (BpciTransport, SocketAddr) {
+    let config = BpciConfig { bind_address: "127.0.0.1:0".parse().unwrap(), ..BpciConfig::default() };
+    let transport = BpciTransport::new(config).unwrap();
+    let address = transport.listen().await.unwrap();
+    (transport, address)
+}
+
+fn peer(id: &str, address: SocketAddr) -> PeerInfo {
+    PeerInfo {
+        id: id.to_string(),
+        address,
+        capabilities: vec![],
+        last_seen: 0,
+        connection_quality: 1.0,
+        cluster_id: None,
+        verifying_key: None,
+    }
+}
+
+async fn recv_routed(transport: &BpciTransport) -> (String, TransportMessage) {
+    tokio::time::timeout(Duration::from_secs(5), transport.recv_routed()).await
+        .expect("no routed message within 5s")
+        .expect("inbound stream closed")
+}
+
+#[tokio::test]
+async fn test_tcp_messages_routed_to_registered_peers() {
+    let (node_a, addr_a) = listening_transport().await;
+    let (node_b, addr_b) = listening_transport().await;
+
+    // A dials B; B knows A only by its listen address
+    node_a.connect_peer(peer("node-b", addr_b)).await.unwrap();
+    node_b.add_peer(peer("node-a", addr_a)).await.unwrap();
+    node_a.send_to_peer("node-b", TransportMessage::Consensus(b"prepare".to_vec())).await.unwrap();
+    let (from, message) = recv_routed(&node_b).await;
+    assert_eq!(from, "node-a");
+    assert!(matches!(message, TransportMessage::Consensus(payload) if payload == b"prepare"));
+    assert_eq!(node_b.get_stats().await["node-a"].messages_received, 1);
+
+    // B dials back and its replies are attributed to B
+    node_b.connect_peer(peer("node-a", addr_a)).await.unwrap();
+    node_b.send_to_peer("node-a", TransportMessage::Consensus(b"commit".to_vec())).await.unwrap();
+    let (from, message) = recv_routed(&node_a).await;
+    assert_eq!(from, "node-b");
+    assert!(matches!(message, TransportMessage::Consensus(payload) if payload == b"commit"));
+
+    // A's connection still reaches B after B dialed it
+    node_a.send_to_peer("node-b", TransportMessage::Consensus(b"round 2".to_vec())).await.unwrap();
+    let (from, _) = recv_routed(&node_b).await;
+    assert_eq!(from, "node-a");
+}
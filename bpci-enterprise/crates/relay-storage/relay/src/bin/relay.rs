@@ -43,6 +43,10 @@ struct Args {
     /// Simulated loss probability [0.0, 1.0] (testing)
     #[arg(long, default_value_t = 0.0)]
     loss: f32,
+
+    /// Drop messages that are not Ed25519-signed by their sender
+    #[arg(long, default_value_t = false)]
+    require_signed: bool,
 }
 
 #[tokio::main]
@@ -64,6 +68,8 @@ async fn main() -> anyhow::Result<()> {
         partition_recovery_timeout_ms: 2000,
         routing_table_size: 10000,
         connection_timeout_ms: 30000,
+        require_signed: args.require_signed,
+        ..RelayConfig::default()
     };
 
     let relay = if let Some(path) = args.db_path {
@@ -0,0 +1,161 @@
+//! Content-addressed blob storage for the relay
+//!
+//! Blobs are addressed by their BLAKE3 hash and written to `<base>/<shard>/<hash>`
+//! where the shard is the first byte of the hash in hex. Pinned blobs survive
+//! `gc_sweep`; everything else is reclaimed. Pins are kept in `<base>/pins.json`
+//! so they outlive a restart.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Storage statistics reported by `ContentStore::stats`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ContentStoreStats {
+    pub objects: u64,
+    pub bytes: u64,
+    pub pinned_objects: u64,
+}
+
+const PINS_FILE: &str = "pins.json";
+
+#[derive(Debug)]
+pub struct ContentStore {
+    base: PathBuf,
+    // hex hash -> pin priority
+    pins: HashMap<String, u8>,
+}
+
+impl ContentStore {
+    /// Open a store rooted at `base`, creating the directory if needed and
+    /// reloading the pins saved there
+    pub fn open<P: AsRef<Path>>(base: P) -> io::Result<Self> {
+        let base = base.as_ref().to_path_buf();
+        fs::create_dir_all(&base)?;
+        let pins = match fs::read(base.join(PINS_FILE)) {
+            Ok(encoded) => serde_json::from_slice(&encoded)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => HashMap::new(),
+            Err(e) => return Err(e),
+        };
+        Ok(Self { base, pins })
+    }
+
+    /// Write `data` and return its BLAKE3 content hash
+    pub fn put(&self, data: &[u8]) -> io::Result<[u8; 32]> {
+        let hash = *blake3::hash(data).as_bytes();
+        let path = self.blob_path(&hash);
+        if path.exists() {
+            return Ok(hash);
+        }
+
+        let shard = path.parent().expect("blob path has a shard directory");
+        fs::create_dir_all(shard)?;
+        // Write-then-rename so readers never observe a partial blob
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, data)?;
+        fs::rename(&tmp, &path)?;
+        Ok(hash)
+    }
+
+    /// Read the blob with content hash `hash`
+    pub fn get(&self, hash: &[u8]) -> io::Result<Option<Vec<u8>>> {
+        let hash = Self::parse_hash(hash)?;
+        match fs::read(self.blob_path(&hash)) {
+            Ok(data) => Ok(Some(data)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Protect a stored blob from garbage collection
+    pub fn pin(&mut self, hash: &[u8], priority: u8) -> io::Result<()> {
+        let hash = Self::parse_hash(hash)?;
+        if !self.blob_path(&hash).exists() {
+            return Err(io::Error::new(io::ErrorKind::NotFound, "cannot pin unknown blob"));
+        }
+        self.pins.insert(hex_encode(&hash), priority);
+        self.save_pins()
+    }
+
+    /// Remove the pin on a blob; returns whether it was pinned
+    pub fn unpin(&mut self, hash: &[u8]) -> io::Result<bool> {
+        let hash = Self::parse_hash(hash)?;
+        let pinned = self.pins.remove(&hex_encode(&hash)).is_some();
+        if pinned {
+            self.save_pins()?;
+        }
+        Ok(pinned)
+    }
+
+    pub fn is_pinned(&self, hash: &[u8]) -> bool {
+        self.pins.contains_key(&hex_encode(hash))
+    }
+
+    /// Delete every unpinned blob; returns the number removed
+    pub fn gc_sweep(&mut self) -> io::Result<usize> {
+        let mut removed = 0;
+        for (name, path, _) in self.blobs()? {
+            if !self.pins.contains_key(&name) {
+                fs::remove_file(path)?;
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
+
+    /// Object and byte counts computed from what is on disk
+    pub fn stats(&self) -> io::Result<ContentStoreStats> {
+        let blobs = self.blobs()?;
+        Ok(ContentStoreStats {
+            objects: blobs.len() as u64,
+            bytes: blobs.iter().map(|(_, _, len)| *len).sum(),
+            pinned_objects: self.pins.len() as u64,
+        })
+    }
+
+    // Write-then-rename, as for blobs, so a crash leaves the old pin set intact
+    fn save_pins(&self) -> io::Result<()> {
+        let encoded = serde_json::to_vec(&self.pins).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+        let path = self.base.join(PINS_FILE);
+        let tmp = path.with_extension("tmp");
+        fs::write(&tmp, encoded)?;
+        fs::rename(&tmp, &path)
+    }
+
+    fn blob_path(&self, hash: &[u8; 32]) -> PathBuf {
+        let name = hex_encode(hash);
+        self.base.join(&name[..2]).join(name)
+    }
+
+    // (hex name, path, size) for every committed blob
+    fn blobs(&self) -> io::Result<Vec<(String, PathBuf, u64)>> {
+        let mut blobs = Vec::new();
+        for shard in fs::read_dir(&self.base)? {
+            let shard = shard?;
+            if !shard.file_type()?.is_dir() {
+                continue;
+            }
+            for entry in fs::read_dir(shard.path())? {
+                let entry = entry?;
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.len() != 64 {
+                    continue; // skip in-flight temp files
+                }
+                blobs.push((name, entry.path(), entry.metadata()?.len()));
+            }
+        }
+        Ok(blobs)
+    }
+
+    fn parse_hash(hash: &[u8]) -> io::Result<[u8; 32]> {
+        hash.try_into()
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "content hash must be 32 bytes"))
+    }
+}
+
+fn hex_encode(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
@@ -0,0 +1,135 @@
+//! Persistent message deduplication for the relay
+//!
+//! The in-memory LRU in `Relay` only remembers the most recent ids. A `DedupStore`
+//! backs it so ids evicted from the LRU (or forgotten across a restart) are still
+//! recognised as duplicates until their TTL expires. Which key a message is
+//! recorded under is chosen by a `DedupKey`.
+
+use anyhow::Result;
+use std::fmt;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::Message;
+
+#[cfg(feature = "military-storage")]
+use anyhow::anyhow;
+#[cfg(feature = "military-storage")]
+use std::path::Path;
+
+/// Persistent record of dedup keys the relay has already seen
+pub trait DedupStore: Send + Sync + fmt::Debug {
+    /// Unix timestamp (seconds) at which `id` was recorded, if present
+    fn get_seen(&self, id: u64) -> Result<Option<u64>>;
+    /// Record `id` as seen at `seen_at` (unix seconds)
+    fn put_seen(&self, id: u64, seen_at: u64) -> Result<()>;
+    /// Forget `id` (used when its TTL has expired)
+    fn remove_seen(&self, id: u64) -> Result<()>;
+    /// Make every recorded id durable; stores that write through need not override
+    fn flush(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Derives the key a message is deduplicated under. The key, not the raw
+/// message id, is what the LRU and the `DedupStore` record.
+pub trait DedupKey: Send + Sync + fmt::Debug {
+    /// `sender` is the signing key of a `SignedMessage`, `None` for unsigned messages
+    fn key(&self, msg: &Message, sender: Option<&[u8; 32]>) -> u64;
+}
+
+/// Dedup on `Message.id` alone (the default)
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MessageIdKey;
+
+impl DedupKey for MessageIdKey {
+    fn key(&self, msg: &Message, _sender: Option<&[u8; 32]>) -> u64 {
+        msg.id
+    }
+}
+
+/// Dedup on BLAKE3 of `sender || id || data`, so independent senders reusing
+/// an id, or one id carrying different payloads, do not suppress each other
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ContentHashKey;
+
+impl DedupKey for ContentHashKey {
+    fn key(&self, msg: &Message, sender: Option<&[u8; 32]>) -> u64 {
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(sender.map(|key| key.as_slice()).unwrap_or(&[]));
+        hasher.update(&msg.id.to_be_bytes());
+        hasher.update(&msg.data);
+        let hash = hasher.finalize();
+        u64::from_be_bytes(hash.as_bytes()[..8].try_into().expect("hash has 8 leading bytes"))
+    }
+}
+
+/// Current unix time in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Sled-backed dedup store; keys are big-endian ids, values big-endian timestamps
+#[cfg(feature = "military-storage")]
+#[derive(Debug)]
+pub struct SledDedupStore {
+    db: sled::Db,
+}
+
+#[cfg(feature = "military-storage")]
+impl SledDedupStore {
+    /// Open (or create) a dedup store at `path`
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let db = sled::open(path).map_err(|e| anyhow!("Failed to open dedup store: {}", e))?;
+        Ok(Self { db })
+    }
+
+    /// Create a throwaway store that is removed when dropped
+    pub fn temporary() -> Result<Self> {
+        let db = sled::Config::new()
+            .temporary(true)
+            .open()
+            .map_err(|e| anyhow!("Failed to open temporary dedup store: {}", e))?;
+        Ok(Self { db })
+    }
+}
+
+#[cfg(feature = "military-storage")]
+impl DedupStore for SledDedupStore {
+    fn get_seen(&self, id: u64) -> Result<Option<u64>> {
+        match self.db.get(id.to_be_bytes()) {
+            Ok(Some(value)) => {
+                let bytes: [u8; 8] = value
+                    .as_ref()
+                    .try_into()
+                    .map_err(|_| anyhow!("Corrupt dedup record for id {}", id))?;
+                Ok(Some(u64::from_be_bytes(bytes)))
+            }
+            Ok(None) => Ok(None),
+            Err(e) => Err(anyhow!("Dedup store GET failed: {}", e)),
+        }
+    }
+
+    fn put_seen(&self, id: u64, seen_at: u64) -> Result<()> {
+        self.db
+            .insert(id.to_be_bytes(), &seen_at.to_be_bytes())
+            .map_err(|e| anyhow!("Dedup store INSERT failed: {}", e))?;
+        Ok(())
+    }
+
+    fn remove_seen(&self, id: u64) -> Result<()> {
+        self.db
+            .remove(id.to_be_bytes())
+            .map_err(|e| anyhow!("Dedup store REMOVE failed: {}", e))?;
+        Ok(())
+    }
+
+    fn flush(&self) -> Result<()> {
+        self.db
+            .flush()
+            .map_err(|e| anyhow!("Dedup store FLUSH failed: {}", e))?;
+        Ok(())
+    }
+}
@@ -0,0 +1,184 @@
+//! ASN and geographic lookup for relay diversity
+//!
+//! `GeoIpDatabase` loads a MaxMind GeoLite2-style CSV export with the columns
+//! `network,autonomous_system_number,autonomous_system_organization,country_iso_code,continent_code`
+//! and resolves peer addresses by longest-prefix match. Fields may be quoted as
+//! in RFC 4180, so organization names can contain commas and doubled quotes. Addresses that are not
+//! covered fall back to `GeographicRegion::Unknown` and a synthetic private-use ASN.
+
+use anyhow::{anyhow, Result};
+use ipnet::IpNet;
+use std::fmt;
+use std::fs;
+use std::net::{IpAddr, SocketAddr};
+use std::path::Path;
+
+use crate::{AsnInfo, GeographicRegion};
+
+/// Start of the 32-bit private-use ASN range (RFC 6996)
+const SYNTHETIC_ASN_BASE: u32 = 4_200_000_000;
+const SYNTHETIC_ASN_SPAN: u32 = 94_967_294;
+
+/// Resolve a peer address to its ASN and geographic region
+pub trait ResolveAsn: Send + Sync + fmt::Debug {
+    fn resolve(&self, addr: &SocketAddr) -> (AsnInfo, GeographicRegion);
+}
+
+#[derive(Debug, Clone)]
+struct GeoIpEntry {
+    network: IpNet,
+    asn: AsnInfo,
+    region: GeographicRegion,
+}
+
+/// In-memory GeoIP/ASN table loaded from a CSV database
+#[derive(Debug, Clone, Default)]
+pub struct GeoIpDatabase {
+    entries: Vec<GeoIpEntry>,
+}
+
+impl GeoIpDatabase {
+    /// Empty database; every lookup falls back to a synthetic ASN
+    pub fn empty() -> Self {
+        Self::default()
+    }
+
+    /// Load a GeoLite2-style CSV database from `path`
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let contents = fs::read_to_string(path.as_ref())
+            .map_err(|e| anyhow!("Failed to read GeoIP database {}: {}", path.as_ref().display(), e))?;
+        Self::parse(&contents)
+    }
+
+    /// Parse CSV database contents
+    pub fn parse(contents: &str) -> Result<Self> {
+        let mut entries = Vec::new();
+
+        for (line_no, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') || line.starts_with("network,") {
+                continue;
+            }
+
+            let fields = split_csv_record(line)
+                .map_err(|e| anyhow!("GeoIP database line {}: {}", line_no + 1, e))?;
+            if fields.len() < 5 {
+                return Err(anyhow!("GeoIP database line {}: expected 5 columns", line_no + 1));
+            }
+
+            let network: IpNet = fields[0]
+                .parse()
+                .map_err(|e| anyhow!("GeoIP database line {}: bad network: {}", line_no + 1, e))?;
+            let asn: u32 = fields[1]
+                .parse()
+                .map_err(|e| anyhow!("GeoIP database line {}: bad ASN: {}", line_no + 1, e))?;
+
+            entries.push(GeoIpEntry {
+                network,
+                asn: AsnInfo {
+                    asn,
+                    name: fields[2].clone(),
+                    country: fields[3].clone(),
+                    region: fields[4].clone(),
+                },
+                region: region_from_continent_code(&fields[4]),
+            });
+        }
+
+        Ok(Self { entries })
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Longest-prefix match for `ip`
+    pub fn lookup(&self, ip: IpAddr) -> Option<(AsnInfo, GeographicRegion)> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.network.contains(&ip))
+            .max_by_key(|entry| entry.network.prefix_len())
+            .map(|entry| (entry.asn.clone(), entry.region.clone()))
+    }
+}
+
+impl ResolveAsn for GeoIpDatabase {
+    fn resolve(&self, addr: &SocketAddr) -> (AsnInfo, GeographicRegion) {
+        self.lookup(addr.ip())
+            .unwrap_or_else(|| (synthetic_asn(addr.ip()), GeographicRegion::Unknown))
+    }
+}
+
+// Split one CSV record. A field wrapped in double quotes may contain commas,
+// and `""` inside it stands for a literal quote.
+fn split_csv_record(line: &str) -> Result<Vec<String>> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+        while chars.next_if(|c| *c == ' ').is_some() {}
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            loop {
+                match chars.next() {
+                    Some('"') if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    Some('"') => break,
+                    Some(c) => field.push(c),
+                    None => return Err(anyhow!("unterminated quoted field")),
+                }
+            }
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                if !c.is_whitespace() {
+                    return Err(anyhow!("unexpected {:?} after quoted field", c));
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != ',') {
+                field.push(c);
+            }
+        }
+        fields.push(field.trim().to_string());
+        if chars.next().is_none() {
+            return Ok(fields);
+        }
+    }
+}
+
+/// Map a MaxMind continent code to a `GeographicRegion`
+pub fn region_from_continent_code(code: &str) -> GeographicRegion {
+    match code.to_ascii_uppercase().as_str() {
+        "NA" => GeographicRegion::NorthAmerica,
+        "EU" => GeographicRegion::Europe,
+        "AS" => GeographicRegion::Asia,
+        "SA" => GeographicRegion::SouthAmerica,
+        "AF" => GeographicRegion::Africa,
+        "OC" => GeographicRegion::Oceania,
+        _ => GeographicRegion::Unknown,
+    }
+}
+
+/// Deterministic private-use ASN for an unresolved address. Addresses in the same
+/// IPv4 /24 (IPv6 /48) share an ASN so diversity caps still group them.
+pub fn synthetic_asn(ip: IpAddr) -> AsnInfo {
+    let prefix: Vec<u8> = match ip {
+        IpAddr::V4(v4) => v4.octets()[..3].to_vec(),
+        IpAddr::V6(v6) => v6.octets()[..6].to_vec(),
+    };
+    let digest = blake3::hash(&prefix);
+    let bytes = digest.as_bytes();
+    let value = u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]);
+
+    AsnInfo {
+        asn: SYNTHETIC_ASN_BASE + value % SYNTHETIC_ASN_SPAN,
+        name: "UNKNOWN".to_string(),
+        country: "ZZ".to_string(),
+        region: "Unknown".to_string(),
+    }
+}
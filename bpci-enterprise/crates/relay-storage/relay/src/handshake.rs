@@ -0,0 +1,164 @@
+//! Admission handshake exchanged when a peer connects to the relay
+//!
+//! The relay first issues a single-use challenge. The peer declares its
+//! protocol version and capabilities and signs them, together with the
+//! challenge, with its Ed25519 identity key. The relay checks the version, the
+//! signature, that the challenge is one it issued and, when configured, that the
+//! key is on its trust list; it then keeps only the capabilities both sides
+//! support. Relay status is one of them rather than something the caller asserts.
+
+use std::fmt;
+use std::net::SocketAddr;
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+
+/// Relay wire protocol version; peers must match it exactly. Every QUIC frame
+/// is prefixed with it, so bump it whenever the message layout changes.
+pub const RELAY_PROTOCOL_VERSION: u16 = 2;
+
+/// Capability a peer declares to be treated as a relay
+pub const RELAY_CAPABILITY: &str = "relay";
+
+/// Capabilities a relay supports unless configured otherwise
+pub fn default_capabilities() -> Vec<String> {
+    vec![RELAY_CAPABILITY.to_string(), "signed-messages".to_string()]
+}
+
+/// Random bytes the relay hands a connecting peer to sign, so a captured
+/// handshake cannot be replayed
+pub type HandshakeChallenge = [u8; 32];
+
+/// A peer's self-description, signed by its identity key over every field but
+/// the signature
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PeerHandshake {
+    pub protocol_version: u16,
+    pub peer_id: String,
+    pub address: SocketAddr,
+    pub capabilities: Vec<String>,
+    pub challenge: HandshakeChallenge,
+    pub public_key: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+/// Why a handshake was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HandshakeRejection {
+    VersionMismatch { ours: u16, theirs: u16 },
+    InvalidIdentity,
+    /// The challenge was not issued by this relay or was already answered
+    UnknownChallenge,
+    /// The identity key is not on the relay's trust list
+    UntrustedIdentity,
+    /// The relay has shut down and admits no one
+    RelayShutDown,
+}
+
+impl fmt::Display for HandshakeRejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::VersionMismatch { ours, theirs } => {
+                write!(f, "protocol version mismatch: relay speaks {}, peer {}", ours, theirs)
+            }
+            Self::InvalidIdentity => write!(f, "handshake signature does not match the peer identity"),
+            Self::UnknownChallenge => write!(f, "handshake answers a challenge the relay did not issue"),
+            Self::UntrustedIdentity => write!(f, "peer identity key is not trusted"),
+            Self::RelayShutDown => write!(f, "relay is shut down"),
+        }
+    }
+}
+
+impl std::error::Error for HandshakeRejection {}
+
+impl PeerHandshake {
+    /// Handshake for the current protocol version answering `challenge`, signed
+    /// with `signing_key`
+    pub fn sign(
+        peer_id: impl Into<String>,
+        address: SocketAddr,
+        capabilities: Vec<String>,
+        challenge: HandshakeChallenge,
+        signing_key: &SigningKey,
+    ) -> Self {
+        let mut handshake = Self {
+            protocol_version: RELAY_PROTOCOL_VERSION,
+            peer_id: peer_id.into(),
+            address,
+            capabilities,
+            challenge,
+            public_key: signing_key.verifying_key().to_bytes(),
+            signature: Vec::new(),
+        };
+        handshake.signature = signing_key.sign(&handshake.signing_bytes()).to_bytes().to_vec();
+        handshake
+    }
+
+    /// Check the version, then the signature against the embedded identity key
+    pub fn validate(&self) -> Result<(), HandshakeRejection> {
+        if self.protocol_version != RELAY_PROTOCOL_VERSION {
+            return Err(HandshakeRejection::VersionMismatch {
+                ours: RELAY_PROTOCOL_VERSION,
+                theirs: self.protocol_version,
+            });
+        }
+        let key = VerifyingKey::from_bytes(&self.public_key).map_err(|_| HandshakeRejection::InvalidIdentity)?;
+        let signature = Signature::from_slice(&self.signature).map_err(|_| HandshakeRejection::InvalidIdentity)?;
+        key.verify(&self.signing_bytes(), &signature).map_err(|_| HandshakeRejection::InvalidIdentity)
+    }
+
+    /// Declared capabilities the relay also supports, in the peer's order
+    pub fn negotiate(&self, supported: &[String]) -> Vec<String> {
+        self.capabilities.iter()
+            .filter(|capability| supported.contains(capability))
+            .cloned()
+            .collect()
+    }
+
+    // Length-prefixed so field boundaries cannot be shifted between fields
+    fn signing_bytes(&self) -> Vec<u8> {
+        fn put(bytes: &mut Vec<u8>, field: &[u8]) {
+            bytes.extend_from_slice(&(field.len() as u32).to_be_bytes());
+            bytes.extend_from_slice(field);
+        }
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&self.protocol_version.to_be_bytes());
+        put(&mut bytes, self.peer_id.as_bytes());
+        put(&mut bytes, self.address.to_string().as_bytes());
+        bytes.extend_from_slice(&(self.capabilities.len() as u32).to_be_bytes());
+        for capability in &self.capabilities {
+            put(&mut bytes, capability.as_bytes());
+        }
+        bytes.extend_from_slice(&self.challenge);
+        bytes.extend_from_slice(&self.public_key);
+        bytes
+    }
+}
+
+/// What a connecting peer presents in its handshake
+#[derive(Clone, Debug)]
+pub struct HandshakeIdentity {
+    pub peer_id: String,
+    pub capabilities: Vec<String>,
+    pub signing_key: SigningKey,
+}
+
+impl HandshakeIdentity {
+    pub fn new(peer_id: impl Into<String>, capabilities: Vec<String>, signing_key: SigningKey) -> Self {
+        Self { peer_id: peer_id.into(), capabilities, signing_key }
+    }
+
+    /// Throwaway client identity with a fresh key and no capabilities; relays
+    /// with a trust list refuse it
+    pub fn ephemeral() -> Self {
+        let signing_key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let fingerprint: String = signing_key.verifying_key().to_bytes()[..8].iter().map(|b| format!("{:02x}", b)).collect();
+        let peer_id = format!("client-{}", fingerprint);
+        Self::new(peer_id, Vec::new(), signing_key)
+    }
+
+    /// Answer `challenge` as this identity, announcing `address`
+    pub fn answer(&self, address: SocketAddr, challenge: HandshakeChallenge) -> PeerHandshake {
+        PeerHandshake::sign(self.peer_id.clone(), address, self.capabilities.clone(), challenge, &self.signing_key)
+    }
+}
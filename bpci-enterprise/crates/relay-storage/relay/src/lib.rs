@@ -1,22 +1,43 @@
 use ahash::AHashMap as HashMap;
 use lru::LruCache;
-use rand::Rng;
+use rand::{Rng, RngCore, SeedableRng};
 use serde::{Deserialize, Serialize};
 use std::num::NonZeroUsize;
 use std::sync::Arc;
 use std::time::Instant;
 use tokio::sync::mpsc;
 use tokio::sync::Mutex;
-use prometheus::{Encoder, TextEncoder, Counter, Gauge};
+use prometheus::{Encoder, TextEncoder, Counter, Gauge, Histogram, HistogramOpts};
 use once_cell::sync::Lazy;
 use axum::{routing::get, Router};
 use axum::http::StatusCode;
 use std::net::{SocketAddr, IpAddr, Ipv4Addr};
 use chrono::{DateTime, Utc};
 
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, VecDeque};
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
+
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+
+use tracing::warn;
+
+pub mod dedup;
+use dedup::DedupStore;
+pub use dedup::{ContentHashKey, DedupKey, MessageIdKey};
+
+pub mod content_store;
+use content_store::ContentStore;
+
+pub mod geoip;
+use geoip::{GeoIpDatabase, ResolveAsn};
+
+pub mod peer_channel;
+pub use peer_channel::{BackpressurePolicy, PeerReceiver};
+use peer_channel::{peer_channel, PeerSendError, PeerSender, Queued};
+
+pub mod handshake;
+pub use handshake::{HandshakeChallenge, HandshakeIdentity, HandshakeRejection, PeerHandshake, RELAY_CAPABILITY, RELAY_PROTOCOL_VERSION};
 
 // Storage integration temporarily disabled for compilation
 // mod storage;
@@ -27,35 +48,207 @@ use std::path::Path;
 
 
 
+/// Routing entries not refreshed within this window are dropped
+const ROUTE_MAX_AGE_SECS: u64 = 300;
+
+/// zstd level for persisted relay state
+const STATE_COMPRESSION_LEVEL: i32 = 3;
+
+/// Hop budget given to newly created messages
+pub const DEFAULT_MESSAGE_TTL: u8 = 16;
+
+// Outstanding handshake challenges kept; the oldest are forgotten first
+const MAX_PENDING_CHALLENGES: usize = 1024;
+
+/// How far ahead of the local clock a message's `created_at` may be before it
+/// is treated as expired; otherwise a future stamp would never age out
+pub const MAX_CLOCK_SKEW_SECS: u64 = 30;
+
+// Field defaults cannot help here: bincode has no field names, so a frame
+// from a peer on another layout fails to decode. Layout changes bump
+// `RELAY_PROTOCOL_VERSION`, which prefixes every frame (see `net`).
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Message {
     pub id: u64,
-    pub data: Vec<u8>,
+    // Shared so fanning out to many peers clones a pointer, not the payload
+    pub data: Arc<[u8]>,
+    // Remaining hops; decremented on each routed forward, dropped at zero
+    pub ttl: u8,
+    // Per-source sequence number, starting at 0; used when the relay orders by source
+    pub seq: Option<u64>,
+    // Unix seconds when the message was created; 0 means unknown and never expires
+    pub created_at: u64,
+}
+
+impl Message {
+    pub fn new(id: u64, data: Vec<u8>) -> Self {
+        Self { id, data: data.into(), ttl: DEFAULT_MESSAGE_TTL, seq: None, created_at: dedup::unix_now() }
+    }
+
+    pub fn with_seq(mut self, seq: u64) -> Self {
+        self.seq = Some(seq);
+        self
+    }
+
+    /// Whether the message is at least `max_age` old at unix time `now`, or
+    /// claims to be created more than `MAX_CLOCK_SKEW_SECS` in the future
+    pub fn is_expired(&self, max_age: std::time::Duration, now: u64) -> bool {
+        if self.created_at == 0 {
+            return false;
+        }
+        self.created_at > now.saturating_add(MAX_CLOCK_SKEW_SECS)
+            || std::time::Duration::from_secs(now.saturating_sub(self.created_at)) >= max_age
+    }
+}
+
+/// A `Message` authenticated by its sender's Ed25519 key. The signature covers
+/// `id`, `data`, `seq` and `created_at` so neither the payload, the dedup id,
+/// the ordering nor the age can be substituted; `ttl` is left out so
+/// forwarding relays can decrement it.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SignedMessage {
+    pub message: Message,
+    pub sender_public_key: [u8; 32],
+    pub signature: Vec<u8>,
+}
+
+impl SignedMessage {
+    pub fn sign(message: Message, signing_key: &SigningKey) -> Self {
+        let signature = signing_key.sign(&Self::signing_bytes(&message));
+        Self {
+            message,
+            sender_public_key: signing_key.verifying_key().to_bytes(),
+            signature: signature.to_bytes().to_vec(),
+        }
+    }
+
+    /// Check the signature against the embedded sender key
+    pub fn verify(&self) -> bool {
+        let key = match VerifyingKey::from_bytes(&self.sender_public_key) {
+            Ok(key) => key,
+            Err(_) => return false,
+        };
+        let signature = match Signature::from_slice(&self.signature) {
+            Ok(signature) => signature,
+            Err(_) => return false,
+        };
+        key.verify(&Self::signing_bytes(&self.message), &signature).is_ok()
+    }
+
+    // Fixed layout: id, data length and data, then seq and created_at, each
+    // behind a presence byte. Every field is always encoded, so no field can be
+    // stripped or shifted into another without breaking the signature.
+    fn signing_bytes(message: &Message) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(36 + message.data.len());
+        bytes.extend_from_slice(&message.id.to_be_bytes());
+        bytes.extend_from_slice(&(message.data.len() as u64).to_be_bytes());
+        bytes.extend_from_slice(&message.data);
+        match message.seq {
+            Some(seq) => {
+                bytes.push(1);
+                bytes.extend_from_slice(&seq.to_be_bytes());
+            }
+            None => bytes.extend_from_slice(&[0; 9]),
+        }
+        bytes.push(u8::from(message.created_at != 0));
+        bytes.extend_from_slice(&message.created_at.to_be_bytes());
+        bytes
+    }
+}
+
+/// What a relay queues for a peer and puts on the wire. A signed message keeps
+/// its envelope end to end so every relay along the way can verify the sender;
+/// it derefs to the inner `Message` either way.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub enum WireMessage {
+    Plain(Message),
+    Signed(SignedMessage),
+}
+
+impl WireMessage {
+    pub fn message(&self) -> &Message {
+        match self {
+            Self::Plain(message) => message,
+            Self::Signed(signed) => &signed.message,
+        }
+    }
+
+    // Only unsigned fields such as `ttl` may be changed on a signed message
+    fn message_mut(&mut self) -> &mut Message {
+        match self {
+            Self::Plain(message) => message,
+            Self::Signed(signed) => &mut signed.message,
+        }
+    }
+
+    /// Signing key of a signed message
+    pub fn sender(&self) -> Option<&[u8; 32]> {
+        match self {
+            Self::Plain(_) => None,
+            Self::Signed(signed) => Some(&signed.sender_public_key),
+        }
+    }
+}
+
+impl std::ops::Deref for WireMessage {
+    type Target = Message;
+
+    fn deref(&self) -> &Message {
+        self.message()
+    }
+}
+
+impl From<Message> for WireMessage {
+    fn from(message: Message) -> Self {
+        Self::Plain(message)
+    }
+}
+
+impl From<SignedMessage> for WireMessage {
+    fn from(signed: SignedMessage) -> Self {
+        Self::Signed(signed)
+    }
 }
 
 impl Relay {
     fn already_seen(&mut self, id: u64) -> bool {
         if self.seen.contains(&id) { return true; }
         
-        // Military-grade distributed deduplication with chaos resistance
-        // Storage temporarily disabled - always return false
-        // match self.storage.get_with_chaos_distribution(&id.to_be_bytes()) {
-        //     Ok(Some(data)) => { /* check data */ },
-        //     Ok(None) => { /* record miss */ },
-        //     Err(_) => { /* record error */ },
-        // }
-        false
+        // LRU miss: fall back to the persistent dedup store
+        let store = match &self.dedup_store {
+            Some(store) => store,
+            None => return false,
+        };
+        match store.get_seen(id) {
+            Ok(Some(seen_at)) => {
+                let age = dedup::unix_now().saturating_sub(seen_at);
+                if self.dedup_ttl_secs > 0 && age > self.dedup_ttl_secs {
+                    // Expired record; forget it so the id can be relayed again
+                    if let Err(e) = store.remove_seen(id) {
+                        warn!("Failed to expire dedup record {}: {}", id, e);
+                    }
+                    false
+                } else {
+                    self.seen.put(id, Instant::now());
+                    true
+                }
+            }
+            Ok(None) => false,
+            Err(e) => {
+                warn!("Dedup store lookup failed for {}: {}", id, e);
+                false
+            }
+        }
     }
 
     fn record_seen(&mut self, id: u64) {
         self.seen.put(id, Instant::now());
         
-        // Storage temporarily disabled
-        // let key = id.to_be_bytes();
-        // let now = std::time::SystemTime::now().duration_since(std::time::SystemTime::UNIX_EPOCH).unwrap().as_secs().to_be_bytes();
-        // if let Err(e) = self.storage.put_with_distribution(&key, &now) {
-        //     warn!("Failed to persist seen record: {}", e);
-        // }
+        if let Some(store) = &self.dedup_store {
+            if let Err(e) = store.put_seen(id, dedup::unix_now()) {
+                warn!("Failed to persist seen record {}: {}", id, e);
+            }
+        }
     }
 }
 
@@ -64,6 +257,9 @@ pub struct RelayConfig {
     pub dedup_cache: usize,
     pub rate_limit_per_sec: f64,
     pub rate_limit_burst: f64,
+    // Per-source bandwidth quota, a second token bucket charged `data.len()` bytes
+    pub byte_rate_per_sec: f64,
+    pub byte_burst: f64,
     pub loss_probability: f32, // for tests/sims; 0.0 in prod
     // Stage 19 enhancements
     pub max_clients: usize,
@@ -71,6 +267,33 @@ pub struct RelayConfig {
     pub partition_recovery_timeout_ms: u64,
     pub routing_table_size: usize,
     pub connection_timeout_ms: u64,
+    // Drop messages that do not arrive as a valid SignedMessage
+    pub require_signed: bool,
+    // Recent messages kept for re-sending to relays once a partition heals
+    pub partition_buffer_size: usize,
+    // Cap on full-fanout broadcasts per second while partitioned
+    pub partition_fanout_per_sec: f64,
+    // Deliver sequenced messages in per-source `seq` order
+    pub order_by_source: bool,
+    // Out-of-order messages held per source before a gap is skipped
+    pub reorder_window: usize,
+    // How long a gap may hold back later messages before it is skipped
+    pub reorder_timeout_ms: u64,
+    // Origins with reorder state at once; the least recently active is dropped
+    // to make room, and one idle past `reorder_timeout_ms` is dropped anyway
+    pub max_reorder_origins: usize,
+    // Messages queued per peer before backpressure sheds load; None is unbounded
+    pub peer_channel_bound: Option<usize>,
+    // Which message a full peer queue drops
+    pub backpressure_policy: BackpressurePolicy,
+    // Capabilities offered to peers during the admission handshake
+    pub capabilities: Vec<String>,
+    // Identity keys allowed through the handshake; None admits any valid key
+    pub trusted_peer_keys: Option<Vec<[u8; 32]>>,
+    // Messages older than this are dropped instead of relayed; None never expires
+    pub message_max_age: Option<std::time::Duration>,
+    // Peers one aggressive anti-eclipse broadcast reaches; None reaches them all
+    pub anti_eclipse_max_fanout: Option<usize>,
 }
 
 impl Default for RelayConfig {
@@ -79,6 +302,8 @@ impl Default for RelayConfig {
             dedup_cache: 4096, 
             rate_limit_per_sec: 10_000.0, 
             rate_limit_burst: 10_000.0, 
+            byte_rate_per_sec: 64.0 * 1024.0 * 1024.0,
+            byte_burst: 64.0 * 1024.0 * 1024.0,
             loss_probability: 0.0,
             // Stage 19 defaults
             max_clients: 1000,
@@ -86,6 +311,19 @@ impl Default for RelayConfig {
             partition_recovery_timeout_ms: 2000, // 2-block recovery time
             routing_table_size: 10000,
             connection_timeout_ms: 30000,
+            require_signed: false,
+            partition_buffer_size: 64,
+            partition_fanout_per_sec: 100.0,
+            order_by_source: false,
+            reorder_window: 64,
+            reorder_timeout_ms: 500,
+            max_reorder_origins: 4096,
+            peer_channel_bound: None,
+            backpressure_policy: BackpressurePolicy::DropNew,
+            capabilities: handshake::default_capabilities(),
+            trusted_peer_keys: None,
+            message_max_age: None,
+            anti_eclipse_max_fanout: None,
         }
     }
 }
@@ -115,20 +353,206 @@ pub struct AntiEclipseState {
     pub last_relay_broadcast: Instant,
     pub partition_detected: bool,
     pub recovery_start: Option<Instant>,
+    // Messages broadcast while partitioned, re-sent to relays on recovery
+    pub partition_buffer: VecDeque<WireMessage>,
+    // Token bucket limiting aggressive fanout during a partition
+    pub fanout_bucket: (f64, Instant),
+    // Where the next capped aggressive fanout resumes in quality order
+    pub fanout_cursor: usize,
+}
+
+/// Messages dropped by this relay, by reason
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DropCounts {
+    pub dedup: u64,
+    pub rate_limit: u64,
+    pub loss: u64,
+    pub unauthenticated: u64,
+    pub ttl: u64,
+    pub stale_seq: u64,
+    pub byte_limit: u64,
+    #[serde(default)]
+    pub backpressure: u64,
+    #[serde(default)]
+    pub expired: u64,
+}
+
+/// Whose sequence a message is ordered against: its signer when signed, since
+/// copies may arrive through different peers, else the peer it arrived from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum OrderOrigin {
+    Peer(usize),
+    Signer([u8; 32]),
+}
+
+/// Per-origin reordering state: the next `seq` owed, the messages held behind
+/// a gap, each with the peer it arrived from and when it was held, and when
+/// the origin last sent anything
+#[derive(Debug)]
+struct SourceOrder {
+    next_seq: u64,
+    pending: BTreeMap<u64, (WireMessage, usize, Instant)>,
+    last_active: Instant,
+}
+
+impl SourceOrder {
+    fn new() -> Self {
+        Self { next_seq: 0, pending: BTreeMap::new(), last_active: Instant::now() }
+    }
+
+    // Nothing held and nothing heard for `timeout`; forgetting it loses no
+    // messages, and its next message starts it over like a new origin
+    fn is_idle(&self, timeout: std::time::Duration) -> bool {
+        self.pending.is_empty() && self.last_active.elapsed() >= timeout
+    }
+
+    // Pop the contiguous run starting at `next_seq`, with each message's source peer
+    fn release_ready(&mut self) -> Vec<(usize, WireMessage)> {
+        let mut ready = Vec::new();
+        while let Some((msg, source, _)) = self.pending.remove(&self.next_seq) {
+            ready.push((source, msg));
+            self.next_seq += 1;
+        }
+        ready
+    }
+
+    // Give up on the current gap and resume from the lowest held message
+    fn skip_gap(&mut self) -> Vec<(usize, WireMessage)> {
+        if let Some(&lowest) = self.pending.keys().next() {
+            self.next_seq = lowest;
+        }
+        self.release_ready()
+    }
+}
+
+/// Point-in-time view of a single relay, returned by `Relay::snapshot_stats`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelayStatsSnapshot {
+    pub broadcasted: u64,
+    pub dropped: DropCounts,
+    pub peers_connected: usize,
+    pub relay_peers: usize,
+    pub routing_table_size: usize,
+    pub partition_detected: bool,
+}
+
+/// On-disk form of `Relay` dedup, routing and peer state. `Instant`s are
+/// converted to unix seconds so ages survive a restart. Peer indices are only
+/// meaningful within one process, so peers and next hops are stored by peer id.
+#[derive(Debug, Serialize, Deserialize)]
+struct PersistedRelayState {
+    seen: Vec<(u64, u64)>, // (message id, seen at), least recent first
+    routes: Vec<PersistedRoute>,
+    peers: Vec<PersistedPeer>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedRoute {
+    destination: String,
+    next_hop_peer: String, // `PeerInfo::id` of the next hop
+    hop_count: u32,
+    updated_at: u64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct PersistedPeer {
+    id: String,
+    address: SocketAddr,
+    last_seen_at: u64,
+    message_count: u64,
+    is_relay: bool,
+    connection_quality: f64,
+}
+
+// Wall-clock time at which `instant` occurred, and the reverse
+fn instant_to_unix(instant: Instant, now_unix: u64) -> u64 {
+    now_unix.saturating_sub(instant.elapsed().as_secs())
+}
+
+fn unix_to_instant(at: u64, now_unix: u64) -> Instant {
+    let age = std::time::Duration::from_secs(now_unix.saturating_sub(at));
+    Instant::now().checked_sub(age).unwrap_or_else(Instant::now)
+}
+
+// Queue `msg` for one peer, counting the delivery and any message a full
+// queue shed. Returns whether `msg` was queued.
+fn send_to_peer(
+    tx: &PeerSender,
+    msg: WireMessage,
+    metrics: &RelayMetrics,
+    broadcasted: &mut u64,
+    dropped: &mut DropCounts,
+) -> bool {
+    let queued = match tx.send(msg) {
+        Ok(queued) => queued,
+        Err(PeerSendError::Full) => {
+            metrics.drop_backpressure.inc();
+            dropped.backpressure += 1;
+            return false;
+        }
+        Err(PeerSendError::Closed) => return false,
+    };
+    if queued == Queued::DisplacedOldest {
+        metrics.drop_backpressure.inc();
+        dropped.backpressure += 1;
+    }
+    metrics.broadcasted.inc();
+    *broadcasted += 1;
+    true
+}
+
+/// Generator behind simulated loss; opaque to `Debug`
+struct LossRng(Box<dyn RngCore + Send>);
+
+impl std::fmt::Debug for LossRng {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("LossRng")
+    }
 }
 
 #[derive(Debug)]
 pub struct Relay {
-    peers: Vec<Option<mpsc::UnboundedSender<Message>>>,
+    peers: Vec<Option<PeerSender>>,
     paused: HashMap<usize, bool>,
     seen: LruCache<u64, Instant>,
     per_source_buckets: HashMap<usize, (f64, Instant)>,
+    per_source_byte_buckets: HashMap<usize, (f64, Instant)>,
+    peer_rate_multipliers: HashMap<usize, f64>,
     cfg: RelayConfig,
     metrics: &'static RelayMetrics,
     // Stage 19 enhancements
     peer_info: HashMap<usize, PeerInfo>,
+    // Capabilities agreed with each handshake-admitted peer
+    peer_capabilities: HashMap<usize, Vec<String>>,
     routing_table: HashMap<String, RoutingEntry>,
     anti_eclipse: AntiEclipseState,
+    // Key messages are deduplicated under; the id unless replaced
+    dedup_key: Box<dyn DedupKey>,
+    // Persistent dedup backing the in-memory LRU
+    dedup_store: Option<Box<dyn DedupStore>>,
+    dedup_ttl_secs: u64,
+    // Content-addressed blob storage (None unless configured)
+    content_store: Option<ContentStore>,
+    // Stage 47: optional diversity-aware relay selection
+    diversity: Option<RelayDiversityEngine>,
+    // Per-origin reorder buffers (used when `order_by_source` is set)
+    reorder: HashMap<OrderOrigin, SourceOrder>,
+    // Handshake challenges issued and not yet answered
+    challenges: LruCache<HandshakeChallenge, ()>,
+    // Where `shutdown` persists state, if anywhere
+    state_path: Option<PathBuf>,
+    // Restored peers and their routes, by peer id, waiting for that peer to reconnect
+    restored_peers: HashMap<String, PersistedPeer>,
+    restored_routes: HashMap<String, Vec<PersistedRoute>>,
+    // Cleared by `shutdown`; broadcasts are no-ops afterwards
+    operational: bool,
+    // Draws for `loss_probability`; replaceable so loss runs can be reproduced
+    rng: LossRng,
+    // Per-relay counters mirrored from the global metrics
+    broadcasted: u64,
+    // This relay's share of the `peers_current` gauge
+    reported_peers: usize,
+    dropped: DropCounts,
     // Storage temporarily disabled
     // storage: MilitaryStorage,
     // storage_metrics: StorageMetrics,
@@ -137,22 +561,45 @@ pub struct Relay {
 impl Relay {
     pub fn new(cfg: RelayConfig) -> Self {
         let cap = NonZeroUsize::new(cfg.dedup_cache.max(1)).unwrap();
+        let fanout_burst = cfg.partition_fanout_per_sec;
         Self {
             peers: Vec::new(),
             paused: HashMap::new(),
             seen: LruCache::new(cap),
             per_source_buckets: HashMap::new(),
+            per_source_byte_buckets: HashMap::new(),
+            peer_rate_multipliers: HashMap::new(),
             cfg,
             metrics: &METRICS,
             // Stage 19 initialization
             peer_info: HashMap::new(),
+            peer_capabilities: HashMap::new(),
             routing_table: HashMap::new(),
             anti_eclipse: AntiEclipseState {
                 relay_peers: HashMap::new(),
                 last_relay_broadcast: Instant::now(),
                 partition_detected: false,
                 recovery_start: None,
+                partition_buffer: VecDeque::new(),
+                fanout_bucket: (fanout_burst, Instant::now()),
+                fanout_cursor: 0,
             },
+            dedup_key: Box::new(MessageIdKey),
+            dedup_store: None,
+            dedup_ttl_secs: 0,
+            content_store: None,
+            diversity: None,
+            reorder: HashMap::new(),
+            challenges: LruCache::new(NonZeroUsize::new(MAX_PENDING_CHALLENGES).unwrap()),
+            state_path: None,
+            restored_peers: HashMap::new(),
+            restored_routes: HashMap::new(),
+            operational: true,
+            // Entropy-seeded like `thread_rng`, which is not `Send`
+            rng: LossRng(Box::new(rand::rngs::StdRng::from_entropy())),
+            broadcasted: 0,
+            reported_peers: 0,
+            dropped: DropCounts::default(),
             // Storage temporarily disabled
             // storage: MilitaryStorage::new(StorageConfig::default()).expect("Failed to initialize military storage"),
             // storage_metrics: StorageMetrics::new(),
@@ -160,68 +607,129 @@ impl Relay {
     }
 
     // Revolutionary Military-Grade Distributed Storage - Surpasses IPFS/Storj
-    pub fn new_with_distributed_storage<P: AsRef<Path>>(cfg: RelayConfig, _storage_path: P) -> Self {
-        let r = Self::new(cfg);
-        // Storage temporarily disabled for clean build
+    pub fn new_with_distributed_storage<P: AsRef<Path>>(cfg: RelayConfig, storage_path: P) -> Self {
+        let mut r = Self::new(cfg);
+        match ContentStore::open(storage_path) {
+            Ok(store) => r.content_store = Some(store),
+            Err(e) => warn!("Distributed storage unavailable: {}", e),
+        }
         r
     }
     
-    // Simple persistent storage function for binary compatibility
+    /// Relay whose dedup is backed by `store`; records older than `dedup_ttl` seconds
+    /// are treated as unseen (0 disables expiry)
+    pub fn new_with_dedup_store(cfg: RelayConfig, store: Box<dyn DedupStore>, dedup_ttl: u64) -> Self {
+        let mut r = Self::new(cfg);
+        r.dedup_store = Some(store);
+        r.dedup_ttl_secs = dedup_ttl;
+        r
+    }
+
+    /// Dedup under `key` instead of the message id. Set before relaying: keys
+    /// already recorded were derived the old way.
+    pub fn with_dedup_key(mut self, key: impl DedupKey + 'static) -> Self {
+        self.dedup_key = Box::new(key);
+        self
+    }
+
+    /// Write state to `path` with `persist_state` when the relay shuts down
+    pub fn with_state_path(mut self, path: impl Into<PathBuf>) -> Self {
+        self.state_path = Some(path.into());
+        self
+    }
+
+    /// Draw simulated loss from `rng` instead of an entropy-seeded generator;
+    /// pass a seeded rng to get the same delivery pattern on every run
+    pub fn with_rng(mut self, rng: impl RngCore + Send + 'static) -> Self {
+        self.rng = LossRng(Box::new(rng));
+        self
+    }
+
+    /// Relay with sled-backed persistent dedup at `path`
+    #[cfg(feature = "military-storage")]
+    pub fn new_with_persistent<P: AsRef<Path>>(cfg: RelayConfig, path: P, dedup_ttl: u64) -> Self {
+        match dedup::SledDedupStore::open(path) {
+            Ok(store) => Self::new_with_dedup_store(cfg, Box::new(store), dedup_ttl),
+            Err(e) => {
+                warn!("Persistent dedup unavailable, using in-memory only: {}", e);
+                Self::new(cfg)
+            }
+        }
+    }
+
+    /// Without storage support persistent dedup falls back to the in-memory LRU
+    #[cfg(not(feature = "military-storage"))]
     pub fn new_with_persistent<P: AsRef<Path>>(cfg: RelayConfig, _path: P, _dedup_ttl: u64) -> Self {
-        // For now, just use the basic constructor
         Self::new(cfg)
     }
 
     // Revolutionary Distributed Storage Operations - Beyond IPFS/Storj
     
-    /// Store data with military-grade distribution and chaos resistance
-    pub async fn store_distributed(&mut self, key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
-        // Storage temporarily disabled
-        // let content_hash = self.storage.compute_content_hash(&data);
-        // if let Err(e) = self.storage.put_with_distribution(&data_key, &data).await {
-        //     warn!("Storage failed for data key {}: {}", hex::encode(&data_key), e);
-        // }
-        // self.storage_metrics.record_write();
-        // self.storage_metrics.add_stored_bytes(data.len());
-        
-        Ok(Vec::new())
+    /// Store data content-addressed; returns its BLAKE3 hash. Blobs are addressed
+    /// purely by content, `_key` is accepted for API compatibility.
+    pub async fn store_distributed(&mut self, _key: &[u8], data: &[u8]) -> Result<Vec<u8>, String> {
+        let store = self.content_store.as_ref().ok_or("Distributed storage not configured")?;
+        let hash = store.put(data).map_err(|e| format!("Storage failed: {}", e))?;
+        Ok(hash.to_vec())
     }
     
-    /// Retrieve data (temporarily disabled)
+    /// Retrieve data by content hash
     pub async fn retrieve_distributed(&mut self, key: &[u8]) -> Result<Option<Vec<u8>>, String> {
-        // Storage temporarily disabled
-        Ok(None)
+        let store = self.content_store.as_ref().ok_or("Distributed storage not configured")?;
+        store.get(key).map_err(|e| format!("Retrieve failed: {}", e))
     }
     
-    /// Pin data (temporarily disabled)
+    /// Pin data so it survives garbage collection
     pub async fn pin_data(&mut self, key: &[u8], priority: u8) -> Result<(), String> {
-        // Storage temporarily disabled
-        Ok(())
+        let store = self.content_store.as_mut().ok_or("Distributed storage not configured")?;
+        store.pin(key, priority).map_err(|e| format!("Pin failed: {}", e))
     }
     
-    /// Unpin data (temporarily disabled)
+    /// Unpin data, making it eligible for garbage collection
     pub async fn unpin_data(&mut self, key: &[u8]) -> Result<(), String> {
-        // Storage temporarily disabled
-        Ok(())
+        let store = self.content_store.as_mut().ok_or("Distributed storage not configured")?;
+        store.unpin(key).map(|_| ()).map_err(|e| format!("Unpin failed: {}", e))
+    }
+
+    /// Remove every unpinned blob; returns the number removed
+    pub async fn gc_storage(&mut self) -> Result<usize, String> {
+        let store = self.content_store.as_mut().ok_or("Distributed storage not configured")?;
+        store.gc_sweep().map_err(|e| format!("GC failed: {}", e))
     }
     
-    /// Get storage statistics (temporarily disabled)
+    /// Get storage statistics as JSON
     pub fn get_storage_stats(&self) -> String {
-        "Storage temporarily disabled".to_string()
+        match &self.content_store {
+            Some(store) => match store.stats() {
+                Ok(stats) => serde_json::to_string(&stats).unwrap_or_default(),
+                Err(e) => serde_json::json!({ "error": e.to_string() }).to_string(),
+            },
+            None => serde_json::json!({ "error": "Distributed storage not configured" }).to_string(),
+        }
     }
     
-    // Add a peer; returns (peer_id, receiver)
-    pub fn add_peer(&mut self) -> (usize, mpsc::UnboundedReceiver<Message>) {
-        let (tx, rx) = mpsc::unbounded_channel();
+    // Add a peer; returns (peer_id, receiver). After `shutdown` the peer is
+    // refused: nothing is registered and the receiver is already closed.
+    pub fn add_peer(&mut self) -> (usize, PeerReceiver) {
+        let (tx, rx) = peer_channel(self.cfg.peer_channel_bound, self.cfg.backpressure_policy);
         let id = self.peers.len();
+        if !self.operational {
+            warn!("Refusing peer {}: relay is shut down", id);
+            return (id, rx);
+        }
         self.peers.push(Some(tx));
         self.metrics.peers_connected.inc();
+        self.update_peer_gauge();
         (id, rx)
     }
 
     // Stage 19: Add peer with enhanced info
-    pub fn add_peer_with_info(&mut self, peer_info: PeerInfo) -> (usize, mpsc::UnboundedReceiver<Message>) {
+    pub fn add_peer_with_info(&mut self, mut peer_info: PeerInfo) -> (usize, PeerReceiver) {
         let (id, rx) = self.add_peer();
+        if !self.operational {
+            return (id, rx);
+        }
+        self.rebind_restored(id, &mut peer_info);
         self.peer_info.insert(id, peer_info.clone());
         
         // Track relay peers for anti-eclipse
@@ -232,6 +740,53 @@ impl Relay {
         (id, rx)
     }
 
+    /// Fresh challenge for a connecting peer to sign; each is accepted once
+    pub fn handshake_challenge(&mut self) -> HandshakeChallenge {
+        let challenge = rand::random::<HandshakeChallenge>();
+        self.challenges.put(challenge, ());
+        challenge
+    }
+
+    /// Admit a peer through its handshake: the version and signed identity must
+    /// check out, the challenge must come from `handshake_challenge`, the key must
+    /// be trusted when a trust list is set, and the peer counts as a relay only if
+    /// `relay` survives negotiation
+    pub fn admit_peer(&mut self, handshake: PeerHandshake) -> Result<(usize, PeerReceiver), HandshakeRejection> {
+        if !self.operational {
+            return Err(HandshakeRejection::RelayShutDown);
+        }
+        let checked = handshake.validate().and_then(|_| {
+            if self.challenges.pop(&handshake.challenge).is_none() {
+                return Err(HandshakeRejection::UnknownChallenge);
+            }
+            match &self.cfg.trusted_peer_keys {
+                Some(trusted) if !trusted.contains(&handshake.public_key) => Err(HandshakeRejection::UntrustedIdentity),
+                _ => Ok(()),
+            }
+        });
+        if let Err(rejection) = checked {
+            warn!("Refusing peer {}: {}", handshake.peer_id, rejection);
+            return Err(rejection);
+        }
+        let capabilities = handshake.negotiate(&self.cfg.capabilities);
+        let peer_info = PeerInfo {
+            id: handshake.peer_id,
+            address: handshake.address,
+            last_seen: Instant::now(),
+            message_count: 0,
+            is_relay: capabilities.iter().any(|capability| capability == RELAY_CAPABILITY),
+            connection_quality: 1.0,
+        };
+        let (id, rx) = self.add_peer_with_info(peer_info);
+        self.peer_capabilities.insert(id, capabilities);
+        Ok((id, rx))
+    }
+
+    /// Capabilities negotiated with a handshake-admitted peer
+    pub fn peer_capabilities(&self, id: usize) -> Option<&[String]> {
+        self.peer_capabilities.get(&id).map(Vec::as_slice)
+    }
+
     // Stage 19: Update routing table
     pub fn update_routing(&mut self, destination: String, next_hop: usize, hop_count: u32) {
         let entry = RoutingEntry {
@@ -249,7 +804,24 @@ impl Relay {
     }
 
     // Stage 19: Anti-eclipse broadcast to multiple relays
-    pub fn anti_eclipse_broadcast(&mut self, msg: Message) {
+    pub fn anti_eclipse_broadcast(&mut self, msg: impl Into<WireMessage>) {
+        if !self.operational {
+            return;
+        }
+        let msg = msg.into();
+        if self.anti_eclipse.partition_detected {
+            // Partitioned - fan out to every peer while the rate limit allows and
+            // keep the message for the relays; this is not relay contact, so the
+            // partition timer is left alone
+            self.buffer_for_recovery(&msg);
+            if self.take_fanout_token() {
+                self.broadcast_to_all_peers(msg);
+            } else {
+                self.broadcast_to_relay_peers(msg);
+            }
+            return;
+        }
+
         let relay_count = self.anti_eclipse.relay_peers.len();
         
         if relay_count < self.cfg.anti_eclipse_min_relays {
@@ -263,6 +835,39 @@ impl Relay {
         self.anti_eclipse.last_relay_broadcast = Instant::now();
     }
 
+    // Keep the most recent partition-time messages, oldest evicted first
+    fn buffer_for_recovery(&mut self, msg: &WireMessage) {
+        if self.cfg.partition_buffer_size == 0 { return; }
+        let buffer = &mut self.anti_eclipse.partition_buffer;
+        if buffer.len() >= self.cfg.partition_buffer_size {
+            buffer.pop_front();
+        }
+        buffer.push_back(msg.clone());
+    }
+
+    // Aggressive fanout is rate limited so a partition cannot amplify a storm
+    fn take_fanout_token(&mut self) -> bool {
+        let now = Instant::now();
+        let rate = self.cfg.partition_fanout_per_sec;
+        let (tokens, last) = &mut self.anti_eclipse.fanout_bucket;
+        let elapsed = now.saturating_duration_since(*last).as_secs_f64();
+        *tokens = (*tokens + elapsed * rate).min(rate);
+        *last = now;
+        if *tokens < 1.0 {
+            return false;
+        }
+        *tokens -= 1.0;
+        true
+    }
+
+    // Re-send everything buffered during the partition to the returning relays
+    fn resend_partition_buffer(&mut self) {
+        let buffered: Vec<WireMessage> = self.anti_eclipse.partition_buffer.drain(..).collect();
+        for msg in buffered {
+            self.broadcast_to_relay_peers(msg);
+        }
+    }
+
     // Stage 19: Detect and handle network partitions
     pub fn check_partition_recovery(&mut self) -> bool {
         let now = Instant::now();
@@ -285,6 +890,7 @@ impl Relay {
                     // Partition recovered
                     self.anti_eclipse.partition_detected = false;
                     self.anti_eclipse.recovery_start = None;
+                    self.resend_partition_buffer();
                     return true; // Recovery successful
                 } else if recovery_time > timeout_ms * 2 {
                     // Recovery failed, reset
@@ -297,24 +903,44 @@ impl Relay {
     }
 
     // Stage 19: Helper methods for anti-eclipse broadcasting
-    fn broadcast_to_all_peers(&mut self, msg: Message) {
-        for (i, peer_opt) in self.peers.iter().enumerate() {
-            if let Some(peer) = peer_opt {
-                if !self.paused.get(&i).unwrap_or(&false) {
-                    let _ = peer.send(msg.clone());
-                    self.metrics.broadcasted.inc();
-                }
+    fn broadcast_to_all_peers(&mut self, msg: WireMessage) {
+        for peer_id in self.aggressive_fanout_targets() {
+            if let Some(Some(peer)) = self.peers.get(peer_id) {
+                send_to_peer(peer, msg.clone(), self.metrics, &mut self.broadcasted, &mut self.dropped);
             }
         }
     }
 
-    fn broadcast_to_relay_peers(&mut self, msg: Message) {
+    // Connected, unpaused peers for an aggressive broadcast. Under
+    // `anti_eclipse_max_fanout` the best-connected peers go first and each call
+    // resumes where the previous one stopped, so every peer is reached over
+    // successive rounds.
+    fn aggressive_fanout_targets(&mut self) -> Vec<usize> {
+        let mut candidates: Vec<usize> = self.peers.iter().enumerate()
+            .filter(|(i, peer)| peer.is_some() && !self.paused.get(i).copied().unwrap_or(false))
+            .map(|(i, _)| i)
+            .collect();
+        let max_fanout = match self.cfg.anti_eclipse_max_fanout {
+            Some(max_fanout) if candidates.len() > max_fanout => max_fanout.max(1),
+            _ => return candidates,
+        };
+
+        let quality = |id: &usize| self.peer_info.get(id).map_or(0.0, |info| info.connection_quality);
+        candidates.sort_by(|a, b| {
+            quality(b).partial_cmp(&quality(a)).unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.cmp(b))
+        });
+        let start = self.anti_eclipse.fanout_cursor % candidates.len();
+        self.anti_eclipse.fanout_cursor = (start + max_fanout) % candidates.len();
+        candidates.iter().cycle().skip(start).take(max_fanout).copied().collect()
+    }
+
+    fn broadcast_to_relay_peers(&mut self, msg: WireMessage) {
         for (peer_id, peer_info) in &self.peer_info {
             if peer_info.is_relay {
                 if let Some(Some(peer)) = self.peers.get(*peer_id) {
                     if !self.paused.get(peer_id).unwrap_or(&false) {
-                        let _ = peer.send(msg.clone());
-                        self.metrics.broadcasted.inc();
+                        send_to_peer(peer, msg.clone(), self.metrics, &mut self.broadcasted, &mut self.dropped);
                     }
                 }
             }
@@ -323,7 +949,7 @@ impl Relay {
 
     fn cleanup_routing_table(&mut self) {
         let now = Instant::now();
-        let max_age = std::time::Duration::from_secs(300); // 5 minutes
+        let max_age = std::time::Duration::from_secs(ROUTE_MAX_AGE_SECS);
         
         self.routing_table.retain(|_, entry| {
             now.duration_since(entry.last_updated) < max_age
@@ -352,11 +978,138 @@ impl Relay {
         )
     }
 
+    /// Counters for this relay instance, independent of the global Prometheus registry
+    pub fn snapshot_stats(&self) -> RelayStatsSnapshot {
+        RelayStatsSnapshot {
+            broadcasted: self.broadcasted,
+            dropped: self.dropped.clone(),
+            peers_connected: self.peers.iter().filter(|p| p.is_some()).count(),
+            relay_peers: self.anti_eclipse.relay_peers.len(),
+            routing_table_size: self.routing_table.len(),
+            partition_detected: self.anti_eclipse.partition_detected,
+        }
+    }
+
+    /// Write the seen-id set, routing table and peer info to `path`, zstd-compressed,
+    /// so a restarted relay keeps deduplicating and routing. Peers are written by
+    /// id; disconnected peers and routes through them are left out, while restored
+    /// peers that have not reconnected yet are carried over.
+    pub fn persist_state<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<()> {
+        let now = dedup::unix_now();
+        let live = |index: &usize| matches!(self.peers.get(*index), Some(Some(_)));
+        let mut peers: Vec<PersistedPeer> = self.peer_info.iter()
+            .filter(|(index, _)| live(index))
+            .map(|(_, info)| PersistedPeer {
+                id: info.id.clone(),
+                address: info.address,
+                last_seen_at: instant_to_unix(info.last_seen, now),
+                message_count: info.message_count,
+                is_relay: info.is_relay,
+                connection_quality: info.connection_quality,
+            })
+            .collect();
+        let mut routes: Vec<PersistedRoute> = self.routing_table.values()
+            .filter(|entry| live(&entry.next_hop))
+            .filter_map(|entry| {
+                let next_hop_peer = self.peer_info.get(&entry.next_hop)?.id.clone();
+                Some(PersistedRoute {
+                    destination: entry.destination.clone(),
+                    next_hop_peer,
+                    hop_count: entry.hop_count,
+                    updated_at: instant_to_unix(entry.last_updated, now),
+                })
+            })
+            .collect();
+        peers.extend(self.restored_peers.values().cloned());
+        routes.extend(self.restored_routes.values().flatten().cloned());
+
+        let state = PersistedRelayState {
+            // LruCache iterates most recent first; store oldest first so restore keeps the order
+            seen: self.seen.iter().rev().map(|(id, at)| (*id, instant_to_unix(*at, now))).collect(),
+            routes,
+            peers,
+        };
+
+        let encoded = bincode::serialize(&state)?;
+        let compressed = zstd::encode_all(encoded.as_slice(), STATE_COMPRESSION_LEVEL)?;
+        std::fs::write(path, compressed)?;
+        Ok(())
+    }
+
+    /// Load state written by `persist_state`. Seen ids older than the dedup TTL
+    /// and routes older than the routing max age are skipped. Peers and their
+    /// routes are held until a peer with the same id is added through
+    /// `add_peer_with_info`, which then gets their stats and routes.
+    pub fn restore_state<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
+        let compressed = std::fs::read(path)?;
+        let encoded = zstd::decode_all(compressed.as_slice())?;
+        let state: PersistedRelayState = bincode::deserialize(&encoded)?;
+        let now = dedup::unix_now();
+
+        for (id, seen_at) in state.seen {
+            if self.dedup_ttl_secs > 0 && now.saturating_sub(seen_at) > self.dedup_ttl_secs {
+                continue;
+            }
+            self.seen.put(id, unix_to_instant(seen_at, now));
+        }
+
+        for route in state.routes {
+            if now.saturating_sub(route.updated_at) >= ROUTE_MAX_AGE_SECS {
+                continue;
+            }
+            self.restored_routes.entry(route.next_hop_peer.clone()).or_default().push(route);
+        }
+
+        for peer in state.peers {
+            self.restored_peers.insert(peer.id.clone(), peer);
+        }
+
+        // Peers already connected pick their state up straight away
+        let connected: Vec<usize> = self.peer_info.keys().copied().collect();
+        for index in connected {
+            if let Some(mut info) = self.peer_info.remove(&index) {
+                self.rebind_restored(index, &mut info);
+                self.peer_info.insert(index, info);
+            }
+        }
+
+        Ok(())
+    }
+
+    // Hand restored stats and routes for `info.id` to the peer now at `index`
+    fn rebind_restored(&mut self, index: usize, info: &mut PeerInfo) {
+        if let Some(peer) = self.restored_peers.remove(&info.id) {
+            info.message_count = info.message_count.max(peer.message_count);
+            info.connection_quality = peer.connection_quality;
+        }
+        let now = dedup::unix_now();
+        for route in self.restored_routes.remove(&info.id).unwrap_or_default() {
+            if now.saturating_sub(route.updated_at) >= ROUTE_MAX_AGE_SECS
+                || self.routing_table.contains_key(&route.destination)
+            {
+                continue;
+            }
+            self.routing_table.insert(route.destination.clone(), RoutingEntry {
+                destination: route.destination,
+                next_hop: index,
+                hop_count: route.hop_count,
+                last_updated: unix_to_instant(route.updated_at, now),
+            });
+        }
+    }
+
     pub fn remove_peer(&mut self, id: usize) {
         if id < self.peers.len() {
             self.peers[id] = None;
+            self.update_peer_gauge();
             self.paused.remove(&id);
+            self.per_source_buckets.remove(&id);
+            self.per_source_byte_buckets.remove(&id);
+            self.peer_rate_multipliers.remove(&id);
+            self.reorder.remove(&OrderOrigin::Peer(id));
             
+            self.peer_capabilities.remove(&id);
+
             // Stage 19: Remove from enhanced tracking
             if let Some(peer_info) = self.peer_info.remove(&id) {
                 if peer_info.is_relay {
@@ -366,6 +1119,52 @@ impl Relay {
         }
     }
 
+    /// Stop relaying: state is persisted to the `with_state_path` file, every
+    /// peer sender is dropped so receivers see the channel close, routing and
+    /// anti-eclipse state is cleared, and the persistent dedup store is flushed.
+    /// Later broadcasts are no-ops and new peers are refused.
+    pub fn shutdown(&mut self) {
+        if !self.operational {
+            return;
+        }
+        self.operational = false;
+
+        if let Some(path) = &self.state_path {
+            if let Err(e) = self.persist_state(path) {
+                warn!("Failed to persist relay state on shutdown: {}", e);
+            }
+        }
+
+        for peer in self.peers.iter_mut() {
+            *peer = None;
+        }
+        self.update_peer_gauge();
+        self.paused.clear();
+        self.per_source_buckets.clear();
+        self.per_source_byte_buckets.clear();
+        self.peer_rate_multipliers.clear();
+        self.reorder.clear();
+        self.peer_capabilities.clear();
+        self.peer_info.clear();
+
+        self.routing_table.clear();
+        self.anti_eclipse.relay_peers.clear();
+        self.anti_eclipse.partition_buffer.clear();
+        self.anti_eclipse.partition_detected = false;
+        self.anti_eclipse.recovery_start = None;
+
+        if let Some(store) = &self.dedup_store {
+            if let Err(e) = store.flush() {
+                warn!("Failed to flush dedup store on shutdown: {}", e);
+            }
+        }
+    }
+
+    /// False once `shutdown` has run
+    pub fn is_operational(&self) -> bool {
+        self.operational
+    }
+
     pub fn pause_peer(&mut self, id: usize) { self.paused.insert(id, true); }
     pub fn resume_peer(&mut self, id: usize) { self.paused.insert(id, false); }
 
@@ -374,6 +1173,10 @@ impl Relay {
         if let Some(peer_info) = self.peer_info.get_mut(&id) {
             peer_info.last_seen = Instant::now();
             peer_info.message_count += 1;
+            // Hearing from a relay is what ends a partition
+            if peer_info.is_relay {
+                self.anti_eclipse.last_relay_broadcast = peer_info.last_seen;
+            }
         }
     }
 
@@ -381,55 +1184,354 @@ impl Relay {
         self.peer_info.get(&id)
     }
 
-    fn rate_limited(&mut self, source: usize) -> bool {
-        let now = Instant::now();
-        let rate = self.cfg.rate_limit_per_sec as f64;
-        let burst = self.cfg.rate_limit_burst as f64;
-        let entry = self.per_source_buckets.entry(source).or_insert((burst, now));
-        let (tokens, last) = entry;
+    /// Override the rate-limit scaling for a peer; takes precedence over its
+    /// connection quality. `factor` multiplies both rate and burst.
+    pub fn set_peer_rate_multiplier(&mut self, id: usize, factor: f64) {
+        self.peer_rate_multipliers.insert(id, factor.max(0.0));
+    }
+
+    // Token-bucket scale for a source: manual override, else derived from
+    // connection quality (0.5 is neutral), else the plain config values
+    fn rate_multiplier(&self, source: usize) -> f64 {
+        if let Some(factor) = self.peer_rate_multipliers.get(&source) {
+            return *factor;
+        }
+        match self.peer_info.get(&source) {
+            Some(info) => (info.connection_quality.clamp(0.0, 1.0) * 2.0).max(0.1),
+            None => 1.0,
+        }
+    }
+
+    // Refill a token bucket up to `burst` for the time since it was last touched
+    fn refill(buckets: &mut HashMap<usize, (f64, Instant)>, source: usize, rate: f64, burst: f64, now: Instant) -> &mut f64 {
+        let (tokens, last) = buckets.entry(source).or_insert((burst, now));
         let elapsed = now.saturating_duration_since(*last).as_secs_f64();
-        // refill
-        let added = elapsed * rate;
-        *tokens = (*tokens + added).min(burst);
+        *tokens = (*tokens + elapsed * rate).min(burst);
         *last = now;
-        if *tokens < 1.0 {
+        tokens
+    }
+
+    // A message must fit both the message-rate and the byte budget; neither
+    // bucket is charged unless both have room
+    fn rate_limited(&mut self, source: usize, bytes: usize) -> bool {
+        let now = Instant::now();
+        let multiplier = self.rate_multiplier(source);
+        let rate = self.cfg.rate_limit_per_sec * multiplier;
+        let burst = self.cfg.rate_limit_burst * multiplier;
+        let byte_rate = self.cfg.byte_rate_per_sec * multiplier;
+        let byte_burst = self.cfg.byte_burst * multiplier;
+
+        let message_tokens = *Self::refill(&mut self.per_source_buckets, source, rate, burst, now);
+        if message_tokens < 1.0 {
             self.metrics.drop_rate_limit.inc();
+            self.dropped.rate_limit += 1;
             return true;
         }
-        *tokens -= 1.0;
+        let byte_tokens = Self::refill(&mut self.per_source_byte_buckets, source, byte_rate, byte_burst, now);
+        if *byte_tokens < bytes as f64 {
+            self.metrics.drop_byte_limit.inc();
+            self.dropped.byte_limit += 1;
+            return true;
+        }
+        *byte_tokens -= bytes as f64;
+        if let Some((tokens, _)) = self.per_source_buckets.get_mut(&source) {
+            *tokens -= 1.0;
+        }
         false
     }
 
-    // Broadcast a message injected by source peer id. Dedup on message id.
-    pub fn broadcast_from(&mut self, source: usize, msg: Message) {
+    // Dedup and rate-limit admission shared by all broadcast paths
+    fn admit(&mut self, source: usize, msg: &Message, sender: Option<&[u8; 32]>) -> bool {
         // Dedup (memory + optional persistent store)
-        if self.already_seen(msg.id) {
+        let key = self.dedup_key.key(msg, sender);
+        if self.already_seen(key) {
             self.metrics.drop_dedup.inc();
-            return;
+            self.dropped.dedup += 1;
+            return false;
         }
-        self.record_seen(msg.id);
+        self.record_seen(key);
 
         // Rate limit per source
-        if self.rate_limited(source) {
+        !self.rate_limited(source, msg.data.len())
+    }
+
+    // Broadcast a message injected by source peer id. Dedup on the `DedupKey`.
+    pub fn broadcast_from(&mut self, source: usize, msg: Message) {
+        if !self.operational {
             return;
         }
+        let msg = WireMessage::Plain(msg);
+        if self.authenticated(&msg) {
+            self.relay_admitted(source, msg);
+        }
+    }
 
-        let mut rng = rand::thread_rng();
-        for (peer_id, maybe_tx) in self.peers.iter().enumerate() {
-            if peer_id == source { continue; }
-            if self.paused.get(&peer_id).copied().unwrap_or(false) { continue; }
-            if let Some(tx) = maybe_tx {
-                // Simulate loss (for tests only)
-                if self.cfg.loss_probability > 0.0 {
-                    let p: f32 = rng.gen();
+    /// Broadcast a signed message; forgeries are dropped and counted as
+    /// unauthenticated. Peers receive the envelope unchanged.
+    pub fn broadcast_signed(&mut self, source: usize, signed: SignedMessage) {
+        if !self.operational {
+            return;
+        }
+        let msg = WireMessage::Signed(signed);
+        if self.authenticated(&msg) {
+            self.relay_admitted(source, msg);
+        }
+    }
+
+    // A signed message must verify; an unsigned one passes only while
+    // signatures are optional. Failures count as unauthenticated drops.
+    fn authenticated(&mut self, msg: &WireMessage) -> bool {
+        let authenticated = match msg {
+            WireMessage::Plain(_) => !self.cfg.require_signed,
+            WireMessage::Signed(signed) => signed.verify(),
+        };
+        if !authenticated {
+            self.metrics.drop_unauthenticated.inc();
+            self.dropped.unauthenticated += 1;
+        }
+        authenticated
+    }
+
+    /// Broadcast a message as it arrived from a peer, signed or not
+    pub fn receive(&mut self, source: usize, msg: WireMessage) {
+        match msg {
+            WireMessage::Plain(msg) => self.broadcast_from(source, msg),
+            WireMessage::Signed(signed) => self.broadcast_signed(source, signed),
+        }
+    }
+
+    // Messages past `message_max_age` are counted and dropped
+    fn expired(&mut self, msg: &Message) -> bool {
+        let expired = self.cfg.message_max_age.is_some_and(|max_age| msg.is_expired(max_age, dedup::unix_now()));
+        if expired {
+            self.metrics.drop_expired.inc();
+            self.dropped.expired += 1;
+        }
+        expired
+    }
+
+    fn relay_admitted(&mut self, source: usize, msg: WireMessage) {
+        if self.expired(&msg) {
+            return;
+        }
+        if !self.admit(source, msg.message(), msg.sender()) {
+            return;
+        }
+        if self.anti_eclipse.partition_detected {
+            self.buffer_for_recovery(&msg);
+        }
+        let _timer = self.metrics.broadcast_duration.start_timer();
+        for (source, msg) in self.sequence(source, msg) {
+            self.flood(Some(source), &msg);
+        }
+    }
+
+    // Order a message against its origin's sequence, returning whatever is now
+    // deliverable with the peer each message arrived from. Unsequenced messages,
+    // or any message when ordering is off, pass through.
+    fn sequence(&mut self, source: usize, msg: WireMessage) -> Vec<(usize, WireMessage)> {
+        let seq = match msg.seq {
+            Some(seq) if self.cfg.order_by_source => seq,
+            _ => return vec![(source, msg)],
+        };
+        let timeout = std::time::Duration::from_millis(self.cfg.reorder_timeout_ms);
+        let window = self.cfg.reorder_window.max(1);
+        let origin = match msg.sender() {
+            Some(signer) => OrderOrigin::Signer(*signer),
+            None => OrderOrigin::Peer(source),
+        };
+        let mut ready = Vec::new();
+        if !self.reorder.contains_key(&origin) {
+            ready.extend(self.make_room_for_origin(timeout));
+        }
+        let order = self.reorder.entry(origin).or_insert_with(SourceOrder::new);
+        order.last_active = Instant::now();
+
+        if seq < order.next_seq {
+            // Its slot was already skipped or delivered
+            self.metrics.drop_stale_seq.inc();
+            self.dropped.stale_seq += 1;
+            return ready;
+        }
+        order.pending.insert(seq, (msg, source, Instant::now()));
+
+        ready.extend(order.release_ready());
+        let gap_expired = order.pending.values().next()
+            .map(|(_, _, held_at)| held_at.elapsed() >= timeout)
+            .unwrap_or(false);
+        if order.pending.len() > window || gap_expired {
+            ready.extend(order.skip_gap());
+        }
+        ready
+    }
+
+    // Keep the tracked origins under `max_reorder_origins` before another is
+    // added: idle origins go first, then the least recently active one, whose
+    // held messages are released as if their gaps had timed out
+    fn make_room_for_origin(&mut self, timeout: std::time::Duration) -> Vec<(usize, WireMessage)> {
+        let cap = self.cfg.max_reorder_origins.max(1);
+        if self.reorder.len() < cap {
+            return Vec::new();
+        }
+        self.reorder.retain(|_, order| !order.is_idle(timeout));
+        let mut released = Vec::new();
+        while self.reorder.len() >= cap {
+            let oldest = self.reorder.iter()
+                .min_by_key(|(_, order)| order.last_active)
+                .map(|(origin, _)| *origin);
+            if let Some(mut order) = oldest.and_then(|origin| self.reorder.remove(&origin)) {
+                while !order.pending.is_empty() {
+                    released.extend(order.skip_gap());
+                }
+            }
+        }
+        released
+    }
+
+    /// Release messages held behind gaps older than `reorder_timeout_ms` so a
+    /// permanently lost message cannot stall its origin, and forget origins
+    /// idle for as long. `net::QuicServer` calls it on a timer; relays driven
+    /// by hand must call it periodically.
+    pub fn poll_reorder_timeouts(&mut self) {
+        let timeout = std::time::Duration::from_millis(self.cfg.reorder_timeout_ms);
+        let mut released = Vec::new();
+        for order in self.reorder.values_mut() {
+            while order.pending.values().any(|(_, _, held_at)| held_at.elapsed() >= timeout) {
+                released.extend(order.skip_gap());
+                // Its stream resumes from here, so it is not idle yet
+                order.last_active = Instant::now();
+            }
+        }
+        self.reorder.retain(|_, order| !order.is_idle(timeout));
+        for (source, msg) in released {
+            self.flood(Some(source), &msg);
+        }
+    }
+
+    /// Send `msg`, received from peer `source`, toward `destination` via its
+    /// routing-table next hop, decrementing the TTL. It is authenticated,
+    /// deduplicated and rate limited like a broadcast first. Floods to every
+    /// peer but `source` when there is no usable route.
+    pub fn route_to(&mut self, source: usize, destination: &str, msg: impl Into<WireMessage>) {
+        if !self.operational {
+            return;
+        }
+        let mut msg = msg.into();
+        if !self.authenticated(&msg) || self.expired(&msg) {
+            return;
+        }
+        if msg.ttl == 0 {
+            self.metrics.drop_ttl.inc();
+            self.dropped.ttl += 1;
+            return;
+        }
+        if !self.admit(source, msg.message(), msg.sender()) {
+            return;
+        }
+        msg.message_mut().ttl -= 1;
+
+        let next_hop = self.routing_table.get(destination)
+            .map(|entry| entry.next_hop)
+            .filter(|next_hop| *next_hop != source);
+        if let Some(peer_id) = next_hop {
+            let paused = self.paused.get(&peer_id).copied().unwrap_or(false);
+            if let Some(Some(tx)) = self.peers.get(peer_id) {
+                if !paused && send_to_peer(tx, msg.clone(), self.metrics, &mut self.broadcasted, &mut self.dropped) {
+                    return;
+                }
+            }
+        }
+
+        self.flood(Some(source), &msg);
+    }
+
+    // The gauge is shared by every relay in the process, so each one adds
+    // only the change in its own peer count
+    fn update_peer_gauge(&mut self) {
+        let connected = self.peers.iter().filter(|p| p.is_some()).count();
+        self.metrics.peers_current.add(connected as f64 - self.reported_peers as f64);
+        self.reported_peers = connected;
+    }
+
+    // Deliver to every connected, unpaused peer except `source`
+    fn flood(&mut self, source: Option<usize>, msg: &WireMessage) {
+        for (peer_id, maybe_tx) in self.peers.iter().enumerate() {
+            if Some(peer_id) == source { continue; }
+            if self.paused.get(&peer_id).copied().unwrap_or(false) { continue; }
+            if let Some(tx) = maybe_tx {
+                // Simulate loss (for tests only)
+                if self.cfg.loss_probability > 0.0 {
+                    let p: f32 = self.rng.0.gen();
                     if p < self.cfg.loss_probability {
                         self.metrics.drop_loss.inc();
+                        self.dropped.loss += 1;
                         continue;
                     }
                 }
-                if tx.send(Message { id: msg.id, data: msg.data.clone() }).is_ok() {
-                    self.metrics.broadcasted.inc();
-                }
+                send_to_peer(tx, msg.clone(), self.metrics, &mut self.broadcasted, &mut self.dropped);
+            }
+        }
+    }
+
+    // Stage 47: Diversity-aware relay selection
+    pub fn set_diversity_engine(&mut self, engine: RelayDiversityEngine) {
+        self.diversity = Some(engine);
+    }
+
+    pub fn diversity_engine(&self) -> Option<&RelayDiversityEngine> {
+        self.diversity.as_ref()
+    }
+
+    pub fn diversity_engine_mut(&mut self) -> Option<&mut RelayDiversityEngine> {
+        self.diversity.as_mut()
+    }
+
+    /// Broadcast only to the diverse, healthy relay set chosen by the diversity engine.
+    /// Falls back to full fanout when there is no engine or it cannot supply
+    /// `min_asn_diversity` relays.
+    pub fn broadcast_diverse(&mut self, source: usize, msg: Message) {
+        if !self.operational {
+            return;
+        }
+        if self.cfg.require_signed {
+            self.metrics.drop_unauthenticated.inc();
+            self.dropped.unauthenticated += 1;
+            return;
+        }
+        let selected = match &self.diversity {
+            Some(engine) => {
+                let selected = engine.select_routing_relays(engine.active_relays.len());
+                if selected.len() >= engine.policy.min_asn_diversity { Some(selected) } else { None }
+            }
+            None => None,
+        };
+        let selected = match selected {
+            Some(selected) => selected,
+            None => return self.broadcast_from(source, msg),
+        };
+
+        if !self.admit(source, &msg, None) {
+            return;
+        }
+        let msg = WireMessage::Plain(msg);
+
+        let mut deliveries = Vec::new();
+        for (peer_id, info) in &self.peer_info {
+            if *peer_id == source || !selected.contains(&info.id) { continue; }
+            if self.paused.get(peer_id).copied().unwrap_or(false) { continue; }
+            if let Some(Some(tx)) = self.peers.get(*peer_id) {
+                let delivered = send_to_peer(tx, msg.clone(), self.metrics, &mut self.broadcasted, &mut self.dropped);
+                deliveries.push((info.id.clone(), delivered));
+            }
+        }
+
+        // Feed delivery outcomes back into relay health
+        if let Some(engine) = self.diversity.as_mut() {
+            for (relay_id, delivered) in deliveries {
+                let latency_ms = engine.active_relays.get(&relay_id)
+                    .map(|r| r.health.latency_ms)
+                    .unwrap_or_default();
+                engine.update_relay_health(&relay_id, latency_ms, delivered);
             }
         }
     }
@@ -443,29 +1545,89 @@ struct RelayMetrics {
     drop_dedup: Counter,
     drop_rate_limit: Counter,
     drop_loss: Counter,
+    drop_unauthenticated: Counter,
+    drop_ttl: Counter,
+    drop_stale_seq: Counter,
+    drop_byte_limit: Counter,
+    drop_backpressure: Counter,
+    drop_expired: Counter,
+    broadcast_duration: Histogram,
+    peers_current: Gauge,
 }
 
 impl RelayMetrics {
+    // Registered once through the static below so `/metrics` serves them
     fn init() -> Self {
-        // Use unregistered counters to avoid registration conflicts in tests
+        let metrics = Self::new();
+        let collectors: Vec<Box<dyn prometheus::core::Collector>> = vec![
+            Box::new(metrics.broadcasted.clone()),
+            Box::new(metrics.messages_relayed.clone()),
+            Box::new(metrics.peers_connected.clone()),
+            Box::new(metrics.drop_dedup.clone()),
+            Box::new(metrics.drop_rate_limit.clone()),
+            Box::new(metrics.drop_loss.clone()),
+            Box::new(metrics.drop_unauthenticated.clone()),
+            Box::new(metrics.drop_ttl.clone()),
+            Box::new(metrics.drop_stale_seq.clone()),
+            Box::new(metrics.drop_byte_limit.clone()),
+            Box::new(metrics.drop_backpressure.clone()),
+            Box::new(metrics.drop_expired.clone()),
+            Box::new(metrics.broadcast_duration.clone()),
+            Box::new(metrics.peers_current.clone()),
+        ];
+        for collector in collectors {
+            if let Err(e) = prometheus::register(collector) {
+                warn!("Failed to register relay metric: {}", e);
+            }
+        }
+        metrics
+    }
+
+    fn new() -> Self {
         let broadcasted = Counter::new("relay_broadcasted_total", "Messages delivered to peers").unwrap();
         let messages_relayed = Counter::new("relay_messages_total", "Total messages relayed").unwrap();
         let peers_connected = Counter::new("relay_peers_connected", "Total peers connected").unwrap();
         let drop_dedup = Counter::new("relay_drop_dedup_total", "Messages dropped due to dedup").unwrap();
         let drop_rate_limit = Counter::new("relay_drop_rate_limit_total", "Messages dropped due to rate limiting").unwrap();
         let drop_loss = Counter::new("relay_drop_loss_total", "Messages dropped due to simulated loss").unwrap();
-        
+        let drop_unauthenticated = Counter::new("relay_drop_unauthenticated_total", "Messages dropped for missing or invalid signatures").unwrap();
+        let drop_ttl = Counter::new("relay_drop_ttl_total", "Messages dropped after exhausting their TTL").unwrap();
+        let drop_byte_limit = Counter::new("relay_drop_byte_limit_total", "Messages dropped for exceeding the per-source byte budget").unwrap();
+        let drop_stale_seq = Counter::new("relay_drop_stale_seq_total", "Sequenced messages dropped after their slot was skipped").unwrap();
+        let drop_backpressure = Counter::new("relay_drop_backpressure_total", "Messages shed because a peer's queue was full").unwrap();
+        let drop_expired = Counter::new("relay_drop_expired_total", "Messages dropped for exceeding the configured max age").unwrap();
+        let broadcast_duration = Histogram::with_opts(HistogramOpts::new(
+            "relay_broadcast_duration_seconds",
+            "Time spent fanning an admitted message out to peers",
+        )).unwrap();
+        let peers_current = Gauge::new("relay_peers_current", "Peers currently connected across all relays in the process").unwrap();
+
         Self { 
             broadcasted, 
             messages_relayed, 
             peers_connected, 
             drop_dedup, 
             drop_rate_limit, 
-            drop_loss 
+            drop_loss,
+            drop_unauthenticated,
+            drop_ttl,
+            drop_stale_seq,
+            drop_byte_limit,
+            drop_backpressure,
+            drop_expired,
+            broadcast_duration,
+            peers_current,
         }
     }
 }
 
+// A dropped relay's peers are gone too
+impl Drop for Relay {
+    fn drop(&mut self) {
+        self.metrics.peers_current.sub(self.reported_peers as f64);
+    }
+}
+
 static METRICS: Lazy<RelayMetrics> = Lazy::new(|| RelayMetrics::init());
 
 // --- HTTP metrics & health endpoints ---
@@ -524,34 +1686,248 @@ pub mod net {
     use std::net::{IpAddr, Ipv4Addr, SocketAddr};
     use tokio::task::JoinHandle;
 
+    const ALPN_RELAY: &[u8] = b"mesh/relay/1";
+
+    /// How a client decides whether to trust the relay it connects to
+    #[derive(Clone)]
+    pub enum ClientTrust {
+        /// Trust exactly this certificate (self-signed test setups)
+        PinnedCert(rustls::Certificate),
+        /// Validate the server chain against a set of CA roots
+        CaRoots(rustls::RootCertStore),
+    }
+
+    impl ClientTrust {
+        /// Pin a self-signed rcgen certificate
+        pub fn pinned(cert: &Certificate) -> Result<Self> {
+            Ok(Self::PinnedCert(rustls::Certificate(cert.serialize_der()?)))
+        }
+
+        fn root_store(&self) -> Result<rustls::RootCertStore> {
+            match self {
+                Self::PinnedCert(cert) => {
+                    let mut roots = rustls::RootCertStore::empty();
+                    roots.add(cert)?;
+                    Ok(roots)
+                }
+                Self::CaRoots(roots) => Ok(roots.clone()),
+            }
+        }
+    }
+
+    /// Certificate chain and key a relay presents to its peer
+    #[derive(Clone)]
+    pub struct TlsIdentity {
+        pub cert_chain: Vec<rustls::Certificate>,
+        pub key: rustls::PrivateKey,
+    }
+
+    impl TlsIdentity {
+        /// Identity from a single self-signed rcgen certificate
+        pub fn self_signed(cert: &Certificate) -> Result<Self> {
+            Ok(Self {
+                cert_chain: vec![rustls::Certificate(cert.serialize_der()?)],
+                key: rustls::PrivateKey(cert.serialize_private_key_der()),
+            })
+        }
+    }
+
+    /// Mutual TLS policy: client certs must chain to `roots` and, when
+    /// `allowed_identities` is non-empty, be valid for one of those relay names
+    #[derive(Clone)]
+    pub struct ClientAuth {
+        pub roots: rustls::RootCertStore,
+        pub allowed_identities: Vec<String>,
+    }
+
+    /// Server-side TLS options for `QuicServer::bind_and_run_with_options`
+    #[derive(Clone)]
+    pub struct ServerTlsOptions {
+        pub identity: TlsIdentity,
+        pub client_auth: Option<ClientAuth>,
+    }
+
+    /// Client-side TLS options for `QuicClient::connect_with_options`
+    #[derive(Clone)]
+    pub struct ClientTlsOptions {
+        pub server_name: String,
+        pub trust: ClientTrust,
+        pub identity: Option<TlsIdentity>,
+    }
+
+    // Client cert verifier that checks the chain against CA roots, then
+    // restricts the end-entity cert to the configured relay identities
+    struct RelayIdentityVerifier {
+        inner: rustls::server::AllowAnyAuthenticatedClient,
+        allowed_identities: Vec<String>,
+    }
+
+    impl rustls::server::ClientCertVerifier for RelayIdentityVerifier {
+        fn client_auth_root_subjects(&self) -> &[rustls::DistinguishedName] {
+            self.inner.client_auth_root_subjects()
+        }
+
+        fn verify_client_cert(
+            &self,
+            end_entity: &rustls::Certificate,
+            intermediates: &[rustls::Certificate],
+            now: std::time::SystemTime,
+        ) -> std::result::Result<rustls::server::ClientCertVerified, rustls::Error> {
+            let verified = self.inner.verify_client_cert(end_entity, intermediates, now)?;
+            if self.allowed_identities.is_empty() {
+                return Ok(verified);
+            }
+
+            let cert = webpki::EndEntityCert::try_from(end_entity.0.as_slice())
+                .map_err(|_| rustls::Error::InvalidCertificate(rustls::CertificateError::BadEncoding))?;
+            let allowed = self.allowed_identities.iter().any(|identity| {
+                webpki::SubjectNameRef::try_from_ascii_str(identity)
+                    .map(|name| cert.verify_is_valid_for_subject_name(name).is_ok())
+                    .unwrap_or(false)
+            });
+            if allowed {
+                Ok(verified)
+            } else {
+                Err(rustls::Error::InvalidCertificate(rustls::CertificateError::NotValidForName))
+            }
+        }
+    }
+
     fn server_config(cert: &Certificate) -> Result<ServerConfig> {
-        let cert_der = cert.serialize_der()?;
-        let key_der = cert.serialize_private_key_der();
-        let cert_chain = vec![rustls::Certificate(cert_der)];
-        let key = rustls::PrivateKey(key_der);
-        let mut cfg = rustls::ServerConfig::builder()
-            .with_safe_defaults()
-            .with_no_client_auth()
-            .with_single_cert(cert_chain, key)?;
-        cfg.alpn_protocols = vec![b"mesh/relay/1".to_vec()];
+        server_config_with_options(&ServerTlsOptions {
+            identity: TlsIdentity::self_signed(cert)?,
+            client_auth: None,
+        })
+    }
+
+    fn server_config_with_options(options: &ServerTlsOptions) -> Result<ServerConfig> {
+        let builder = rustls::ServerConfig::builder().with_safe_defaults();
+        let builder = match &options.client_auth {
+            Some(auth) => builder.with_client_cert_verifier(Arc::new(RelayIdentityVerifier {
+                inner: rustls::server::AllowAnyAuthenticatedClient::new(auth.roots.clone()),
+                allowed_identities: auth.allowed_identities.clone(),
+            })),
+            None => builder.with_no_client_auth(),
+        };
+        let mut cfg = builder.with_single_cert(options.identity.cert_chain.clone(), options.identity.key.clone())?;
+        cfg.alpn_protocols = vec![ALPN_RELAY.to_vec()];
         Ok(ServerConfig::with_crypto(Arc::new(cfg)))
     }
 
-    fn client_config(cert: &Certificate) -> Result<ClientConfig> {
-        let cert_der = cert.serialize_der()?;
-        let mut roots = rustls::RootCertStore::empty();
-        roots.add(&rustls::Certificate(cert_der))?;
-        let mut cfg = rustls::ClientConfig::builder()
+    fn client_config(options: &ClientTlsOptions) -> Result<ClientConfig> {
+        let builder = rustls::ClientConfig::builder()
             .with_safe_defaults()
-            .with_root_certificates(roots)
-            .with_no_client_auth();
-        cfg.alpn_protocols = vec![b"mesh/relay/1".to_vec()];
+            .with_root_certificates(options.trust.root_store()?);
+        let mut cfg = match &options.identity {
+            Some(identity) => builder.with_client_auth_cert(identity.cert_chain.clone(), identity.key.clone())?,
+            None => builder.with_no_client_auth(),
+        };
+        cfg.alpn_protocols = vec![ALPN_RELAY.to_vec()];
         Ok(ClientConfig::new(Arc::new(cfg)))
     }
 
+    const MAX_MESSAGE_BYTES: usize = 8 * 1024 * 1024;
+    const MAX_HANDSHAKE_BYTES: usize = 64 * 1024;
+    const HANDSHAKE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+    const HANDSHAKE_ACCEPTED: u8 = 1;
+    const HANDSHAKE_REFUSED: u8 = 0;
+
+    // Server side of the admission handshake: send a challenge on a uni stream,
+    // read the signed answer from the first bi stream and reply with the verdict
+    async fn accept_handshake(relay: &Mutex<Relay>, connection: &quinn::Connection) -> Result<(usize, PeerReceiver)> {
+        let challenge = relay.lock().await.handshake_challenge();
+        let mut challenge_stream = connection.open_uni().await?;
+        challenge_stream.write_all(&challenge).await?;
+        challenge_stream.finish().await?;
+
+        let (mut send, mut recv) = connection.accept_bi().await?;
+        let handshake: PeerHandshake = bincode::deserialize(&recv.read_to_end(MAX_HANDSHAKE_BYTES).await?)?;
+        let admitted = relay.lock().await.admit_peer(handshake);
+        let verdict = if admitted.is_ok() { HANDSHAKE_ACCEPTED } else { HANDSHAKE_REFUSED };
+        send.write_all(&[verdict]).await?;
+        send.finish().await?;
+        Ok(admitted?)
+    }
+
+    // Client side of `accept_handshake`
+    async fn offer_handshake(connection: &quinn::Connection, identity: &HandshakeIdentity, address: SocketAddr) -> Result<()> {
+        let mut challenge_stream = connection.accept_uni().await?;
+        let challenge: HandshakeChallenge = challenge_stream.read_to_end(MAX_HANDSHAKE_BYTES).await?
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("malformed handshake challenge"))?;
+
+        let (mut send, mut recv) = connection.open_bi().await?;
+        send.write_all(&bincode::serialize(&identity.answer(address, challenge))?).await?;
+        send.finish().await?;
+        match recv.read_to_end(1).await?.as_slice() {
+            [HANDSHAKE_ACCEPTED] => Ok(()),
+            _ => anyhow::bail!("relay refused the handshake"),
+        }
+    }
+
+    /// Frame `msg` for the wire: the protocol version, then the bincode body
+    pub fn encode_frame(msg: &WireMessage) -> Result<Vec<u8>> {
+        let mut frame = RELAY_PROTOCOL_VERSION.to_be_bytes().to_vec();
+        bincode::serialize_into(&mut frame, msg)?;
+        Ok(frame)
+    }
+
+    /// Decode a frame from `encode_frame`, refusing other protocol versions
+    pub fn decode_frame(frame: &[u8]) -> Result<WireMessage> {
+        let (version, body) = match frame {
+            [high, low, body @ ..] => (u16::from_be_bytes([*high, *low]), body),
+            _ => anyhow::bail!("frame too short for a protocol version"),
+        };
+        if version != RELAY_PROTOCOL_VERSION {
+            anyhow::bail!("frame is protocol version {}, relay speaks {}", version, RELAY_PROTOCOL_VERSION);
+        }
+        Ok(bincode::deserialize(body)?)
+    }
+
+    // Deliver relay messages queued for a client, one uni stream per message;
+    // signed messages go out in their original envelope
+    async fn forward_to_client(connection: quinn::Connection, mut outbound: PeerReceiver) {
+        while let Some(msg) = outbound.recv().await {
+            let bytes = match encode_frame(&msg) {
+                Ok(bytes) => bytes,
+                Err(_) => continue,
+            };
+            let mut send = match connection.open_uni().await {
+                Ok(send) => send,
+                Err(_) => break,
+            };
+            if send.write_all(&bytes).await.is_err() {
+                break;
+            }
+            let _ = send.finish().await;
+        }
+    }
+
     pub struct QuicServer {
         pub endpoint: Endpoint,
         _recv_task: JoinHandle<()>,
+        reorder_task: JoinHandle<()>,
+    }
+
+    // The reorder timer holds the relay; stop it with the server
+    impl Drop for QuicServer {
+        fn drop(&mut self) {
+            self.reorder_task.abort();
+        }
+    }
+
+    // Release reorder gaps on a timer while the relay is operational
+    async fn drive_reorder_timeouts(relay: Arc<Mutex<Relay>>) {
+        let timeout_ms = relay.lock().await.cfg.reorder_timeout_ms;
+        let mut ticker = tokio::time::interval(std::time::Duration::from_millis((timeout_ms / 2).max(1)));
+        loop {
+            ticker.tick().await;
+            let mut relay = relay.lock().await;
+            if !relay.is_operational() {
+                break;
+            }
+            relay.poll_reorder_timeouts();
+        }
     }
 
     impl QuicServer {
@@ -559,11 +1935,22 @@ pub mod net {
             relay: Arc<Mutex<Relay>>,
             cert: Arc<Certificate>,
         ) -> Result<(Self, SocketAddr)> {
-            let server_cfg = server_config(&cert)?;
+            Self::bind_and_run(relay, server_config(&cert)?)
+        }
+
+        /// Bind with an explicit identity and optional mutual TLS
+        pub async fn bind_and_run_with_options(
+            relay: Arc<Mutex<Relay>>,
+            options: ServerTlsOptions,
+        ) -> Result<(Self, SocketAddr)> {
+            Self::bind_and_run(relay, server_config_with_options(&options)?)
+        }
+
+        fn bind_and_run(relay: Arc<Mutex<Relay>>, server_cfg: ServerConfig) -> Result<(Self, SocketAddr)> {
             let endpoint = Endpoint::server(server_cfg, SocketAddr::new(IpAddr::V4(Ipv4Addr::LOCALHOST), 0))?;
             let local_addr = endpoint.local_addr()?;
 
-            let (tx, mut rx) = mpsc::unbounded_channel::<Message>();
+            let reorder_task = tokio::spawn(drive_reorder_timeouts(relay.clone()));
 
             // Spawn accept loop
             let ep = endpoint.clone();
@@ -573,20 +1960,34 @@ pub mod net {
                     if conn_opt.is_none() { break; }
                     let conn = conn_opt.unwrap();
                     let relay = relay.clone();
-                    let tx = tx.clone();
                     tokio::spawn(async move {
                         if let Ok(connection) = conn.await {
+                            // Admit the client through the handshake so broadcasts reach it
+                            let admitted = tokio::time::timeout(HANDSHAKE_TIMEOUT, accept_handshake(&relay, &connection)).await;
+                            let (peer_id, outbound) = match admitted {
+                                Ok(Ok(admitted)) => admitted,
+                                Ok(Err(e)) => {
+                                    warn!("Handshake with {} failed: {}", connection.remote_address(), e);
+                                    connection.close(1u32.into(), b"handshake refused");
+                                    return;
+                                }
+                                Err(_) => {
+                                    connection.close(1u32.into(), b"handshake timed out");
+                                    return;
+                                }
+                            };
+                            let forward_task = tokio::spawn(forward_to_client(connection.clone(), outbound));
+
                             loop {
                                 match connection.accept_bi().await {
                                     Ok((mut send, mut recv)) => {
                                         let _ = send.finish().await; // best-effort
                                         // Read entire stream up to 8 MiB
-                                        if let Ok(buf) = recv.read_to_end(8 * 1024 * 1024).await {
+                                        if let Ok(buf) = recv.read_to_end(MAX_MESSAGE_BYTES).await {
                                             if !buf.is_empty() {
-                                                if let Ok(msg) = bincode::deserialize::<Message>(&buf) {
-                                                    let _ = tx.send(msg.clone());
-                                                    let mut r = relay.lock().await;
-                                                    r.broadcast_from(0, msg);
+                                                match decode_frame(&buf) {
+                                                    Ok(msg) => relay.lock().await.receive(peer_id, msg),
+                                                    Err(e) => warn!("Dropping undecodable frame from peer {}: {}", peer_id, e),
                                                 }
                                             }
                                         }
@@ -594,34 +1995,89 @@ pub mod net {
                                     Err(_) => break,
                                 }
                             }
+
+                            forward_task.abort();
+                            relay.lock().await.remove_peer(peer_id);
                         }
                     });
                 }
             });
 
-            // Also spawn a task to drain tx to nowhere in case user doesn't read
-            let _bg = tokio::spawn(async move {
-                while let Some(_m) = rx.recv().await {}
-            });
-
-            Ok((Self { endpoint, _recv_task: recv_task }, local_addr))
+            Ok((Self { endpoint, _recv_task: recv_task, reorder_task }, local_addr))
         }
     }
 
     pub struct QuicClient;
 
-    impl QuicClient {
-        pub async fn connect_and_send(addr: SocketAddr, trust_cert: Arc<Certificate>, msg: &Message) -> Result<()> {
-            let client_cfg = client_config(&trust_cert)?;
-            let mut endpoint = Endpoint::client(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0))?;
-            endpoint.set_default_client_config(client_cfg);
-            let conn = endpoint.connect(addr, "localhost")?.await?;
-            let (mut send, _recv) = conn.open_bi().await?;
-            let bytes = bincode::serialize(msg)?;
+    /// Long-lived client connection that can both send and receive relayed messages
+    pub struct QuicConnection {
+        _endpoint: Endpoint,
+        connection: quinn::Connection,
+    }
+
+    impl QuicConnection {
+        pub async fn send(&self, msg: &Message) -> Result<()> {
+            self.send_wire(&WireMessage::Plain(msg.clone())).await
+        }
+
+        /// Send a signed message, which relays requiring signatures accept
+        pub async fn send_signed(&self, signed: &SignedMessage) -> Result<()> {
+            self.send_wire(&WireMessage::Signed(signed.clone())).await
+        }
+
+        async fn send_wire(&self, msg: &WireMessage) -> Result<()> {
+            let (mut send, _recv) = self.connection.open_bi().await?;
+            let bytes = encode_frame(msg)?;
             send.write_all(&bytes).await?;
             send.finish().await?;
             Ok(())
         }
+
+        /// Wait for the next message forwarded by the relay
+        pub async fn recv(&self) -> Result<WireMessage> {
+            let mut recv = self.connection.accept_uni().await?;
+            let buf = recv.read_to_end(MAX_MESSAGE_BYTES).await?;
+            decode_frame(&buf)
+        }
+    }
+
+    impl QuicClient {
+        /// Connect trusting only `trust_cert` (self-signed test setups)
+        pub async fn connect(addr: SocketAddr, trust_cert: Arc<Certificate>) -> Result<QuicConnection> {
+            let options = ClientTlsOptions {
+                server_name: "localhost".to_string(),
+                trust: ClientTrust::pinned(&trust_cert)?,
+                identity: None,
+            };
+            Self::connect_with_options(addr, &options).await
+        }
+
+        /// Connect validating the server against `options.trust`, presenting
+        /// `options.identity` when the server requires mutual TLS. The admission
+        /// handshake uses a throwaway identity.
+        pub async fn connect_with_options(addr: SocketAddr, options: &ClientTlsOptions) -> Result<QuicConnection> {
+            Self::connect_with_handshake(addr, options, &HandshakeIdentity::ephemeral()).await
+        }
+
+        /// Connect as in `connect_with_options`, answering the relay's admission
+        /// handshake as `identity`
+        pub async fn connect_with_handshake(
+            addr: SocketAddr,
+            options: &ClientTlsOptions,
+            identity: &HandshakeIdentity,
+        ) -> Result<QuicConnection> {
+            let client_cfg = client_config(options)?;
+            let mut endpoint = Endpoint::client(SocketAddr::new(IpAddr::V4(Ipv4Addr::UNSPECIFIED), 0))?;
+            endpoint.set_default_client_config(client_cfg);
+            let connection = endpoint.connect(addr, &options.server_name)?.await?;
+            offer_handshake(&connection, identity, endpoint.local_addr()?).await?;
+            Ok(QuicConnection { _endpoint: endpoint, connection })
+        }
+
+        pub async fn connect_and_send(addr: SocketAddr, trust_cert: Arc<Certificate>, msg: &Message) -> Result<()> {
+            let conn = Self::connect(addr, trust_cert).await?;
+            conn.send(msg).await
+        }
     }
 }
 
@@ -636,18 +2092,33 @@ mod tests {
             dedup_cache: 128, 
             rate_limit_per_sec: 100.0, 
             rate_limit_burst: 100.0,
+            byte_rate_per_sec: 64.0 * 1024.0 * 1024.0,
+            byte_burst: 64.0 * 1024.0 * 1024.0,
             loss_probability: 0.0,
             max_clients: 1000,
             anti_eclipse_min_relays: 3,
             partition_recovery_timeout_ms: 5000,
             routing_table_size: 10000,
             connection_timeout_ms: 30000,
+            require_signed: false,
+            partition_buffer_size: 64,
+            partition_fanout_per_sec: 100.0,
+            order_by_source: false,
+            reorder_window: 64,
+            reorder_timeout_ms: 500,
+            max_reorder_origins: 4096,
+            peer_channel_bound: None,
+            backpressure_policy: BackpressurePolicy::DropNew,
+            capabilities: handshake::default_capabilities(),
+            trusted_peer_keys: None,
+            message_max_age: None,
+            anti_eclipse_max_fanout: None,
         });
         let (a, mut ra) = relay.add_peer();
         let (_b, mut rb) = relay.add_peer();
         let (_c, mut rc) = relay.add_peer();
 
-        let msg = Message { id: 42, data: b"hello".to_vec() };
+        let msg = Message::new(42, b"hello".to_vec());
         relay.broadcast_from(a, msg.clone());
         relay.broadcast_from(a, msg.clone()); // duplicate, should be ignored
 
@@ -668,18 +2139,33 @@ mod tests {
             dedup_cache: 1024, 
             rate_limit_per_sec: 1000.0, 
             rate_limit_burst: 1000.0,
+            byte_rate_per_sec: 64.0 * 1024.0 * 1024.0,
+            byte_burst: 64.0 * 1024.0 * 1024.0,
             loss_probability: 0.0,
             max_clients: 1000,
             anti_eclipse_min_relays: 3,
             partition_recovery_timeout_ms: 5000,
             routing_table_size: 10000,
             connection_timeout_ms: 30000,
+            require_signed: false,
+            partition_buffer_size: 64,
+            partition_fanout_per_sec: 100.0,
+            order_by_source: false,
+            reorder_window: 64,
+            reorder_timeout_ms: 500,
+            max_reorder_origins: 4096,
+            peer_channel_bound: None,
+            backpressure_policy: BackpressurePolicy::DropNew,
+            capabilities: handshake::default_capabilities(),
+            trusted_peer_keys: None,
+            message_max_age: None,
+            anti_eclipse_max_fanout: None,
         });
         let (a, _ra) = relay.add_peer();
         let (_b, mut rb) = relay.add_peer();
         // Send 10 messages quickly; only ~5 should pass within the same second
         for i in 0..10u64 {
-            relay.broadcast_from(a, Message { id: 1000 + i, data: vec![1,2,3] });
+            relay.broadcast_from(a, Message::new(1000 + i, vec![1,2,3]));
         }
         // Drain what arrived
         let mut count = 0;
@@ -689,7 +2175,7 @@ mod tests {
         assert!(count <= 5, "rate limit exceeded: {} > 5", count);
         // After 1s window, more should pass
         sleep(Duration::from_millis(1050)).await;
-        for i in 10..15u64 { relay.broadcast_from(a, Message { id: 1000 + i, data: vec![4,5,6] }); }
+        for i in 10..15u64 { relay.broadcast_from(a, Message::new(1000 + i, vec![4,5,6])); }
         let mut count2 = 0; while rb.try_recv().is_ok() { count2 += 1; }
         assert!(count2 >= 1);
     }
@@ -700,16 +2186,31 @@ mod tests {
             dedup_cache: 2048, 
             rate_limit_per_sec: 10000.0, 
             rate_limit_burst: 10000.0,
+            byte_rate_per_sec: 64.0 * 1024.0 * 1024.0,
+            byte_burst: 64.0 * 1024.0 * 1024.0,
             loss_probability: 0.3,
             max_clients: 1000,
             anti_eclipse_min_relays: 3,
             partition_recovery_timeout_ms: 5000,
             routing_table_size: 10000,
             connection_timeout_ms: 30000,
+            require_signed: false,
+            partition_buffer_size: 64,
+            partition_fanout_per_sec: 100.0,
+            order_by_source: false,
+            reorder_window: 64,
+            reorder_timeout_ms: 500,
+            max_reorder_origins: 4096,
+            peer_channel_bound: None,
+            backpressure_policy: BackpressurePolicy::DropNew,
+            capabilities: handshake::default_capabilities(),
+            trusted_peer_keys: None,
+            message_max_age: None,
+            anti_eclipse_max_fanout: None,
         });
         let (a, _ra) = relay.add_peer();
         let (_b, mut rb) = relay.add_peer();
-        for i in 0..20u64 { relay.broadcast_from(a, Message { id: 5000 + i, data: vec![9] }); }
+        for i in 0..20u64 { relay.broadcast_from(a, Message::new(5000 + i, vec![9])); }
         // Count received
         let mut recv = 0; while rb.try_recv().is_ok() { recv += 1; }
         // Expect at least some deliveries despite loss; probabilistic threshold kept low
@@ -728,8 +2229,8 @@ mod tests {
         relay.pause_peer(2);
         relay.pause_peer(3);
 
-        relay.broadcast_from(a, Message { id: 10, data: b"blk-10".to_vec() });
-        relay.broadcast_from(a, Message { id: 11, data: b"blk-11".to_vec() });
+        relay.broadcast_from(a, Message::new(10, b"blk-10".to_vec()));
+        relay.broadcast_from(a, Message::new(11, b"blk-11".to_vec()));
         // B should receive; C and D should not
         let mut cnt_b = 0; while rb.try_recv().is_ok() { cnt_b += 1; }
         assert!(cnt_b >= 1);
@@ -741,8 +2242,8 @@ mod tests {
         relay.resume_peer(3);
 
         // Within next two broadcasts, all should receive
-        relay.broadcast_from(a, Message { id: 12, data: b"blk-12".to_vec() });
-        relay.broadcast_from(a, Message { id: 13, data: b"blk-13".to_vec() });
+        relay.broadcast_from(a, Message::new(12, b"blk-12".to_vec()));
+        relay.broadcast_from(a, Message::new(13, b"blk-13".to_vec()));
 
         // Drain
         let mut got_b = 0; while rb.try_recv().is_ok() { got_b += 1; }
@@ -788,7 +2289,7 @@ mod tests {
         assert_eq!(relay.routing_table.len(), 2);
         
         // Test anti-eclipse broadcast
-        let msg = Message { id: 100, data: b"test anti-eclipse".to_vec() };
+        let msg = Message::new(100, b"test anti-eclipse".to_vec());
         relay.anti_eclipse_broadcast(msg.clone());
         
         // Should broadcast to relay peer
@@ -834,7 +2335,7 @@ mod tests {
         assert!(!partition_detected);
         
         // Test anti-eclipse broadcast with insufficient relays
-        let msg = Message { id: 200, data: b"eclipse test".to_vec() };
+        let msg = Message::new(200, b"eclipse test".to_vec());
         relay.anti_eclipse_broadcast(msg.clone());
         
         // Should broadcast to all peers due to insufficient relays
@@ -871,46 +2372,397 @@ mod tests {
         
         println!("✅ Stage 19: Partition recovery working");
     }
-    
-    #[tokio::test]
-    async fn test_stage19_resilience_30_percent_loss() {
-        let mut config = RelayConfig::default();
-        config.loss_probability = 0.3; // 30% loss simulation
-        let mut relay = Relay::new(config);
-        
-        let (a, _ra) = relay.add_peer();
-        let (_b, mut rb) = relay.add_peer();
-        let (_c, mut rc) = relay.add_peer();
-        
-        let mut successful_deliveries = 0;
-        let total_messages = 100;
-        
-        // Send many messages and count successful deliveries
-        for i in 0..total_messages {
-            let msg = Message { id: i, data: format!("test-{}", i).into_bytes() };
-            relay.broadcast_from(a, msg);
-        }
-        
-        // Count received messages
-        while let Ok(_) = rb.try_recv() {
-            successful_deliveries += 1;
+
+    fn stage19_peer(id: &str, is_relay: bool) -> PeerInfo {
+        PeerInfo {
+            id: id.to_string(),
+            address: "127.0.0.1:8001".parse().unwrap(),
+            last_seen: Instant::now(),
+            message_count: 0,
+            is_relay,
+            connection_quality: 0.9,
         }
-        while let Ok(_) = rc.try_recv() {
-            successful_deliveries += 1;
+    }
+
+    #[tokio::test]
+    async fn test_partition_aggressive_fanout_and_recovery_resend() {
+        let mut relay = Relay::new(RelayConfig {
+            anti_eclipse_min_relays: 1,
+            partition_recovery_timeout_ms: 50,
+            partition_fanout_per_sec: 2.0,
+            ..RelayConfig::default()
+        });
+        let (relay_id, mut relay_rx) = relay.add_peer_with_info(stage19_peer("relay-1", true));
+        let (_client_id, mut client_rx) = relay.add_peer_with_info(stage19_peer("client-1", false));
+
+        // Healthy: enough relays, so clients are not part of the fanout
+        relay.anti_eclipse_broadcast(Message::new(300, b"normal".to_vec()));
+        assert_eq!(relay_rx.try_recv().unwrap().id, 300);
+        assert!(client_rx.try_recv().is_err());
+
+        // Relays go silent and become unreachable
+        relay.pause_peer(relay_id);
+        sleep(Duration::from_millis(80)).await;
+        assert!(!relay.check_partition_recovery());
+        assert!(relay.snapshot_stats().partition_detected);
+
+        // Aggressive fanout reaches every peer until the rate limit kicks in
+        for id in 301..304u64 {
+            relay.anti_eclipse_broadcast(Message::new(id, b"partitioned".to_vec()));
         }
-        
-        // With 30% loss, we should get roughly 70% * 2 peers = 140 deliveries
-        // Allow some variance due to randomness
-        let expected_min = (total_messages as f32 * 0.7 * 2.0 * 0.8) as usize; // 80% of expected
-        assert!(successful_deliveries >= expected_min, 
-                "Only {} deliveries, expected at least {}", successful_deliveries, expected_min);
-        
-        println!("✅ Stage 19: 30% loss tolerance working - {} deliveries", successful_deliveries);
+        assert_eq!(client_rx.try_recv().unwrap().id, 301);
+        assert_eq!(client_rx.try_recv().unwrap().id, 302);
+        assert!(client_rx.try_recv().is_err());
+        assert!(relay_rx.try_recv().is_err());
+        assert!(relay.snapshot_stats().partition_detected);
+
+        // The relay comes back; everything it missed is re-sent once
+        relay.resume_peer(relay_id);
+        relay.update_peer_activity(relay_id);
+        assert!(relay.check_partition_recovery());
+        let resent: Vec<u64> = std::iter::from_fn(|| relay_rx.try_recv().ok()).map(|m| m.id).collect();
+        assert_eq!(resent, vec![301, 302, 303]);
+        assert!(relay.anti_eclipse.partition_buffer.is_empty());
     }
-    
+
     #[tokio::test]
-    async fn test_stage19_exit_criteria() {
-        println!("\n=== Stage 19: Relay Service Exit Criteria ===");
+    async fn test_anti_eclipse_fanout_cap_rotates_through_peers() {
+        // No relays at all, so every broadcast takes the aggressive path
+        let mut relay = Relay::new(RelayConfig {
+            anti_eclipse_max_fanout: Some(4),
+            ..RelayConfig::default()
+        });
+        let mut receivers = Vec::new();
+        for i in 0..20 {
+            let mut peer = stage19_peer(&format!("client-{}", i), false);
+            peer.connection_quality = i as f64 / 20.0;
+            receivers.push(relay.add_peer_with_info(peer).1);
+        }
+        let delivered_to = |receivers: &mut Vec<PeerReceiver>| -> Vec<usize> {
+            receivers.iter_mut().enumerate()
+                .filter_map(|(i, rx)| rx.try_recv().ok().map(|_| i))
+                .collect()
+        };
+
+        // The first round goes to the best-connected peers
+        relay.anti_eclipse_broadcast(Message::new(400, b"eclipsed".to_vec()));
+        assert_eq!(delivered_to(&mut receivers), vec![16, 17, 18, 19]);
+
+        // Later rounds move on, so five rounds cover every peer once
+        let mut reached = std::collections::HashSet::new();
+        for id in 401..405u64 {
+            relay.anti_eclipse_broadcast(Message::new(id, b"eclipsed".to_vec()));
+            let round = delivered_to(&mut receivers);
+            assert_eq!(round.len(), 4);
+            assert!(round.iter().all(|peer| *peer < 16));
+            reached.extend(round);
+        }
+        assert_eq!(reached.len(), 16);
+
+        // Without a cap the fallback still reaches everyone
+        let mut uncapped = Relay::new(RelayConfig::default());
+        let mut receivers: Vec<_> = (0..20).map(|i| uncapped.add_peer_with_info(stage19_peer(&format!("client-{}", i), false)).1).collect();
+        uncapped.anti_eclipse_broadcast(Message::new(410, b"eclipsed".to_vec()));
+        assert_eq!(delivered_to(&mut receivers).len(), 20);
+    }
+
+    #[tokio::test]
+    async fn test_large_broadcast_shares_payload() {
+        let mut relay = Relay::new(RelayConfig {
+            byte_rate_per_sec: 0.0,
+            byte_burst: 8.0 * 1024.0 * 1024.0,
+            ..RelayConfig::default()
+        });
+        let (source, _rsource) = relay.add_peer();
+        let mut receivers: Vec<_> = (0..50).map(|_| relay.add_peer().1).collect();
+
+        let msg = Message::new(700, vec![0xabu8; 4 * 1024 * 1024]);
+        let payload = msg.data.clone();
+        relay.broadcast_from(source, msg);
+
+        // Every peer holds the same allocation rather than its own copy
+        for rx in receivers.iter_mut() {
+            let got = rx.try_recv().unwrap();
+            assert!(Arc::ptr_eq(&got.data, &payload));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_byte_budget_limits_large_infrequent_sender() {
+        let mut relay = Relay::new(RelayConfig {
+            rate_limit_per_sec: 0.0,
+            rate_limit_burst: 100.0,
+            byte_rate_per_sec: 0.0,
+            byte_burst: 10_000.0,
+            ..RelayConfig::default()
+        });
+        let (big, _rbig) = relay.add_peer();
+        let (_sink, mut rsink) = relay.add_peer();
+
+        // Few messages, but each 4 KB: the third one exceeds the 10 KB byte budget
+        for i in 0..3u64 {
+            relay.broadcast_from(big, Message::new(900 + i, vec![0u8; 4_000]));
+        }
+        assert_eq!(drain_ids(&mut rsink), vec![900, 901]);
+        let dropped = relay.snapshot_stats().dropped;
+        assert_eq!(dropped.byte_limit, 1);
+        assert_eq!(dropped.rate_limit, 0);
+    }
+
+    #[tokio::test]
+    async fn test_message_rate_limits_small_frequent_sender() {
+        let mut relay = Relay::new(RelayConfig {
+            rate_limit_per_sec: 0.0,
+            rate_limit_burst: 5.0,
+            byte_rate_per_sec: 0.0,
+            byte_burst: 10_000.0,
+            ..RelayConfig::default()
+        });
+        let (small, _rsmall) = relay.add_peer();
+        let (_sink, mut rsink) = relay.add_peer();
+
+        // Many 10-byte messages stay well inside the byte budget
+        for i in 0..8u64 {
+            relay.broadcast_from(small, Message::new(950 + i, vec![0u8; 10]));
+        }
+        assert_eq!(drain_ids(&mut rsink).len(), 5);
+        let dropped = relay.snapshot_stats().dropped;
+        assert_eq!(dropped.rate_limit, 3);
+        assert_eq!(dropped.byte_limit, 0);
+    }
+
+    fn bounded_relay(policy: BackpressurePolicy) -> Relay {
+        Relay::new(RelayConfig {
+            peer_channel_bound: Some(2),
+            backpressure_policy: policy,
+            ..RelayConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_slow_peer_sheds_new_messages_under_bound() {
+        let mut relay = bounded_relay(BackpressurePolicy::DropNew);
+        let (source, _rsource) = relay.add_peer();
+        let (_fast, mut rfast) = relay.add_peer();
+        let (_slow, mut rslow) = relay.add_peer();
+
+        // The fast peer keeps up; the slow one never reads while broadcasting
+        let mut fast_ids = Vec::new();
+        for id in 0..10u64 {
+            relay.broadcast_from(source, Message::new(1000 + id, b"tick".to_vec()));
+            fast_ids.extend(drain_ids(&mut rfast));
+        }
+        assert_eq!(fast_ids, (1000..1010).collect::<Vec<_>>());
+        assert_eq!(rslow.len(), 2);
+        assert_eq!(drain_ids(&mut rslow), vec![1000, 1001]);
+
+        let stats = relay.snapshot_stats();
+        assert_eq!(stats.dropped.backpressure, 8);
+        assert_eq!(stats.broadcasted, 12);
+    }
+
+    #[tokio::test]
+    async fn test_slow_peer_drops_oldest_under_bound() {
+        let mut relay = bounded_relay(BackpressurePolicy::DropOldest);
+        let (source, _rsource) = relay.add_peer();
+        let (_fast, mut rfast) = relay.add_peer();
+        let (_slow, mut rslow) = relay.add_peer();
+
+        for id in 0..10u64 {
+            relay.broadcast_from(source, Message::new(2000 + id, b"tick".to_vec()));
+            assert_eq!(drain_ids(&mut rfast), vec![2000 + id]);
+        }
+        // The slow peer is left with the freshest messages
+        assert_eq!(drain_ids(&mut rslow), vec![2008, 2009]);
+        assert_eq!(relay.snapshot_stats().dropped.backpressure, 8);
+
+        // Once drained it receives again
+        relay.broadcast_from(source, Message::new(2010, b"tick".to_vec()));
+        assert_eq!(rslow.recv().await.unwrap().id, 2010);
+    }
+
+    fn ordered_relay() -> Relay {
+        Relay::new(RelayConfig {
+            order_by_source: true,
+            reorder_window: 4,
+            reorder_timeout_ms: 50,
+            ..RelayConfig::default()
+        })
+    }
+
+    fn drain_ids(rx: &mut PeerReceiver) -> Vec<u64> {
+        std::iter::from_fn(|| rx.try_recv().ok()).map(|m| m.id).collect()
+    }
+
+    #[tokio::test]
+    async fn test_reorder_in_order_passthrough() {
+        let mut relay = ordered_relay();
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        for seq in 0..3u64 {
+            relay.broadcast_from(a, Message::new(600 + seq, vec![]).with_seq(seq));
+        }
+        // Unsequenced messages are never held
+        relay.broadcast_from(a, Message::new(699, vec![]));
+        assert_eq!(drain_ids(&mut rb), vec![600, 601, 602, 699]);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_sequences_swapped_pair() {
+        let mut relay = ordered_relay();
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        relay.broadcast_from(a, Message::new(701, vec![]).with_seq(1));
+        assert!(drain_ids(&mut rb).is_empty());
+        relay.broadcast_from(a, Message::new(700, vec![]).with_seq(0));
+        assert_eq!(drain_ids(&mut rb), vec![700, 701]);
+
+        // A late copy of an already delivered slot is dropped
+        relay.broadcast_from(a, Message::new(702, vec![]).with_seq(0));
+        assert!(drain_ids(&mut rb).is_empty());
+        assert_eq!(relay.snapshot_stats().dropped.stale_seq, 1);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_permanent_gap_times_out() {
+        let mut relay = ordered_relay();
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        relay.broadcast_from(a, Message::new(800, vec![]).with_seq(0));
+        relay.broadcast_from(a, Message::new(802, vec![]).with_seq(2));
+        relay.broadcast_from(a, Message::new(803, vec![]).with_seq(3));
+        assert_eq!(drain_ids(&mut rb), vec![800]);
+
+        // seq 1 never arrives
+        sleep(Duration::from_millis(80)).await;
+        relay.poll_reorder_timeouts();
+        assert_eq!(drain_ids(&mut rb), vec![802, 803]);
+
+        relay.broadcast_from(a, Message::new(804, vec![]).with_seq(4));
+        assert_eq!(drain_ids(&mut rb), vec![804]);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_keys_signed_messages_by_origin() {
+        let mut relay = ordered_relay();
+        let (a, mut ra) = relay.add_peer();
+        let (b, mut rb) = relay.add_peer();
+        let (_c, mut rc) = relay.add_peer();
+        let origin = SigningKey::from_bytes(&[11u8; 32]);
+
+        // One origin's stream arrives split across two peers, out of order
+        relay.broadcast_signed(a, SignedMessage::sign(Message::new(1101, vec![]).with_seq(1), &origin));
+        assert!(drain_ids(&mut rc).is_empty());
+        relay.broadcast_signed(b, SignedMessage::sign(Message::new(1100, vec![]).with_seq(0), &origin));
+        assert_eq!(drain_ids(&mut rc), vec![1100, 1101]);
+
+        // Each message still skips the peer it came from
+        assert_eq!(drain_ids(&mut ra), vec![1100]);
+        assert_eq!(drain_ids(&mut rb), vec![1101]);
+    }
+
+    #[tokio::test]
+    async fn test_reorder_origins_evicted_when_idle_or_over_cap() {
+        let mut relay = Relay::new(RelayConfig {
+            order_by_source: true,
+            reorder_window: 4,
+            reorder_timeout_ms: 50,
+            max_reorder_origins: 2,
+            ..RelayConfig::default()
+        });
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let origins: Vec<SigningKey> = (0..4u8).map(|i| SigningKey::from_bytes(&[20 + i; 32])).collect();
+        let signer = |key: &SigningKey| OrderOrigin::Signer(key.verifying_key().to_bytes());
+
+        relay.broadcast_signed(a, SignedMessage::sign(Message::new(1200, vec![]).with_seq(0), &origins[0]));
+        relay.broadcast_signed(a, SignedMessage::sign(Message::new(1201, vec![]).with_seq(1), &origins[1]));
+        assert_eq!(drain_ids(&mut rb), vec![1200]);
+
+        // A third origin displaces the least recently active one
+        relay.broadcast_signed(a, SignedMessage::sign(Message::new(1202, vec![]).with_seq(0), &origins[2]));
+        assert_eq!(relay.reorder.len(), 2);
+        assert!(!relay.reorder.contains_key(&signer(&origins[0])));
+        assert_eq!(drain_ids(&mut rb), vec![1202]);
+
+        // Displacing an origin releases what it held behind its gap
+        relay.broadcast_signed(a, SignedMessage::sign(Message::new(1203, vec![]).with_seq(0), &origins[3]));
+        assert_eq!(relay.reorder.len(), 2);
+        assert_eq!(drain_ids(&mut rb), vec![1201, 1203]);
+
+        // Origins with nothing held are forgotten once idle
+        sleep(Duration::from_millis(80)).await;
+        relay.poll_reorder_timeouts();
+        assert!(relay.reorder.is_empty());
+
+        // However many keys sign, the tracked origins stay capped
+        for i in 0..50u8 {
+            let key = SigningKey::from_bytes(&[100 + i; 32]);
+            relay.broadcast_signed(a, SignedMessage::sign(Message::new(1300 + i as u64, vec![]).with_seq(1), &key));
+        }
+        assert_eq!(relay.reorder.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_quic_server_releases_reorder_gaps() {
+        let relay = Arc::new(Mutex::new(ordered_relay()));
+        let (_local, mut rlocal) = relay.lock().await.add_peer();
+        let cert = Arc::new(rcgen::generate_simple_self_signed(["localhost".into()]).unwrap());
+        let (server, addr) = net::QuicServer::bind_and_run_with_cert(relay.clone(), cert.clone()).await.unwrap();
+        let client = net::QuicClient::connect(addr, cert.clone()).await.unwrap();
+
+        client.send(&Message::new(1200, vec![]).with_seq(0)).await.unwrap();
+        client.send(&Message::new(1202, vec![]).with_seq(2)).await.unwrap();
+        assert_eq!(tokio::time::timeout(Duration::from_secs(2), rlocal.recv()).await.unwrap().unwrap().id, 1200);
+
+        // seq 1 never comes; the server's timer releases seq 2 without a manual poll
+        let released = tokio::time::timeout(Duration::from_secs(2), rlocal.recv()).await.unwrap().unwrap();
+        assert_eq!(released.id, 1202);
+        drop(server);
+    }
+    
+    #[tokio::test]
+    async fn test_stage19_resilience_30_percent_loss() {
+        let mut config = RelayConfig::default();
+        config.loss_probability = 0.3; // 30% loss simulation
+        let mut relay = Relay::new(config);
+        
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let (_c, mut rc) = relay.add_peer();
+        
+        let mut successful_deliveries = 0;
+        let total_messages = 100;
+        
+        // Send many messages and count successful deliveries
+        for i in 0..total_messages {
+            let msg = Message::new(i, format!("test-{}", i).into_bytes());
+            relay.broadcast_from(a, msg);
+        }
+        
+        // Count received messages
+        while let Ok(_) = rb.try_recv() {
+            successful_deliveries += 1;
+        }
+        while let Ok(_) = rc.try_recv() {
+            successful_deliveries += 1;
+        }
+        
+        // With 30% loss, we should get roughly 70% * 2 peers = 140 deliveries
+        // Allow some variance due to randomness
+        let expected_min = (total_messages as f32 * 0.7 * 2.0 * 0.8) as usize; // 80% of expected
+        assert!(successful_deliveries >= expected_min, 
+                "Only {} deliveries, expected at least {}", successful_deliveries, expected_min);
+        
+        println!("✅ Stage 19: 30% loss tolerance working - {} deliveries", successful_deliveries);
+    }
+    
+    #[tokio::test]
+    async fn test_stage19_exit_criteria() {
+        println!("\n=== Stage 19: Relay Service Exit Criteria ===");
         
         // Test 1: QUIC/MASQUE server with multi-client support
         let config = RelayConfig::default();
@@ -943,40 +2795,1061 @@ mod tests {
             is_relay: true,
             connection_quality: 0.95,
         };
-        let (peer_id, _rx) = relay.add_peer_with_info(peer_info);
-        assert!(relay.get_peer_info(peer_id).is_some());
-        println!("✅ Test 5: Enhanced peer management - PASSED");
-        
-        // Test 6: Statistics and monitoring
-        let (total_peers, relay_peers, routing_entries, partition_detected) = relay.get_relay_stats();
-        assert_eq!(total_peers, 1);
-        assert_eq!(relay_peers, 1);
-        assert_eq!(routing_entries, 1);
-        assert!(!partition_detected);
-        println!("✅ Test 6: Statistics and monitoring - PASSED");
-        
-        println!("\n🎉 Stage 19: Relay Service - ALL TESTS PASSED!");
-        println!("📊 Features: Multi-client routing, Anti-eclipse logic, Partition recovery");
-        println!("🔧 Performance: 30% loss tolerance, 2-block recovery time capability");
-        println!("🏗️  Architecture: Production-ready relay service with resilience testing");
+        let (peer_id, _rx) = relay.add_peer_with_info(peer_info);
+        assert!(relay.get_peer_info(peer_id).is_some());
+        println!("✅ Test 5: Enhanced peer management - PASSED");
+        
+        // Test 6: Statistics and monitoring
+        let (total_peers, relay_peers, routing_entries, partition_detected) = relay.get_relay_stats();
+        assert_eq!(total_peers, 1);
+        assert_eq!(relay_peers, 1);
+        assert_eq!(routing_entries, 1);
+        assert!(!partition_detected);
+        println!("✅ Test 6: Statistics and monitoring - PASSED");
+        
+        println!("\n🎉 Stage 19: Relay Service - ALL TESTS PASSED!");
+        println!("📊 Features: Multi-client routing, Anti-eclipse logic, Partition recovery");
+        println!("🔧 Performance: 30% loss tolerance, 2-block recovery time capability");
+        println!("🏗️  Architecture: Production-ready relay service with resilience testing");
+    }
+
+    #[tokio::test]
+    async fn test_quic_skeleton_send() {
+        let relay = Arc::new(Mutex::new(Relay::new(RelayConfig::default())));
+        let cert = Arc::new(rcgen::generate_simple_self_signed(["localhost".into()]).unwrap());
+        let (server, addr) = net::QuicServer::bind_and_run_with_cert(relay.clone(), cert.clone()).await.unwrap();
+        // Connect client and send a message
+        let msg = Message::new(9999, b"net".to_vec());
+        net::QuicClient::connect_and_send(addr, cert.clone(), &msg).await.unwrap();
+        // Allow some time for processing
+        sleep(Duration::from_millis(50)).await;
+        // Ensure relay has seen the message (dedup contains id)
+        let seen = relay.lock().await.seen.contains(&9999);
+        assert!(seen);
+        // Drop server endpoint to close
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn test_quic_forwards_between_clients() {
+        let relay = Arc::new(Mutex::new(Relay::new(RelayConfig::default())));
+        let cert = Arc::new(rcgen::generate_simple_self_signed(["localhost".into()]).unwrap());
+        let (server, addr) = net::QuicServer::bind_and_run_with_cert(relay.clone(), cert.clone()).await.unwrap();
+
+        let client_a = net::QuicClient::connect(addr, cert.clone()).await.unwrap();
+        let client_b = net::QuicClient::connect(addr, cert.clone()).await.unwrap();
+        // Let the server register both connections as peers
+        sleep(Duration::from_millis(50)).await;
+
+        let msg = Message::new(4242, b"a-to-b".to_vec());
+        client_a.send(&msg).await.unwrap();
+
+        let got = tokio::time::timeout(Duration::from_secs(2), client_b.recv()).await.unwrap().unwrap();
+        assert_eq!(got.id, 4242);
+        assert_eq!(&got.data[..], b"a-to-b");
+
+        // The sender does not get its own message echoed back
+        assert!(tokio::time::timeout(Duration::from_millis(100), client_a.recv()).await.is_err());
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn test_signed_message_crosses_two_signed_only_relays() {
+        // client -> relay A (QUIC) -> bridge -> relay B -> local peer; both relays require signatures
+        let relay_a = Arc::new(Mutex::new(signed_only_relay()));
+        let cert = Arc::new(rcgen::generate_simple_self_signed(["localhost".into()]).unwrap());
+        let (server, addr) = net::QuicServer::bind_and_run_with_cert(relay_a.clone(), cert.clone()).await.unwrap();
+        let mut relay_b = signed_only_relay();
+        let (bridge_peer, _rbridge) = relay_b.add_peer();
+        let (_b, mut rb) = relay_b.add_peer();
+
+        let client = net::QuicClient::connect(addr, cert.clone()).await.unwrap();
+        let bridge = net::QuicClient::connect(addr, cert.clone()).await.unwrap();
+        sleep(Duration::from_millis(50)).await;
+
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let signed = SignedMessage::sign(Message::new(4343, b"two hops".to_vec()), &key);
+        client.send_signed(&signed).await.unwrap();
+
+        let forwarded = tokio::time::timeout(Duration::from_secs(2), bridge.recv()).await.unwrap().unwrap();
+        assert!(matches!(&forwarded, WireMessage::Signed(envelope) if envelope.verify()));
+        relay_b.receive(bridge_peer, forwarded);
+        let got = rb.try_recv().unwrap();
+        assert_eq!(got.id, 4343);
+        assert_eq!(got.sender(), Some(&key.verifying_key().to_bytes()));
+
+        // An unsigned message stops at the first relay
+        client.send(&Message::new(4344, b"plain".to_vec())).await.unwrap();
+        assert!(tokio::time::timeout(Duration::from_millis(100), bridge.recv()).await.is_err());
+        drop(server);
+    }
+
+    fn signed_handshake(relay: &mut Relay, peer_id: &str, capabilities: &[&str]) -> PeerHandshake {
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let capabilities = capabilities.iter().map(|c| c.to_string()).collect();
+        PeerHandshake::sign(peer_id, "127.0.0.1:9100".parse().unwrap(), capabilities, relay.handshake_challenge(), &key)
+    }
+
+    #[tokio::test]
+    async fn test_handshake_admits_relay_peer() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let (id, mut rx) = {
+            let handshake = signed_handshake(&mut relay, "relay-9", &["relay", "signed-messages", "teleport"]);
+            relay.admit_peer(handshake)
+        }.unwrap();
+
+        let info = relay.get_peer_info(id).unwrap();
+        assert!(info.is_relay);
+        assert_eq!(info.id, "relay-9");
+        // Capabilities the relay does not offer are not agreed
+        assert_eq!(relay.peer_capabilities(id).unwrap(), ["relay", "signed-messages"]);
+        assert_eq!(relay.snapshot_stats().relay_peers, 1);
+
+        // A peer that does not declare `relay` is admitted as a client
+        let (client, _rclient) = {
+            let handshake = signed_handshake(&mut relay, "client-9", &["signed-messages"]);
+            relay.admit_peer(handshake)
+        }.unwrap();
+        assert!(!relay.get_peer_info(client).unwrap().is_relay);
+
+        relay.broadcast_from(client, Message::new(3000, b"hi".to_vec()));
+        assert_eq!(rx.try_recv().unwrap().id, 3000);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_version_mismatch_refused() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let challenge = relay.handshake_challenge();
+        let mut handshake = PeerHandshake::sign("relay-old", "127.0.0.1:9101".parse().unwrap(), vec!["relay".to_string()], challenge, &key);
+        handshake.protocol_version = RELAY_PROTOCOL_VERSION + 1;
+
+        assert_eq!(
+            relay.admit_peer(handshake).unwrap_err(),
+            HandshakeRejection::VersionMismatch { ours: RELAY_PROTOCOL_VERSION, theirs: RELAY_PROTOCOL_VERSION + 1 }
+        );
+        assert_eq!(relay.snapshot_stats().peers_connected, 0);
+
+        // Claiming relay status after signing breaks the identity check
+        let mut forged = signed_handshake(&mut relay, "client-x", &["signed-messages"]);
+        forged.capabilities.push("relay".to_string());
+        assert_eq!(relay.admit_peer(forged).unwrap_err(), HandshakeRejection::InvalidIdentity);
+        assert_eq!(relay.snapshot_stats().relay_peers, 0);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_challenge_is_single_use() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let handshake = signed_handshake(&mut relay, "relay-1", &["relay"]);
+        assert!(relay.admit_peer(handshake.clone()).is_ok());
+        // Replaying a captured handshake fails once its challenge is spent
+        assert_eq!(relay.admit_peer(handshake).unwrap_err(), HandshakeRejection::UnknownChallenge);
+
+        // So does answering a challenge the relay never issued
+        let key = SigningKey::from_bytes(&[6u8; 32]);
+        let made_up = PeerHandshake::sign("relay-2", "127.0.0.1:9102".parse().unwrap(), vec![], [7u8; 32], &key);
+        assert_eq!(relay.admit_peer(made_up).unwrap_err(), HandshakeRejection::UnknownChallenge);
+        assert_eq!(relay.snapshot_stats().peers_connected, 1);
+    }
+
+    #[tokio::test]
+    async fn test_handshake_enforces_trust_list() {
+        let trusted = SigningKey::from_bytes(&[8u8; 32]);
+        let mut relay = Relay::new(RelayConfig {
+            trusted_peer_keys: Some(vec![trusted.verifying_key().to_bytes()]),
+            ..RelayConfig::default()
+        });
+
+        let stranger = signed_handshake(&mut relay, "relay-x", &["relay"]);
+        assert_eq!(relay.admit_peer(stranger).unwrap_err(), HandshakeRejection::UntrustedIdentity);
+
+        let challenge = relay.handshake_challenge();
+        let known = PeerHandshake::sign("relay-y", "127.0.0.1:9103".parse().unwrap(), vec!["relay".to_string()], challenge, &trusted);
+        assert!(relay.admit_peer(known).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_quic_accept_runs_handshake() {
+        let trusted = SigningKey::from_bytes(&[10u8; 32]);
+        let relay = Arc::new(Mutex::new(Relay::new(RelayConfig {
+            trusted_peer_keys: Some(vec![trusted.verifying_key().to_bytes()]),
+            ..RelayConfig::default()
+        })));
+        let cert = Arc::new(rcgen::generate_simple_self_signed(vec!["localhost".into()]).unwrap());
+        let (_server, addr) = net::QuicServer::bind_and_run_with_cert(relay.clone(), cert.clone()).await.unwrap();
+        let options = net::ClientTlsOptions {
+            server_name: "localhost".to_string(),
+            trust: net::ClientTrust::pinned(&cert).unwrap(),
+            identity: None,
+        };
+
+        // A throwaway identity is not on the trust list
+        assert!(net::QuicClient::connect(addr, cert.clone()).await.is_err());
+
+        let identity = HandshakeIdentity::new("relay-quic", vec!["relay".to_string()], trusted);
+        let _conn = net::QuicClient::connect_with_handshake(addr, &options, &identity).await.unwrap();
+        let relay = relay.lock().await;
+        let stats = relay.snapshot_stats();
+        assert_eq!(stats.peers_connected, 1);
+        assert_eq!(stats.relay_peers, 1);
+    }
+
+    fn signed_only_relay() -> Relay {
+        Relay::new(RelayConfig { require_signed: true, ..RelayConfig::default() })
+    }
+
+    #[tokio::test]
+    async fn test_signed_message_accepted() {
+        let mut relay = signed_only_relay();
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let signed = SignedMessage::sign(Message::new(11, b"signed".to_vec()), &key);
+        assert!(signed.verify());
+        relay.broadcast_signed(a, signed);
+
+        let got = rb.recv().await.unwrap();
+        assert_eq!(got.id, 11);
+        assert_eq!(&got.data[..], b"signed");
+    }
+
+    #[tokio::test]
+    async fn test_forged_signed_message_dropped() {
+        let mut relay = signed_only_relay();
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let before = METRICS.drop_unauthenticated.get();
+
+        let key = SigningKey::from_bytes(&rand::random::<[u8; 32]>());
+        let mut forged = SignedMessage::sign(Message::new(12, b"original".to_vec()), &key);
+        forged.message.data = b"tampered"[..].into();
+        relay.broadcast_signed(a, forged.clone());
+
+        // Re-using the signature under a different dedup id is also rejected
+        forged.message = Message::new(13, b"original".to_vec());
+        relay.broadcast_signed(a, forged);
+
+        assert!(rb.try_recv().is_err());
+        assert!(METRICS.drop_unauthenticated.get() >= before + 2.0);
+        // A forged id must not poison dedup for the genuine message
+        assert!(!relay.already_seen(12));
+    }
+
+    #[tokio::test]
+    async fn test_unsigned_message_dropped_when_required() {
+        let mut relay = signed_only_relay();
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let before = METRICS.drop_unauthenticated.get();
+
+        relay.broadcast_from(a, Message::new(14, b"plain".to_vec()));
+        assert!(rb.try_recv().is_err());
+        assert!(METRICS.drop_unauthenticated.get() >= before + 1.0);
+
+        // Without the flag unsigned messages still flow
+        let mut open = Relay::new(RelayConfig::default());
+        let (a, _ra) = open.add_peer();
+        let (_b, mut rb) = open.add_peer();
+        open.broadcast_from(a, Message::new(14, b"plain".to_vec()));
+        assert_eq!(rb.recv().await.unwrap().id, 14);
+    }
+
+    #[tokio::test]
+    async fn test_route_to_direct_next_hop() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let (a, mut ra) = relay.add_peer();
+        let (b, mut rb) = relay.add_peer();
+        let (_c, mut rc) = relay.add_peer();
+        relay.update_routing("node-b".to_string(), b, 1);
+
+        relay.route_to(a, "node-b", Message::new(21, b"direct".to_vec()));
+
+        let got = rb.recv().await.unwrap();
+        assert_eq!(got.id, 21);
+        assert_eq!(got.ttl, DEFAULT_MESSAGE_TTL - 1);
+        // Only the next hop receives it
+        assert!(ra.try_recv().is_err());
+        assert!(rc.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_route_to_multi_hop() {
+        // relay_1 -> relay_2 -> relay_3 -> destination, one TTL per hop
+        let mut relays: Vec<Relay> = (0..3).map(|_| Relay::new(RelayConfig::default())).collect();
+        let mut hop_rxs = Vec::new();
+        for (i, relay) in relays.iter_mut().enumerate() {
+            let (upstream, other_rx) = relay.add_peer();
+            let (next, rx) = relay.add_peer();
+            relay.update_routing("far-node".to_string(), next, (3 - i) as u32);
+            hop_rxs.push((upstream, rx, other_rx));
+        }
+
+        let mut msg: WireMessage = Message::new(22, b"multi-hop".to_vec()).into();
+        for (relay, (upstream, rx, other_rx)) in relays.iter_mut().zip(hop_rxs.iter_mut()) {
+            relay.route_to(*upstream, "far-node", msg);
+            msg = rx.recv().await.unwrap();
+            assert!(other_rx.try_recv().is_err());
+        }
+
+        assert_eq!(msg.id, 22);
+        assert_eq!(msg.ttl, DEFAULT_MESSAGE_TTL - 3);
+    }
+
+    #[tokio::test]
+    async fn test_route_to_ttl_exhaustion_and_flood_fallback() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let (a, mut ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let (c, mut rc) = relay.add_peer();
+        let before = METRICS.drop_ttl.get();
+
+        // Last hop: forwarded with TTL 0, then dropped by the next relay
+        let mut msg = Message::new(23, b"ttl".to_vec());
+        msg.ttl = 1;
+        relay.route_to(c, "unknown", msg);
+        let flooded_a = ra.recv().await.unwrap();
+        let flooded_b = rb.recv().await.unwrap();
+        assert_eq!(flooded_a.ttl, 0);
+        assert_eq!(flooded_b.ttl, 0);
+        // The flood skips the peer the message came from
+        assert!(rc.try_recv().is_err());
+
+        relay.route_to(a, "unknown", flooded_a);
+        assert!(ra.try_recv().is_err());
+        assert!(rb.try_recv().is_err());
+        assert!(rc.try_recv().is_err());
+        assert!(METRICS.drop_ttl.get() >= before + 1.0);
+    }
+
+    #[tokio::test]
+    async fn test_route_to_skips_next_hop_that_is_the_source() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let (a, mut ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        relay.update_routing("node-a".to_string(), a, 1);
+
+        // Routing back to the sender would loop; flood the others instead
+        relay.route_to(a, "node-a", Message::new(24, b"loop".to_vec()));
+        assert_eq!(rb.recv().await.unwrap().id, 24);
+        assert!(ra.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_route_to_dedups_and_authenticates() {
+        let mut relay = Relay::new(RelayConfig { require_signed: true, ..RelayConfig::default() });
+        let (a, _ra) = relay.add_peer();
+        let (b, mut rb) = relay.add_peer();
+        relay.update_routing("node-b".to_string(), b, 1);
+        let unauthenticated = relay.dropped.unauthenticated;
+
+        relay.route_to(a, "node-b", Message::new(25, b"unsigned".to_vec()));
+        assert!(rb.try_recv().is_err());
+        assert_eq!(relay.dropped.unauthenticated, unauthenticated + 1);
+
+        let key = SigningKey::from_bytes(&[9u8; 32]);
+        let signed = SignedMessage::sign(Message::new(26, b"signed".to_vec()), &key);
+        relay.route_to(a, "node-b", signed.clone());
+        let got = rb.recv().await.unwrap();
+        assert_eq!(got.id, 26);
+        assert_eq!(got.sender(), Some(&key.verifying_key().to_bytes()));
+
+        // The same message arriving again is a duplicate
+        relay.route_to(a, "node-b", signed);
+        assert!(rb.try_recv().is_err());
+    }
+
+    #[test]
+    fn test_frame_rejects_other_protocol_versions() {
+        let msg = WireMessage::Plain(Message::new(27, b"framed".to_vec()));
+        let frame = net::encode_frame(&msg).unwrap();
+        assert_eq!(net::decode_frame(&frame).unwrap().id, 27);
+
+        let mut stale = frame.clone();
+        stale[..2].copy_from_slice(&(RELAY_PROTOCOL_VERSION - 1).to_be_bytes());
+        assert!(net::decode_frame(&stale).is_err());
+        assert!(net::decode_frame(&frame[..1]).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_snapshot_stats_reflects_rate_limit_drop() {
+        let mut relay = Relay::new(RelayConfig {
+            rate_limit_per_sec: 0.0,
+            rate_limit_burst: 2.0,
+            ..RelayConfig::default()
+        });
+        let (a, _ra) = relay.add_peer();
+        let (_b, _rb) = relay.add_peer();
+        let (_c, _rc) = relay.add_peer();
+        relay.remove_peer(2);
+        relay.update_routing("node-b".to_string(), 1, 1);
+
+        for i in 0..3u64 {
+            relay.broadcast_from(a, Message::new(300 + i, vec![i as u8]));
+        }
+        relay.broadcast_from(a, Message::new(300, vec![0]));
+
+        let stats = relay.snapshot_stats();
+        assert_eq!(stats.broadcasted, 2);
+        assert_eq!(stats.dropped.rate_limit, 1);
+        assert_eq!(stats.dropped.dedup, 1);
+        assert_eq!(stats.dropped.loss, 0);
+        assert_eq!(stats.peers_connected, 2);
+        assert_eq!(stats.routing_table_size, 1);
+        assert!(!stats.partition_detected);
+    }
+
+    fn quality_peer(id: &str, quality: f64) -> PeerInfo {
+        PeerInfo {
+            id: id.to_string(),
+            address: "127.0.0.1:9000".parse().unwrap(),
+            last_seen: Instant::now(),
+            message_count: 0,
+            is_relay: true,
+            connection_quality: quality,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_scales_with_connection_quality() {
+        let mut relay = Relay::new(RelayConfig {
+            rate_limit_per_sec: 0.0,
+            rate_limit_burst: 10.0,
+            ..RelayConfig::default()
+        });
+        let (good, _rg) = relay.add_peer_with_info(quality_peer("good", 0.9));
+        let (poor, _rp) = relay.add_peer_with_info(quality_peer("poor", 0.2));
+        let (_sink, mut sink_rx) = relay.add_peer();
+
+        let mut delivered = |relay: &mut Relay, source: usize, base: u64| {
+            for i in 0..30u64 {
+                relay.broadcast_from(source, Message::new(base + i, vec![]));
+            }
+            let mut n = 0;
+            while sink_rx.try_recv().is_ok() { n += 1; }
+            n
+        };
+
+        let good_count = delivered(&mut relay, good, 6000);
+        let poor_count = delivered(&mut relay, poor, 7000);
+        assert_eq!(good_count, 18);
+        assert_eq!(poor_count, 4);
+        assert!(good_count > poor_count);
     }
 
     #[tokio::test]
-    async fn test_quic_skeleton_send() {
+    async fn test_peer_rate_multiplier_override() {
+        let mut relay = Relay::new(RelayConfig {
+            rate_limit_per_sec: 0.0,
+            rate_limit_burst: 10.0,
+            ..RelayConfig::default()
+        });
+        let (poor, _rp) = relay.add_peer_with_info(quality_peer("poor", 0.2));
+        let (unknown, _ru) = relay.add_peer();
+        let (_sink, mut sink_rx) = relay.add_peer();
+        relay.set_peer_rate_multiplier(poor, 3.0);
+
+        for i in 0..40u64 {
+            relay.broadcast_from(poor, Message::new(8000 + i, vec![]));
+            relay.broadcast_from(unknown, Message::new(9000 + i, vec![]));
+        }
+
+        let mut from_poor = 0;
+        let mut from_unknown = 0;
+        while let Ok(msg) = sink_rx.try_recv() {
+            if msg.id < 9000 { from_poor += 1 } else { from_unknown += 1 }
+        }
+        assert_eq!(from_poor, 30);
+        // Peers without PeerInfo or an override use the config values
+        assert_eq!(from_unknown, 10);
+    }
+
+    fn test_ca() -> rcgen::Certificate {
+        let mut params = rcgen::CertificateParams::new(Vec::<String>::new());
+        params.is_ca = rcgen::IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        params.distinguished_name.push(rcgen::DnType::CommonName, "relay test CA");
+        rcgen::Certificate::from_params(params).unwrap()
+    }
+
+    fn ca_signed_identity(ca: &rcgen::Certificate, name: &str) -> net::TlsIdentity {
+        let leaf = rcgen::generate_simple_self_signed([name.to_string()]).unwrap();
+        net::TlsIdentity {
+            cert_chain: vec![rustls::Certificate(leaf.serialize_der_with_signer(ca).unwrap())],
+            key: rustls::PrivateKey(leaf.serialize_private_key_der()),
+        }
+    }
+
+    fn ca_roots(ca: &rcgen::Certificate) -> rustls::RootCertStore {
+        let mut roots = rustls::RootCertStore::empty();
+        roots.add(&rustls::Certificate(ca.serialize_der().unwrap())).unwrap();
+        roots
+    }
+
+    #[tokio::test]
+    async fn test_quic_ca_validated_connection() {
+        let ca = test_ca();
         let relay = Arc::new(Mutex::new(Relay::new(RelayConfig::default())));
-        let cert = Arc::new(rcgen::generate_simple_self_signed(["localhost".into()]).unwrap());
-        let (server, addr) = net::QuicServer::bind_and_run_with_cert(relay.clone(), cert.clone()).await.unwrap();
-        // Connect client and send a message
-        let msg = Message { id: 9999, data: b"net".to_vec() };
-        net::QuicClient::connect_and_send(addr, cert.clone(), &msg).await.unwrap();
-        // Allow some time for processing
+        let server_opts = net::ServerTlsOptions {
+            identity: ca_signed_identity(&ca, "relay-1.mesh"),
+            client_auth: None,
+        };
+        let (server, addr) = net::QuicServer::bind_and_run_with_options(relay.clone(), server_opts).await.unwrap();
+
+        let client_opts = net::ClientTlsOptions {
+            server_name: "relay-1.mesh".to_string(),
+            trust: net::ClientTrust::CaRoots(ca_roots(&ca)),
+            identity: None,
+        };
+        let client = net::QuicClient::connect_with_options(addr, &client_opts).await.unwrap();
+        client.send(&Message::new(7, b"ca".to_vec())).await.unwrap();
         sleep(Duration::from_millis(50)).await;
-        // Ensure relay has seen the message (dedup contains id)
-        let seen = relay.lock().await.seen.contains(&9999);
-        assert!(seen);
-        // Drop server endpoint to close
+        assert!(relay.lock().await.already_seen(7));
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn test_quic_rejects_untrusted_server_cert() {
+        let ca = test_ca();
+        let relay = Arc::new(Mutex::new(Relay::new(RelayConfig::default())));
+        // Server presents a self-signed cert that does not chain to the client's CA
+        let cert = Arc::new(rcgen::generate_simple_self_signed(["localhost".into()]).unwrap());
+        let (server, addr) = net::QuicServer::bind_and_run_with_cert(relay, cert).await.unwrap();
+
+        let client_opts = net::ClientTlsOptions {
+            server_name: "localhost".to_string(),
+            trust: net::ClientTrust::CaRoots(ca_roots(&ca)),
+            identity: None,
+        };
+        assert!(net::QuicClient::connect_with_options(addr, &client_opts).await.is_err());
+        drop(server);
+    }
+
+    #[tokio::test]
+    async fn test_quic_mutual_tls_restricts_relay_identities() {
+        let ca = test_ca();
+        let relay = Arc::new(Mutex::new(Relay::new(RelayConfig::default())));
+        let server_opts = net::ServerTlsOptions {
+            identity: ca_signed_identity(&ca, "relay-1.mesh"),
+            client_auth: Some(net::ClientAuth {
+                roots: ca_roots(&ca),
+                allowed_identities: vec!["relay-2.mesh".to_string()],
+            }),
+        };
+        let (server, addr) = net::QuicServer::bind_and_run_with_options(relay.clone(), server_opts).await.unwrap();
+
+        let client_opts = |identity: Option<net::TlsIdentity>| net::ClientTlsOptions {
+            server_name: "relay-1.mesh".to_string(),
+            trust: net::ClientTrust::CaRoots(ca_roots(&ca)),
+            identity,
+        };
+
+        let allowed = net::QuicClient::connect_with_options(addr, &client_opts(Some(ca_signed_identity(&ca, "relay-2.mesh"))))
+            .await
+            .unwrap();
+        allowed.send(&Message::new(1, b"ok".to_vec())).await.unwrap();
+
+        // A CA-signed cert for an unlisted relay, or no cert at all, never gets a message through
+        for identity in [Some(ca_signed_identity(&ca, "relay-3.mesh")), None] {
+            if let Ok(conn) = net::QuicClient::connect_with_options(addr, &client_opts(identity)).await {
+                let _ = conn.send(&Message::new(2, b"denied".to_vec())).await;
+            }
+        }
+
+        sleep(Duration::from_millis(100)).await;
+        let mut r = relay.lock().await;
+        assert!(r.already_seen(1));
+        assert!(!r.already_seen(2));
+        drop(r);
         drop(server);
     }
+
+    fn temp_storage_dir(name: &str) -> std::path::PathBuf {
+        std::env::temp_dir().join(format!("bpi-relay-{}-{}", name, rand::random::<u64>()))
+    }
+
+    #[tokio::test]
+    async fn test_persist_and_restore_state() {
+        let path = temp_storage_dir("state").with_extension("zst");
+        let mut relay = Relay::new(RelayConfig::default());
+        let (relay_peer, _rx) = relay.add_peer_with_info(PeerInfo {
+            id: "relay-a".to_string(),
+            address: "127.0.0.1:9000".parse().unwrap(),
+            last_seen: Instant::now(),
+            message_count: 7,
+            is_relay: true,
+            connection_quality: 0.9,
+        });
+
+        relay.record_seen(1);
+        relay.record_seen(2);
+        relay.seen.put(3, Instant::now() - Duration::from_secs(600));
+        relay.update_routing("fresh".to_string(), relay_peer, 1);
+        relay.update_routing("stale".to_string(), relay_peer, 2);
+        relay.routing_table.get_mut("stale").unwrap().last_updated =
+            Instant::now() - Duration::from_secs(ROUTE_MAX_AGE_SECS + 60);
+
+        // A disconnected peer and its routes are not persisted
+        let (gone, _gone_rx) = relay.add_peer_with_info(PeerInfo {
+            id: "gone".to_string(),
+            address: "127.0.0.1:9001".parse().unwrap(),
+            last_seen: Instant::now(),
+            message_count: 1,
+            is_relay: false,
+            connection_quality: 0.5,
+        });
+        relay.update_routing("via-gone".to_string(), gone, 1);
+        relay.remove_peer(gone);
+
+        relay.persist_state(&path).unwrap();
+
+        let mut restored = Relay::new(RelayConfig::default());
+        restored.dedup_ttl_secs = 60;
+        restored.restore_state(&path).unwrap();
+
+        assert!(restored.already_seen(1));
+        assert!(restored.already_seen(2));
+        assert!(!restored.already_seen(3));
+        // Nothing is bound to an index until the peer reconnects
+        assert!(restored.routing_table.is_empty());
+        assert!(restored.peer_info.is_empty());
+
+        // Relay-a comes back at a different index; its routes follow it
+        let (_other, _other_rx) = restored.add_peer();
+        let (rejoined, _rejoined_rx) = restored.add_peer_with_info(PeerInfo {
+            id: "relay-a".to_string(),
+            address: "127.0.0.1:9000".parse().unwrap(),
+            last_seen: Instant::now(),
+            message_count: 0,
+            is_relay: true,
+            connection_quality: 0.1,
+        });
+        assert_ne!(rejoined, relay_peer);
+        assert_eq!(restored.routing_table.get("fresh").map(|entry| entry.next_hop), Some(rejoined));
+        assert!(!restored.routing_table.contains_key("stale"));
+        assert_eq!(restored.peer_info.get(&rejoined).map(|info| info.message_count), Some(7));
+        assert!(restored.anti_eclipse.relay_peers.contains_key("relay-a"));
+
+        let (_gone_again, _rx) = restored.add_peer_with_info(PeerInfo {
+            id: "gone".to_string(),
+            address: "127.0.0.1:9001".parse().unwrap(),
+            last_seen: Instant::now(),
+            message_count: 0,
+            is_relay: false,
+            connection_quality: 0.5,
+        });
+        assert!(!restored.routing_table.contains_key("via-gone"));
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_distributed_store_retrieve_round_trip() {
+        let dir = temp_storage_dir("roundtrip");
+        let mut relay = Relay::new_with_distributed_storage(RelayConfig::default(), &dir);
+
+        let hash = relay.store_distributed(b"doc", b"hello relay storage").await.unwrap();
+        assert_eq!(hash, blake3::hash(b"hello relay storage").as_bytes().to_vec());
+
+        let data = relay.retrieve_distributed(&hash).await.unwrap();
+        assert_eq!(data, Some(b"hello relay storage".to_vec()));
+        assert_eq!(relay.retrieve_distributed(&[0u8; 32]).await.unwrap(), None);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_distributed_pin_protects_from_gc() {
+        let dir = temp_storage_dir("gc");
+        let mut relay = Relay::new_with_distributed_storage(RelayConfig::default(), &dir);
+
+        let pinned = relay.store_distributed(b"a", b"keep me").await.unwrap();
+        let unpinned = relay.store_distributed(b"b", b"collect me").await.unwrap();
+        relay.pin_data(&pinned, 10).await.unwrap();
+
+        assert_eq!(relay.gc_storage().await.unwrap(), 1);
+        assert!(relay.retrieve_distributed(&pinned).await.unwrap().is_some());
+        assert!(relay.retrieve_distributed(&unpinned).await.unwrap().is_none());
+
+        relay.unpin_data(&pinned).await.unwrap();
+        assert_eq!(relay.gc_storage().await.unwrap(), 1);
+        assert!(relay.retrieve_distributed(&pinned).await.unwrap().is_none());
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_distributed_pins_survive_restart() {
+        let dir = temp_storage_dir("pins");
+        let mut relay = Relay::new_with_distributed_storage(RelayConfig::default(), &dir);
+        let pinned = relay.store_distributed(b"a", b"keep me").await.unwrap();
+        let unpinned = relay.store_distributed(b"b", b"collect me").await.unwrap();
+        relay.pin_data(&pinned, 10).await.unwrap();
+        drop(relay);
+
+        // A restarted relay still honours the pin
+        let mut restarted = Relay::new_with_distributed_storage(RelayConfig::default(), &dir);
+        assert_eq!(restarted.gc_storage().await.unwrap(), 1);
+        assert!(restarted.retrieve_distributed(&pinned).await.unwrap().is_some());
+        assert!(restarted.retrieve_distributed(&unpinned).await.unwrap().is_none());
+
+        // And an unpin is just as durable
+        restarted.unpin_data(&pinned).await.unwrap();
+        drop(restarted);
+        let mut again = Relay::new_with_distributed_storage(RelayConfig::default(), &dir);
+        assert_eq!(again.gc_storage().await.unwrap(), 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[tokio::test]
+    async fn test_distributed_storage_stats() {
+        let dir = temp_storage_dir("stats");
+        let mut relay = Relay::new_with_distributed_storage(RelayConfig::default(), &dir);
+
+        let first = relay.store_distributed(b"a", &[1u8; 100]).await.unwrap();
+        relay.store_distributed(b"b", &[2u8; 250]).await.unwrap();
+        // Duplicate content is stored once
+        relay.store_distributed(b"c", &[1u8; 100]).await.unwrap();
+        relay.pin_data(&first, 1).await.unwrap();
+
+        let stats: content_store::ContentStoreStats = serde_json::from_str(&relay.get_storage_stats()).unwrap();
+        assert_eq!(stats.objects, 2);
+        assert_eq!(stats.bytes, 350);
+        assert_eq!(stats.pinned_objects, 1);
+
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[cfg(feature = "military-storage")]
+    #[tokio::test]
+    async fn test_persistent_dedup_after_lru_eviction() {
+        let cfg = RelayConfig {
+            dedup_cache: 2,
+            ..RelayConfig::default()
+        };
+        let store = dedup::SledDedupStore::temporary().unwrap();
+        let mut relay = Relay::new_with_dedup_store(cfg, Box::new(store), 3600);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        // Ids 1..=3 fill the two-entry LRU, evicting id 1
+        for id in 1..=3u64 {
+            relay.broadcast_from(a, Message::new(id, vec![id as u8]));
+        }
+        assert!(!relay.seen.contains(&1));
+        let mut delivered = 0; while rb.try_recv().is_ok() { delivered += 1; }
+        assert_eq!(delivered, 3);
+
+        // Replaying the evicted id is still caught by the persistent store
+        relay.broadcast_from(a, Message::new(1, vec![1]));
+        assert!(rb.try_recv().is_err());
+    }
+
+    #[cfg(feature = "military-storage")]
+    #[tokio::test]
+    async fn test_persistent_dedup_respects_ttl() {
+        let cfg = RelayConfig {
+            dedup_cache: 1,
+            ..RelayConfig::default()
+        };
+        let store = dedup::SledDedupStore::temporary().unwrap();
+        // Record id 7 as seen two hours ago
+        store.put_seen(7, dedup::unix_now() - 7200).unwrap();
+        let mut relay = Relay::new_with_dedup_store(cfg, Box::new(store), 3600);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        relay.broadcast_from(a, Message::new(7, vec![7]));
+        assert!(rb.try_recv().is_ok());
+    }
+
+    fn relay_with_max_age(secs: u64) -> Relay {
+        Relay::new(RelayConfig {
+            message_max_age: Some(Duration::from_secs(secs)),
+            ..RelayConfig::default()
+        })
+    }
+
+    #[tokio::test]
+    async fn test_fresh_message_delivered_within_max_age() {
+        let mut relay = relay_with_max_age(60);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        relay.broadcast_from(a, Message::new(900, b"fresh".to_vec()));
+        assert_eq!(&*rb.try_recv().unwrap().data, b"fresh");
+
+        // Messages without a creation time are never treated as expired
+        let mut undated = Message::new(901, b"undated".to_vec());
+        undated.created_at = 0;
+        relay.broadcast_from(a, undated);
+        assert!(rb.try_recv().is_ok());
+        assert_eq!(relay.snapshot_stats().dropped.expired, 0);
+    }
+
+    #[tokio::test]
+    async fn test_expired_message_dropped_before_fanout() {
+        let mut relay = relay_with_max_age(60);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let before = METRICS.drop_expired.get();
+
+        let mut stale = Message::new(902, b"stale".to_vec());
+        stale.created_at = dedup::unix_now() - 3600;
+        relay.broadcast_from(a, stale.clone());
+        assert!(rb.try_recv().is_err());
+        assert_eq!(relay.snapshot_stats().dropped.expired, 1);
+        assert!(METRICS.drop_expired.get() >= before + 1.0);
+
+        // Signed messages cannot have their age refreshed in transit
+        let key = SigningKey::from_bytes(&[4u8; 32]);
+        let mut signed = SignedMessage::sign(stale, &key);
+        signed.message.created_at = dedup::unix_now();
+        assert!(!signed.verify());
+    }
+
+    #[tokio::test]
+    async fn test_future_dated_message_is_expired() {
+        let mut relay = relay_with_max_age(60);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        // Within the allowed skew a slightly fast clock is tolerated
+        let mut skewed = Message::new(903, b"skewed".to_vec());
+        skewed.created_at = dedup::unix_now() + MAX_CLOCK_SKEW_SECS / 2;
+        relay.broadcast_from(a, skewed);
+        assert!(rb.try_recv().is_ok());
+
+        let mut future = Message::new(904, b"future".to_vec());
+        future.created_at = dedup::unix_now() + 3600;
+        relay.broadcast_from(a, future);
+        assert!(rb.try_recv().is_err());
+        assert_eq!(relay.snapshot_stats().dropped.expired, 1);
+    }
+
+    #[tokio::test]
+    async fn test_route_to_drops_expired_message() {
+        let mut relay = relay_with_max_age(60);
+        let (a, _ra) = relay.add_peer();
+        let (b, mut rb) = relay.add_peer();
+        relay.update_routing("node-b".to_string(), b, 1);
+
+        let mut stale = Message::new(905, b"stale".to_vec());
+        stale.created_at = dedup::unix_now() - 3600;
+        relay.route_to(a, "node-b", stale);
+        assert!(rb.try_recv().is_err());
+        assert_eq!(relay.snapshot_stats().dropped.expired, 1);
+    }
+
+    #[test]
+    fn test_signature_covers_seq_and_created_at_presence() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let mut message = Message::new(906, b"fields".to_vec()).with_seq(3);
+        message.created_at = 1_700_000_000;
+        let signed = SignedMessage::sign(message, &key);
+        assert!(signed.verify());
+
+        // Stripping either optional field breaks the signature
+        let mut unsequenced = signed.clone();
+        unsequenced.message.seq = None;
+        assert!(!unsequenced.verify());
+        let mut undated = signed.clone();
+        undated.message.created_at = 0;
+        assert!(!undated.verify());
+
+        // Bytes cannot move between the payload and the trailing fields
+        let mut shifted = signed;
+        let mut data = shifted.message.data.to_vec();
+        data.extend_from_slice(&3u64.to_be_bytes());
+        shifted.message.data = data.into();
+        shifted.message.seq = None;
+        assert!(!shifted.verify());
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_duration_histogram_records_fanout() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let (a, _ra) = relay.add_peer();
+        let (_b, _rb) = relay.add_peer();
+        let (_c, _rc) = relay.add_peer();
+        let before = METRICS.broadcast_duration.get_sample_count();
+
+        for i in 0..3u64 {
+            relay.broadcast_from(a, Message::new(950 + i, vec![i as u8]));
+        }
+        assert!(METRICS.broadcast_duration.get_sample_count() >= before + 3);
+
+        // Everything is served by `/metrics` from the default registry
+        let names: Vec<String> = prometheus::gather().iter().map(|family| family.get_name().to_string()).collect();
+        for expected in [
+            "relay_broadcast_duration_seconds",
+            "relay_peers_current",
+            "relay_broadcasted_total",
+            "relay_drop_dedup_total",
+            "relay_drop_rate_limit_total",
+            "relay_drop_expired_total",
+        ] {
+            assert!(names.iter().any(|name| name == expected), "{} not registered", expected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_peer_gauge_sums_relays() {
+        // Private metrics so concurrently running tests do not move the gauge
+        let metrics: &'static RelayMetrics = Box::leak(Box::new(RelayMetrics::new()));
+        let mut first = Relay::new(RelayConfig::default());
+        let mut second = Relay::new(RelayConfig::default());
+        first.metrics = metrics;
+        second.metrics = metrics;
+
+        let (_a, _ra) = first.add_peer();
+        let (b, _rb) = first.add_peer();
+        let (_c, _rc) = second.add_peer();
+        assert_eq!(metrics.peers_current.get(), 3.0);
+
+        // One relay losing a peer does not hide the other's
+        first.remove_peer(b);
+        assert_eq!(metrics.peers_current.get(), 2.0);
+        second.shutdown();
+        assert_eq!(metrics.peers_current.get(), 1.0);
+        drop(first);
+        assert_eq!(metrics.peers_current.get(), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_content_dedup_key_separates_payloads_sharing_an_id() {
+        let mut relay = Relay::new(RelayConfig::default()).with_dedup_key(ContentHashKey);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+
+        relay.broadcast_from(a, Message::new(1, b"first".to_vec()));
+        relay.broadcast_from(a, Message::new(1, b"second".to_vec()));
+        assert_eq!(&*rb.try_recv().unwrap().data, b"first");
+        assert_eq!(&*rb.try_recv().unwrap().data, b"second");
+
+        // The same payload under the same id is still a duplicate
+        relay.broadcast_from(a, Message::new(1, b"first".to_vec()));
+        assert!(rb.try_recv().is_err());
+        assert_eq!(relay.snapshot_stats().dropped.dedup, 1);
+
+        // The default keys on the id alone
+        let mut by_id = Relay::new(RelayConfig::default());
+        let (a, _ra) = by_id.add_peer();
+        let (_b, mut rb) = by_id.add_peer();
+        by_id.broadcast_from(a, Message::new(1, b"first".to_vec()));
+        by_id.broadcast_from(a, Message::new(1, b"second".to_vec()));
+        assert!(rb.try_recv().is_ok());
+        assert!(rb.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_content_dedup_key_separates_signed_senders() {
+        let mut relay = Relay::new(RelayConfig::default()).with_dedup_key(ContentHashKey);
+        let (a, _ra) = relay.add_peer();
+        let (_b, mut rb) = relay.add_peer();
+        let alice = SigningKey::from_bytes(&[5u8; 32]);
+        let bob = SigningKey::from_bytes(&[6u8; 32]);
+        let msg = Message::new(2, b"same".to_vec());
+
+        relay.broadcast_signed(a, SignedMessage::sign(msg.clone(), &alice));
+        relay.broadcast_signed(a, SignedMessage::sign(msg.clone(), &bob));
+        relay.broadcast_signed(a, SignedMessage::sign(msg, &alice));
+        assert!(rb.try_recv().is_ok());
+        assert!(rb.try_recv().is_ok());
+        assert!(rb.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_seeded_rng_reproduces_loss_pattern() {
+        let run = || {
+            let mut relay = Relay::new(RelayConfig { loss_probability: 0.5, ..RelayConfig::default() })
+                .with_rng(rand_chacha::ChaCha8Rng::seed_from_u64(42));
+            let (a, _ra) = relay.add_peer();
+            let mut receivers: Vec<_> = (0..3).map(|_| relay.add_peer().1).collect();
+            for id in 0..20u64 {
+                relay.broadcast_from(a, Message::new(970 + id, vec![id as u8]));
+            }
+            receivers.iter_mut()
+                .map(|rx| std::iter::from_fn(|| rx.try_recv().ok()).map(|msg| msg.id).collect::<Vec<_>>())
+                .collect::<Vec<_>>()
+        };
+
+        // One draw per message per peer, in message-then-peer order
+        let mut draws = rand_chacha::ChaCha8Rng::seed_from_u64(42);
+        let mut expected = vec![Vec::new(); 3];
+        for id in 0..20u64 {
+            for delivered in expected.iter_mut() {
+                let p: f32 = draws.gen();
+                if p >= 0.5 {
+                    delivered.push(970 + id);
+                }
+            }
+        }
+
+        let pattern = run();
+        assert_eq!(pattern, expected);
+        assert_eq!(run(), pattern);
+        let delivered: usize = pattern.iter().map(Vec::len).sum();
+        assert!(delivered > 0 && delivered < 60);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_closes_peer_channels() {
+        let mut relay = Relay::new(RelayConfig::default());
+        let (a, mut ra) = relay.add_peer_with_info(stage19_peer("relay-a", true));
+        let (_b, mut rb) = relay.add_peer_with_info(stage19_peer("client-b", false));
+        relay.update_routing("client-b".to_string(), 1, 1);
+        relay.broadcast_from(a, Message::new(960, b"before".to_vec()));
+
+        relay.shutdown();
+        assert!(!relay.is_operational());
+        assert_eq!(relay.get_relay_stats(), (0, 0, 0, false));
+        assert_eq!(relay.snapshot_stats().peers_connected, 0);
+
+        // Queued messages drain, then the channel reports closure
+        assert_eq!(&*rb.recv().await.unwrap().data, b"before");
+        assert!(rb.recv().await.is_none());
+        assert!(matches!(ra.try_recv(), Err(tokio::sync::mpsc::error::TryRecvError::Disconnected)));
+
+        // Later broadcasts are no-ops rather than drops
+        let broadcasted = relay.snapshot_stats().broadcasted;
+        relay.broadcast_from(a, Message::new(961, b"after".to_vec()));
+        let stats = relay.snapshot_stats();
+        assert_eq!(stats.broadcasted, broadcasted);
+        assert_eq!(stats.dropped, DropCounts::default());
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_refuses_new_peers() {
+        let mut relay = Relay::new(RelayConfig::default());
+        relay.shutdown();
+
+        let (_late, mut rx) = relay.add_peer_with_info(stage19_peer("relay-late", true));
+        assert!(rx.recv().await.is_none());
+        assert_eq!(relay.snapshot_stats().peers_connected, 0);
+        assert_eq!(relay.snapshot_stats().relay_peers, 0);
+
+        let handshake = signed_handshake(&mut relay, "relay-late", &["relay"]);
+        assert_eq!(relay.admit_peer(handshake).unwrap_err(), HandshakeRejection::RelayShutDown);
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_persists_state() {
+        let path = temp_storage_dir("shutdown").with_extension("zst");
+        let mut relay = Relay::new(RelayConfig::default()).with_state_path(&path);
+        let (a, _ra) = relay.add_peer_with_info(stage19_peer("relay-a", true));
+        relay.update_routing("far".to_string(), a, 2);
+        relay.record_seen(42);
+        relay.shutdown();
+
+        let mut restored = Relay::new(RelayConfig::default());
+        restored.restore_state(&path).unwrap();
+        assert!(restored.already_seen(42));
+        let (rejoined, _rx) = restored.add_peer_with_info(stage19_peer("relay-a", true));
+        assert_eq!(restored.routing_table.get("far").map(|entry| entry.next_hop), Some(rejoined));
+
+        let _ = std::fs::remove_file(path);
+    }
 }
 
 // ===== Stage 47: Relay Diversity Controls =====
@@ -1028,6 +3901,23 @@ impl Default for RelayHealth {
     }
 }
 
+/// Score an unprobed relay's health drifts toward
+pub const NEUTRAL_HEALTH_SCORE: f64 = 0.5;
+
+impl RelayHealth {
+    /// `health_score` as of `now`: once `grace_ms` have passed since the last
+    /// check, the score closes its gap to `NEUTRAL_HEALTH_SCORE` by
+    /// `decay_per_hour` of the original gap per idle hour
+    pub fn decayed_score(&self, now: DateTime<Utc>, decay_per_hour: f64, grace_ms: u64) -> f64 {
+        let idle_ms = (now - self.last_health_check).num_milliseconds() - grace_ms as i64;
+        if idle_ms <= 0 || decay_per_hour <= 0.0 {
+            return self.health_score;
+        }
+        let remaining = (1.0 - decay_per_hour * idle_ms as f64 / 3_600_000.0).max(0.0);
+        NEUTRAL_HEALTH_SCORE + (self.health_score - NEUTRAL_HEALTH_SCORE) * remaining
+    }
+}
+
 /// Enhanced relay peer with diversity information
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiversityRelayPeer {
@@ -1042,6 +3932,10 @@ pub struct DiversityRelayPeer {
     pub priority: u8, // 0-255, higher is better
 }
 
+/// Health multiplier for relays in the sender's region under
+/// `select_routing_relays_for_region`
+pub const SAME_REGION_PREFERENCE: f64 = 1.5;
+
 /// Diversity policy configuration
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct DiversityPolicy {
@@ -1050,8 +3944,30 @@ pub struct DiversityPolicy {
     pub max_relays_per_asn: usize,
     pub max_relays_per_region: usize,
     pub health_threshold: f64,
+    /// Score below which a `HealthWarning` is emitted; sits above `health_threshold`
+    pub health_warning_threshold: f64,
     pub rotation_interval_ms: u64,
     pub failure_threshold: u32,
+    /// How long a relay deactivated for repeated failures stays ineligible
+    pub blacklist_duration_ms: u64,
+    /// Refuse to select routing relays while the active set is below the
+    /// minimum ASN/region diversity
+    pub strict_diversity: bool,
+    /// Fraction of the gap to `NEUTRAL_HEALTH_SCORE` an unprobed relay's
+    /// score loses per hour when ranking relays; 0 disables decay
+    #[serde(default = "default_health_decay_per_hour")]
+    pub health_decay_per_hour: f64,
+    /// Time after a health check before its score starts to decay
+    #[serde(default = "default_health_decay_grace_ms")]
+    pub health_decay_grace_ms: u64,
+}
+
+fn default_health_decay_per_hour() -> f64 {
+    0.1
+}
+
+fn default_health_decay_grace_ms() -> u64 {
+    60000 // 1 minute
 }
 
 impl Default for DiversityPolicy {
@@ -1062,12 +3978,27 @@ impl Default for DiversityPolicy {
             max_relays_per_asn: 2,
             max_relays_per_region: 5,
             health_threshold: 0.7,
+            health_warning_threshold: 0.8,
             rotation_interval_ms: 300000, // 5 minutes
             failure_threshold: 3,
+            blacklist_duration_ms: 600000, // 10 minutes
+            strict_diversity: false,
+            health_decay_per_hour: default_health_decay_per_hour(),
+            health_decay_grace_ms: default_health_decay_grace_ms(),
         }
     }
 }
 
+/// Relay lifecycle notifications from `RelayDiversityEngine`
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiversityEvent {
+    /// Health score dropped below the warning threshold but the relay is still active
+    HealthWarning { relay_id: String, health_score: f64 },
+    Deactivated { relay_id: String, health_score: f64 },
+    Blacklisted { relay_id: String, until: DateTime<Utc> },
+    Rotated { deactivated: Vec<String>, activated: Vec<String> },
+}
+
 /// Relay diversity policy engine
 #[derive(Debug)]
 pub struct RelayDiversityEngine {
@@ -1077,7 +4008,15 @@ pub struct RelayDiversityEngine {
     pub asn_distribution: BTreeMap<u32, Vec<String>>,
     pub region_distribution: BTreeMap<GeographicRegion, Vec<String>>,
     pub last_rotation: DateTime<Utc>,
+    /// Relays barred from activation and routing, with the time their entry expires
+    pub blacklist: HashMap<String, DateTime<Utc>>,
+    /// Relays returned by the last `select_routing_relays_rotating` call
+    pub last_routing_set: Vec<String>,
     pub metrics: &'static DiversityMetrics,
+    /// GeoIP/ASN database backing `add_candidate_from_addr`, if one was loaded
+    pub geoip_db_path: Option<PathBuf>,
+    asn_resolver: Box<dyn ResolveAsn>,
+    event_tx: Option<mpsc::Sender<DiversityEvent>>,
 }
 
 impl RelayDiversityEngine {
@@ -1089,10 +4028,56 @@ impl RelayDiversityEngine {
             asn_distribution: BTreeMap::new(),
             region_distribution: BTreeMap::new(),
             last_rotation: Utc::now(),
+            blacklist: HashMap::new(),
+            last_routing_set: Vec::new(),
             metrics: &DIVERSITY_METRICS,
+            geoip_db_path: None,
+            asn_resolver: Box::new(GeoIpDatabase::empty()),
+            event_tx: None,
+        }
+    }
+
+    /// Send lifecycle events to `tx`. Events are dropped if the channel is full.
+    pub fn set_event_sender(&mut self, tx: mpsc::Sender<DiversityEvent>) {
+        self.event_tx = Some(tx);
+    }
+
+    fn emit(&self, event: DiversityEvent) {
+        if let Some(tx) = &self.event_tx {
+            let _ = tx.try_send(event);
         }
     }
 
+    /// Load a GeoLite2-style CSV database used to resolve candidate addresses
+    pub fn load_geoip_database<P: AsRef<Path>>(&mut self, path: P) -> anyhow::Result<()> {
+        let database = GeoIpDatabase::load(path.as_ref())?;
+        self.asn_resolver = Box::new(database);
+        self.geoip_db_path = Some(path.as_ref().to_path_buf());
+        Ok(())
+    }
+
+    /// Replace the ASN resolver (e.g. with a live lookup service)
+    pub fn set_asn_resolver(&mut self, resolver: Box<dyn ResolveAsn>) {
+        self.asn_resolver = resolver;
+        self.geoip_db_path = None;
+    }
+
+    /// Add a candidate relay, resolving its ASN and region from its address
+    pub fn add_candidate_from_addr(&mut self, id: String, address: SocketAddr) {
+        let (asn_info, region) = self.asn_resolver.resolve(&address);
+        self.add_candidate_relay(DiversityRelayPeer {
+            id,
+            address,
+            asn_info,
+            region,
+            health: RelayHealth::default(),
+            is_active: false,
+            last_seen: Utc::now(),
+            message_count: 0,
+            priority: 100,
+        });
+    }
+
     /// Add a relay peer to the candidate pool
     pub fn add_candidate_relay(&mut self, relay: DiversityRelayPeer) {
         let id = relay.id.clone();
@@ -1101,8 +4086,16 @@ impl RelayDiversityEngine {
         self.metrics.candidate_relays.set(self.candidate_relays.len() as f64);
     }
 
+    /// Whether `relay_id` is blacklisted and its entry has not yet expired
+    pub fn is_blacklisted(&self, relay_id: &str) -> bool {
+        self.blacklist.get(relay_id).is_some_and(|until| *until > Utc::now())
+    }
+
     /// Activate relays based on diversity policy
     pub fn activate_relays(&mut self) -> Vec<String> {
+        let now = Utc::now();
+        self.blacklist.retain(|_, until| *until > now);
+
         let mut activated = Vec::new();
         let mut asn_counts: HashMap<u32, usize> = HashMap::new();
         let mut region_counts: HashMap<GeographicRegion, usize> = HashMap::new();
@@ -1113,9 +4106,14 @@ impl RelayDiversityEngine {
             b.health.health_score.partial_cmp(&a.health.health_score)
                 .unwrap_or(std::cmp::Ordering::Equal)
                 .then_with(|| b.priority.cmp(&a.priority))
+                .then_with(|| a.id.cmp(&b.id))
         });
 
         for candidate in candidates {
+            if self.blacklist.contains_key(&candidate.id) {
+                continue;
+            }
+
             let asn_count = asn_counts.get(&candidate.asn_info.asn).unwrap_or(&0);
             let region_count = region_counts.get(&candidate.region).unwrap_or(&0);
 
@@ -1147,7 +4145,9 @@ impl RelayDiversityEngine {
 
     /// Update health metrics for a relay
     pub fn update_relay_health(&mut self, relay_id: &str, latency_ms: f64, success: bool) {
+        let mut warning = None;
         let should_deactivate = if let Some(relay) = self.active_relays.get_mut(relay_id) {
+            let previous_score = relay.health.health_score;
             relay.health.latency_ms = latency_ms;
             relay.health.last_health_check = Utc::now();
             
@@ -1168,20 +4168,43 @@ impl RelayDiversityEngine {
                 1.0 / (relay.health.consecutive_failures as f64 + 1.0) 
             };
             relay.health.health_score = (uptime_score * 0.3 + latency_score * 0.3 + error_score * 0.2 + failure_score * 0.2).max(0.0).min(1.0);
-            
+
             // Check if should deactivate
-            relay.health.health_score < self.policy.health_threshold 
-                || relay.health.consecutive_failures >= self.policy.failure_threshold
+            let should_deactivate = relay.health.health_score < self.policy.health_threshold
+                || relay.health.consecutive_failures >= self.policy.failure_threshold;
+
+            // Warn once, on the update that crosses the warning threshold
+            if !should_deactivate
+                && previous_score >= self.policy.health_warning_threshold
+                && relay.health.health_score < self.policy.health_warning_threshold {
+                warning = Some(relay.health.health_score);
+            }
+            should_deactivate
         } else {
             false
         };
 
+        if let Some(health_score) = warning {
+            self.emit(DiversityEvent::HealthWarning { relay_id: relay_id.to_string(), health_score });
+        }
+
         // Deactivate if needed (after releasing the mutable borrow)
         if should_deactivate {
             self.deactivate_relay(relay_id);
         }
     }
 
+    /// Health score of an active relay with idle-time decay applied, as used
+    /// for routing order
+    pub fn effective_health_score(&self, relay_id: &str) -> Option<f64> {
+        let now = Utc::now();
+        self.active_relays.get(relay_id).map(|relay| self.decayed_health(relay, now))
+    }
+
+    fn decayed_health(&self, relay: &DiversityRelayPeer, now: DateTime<Utc>) -> f64 {
+        relay.health.decayed_score(now, self.policy.health_decay_per_hour, self.policy.health_decay_grace_ms)
+    }
+
     /// Calculate composite health score
     fn calculate_health_score(&self, health: &RelayHealth) -> f64 {
         let uptime_score = health.uptime_percentage / 100.0;
@@ -1194,10 +4217,17 @@ impl RelayDiversityEngine {
         (uptime_score * 0.3 + latency_score * 0.3 + error_score * 0.2 + failure_score * 0.2).max(0.0).min(1.0)
     }
 
-    /// Deactivate a relay
+    /// Deactivate a relay. One that has reached the failure threshold is also
+    /// blacklisted for `blacklist_duration_ms` so it cannot flap straight back.
     pub fn deactivate_relay(&mut self, relay_id: &str) -> bool {
         if let Some(mut relay) = self.active_relays.remove(relay_id) {
             relay.is_active = false;
+            self.emit(DiversityEvent::Deactivated { relay_id: relay_id.to_string(), health_score: relay.health.health_score });
+            if relay.health.consecutive_failures >= self.policy.failure_threshold {
+                let until = Utc::now() + chrono::Duration::milliseconds(self.policy.blacklist_duration_ms as i64);
+                self.blacklist.insert(relay_id.to_string(), until);
+                self.emit(DiversityEvent::Blacklisted { relay_id: relay_id.to_string(), until });
+            }
             self.candidate_relays.insert(relay_id.to_string(), relay);
             self.update_distributions();
             self.metrics.active_relays.set(self.active_relays.len() as f64);
@@ -1239,7 +4269,8 @@ impl RelayDiversityEngine {
 
         self.last_rotation = Utc::now();
         self.metrics.relay_rotations.inc();
-        
+        self.emit(DiversityEvent::Rotated { deactivated: deactivated.clone(), activated: activated.clone() });
+
         (deactivated, activated)
     }
 
@@ -1276,16 +4307,110 @@ impl RelayDiversityEngine {
         }
     }
 
-    /// Select best relays for routing based on health and diversity
+    /// Whether routing may proceed: always in permissive mode, and in strict
+    /// mode only once the active set meets the minimum diversity
+    fn routing_allowed(&self) -> bool {
+        if !self.policy.strict_diversity || self.get_diversity_stats().meets_diversity_policy {
+            return true;
+        }
+        warn!("Routing blocked: active relays span {} ASNs / {} regions, policy requires {} / {}",
+              self.asn_distribution.len(), self.region_distribution.len(),
+              self.policy.min_asn_diversity, self.policy.min_region_diversity);
+        self.metrics.diversity_violations.inc();
+        false
+    }
+
+    /// Select best relays for routing based on health and diversity. Returns
+    /// nothing under `strict_diversity` until enough diverse relays are active.
     pub fn select_routing_relays(&self, count: usize) -> Vec<String> {
+        if !self.routing_allowed() {
+            return Vec::new();
+        }
+
+        // Sort active relays by decayed health score
+        let now = Utc::now();
+        let mut relays: Vec<_> = self.active_relays.values()
+            .filter(|relay| !self.is_blacklisted(&relay.id))
+            .map(|relay| (self.decayed_health(relay, now), relay))
+            .collect();
+        relays.sort_by(|(a_score, a), (b_score, b)| {
+            b_score.partial_cmp(a_score)
+                .unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        self.take_within_caps(relays.into_iter().map(|(_, relay)| relay), count)
+    }
+
+    /// Like `select_routing_relays`, but relays not returned by the previous
+    /// call go first, so successive messages take shifted paths instead of
+    /// always the same top relays. Health order holds within each group.
+    pub fn select_routing_relays_rotating(&mut self, count: usize) -> Vec<String> {
+        if !self.routing_allowed() {
+            return Vec::new();
+        }
+
+        let now = Utc::now();
+        let mut relays: Vec<_> = self.active_relays.values()
+            .filter(|relay| !self.is_blacklisted(&relay.id))
+            .map(|relay| (self.decayed_health(relay, now), relay))
+            .collect();
+        relays.sort_by(|(a_score, a), (b_score, b)| {
+            let a_used = self.last_routing_set.contains(&a.id);
+            let b_used = self.last_routing_set.contains(&b.id);
+            a_used.cmp(&b_used)
+                .then_with(|| b_score.partial_cmp(a_score).unwrap_or(std::cmp::Ordering::Equal))
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        let selected = self.take_within_caps(relays.into_iter().map(|(_, relay)| relay), count);
+        self.last_routing_set = selected.clone();
+        selected
+    }
+
+    /// Like `select_routing_relays`, but relays in `sender_region` have their
+    /// health scaled by `SAME_REGION_PREFERENCE`, as nearby relays usually mean
+    /// a shorter round trip. The best relay of each region is taken first until
+    /// `min_region_diversity` regions are covered, so the preference never
+    /// collapses the route onto the sender's region.
+    pub fn select_routing_relays_for_region(&self, sender_region: &GeographicRegion, count: usize) -> Vec<String> {
+        if !self.routing_allowed() {
+            return Vec::new();
+        }
+
+        let now = Utc::now();
+        let weight = |relay: &DiversityRelayPeer| {
+            let preference = if &relay.region == sender_region { SAME_REGION_PREFERENCE } else { 1.0 };
+            self.decayed_health(relay, now) * preference
+        };
+        let mut relays: Vec<_> = self.active_relays.values()
+            .filter(|relay| !self.is_blacklisted(&relay.id))
+            .collect();
+        relays.sort_by(|a, b| {
+            weight(b).partial_cmp(&weight(a)).unwrap_or(std::cmp::Ordering::Equal)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        let mut seeds: Vec<&DiversityRelayPeer> = Vec::new();
+        for relay in &relays {
+            if seeds.len() >= self.policy.min_region_diversity.min(count) {
+                break;
+            }
+            if seeds.iter().all(|seed| seed.region != relay.region) {
+                seeds.push(relay);
+            }
+        }
+        let rest = relays.iter().copied().filter(|relay| seeds.iter().all(|seed| seed.id != relay.id));
+        self.take_within_caps(seeds.iter().copied().chain(rest), count)
+    }
+
+    /// Take relays in order, up to `count`, skipping any that would exceed the
+    /// per-ASN or per-region cap
+    fn take_within_caps<'a>(&self, relays: impl IntoIterator<Item = &'a DiversityRelayPeer>, count: usize) -> Vec<String> {
         let mut selected = Vec::new();
         let mut asn_used: HashMap<u32, usize> = HashMap::new();
         let mut region_used: HashMap<GeographicRegion, usize> = HashMap::new();
 
-        // Sort active relays by health score
-        let mut relays: Vec<_> = self.active_relays.values().collect();
-        relays.sort_by(|a, b| b.health.health_score.partial_cmp(&a.health.health_score).unwrap_or(std::cmp::Ordering::Equal));
-
         for relay in relays {
             if selected.len() >= count {
                 break;
@@ -1304,6 +4429,109 @@ impl RelayDiversityEngine {
 
         selected
     }
+
+    /// Weighted random relay selection. Relays are sampled without replacement with
+    /// weight `health_score * priority`, so healthier relays are favoured without
+    /// concentrating all traffic on the top few. Per-ASN and per-region caps still apply.
+    pub fn select_routing_relays_weighted(&self, count: usize, seed: u64) -> Vec<String> {
+        if !self.routing_allowed() {
+            return Vec::new();
+        }
+
+        let mut rng = rand_chacha::ChaCha8Rng::seed_from_u64(seed);
+
+        // Draw in id order: `active_relays` iterates in a per-process random
+        // order, which would make the same seed pick differently on each node
+        let mut relays: Vec<&DiversityRelayPeer> = self.active_relays.values().collect();
+        relays.sort_by(|a, b| a.id.cmp(&b.id));
+
+        // Efraimidis-Spirakis: key = u^(1/w); taking keys in descending order is a
+        // weighted sample without replacement
+        let now = Utc::now();
+        let mut keyed: Vec<(f64, &DiversityRelayPeer)> = relays
+            .into_iter()
+            .filter(|relay| !self.is_blacklisted(&relay.id))
+            .filter_map(|relay| {
+                let weight = self.decayed_health(relay, now).max(0.0) * relay.priority as f64;
+                if weight <= 0.0 {
+                    return None;
+                }
+                let u: f64 = rng.gen_range(f64::MIN_POSITIVE..1.0);
+                Some((u.powf(1.0 / weight), relay))
+            })
+            .collect();
+        keyed.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(std::cmp::Ordering::Equal));
+
+        self.take_within_caps(keyed.into_iter().map(|(_, relay)| relay), count)
+    }
+}
+
+/// Handle to a running relay health monitor
+#[derive(Debug)]
+pub struct HealthMonitorHandle {
+    shutdown_tx: tokio::sync::watch::Sender<bool>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl HealthMonitorHandle {
+    /// Stop the monitor and wait for the current cycle to finish
+    pub async fn shutdown(self) {
+        let _ = self.shutdown_tx.send(true);
+        let _ = self.task.await;
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.task.is_finished()
+    }
+}
+
+impl RelayDiversityEngine {
+    /// Probe every active relay each `rotation_interval_ms`, feed the measured
+    /// latency/success into `update_relay_health`, and rotate when due.
+    /// `probe` returns `(latency_ms, success)` for a relay address.
+    pub fn spawn_health_monitor<F, Fut>(engine: Arc<Mutex<Self>>, probe: F) -> HealthMonitorHandle
+    where
+        F: Fn(&SocketAddr) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = (f64, bool)> + Send + 'static,
+    {
+        let (shutdown_tx, mut shutdown_rx) = tokio::sync::watch::channel(false);
+
+        let task = tokio::spawn(async move {
+            let interval_ms = engine.lock().await.policy.rotation_interval_ms.max(1);
+            let mut ticker = tokio::time::interval(std::time::Duration::from_millis(interval_ms));
+            ticker.tick().await; // first tick completes immediately
+
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = shutdown_rx.changed() => break,
+                }
+
+                // Snapshot targets so probes run without holding the engine lock
+                let targets: Vec<(String, SocketAddr)> = engine.lock().await
+                    .active_relays
+                    .values()
+                    .map(|relay| (relay.id.clone(), relay.address))
+                    .collect();
+
+                let mut results = Vec::with_capacity(targets.len());
+                for (id, address) in targets {
+                    let (latency_ms, success) = probe(&address).await;
+                    results.push((id, latency_ms, success));
+                }
+
+                let mut engine = engine.lock().await;
+                for (id, latency_ms, success) in results {
+                    engine.update_relay_health(&id, latency_ms, success);
+                }
+                if engine.should_rotate() {
+                    engine.rotate_relays();
+                }
+            }
+        });
+
+        HealthMonitorHandle { shutdown_tx, task }
+    }
 }
 
 /// Diversity statistics
@@ -1585,6 +4813,486 @@ mod diversity_tests {
         println!("✅ Maintain throughput under relay loss working");
     }
 
+    fn diverse_relay(id: &str, asn: u32, region: GeographicRegion) -> DiversityRelayPeer {
+        DiversityRelayPeer {
+            id: id.to_string(),
+            address: "127.0.0.1:9000".parse().unwrap(),
+            asn_info: AsnInfo { asn, name: format!("ASN{}", asn), country: "US".to_string(), region: "Global".to_string() },
+            region,
+            health: RelayHealth::default(),
+            is_active: false,
+            last_seen: Utc::now(),
+            message_count: 0,
+            priority: 100,
+        }
+    }
+
+    fn relay_peer_info(id: &str) -> PeerInfo {
+        PeerInfo {
+            id: id.to_string(),
+            address: "127.0.0.1:9000".parse().unwrap(),
+            last_seen: std::time::Instant::now(),
+            message_count: 0,
+            is_relay: true,
+            connection_quality: 0.9,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_diverse_respects_asn_and_region_caps() {
+        let policy = DiversityPolicy {
+            min_asn_diversity: 2,
+            max_relays_per_asn: 1,
+            max_relays_per_region: 1,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        engine.add_candidate_relay(diverse_relay("relay-a", 100, GeographicRegion::NorthAmerica));
+        engine.add_candidate_relay(diverse_relay("relay-b", 100, GeographicRegion::Europe));
+        engine.add_candidate_relay(diverse_relay("relay-c", 200, GeographicRegion::Europe));
+        engine.add_candidate_relay(diverse_relay("relay-d", 300, GeographicRegion::Asia));
+        engine.activate_relays();
+
+        let mut relay = Relay::new(RelayConfig::default());
+        let (source, _rs) = relay.add_peer();
+        let mut receivers = Vec::new();
+        for id in ["relay-a", "relay-b", "relay-c", "relay-d"] {
+            let (_, rx) = relay.add_peer_with_info(relay_peer_info(id));
+            receivers.push((id, rx));
+        }
+        let allowed = engine.select_routing_relays(4);
+        relay.set_diversity_engine(engine);
+
+        relay.broadcast_diverse(source, Message::new(77, b"diverse".to_vec()));
+
+        let mut asns = std::collections::HashSet::new();
+        let mut regions = std::collections::HashSet::new();
+        let mut delivered = 0;
+        for (id, rx) in receivers.iter_mut() {
+            if rx.try_recv().is_ok() {
+                delivered += 1;
+                assert!(allowed.contains(&id.to_string()));
+                let r = &relay.diversity_engine().unwrap().active_relays[*id];
+                assert!(asns.insert(r.asn_info.asn), "ASN cap violated");
+                assert!(regions.insert(r.region.clone()), "region cap violated");
+            }
+        }
+        assert_eq!(delivered, allowed.len());
+        assert!(delivered < 4);
+    }
+
+    #[tokio::test]
+    async fn test_broadcast_diverse_falls_back_to_full_fanout() {
+        let policy = DiversityPolicy {
+            min_asn_diversity: 5,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        engine.add_candidate_relay(diverse_relay("relay-a", 100, GeographicRegion::NorthAmerica));
+        engine.activate_relays();
+
+        let mut relay = Relay::new(RelayConfig::default());
+        let (source, _rs) = relay.add_peer();
+        let (_, mut ra) = relay.add_peer_with_info(relay_peer_info("relay-a"));
+        let (_, mut rc) = relay.add_peer();
+        relay.set_diversity_engine(engine);
+
+        relay.broadcast_diverse(source, Message::new(78, b"fallback".to_vec()));
+        assert!(ra.try_recv().is_ok());
+        assert!(rc.try_recv().is_ok());
+    }
+
+    const GEOIP_FIXTURE: &str = "\
+network,autonomous_system_number,autonomous_system_organization,country_iso_code,continent_code
+203.0.113.0/24,64500,Example Transit,DE,EU
+203.0.113.128/25,64501,Example Edge,FR,EU
+198.51.100.0/24,64510,Example Cloud,US,NA
+100.64.0.0/24,64520,\"Example, Inc. \"\"Edge\"\"\",JP,AS
+";
+
+    #[tokio::test]
+    async fn test_add_candidate_from_addr_known_ip() {
+        let path = std::env::temp_dir().join(format!("bpi-relay-geoip-{}.csv", rand::random::<u64>()));
+        std::fs::write(&path, GEOIP_FIXTURE).unwrap();
+
+        let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+        engine.load_geoip_database(&path).unwrap();
+        assert_eq!(engine.geoip_db_path.as_deref(), Some(path.as_path()));
+
+        engine.add_candidate_from_addr("relay-us".to_string(), "198.51.100.7:7000".parse().unwrap());
+        // Longest prefix wins over the enclosing /24
+        engine.add_candidate_from_addr("relay-fr".to_string(), "203.0.113.200:7000".parse().unwrap());
+        // Quoted organization names keep their commas and quotes
+        engine.add_candidate_from_addr("relay-jp".to_string(), "100.64.0.9:7000".parse().unwrap());
+        let jp = &engine.candidate_relays["relay-jp"];
+        assert_eq!(jp.asn_info.asn, 64520);
+        assert_eq!(jp.asn_info.name, "Example, Inc. \"Edge\"");
+        assert_eq!(jp.asn_info.country, "JP");
+        assert_eq!(jp.region, GeographicRegion::Asia);
+
+        let us = &engine.candidate_relays["relay-us"];
+        assert_eq!(us.asn_info.asn, 64510);
+        assert_eq!(us.asn_info.country, "US");
+        assert_eq!(us.region, GeographicRegion::NorthAmerica);
+
+        let fr = &engine.candidate_relays["relay-fr"];
+        assert_eq!(fr.asn_info.asn, 64501);
+        assert_eq!(fr.region, GeographicRegion::Europe);
+
+        let _ = std::fs::remove_file(path);
+    }
+
+    #[tokio::test]
+    async fn test_add_candidate_from_addr_unknown_ip() {
+        let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+        engine.set_asn_resolver(Box::new(geoip::GeoIpDatabase::parse(GEOIP_FIXTURE).unwrap()));
+
+        engine.add_candidate_from_addr("relay-x".to_string(), "192.0.2.10:7000".parse().unwrap());
+        engine.add_candidate_from_addr("relay-y".to_string(), "192.0.2.99:7000".parse().unwrap());
+
+        let x = &engine.candidate_relays["relay-x"];
+        assert_eq!(x.region, GeographicRegion::Unknown);
+        assert!(x.asn_info.asn >= 4_200_000_000);
+        // Same /24 resolves to the same synthetic ASN
+        assert_eq!(x.asn_info.asn, engine.candidate_relays["relay-y"].asn_info.asn);
+    }
+
+    #[test]
+    fn test_geoip_rejects_unterminated_quote() {
+        let err = geoip::GeoIpDatabase::parse("203.0.113.0/24,64500,\"Example, Inc.,DE,EU\n").unwrap_err();
+        assert!(err.to_string().contains("line 1"));
+    }
+
+    #[tokio::test]
+    async fn test_health_monitor_deactivates_unhealthy_relay() {
+        let policy = DiversityPolicy {
+            rotation_interval_ms: 20,
+            failure_threshold: 1,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        let mut bad = diverse_relay("relay-bad", 100, GeographicRegion::NorthAmerica);
+        bad.address = "127.0.0.1:9001".parse().unwrap();
+        let mut good = diverse_relay("relay-good", 200, GeographicRegion::Europe);
+        good.address = "127.0.0.1:9002".parse().unwrap();
+        engine.add_candidate_relay(bad);
+        engine.add_candidate_relay(good);
+        engine.activate_relays();
+        assert_eq!(engine.active_relays.len(), 2);
+
+        let engine = Arc::new(Mutex::new(engine));
+        let bad_addr: SocketAddr = "127.0.0.1:9001".parse().unwrap();
+        let handle = RelayDiversityEngine::spawn_health_monitor(engine.clone(), move |addr| {
+            let healthy = *addr != bad_addr;
+            async move { if healthy { (10.0, true) } else { (1000.0, false) } }
+        });
+
+        sleep(Duration::from_millis(100)).await;
+        handle.shutdown().await;
+
+        let engine = engine.lock().await;
+        assert!(!engine.active_relays.contains_key("relay-bad"));
+        assert!(engine.active_relays.contains_key("relay-good"));
+        assert!(engine.candidate_relays.contains_key("relay-bad"));
+    }
+
+    #[tokio::test]
+    async fn test_weighted_selection_favours_healthy_relays() {
+        let policy = DiversityPolicy {
+            health_threshold: 0.0,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        for (i, score) in [0.95, 0.2, 0.2, 0.2].iter().enumerate() {
+            let mut relay = diverse_relay(&format!("relay-{}", i), 100 + i as u32, GeographicRegion::NorthAmerica);
+            relay.health.health_score = *score;
+            engine.add_candidate_relay(relay);
+        }
+        engine.activate_relays();
+        assert_eq!(engine.active_relays.len(), 4);
+
+        let mut picks: HashMap<String, usize> = HashMap::new();
+        for seed in 0..2000u64 {
+            let selected = engine.select_routing_relays_weighted(1, seed);
+            assert_eq!(selected.len(), 1);
+            *picks.entry(selected[0].clone()).or_insert(0) += 1;
+        }
+
+        let healthy = picks.get("relay-0").copied().unwrap_or(0);
+        let weak = picks.get("relay-1").copied().unwrap_or(0);
+        assert!(healthy > weak * 2, "healthy={} weak={}", healthy, weak);
+        // Low-health relays still receive some traffic
+        assert!(weak > 0);
+        assert!(healthy < 2000);
+
+        // Same seed gives the same selection
+        assert_eq!(engine.select_routing_relays_weighted(3, 42), engine.select_routing_relays_weighted(3, 42));
+
+        // Including on another engine that learned the relays in another order
+        let policy = DiversityPolicy {
+            health_threshold: 0.0,
+            ..DiversityPolicy::default()
+        };
+        let mut other = RelayDiversityEngine::new(policy);
+        for (i, score) in [0.95, 0.2, 0.2, 0.2].iter().enumerate().rev() {
+            let mut relay = diverse_relay(&format!("relay-{}", i), 100 + i as u32, GeographicRegion::NorthAmerica);
+            relay.health.health_score = *score;
+            other.add_candidate_relay(relay);
+        }
+        other.activate_relays();
+        for seed in 0..50u64 {
+            assert_eq!(engine.select_routing_relays_weighted(2, seed), other.select_routing_relays_weighted(2, seed));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_weighted_selection_respects_caps() {
+        let policy = DiversityPolicy {
+            max_relays_per_asn: 1,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        engine.add_candidate_relay(diverse_relay("relay-a", 100, GeographicRegion::NorthAmerica));
+        engine.add_candidate_relay(diverse_relay("relay-b", 200, GeographicRegion::Europe));
+        engine.activate_relays();
+        // Same ASN as relay-a; inserted directly to bypass activation caps
+        let mut dup = diverse_relay("relay-c", 100, GeographicRegion::Asia);
+        dup.is_active = true;
+        engine.active_relays.insert(dup.id.clone(), dup);
+
+        for seed in 0..200u64 {
+            let selected = engine.select_routing_relays_weighted(3, seed);
+            assert_eq!(selected.len(), 2);
+            assert!(!(selected.contains(&"relay-a".to_string()) && selected.contains(&"relay-c".to_string())));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_failing_relay_blacklisted_until_expiry() {
+        let policy = DiversityPolicy {
+            health_threshold: 0.5,
+            blacklist_duration_ms: 100,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        engine.add_candidate_relay(diverse_relay("relay-a", 100, GeographicRegion::NorthAmerica));
+        engine.add_candidate_relay(diverse_relay("relay-b", 200, GeographicRegion::Europe));
+        engine.activate_relays();
+
+        for _ in 0..3 {
+            engine.update_relay_health("relay-a", 10.0, false);
+        }
+        assert!(!engine.active_relays.contains_key("relay-a"));
+        assert!(engine.is_blacklisted("relay-a"));
+
+        // Healthy enough to pass the threshold, but barred during the TTL
+        assert!(engine.candidate_relays["relay-a"].health.health_score >= 0.5);
+        assert!(engine.activate_relays().iter().all(|id| id != "relay-a"));
+        assert!(!engine.active_relays.contains_key("relay-a"));
+        assert_eq!(engine.select_routing_relays(2), vec!["relay-b".to_string()]);
+
+        sleep(Duration::from_millis(150)).await;
+        assert!(!engine.is_blacklisted("relay-a"));
+        assert!(engine.activate_relays().contains(&"relay-a".to_string()));
+        assert!(engine.blacklist.is_empty());
+        assert!(engine.select_routing_relays(2).contains(&"relay-a".to_string()));
+    }
+
+    fn under_diverse_engine(strict_diversity: bool) -> RelayDiversityEngine {
+        let policy = DiversityPolicy {
+            strict_diversity,
+            ..DiversityPolicy::default()
+        };
+        let mut engine = RelayDiversityEngine::new(policy);
+        // Two ASNs in one region: short of the 3 ASN / 2 region minimum
+        engine.add_candidate_relay(diverse_relay("relay-a", 100, GeographicRegion::NorthAmerica));
+        engine.add_candidate_relay(diverse_relay("relay-b", 200, GeographicRegion::NorthAmerica));
+        engine.activate_relays();
+        assert!(!engine.get_diversity_stats().meets_diversity_policy);
+        engine
+    }
+
+    #[tokio::test]
+    async fn test_strict_diversity_blocks_under_diverse_routing() {
+        let mut engine = under_diverse_engine(true);
+        assert!(engine.select_routing_relays(2).is_empty());
+        assert!(engine.select_routing_relays_weighted(2, 7).is_empty());
+
+        // Activating enough diverse candidates unblocks routing
+        engine.add_candidate_relay(diverse_relay("relay-c", 300, GeographicRegion::Europe));
+        engine.activate_relays();
+        assert!(engine.get_diversity_stats().meets_diversity_policy);
+        assert_eq!(engine.select_routing_relays(3).len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_permissive_diversity_allows_under_diverse_routing() {
+        let engine = under_diverse_engine(false);
+        assert_eq!(engine.select_routing_relays(2).len(), 2);
+        assert_eq!(engine.select_routing_relays_weighted(2, 7).len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_region_aware_selection_prefers_sender_region() {
+        let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+        // Nearby relays are slightly less healthy than the distant ones
+        let relays = [
+            ("eu-0", GeographicRegion::Europe, 0.85),
+            ("eu-1", GeographicRegion::Europe, 0.84),
+            ("eu-2", GeographicRegion::Europe, 0.83),
+            ("na-0", GeographicRegion::NorthAmerica, 0.99),
+            ("na-1", GeographicRegion::NorthAmerica, 0.98),
+            ("as-0", GeographicRegion::Asia, 0.97),
+        ];
+        for (i, (id, region, score)) in relays.iter().enumerate() {
+            let mut relay = diverse_relay(id, 100 + i as u32, region.clone());
+            relay.health.health_score = *score;
+            engine.add_candidate_relay(relay);
+        }
+        engine.activate_relays();
+        assert_eq!(engine.active_relays.len(), 6);
+
+        // Health alone picks no European relay at all
+        let by_health = engine.select_routing_relays(3);
+        assert!(by_health.iter().all(|id| !id.starts_with("eu")));
+
+        let selected = engine.select_routing_relays_for_region(&GeographicRegion::Europe, 4);
+        assert_eq!(selected.len(), 4);
+        let european = selected.iter().filter(|id| id.starts_with("eu")).count();
+        assert_eq!(european, 3);
+        assert_eq!(selected[0], "eu-0");
+
+        // Still spans the minimum number of regions
+        let regions: std::collections::HashSet<_> = selected.iter()
+            .map(|id| engine.active_relays[id].region.clone())
+            .collect();
+        assert!(regions.len() >= engine.policy.min_region_diversity);
+
+        // Even when the sender's region could fill every slot
+        let pair = engine.select_routing_relays_for_region(&GeographicRegion::Europe, 2);
+        assert_eq!(pair, vec!["eu-0", "na-0"]);
+    }
+
+    #[tokio::test]
+    async fn test_equal_health_relays_order_by_id() {
+        let ids = ["relay-d", "relay-a", "relay-e", "relay-c", "relay-b"];
+        let regions = [
+            GeographicRegion::NorthAmerica,
+            GeographicRegion::Europe,
+            GeographicRegion::Asia,
+            GeographicRegion::SouthAmerica,
+            GeographicRegion::Oceania,
+        ];
+        let build = |order: &[usize]| {
+            let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+            for &i in order {
+                engine.add_candidate_relay(diverse_relay(ids[i], 100 + i as u32, regions[i].clone()));
+            }
+            engine
+        };
+
+        let mut forward = build(&[0, 1, 2, 3, 4]);
+        let mut reverse = build(&[4, 3, 2, 1, 0]);
+        let activated = forward.activate_relays();
+        assert_eq!(activated, vec!["relay-a", "relay-b", "relay-c", "relay-d", "relay-e"]);
+        assert_eq!(reverse.activate_relays(), activated);
+
+        for _ in 0..5 {
+            let selected = forward.select_routing_relays(3);
+            assert_eq!(selected, vec!["relay-a", "relay-b", "relay-c"]);
+            assert_eq!(reverse.select_routing_relays(3), selected);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_idle_relay_health_decays_below_probed_peer() {
+        let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+        let mut idle = diverse_relay("relay-idle", 100, GeographicRegion::NorthAmerica);
+        idle.health.last_health_check = Utc::now() - chrono::Duration::hours(5);
+        engine.add_candidate_relay(idle);
+        engine.add_candidate_relay(diverse_relay("relay-probed", 200, GeographicRegion::Europe));
+        engine.activate_relays();
+
+        // A real probe lands below the idle relay's stale perfect score
+        engine.update_relay_health("relay-probed", 50.0, true);
+        let probed = engine.effective_health_score("relay-probed").unwrap();
+        assert!(probed < 1.0);
+        assert_eq!(engine.active_relays["relay-idle"].health.health_score, 1.0);
+
+        // Five idle hours at 0.1/hour halve the gap to neutral
+        let idle_score = engine.effective_health_score("relay-idle").unwrap();
+        assert!((idle_score - 0.75).abs() < 0.01, "idle score {}", idle_score);
+        assert!(idle_score < probed);
+        assert_eq!(engine.select_routing_relays(2), vec!["relay-probed", "relay-idle"]);
+
+        // With decay disabled the stale score still wins
+        let mut stale = RelayDiversityEngine::new(DiversityPolicy { health_decay_per_hour: 0.0, ..DiversityPolicy::default() });
+        for relay in engine.active_relays.values() {
+            stale.add_candidate_relay(relay.clone());
+        }
+        stale.activate_relays();
+        assert_eq!(stale.select_routing_relays(2), vec!["relay-idle", "relay-probed"]);
+    }
+
+    #[tokio::test]
+    async fn test_rotating_selection_shifts_path() {
+        let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+        let regions = [
+            GeographicRegion::NorthAmerica,
+            GeographicRegion::Europe,
+            GeographicRegion::Asia,
+            GeographicRegion::SouthAmerica,
+            GeographicRegion::Oceania,
+        ];
+        for (i, region) in regions.iter().enumerate() {
+            let mut relay = diverse_relay(&format!("relay-{}", i), 100 + i as u32, region.clone());
+            relay.health.health_score = 1.0 - i as f64 * 0.05;
+            engine.add_candidate_relay(relay);
+        }
+        engine.activate_relays();
+
+        // Plain selection never moves
+        assert_eq!(engine.select_routing_relays(3), engine.select_routing_relays(3));
+
+        let first = engine.select_routing_relays_rotating(3);
+        assert_eq!(first, vec!["relay-0", "relay-1", "relay-2"]);
+        let second = engine.select_routing_relays_rotating(3);
+        assert_eq!(second, vec!["relay-3", "relay-4", "relay-0"]);
+        let third = engine.select_routing_relays_rotating(3);
+        assert_eq!(third, vec!["relay-1", "relay-2", "relay-0"]);
+
+        for (previous, next) in [(&first, &second), (&second, &third)] {
+            assert_ne!(previous, next);
+            assert!(next.iter().any(|id| previous.contains(id)));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_health_warning_emitted_before_deactivation() {
+        let mut engine = RelayDiversityEngine::new(DiversityPolicy::default());
+        let (tx, mut rx) = mpsc::channel(16);
+        engine.set_event_sender(tx);
+        engine.add_candidate_relay(diverse_relay("relay-a", 100, GeographicRegion::NorthAmerica));
+        engine.activate_relays();
+
+        // Latency 140ms scores 0.79: under the 0.8 warning, above the 0.7 cutoff
+        engine.update_relay_health("relay-a", 140.0, true);
+
+        match rx.try_recv() {
+            Ok(DiversityEvent::HealthWarning { relay_id, health_score }) => {
+                assert_eq!(relay_id, "relay-a");
+                assert!((0.7..0.8).contains(&health_score));
+            }
+            other => panic!("expected HealthWarning, got {:?}", other),
+        }
+        assert!(rx.try_recv().is_err());
+        assert!(engine.active_relays.contains_key("relay-a"));
+
+        // Staying below the warning threshold does not warn again
+        engine.update_relay_health("relay-a", 140.0, true);
+        assert!(rx.try_recv().is_err());
+    }
+
     #[tokio::test]
     async fn test_stage47_exit_criteria() {
         println!("\n=== Stage 47: Relay Diversity Controls Exit Criteria ===");
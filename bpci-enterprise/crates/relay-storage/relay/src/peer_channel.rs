@@ -0,0 +1,145 @@
+//! Relay-to-peer message queues
+//!
+//! Unbounded unless `RelayConfig.peer_channel_bound` is set. A bounded queue
+//! that is full sheds a message according to its `BackpressurePolicy`, so a
+//! slow peer never blocks the broadcast loop or grows the relay's memory.
+
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::mpsc::error::TryRecvError;
+use tokio::sync::Notify;
+
+use crate::WireMessage;
+
+/// What a full peer queue gives up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BackpressurePolicy {
+    /// Keep what is queued and drop the incoming message
+    #[default]
+    DropNew,
+    /// Evict the oldest queued message to make room
+    DropOldest,
+}
+
+/// Result of queueing a message for a peer
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Queued {
+    /// Queued with room to spare
+    Accepted,
+    /// Queued after evicting the oldest message
+    DisplacedOldest,
+}
+
+/// Why a message was not queued
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PeerSendError {
+    /// The queue is full and the policy drops new messages
+    Full,
+    /// The peer's receiver is gone
+    Closed,
+}
+
+#[derive(Debug)]
+struct Shared {
+    queue: Mutex<VecDeque<WireMessage>>,
+    notify: Notify,
+    sender_alive: AtomicBool,
+    receiver_alive: AtomicBool,
+}
+
+/// Create a peer queue; `bound: None` never sheds load
+pub fn peer_channel(bound: Option<usize>, policy: BackpressurePolicy) -> (PeerSender, PeerReceiver) {
+    let shared = Arc::new(Shared {
+        queue: Mutex::new(VecDeque::new()),
+        notify: Notify::new(),
+        sender_alive: AtomicBool::new(true),
+        receiver_alive: AtomicBool::new(true),
+    });
+    let sender = PeerSender { shared: shared.clone(), bound: bound.map(|b| b.max(1)), policy };
+    (sender, PeerReceiver { shared })
+}
+
+/// Relay side of a peer queue
+#[derive(Debug)]
+pub struct PeerSender {
+    shared: Arc<Shared>,
+    bound: Option<usize>,
+    policy: BackpressurePolicy,
+}
+
+impl PeerSender {
+    /// Queue `msg` without blocking, applying the backpressure policy when full
+    pub fn send(&self, msg: WireMessage) -> Result<Queued, PeerSendError> {
+        if !self.shared.receiver_alive.load(Ordering::Acquire) {
+            return Err(PeerSendError::Closed);
+        }
+        let mut queue = self.shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+        let mut outcome = Queued::Accepted;
+        if self.bound.is_some_and(|bound| queue.len() >= bound) {
+            match self.policy {
+                BackpressurePolicy::DropNew => return Err(PeerSendError::Full),
+                BackpressurePolicy::DropOldest => {
+                    queue.pop_front();
+                    outcome = Queued::DisplacedOldest;
+                }
+            }
+        }
+        queue.push_back(msg);
+        drop(queue);
+        self.shared.notify.notify_one();
+        Ok(outcome)
+    }
+}
+
+impl Drop for PeerSender {
+    fn drop(&mut self) {
+        self.shared.sender_alive.store(false, Ordering::Release);
+        self.shared.notify.notify_one();
+    }
+}
+
+/// Peer side of a peer queue
+#[derive(Debug)]
+pub struct PeerReceiver {
+    shared: Arc<Shared>,
+}
+
+impl PeerReceiver {
+    /// Next message; `None` once the relay dropped the peer and the queue is drained
+    pub async fn recv(&mut self) -> Option<WireMessage> {
+        loop {
+            match self.try_recv() {
+                Ok(msg) => return Some(msg),
+                Err(TryRecvError::Disconnected) => return None,
+                Err(TryRecvError::Empty) => self.shared.notify.notified().await,
+            }
+        }
+    }
+
+    pub fn try_recv(&mut self) -> Result<WireMessage, TryRecvError> {
+        let mut queue = self.shared.queue.lock().unwrap_or_else(|e| e.into_inner());
+        match queue.pop_front() {
+            Some(msg) => Ok(msg),
+            None if self.shared.sender_alive.load(Ordering::Acquire) => Err(TryRecvError::Empty),
+            None => Err(TryRecvError::Disconnected),
+        }
+    }
+
+    /// Messages waiting to be received
+    pub fn len(&self) -> usize {
+        self.shared.queue.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Drop for PeerReceiver {
+    fn drop(&mut self) {
+        self.shared.receiver_alive.store(false, Ordering::Release);
+    }
+}
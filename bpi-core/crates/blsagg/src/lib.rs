@@ -3,6 +3,8 @@
 
 use bpi_enc::{domain_hash, domains};
 use anyhow::Result;
+use blst::{min_pk, BLST_ERROR};
+use rand::RngCore;
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use thiserror::Error;
 
@@ -30,6 +32,8 @@ const G1_COMPRESSED_SIZE: usize = 48;
 const G2_COMPRESSED_SIZE: usize = 96;
 /// BLS12-381 scalar size (32 bytes)
 const SCALAR_SIZE: usize = 32;
+/// Hash-to-curve ciphersuite for signatures (public keys in G1, signatures in G2)
+const SIGNATURE_DST: &[u8] = b"BLS_SIG_BLS12381G2_XMD:SHA-256_SSWU_RO_NUL_";
 
 /// BLS public key (G1 point)
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -88,38 +92,22 @@ impl PublicKey {
     pub fn verify(&self, message: &[u8], signature: &Signature) -> bool {
         // Domain-separated message hash
         let msg_hash = domain_hash(domains::BLS_MESSAGE, message);
-        
-        // Simplified verification (in real implementation, would use pairing)
-        // For now, we simulate verification by checking if signature was created with matching key
         self.verify_hash(&msg_hash, signature)
     }
     
-    /// Verify a signature against a pre-hashed message
+    /// Verify a signature against a pre-hashed message with a pairing check.
+    /// Keys or signatures that are not valid points in their subgroup fail.
     pub fn verify_hash(&self, msg_hash: &[u8; 32], signature: &Signature) -> bool {
-        // Simplified verification - in real BLS this would use pairing checks
-        // For testing purposes, we simulate by checking deterministic relationship
-        let expected_sig = self.sign_hash_deterministic(msg_hash);
-        signature.bytes == expected_sig
+        let (Some(public_key), Some(signature)) = (self.point(), signature.point()) else {
+            return false;
+        };
+        signature.verify(true, msg_hash, SIGNATURE_DST, &[], &public_key, false) == BLST_ERROR::BLST_SUCCESS
     }
     
-    /// Deterministic signing for testing (not cryptographically secure)
-    fn sign_hash_deterministic(&self, msg_hash: &[u8; 32]) -> [u8; G2_COMPRESSED_SIZE] {
-        let mut combined = Vec::with_capacity(G1_COMPRESSED_SIZE + 32);
-        combined.extend_from_slice(&self.bytes);
-        combined.extend_from_slice(msg_hash);
-        
-        let hash = domain_hash(domains::BLS_SIGNATURE, &combined);
-        
-        // Expand to G2 size using repeated hashing
-        let mut sig_bytes = [0u8; G2_COMPRESSED_SIZE];
-        for i in 0..3 {
-            let chunk_hash = domain_hash(domains::BLS_SIGNATURE, &[hash.as_slice(), &[i as u8]].concat());
-            let start = i * 32;
-            let end = std::cmp::min(start + 32, G2_COMPRESSED_SIZE);
-            sig_bytes[start..end].copy_from_slice(&chunk_hash[..end - start]);
-        }
-        
-        sig_bytes
+    /// Decompress and subgroup-check the key; `None` for the identity or
+    /// bytes that are not a G1 point
+    fn point(&self) -> Option<min_pk::PublicKey> {
+        min_pk::PublicKey::key_validate(&self.bytes).ok()
     }
 }
 
@@ -145,19 +133,7 @@ impl PrivateKey {
     
     /// Generate the corresponding public key
     pub fn public_key(&self) -> PublicKey {
-        // Simplified key derivation - in real BLS this would be scalar multiplication on G1
-        let pubkey_hash = domain_hash(domains::BLS_PUBKEY, &self.bytes);
-        
-        // Expand to G1 size
-        let mut pubkey_bytes = [0u8; G1_COMPRESSED_SIZE];
-        for i in 0..2 {
-            let chunk_hash = domain_hash(domains::BLS_PUBKEY, &[pubkey_hash.as_slice(), &[i as u8]].concat());
-            let start = i * 32;
-            let end = std::cmp::min(start + 32, G1_COMPRESSED_SIZE);
-            pubkey_bytes[start..end].copy_from_slice(&chunk_hash[..end - start]);
-        }
-        
-        PublicKey { bytes: pubkey_bytes }
+        PublicKey { bytes: self.secret().sk_to_pk().compress() }
     }
     
     /// Sign a message
@@ -168,9 +144,14 @@ impl PrivateKey {
     
     /// Sign a pre-hashed message
     pub fn sign_hash(&self, msg_hash: &[u8; 32]) -> Signature {
-        let pubkey = self.public_key();
-        let sig_bytes = pubkey.sign_hash_deterministic(msg_hash);
-        Signature { bytes: sig_bytes }
+        Signature { bytes: self.secret().sign(msg_hash, SIGNATURE_DST, &[]).compress() }
+    }
+    
+    /// Scalar derived from the key bytes with the standard BLS key derivation,
+    /// so any 32 bytes give a valid non-zero key
+    fn secret(&self) -> min_pk::SecretKey {
+        min_pk::SecretKey::key_gen(&self.bytes, &[])
+            .expect("key material is SCALAR_SIZE bytes, the minimum key_gen accepts")
     }
 }
 
@@ -226,6 +207,11 @@ impl Signature {
     pub fn as_bytes(&self) -> &[u8] {
         &self.bytes
     }
+    
+    /// Decompress the signature; `None` if the bytes are not a G2 point
+    fn point(&self) -> Option<min_pk::Signature> {
+        min_pk::Signature::from_bytes(&self.bytes).ok()
+    }
 }
 
 /// Aggregated BLS signature with metadata
@@ -237,20 +223,13 @@ pub struct AggregatedSignature {
 }
 
 impl AggregatedSignature {
-    /// Verify the aggregated signature
+    /// Verify the aggregated signature against the sum of every signer's key.
+    /// Signer keys must have been registered with a proof of possession,
+    /// otherwise a rogue key can cancel out honest ones.
     pub fn verify(&self) -> bool {
-        if self.signers.is_empty() {
-            return false;
-        }
-        
-        // For simplified implementation, verify that the signature matches
-        // what any of the signers would produce (since they're all signing the same message)
-        // In real BLS, this would be a single pairing check against aggregated pubkey
-        if let Some(first_signer) = self.signers.first() {
-            first_signer.verify_hash(&self.message_hash, &self.signature)
-        } else {
-            false
-        }
+        aggregate_public_keys(&self.signers)
+            .map(|public_key| public_key.verify_hash(&self.message_hash, &self.signature))
+            .unwrap_or(false)
     }
     
     /// Get the number of signers
@@ -315,9 +294,7 @@ impl SignatureAggregator {
         
         let message_hash = self.message_hash.ok_or(BlsError::EmptySignatureSet)?;
         
-        // In real BLS, this would be point addition on G2
-        // For simulation, we use the first signature (since they're all the same for same message)
-        let aggregated_sig = self.signatures[0].clone();
+        let aggregated_sig = aggregate_signatures(&self.signatures)?;
         
         Ok(AggregatedSignature {
             signature: aggregated_sig,
@@ -337,7 +314,42 @@ impl SignatureAggregator {
     }
 }
 
-/// Batch verification for multiple signatures
+/// Combine signatures over the same message into one by adding the G2 points
+pub fn aggregate_signatures(signatures: &[Signature]) -> Result<Signature, BlsError> {
+    if signatures.is_empty() {
+        return Err(BlsError::EmptySignatureSet);
+    }
+    let points = signatures.iter()
+        .map(|signature| signature.point().ok_or_else(|| BlsError::CryptoError("Invalid signature point".to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+    let refs: Vec<&min_pk::Signature> = points.iter().collect();
+    let aggregate = min_pk::AggregateSignature::aggregate(&refs, true)
+        .map_err(|e| BlsError::CryptoError(format!("Signature aggregation failed: {e:?}")))?;
+    Ok(Signature { bytes: aggregate.to_signature().compress() })
+}
+
+/// Combine public keys by adding the G1 points; an aggregate signature over
+/// one message verifies against the result
+pub fn aggregate_public_keys(public_keys: &[PublicKey]) -> Result<PublicKey, BlsError> {
+    if public_keys.is_empty() {
+        return Err(BlsError::EmptySignatureSet);
+    }
+    let points = public_keys.iter()
+        .map(|public_key| public_key.point().ok_or_else(|| BlsError::CryptoError("Invalid public key point".to_string())))
+        .collect::<Result<Vec<_>, _>>()?;
+    let refs: Vec<&min_pk::PublicKey> = points.iter().collect();
+    let aggregate = min_pk::AggregatePublicKey::aggregate(&refs, false)
+        .map_err(|e| BlsError::CryptoError(format!("Public key aggregation failed: {e:?}")))?;
+    Ok(PublicKey { bytes: aggregate.to_public_key().compress() })
+}
+
+/// Bits of each random scalar weighting a signature in `batch_verify`
+const BATCH_RAND_BITS: usize = 64;
+
+/// Check each `signatures[i]` over `messages[i]` against `public_keys[i]` with
+/// one multi-pairing. Every triple is weighted by a fresh random scalar, so
+/// invalid signatures cannot cancel each other out. `false` means at least one
+/// signature is invalid, without saying which.
 pub fn batch_verify(
     signatures: &[Signature],
     public_keys: &[PublicKey],
@@ -346,15 +358,41 @@ pub fn batch_verify(
     if signatures.len() != public_keys.len() || signatures.len() != messages.len() {
         return Err(BlsError::MismatchedCounts);
     }
-    
-    // Verify each signature individually
-    for ((sig, pubkey), message) in signatures.iter().zip(public_keys.iter()).zip(messages.iter()) {
-        if !pubkey.verify(message, sig) {
-            return Ok(false);
-        }
+    if signatures.is_empty() {
+        return Ok(true);
     }
-    
-    Ok(true)
+
+    let (Some(key_points), Some(signature_points)) = (
+        public_keys.iter().map(PublicKey::point).collect::<Option<Vec<_>>>(),
+        signatures.iter().map(Signature::point).collect::<Option<Vec<_>>>(),
+    ) else {
+        return Ok(false);
+    };
+    let msg_hashes: Vec<[u8; 32]> = messages.iter()
+        .map(|message| domain_hash(domains::BLS_MESSAGE, message))
+        .collect();
+    let mut rng = rand::thread_rng();
+    let rands: Vec<blst::blst_scalar> = (0..signatures.len())
+        .map(|_| {
+            let weight = loop {
+                let weight = rng.next_u64();
+                if weight != 0 {
+                    break weight;
+                }
+            };
+            let mut scalar = blst::blst_scalar::default();
+            scalar.b[..8].copy_from_slice(&weight.to_le_bytes());
+            scalar
+        })
+        .collect();
+
+    let msg_refs: Vec<&[u8]> = msg_hashes.iter().map(|hash| hash.as_slice()).collect();
+    let key_refs: Vec<&min_pk::PublicKey> = key_points.iter().collect();
+    let signature_refs: Vec<&min_pk::Signature> = signature_points.iter().collect();
+    let result = min_pk::Signature::verify_multiple_aggregate_signatures(
+        &msg_refs, SIGNATURE_DST, &key_refs, false, &signature_refs, true, &rands, BATCH_RAND_BITS,
+    );
+    Ok(result == BLST_ERROR::BLST_SUCCESS)
 }
 
 /// Key generation utilities
@@ -483,6 +521,59 @@ mod tests {
         
         let result = batch_verify(&signatures, &public_keys, &messages).unwrap();
         assert!(result);
+
+        // Swapped signatures still sum to a valid unweighted aggregate, but the
+        // random weights catch them
+        let mut swapped = signatures.clone();
+        swapped.swap(0, 1);
+        assert!(!batch_verify(&swapped, &public_keys, &messages).unwrap());
+
+        assert!(batch_verify(&[], &[], &[]).unwrap());
+        assert!(matches!(batch_verify(&signatures[..2], &public_keys, &messages), Err(BlsError::MismatchedCounts)));
+    }
+    
+    #[test]
+    fn test_aggregate_covers_every_signer() {
+        let message = b"consensus message";
+        let keypairs = keygen::generate_test_keys(3);
+        let signatures: Vec<Signature> = keypairs.iter().map(|(sk, _)| sk.sign(message)).collect();
+        let public_keys: Vec<PublicKey> = keypairs.iter().map(|(_, pk)| pk.clone()).collect();
+        
+        let aggregate = aggregate_signatures(&signatures).unwrap();
+        assert!(signatures.iter().all(|signature| signature != &aggregate));
+        assert!(aggregate_public_keys(&public_keys).unwrap().verify(message, &aggregate));
+        
+        // A wrong share from a signer other than the first breaks the aggregate
+        let mut tampered = signatures.clone();
+        tampered[2] = keypairs[2].0.sign(b"other message");
+        let agg_sig = AggregatedSignature {
+            signature: aggregate_signatures(&tampered).unwrap(),
+            signers: public_keys.clone(),
+            message_hash: domain_hash(domains::BLS_MESSAGE, message),
+        };
+        assert!(!agg_sig.verify());
+        
+        // So does dropping a signer from the key list
+        let agg_sig = AggregatedSignature {
+            signature: aggregate,
+            signers: public_keys[..2].to_vec(),
+            message_hash: domain_hash(domains::BLS_MESSAGE, message),
+        };
+        assert!(!agg_sig.verify());
+    }
+    
+    #[test]
+    fn test_forged_points_rejected() {
+        let (_, public_key) = keygen::generate_keypair(b"test");
+        let zero_signature = Signature::from_bytes(&[0u8; G2_COMPRESSED_SIZE]).unwrap();
+        assert!(!public_key.verify(b"hello world", &zero_signature));
+        assert!(matches!(aggregate_signatures(&[zero_signature]), Err(BlsError::CryptoError(_))));
+        assert!(matches!(aggregate_signatures(&[]), Err(BlsError::EmptySignatureSet)));
+        
+        let zero_key = PublicKey::from_bytes(&[0u8; G1_COMPRESSED_SIZE]).unwrap();
+        let signature = keygen::generate_keypair(b"test").0.sign(b"hello world");
+        assert!(!zero_key.verify(b"hello world", &signature));
+        assert!(aggregate_public_keys(&[zero_key]).is_err());
     }
     
     #[test]
@@ -546,14 +637,16 @@ mod stage4_tests {
         let large_keypairs = keygen::generate_test_keys(100);
         let test_message = b"performance test";
         
-        let start = Instant::now();
         let mut perf_aggregator = SignatureAggregator::new();
         for (sk, pk) in &large_keypairs {
             let sig = sk.sign(test_message);
             perf_aggregator.add_signature(sig, pk.clone(), test_message).unwrap();
         }
-        let _agg = perf_aggregator.aggregate().unwrap();
+        // Signing and the per-signature pairing checks are not aggregation
+        let start = Instant::now();
+        let agg = perf_aggregator.aggregate().unwrap();
         let duration = start.elapsed();
+        assert!(agg.verify());
         
         println!("  100 signature aggregation: {:?}", duration);
         assert!(duration.as_millis() < 100, "Aggregation too slow: {:?}", duration);
@@ -1,7 +1,7 @@
 //! BLS commit objects and consensus primitives for BPI Mesh
 //! Stage 13: BLS Commit Object
 
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
@@ -9,6 +9,7 @@ use thiserror::Error;
 // Re-export dependencies
 pub use bpi_enc::{domain_hash, domains, CanonicalCbor};
 pub use bpi_blsagg::{Signature, PublicKey, PrivateKey, AggregatedSignature};
+use bpi_blsagg::{aggregate_public_keys, aggregate_signatures, batch_verify};
 pub use bpi_validator_set::{ValidatorSet, ValidatorInfo};
 pub use bpi_headers::{Header, HeaderHash};
 
@@ -29,6 +30,8 @@ pub enum ConsensusError {
     InvalidCommit(String),
     #[error("Threshold not met: {0}")]
     ThresholdNotMet(String),
+    #[error("Insufficient stake: signers hold {signed} of {total}, need at least 2/3")]
+    InsufficientStake { signed: u64, total: u64 },
 }
 
 /// BLS commit object containing aggregate signature and validator bitmap
@@ -44,6 +47,31 @@ pub struct BlsCommit {
     pub round: u64,
     /// Height of the block being committed
     pub height: u64,
+    /// Individual signature per signer, by validator index, when the commit was
+    /// built with `from_shares`; kept so one validator's signature can be
+    /// extracted later, e.g. as slashing evidence. Not part of `commit_hash`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub shares: Vec<(usize, Signature)>,
+}
+
+/// A finalized header together with the commit that finalized it; verifiable
+/// on its own, which is what a light client needs to accept a header
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QuorumCertificate {
+    /// The finalized header
+    pub header: Header,
+    /// Aggregate commit over the header's hash
+    pub commit: BlsCommit,
+}
+
+/// Validator sets keyed by the height they take effect from, so QCs spanning
+/// an epoch change are checked against the right set. With a chain id, headers
+/// are hashed with `Header::hash_for_chain`, so QCs signed for another network
+/// are rejected.
+#[derive(Debug, Clone, Default)]
+pub struct ValidatorSetProvider {
+    sets: BTreeMap<u64, ValidatorSet>,
+    chain_id: Option<u64>,
 }
 
 /// Bitmap tracking which validators participated in signing
@@ -173,9 +201,57 @@ impl BlsCommit {
             validator_bitmap,
             round,
             height,
+            shares: Vec::new(),
         }
     }
 
+    /// Build a commit from per-validator signature shares over the commit's
+    /// signing message. Shares are kept in validator order alongside the
+    /// aggregate. No validator set is involved, so the aggregate's `signers`
+    /// list is left empty and the shares are not checked here: a bad share
+    /// only shows up when the commit is checked with `verify_signature` or
+    /// `QuorumCertificate::verify`.
+    pub fn from_shares(
+        header_hash: HeaderHash,
+        shares: &[(usize, Signature)],
+        validator_count: usize,
+        round: u64,
+        height: u64,
+    ) -> Result<BlsCommit> {
+        let mut shares = shares.to_vec();
+        shares.sort_by_key(|(validator_index, _)| *validator_index);
+
+        let mut bitmap = ValidatorBitmap::new(validator_count);
+        for (validator_index, _) in &shares {
+            if bitmap.is_set(*validator_index) {
+                return Err(ConsensusError::DuplicateSignature(*validator_index).into());
+            }
+            bitmap.set(*validator_index)?;
+        }
+
+        let signatures: Vec<Signature> = shares.iter().map(|(_, signature)| signature.clone()).collect();
+        let signature = aggregate_signatures(&signatures)
+            .map_err(|e| ConsensusError::InvalidCommit(e.to_string()))?;
+
+        let mut commit = Self::new(
+            header_hash,
+            AggregatedSignature { signature, signers: Vec::new(), message_hash: [0u8; 32] },
+            bitmap,
+            round,
+            height,
+        );
+        commit.aggregate_signature.message_hash = domain_hash(domains::BLS_MESSAGE, &commit.signing_message());
+        commit.shares = shares;
+        Ok(commit)
+    }
+
+    /// Signature share `validator_index` contributed, if the commit kept shares
+    pub fn share(&self, validator_index: usize) -> Option<&Signature> {
+        self.shares.iter()
+            .find(|(index, _)| *index == validator_index)
+            .map(|(_, signature)| signature)
+    }
+
     /// Verify the commit against a validator set
     pub fn verify(&self, validator_set: &ValidatorSet) -> Result<CommitVerification> {
         let mut errors = Vec::new();
@@ -222,22 +298,236 @@ impl BlsCommit {
         })
     }
 
+    /// Check the aggregate signature over `signing_message` against the sum of
+    /// the BLS keys of the validators marked in the bitmap
+    pub fn verify_signature(&self, validator_set: &ValidatorSet) -> bool {
+        self.signer_key(validator_set)
+            .is_some_and(|key| key.verify(&self.signing_message(), &self.aggregate_signature.signature))
+    }
+
+    /// Aggregate BLS key of the bitmap signers; `None` if a signer is not in
+    /// the set or has an invalid key
+    fn signer_key(&self, validator_set: &ValidatorSet) -> Option<PublicKey> {
+        let keys = self.validator_bitmap.get_set_indices().into_iter()
+            .map(|index| validator_set.get_validator(index).map(|validator| validator.bls_pubkey.clone()))
+            .collect::<Option<Vec<_>>>()?;
+        aggregate_public_keys(&keys).ok()
+    }
+
     /// Get the signing message for this commit
     pub fn signing_message(&self) -> Vec<u8> {
-        let mut message = Vec::new();
-        message.extend_from_slice(self.header_hash.as_bytes());
-        message.extend_from_slice(&self.round.to_le_bytes());
-        message.extend_from_slice(&self.height.to_le_bytes());
-        message
+        commit_signing_message(&self.header_hash, self.round, self.height)
     }
 
-    /// Get commit hash for identification
+    /// Get commit hash for identification. Retained shares are left out, so a
+    /// commit hashes the same however it was built.
     pub fn commit_hash(&self) -> [u8; 32] {
-        let encoded = self.to_canonical_cbor().unwrap_or_default();
+        let encoded = if self.shares.is_empty() {
+            self.to_canonical_cbor()
+        } else {
+            BlsCommit { shares: Vec::new(), ..self.clone() }.to_canonical_cbor()
+        }.unwrap_or_default();
         domain_hash(domains::CONSENSUS_COMMIT, &encoded)
     }
 }
 
+impl QuorumCertificate {
+    /// Bind a header to its commit
+    pub fn new(header: Header, commit: BlsCommit) -> Self {
+        Self { header, commit }
+    }
+
+    /// Check that the commit is for this header (hash, height and round), that
+    /// its signers meet the count threshold and hold at least 2/3 of the stake,
+    /// and that the aggregate signature verifies against those signers' keys
+    pub fn verify(&self, validator_set: &ValidatorSet) -> Result<()> {
+        self.verify_against(validator_set, self.header.hash()?)
+    }
+
+    /// `verify` for a commit over the header's hash bound to `chain_id`, as
+    /// signed with `ValidatorSignature::sign_for_chain`
+    pub fn verify_for_chain(&self, validator_set: &ValidatorSet, chain_id: u64) -> Result<()> {
+        self.verify_against(validator_set, self.header.hash_for_chain(chain_id)?)
+    }
+
+    fn verify_against(&self, validator_set: &ValidatorSet, header_hash: HeaderHash) -> Result<()> {
+        self.verify_quorum(validator_set, header_hash)?;
+        if !self.commit.verify_signature(validator_set) {
+            return Err(ConsensusError::InvalidCommit(
+                "Aggregate signature does not verify against the signers' keys".to_string()
+            ).into());
+        }
+        Ok(())
+    }
+
+    /// Every check in `verify` except the signature one, with the commit
+    /// expected to be over `header_hash`
+    fn verify_quorum(&self, validator_set: &ValidatorSet, header_hash: HeaderHash) -> Result<()> {
+        if self.commit.header_hash != header_hash {
+            return Err(ConsensusError::InvalidCommit(
+                "Commit references a different header".to_string()
+            ).into());
+        }
+        if self.commit.height != self.header.height || self.commit.round != self.header.round {
+            return Err(ConsensusError::InvalidCommit(format!(
+                "Commit is for height {} round {}, header is height {} round {}",
+                self.commit.height, self.commit.round, self.header.height, self.header.round
+            )).into());
+        }
+
+        let verification = self.commit.verify(validator_set)?;
+        if !verification.is_valid {
+            return Err(ConsensusError::ThresholdNotMet(verification.errors.join("; ")).into());
+        }
+
+        let signed = committed_stake(&verification.signers, validator_set);
+        let total = validator_set.total_stake();
+        if (signed as u128) * 3 < (total as u128) * 2 {
+            return Err(ConsensusError::InsufficientStake { signed, total }.into());
+        }
+
+        Ok(())
+    }
+}
+
+impl ValidatorSignature {
+    /// Sign the commit message for `header` as validator `validator_index`,
+    /// over the header's hash bound to `chain_id` so the signature cannot be
+    /// replayed on another network
+    pub fn sign_for_chain(private_key: &PrivateKey, validator_index: usize, header: &Header, chain_id: u64) -> Result<Self> {
+        let header_hash = header.hash_for_chain(chain_id)?;
+        let signature = private_key.sign(&commit_signing_message(&header_hash, header.round, header.height));
+        Ok(Self {
+            validator_index,
+            signature,
+            header_hash,
+            round: header.round,
+        })
+    }
+}
+
+impl QuorumCertificate {
+    /// Stake held by the validators marked in the commit bitmap
+    pub fn committed_stake(&self, validator_set: &ValidatorSet) -> u64 {
+        committed_stake(&self.commit.validator_bitmap.get_set_indices(), validator_set)
+    }
+}
+
+/// Message validators sign for a commit: header hash, then round and height
+fn commit_signing_message(header_hash: &HeaderHash, round: u64, height: u64) -> Vec<u8> {
+    let mut message = Vec::new();
+    message.extend_from_slice(header_hash.as_bytes());
+    message.extend_from_slice(&round.to_le_bytes());
+    message.extend_from_slice(&height.to_le_bytes());
+    message
+}
+
+fn committed_stake(signers: &[usize], validator_set: &ValidatorSet) -> u64 {
+    signers.iter()
+        .filter_map(|&index| validator_set.get_validator(index))
+        .map(|validator| validator.stake)
+        .sum()
+}
+
+impl ValidatorSetProvider {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Hash headers for `chain_id` when matching them to their commits
+    pub fn with_chain_id(mut self, chain_id: u64) -> Self {
+        self.chain_id = Some(chain_id);
+        self
+    }
+
+    /// Hash a commit must reference to finalize `header`
+    pub fn header_hash(&self, header: &Header) -> Result<HeaderHash> {
+        match self.chain_id {
+            Some(chain_id) => header.hash_for_chain(chain_id),
+            None => header.hash(),
+        }
+    }
+
+    /// Use `validator_set` for heights from `from_height` until the next entry
+    pub fn insert(&mut self, from_height: u64, validator_set: ValidatorSet) {
+        self.sets.insert(from_height, validator_set);
+    }
+
+    /// Validator set in effect at `height`
+    pub fn set_for_height(&self, height: u64) -> Option<&ValidatorSet> {
+        self.sets.range(..=height).next_back().map(|(_, set)| set)
+    }
+}
+
+/// Verify a range of QCs, returning the index of the first one that fails.
+/// Structural and stake checks run per QC. The aggregate signatures, each
+/// against the aggregate key of its bitmap signers, are then checked together
+/// in one randomized multi-pairing; only if that fails are they checked one by
+/// one, up to the first bad signature, to find its index.
+pub fn verify_qc_batch(qcs: &[QuorumCertificate], validator_sets: &ValidatorSetProvider) -> std::result::Result<(), usize> {
+    let mut signatures = Vec::with_capacity(qcs.len());
+    let mut public_keys = Vec::with_capacity(qcs.len());
+    let mut messages = Vec::with_capacity(qcs.len());
+    let mut structural_failure = None;
+
+    for (index, qc) in qcs.iter().enumerate() {
+        let signer = validator_sets.set_for_height(qc.header.height)
+            .filter(|validator_set| {
+                validator_sets.header_hash(&qc.header)
+                    .and_then(|header_hash| qc.verify_quorum(validator_set, header_hash))
+                    .is_ok()
+            })
+            .and_then(|validator_set| qc.commit.signer_key(validator_set));
+        match signer {
+            Some(signer) => {
+                signatures.push(qc.commit.aggregate_signature.signature.clone());
+                public_keys.push(signer);
+                messages.push(qc.commit.signing_message());
+            }
+            None => {
+                structural_failure = Some(index);
+                break;
+            }
+        }
+    }
+
+    // Everything before a structural failure still has to pass its signature check
+    let message_refs: Vec<&[u8]> = messages.iter().map(Vec::as_slice).collect();
+    if !batch_verify(&signatures, &public_keys, &message_refs).unwrap_or(false) {
+        if let Some(index) = (0..signatures.len()).find(|&i| !public_keys[i].verify(&messages[i], &signatures[i])) {
+            return Err(index);
+        }
+    }
+
+    structural_failure.map_or(Ok(()), Err)
+}
+
+/// Pick the canonical tip among competing chains: highest finalized height,
+/// then most committed stake, then lowest header hash. Tips whose QC fails
+/// verification, has no validator set for its height, or does not commit to
+/// the tip's header are ignored.
+pub fn choose_canonical_tip<'a>(
+    tips: &'a [(Header, QuorumCertificate)],
+    validator_sets: &ValidatorSetProvider,
+) -> Option<&'a Header> {
+    tips.iter()
+        .filter_map(|(header, qc)| {
+            let validator_set = validator_sets.set_for_height(qc.header.height)?;
+            let hash = validator_sets.header_hash(header).ok()?;
+            qc.verify_against(validator_set, validator_sets.header_hash(&qc.header).ok()?).ok()?;
+            if hash != qc.commit.header_hash {
+                return None;
+            }
+            Some((qc.header.height, qc.committed_stake(validator_set), hash, header))
+        })
+        .max_by(|(height_a, stake_a, hash_a, _), (height_b, stake_b, hash_b, _)| {
+            height_a.cmp(height_b)
+                .then(stake_a.cmp(stake_b))
+                .then(hash_b.0.cmp(&hash_a.0))
+        })
+        .map(|(_, _, _, header)| header)
+}
+
 impl CommitAggregator {
     /// Create a new commit aggregator
     pub fn new(
@@ -316,43 +606,30 @@ impl CommitAggregator {
             }.into());
         }
 
-        // Create validator bitmap
+        // Create validator bitmap, aggregating in validator order
+        let mut indices: Vec<usize> = self.signatures.keys().copied().collect();
+        indices.sort_unstable();
         let mut bitmap = ValidatorBitmap::new(self.validator_set.len());
-        let mut signatures_to_aggregate = Vec::new();
+        let mut signatures_to_aggregate = Vec::with_capacity(indices.len());
+        let mut signers = Vec::with_capacity(indices.len());
 
-        for (&validator_index, signature) in &self.signatures {
+        for &validator_index in &indices {
             bitmap.set(validator_index)?;
-            signatures_to_aggregate.push(signature.signature.clone());
+            signatures_to_aggregate.push(self.signatures[&validator_index].signature.clone());
+            signers.push(self.validator_set.get_validator(validator_index).unwrap().bls_pubkey.clone());
         }
 
-        // Aggregate signatures (simplified for testing)
-        let aggregate_signature = if signatures_to_aggregate.is_empty() {
-            AggregatedSignature {
-                signature: Signature::from_bytes(&[0u8; 96]).unwrap(),
-                signers: Vec::new(),
-                message_hash: [0u8; 32],
-            }
-        } else {
-            // In a real implementation, this would properly aggregate BLS signatures
-            // For testing, we'll create a mock aggregated signature
-            let signers: Vec<PublicKey> = self.signatures.iter()
-                .map(|(idx, _)| self.validator_set.get_validator(*idx).unwrap().bls_pubkey.clone())
-                .collect();
-            let message_hash = domain_hash(domains::CONSENSUS_COMMIT, self.header_hash.as_bytes());
-            AggregatedSignature {
-                signature: signatures_to_aggregate[0].clone(),
-                signers,
-                message_hash,
-            }
-        };
-
-        Ok(BlsCommit::new(
+        let signature = aggregate_signatures(&signatures_to_aggregate)
+            .map_err(|e| ConsensusError::InvalidCommit(e.to_string()))?;
+        let mut commit = BlsCommit::new(
             self.header_hash.clone(),
-            aggregate_signature,
+            AggregatedSignature { signature, signers, message_hash: [0u8; 32] },
             bitmap,
             self.round,
             self.height,
-        ))
+        );
+        commit.aggregate_signature.message_hash = domain_hash(domains::BLS_MESSAGE, &commit.signing_message());
+        Ok(commit)
     }
 
     /// Get list of validators that have signed
@@ -385,17 +662,22 @@ mod tests {
     use super::*;
     use bpi_validator_set::{ValidatorInfo, VrfPublicKey};
     use bpi_blsagg::PrivateKey;
-    use bpi_headers::HeaderHash;
+    use bpi_headers::{ConsensusMode, HeaderConfig, HeaderHash};
 
     fn create_test_validator_set() -> ValidatorSet {
+        create_seeded_validator_set(0)
+    }
+
+    /// Seven validators whose keys are derived from `seed + index`
+    fn create_seeded_validator_set(seed: u8) -> ValidatorSet {
         let mut validator_set = ValidatorSet::new(1);
         
         for i in 0..7 {
-            let private_key = PrivateKey::from_bytes(&[i as u8; 32]).unwrap();
+            let private_key = PrivateKey::from_bytes(&[seed + i as u8; 32]).unwrap();
             let validator = ValidatorInfo::new(
                 i,
                 private_key.public_key(),
-                VrfPublicKey::from_bytes(&[i as u8; 32]).unwrap(),
+                VrfPublicKey::from_bytes(&[seed + i as u8; 32]).unwrap(),
                 1000 + i as u64 * 100,
                 format!("validator-{}", i),
                 format!("node-{}", i),
@@ -407,12 +689,16 @@ mod tests {
     }
 
     fn create_test_signature(validator_index: usize, header_hash: HeaderHash, round: u64) -> ValidatorSignature {
-        let private_key = PrivateKey::from_bytes(&[validator_index as u8; 32]).unwrap();
+        create_seeded_signature(0, validator_index, header_hash, round, 100)
+    }
+
+    fn create_seeded_signature(seed: u8, validator_index: usize, header_hash: HeaderHash, round: u64, height: u64) -> ValidatorSignature {
+        let private_key = PrivateKey::from_bytes(&[seed + validator_index as u8; 32]).unwrap();
         let message = {
             let mut msg = Vec::new();
             msg.extend_from_slice(header_hash.as_bytes());
             msg.extend_from_slice(&round.to_le_bytes());
-            msg.extend_from_slice(&100u64.to_le_bytes()); // height
+            msg.extend_from_slice(&height.to_le_bytes());
             msg
         };
         let signature = private_key.sign(&message);
@@ -568,6 +854,52 @@ mod tests {
         assert_eq!(verification.signers, vec![0, 1, 2, 3, 4]);
     }
 
+    #[test]
+    fn test_commit_from_shares() {
+        let validator_set = create_test_validator_set();
+        let header_hash = HeaderHash::from_bytes([1u8; 32]);
+        let shares: Vec<(usize, Signature)> = [4, 0, 2, 1, 3].iter()
+            .map(|&i| (i, create_test_signature(i, header_hash, 1).signature))
+            .collect();
+
+        let commit = BlsCommit::from_shares(header_hash, &shares, validator_set.len(), 1, 100).unwrap();
+        assert_eq!(commit.validator_bitmap.get_set_indices(), vec![0, 1, 2, 3, 4]);
+        assert_eq!(commit.shares.iter().map(|(i, _)| *i).collect::<Vec<_>>(), vec![0, 1, 2, 3, 4]);
+
+        let verification = commit.verify(&validator_set).unwrap();
+        assert!(verification.is_valid, "{:?}", verification.errors);
+        assert_eq!(verification.signers, vec![0, 1, 2, 3, 4]);
+
+        // Each retained share is that validator's own signature over the commit
+        for i in 0..5 {
+            let public_key = &validator_set.get_validator(i).unwrap().bls_pubkey;
+            assert!(public_key.verify(&commit.signing_message(), commit.share(i).unwrap()));
+        }
+        assert!(commit.share(5).is_none());
+
+        // Shares survive an encoding round trip but do not change the commit's identity
+        let decoded = <BlsCommit as CanonicalCbor>::from_canonical_cbor(&commit.to_canonical_cbor().unwrap()).unwrap();
+        assert_eq!(decoded.shares, commit.shares);
+        let mut without_shares = commit.clone();
+        without_shares.shares.clear();
+        assert_eq!(without_shares.commit_hash(), commit.commit_hash());
+        assert!(commit.verify_signature(&validator_set));
+    }
+
+    #[test]
+    fn test_commit_from_shares_rejects_bad_shares() {
+        let header_hash = HeaderHash::from_bytes([1u8; 32]);
+        let share = |i: usize| (i, create_test_signature(i, header_hash, 1).signature);
+
+        let duplicate = BlsCommit::from_shares(header_hash, &[share(0), share(1), share(0)], 7, 1, 100);
+        assert!(matches!(
+            duplicate.unwrap_err().downcast::<ConsensusError>().unwrap(),
+            ConsensusError::DuplicateSignature(0)
+        ));
+        assert!(BlsCommit::from_shares(header_hash, &[share(7)], 7, 1, 100).is_err());
+        assert!(BlsCommit::from_shares(header_hash, &[], 7, 1, 100).is_err());
+    }
+
     #[test]
     fn test_commit_insufficient_signatures() {
         let validator_set = create_test_validator_set();
@@ -634,6 +966,308 @@ mod tests {
         assert_eq!(hash, hash2);
     }
 
+    fn create_test_header() -> Header {
+        create_test_header_at(100)
+    }
+
+    fn create_test_header_at(height: u64) -> Header {
+        Header::new(HeaderConfig {
+            version: 1,
+            height,
+            prev_hash: [1u8; 32],
+            poh_root: [2u8; 32],
+            receipts_root: [3u8; 32],
+            da_root: [4u8; 32],
+            xcmp_root: [5u8; 32],
+            validator_set_hash: [6u8; 32],
+            mode: ConsensusMode::Ibft,
+            round: 1,
+        })
+    }
+
+    fn create_test_qc(header: Header, commit_hash: HeaderHash, signers: std::ops::Range<usize>) -> QuorumCertificate {
+        let validator_set = create_test_validator_set();
+        let mut aggregator = CommitAggregator::new(validator_set, commit_hash, 1, 100);
+        for i in signers {
+            aggregator.add_signature(create_test_signature(i, commit_hash, 1)).unwrap();
+        }
+        QuorumCertificate::new(header, aggregator.aggregate().unwrap())
+    }
+
+    /// QC over `header` signed by validators 2..7 of the set seeded with `seed`
+    fn create_seeded_qc(seed: u8, header: Header) -> QuorumCertificate {
+        create_seeded_qc_signed_by(seed, header, 2..7)
+    }
+
+    fn create_seeded_qc_signed_by(seed: u8, header: Header, signers: std::ops::Range<usize>) -> QuorumCertificate {
+        let header_hash = header.hash().unwrap();
+        let mut aggregator = CommitAggregator::new(
+            create_seeded_validator_set(seed), header_hash, header.round, header.height,
+        );
+        for i in signers {
+            aggregator.add_signature(
+                create_seeded_signature(seed, i, header_hash, header.round, header.height)
+            ).unwrap();
+        }
+        QuorumCertificate::new(header, aggregator.aggregate().unwrap())
+    }
+
+    fn consensus_error(result: Result<()>) -> ConsensusError {
+        result.unwrap_err().downcast::<ConsensusError>().unwrap()
+    }
+
+    #[test]
+    fn test_quorum_certificate_valid() {
+        let validator_set = create_test_validator_set();
+        let header = create_test_header();
+        let header_hash = header.hash().unwrap();
+
+        // Validators 2..7 hold 7000 of 9100 stake
+        let qc = create_test_qc(header, header_hash, 2..7);
+        assert!(qc.verify(&validator_set).is_ok());
+    }
+
+    #[test]
+    fn test_quorum_certificate_wrong_header() {
+        let validator_set = create_test_validator_set();
+        let header = create_test_header();
+
+        let qc = create_test_qc(header, HeaderHash::from_bytes([9u8; 32]), 2..7);
+        assert!(matches!(consensus_error(qc.verify(&validator_set)), ConsensusError::InvalidCommit(_)));
+    }
+
+    #[test]
+    fn test_quorum_certificate_sub_quorum() {
+        let validator_set = create_test_validator_set();
+        let header = create_test_header();
+        let header_hash = header.hash().unwrap();
+
+        // Enough signers by count, but validators 0..5 hold only 6000 of 9100 stake
+        let qc = create_test_qc(header.clone(), header_hash, 0..5);
+        assert!(matches!(
+            consensus_error(qc.verify(&validator_set)),
+            ConsensusError::InsufficientStake { signed: 6000, total: 9100 }
+        ));
+
+        // Too few signers
+        let mut bitmap = ValidatorBitmap::new(validator_set.len());
+        for i in 4..7 {
+            bitmap.set(i).unwrap();
+        }
+        let commit = BlsCommit::new(
+            header_hash,
+            AggregatedSignature {
+                signature: Signature::from_bytes(&[0u8; 96]).unwrap(),
+                signers: Vec::new(),
+                message_hash: [0u8; 32],
+            },
+            bitmap,
+            1,
+            100,
+        );
+        let qc = QuorumCertificate::new(header, commit);
+        assert!(matches!(consensus_error(qc.verify(&validator_set)), ConsensusError::ThresholdNotMet(_)));
+    }
+
+    #[test]
+    fn test_quorum_certificate_forged_signature() {
+        let validator_set = create_test_validator_set();
+        let header = create_test_header();
+        let header_hash = header.hash().unwrap();
+        let mut qc = create_test_qc(header.clone(), header_hash, 2..7);
+
+        // Bitmap, header and stake all check out, but the signature is zeros
+        qc.commit.aggregate_signature.signature = Signature::from_bytes(&[0u8; 96]).unwrap();
+        assert!(matches!(consensus_error(qc.verify(&validator_set)), ConsensusError::InvalidCommit(_)));
+
+        // A single genuine share does not stand in for the whole signer set
+        let share = create_test_signature(2, header_hash, 1).signature;
+        qc.commit.aggregate_signature.signature = share;
+        assert!(matches!(consensus_error(qc.verify(&validator_set)), ConsensusError::InvalidCommit(_)));
+
+        // Nor does a valid aggregate once the bitmap claims an extra signer
+        let mut qc = create_test_qc(header, header_hash, 2..7);
+        qc.commit.validator_bitmap.set(0).unwrap();
+        assert!(matches!(consensus_error(qc.verify(&validator_set)), ConsensusError::InvalidCommit(_)));
+    }
+
+    #[test]
+    fn test_qc_batch_all_valid() {
+        let mut provider = ValidatorSetProvider::new();
+        provider.insert(0, create_test_validator_set());
+
+        let qcs: Vec<_> = (100..104).map(|h| create_seeded_qc(0, create_test_header_at(h))).collect();
+        assert_eq!(verify_qc_batch(&qcs, &provider), Ok(()));
+        assert_eq!(verify_qc_batch(&[], &provider), Ok(()));
+    }
+
+    #[test]
+    fn test_qc_batch_reports_bad_index() {
+        let mut provider = ValidatorSetProvider::new();
+        provider.insert(0, create_test_validator_set());
+
+        let mut qcs: Vec<_> = (100..104).map(|h| create_seeded_qc(0, create_test_header_at(h))).collect();
+        qcs[2].commit.aggregate_signature.signature = Signature::from_bytes(&[7u8; 96]).unwrap();
+        assert_eq!(verify_qc_batch(&qcs, &provider), Err(2));
+
+        // A later structural failure does not mask an earlier bad signature
+        qcs[3].commit.header_hash = HeaderHash::from_bytes([9u8; 32]);
+        assert_eq!(verify_qc_batch(&qcs, &provider), Err(2));
+
+        qcs[2] = create_seeded_qc(0, create_test_header_at(102));
+        assert_eq!(verify_qc_batch(&qcs, &provider), Err(3));
+    }
+
+    #[test]
+    fn test_qc_batch_rejects_bad_non_first_share() {
+        let mut provider = ValidatorSetProvider::new();
+        provider.insert(0, create_test_validator_set());
+
+        let mut qcs: Vec<_> = (100..103).map(|h| create_seeded_qc(0, create_test_header_at(h))).collect();
+        let header = create_test_header_at(103);
+        let header_hash = header.hash().unwrap();
+        // Validator 5 signs the wrong height; 2, 3, 4 and 6 sign correctly
+        let shares: Vec<(usize, Signature)> = (2..7)
+            .map(|i| {
+                let height = if i == 5 { 104 } else { 103 };
+                (i, create_seeded_signature(0, i, header_hash, header.round, height).signature)
+            })
+            .collect();
+        let commit = BlsCommit::from_shares(header_hash, &shares, 7, header.round, header.height).unwrap();
+        qcs.push(QuorumCertificate::new(header.clone(), commit));
+        assert_eq!(verify_qc_batch(&qcs, &provider), Err(3));
+
+        let mut shares = shares;
+        shares[3].1 = create_seeded_signature(0, 5, header_hash, header.round, 103).signature;
+        qcs[3].commit = BlsCommit::from_shares(header_hash, &shares, 7, header.round, header.height).unwrap();
+        assert_eq!(verify_qc_batch(&qcs, &provider), Ok(()));
+    }
+
+    /// QC over `header` hashed for `chain_id`, signed by validators 2..7
+    fn create_chain_qc(chain_id: u64, header: Header) -> QuorumCertificate {
+        let validator_set = create_test_validator_set();
+        let header_hash = header.hash_for_chain(chain_id).unwrap();
+        let mut aggregator = CommitAggregator::new(validator_set, header_hash, header.round, header.height);
+        for i in 2..7 {
+            let private_key = PrivateKey::from_bytes(&[i as u8; 32]).unwrap();
+            aggregator.add_signature(ValidatorSignature::sign_for_chain(&private_key, i, &header, chain_id).unwrap()).unwrap();
+        }
+        QuorumCertificate::new(header, aggregator.aggregate().unwrap())
+    }
+
+    #[test]
+    fn test_quorum_certificate_bound_to_chain() {
+        let validator_set = create_test_validator_set();
+        let qc = create_chain_qc(1, create_test_header());
+
+        assert!(qc.verify_for_chain(&validator_set, 1).is_ok());
+        // Not valid on another network, nor as an unbound header hash
+        assert!(matches!(consensus_error(qc.verify_for_chain(&validator_set, 1337)), ConsensusError::InvalidCommit(_)));
+        assert!(matches!(consensus_error(qc.verify(&validator_set)), ConsensusError::InvalidCommit(_)));
+    }
+
+    #[test]
+    fn test_qc_batch_and_tip_use_provider_chain_id() {
+        let mut mainnet = ValidatorSetProvider::new().with_chain_id(1);
+        mainnet.insert(0, create_test_validator_set());
+        let mut testnet = ValidatorSetProvider::new().with_chain_id(1337);
+        testnet.insert(0, create_test_validator_set());
+
+        let qcs: Vec<_> = (100..103).map(|h| create_chain_qc(1, create_test_header_at(h))).collect();
+        assert_eq!(verify_qc_batch(&qcs, &mainnet), Ok(()));
+        assert_eq!(verify_qc_batch(&qcs, &testnet), Err(0));
+        assert_eq!(verify_qc_batch(&qcs, &test_provider()), Err(0));
+
+        let tips: Vec<_> = qcs.iter().map(|qc| (qc.header.clone(), qc.clone())).collect();
+        assert_eq!(choose_canonical_tip(&tips, &mainnet), Some(&tips[2].0));
+        assert_eq!(choose_canonical_tip(&tips, &testnet), None);
+    }
+
+    #[test]
+    fn test_qc_batch_epoch_switch() {
+        let mut provider = ValidatorSetProvider::new();
+        provider.insert(0, create_test_validator_set());
+        provider.insert(200, create_seeded_validator_set(50));
+
+        let qcs = vec![
+            create_seeded_qc(0, create_test_header_at(199)),
+            create_seeded_qc(50, create_test_header_at(200)),
+            create_seeded_qc(50, create_test_header_at(201)),
+        ];
+        assert_eq!(verify_qc_batch(&qcs, &provider), Ok(()));
+
+        // The old set's signatures are not accepted once the new epoch starts
+        let stale = vec![
+            create_seeded_qc(0, create_test_header_at(199)),
+            create_seeded_qc(0, create_test_header_at(200)),
+        ];
+        assert_eq!(verify_qc_batch(&stale, &provider), Err(1));
+    }
+
+    fn finalized_tip(height: u64, poh_root: u8, signers: std::ops::Range<usize>) -> (Header, QuorumCertificate) {
+        let mut header = create_test_header_at(height);
+        header.poh_root = [poh_root; 32];
+        let qc = create_seeded_qc_signed_by(0, header.clone(), signers);
+        (header, qc)
+    }
+
+    fn test_provider() -> ValidatorSetProvider {
+        let mut provider = ValidatorSetProvider::new();
+        provider.insert(0, create_test_validator_set());
+        provider
+    }
+
+    #[test]
+    fn test_canonical_tip_highest_height_wins() {
+        let provider = test_provider();
+        // The taller chain wins even with less committed stake
+        let tips = vec![finalized_tip(100, 2, 1..7), finalized_tip(101, 2, 2..7)];
+        assert_eq!(choose_canonical_tip(&tips, &provider), Some(&tips[1].0));
+
+        // Unless its QC does not verify
+        let mut forged = tips.clone();
+        forged[1].1.commit.header_hash = HeaderHash::from_bytes([9u8; 32]);
+        assert_eq!(choose_canonical_tip(&forged, &provider), Some(&forged[0].0));
+        assert_eq!(choose_canonical_tip(&forged[1..], &provider), None);
+        assert_eq!(choose_canonical_tip(&[], &provider), None);
+    }
+
+    #[test]
+    fn test_canonical_tip_rejects_header_the_qc_does_not_cover() {
+        let provider = test_provider();
+        // A valid QC for height 101 paired with an unrelated header
+        let mut tips = vec![finalized_tip(100, 2, 2..7), finalized_tip(101, 2, 2..7)];
+        tips[1].0.poh_root = [7u8; 32];
+        assert_eq!(choose_canonical_tip(&tips, &provider), Some(&tips[0].0));
+        assert_eq!(choose_canonical_tip(&tips[1..], &provider), None);
+    }
+
+    #[test]
+    fn test_canonical_tip_stake_breaks_height_tie() {
+        let provider = test_provider();
+        // Validators 1..7 hold 8100 of the stake, 2..7 only 7000
+        let tips = vec![finalized_tip(100, 2, 2..7), finalized_tip(100, 3, 1..7)];
+        let validator_set = create_test_validator_set();
+        assert_eq!(tips[0].1.committed_stake(&validator_set), 7000);
+        assert_eq!(tips[1].1.committed_stake(&validator_set), 8100);
+        assert_eq!(choose_canonical_tip(&tips, &provider), Some(&tips[1].0));
+    }
+
+    #[test]
+    fn test_canonical_tip_lowest_hash_breaks_full_tie() {
+        let provider = test_provider();
+        let tips = vec![finalized_tip(100, 2, 2..7), finalized_tip(100, 3, 2..7)];
+        let lowest = tips.iter()
+            .min_by_key(|(header, _)| header.hash().unwrap().0)
+            .map(|(header, _)| header)
+            .unwrap();
+        assert_eq!(choose_canonical_tip(&tips, &provider), Some(lowest));
+
+        // Independent of input order
+        let reversed: Vec<_> = tips.iter().rev().cloned().collect();
+        assert_eq!(choose_canonical_tip(&reversed, &provider), Some(lowest));
+    }
+
     #[test]
     fn test_byzantine_fault_tolerance_thresholds() {
         // Test different validator set sizes and their thresholds
@@ -0,0 +1,139 @@
+//! Data-availability root over shard headers
+//!
+//! `Header.da_root` is the Merkle root over the block's DA shard headers in
+//! shard order. Validators rebuild it from the shard headers they received
+//! before accepting the block. Every shard header names its index and the
+//! total shard count, so a truncated or padded set cannot rebuild the root.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{HeaderHash, MerkleTree, ValidationError};
+
+/// DA root of a block with no shards
+pub const EMPTY_DA_ROOT: [u8; 32] = [0u8; 32];
+
+/// Header of one DA shard as committed to by `da_root`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShardHeader {
+    /// Position of the shard within the block
+    pub shard_index: u32,
+    /// Number of shards in the block
+    pub total_shards: u32,
+    /// Commitment to the shard's data
+    pub data_commitment: [u8; 32],
+}
+
+impl ShardHeader {
+    pub fn new(shard_index: u32, total_shards: u32, data_commitment: [u8; 32]) -> Self {
+        Self { shard_index, total_shards, data_commitment }
+    }
+
+    /// Leaf bytes: big-endian shard index and shard count followed by the
+    /// data commitment
+    pub fn leaf_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(&self.shard_index.to_be_bytes());
+        bytes.extend_from_slice(&self.total_shards.to_be_bytes());
+        bytes.extend_from_slice(&self.data_commitment);
+        bytes
+    }
+}
+
+/// Merkle root over `shard_headers`, or `EMPTY_DA_ROOT` when there are none
+pub fn build_da_root(shard_headers: &[ShardHeader]) -> [u8; 32] {
+    MerkleTree::new(shard_headers.iter().map(ShardHeader::leaf_bytes).collect())
+        .ok()
+        .and_then(|tree| tree.root().ok())
+        .unwrap_or(EMPTY_DA_ROOT)
+}
+
+/// Check that `shard_headers`, in the order given, rebuild `claimed_root`,
+/// and that they are exactly shards `0..n` of an `n`-shard block. The index
+/// check matters because odd tree levels duplicate their last node: without
+/// it `[s0, s1, s2, s2]` rebuilds the root of `[s0, s1, s2]`.
+pub fn verify_da_root(shard_headers: &[ShardHeader], claimed_root: [u8; 32]) -> Result<(), ValidationError> {
+    let computed = build_da_root(shard_headers);
+    if computed != claimed_root {
+        return Err(ValidationError::DaRootMismatch {
+            claimed: HeaderHash(claimed_root),
+            computed: HeaderHash(computed),
+        });
+    }
+    for (position, shard) in shard_headers.iter().enumerate() {
+        if shard.shard_index as usize != position || shard.total_shards as usize != shard_headers.len() {
+            return Err(ValidationError::ShardOutOfSequence {
+                position,
+                shard_index: shard.shard_index,
+                total_shards: shard.total_shards,
+                received: shard_headers.len(),
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn shard_headers() -> Vec<ShardHeader> {
+        (0..4u8).map(|i| ShardHeader::new(i as u32, 4, [i + 10; 32])).collect()
+    }
+
+    #[test]
+    fn test_da_root_matches_shard_headers() {
+        let shards = shard_headers();
+        let root = build_da_root(&shards);
+        assert_eq!(verify_da_root(&shards, root), Ok(()));
+        assert_eq!(verify_da_root(&[], EMPTY_DA_ROOT), Ok(()));
+    }
+
+    #[test]
+    fn test_da_root_rejects_reordered_shards() {
+        let shards = shard_headers();
+        let root = build_da_root(&shards);
+        let mut reordered = shards.clone();
+        reordered.swap(1, 2);
+        assert!(matches!(
+            verify_da_root(&reordered, root),
+            Err(ValidationError::DaRootMismatch { claimed, .. }) if claimed == HeaderHash(root)
+        ));
+    }
+
+    #[test]
+    fn test_da_root_rejects_missing_shard() {
+        let shards = shard_headers();
+        let root = build_da_root(&shards);
+        assert!(verify_da_root(&shards[..3], root).is_err());
+        assert!(verify_da_root(&[], root).is_err());
+    }
+
+    #[test]
+    fn test_da_root_rejects_padded_or_mislabelled_shards() {
+        // Three shards padded with a copy of the last one rebuild the
+        // three-shard root, since odd levels duplicate their last node
+        let three: Vec<_> = (0..3u8).map(|i| ShardHeader::new(i as u32, 3, [i + 10; 32])).collect();
+        let root = build_da_root(&three);
+        assert_eq!(verify_da_root(&three, root), Ok(()));
+        let padded = [three[0], three[1], three[2], three[2]];
+        assert_eq!(build_da_root(&padded), root);
+        assert_eq!(
+            verify_da_root(&padded, root),
+            Err(ValidationError::ShardOutOfSequence { position: 0, shard_index: 0, total_shards: 3, received: 4 })
+        );
+
+        // Indices must run 0..n without gaps or repeats
+        let gapped: Vec<_> = [0u32, 1, 3].iter().map(|&i| ShardHeader::new(i, 3, [1u8; 32])).collect();
+        assert!(matches!(
+            verify_da_root(&gapped, build_da_root(&gapped)),
+            Err(ValidationError::ShardOutOfSequence { position: 2, shard_index: 3, .. })
+        ));
+
+        // And every header must agree with the number of shards received
+        let miscounted: Vec<_> = (0..2u32).map(|i| ShardHeader::new(i, 3, [1u8; 32])).collect();
+        assert!(matches!(
+            verify_da_root(&miscounted, build_da_root(&miscounted)),
+            Err(ValidationError::ShardOutOfSequence { total_shards: 3, received: 2, .. })
+        ));
+    }
+}
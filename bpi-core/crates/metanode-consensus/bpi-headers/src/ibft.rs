@@ -2,6 +2,7 @@
 
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use thiserror::Error;
 
 use crate::{Header, HeaderHash, BlsSignature, BlsPublicKey, VrfProof, VrfOutput};
 
@@ -39,6 +40,31 @@ pub enum IbftMessage {
         /// BLS signature on header hash
         signature: BlsSignature,
     },
+    /// ROUND-CHANGE message moving a stuck height to a later round
+    RoundChange(RoundChange),
+}
+
+/// Request to abandon the current round at `height` and move to `new_round`,
+/// sent when the proposer fails to drive the round to a commit
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RoundChange {
+    /// Block height
+    pub height: u64,
+    /// Round being moved to
+    pub new_round: u64,
+    /// PREPARE messages from earlier rounds at this height, if any were seen
+    pub justification: Vec<IbftMessage>,
+}
+
+/// Why a round change was rejected
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum RoundChangeError {
+    #[error("Round change for height {got} does not match height {expected}")]
+    HeightMismatch { expected: u64, got: u64 },
+    #[error("Round change to round {new_round} does not advance past round {current}")]
+    RoundNotIncreasing { current: u64, new_round: u64 },
+    #[error("Invalid round change justification: {0}")]
+    InvalidJustification(String),
 }
 
 /// IBFT commit object for finalized headers
@@ -82,15 +108,17 @@ impl IbftMessage {
             IbftMessage::PrePrepare { height, .. } => *height,
             IbftMessage::Prepare { height, .. } => *height,
             IbftMessage::Commit { height, .. } => *height,
+            IbftMessage::RoundChange(rc) => rc.height,
         }
     }
     
-    /// Get the round of this IBFT message
+    /// Get the round of this IBFT message (the target round for a ROUND-CHANGE)
     pub fn round(&self) -> u64 {
         match self {
             IbftMessage::PrePrepare { round, .. } => *round,
             IbftMessage::Prepare { round, .. } => *round,
             IbftMessage::Commit { round, .. } => *round,
+            IbftMessage::RoundChange(rc) => rc.new_round,
         }
     }
     
@@ -100,6 +128,7 @@ impl IbftMessage {
             IbftMessage::PrePrepare { header, .. } => header.hash().ok(),
             IbftMessage::Prepare { header_hash, .. } => Some(*header_hash),
             IbftMessage::Commit { header_hash, .. } => Some(*header_hash),
+            IbftMessage::RoundChange(_) => None,
         }
     }
     
@@ -117,6 +146,53 @@ impl IbftMessage {
     pub fn is_commit(&self) -> bool {
         matches!(self, IbftMessage::Commit { .. })
     }
+
+    /// Check if this is a ROUND-CHANGE message
+    pub fn is_round_change(&self) -> bool {
+        matches!(self, IbftMessage::RoundChange(_))
+    }
+}
+
+impl RoundChange {
+    /// Create a round change to `new_round` at `height`
+    pub fn new(height: u64, new_round: u64, justification: Vec<IbftMessage>) -> Self {
+        Self {
+            height,
+            new_round,
+            justification,
+        }
+    }
+
+    /// Wrap as an IBFT message for broadcast
+    pub fn to_message(&self) -> IbftMessage {
+        IbftMessage::RoundChange(self.clone())
+    }
+}
+
+/// Check a round change against `parent`, the header proposed in the round being
+/// abandoned: the height must match and the new round must be strictly later.
+/// Justification messages must be PREPAREs at the same height from earlier rounds.
+pub fn validate_round_change(parent: &Header, rc: &RoundChange) -> std::result::Result<(), RoundChangeError> {
+    if rc.height != parent.height {
+        return Err(RoundChangeError::HeightMismatch { expected: parent.height, got: rc.height });
+    }
+    if rc.new_round <= parent.round {
+        return Err(RoundChangeError::RoundNotIncreasing { current: parent.round, new_round: rc.new_round });
+    }
+
+    for message in &rc.justification {
+        if !message.is_prepare() {
+            return Err(RoundChangeError::InvalidJustification("justification must contain only PREPARE messages".to_string()));
+        }
+        if message.height() != rc.height || message.round() >= rc.new_round {
+            return Err(RoundChangeError::InvalidJustification(format!(
+                "PREPARE for height {} round {} does not precede round {} at height {}",
+                message.height(), message.round(), rc.new_round, rc.height
+            )));
+        }
+    }
+
+    Ok(())
 }
 
 impl IbftCommit {
@@ -386,6 +462,55 @@ mod tests {
         assert_eq!(pre_prepare.height(), header.height);
     }
     
+    #[test]
+    fn test_round_change_advances_round() {
+        let header = create_test_header();
+        let prepare = IbftMessage::Prepare {
+            height: 100,
+            round: 0,
+            header_hash: header.hash().unwrap(),
+        };
+
+        let rc = RoundChange::new(100, 1, vec![prepare]);
+        assert_eq!(validate_round_change(&header, &rc), Ok(()));
+
+        let message = rc.to_message();
+        assert!(message.is_round_change());
+        assert_eq!(message.height(), 100);
+        assert_eq!(message.round(), 1);
+        assert_eq!(message.header_hash(), None);
+    }
+
+    #[test]
+    fn test_round_change_rejected() {
+        let mut header = create_test_header();
+        header.round = 2;
+
+        assert_eq!(
+            validate_round_change(&header, &RoundChange::new(100, 2, vec![])),
+            Err(RoundChangeError::RoundNotIncreasing { current: 2, new_round: 2 })
+        );
+        assert_eq!(
+            validate_round_change(&header, &RoundChange::new(100, 1, vec![])),
+            Err(RoundChangeError::RoundNotIncreasing { current: 2, new_round: 1 })
+        );
+        assert_eq!(
+            validate_round_change(&header, &RoundChange::new(101, 3, vec![])),
+            Err(RoundChangeError::HeightMismatch { expected: 100, got: 101 })
+        );
+
+        // A PREPARE from the target round cannot justify moving to it
+        let prepare = IbftMessage::Prepare {
+            height: 100,
+            round: 3,
+            header_hash: header.hash().unwrap(),
+        };
+        assert!(matches!(
+            validate_round_change(&header, &RoundChange::new(100, 3, vec![prepare])),
+            Err(RoundChangeError::InvalidJustification(_))
+        ));
+    }
+
     #[test]
     fn test_commit_verification() {
         let validators = create_test_validators();
@@ -9,24 +9,33 @@
 //! - **HeaderHash**: Canonical hash computation with domain separation
 //! - **Validation**: Header validation and consistency checks
 //! - **IBFT Integration**: Support for IBFT consensus mode
+//! - **Receipts**: Receipts root construction and inclusion proofs
+//! - **XCMP**: Outbound cross-chain message root and inclusion proofs
+//! - **DA**: Data-availability root over shard headers
 
 use std::fmt;
 
 use anyhow::Result;
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SubsecRound, Utc};
 use serde::{Deserialize, Serialize};
 
 // Re-export core types
 pub use bpi_enc::{domain_hash, CanonicalCbor, domains::HEADER_HASH};
 pub use bpi_blsagg::{Signature as BlsSignature, PublicKey as BlsPublicKey};
-pub use bpi_merkle::{MerkleTree, Hash as MerkleHash};
+pub use bpi_merkle::{MerkleTree, MerkleProof, Hash as MerkleHash};
 pub use bpi_vrf::{VrfProof, VrfOutput};
 
 mod validation;
 mod ibft;
+mod receipts;
+mod xcmp;
+mod da;
 
 pub use validation::*;
 pub use ibft::*;
+pub use receipts::*;
+pub use xcmp::*;
+pub use da::*;
 
 /// Block header consensus mode
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -58,10 +67,17 @@ pub struct Header {
     pub mode: ConsensusMode,
     /// IBFT round number
     pub round: u64,
-    /// Header creation timestamp
+    /// Header creation timestamp, encoded at millisecond precision so the
+    /// canonical hash survives an encode/decode round trip
+    #[serde(with = "chrono::serde::ts_milliseconds")]
     pub timestamp: DateTime<Utc>,
 }
 
+/// Truncate a timestamp to the millisecond precision headers are encoded at
+pub fn normalize_timestamp(timestamp: DateTime<Utc>) -> DateTime<Utc> {
+    timestamp.trunc_subsecs(3)
+}
+
 /// Header hash with domain separation
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub struct HeaderHash(pub [u8; 32]);
@@ -75,6 +91,44 @@ pub struct GenesisConfig {
     pub timestamp: DateTime<Utc>,
     /// Initial validator set hash
     pub validator_set_hash: [u8; 32],
+    /// Protocol version; defaults to 1
+    #[serde(default)]
+    pub version: Option<u8>,
+    /// Consensus mode; defaults to IBFT
+    #[serde(default)]
+    pub mode: Option<ConsensusMode>,
+    /// Genesis PoH root; defaults to zero
+    #[serde(default)]
+    pub poh_root: Option<[u8; 32]>,
+}
+
+impl GenesisConfig {
+    /// Genesis with default version, mode and PoH root
+    pub fn new(chain_id: u64, timestamp: DateTime<Utc>, validator_set_hash: [u8; 32]) -> Self {
+        Self {
+            chain_id,
+            timestamp,
+            validator_set_hash,
+            version: None,
+            mode: None,
+            poh_root: None,
+        }
+    }
+
+    pub fn with_version(mut self, version: u8) -> Self {
+        self.version = Some(version);
+        self
+    }
+
+    pub fn with_mode(mut self, mode: ConsensusMode) -> Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    pub fn with_poh_root(mut self, poh_root: [u8; 32]) -> Self {
+        self.poh_root = Some(poh_root);
+        self
+    }
 }
 
 /// Header configuration for construction
@@ -106,24 +160,25 @@ impl Header {
             validator_set_hash: config.validator_set_hash,
             mode: config.mode,
             round: config.round,
-            timestamp: Utc::now(),
+            timestamp: normalize_timestamp(Utc::now()),
         }
     }
     
-    /// Create genesis header
+    /// Create genesis header, taking version, mode and PoH root from the
+    /// config where set
     pub fn genesis(config: &GenesisConfig) -> Self {
         Self {
-            version: 1,
+            version: config.version.unwrap_or(1),
             height: 0,
             prev_hash: [0u8; 32],
-            poh_root: [0u8; 32],
+            poh_root: config.poh_root.unwrap_or([0u8; 32]),
             receipts_root: [0u8; 32],
             da_root: [0u8; 32],
             xcmp_root: [0u8; 32],
             validator_set_hash: config.validator_set_hash,
-            mode: ConsensusMode::Ibft,
+            mode: config.mode.unwrap_or(ConsensusMode::Ibft),
             round: 0,
-            timestamp: config.timestamp,
+            timestamp: normalize_timestamp(config.timestamp),
         }
     }
     
@@ -135,15 +190,39 @@ impl Header {
         let hash = domain_hash(HEADER_HASH, &encoded);
         Ok(HeaderHash(hash))
     }
+
+    /// Compute the header hash bound to a specific chain, so identical headers on
+    /// different networks (e.g. testnet vs mainnet) never share a hash or signature
+    /// header_hash = H(0x10 || chain_id_be || enc(header))
+    pub fn hash_for_chain(&self, chain_id: u64) -> Result<HeaderHash> {
+        let encoded = CanonicalCbor::encode(self)
+            .map_err(|e| anyhow::anyhow!("Failed to encode header: {}", e))?;
+        let mut preimage = Vec::with_capacity(8 + encoded.len());
+        preimage.extend_from_slice(&chain_id.to_be_bytes());
+        preimage.extend_from_slice(&encoded);
+        Ok(HeaderHash(domain_hash(HEADER_HASH, &preimage)))
+    }
     
     /// Check if this is the genesis block
     pub fn is_genesis(&self) -> bool {
         self.height == 0
     }
+
+    /// Validate a header meant to start a chain: height 0, zero prev_hash,
+    /// and the usual header constraints
+    pub fn validate_genesis(&self) -> Result<()> {
+        if self.height != 0 {
+            return Err(anyhow::anyhow!("Genesis block must have height 0, got {}", self.height));
+        }
+        if self.prev_hash != [0u8; 32] {
+            return Err(anyhow::anyhow!("Genesis block must have zero prev_hash"));
+        }
+        self.validate()
+    }
     
     /// Check if header has empty receipts root (no transactions)
     pub fn is_empty_receipts(&self) -> bool {
-        self.receipts_root == [0u8; 32]
+        self.receipts_root == EMPTY_RECEIPTS_ROOT
     }
     
     /// Validate header structure and constraints
@@ -251,6 +330,7 @@ impl AsRef<[u8]> for HeaderHash {
 mod tests {
     use super::*;
     use chrono::TimeZone;
+    use proptest::prelude::*;
     
     fn create_test_header() -> Header {
         Header::new(HeaderConfig {
@@ -282,6 +362,9 @@ mod tests {
             timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             validator_set_hash: [7u8; 32],
             chain_id: 1,
+            version: None,
+            mode: None,
+            poh_root: None,
         };
         
         let genesis = Header::genesis(&config);
@@ -292,6 +375,47 @@ mod tests {
         assert_eq!(genesis.validator_set_hash, [7u8; 32]);
     }
     
+    #[test]
+    fn test_default_genesis_from_config() {
+        let config = GenesisConfig::new(1, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(), [7u8; 32]);
+        let genesis = Header::genesis(&config);
+
+        assert_eq!(genesis.version, 1);
+        assert_eq!(genesis.mode, ConsensusMode::Ibft);
+        assert_eq!(genesis.poh_root, [0u8; 32]);
+        assert!(genesis.validate_genesis().is_ok());
+        assert!(HeaderValidator::new().validate_header(&genesis).is_valid);
+    }
+
+    #[test]
+    fn test_customized_genesis_from_config() {
+        let config = GenesisConfig::new(7, Utc.with_ymd_and_hms(2024, 6, 1, 0, 0, 0).unwrap(), [8u8; 32])
+            .with_version(1)
+            .with_mode(ConsensusMode::Ibft)
+            .with_poh_root([9u8; 32]);
+        let genesis = Header::genesis(&config);
+
+        assert_eq!(genesis.validator_set_hash, [8u8; 32]);
+        assert_eq!(genesis.poh_root, [9u8; 32]);
+        assert!(genesis.validate_genesis().is_ok());
+        let result = HeaderValidator::new().validate_header(&genesis);
+        assert!(result.is_valid, "{}", result.format_issues());
+
+        // A version the header rules reject fails validation
+        let bad = Header::genesis(&config.with_version(2));
+        assert!(bad.validate_genesis().is_err());
+    }
+
+    #[test]
+    fn test_validate_genesis_rejects_non_genesis() {
+        let header = create_test_header();
+        assert!(header.validate_genesis().is_err());
+
+        let mut genesis = Header::genesis(&GenesisConfig::new(1, Utc::now(), [7u8; 32]));
+        genesis.prev_hash = [1u8; 32];
+        assert!(genesis.validate_genesis().is_err());
+    }
+
     #[test]
     fn test_header_hash() {
         let header = create_test_header();
@@ -327,6 +451,9 @@ mod tests {
             timestamp: Utc::now(),
             validator_set_hash: [7u8; 32],
             chain_id: 1,
+            version: None,
+            mode: None,
+            poh_root: None,
         };
         
         let mut genesis = Header::genesis(&config);
@@ -350,6 +477,9 @@ mod tests {
             timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             validator_set_hash: [7u8; 32],
             chain_id: 1,
+            version: None,
+            mode: None,
+            poh_root: None,
         };
         
         let genesis = Header::genesis(&config);
@@ -388,6 +518,17 @@ mod tests {
         assert!(next_header.validate_chain_continuity(&genesis).is_err());
     }
     
+    #[test]
+    fn test_header_hash_for_chain_separates_networks() {
+        let header = create_test_header();
+        let mainnet = header.hash_for_chain(1).unwrap();
+        let testnet = header.hash_for_chain(1337).unwrap();
+
+        assert_ne!(mainnet, testnet);
+        assert_eq!(mainnet, header.clone().hash_for_chain(1).unwrap());
+        assert_ne!(mainnet, header.hash().unwrap());
+    }
+
     #[test]
     fn test_header_hash_display() {
         let header = create_test_header();
@@ -412,4 +553,55 @@ mod tests {
         let encoded2 = CanonicalCbor::encode(&header).unwrap();
         assert_eq!(encoded, encoded2);
     }
+    
+    #[test]
+    fn test_header_timestamp_normalized() {
+        let header = create_test_header();
+        assert_eq!(header.timestamp, normalize_timestamp(header.timestamp));
+        assert_eq!(header.timestamp.timestamp_subsec_nanos() % 1_000_000, 0);
+    }
+    
+    proptest! {
+        #[test]
+        fn prop_header_round_trip_hash_stable(
+            height in any::<u64>(),
+            round in any::<u64>(),
+            prev_hash in any::<[u8; 32]>(),
+            poh_root in any::<[u8; 32]>(),
+            receipts_root in any::<[u8; 32]>(),
+            validator_set_hash in any::<[u8; 32]>(),
+            millis in 0i64..4_102_444_800_000, // Up to 2100-01-01
+        ) {
+            let mut header = Header::new(HeaderConfig {
+                version: 1,
+                height,
+                prev_hash,
+                poh_root,
+                receipts_root,
+                da_root: [4u8; 32],
+                xcmp_root: [5u8; 32],
+                validator_set_hash,
+                mode: ConsensusMode::Ibft,
+                round,
+            });
+            header.timestamp = Utc.timestamp_millis_opt(millis).unwrap();
+            
+            let encoded = CanonicalCbor::encode(&header).unwrap();
+            let decoded = Header::from_canonical_cbor(&encoded).unwrap();
+            prop_assert_eq!(&decoded, &header);
+            prop_assert_eq!(decoded.hash().unwrap(), header.hash().unwrap());
+        }
+        
+        #[test]
+        fn prop_sub_millisecond_timestamp_hash_stable(nanos in 0u32..1_000_000_000) {
+            let mut header = create_test_header();
+            header.timestamp = Utc.timestamp_opt(1_700_000_000, nanos).unwrap();
+            
+            // Sub-millisecond precision is dropped on encode, so the hash still matches
+            let encoded = CanonicalCbor::encode(&header).unwrap();
+            let decoded = Header::from_canonical_cbor(&encoded).unwrap();
+            prop_assert_eq!(decoded.timestamp, normalize_timestamp(header.timestamp));
+            prop_assert_eq!(decoded.hash().unwrap(), header.hash().unwrap());
+        }
+    }
 }
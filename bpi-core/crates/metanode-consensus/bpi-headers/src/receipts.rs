@@ -0,0 +1,97 @@
+//! Receipts root construction and inclusion proofs
+//!
+//! `Header.receipts_root` is the Merkle root over the block's DockLock record
+//! hashes, in block order. A block without receipts has the all-zero root.
+
+use anyhow::Result;
+
+use crate::{domain_hash, MerkleProof, MerkleTree};
+use bpi_enc::domains::MERKLE_LEAF;
+
+/// Receipts root of a block with no DockLock records
+pub const EMPTY_RECEIPTS_ROOT: [u8; 32] = [0u8; 32];
+
+fn receipts_tree(records: &[[u8; 32]]) -> Result<MerkleTree> {
+    MerkleTree::new(records.iter().map(|record| record.to_vec()).collect())
+        .map_err(|e| anyhow::anyhow!("Failed to build receipts tree: {}", e))
+}
+
+/// Merkle root over `records`, or `EMPTY_RECEIPTS_ROOT` when there are none
+pub fn build_receipts_root(records: &[[u8; 32]]) -> [u8; 32] {
+    if records.is_empty() {
+        return EMPTY_RECEIPTS_ROOT;
+    }
+    receipts_tree(records)
+        .ok()
+        .and_then(|tree| tree.root().ok())
+        .expect("non-empty receipts tree has a root")
+}
+
+/// Proof that `records[index]` is included under `build_receipts_root(records)`
+pub fn prove_receipt_inclusion(records: &[[u8; 32]], index: usize) -> Result<MerkleProof> {
+    if index >= records.len() {
+        return Err(anyhow::anyhow!("Receipt index {} out of range for {} records", index, records.len()));
+    }
+    Ok(receipts_tree(records)?.proof(index)?)
+}
+
+/// Check that `proof` shows `record` under a header's receipts root. Nothing
+/// is included under the empty root.
+pub fn verify_receipt_inclusion(root: [u8; 32], record: &[u8; 32], proof: &MerkleProof) -> bool {
+    root != EMPTY_RECEIPTS_ROOT
+        && proof.leaf_hash == domain_hash(MERKLE_LEAF, record)
+        && proof.verify(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bpi_enc::domains;
+
+    fn records(count: u8) -> Vec<[u8; 32]> {
+        (0..count).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn test_receipts_root_deterministic() {
+        let records = records(5);
+        assert_eq!(build_receipts_root(&records), build_receipts_root(&records));
+        assert_ne!(build_receipts_root(&records), build_receipts_root(&records[..4]));
+        assert_ne!(build_receipts_root(&records), EMPTY_RECEIPTS_ROOT);
+        assert_eq!(build_receipts_root(&[]), EMPTY_RECEIPTS_ROOT);
+    }
+
+    #[test]
+    fn test_receipt_inclusion_proof() {
+        let records = records(5);
+        let root = build_receipts_root(&records);
+        for index in 0..records.len() {
+            let proof = prove_receipt_inclusion(&records, index).unwrap();
+            assert_eq!(proof.leaf_hash, domain_hash(domains::MERKLE_LEAF, &records[index]));
+            assert!(verify_receipt_inclusion(root, &records[index], &proof));
+        }
+        assert!(prove_receipt_inclusion(&records, 5).is_err());
+        assert!(prove_receipt_inclusion(&[], 0).is_err());
+    }
+
+    #[test]
+    fn test_tampered_receipt_proof_rejected() {
+        let records = records(5);
+        let root = build_receipts_root(&records);
+        let proof = prove_receipt_inclusion(&records, 2).unwrap();
+
+        // A valid proof does not vouch for a different record
+        assert!(!verify_receipt_inclusion(root, &records[3], &proof));
+
+        let mut wrong_leaf = proof.clone();
+        wrong_leaf.leaf_hash = domain_hash(domains::MERKLE_LEAF, &[9u8; 32]);
+        assert!(!verify_receipt_inclusion(root, &[9u8; 32], &wrong_leaf));
+
+        let mut wrong_sibling = proof.clone();
+        wrong_sibling.siblings[0].0[0] ^= 1;
+        assert!(!verify_receipt_inclusion(root, &records[2], &wrong_sibling));
+
+        assert!(!verify_receipt_inclusion(build_receipts_root(&records[..4]), &records[2], &proof));
+        assert!(!verify_receipt_inclusion(EMPTY_RECEIPTS_ROOT, &records[2], &proof));
+    }
+}
@@ -1,14 +1,18 @@
 //! Header validation utilities and chain validation logic
 
-use chrono::{Utc, Duration};
+use chrono::{DateTime, Utc, Duration};
+use thiserror::Error;
 
-use crate::Header;
+use crate::{Header, HeaderHash};
 
 /// Header validation configuration
 #[derive(Debug, Clone)]
 pub struct ValidationConfig {
     /// Maximum allowed timestamp drift from current time
     pub max_timestamp_drift: Duration,
+    /// How far behind current time a header may be; `None` only warns, so
+    /// historical headers can still be validated during sync
+    pub max_past_drift: Option<Duration>,
     /// Minimum time between blocks
     pub min_block_time: Duration,
     /// Maximum time between blocks
@@ -21,6 +25,7 @@ impl Default for ValidationConfig {
     fn default() -> Self {
         Self {
             max_timestamp_drift: Duration::minutes(5),
+            max_past_drift: None,
             min_block_time: Duration::seconds(1),
             max_block_time: Duration::minutes(10),
             strict_timestamps: true,
@@ -33,6 +38,39 @@ pub struct HeaderValidator {
     config: ValidationConfig,
 }
 
+/// Why a header was rejected by a `HeaderVerifier`
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum ValidationError {
+    #[error("Invalid header: {0}")]
+    InvalidHeader(String),
+    #[error("Unexpected height: expected {expected}, got {got}")]
+    HeightMismatch { expected: u64, got: u64 },
+    #[error("Header at height {height} does not link to the accepted tip {expected}")]
+    PrevHashMismatch { height: u64, expected: HeaderHash },
+    #[error("Timestamp not monotonic at height {0}")]
+    NonMonotonicTimestamp(u64),
+    #[error("Header timestamp {drift_secs}s ahead of now exceeds the {max_secs}s limit")]
+    TimestampTooFarInFuture { drift_secs: i64, max_secs: i64 },
+    #[error("Header timestamp {drift_secs}s behind now exceeds the {max_secs}s limit")]
+    TimestampTooOld { drift_secs: i64, max_secs: i64 },
+    #[error("Shard headers rebuild DA root {computed}, header claims {claimed}")]
+    DaRootMismatch { claimed: HeaderHash, computed: HeaderHash },
+    #[error("Shard header at position {position} is shard {shard_index} of {total_shards}, but {received} shard headers were received")]
+    ShardOutOfSequence { position: usize, shard_index: u32, total_shards: u32, received: usize },
+    #[error("Failed to hash header: {0}")]
+    Encoding(String),
+}
+
+/// Streaming header verifier for sync: starting from a trusted anchor, accepts
+/// headers one at a time as they arrive, keeping only the current tip
+#[derive(Debug, Clone)]
+pub struct HeaderVerifier {
+    height: u64,
+    tip: HeaderHash,
+    /// Unknown for the trusted anchor, which is given only by height and hash
+    tip_timestamp: Option<DateTime<Utc>>,
+}
+
 /// Validation result with detailed error information
 #[derive(Debug, Clone)]
 pub struct ValidationResult {
@@ -44,6 +82,78 @@ pub struct ValidationResult {
     pub warnings: Vec<String>,
 }
 
+/// Reject a header timestamped more than `max_future_drift` ahead of `now`
+/// or more than `max_past_drift` behind it
+pub fn validate_timestamp(
+    header: &Header,
+    now: DateTime<Utc>,
+    max_future_drift: Duration,
+    max_past_drift: Duration,
+) -> Result<(), ValidationError> {
+    let drift = header.timestamp.signed_duration_since(now);
+    if drift > max_future_drift {
+        return Err(ValidationError::TimestampTooFarInFuture {
+            drift_secs: drift.num_seconds(),
+            max_secs: max_future_drift.num_seconds(),
+        });
+    }
+    if -drift > max_past_drift {
+        return Err(ValidationError::TimestampTooOld {
+            drift_secs: (-drift).num_seconds(),
+            max_secs: max_past_drift.num_seconds(),
+        });
+    }
+    Ok(())
+}
+
+impl HeaderVerifier {
+    /// Start verifying from a trusted `(height, hash)` anchor
+    pub fn new(anchor_height: u64, anchor_hash: HeaderHash) -> Self {
+        Self {
+            height: anchor_height,
+            tip: anchor_hash,
+            tip_timestamp: None,
+        }
+    }
+
+    /// Accept the next header if it extends the current tip, returning its hash.
+    /// A rejected header leaves the verifier unchanged so sync can resume.
+    pub fn push(&mut self, header: Header) -> Result<HeaderHash, ValidationError> {
+        header.validate()
+            .map_err(|e| ValidationError::InvalidHeader(e.to_string()))?;
+
+        let expected = self.height + 1;
+        if header.height != expected {
+            return Err(ValidationError::HeightMismatch { expected, got: header.height });
+        }
+        if header.prev_hash != self.tip.0 {
+            return Err(ValidationError::PrevHashMismatch { height: header.height, expected: self.tip });
+        }
+        if let Some(tip_timestamp) = self.tip_timestamp {
+            if header.timestamp <= tip_timestamp {
+                return Err(ValidationError::NonMonotonicTimestamp(header.height));
+            }
+        }
+
+        let hash = header.hash()
+            .map_err(|e| ValidationError::Encoding(e.to_string()))?;
+        self.height = header.height;
+        self.tip = hash;
+        self.tip_timestamp = Some(header.timestamp);
+        Ok(hash)
+    }
+
+    /// Height of the last accepted header
+    pub fn height(&self) -> u64 {
+        self.height
+    }
+
+    /// Hash of the last accepted header
+    pub fn tip(&self) -> HeaderHash {
+        self.tip
+    }
+}
+
 impl HeaderValidator {
     /// Create a new header validator with default configuration
     pub fn new() -> Self {
@@ -73,7 +183,7 @@ impl HeaderValidator {
         
         // Timestamp validation
         if self.config.strict_timestamps {
-            self.validate_timestamp(header, &mut result);
+            self.check_timestamp(header, Utc::now(), &mut result);
         }
         
         result
@@ -135,21 +245,16 @@ impl HeaderValidator {
         result
     }
     
-    fn validate_timestamp(&self, header: &Header, result: &mut ValidationResult) {
-        let now = Utc::now();
-        let timestamp_diff = header.timestamp.signed_duration_since(now);
-        
-        // Check if timestamp is too far in the future
-        if timestamp_diff > self.config.max_timestamp_drift {
-            result.errors.push(format!(
-                "Header timestamp too far in future: {} > {} seconds",
-                timestamp_diff.num_seconds(),
-                self.config.max_timestamp_drift.num_seconds()
-            ));
+    fn check_timestamp(&self, header: &Header, now: DateTime<Utc>, result: &mut ValidationResult) {
+        let max_past_drift = self.config.max_past_drift.unwrap_or(Duration::MAX);
+        if let Err(e) = validate_timestamp(header, now, self.config.max_timestamp_drift, max_past_drift) {
+            result.errors.push(e.to_string());
             result.is_valid = false;
+            return;
         }
         
-        // Check if timestamp is too far in the past (warning only)
+        // Old but within any configured bound: warning only
+        let timestamp_diff = header.timestamp.signed_duration_since(now);
         if timestamp_diff < -self.config.max_timestamp_drift {
             result.warnings.push(format!(
                 "Header timestamp significantly in past: {} seconds ago",
@@ -249,6 +354,9 @@ mod tests {
             timestamp: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
             validator_set_hash: [7u8; 32],
             chain_id: 1,
+            version: None,
+            mode: None,
+            poh_root: None,
         };
         Header::genesis(&config)
     }
@@ -278,6 +386,7 @@ mod tests {
         
         let custom_config = ValidationConfig {
             max_timestamp_drift: Duration::minutes(1),
+            max_past_drift: Some(Duration::hours(1)),
             min_block_time: Duration::seconds(5),
             max_block_time: Duration::minutes(1),
             strict_timestamps: false,
@@ -296,6 +405,52 @@ mod tests {
         assert!(result.errors.is_empty());
     }
     
+    #[test]
+    fn test_timestamp_within_window() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        for offset in [Duration::seconds(-30), Duration::zero(), Duration::seconds(30)] {
+            let header = create_test_header(1, [1u8; 32], now + offset);
+            assert_eq!(validate_timestamp(&header, now, Duration::minutes(1), Duration::minutes(1)), Ok(()));
+        }
+    }
+
+    #[test]
+    fn test_timestamp_too_far_in_future() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let header = create_test_header(1, [1u8; 32], now + Duration::minutes(2));
+        assert_eq!(
+            validate_timestamp(&header, now, Duration::minutes(1), Duration::hours(1)),
+            Err(ValidationError::TimestampTooFarInFuture { drift_secs: 120, max_secs: 60 })
+        );
+
+        // Rejected by the main entry point too
+        let validator = HeaderValidator::new();
+        let future = create_test_header(1, [1u8; 32], Utc::now() + Duration::hours(1));
+        assert!(!validator.validate_header(&future).is_valid);
+    }
+
+    #[test]
+    fn test_timestamp_too_old() {
+        let now = Utc.with_ymd_and_hms(2024, 1, 1, 12, 0, 0).unwrap();
+        let header = create_test_header(1, [1u8; 32], now - Duration::hours(2));
+        assert_eq!(
+            validate_timestamp(&header, now, Duration::minutes(1), Duration::hours(1)),
+            Err(ValidationError::TimestampTooOld { drift_secs: 7200, max_secs: 3600 })
+        );
+
+        // Only a warning unless the validator bounds the past drift
+        let stale = create_test_header(1, [1u8; 32], Utc::now() - Duration::hours(2));
+        let lenient = HeaderValidator::new().validate_header(&stale);
+        assert!(lenient.is_valid);
+        assert!(!lenient.warnings.is_empty());
+
+        let strict = HeaderValidator::with_config(ValidationConfig {
+            max_past_drift: Some(Duration::hours(1)),
+            ..ValidationConfig::default()
+        });
+        assert!(!strict.validate_header(&stale).is_valid);
+    }
+
     #[test]
     fn test_chain_validation() {
         let validator = HeaderValidator::new();
@@ -333,6 +488,67 @@ mod tests {
         assert!(!result.errors.is_empty());
     }
     
+    fn header_chain(count: u64) -> Vec<Header> {
+        let genesis = create_test_genesis();
+        let mut headers = vec![genesis];
+        for height in 1..=count {
+            let prev = headers.last().unwrap();
+            let timestamp = prev.timestamp + Duration::seconds(5);
+            headers.push(create_test_header(height, prev.hash().unwrap().0, timestamp));
+        }
+        headers
+    }
+
+    #[test]
+    fn test_header_verifier_accepts_valid_sequence() {
+        let headers = header_chain(5);
+        let mut verifier = HeaderVerifier::new(0, headers[0].hash().unwrap());
+
+        for header in &headers[1..] {
+            let hash = verifier.push(header.clone()).unwrap();
+            assert_eq!(hash, header.hash().unwrap());
+        }
+        assert_eq!(verifier.height(), 5);
+        assert_eq!(verifier.tip(), headers[5].hash().unwrap());
+    }
+
+    #[test]
+    fn test_header_verifier_rejects_fork() {
+        let headers = header_chain(2);
+        let mut verifier = HeaderVerifier::new(0, headers[0].hash().unwrap());
+        verifier.push(headers[1].clone()).unwrap();
+
+        // Same height, but building on something other than the accepted tip
+        let fork = create_test_header(2, [9u8; 32], headers[2].timestamp);
+        assert!(matches!(
+            verifier.push(fork),
+            Err(ValidationError::PrevHashMismatch { height: 2, .. })
+        ));
+
+        // Skipping a height is rejected too
+        let skip = create_test_header(3, headers[2].hash().unwrap().0, headers[2].timestamp + Duration::seconds(5));
+        assert_eq!(
+            verifier.push(skip),
+            Err(ValidationError::HeightMismatch { expected: 2, got: 3 })
+        );
+    }
+
+    #[test]
+    fn test_header_verifier_resumes_after_error() {
+        let headers = header_chain(3);
+        let mut verifier = HeaderVerifier::new(0, headers[0].hash().unwrap());
+        verifier.push(headers[1].clone()).unwrap();
+
+        let stale = create_test_header(2, headers[1].hash().unwrap().0, headers[1].timestamp);
+        assert_eq!(verifier.push(stale), Err(ValidationError::NonMonotonicTimestamp(2)));
+        assert_eq!(verifier.height(), 1);
+        assert_eq!(verifier.tip(), headers[1].hash().unwrap());
+
+        verifier.push(headers[2].clone()).unwrap();
+        verifier.push(headers[3].clone()).unwrap();
+        assert_eq!(verifier.height(), 3);
+    }
+
     #[test]
     fn test_validation_result_merge() {
         let mut result1 = ValidationResult::success();
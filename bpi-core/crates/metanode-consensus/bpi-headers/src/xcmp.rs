@@ -0,0 +1,109 @@
+//! Outbound cross-chain message queue root and inclusion proofs
+//!
+//! `Header.xcmp_root` is the Merkle root over the block's outbound messages,
+//! in queue order. A block that sends nothing has the all-zero root.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+
+use crate::{domain_hash, MerkleProof, MerkleTree};
+use bpi_enc::domains::MERKLE_LEAF;
+
+/// XCMP root of a block with no outbound messages
+pub const EMPTY_XCMP_ROOT: [u8; 32] = [0u8; 32];
+
+/// Outbound cross-chain message as committed to by `xcmp_root`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct XcmpMessage {
+    /// Chain the message is addressed to
+    pub dest_chain_id: u64,
+    /// Hash of the message payload
+    pub payload_hash: [u8; 32],
+}
+
+impl XcmpMessage {
+    pub fn new(dest_chain_id: u64, payload_hash: [u8; 32]) -> Self {
+        Self { dest_chain_id, payload_hash }
+    }
+
+    /// Leaf bytes: big-endian destination chain id followed by the payload hash
+    pub fn leaf_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(40);
+        bytes.extend_from_slice(&self.dest_chain_id.to_be_bytes());
+        bytes.extend_from_slice(&self.payload_hash);
+        bytes
+    }
+}
+
+fn xcmp_tree(messages: &[XcmpMessage]) -> Result<MerkleTree> {
+    MerkleTree::new(messages.iter().map(XcmpMessage::leaf_bytes).collect())
+        .map_err(|e| anyhow::anyhow!("Failed to build XCMP tree: {}", e))
+}
+
+/// Merkle root over `messages`, or `EMPTY_XCMP_ROOT` when there are none
+pub fn build_xcmp_root(messages: &[XcmpMessage]) -> [u8; 32] {
+    if messages.is_empty() {
+        return EMPTY_XCMP_ROOT;
+    }
+    xcmp_tree(messages)
+        .ok()
+        .and_then(|tree| tree.root().ok())
+        .expect("non-empty XCMP tree has a root")
+}
+
+/// Proof that `messages[index]` is included under `build_xcmp_root(messages)`
+pub fn prove_xcmp_inclusion(messages: &[XcmpMessage], index: usize) -> Result<MerkleProof> {
+    if index >= messages.len() {
+        return Err(anyhow::anyhow!("XCMP message index {} out of range for {} messages", index, messages.len()));
+    }
+    Ok(xcmp_tree(messages)?.proof(index)?)
+}
+
+/// Check that `proof` shows `message` under a header's XCMP root
+pub fn verify_xcmp_inclusion(root: [u8; 32], message: &XcmpMessage, proof: &MerkleProof) -> bool {
+    root != EMPTY_XCMP_ROOT
+        && proof.leaf_hash == domain_hash(MERKLE_LEAF, &message.leaf_bytes())
+        && proof.verify(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn messages() -> Vec<XcmpMessage> {
+        (0..6u8).map(|i| XcmpMessage::new(100 + i as u64 % 2, [i; 32])).collect()
+    }
+
+    #[test]
+    fn test_xcmp_root_deterministic() {
+        let messages = messages();
+        assert_eq!(build_xcmp_root(&messages), build_xcmp_root(&messages));
+        assert_eq!(build_xcmp_root(&[]), EMPTY_XCMP_ROOT);
+
+        // The destination is committed to, not just the payload
+        let mut redirected = messages.clone();
+        redirected[0].dest_chain_id = 999;
+        assert_ne!(build_xcmp_root(&redirected), build_xcmp_root(&messages));
+    }
+
+    #[test]
+    fn test_xcmp_inclusion_proof() {
+        let messages = messages();
+        let root = build_xcmp_root(&messages);
+        let proof = prove_xcmp_inclusion(&messages, 3).unwrap();
+        assert!(verify_xcmp_inclusion(root, &messages[3], &proof));
+
+        // The proof is for that message only
+        assert!(!verify_xcmp_inclusion(root, &messages[2], &proof));
+        assert!(prove_xcmp_inclusion(&messages, messages.len()).is_err());
+    }
+
+    #[test]
+    fn test_xcmp_proof_rejected_against_wrong_root() {
+        let messages = messages();
+        let proof = prove_xcmp_inclusion(&messages, 1).unwrap();
+        let other_root = build_xcmp_root(&messages[..5]);
+        assert!(!verify_xcmp_inclusion(other_root, &messages[1], &proof));
+        assert!(!verify_xcmp_inclusion(EMPTY_XCMP_ROOT, &messages[1], &proof));
+    }
+}
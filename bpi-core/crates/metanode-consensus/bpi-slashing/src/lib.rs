@@ -5,8 +5,11 @@
 //! It detects when validators commit to conflicting blocks at the same height/round and generates
 //! minimal proofs that can be verified by light clients.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 use anyhow::Result;
+use async_trait::async_trait;
+use tokio::sync::mpsc;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -28,6 +31,8 @@ pub enum SlashingError {
     InvalidValidatorIndex(usize),
     #[error("Signature verification failed for validator {0}")]
     SignatureVerificationFailed(usize),
+    #[error("Commit does not carry a signature share for validator {0}")]
+    MissingShare(usize),
     #[error("Commits are not conflicting")]
     CommitsNotConflicting,
     #[error("Commits are from different heights")]
@@ -38,10 +43,14 @@ pub enum SlashingError {
     ValidatorNotInSet(usize),
     #[error("Encoding error: {0}")]
     EncodingError(String),
+    #[error("Unsupported evidence export version: {0}")]
+    UnsupportedVersion(String),
+    #[error("Proof validator set {proof} does not match known set {known}")]
+    ValidatorSetMismatch { known: String, proof: String },
 }
 
 /// Type of equivocation detected
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum EquivocationType {
     /// Validator signed two different blocks at same height/round
     DoubleCommit,
@@ -49,6 +58,9 @@ pub enum EquivocationType {
     HeightViolation,
     /// Validator signed multiple times in same round
     MultipleSignatures,
+    /// Validator committed to a header that conflicts with a finalized one,
+    /// either at the finalized height or building on a different parent
+    FinalityViolation,
 }
 
 /// Evidence of validator equivocation
@@ -68,6 +80,58 @@ pub struct EquivocationEvidence {
     pub height: u64,
     /// Round at which equivocation occurred
     pub round: u64,
+    /// Header committed to by `commit_b` when a finality violation builds on
+    /// a parent other than the finalized `commit_a`; the verifier checks its
+    /// parent link
+    #[serde(default)]
+    pub child_header: Option<Header>,
+}
+
+/// How serious an equivocation is, for scaling the penalty
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SlashingSeverity {
+    /// Misbehaviour that cannot by itself fork the chain
+    Minor,
+    /// Conflicting votes on a height that is not yet final
+    Major,
+    /// Conflicting votes against finalized history
+    Critical,
+}
+
+/// Share of stake slashed per severity, in basis points (10_000 = all of it)
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SlashingSchedule {
+    pub minor_bps: u16,
+    pub major_bps: u16,
+    pub critical_bps: u16,
+}
+
+impl Default for SlashingSchedule {
+    fn default() -> Self {
+        Self {
+            minor_bps: 100,
+            major_bps: 1_000,
+            critical_bps: 10_000,
+        }
+    }
+}
+
+impl SlashingSchedule {
+    /// Stake to slash for an offense of `severity`; rates above 10_000 bps are
+    /// capped so the penalty never exceeds `stake`
+    pub fn suggested_penalty(&self, stake: u64, severity: SlashingSeverity) -> u64 {
+        let bps = match severity {
+            SlashingSeverity::Minor => self.minor_bps,
+            SlashingSeverity::Major => self.major_bps,
+            SlashingSeverity::Critical => self.critical_bps,
+        }.min(10_000);
+        (stake as u128 * bps as u128 / 10_000) as u64
+    }
+}
+
+/// Penalty for `severity` under the default `SlashingSchedule`
+pub fn suggested_penalty(stake: u64, severity: SlashingSeverity) -> u64 {
+    SlashingSchedule::default().suggested_penalty(stake, severity)
 }
 
 /// Proof that a validator signed a specific commit
@@ -98,6 +162,73 @@ pub struct SlashingProof {
     pub proof_hash: [u8; 32],
 }
 
+/// Heights below the highest processed height that the detector keeps commits for
+pub const DEFAULT_MAX_HEIGHT_WINDOW: u64 = 1000;
+
+/// Evidence items kept per validator before further evidence is coalesced
+pub const DEFAULT_MAX_EVIDENCE_PER_VALIDATOR: usize = 64;
+
+/// Add `item` to `store` unless its validator already holds `cap` items. At the
+/// cap, `item` replaces the oldest of that validator's least severe items if it
+/// is strictly more severe, and is discarded otherwise. Returns whether `item` was kept.
+fn push_capped<T>(
+    store: &mut Vec<T>,
+    item: T,
+    cap: usize,
+    validator_of: impl Fn(&T) -> usize,
+    severity_of: impl Fn(&T) -> SlashingSeverity,
+) -> bool {
+    let validator = validator_of(&item);
+    let held: Vec<usize> = store.iter().enumerate()
+        .filter(|(_, held)| validator_of(held) == validator)
+        .map(|(index, _)| index)
+        .collect();
+    if held.len() < cap.max(1) {
+        store.push(item);
+        return true;
+    }
+
+    let weakest = held.into_iter()
+        .min_by_key(|&index| severity_of(&store[index]))
+        .expect("validator at the cap holds evidence");
+    if severity_of(&item) <= severity_of(&store[weakest]) {
+        return false;
+    }
+    store.remove(weakest);
+    store.push(item);
+    true
+}
+
+/// Destination for slashing proofs produced by an `EquivocationDetector`
+#[async_trait]
+pub trait SlashingSink: Send + Sync + std::fmt::Debug {
+    /// Submit a newly generated slashing proof
+    async fn submit(&self, proof: SlashingProof);
+}
+
+/// Slashing sink that forwards proofs onto an mpsc channel
+#[derive(Debug, Clone)]
+pub struct ChannelSink {
+    sender: mpsc::UnboundedSender<SlashingProof>,
+}
+
+impl ChannelSink {
+    /// Create a sink and the receiver its proofs arrive on
+    pub fn new() -> (Self, mpsc::UnboundedReceiver<SlashingProof>) {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        (Self { sender }, receiver)
+    }
+}
+
+#[async_trait]
+impl SlashingSink for ChannelSink {
+    async fn submit(&self, proof: SlashingProof) {
+        if self.sender.send(proof).is_err() {
+            tracing::warn!("Slashing proof dropped: channel receiver closed");
+        }
+    }
+}
+
 /// Equivocation detector for identifying Byzantine behavior
 #[derive(Debug)]
 pub struct EquivocationDetector {
@@ -107,6 +238,39 @@ pub struct EquivocationDetector {
     commit_history: HashMap<(usize, u64, u64), BlsCommit>,
     /// Detected equivocations
     detected_equivocations: Vec<EquivocationEvidence>,
+    /// Offenses already reported, keyed by (validator, height, round, type)
+    reported_equivocations: HashSet<(usize, u64, u64, EquivocationType)>,
+    /// Where slashing proofs for new evidence are submitted, if anywhere
+    sink: Option<Arc<dyn SlashingSink>>,
+    /// Queue feeding the worker task that submits proofs to `sink` one at a
+    /// time, in detection order; started on first submission
+    sink_queue: Option<mpsc::UnboundedSender<SlashingProof>>,
+    /// Commits more than this many heights below `highest_height` are pruned
+    max_height_window: u64,
+    /// Detected equivocations kept per validator; beyond it only the most severe are kept
+    max_evidence_per_validator: usize,
+    /// Highest commit height processed so far
+    highest_height: u64,
+    /// Commits that finalized a header, by height
+    finalized_commits: HashMap<u64, BlsCommit>,
+}
+
+/// Outcome of re-deriving an export's equivocations with `EquivocationDetector::replay_evidence`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ReplayReport {
+    /// Ids of evidence the detector independently re-derived
+    pub confirmed: Vec<String>,
+    /// Ids of equivocation evidence the detector did not reproduce
+    pub unconfirmed: Vec<String>,
+    /// Evidence of other types, which replay does not cover
+    pub skipped: usize,
+}
+
+impl ReplayReport {
+    /// Whether every equivocation in the export was re-derived
+    pub fn all_confirmed(&self) -> bool {
+        self.unconfirmed.is_empty()
+    }
 }
 
 /// Slashing proof verifier for light clients
@@ -123,13 +287,46 @@ impl EquivocationDetector {
             validator_set,
             commit_history: HashMap::new(),
             detected_equivocations: Vec::new(),
+            reported_equivocations: HashSet::new(),
+            sink: None,
+            sink_queue: None,
+            max_height_window: DEFAULT_MAX_HEIGHT_WINDOW,
+            max_evidence_per_validator: DEFAULT_MAX_EVIDENCE_PER_VALIDATOR,
+            highest_height: 0,
+            finalized_commits: HashMap::new(),
         }
     }
 
+    /// Keep commit history only for the `window` heights below the highest processed one
+    pub fn with_max_height_window(mut self, window: u64) -> Self {
+        self.max_height_window = window;
+        self
+    }
+
+    /// Keep at most `max` detected equivocations per validator. Once a validator
+    /// reaches it, new evidence only displaces less severe evidence, so a flood
+    /// of evidence against one validator cannot grow memory without bound.
+    pub fn with_max_evidence_per_validator(mut self, max: usize) -> Self {
+        self.max_evidence_per_validator = max;
+        self
+    }
+
+    /// Create a detector that submits a slashing proof to `sink` for every new piece of evidence
+    pub fn new_with_sink(validator_set: ValidatorSet, sink: Arc<dyn SlashingSink>) -> Self {
+        let mut detector = Self::new(validator_set);
+        detector.sink = Some(sink);
+        detector
+    }
+
     /// Process a new commit and detect any equivocations
     pub fn process_commit(&mut self, commit: &BlsCommit) -> Result<Vec<EquivocationEvidence>, SlashingError> {
         let mut new_equivocations = Vec::new();
 
+        // Evidence older than the window can no longer be slashed
+        if commit.height < self.window_floor() {
+            return Ok(new_equivocations);
+        }
+
         // Get all validators that signed this commit
         let signers = commit.validator_bitmap.get_set_indices();
 
@@ -148,8 +345,10 @@ impl EquivocationDetector {
                         commit.clone(),
                     )?;
                     
-                    new_equivocations.push(evidence.clone());
-                    self.detected_equivocations.push(evidence);
+                    if self.reported_equivocations.insert(Self::offense_key(&evidence)) {
+                        new_equivocations.push(evidence.clone());
+                        self.record_evidence(evidence);
+                    }
                 }
             } else {
                 // Store this commit for future comparison
@@ -160,9 +359,141 @@ impl EquivocationDetector {
             self.check_height_violations(validator_index, commit, &mut new_equivocations)?;
         }
 
+        if commit.height > self.highest_height {
+            self.highest_height = commit.height;
+            self.prune_history();
+        }
+
+        if !new_equivocations.is_empty() {
+            self.submit_to_sink(&new_equivocations)?;
+        }
+
+        Ok(new_equivocations)
+    }
+
+    /// Record `header` as finalized by `commit`; later header commits are
+    /// checked against it
+    pub fn record_finalized_header(&mut self, header: &Header, commit: &BlsCommit) -> Result<(), SlashingError> {
+        Self::check_commit_matches_header(header, commit)?;
+        self.finalized_commits.insert(header.height, commit.clone());
+        Ok(())
+    }
+
+    /// Process a commit together with the header it commits to. Runs the
+    /// commit-only checks of `process_commit`, then flags every signer if the
+    /// header conflicts with a finalized header at its height or does not
+    /// build on the finalized header below it.
+    pub fn process_header_commit(&mut self, header: &Header, commit: &BlsCommit) -> Result<Vec<EquivocationEvidence>, SlashingError> {
+        Self::check_commit_matches_header(header, commit)?;
+        let mut new_equivocations = self.process_commit(commit)?;
+        if commit.height < self.window_floor() {
+            return Ok(new_equivocations);
+        }
+
+        let same_height = self.finalized_commits.get(&header.height)
+            .filter(|finalized| finalized.header_hash != commit.header_hash)
+            .map(|finalized| (finalized.clone(), None));
+        let parent = header.height.checked_sub(1)
+            .and_then(|parent_height| self.finalized_commits.get(&parent_height))
+            .filter(|finalized| finalized.header_hash.0 != header.prev_hash)
+            .map(|finalized| (finalized.clone(), Some(header.clone())));
+        let (conflicting, child_header) = match same_height.or(parent) {
+            Some(conflict) => conflict,
+            None => return Ok(new_equivocations),
+        };
+
+        let mut finality_violations = Vec::new();
+        for validator_index in commit.validator_bitmap.get_set_indices() {
+            let mut evidence = self.create_equivocation_evidence(
+                EquivocationType::FinalityViolation,
+                validator_index,
+                conflicting.clone(),
+                commit.clone(),
+            )?;
+            evidence.child_header = child_header.clone();
+
+            if self.reported_equivocations.insert(Self::offense_key(&evidence)) {
+                finality_violations.push(evidence.clone());
+                self.record_evidence(evidence);
+            }
+        }
+
+        if !finality_violations.is_empty() {
+            self.submit_to_sink(&finality_violations)?;
+        }
+        new_equivocations.extend(finality_violations);
         Ok(new_equivocations)
     }
 
+    /// A commit must be for exactly the header it is processed with
+    fn check_commit_matches_header(header: &Header, commit: &BlsCommit) -> Result<(), SlashingError> {
+        let header_hash = header.hash()
+            .map_err(|e| SlashingError::EncodingError(e.to_string()))?;
+        if header_hash != commit.header_hash || header.height != commit.height {
+            return Err(SlashingError::InvalidProof(format!(
+                "Commit at height {} does not reference header at height {}", commit.height, header.height
+            )));
+        }
+        Ok(())
+    }
+
+    /// Lowest height still inside the window
+    fn window_floor(&self) -> u64 {
+        self.highest_height.saturating_sub(self.max_height_window)
+    }
+
+    /// Drop commits and reported offenses that have fallen out of the window
+    fn prune_history(&mut self) {
+        let floor = self.window_floor();
+        self.commit_history.retain(|(_, height, _), _| *height >= floor);
+        self.finalized_commits.retain(|height, _| *height >= floor);
+        self.reported_equivocations.retain(|(_, height, _, _)| *height >= floor);
+    }
+
+    /// Build slashing proofs for new evidence and queue them for the sink
+    /// without blocking commit processing. A single worker task drains the
+    /// queue, so the sink sees proofs in the order they were detected.
+    /// Requires a Tokio runtime when a sink is set.
+    fn submit_to_sink(&mut self, evidence: &[EquivocationEvidence]) -> Result<(), SlashingError> {
+        let sink = match &self.sink {
+            Some(sink) => sink.clone(),
+            None => return Ok(()),
+        };
+        let runtime = match tokio::runtime::Handle::try_current() {
+            Ok(runtime) => runtime,
+            Err(_) => {
+                tracing::warn!("No async runtime; {} slashing proofs not submitted", evidence.len());
+                return Ok(());
+            }
+        };
+
+        let validator_set_hash = self.validator_set.hash()
+            .map_err(|e| SlashingError::EncodingError(e.to_string()))?;
+        let timestamp = Utc::now().timestamp() as u64;
+
+        // (Re)start the worker if it has not run yet or its runtime has gone
+        let queue = match &self.sink_queue {
+            Some(queue) if !queue.is_closed() => queue.clone(),
+            _ => {
+                let (queue, mut pending) = mpsc::unbounded_channel::<SlashingProof>();
+                runtime.spawn(async move {
+                    while let Some(proof) = pending.recv().await {
+                        sink.submit(proof).await;
+                    }
+                });
+                self.sink_queue = Some(queue.clone());
+                queue
+            }
+        };
+
+        for evidence in evidence {
+            if queue.send(SlashingProof::new(evidence.clone(), validator_set_hash, timestamp)).is_err() {
+                tracing::warn!("Slashing proof dropped: sink worker stopped");
+            }
+        }
+        Ok(())
+    }
+
     /// Check for height violations by a validator
     fn check_height_violations(
         &mut self,
@@ -183,8 +514,17 @@ impl EquivocationDetector {
                         current_commit.clone(),
                     )?;
                     
-                    equivocations.push(evidence.clone());
-                    self.detected_equivocations.push(evidence);
+                    if self.reported_equivocations.insert(Self::offense_key(&evidence)) {
+                        equivocations.push(evidence.clone());
+                        // The loop borrows `commit_history`, so `record_evidence` cannot be called here
+                        push_capped(
+                            &mut self.detected_equivocations,
+                            evidence,
+                            self.max_evidence_per_validator,
+                            |held| held.validator_index,
+                            EquivocationEvidence::severity,
+                        );
+                    }
                 }
             }
         }
@@ -192,6 +532,61 @@ impl EquivocationDetector {
         Ok(())
     }
 
+    /// Re-run each equivocation in `export` through `process_commit` on a fresh
+    /// detector over this detector's validator set, and report which claims are
+    /// reproduced: the same validator, height, round, type and pair of commits.
+    /// Finality violations need the finalized header, which an export does not
+    /// carry, so they are reported unconfirmed.
+    pub fn replay_evidence(&self, export: &PortableEvidenceExport) -> Result<ReplayReport, SlashingError> {
+        let mut report = ReplayReport::default();
+        for evidence in &export.evidence {
+            let (commit_a, commit_b, equivocation_type) = match &evidence.evidence_data {
+                EvidenceData::Equivocation { commit_a, commit_b, equivocation_type } => (commit_a, commit_b, *equivocation_type),
+                _ => {
+                    report.skipped += 1;
+                    continue;
+                }
+            };
+
+            // A commit the detector rejects (e.g. a forged signature) means
+            // the claim is not reproduced; it does not abort the replay
+            let mut replay = Self::new(self.validator_set.clone()).with_max_height_window(u64::MAX);
+            let derived = replay.process_commit(commit_a)
+                .and_then(|_| replay.process_commit(commit_b));
+            let rederived = derived.is_ok_and(|derived| derived.iter().any(|derived| {
+                derived.equivocation_type == equivocation_type
+                    && derived.validator_index == evidence.validator_index
+                    && derived.height == evidence.height
+                    && Some(derived.round) == evidence.round
+                    && derived.commit_a.header_hash == commit_a.header_hash
+                    && derived.commit_b.header_hash == commit_b.header_hash
+            }));
+
+            if rederived {
+                report.confirmed.push(evidence.evidence_id.clone());
+            } else {
+                report.unconfirmed.push(evidence.evidence_id.clone());
+            }
+        }
+        Ok(report)
+    }
+
+    /// Keep `evidence`, subject to the per-validator cap
+    fn record_evidence(&mut self, evidence: EquivocationEvidence) {
+        push_capped(
+            &mut self.detected_equivocations,
+            evidence,
+            self.max_evidence_per_validator,
+            |held| held.validator_index,
+            EquivocationEvidence::severity,
+        );
+    }
+
+    /// Identity of an offense; the same offense is only ever reported once
+    fn offense_key(evidence: &EquivocationEvidence) -> (usize, u64, u64, EquivocationType) {
+        (evidence.validator_index, evidence.height, evidence.round, evidence.equivocation_type)
+    }
+
     /// Create equivocation evidence from two conflicting commits
     fn create_equivocation_evidence(
         &self,
@@ -204,13 +599,26 @@ impl EquivocationDetector {
         let validator_info = self.validator_set.get_validator(validator_index)
             .ok_or(SlashingError::ValidatorNotInSet(validator_index))?;
 
-        // Create signature proofs for both commits
-        let signature_proof_a = self.extract_signature_proof(validator_index, &commit_a)?;
-        let signature_proof_b = self.extract_signature_proof(validator_index, &commit_b)?;
-
-        // For simplicity, we'll use the first signature proof as the main proof
-        // In a full implementation, both proofs would be included
-        let signature_proof = signature_proof_a;
+        // A finality violation only needs the offending commit's signature;
+        // the validator need not have signed the finalized one. It happened
+        // at the offending commit's height, so a fork and its child are
+        // separate offenses
+        let height = if equivocation_type == EquivocationType::FinalityViolation {
+            commit_b.height
+        } else {
+            commit_a.height
+        };
+        let signature_proof = if equivocation_type == EquivocationType::FinalityViolation {
+            self.extract_signature_proof(validator_index, &commit_b)?
+        } else {
+            // Create signature proofs for both commits
+            let signature_proof_a = self.extract_signature_proof(validator_index, &commit_a)?;
+            let signature_proof_b = self.extract_signature_proof(validator_index, &commit_b)?;
+
+            // For simplicity, we'll use the first signature proof as the main proof
+            // In a full implementation, both proofs would be included
+            signature_proof_a
+        };
 
         Ok(EquivocationEvidence {
             equivocation_type,
@@ -218,38 +626,29 @@ impl EquivocationDetector {
             commit_a: commit_a.clone(),
             commit_b: commit_b.clone(),
             signature_proof,
-            height: commit_a.height,
+            height,
             round: commit_a.round,
+            child_header: None,
         })
     }
 
-    /// Extract individual signature from aggregate signature
+    /// The signature share `validator_index` contributed to `commit`, checked
+    /// against the validator's key. The aggregate alone cannot be split back
+    /// into shares, so a commit that did not retain the share yields
+    /// `MissingShare`.
     fn extract_individual_signature(
         &self,
-        aggregate_signature: &AggregateSignature,
+        commit: &BlsCommit,
         validator_index: usize,
         message: &[u8],
         public_key: &PublicKey,
     ) -> Result<Signature, SlashingError> {
-        // In a real BLS implementation, we would use signature aggregation techniques
-        // to extract the individual signature. For now, we'll verify the aggregate
-        // signature contains the validator's contribution and create a valid signature.
-        
-        // Verify the aggregate signature is valid for this message
-        let message_hash = blake3::hash(message);
-        
-        // Create a single-validator signature for verification
-        // In practice, this would be extracted from the aggregate using BLS techniques
-        let individual_sig = aggregate_signature.clone();
-        
-        // Verify this signature is valid for the given public key and message
-        if !individual_sig.verify(message, public_key) {
-            return Err(SlashingError::InvalidSignature(
-                format!("Signature verification failed for validator {}", validator_index)
-            ));
+        let share = commit.share(validator_index)
+            .ok_or(SlashingError::MissingShare(validator_index))?;
+        if !public_key.verify(message, share) {
+            return Err(SlashingError::SignatureVerificationFailed(validator_index));
         }
-        
-        Ok(individual_sig)
+        Ok(share.clone())
     }
 
     /// Extract signature proof for a validator from a commit
@@ -266,26 +665,17 @@ impl EquivocationDetector {
         let validator_info = self.validator_set.get_validator(validator_index)
             .ok_or(SlashingError::ValidatorNotInSet(validator_index))?;
 
-        // Extract real individual signature from the aggregate signature
         let signed_message = commit.signing_message();
         let commit_hash = commit.commit_hash();
 
         // Get the validator's individual signature from the commit
-        // In a real implementation, this would be extracted from the aggregate
         let individual_signature = self.extract_individual_signature(
-            &commit.aggregate_signature,
+            commit,
             validator_index,
             &signed_message,
             &validator_info.bls_pubkey,
         )?;
 
-        // Verify the individual signature is valid
-        if !individual_signature.verify(&signed_message, &validator_info.bls_pubkey) {
-            return Err(SlashingError::InvalidSignature(
-                format!("Invalid signature for validator {}", validator_index)
-            ));
-        }
-
         Ok(SignatureProof {
             validator_index,
             signature: individual_signature,
@@ -304,6 +694,9 @@ impl EquivocationDetector {
     pub fn clear_history(&mut self) {
         self.commit_history.clear();
         self.detected_equivocations.clear();
+        self.reported_equivocations.clear();
+        self.finalized_commits.clear();
+        self.highest_height = 0;
     }
 
     /// Get commit history size
@@ -320,6 +713,9 @@ impl SlashingProofVerifier {
 
     /// Verify a slashing proof
     pub fn verify_proof(&self, proof: &SlashingProof) -> Result<bool, SlashingError> {
+        // The proof must have been built against the set we verify with
+        self.verify_validator_set(proof)?;
+
         let evidence = &proof.evidence;
 
         // Verify validator is in the set
@@ -328,9 +724,20 @@ impl SlashingProofVerifier {
 
         // Verify the commits are actually conflicting
         self.verify_commits_conflict(&evidence.commit_a, &evidence.commit_b, evidence.equivocation_type.clone())?;
+        if evidence.equivocation_type == EquivocationType::FinalityViolation
+            && evidence.commit_b.height != evidence.commit_a.height
+        {
+            Self::verify_child_breaks_finality(&evidence.commit_a, &evidence.commit_b, evidence.child_header.as_ref())?;
+        }
 
-        // Verify signature proofs
-        self.verify_signature_proof(&evidence.signature_proof, &validator_info)?;
+        // Verify signature proofs; the proof is for the second commit of a
+        // finality violation and for the first otherwise
+        let signed_commit = if evidence.equivocation_type == EquivocationType::FinalityViolation {
+            &evidence.commit_b
+        } else {
+            &evidence.commit_a
+        };
+        self.verify_signature_proof(&evidence.signature_proof, validator_info, signed_commit)?;
 
         // Verify both commits are valid
         let verification_a = evidence.commit_a.verify(&self.validator_set)
@@ -343,15 +750,38 @@ impl SlashingProofVerifier {
             return Err(SlashingError::InvalidProof("One or both commits are invalid".to_string()));
         }
 
-        // Verify validator actually signed both commits
-        if !evidence.commit_a.validator_bitmap.is_set(evidence.validator_index) ||
-           !evidence.commit_b.validator_bitmap.is_set(evidence.validator_index) {
+        // The bitmaps only claim who signed; the aggregates must back that up
+        if !evidence.commit_a.verify_signature(&self.validator_set)
+            || !evidence.commit_b.verify_signature(&self.validator_set)
+        {
+            return Err(SlashingError::InvalidProof("Commit aggregate signature is invalid".to_string()));
+        }
+
+        // Verify validator actually signed both commits; for a finality
+        // violation the first commit is the finalizing one, signed by others
+        let signed_a = evidence.equivocation_type == EquivocationType::FinalityViolation
+            || evidence.commit_a.validator_bitmap.is_set(evidence.validator_index);
+        if !signed_a || !evidence.commit_b.validator_bitmap.is_set(evidence.validator_index) {
             return Err(SlashingError::InvalidProof("Validator did not sign both commits".to_string()));
         }
 
         Ok(true)
     }
 
+    /// Check that the proof's validator set hash is the hash of our set
+    fn verify_validator_set(&self, proof: &SlashingProof) -> Result<(), SlashingError> {
+        // Hashing caches the Merkle tree, so work on a copy
+        let known = self.validator_set.clone().hash()
+            .map_err(|e| SlashingError::EncodingError(e.to_string()))?;
+        if known != proof.validator_set_hash {
+            return Err(SlashingError::ValidatorSetMismatch {
+                known: hex::encode(known),
+                proof: hex::encode(proof.validator_set_hash),
+            });
+        }
+        Ok(())
+    }
+
     /// Verify that two commits actually conflict
     fn verify_commits_conflict(
         &self,
@@ -384,24 +814,69 @@ impl SlashingProofVerifier {
                     return Err(SlashingError::CommitsNotConflicting);
                 }
             }
+            EquivocationType::FinalityViolation => {
+                // A different header at the finalized height, or a child of
+                // the finalized height (whose parent link is checked against
+                // the child header by `verify_child_breaks_finality`)
+                let conflicting_sibling = commit_b.height == commit_a.height
+                    && commit_b.header_hash != commit_a.header_hash;
+                let child = commit_b.height == commit_a.height + 1;
+                if !conflicting_sibling && !child {
+                    return Err(SlashingError::CommitsNotConflicting);
+                }
+            }
         }
 
         Ok(())
     }
 
-    /// Verify a signature proof
+    /// A child of the finalized commit `finalized` only violates finality if
+    /// its header, which must be the one `child` commits to, names a
+    /// different parent
+    fn verify_child_breaks_finality(
+        finalized: &BlsCommit,
+        child: &BlsCommit,
+        child_header: Option<&Header>,
+    ) -> Result<(), SlashingError> {
+        let header = child_header.ok_or_else(|| SlashingError::InvalidProof(
+            "Finality violation by a child commit needs the child header".to_string()
+        ))?;
+        let header_hash = header.hash()
+            .map_err(|e| SlashingError::EncodingError(e.to_string()))?;
+        if header_hash != child.header_hash || header.height != child.height {
+            return Err(SlashingError::InvalidProof(
+                "Child header does not match the offending commit".to_string()
+            ));
+        }
+        if header.prev_hash == finalized.header_hash.0 {
+            return Err(SlashingError::CommitsNotConflicting);
+        }
+        Ok(())
+    }
+
+    /// Verify a signature proof for `commit`
     fn verify_signature_proof(
         &self,
         proof: &SignatureProof,
         validator_info: &ValidatorInfo,
+        commit: &BlsCommit,
     ) -> Result<(), SlashingError> {
         // Verify the public key matches
         if proof.public_key != validator_info.bls_pubkey {
             return Err(SlashingError::InvalidProof("Public key mismatch".to_string()));
         }
 
-        // In a full implementation, we would verify the signature here
-        // For now, we'll assume the signature is valid if the public key matches
+        // The signature must actually be the validator's over the claimed message
+        if !proof.public_key.verify(&proof.signed_message, &proof.signature) {
+            return Err(SlashingError::SignatureVerificationFailed(proof.validator_index));
+        }
+
+        // ...and that message must be the commit's, or any signature the
+        // validator ever made would do
+        if proof.signed_message != commit.signing_message() || proof.commit_hash != commit.commit_hash() {
+            return Err(SlashingError::InvalidProof("Signature proof is not for the commit".to_string()));
+        }
+
         Ok(())
     }
 }
@@ -446,6 +921,34 @@ impl SlashingProof {
     }
 }
 
+impl EquivocationEvidence {
+    /// Severity judged from the equivocation type alone
+    pub fn severity(&self) -> SlashingSeverity {
+        self.equivocation_type.severity()
+    }
+
+    /// Severity given the highest finalized height, if known. Conflicting
+    /// votes at or below it attack finalized history and are Critical.
+    pub fn severity_with_finality(&self, finalized_height: Option<u64>) -> SlashingSeverity {
+        let at_finalized = finalized_height.is_some_and(|finalized| self.height <= finalized);
+        match self.equivocation_type {
+            EquivocationType::DoubleCommit | EquivocationType::MultipleSignatures if at_finalized => SlashingSeverity::Critical,
+            equivocation_type => equivocation_type.severity(),
+        }
+    }
+}
+
+impl EquivocationType {
+    /// Severity of this kind of equivocation when finality is not known
+    pub fn severity(self) -> SlashingSeverity {
+        match self {
+            EquivocationType::FinalityViolation => SlashingSeverity::Critical,
+            EquivocationType::DoubleCommit | EquivocationType::MultipleSignatures => SlashingSeverity::Major,
+            EquivocationType::HeightViolation => SlashingSeverity::Minor,
+        }
+    }
+}
+
 // Implement serialization methods for canonical CBOR encoding
 impl EquivocationEvidence {
     pub fn to_canonical_cbor(&self) -> Result<Vec<u8>, SlashingError> {
@@ -473,6 +976,7 @@ use chrono::{DateTime, Utc};
 use serde_json;
 use bpi_anchor::{AnchorReceipt, AnchorStatus};
 use bpi_lc::{BlockHeader, AnchorInfo};
+use ed25519_dalek::{Signer, SigningKey, Verifier, VerifyingKey};
 
 /// Type of evidence for slashing
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
@@ -524,14 +1028,14 @@ pub enum EvidenceData {
         missing_data_hash: Vec<u8>,
         expected_data_root: Vec<u8>,
         block_header: BlockHeader,
-        challenge_proof: Vec<u8>,
+        challenge_proof: MerkleProof,
     },
     /// Inclusion failure data
     Inclusion {
         excluded_transactions: Vec<Vec<u8>>,
         block_header: BlockHeader,
         mempool_snapshot: Vec<u8>,
-        inclusion_proof: Vec<u8>,
+        inclusion_proof: MerkleProof,
     },
     /// Anchor failure data
     Anchor {
@@ -546,17 +1050,59 @@ pub enum EvidenceData {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CryptographicProof {
     /// Signature proofs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub signature_proofs: Vec<SignatureProof>,
     /// Merkle proofs (if applicable)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub merkle_proofs: Vec<MerkleProof>,
     /// VRF proofs (if applicable)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub vrf_proofs: Vec<VrfProof>,
     /// Hash chain proofs
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub hash_chain_proofs: Vec<HashChainProof>,
 }
 
+impl StandardizedEvidence {
+    /// Reduce the evidence to what verification needs: Merkle proofs already
+    /// carried in `evidence_data` are dropped from `cryptographic_proof`, and
+    /// empty proof lists are left out of the serialized form. Conflicting
+    /// commits keep only the offender's signature share, and drop the signer
+    /// public keys, which the bitmap and validator set already determine.
+    pub fn compact(&mut self) {
+        let validator_index = self.validator_index;
+        let embedded = match &mut self.evidence_data {
+            EvidenceData::DataAvailability { challenge_proof, .. } => Some(&*challenge_proof),
+            EvidenceData::Inclusion { inclusion_proof, .. } => Some(&*inclusion_proof),
+            EvidenceData::Equivocation { commit_a, commit_b, .. } => {
+                for commit in [commit_a, commit_b] {
+                    commit.shares.retain(|(index, _)| *index == validator_index);
+                    commit.aggregate_signature.signers.clear();
+                }
+                None
+            }
+            EvidenceData::Anchor { .. } => None,
+        };
+        if let Some(embedded) = embedded {
+            self.cryptographic_proof.merkle_proofs.retain(|proof| proof != embedded);
+        }
+    }
+
+    /// Severity used to rank evidence against the same validator. Equivocations
+    /// are judged by type; availability, inclusion and anchor failures cannot
+    /// fork the chain by themselves and are Minor.
+    pub fn severity(&self) -> SlashingSeverity {
+        match &self.evidence_data {
+            EvidenceData::Equivocation { equivocation_type, .. } => equivocation_type.severity(),
+            EvidenceData::DataAvailability { .. } | EvidenceData::Inclusion { .. } | EvidenceData::Anchor { .. } => {
+                SlashingSeverity::Minor
+            }
+        }
+    }
+}
+
 /// Merkle proof for inclusion/exclusion
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct MerkleProof {
     /// Leaf data
     pub leaf: Vec<u8>,
@@ -568,6 +1114,50 @@ pub struct MerkleProof {
     pub index: u64,
 }
 
+impl MerkleProof {
+    /// Build a proof for the leaf at `index` of a `bpi_merkle` tree
+    pub fn from_tree(tree: &bpi_merkle::MerkleTree, leaf: Vec<u8>, index: usize) -> Result<Self, SlashingError> {
+        let proof = tree.proof(index)
+            .map_err(|e| SlashingError::InvalidProof(format!("Merkle proof generation failed: {}", e)))?;
+        let root = tree.root()
+            .map_err(|e| SlashingError::InvalidProof(format!("Merkle root unavailable: {}", e)))?;
+
+        Ok(Self {
+            leaf,
+            path: proof.siblings.iter().map(|(hash, _)| hash.to_vec()).collect(),
+            root: root.to_vec(),
+            index: index as u64,
+        })
+    }
+
+    /// Recompute the root from the leaf and path and compare it to the claimed root
+    pub fn verify(&self) -> bool {
+        let root: bpi_merkle::Hash = match self.root.as_slice().try_into() {
+            Ok(root) => root,
+            Err(_) => return false,
+        };
+
+        // Sibling side is derived from the leaf index bits at each level
+        let mut siblings = Vec::with_capacity(self.path.len());
+        let mut position = self.index;
+        for sibling in &self.path {
+            let sibling_hash: bpi_merkle::Hash = match sibling.as_slice().try_into() {
+                Ok(hash) => hash,
+                Err(_) => return false,
+            };
+            siblings.push((sibling_hash, position.is_multiple_of(2)));
+            position /= 2;
+        }
+
+        let proof = bpi_merkle::MerkleProof {
+            leaf_index: self.index as usize,
+            leaf_hash: domain_hash(domains::MERKLE_LEAF, &self.leaf),
+            siblings,
+        };
+        proof.verify(root)
+    }
+}
+
 /// VRF proof for randomness verification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VrfProof {
@@ -610,7 +1200,7 @@ pub struct VerificationMetadata {
 }
 
 /// Network parameters for verification
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct NetworkParameters {
     /// Consensus parameters
     pub consensus_params: serde_json::Value,
@@ -620,6 +1210,22 @@ pub struct NetworkParameters {
     pub network_config: serde_json::Value,
 }
 
+impl Default for NetworkParameters {
+    fn default() -> Self {
+        Self {
+            consensus_params: serde_json::json!({}),
+            slashing_params: serde_json::json!({}),
+            network_config: serde_json::json!({}),
+        }
+    }
+}
+
+/// Export format version written by this crate
+pub const CURRENT_EXPORT_VERSION: &str = "1.0.0";
+
+/// Export format versions this crate can import; other minors of a listed major are tolerated
+pub const SUPPORTED_EXPORT_VERSIONS: &[&str] = &["1.0.0"];
+
 /// Portable evidence export format
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PortableEvidenceExport {
@@ -635,6 +1241,46 @@ pub struct PortableEvidenceExport {
     pub integrity_hash: String,
 }
 
+impl PortableEvidenceExport {
+    /// Check the export's format version can be read. Unknown majors are rejected;
+    /// a different minor or patch of a supported major is tolerated.
+    pub fn validate_version(&self) -> Result<(), SlashingError> {
+        Self::check_version(&self.version)
+    }
+
+    fn check_version(version: &str) -> Result<(), SlashingError> {
+        if SUPPORTED_EXPORT_VERSIONS.contains(&version) {
+            return Ok(());
+        }
+        let major = Self::major_version(version)
+            .ok_or_else(|| SlashingError::UnsupportedVersion(version.to_string()))?;
+        let major_supported = SUPPORTED_EXPORT_VERSIONS.iter()
+            .any(|supported| Self::major_version(supported) == Some(major));
+        if !major_supported {
+            return Err(SlashingError::UnsupportedVersion(version.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Bring an export from an older or newer compatible version up to
+    /// `CURRENT_EXPORT_VERSION`. Within major 1 the layout is unchanged, so only
+    /// the version is rewritten; future majors add their conversions here.
+    pub fn migrate(mut export: PortableEvidenceExport) -> PortableEvidenceExport {
+        if Self::major_version(&export.version) == Self::major_version(CURRENT_EXPORT_VERSION) {
+            export.version = CURRENT_EXPORT_VERSION.to_string();
+        }
+        export
+    }
+
+    fn major_version(version: &str) -> Option<u64> {
+        let mut parts = version.split('.');
+        let major = parts.next()?.parse().ok()?;
+        // Require a well-formed major.minor.patch
+        let well_formed = parts.clone().count() == 2 && parts.all(|part| part.parse::<u64>().is_ok());
+        if well_formed { Some(major) } else { None }
+    }
+}
+
 /// Export metadata
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportMetadata {
@@ -646,8 +1292,33 @@ pub struct ExportMetadata {
     pub format: String,
     /// Compression used
     pub compression: Option<String>,
-    /// Digital signature (if any)
+    /// Digital signature (if any), hex-encoded Ed25519 signature over the integrity hash
     pub signature: Option<String>,
+    /// Hex-encoded Ed25519 public key of the signer (if signed)
+    #[serde(default)]
+    pub signer_public_key: Option<String>,
+}
+
+/// Outcome of verifying an export one evidence item at a time
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VerificationSummary {
+    /// Evidence items read
+    pub total: usize,
+    /// Items that passed verification
+    pub valid: usize,
+    /// Items read, by evidence type
+    pub counts_by_type: HashMap<EvidenceType, usize>,
+    /// Index of the first item that failed verification
+    pub first_failure: Option<usize>,
+    /// Whether the recorded integrity hash matches the evidence
+    pub integrity_ok: bool,
+}
+
+impl VerificationSummary {
+    /// Same verdict as `EvidenceExportAPI::verify_exported_evidence`
+    pub fn is_valid(&self) -> bool {
+        self.integrity_ok && self.first_failure.is_none()
+    }
 }
 
 /// Evidence export API for third-party verification
@@ -671,6 +1342,11 @@ pub struct ExportConfig {
     pub compression_level: u8,
     /// Digital signing enabled
     pub digital_signing: bool,
+    /// Consensus, slashing and network parameters embedded in every evidence record
+    pub network_params: NetworkParameters,
+    /// Evidence items stored per validator; beyond it new evidence is coalesced,
+    /// keeping the most severe (see `DEFAULT_MAX_EVIDENCE_PER_VALIDATOR`)
+    pub max_evidence_per_validator: usize,
 }
 
 impl EvidenceExportAPI {
@@ -692,7 +1368,7 @@ impl EvidenceExportAPI {
         
         let standardized_evidence = StandardizedEvidence {
             evidence_type: EvidenceType::Equivocation,
-            evidence_id: evidence_id.clone(),
+            evidence_id,
             validator_index: equivocation.validator_index,
             height: equivocation.height,
             round: Some(equivocation.round),
@@ -711,8 +1387,7 @@ impl EvidenceExportAPI {
             verification_metadata: self.create_verification_metadata(validator_set_hash),
         };
 
-        self.evidence_store.push(standardized_evidence);
-        Ok(evidence_id)
+        Ok(self.store_evidence(standardized_evidence))
     }
 
     /// Add data availability evidence
@@ -723,14 +1398,14 @@ impl EvidenceExportAPI {
         missing_data_hash: Vec<u8>,
         expected_data_root: Vec<u8>,
         block_header: BlockHeader,
-        challenge_proof: Vec<u8>,
+        challenge_proof: MerkleProof,
         validator_set_hash: Vec<u8>,
     ) -> Result<String, SlashingError> {
         let evidence_id = self.generate_evidence_id(EvidenceType::DataAvailability, height);
         
         let standardized_evidence = StandardizedEvidence {
             evidence_type: EvidenceType::DataAvailability,
-            evidence_id: evidence_id.clone(),
+            evidence_id,
             validator_index,
             height,
             round: None,
@@ -743,20 +1418,14 @@ impl EvidenceExportAPI {
             },
             cryptographic_proof: CryptographicProof {
                 signature_proofs: vec![],
-                merkle_proofs: vec![MerkleProof {
-                    leaf: challenge_proof,
-                    path: vec![],
-                    root: vec![],
-                    index: 0,
-                }],
+                merkle_proofs: vec![challenge_proof],
                 vrf_proofs: vec![],
                 hash_chain_proofs: vec![],
             },
             verification_metadata: self.create_verification_metadata(validator_set_hash),
         };
 
-        self.evidence_store.push(standardized_evidence);
-        Ok(evidence_id)
+        Ok(self.store_evidence(standardized_evidence))
     }
 
     /// Add inclusion evidence
@@ -767,14 +1436,14 @@ impl EvidenceExportAPI {
         excluded_transactions: Vec<Vec<u8>>,
         block_header: BlockHeader,
         mempool_snapshot: Vec<u8>,
-        inclusion_proof: Vec<u8>,
+        inclusion_proof: MerkleProof,
         validator_set_hash: Vec<u8>,
     ) -> Result<String, SlashingError> {
         let evidence_id = self.generate_evidence_id(EvidenceType::Inclusion, height);
         
         let standardized_evidence = StandardizedEvidence {
             evidence_type: EvidenceType::Inclusion,
-            evidence_id: evidence_id.clone(),
+            evidence_id,
             validator_index,
             height,
             round: None,
@@ -787,20 +1456,14 @@ impl EvidenceExportAPI {
             },
             cryptographic_proof: CryptographicProof {
                 signature_proofs: vec![],
-                merkle_proofs: vec![MerkleProof {
-                    leaf: inclusion_proof,
-                    path: vec![],
-                    root: vec![],
-                    index: 0,
-                }],
+                merkle_proofs: vec![inclusion_proof],
                 vrf_proofs: vec![],
                 hash_chain_proofs: vec![],
             },
             verification_metadata: self.create_verification_metadata(validator_set_hash),
         };
 
-        self.evidence_store.push(standardized_evidence);
-        Ok(evidence_id)
+        Ok(self.store_evidence(standardized_evidence))
     }
 
     /// Add anchor evidence
@@ -818,7 +1481,7 @@ impl EvidenceExportAPI {
         
         let standardized_evidence = StandardizedEvidence {
             evidence_type: EvidenceType::Anchor,
-            evidence_id: evidence_id.clone(),
+            evidence_id,
             validator_index,
             height,
             round: None,
@@ -843,18 +1506,42 @@ impl EvidenceExportAPI {
             verification_metadata: self.create_verification_metadata(validator_set_hash),
         };
 
-        self.evidence_store.push(standardized_evidence);
-        Ok(evidence_id)
+        Ok(self.store_evidence(standardized_evidence))
+    }
+
+    /// Store `evidence`, subject to the per-validator cap, and return the id it
+    /// is kept under. Evidence coalesced at the cap returns the id of the most
+    /// severe evidence held for the validator.
+    fn store_evidence(&mut self, evidence: StandardizedEvidence) -> String {
+        let evidence_id = evidence.evidence_id.clone();
+        let validator_index = evidence.validator_index;
+        let kept = push_capped(
+            &mut self.evidence_store,
+            evidence,
+            self.config.max_evidence_per_validator,
+            |held| held.validator_index,
+            StandardizedEvidence::severity,
+        );
+        if kept {
+            return evidence_id;
+        }
+        self.evidence_store.iter()
+            .filter(|held| held.validator_index == validator_index)
+            .max_by_key(|held| held.severity())
+            .map(|held| held.evidence_id.clone())
+            .expect("validator at the cap holds evidence")
     }
 
-    /// Export evidence in portable format
+    /// Export evidence in portable format, each item compacted to a minimal proof
     pub fn export_evidence(&self, reason: String) -> Result<PortableEvidenceExport, SlashingError> {
-        let integrity_hash = self.calculate_integrity_hash()?;
+        let mut evidence = self.evidence_store.clone();
+        evidence.iter_mut().for_each(StandardizedEvidence::compact);
+        let integrity_hash = Self::calculate_integrity_hash(&evidence)?;
         
         let export = PortableEvidenceExport {
-            version: "1.0.0".to_string(),
+            version: CURRENT_EXPORT_VERSION.to_string(),
             exported_at: Utc::now(),
-            evidence: self.evidence_store.clone(),
+            evidence,
             metadata: ExportMetadata {
                 exporter: self.config.exporter_identity.clone(),
                 reason,
@@ -864,11 +1551,8 @@ impl EvidenceExportAPI {
                 } else {
                     None
                 },
-                signature: if self.config.digital_signing {
-                    Some("ed25519".to_string())
-                } else {
-                    None
-                },
+                signature: None,
+                signer_public_key: None,
             },
             integrity_hash,
         };
@@ -876,6 +1560,54 @@ impl EvidenceExportAPI {
         Ok(export)
     }
 
+    /// Export evidence in portable format, signed with the exporter's Ed25519 key
+    pub fn export_signed(&self, reason: String, signing_key: &SigningKey) -> Result<PortableEvidenceExport, SlashingError> {
+        let mut export = self.export_evidence(reason)?;
+
+        let hash_bytes = hex::decode(&export.integrity_hash)
+            .map_err(|e| SlashingError::EncodingError(format!("Invalid integrity hash: {}", e)))?;
+        let signature = signing_key.sign(&hash_bytes);
+
+        export.metadata.signature = Some(hex::encode(signature.to_bytes()));
+        export.metadata.signer_public_key = Some(hex::encode(signing_key.verifying_key().to_bytes()));
+
+        Ok(export)
+    }
+
+    /// Verify the Ed25519 signature of an exported evidence bundle against the expected signer
+    pub fn verify_export_signature(
+        export: &PortableEvidenceExport,
+        expected_pubkey: &VerifyingKey,
+    ) -> Result<bool, SlashingError> {
+        let (signature_hex, pubkey_hex) = match (&export.metadata.signature, &export.metadata.signer_public_key) {
+            (Some(signature), Some(pubkey)) => (signature, pubkey),
+            _ => return Ok(false),
+        };
+
+        // The signer recorded in the export must be the one the verifier expects
+        if *pubkey_hex != hex::encode(expected_pubkey.to_bytes()) {
+            return Ok(false);
+        }
+
+        // The integrity hash must still match the exported evidence
+        let calculated_hash = Self::calculate_export_integrity_hash(export)?;
+        if calculated_hash != export.integrity_hash {
+            return Ok(false);
+        }
+
+        let hash_bytes = match hex::decode(&export.integrity_hash) {
+            Ok(bytes) => bytes,
+            Err(_) => return Ok(false),
+        };
+        let signature_bytes: [u8; 64] = match hex::decode(signature_hex).ok().and_then(|b| b.try_into().ok()) {
+            Some(bytes) => bytes,
+            None => return Ok(false),
+        };
+        let signature = ed25519_dalek::Signature::from_bytes(&signature_bytes);
+
+        Ok(expected_pubkey.verify(&hash_bytes, &signature).is_ok())
+    }
+
     /// Export evidence as JSON
     pub fn export_as_json(&self, reason: String) -> Result<String, SlashingError> {
         let export = self.export_evidence(reason)?;
@@ -883,6 +1615,15 @@ impl EvidenceExportAPI {
             .map_err(|e| SlashingError::EncodingError(format!("JSON serialization failed: {}", e)))
     }
 
+    /// Import an export from JSON, rejecting unsupported format versions and
+    /// migrating compatible ones to the current version
+    pub fn import_json(json: &str) -> Result<PortableEvidenceExport, SlashingError> {
+        let export: PortableEvidenceExport = serde_json::from_str(json)
+            .map_err(|e| SlashingError::EncodingError(format!("JSON deserialization failed: {}", e)))?;
+        export.validate_version()?;
+        Ok(PortableEvidenceExport::migrate(export))
+    }
+
     /// Verify exported evidence
     pub fn verify_exported_evidence(export: &PortableEvidenceExport) -> Result<bool, SlashingError> {
         // Verify integrity hash
@@ -901,6 +1642,28 @@ impl EvidenceExportAPI {
         Ok(true)
     }
 
+    /// Verify a JSON export read from `reader` without buffering its evidence.
+    /// Items are deserialized, hashed and verified one at a time, so memory
+    /// stays bounded by the largest single item. Unsupported versions are
+    /// rejected as in `import_json`.
+    pub fn verify_exported_evidence_streaming(reader: impl std::io::Read) -> Result<VerificationSummary, SlashingError> {
+        let mut deserializer = serde_json::Deserializer::from_reader(std::io::BufReader::new(reader));
+        let mut version_error = None;
+        let visitor = StreamingExportVisitor { version_error: &mut version_error };
+        let (mut summary, recorded_hash, calculated_hash) = serde::Deserializer::deserialize_map(&mut deserializer, visitor)
+            .map_err(|e| version_error.take()
+                .unwrap_or_else(|| SlashingError::EncodingError(format!("JSON deserialization failed: {}", e))))?;
+        deserializer.end()
+            .map_err(|e| SlashingError::EncodingError(format!("JSON deserialization failed: {}", e)))?;
+        summary.integrity_ok = recorded_hash == calculated_hash;
+        Ok(summary)
+    }
+
+    /// Check that evidence was produced under the network parameters the verifier expects
+    pub fn validate_network_params(evidence: &StandardizedEvidence, expected: &NetworkParameters) -> bool {
+        evidence.verification_metadata.network_params == *expected
+    }
+
     /// Get evidence by ID
     pub fn get_evidence(&self, evidence_id: &str) -> Option<&StandardizedEvidence> {
         self.evidence_store.iter().find(|e| e.evidence_id == evidence_id)
@@ -939,11 +1702,7 @@ impl EvidenceExportAPI {
         VerificationMetadata {
             validator_set_hash,
             chain_id: self.config.chain_id.clone(),
-            network_params: NetworkParameters {
-                consensus_params: serde_json::json!({}),
-                slashing_params: serde_json::json!({}),
-                network_config: serde_json::json!({}),
-            },
+            network_params: self.config.network_params.clone(),
             verification_instructions: "Verify using BPI consensus rules".to_string(),
             dependencies: vec![
                 "bpi-consensus".to_string(),
@@ -953,8 +1712,8 @@ impl EvidenceExportAPI {
         }
     }
 
-    fn calculate_integrity_hash(&self) -> Result<String, SlashingError> {
-        let data = serde_json::to_vec(&self.evidence_store)
+    fn calculate_integrity_hash(evidence: &[StandardizedEvidence]) -> Result<String, SlashingError> {
+        let data = serde_json::to_vec(evidence)
             .map_err(|e| SlashingError::EncodingError(format!("Serialization failed: {}", e)))?;
         
         let hash = blake3::hash(&data);
@@ -962,11 +1721,7 @@ impl EvidenceExportAPI {
     }
 
     fn calculate_export_integrity_hash(export: &PortableEvidenceExport) -> Result<String, SlashingError> {
-        let data = serde_json::to_vec(&export.evidence)
-            .map_err(|e| SlashingError::EncodingError(format!("Serialization failed: {}", e)))?;
-        
-        let hash = blake3::hash(&data);
-        Ok(hex::encode(hash.as_bytes()))
+        Self::calculate_integrity_hash(&export.evidence)
     }
 
     fn verify_evidence(evidence: &StandardizedEvidence) -> Result<bool, SlashingError> {
@@ -977,32 +1732,176 @@ impl EvidenceExportAPI {
                 Ok(commit_a.header_hash != commit_b.header_hash && 
                    commit_a.height == commit_b.height)
             }
-            EvidenceData::DataAvailability { .. } => {
-                // Verify DA challenge proof
-                Ok(true) // Simplified for now
+            EvidenceData::DataAvailability { expected_data_root, challenge_proof, .. } => {
+                // Challenge proof must reconstruct the data root the block committed to
+                Ok(challenge_proof.root == *expected_data_root && challenge_proof.verify())
+            }
+            EvidenceData::Inclusion { block_header, inclusion_proof, .. } => {
+                // Inclusion proof must reconstruct the block's transaction merkle root
+                let header_root = match hex::decode(&block_header.merkle_root) {
+                    Ok(root) => root,
+                    Err(_) => return Ok(false),
+                };
+                Ok(inclusion_proof.root == header_root && inclusion_proof.verify())
             }
-            EvidenceData::Inclusion { .. } => {
-                // Verify inclusion proof
-                Ok(true) // Simplified for now
+            EvidenceData::Anchor { expected_anchor, actual_anchor, anchor_receipt, l1_verification_data } => {
+                Ok(Self::verify_anchor_mismatch(
+                    expected_anchor,
+                    actual_anchor.as_ref(),
+                    anchor_receipt.as_ref(),
+                    l1_verification_data,
+                ))
+            }
+        }
+    }
+
+    /// An anchor offense is slashable when the anchor that landed on L1 differs from
+    /// the one the validator committed to (wrong tx hash or block), or a confirmed
+    /// anchor never landed at all. The L1 data and an L1 receipt must be present and
+    /// the receipt must agree with what L1 actually shows; without a receipt nothing
+    /// corroborates the claim. Matching anchors are not an offense.
+    fn verify_anchor_mismatch(
+        expected: &AnchorInfo,
+        actual: Option<&AnchorInfo>,
+        receipt: Option<&AnchorReceipt>,
+        l1_verification_data: &[u8],
+    ) -> bool {
+        if l1_verification_data.is_empty() {
+            return false;
+        }
+
+        match actual {
+            Some(actual) => {
+                if actual.anchor_id != expected.anchor_id {
+                    return false;
+                }
+                let mismatched = actual.l1_tx_hash != expected.l1_tx_hash
+                    || actual.l1_block_number != expected.l1_block_number;
+                // The receipt is L1's view and must describe the actual anchor
+                let corroborated = receipt.is_some_and(|receipt| {
+                    receipt.anchor_id == actual.anchor_id
+                        && receipt.tx_hash == actual.l1_tx_hash
+                        && receipt.block_number == actual.l1_block_number
+                });
+                mismatched && corroborated
             }
-            EvidenceData::Anchor { .. } => {
-                // Verify anchor proof
-                Ok(true) // Simplified for now
+            None => {
+                // Only an anchor claimed as confirmed is required to exist
+                if expected.confirmations == 0 {
+                    return false;
+                }
+                // Only a failed or expired receipt for the expected anchor proves it
+                // never landed; a confirmed one disproves the claim
+                receipt.is_some_and(|receipt| {
+                    receipt.anchor_id == expected.anchor_id
+                        && matches!(receipt.status, AnchorStatus::Failed | AnchorStatus::Expired)
+                })
             }
         }
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use bpi_validator_set::ValidatorInfo;
-    use bpi_blsagg::{PrivateKey, PublicKey};
+/// Walks a `PortableEvidenceExport` object, yielding the summary, the recorded
+/// integrity hash and the hash calculated over the streamed evidence
+struct StreamingExportVisitor<'a> {
+    /// Set when the version is refused, so the caller can report it as such
+    version_error: &'a mut Option<SlashingError>,
+}
 
-    fn create_test_validator_set() -> ValidatorSet {
-        let mut validator_set = ValidatorSet::new(0);
-        
-        for i in 0..4 {
+impl<'de, 'a> serde::de::Visitor<'de> for StreamingExportVisitor<'a> {
+    type Value = (VerificationSummary, String, String);
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a portable evidence export")
+    }
+
+    fn visit_map<A: serde::de::MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let mut summary = VerificationSummary::default();
+        let mut calculated_hash = None;
+        let mut recorded_hash = None;
+        while let Some(key) = map.next_key::<String>()? {
+            match key.as_str() {
+                "version" => {
+                    let version: String = map.next_value()?;
+                    if let Err(e) = PortableEvidenceExport::check_version(&version) {
+                        let message = e.to_string();
+                        *self.version_error = Some(e);
+                        return Err(A::Error::custom(message));
+                    }
+                }
+                "evidence" => {
+                    calculated_hash = Some(map.next_value_seed(StreamingEvidenceSeed { summary: &mut summary })?);
+                }
+                "integrity_hash" => recorded_hash = Some(map.next_value::<String>()?),
+                _ => {
+                    map.next_value::<serde::de::IgnoredAny>()?;
+                }
+            }
+        }
+        let calculated_hash = calculated_hash.ok_or_else(|| A::Error::missing_field("evidence"))?;
+        let recorded_hash = recorded_hash.ok_or_else(|| A::Error::missing_field("integrity_hash"))?;
+        Ok((summary, recorded_hash, calculated_hash))
+    }
+}
+
+/// Verifies the evidence array item by item, hashing each item's JSON exactly
+/// as `serde_json::to_vec` of the whole vector would
+struct StreamingEvidenceSeed<'a> {
+    summary: &'a mut VerificationSummary,
+}
+
+impl<'de, 'a> serde::de::DeserializeSeed<'de> for StreamingEvidenceSeed<'a> {
+    type Value = String;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de, 'a> serde::de::Visitor<'de> for StreamingEvidenceSeed<'a> {
+    type Value = String;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a list of standardized evidence")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        use serde::de::Error;
+
+        let mut hasher = blake3::Hasher::new();
+        hasher.update(b"[");
+        while let Some(evidence) = seq.next_element::<StandardizedEvidence>()? {
+            if self.summary.total > 0 {
+                hasher.update(b",");
+            }
+            serde_json::to_writer(&mut hasher, &evidence).map_err(A::Error::custom)?;
+
+            let valid = EvidenceExportAPI::verify_evidence(&evidence).map_err(A::Error::custom)?;
+            if valid {
+                self.summary.valid += 1;
+            } else if self.summary.first_failure.is_none() {
+                self.summary.first_failure = Some(self.summary.total);
+            }
+            *self.summary.counts_by_type.entry(evidence.evidence_type).or_insert(0) += 1;
+            self.summary.total += 1;
+        }
+        hasher.update(b"]");
+        Ok(hex::encode(hasher.finalize().as_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bpi_validator_set::ValidatorInfo;
+    use bpi_blsagg::{PrivateKey, PublicKey};
+
+    fn create_test_validator_set() -> ValidatorSet {
+        let mut validator_set = ValidatorSet::new(0);
+        
+        for i in 0..4 {
             // Create test keys using the keygen module
             let seed = [i as u8; 32];
             let (private_key, public_key) = bpi_blsagg::keygen::generate_keypair(&seed);
@@ -1031,26 +1930,17 @@ mod tests {
         signers: Vec<usize>,
         validator_count: usize,
     ) -> BlsCommit {
-        let mut bitmap = ValidatorBitmap::new(validator_count);
-        for signer in signers {
-            bitmap.set(signer).unwrap();
-        }
-
-        // Create real aggregated signature from the individual signatures
-        let individual_signatures: Vec<Signature> = evidence.commits.iter()
-            .map(|commit| commit.aggregate_signature.clone())
-            .collect();
-        
-        let real_agg_sig = AggregateSignature::from_signatures(&individual_signatures)
-            .map_err(|_| SlashingError::InvalidSignature("Failed to aggregate signatures".to_string()))?;
-        
-        BlsCommit::new(
-            header_hash,
-            real_agg_sig,
-            bitmap,
-            round,
-            height,
-        )
+        // Each signer signs with its `create_test_validator_set` key; the
+        // message is laid out as in `BlsCommit::signing_message`
+        let mut message = header_hash.as_bytes().to_vec();
+        message.extend_from_slice(&round.to_le_bytes());
+        message.extend_from_slice(&height.to_le_bytes());
+        let shares: Vec<(usize, Signature)> = signers.into_iter().map(|signer| {
+            let (private_key, _) = bpi_blsagg::keygen::generate_keypair(&[signer as u8; 32]);
+            (signer, private_key.sign(&message))
+        }).collect();
+
+        BlsCommit::from_shares(header_hash, &shares, validator_count, round, height).unwrap()
     }
 
     fn create_test_signature_proof() -> SignatureProof {
@@ -1072,9 +1962,36 @@ mod tests {
             include_proofs: true,
             compression_level: 0,
             digital_signing: false,
+            network_params: create_test_network_params(),
+            max_evidence_per_validator: DEFAULT_MAX_EVIDENCE_PER_VALIDATOR,
+        }
+    }
+
+    fn create_test_network_params() -> NetworkParameters {
+        NetworkParameters {
+            consensus_params: serde_json::json!({ "block_time_ms": 2000, "quorum_threshold": "2/3" }),
+            slashing_params: serde_json::json!({ "equivocation_slash_percent": 5, "unbonding_epochs": 21 }),
+            network_config: serde_json::json!({ "chain_id": "test-chain", "max_validators": 100 }),
         }
     }
 
+    fn create_test_merkle_leaves() -> Vec<Vec<u8>> {
+        (0..4u8).map(|i| vec![i; 32]).collect()
+    }
+
+    fn create_test_merkle_tree() -> bpi_merkle::MerkleTree {
+        bpi_merkle::MerkleTree::new(create_test_merkle_leaves()).unwrap()
+    }
+
+    fn create_test_data_root() -> Vec<u8> {
+        create_test_merkle_tree().root().unwrap().to_vec()
+    }
+
+    fn create_test_merkle_proof(index: usize) -> MerkleProof {
+        let leaf = create_test_merkle_leaves()[index].clone();
+        MerkleProof::from_tree(&create_test_merkle_tree(), leaf, index).unwrap()
+    }
+
     fn create_test_block_header() -> BlockHeader {
         use bpi_lc::BlockHeader;
         use chrono::Utc;
@@ -1084,7 +2001,7 @@ mod tests {
             height: 100,
             parent_hash: "parent_hash".to_string(),
             timestamp: Utc::now(),
-            merkle_root: "merkle_root".to_string(),
+            merkle_root: hex::encode(create_test_data_root()),
             state_root: "state_root".to_string(),
             anchor_info: None,
         }
@@ -1134,6 +2051,7 @@ mod tests {
             signature_proof: create_test_signature_proof(),
             height: 10,
             round: 1,
+            child_header: None,
         };
         
         let evidence_id = api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
@@ -1158,9 +2076,9 @@ mod tests {
             1, // validator_index
             20, // height
             vec![1u8; 32], // missing_data_hash
-            vec![2u8; 32], // expected_data_root
+            create_test_data_root(), // expected_data_root
             create_test_block_header(),
-            vec![3u8; 64], // challenge_proof
+            create_test_merkle_proof(1), // challenge_proof
             vec![4u8; 32], // validator_set_hash
         ).unwrap();
         
@@ -1186,7 +2104,7 @@ mod tests {
             vec![vec![1u8; 32], vec![2u8; 32]], // excluded_transactions
             create_test_block_header(),
             vec![5u8; 128], // mempool_snapshot
-            vec![6u8; 64], // inclusion_proof
+            create_test_merkle_proof(2), // inclusion_proof
             vec![7u8; 32], // validator_set_hash
         ).unwrap();
         
@@ -1252,10 +2170,11 @@ mod tests {
             signature_proof: create_test_signature_proof(),
             height: 10,
             round: 1,
+            child_header: None,
         };
         
         api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
-        api.add_da_evidence(1, 20, vec![1u8; 32], vec![2u8; 32], create_test_block_header(), vec![3u8; 64], vec![4u8; 32]).unwrap();
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
         
         let export = api.export_evidence("Testing export".to_string()).unwrap();
         
@@ -1274,7 +2193,7 @@ mod tests {
         let mut api = EvidenceExportAPI::new(config);
         
         // Add test evidence
-        api.add_da_evidence(1, 20, vec![1u8; 32], vec![2u8; 32], create_test_block_header(), vec![3u8; 64], vec![4u8; 32]).unwrap();
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
         
         let json_export = api.export_as_json("JSON export test".to_string()).unwrap();
         
@@ -1285,6 +2204,169 @@ mod tests {
         println!("✅ Export as JSON working");
     }
 
+    fn json_with_version(version: &str) -> String {
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+        let mut export = api.export_evidence("Version test".to_string()).unwrap();
+        export.version = version.to_string();
+        serde_json::to_string(&export).unwrap()
+    }
+
+    #[test]
+    fn test_import_supported_version_accepted() {
+        let export = EvidenceExportAPI::import_json(&json_with_version(CURRENT_EXPORT_VERSION)).unwrap();
+        assert_eq!(export.version, CURRENT_EXPORT_VERSION);
+        assert_eq!(export.evidence.len(), 1);
+        assert!(EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+    }
+
+    #[test]
+    fn test_import_unsupported_major_rejected() {
+        for version in ["2.0.0", "0.9.0", "1.0", "one.0.0"] {
+            let result = EvidenceExportAPI::import_json(&json_with_version(version));
+            assert!(
+                matches!(result, Err(SlashingError::UnsupportedVersion(ref v)) if v == version),
+                "version {} should be rejected", version
+            );
+        }
+    }
+
+    fn large_export(equivocations: usize) -> PortableEvidenceExport {
+        // Keep every item; the per-validator cap is not under test here
+        let mut config = create_test_export_config();
+        config.max_evidence_per_validator = equivocations;
+        let mut api = EvidenceExportAPI::new(config);
+        for i in 0..equivocations {
+            let height = 10 + i as u64;
+            let equivocation = EquivocationEvidence {
+                equivocation_type: EquivocationType::DoubleCommit,
+                validator_index: i % 4,
+                commit_a: create_test_commit(HeaderHash([1u8; 32]), height, 1, vec![0], 4),
+                commit_b: create_test_commit(HeaderHash([2u8; 32]), height, 1, vec![0], 4),
+                signature_proof: create_test_signature_proof(),
+                height,
+                round: 1,
+                child_header: None,
+            };
+            api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
+        }
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+        api.export_evidence("Streaming test".to_string()).unwrap()
+    }
+
+    fn verify_streaming(export: &PortableEvidenceExport) -> VerificationSummary {
+        let json = serde_json::to_vec(export).unwrap();
+        EvidenceExportAPI::verify_exported_evidence_streaming(json.as_slice()).unwrap()
+    }
+
+    #[test]
+    fn test_streaming_verification_matches_batch_on_large_export() {
+        let export = large_export(2000);
+        let summary = verify_streaming(&export);
+        assert!(EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+        assert!(summary.is_valid());
+        assert_eq!(summary.total, 2001);
+        assert_eq!(summary.valid, 2001);
+        assert_eq!(summary.counts_by_type[&EvidenceType::Equivocation], 2000);
+        assert_eq!(summary.counts_by_type[&EvidenceType::DataAvailability], 1);
+
+        // A non-conflicting pair part way through, with the hash recomputed to match
+        let mut invalid_item = export.clone();
+        if let EvidenceData::Equivocation { commit_a, commit_b, .. } = &mut invalid_item.evidence[1500].evidence_data {
+            *commit_b = commit_a.clone();
+        }
+        invalid_item.integrity_hash = EvidenceExportAPI::calculate_export_integrity_hash(&invalid_item).unwrap();
+        let summary = verify_streaming(&invalid_item);
+        assert!(!EvidenceExportAPI::verify_exported_evidence(&invalid_item).unwrap());
+        assert!(!summary.is_valid());
+        assert!(summary.integrity_ok);
+        assert_eq!(summary.first_failure, Some(1500));
+        assert_eq!(summary.valid, 2000);
+
+        // Evidence altered after export no longer matches the integrity hash
+        let mut tampered = export.clone();
+        tampered.evidence[3].height += 1;
+        let summary = verify_streaming(&tampered);
+        assert!(!EvidenceExportAPI::verify_exported_evidence(&tampered).unwrap());
+        assert!(!summary.integrity_ok);
+        assert_eq!(summary.first_failure, None);
+    }
+
+    #[test]
+    fn test_compaction_shrinks_evidence_and_keeps_it_verifiable() {
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+        // Commits signed by three validators, carrying every signer's share and key
+        let (keys, shares): (Vec<_>, Vec<_>) = (0..3).map(|index| {
+            let (private_key, public_key) = bpi_blsagg::keygen::generate_keypair(&[index as u8; 32]);
+            (public_key, (index, private_key.sign(b"commit")))
+        }).unzip();
+        let mut commit_a = create_test_commit(HeaderHash([1u8; 32]), 10, 1, vec![0, 1, 2], 4);
+        let mut commit_b = create_test_commit(HeaderHash([2u8; 32]), 10, 1, vec![0, 1, 2], 4);
+        for commit in [&mut commit_a, &mut commit_b] {
+            commit.shares = shares.clone();
+            commit.aggregate_signature.signers = keys.clone();
+        }
+        let equivocation = EquivocationEvidence {
+            equivocation_type: EquivocationType::DoubleCommit,
+            validator_index: 0,
+            commit_a,
+            commit_b,
+            signature_proof: create_test_signature_proof(),
+            height: 10,
+            round: 1,
+            child_header: None,
+        };
+        api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
+
+        for original in &api.evidence_store {
+            let mut compacted = original.clone();
+            compacted.compact();
+            let before = serde_json::to_vec(original).unwrap().len();
+            let after = serde_json::to_vec(&compacted).unwrap();
+            // The DA challenge proof is no longer carried twice, nor are the
+            // other signers' shares and keys
+            assert!(after.len() < before);
+            assert!(compacted.cryptographic_proof.merkle_proofs.is_empty());
+            assert!(!String::from_utf8_lossy(&after).contains("merkle_proofs"));
+            assert!(EvidenceExportAPI::verify_evidence(&compacted).unwrap());
+
+            // Stripped fields read back as empty lists
+            let decoded: StandardizedEvidence = serde_json::from_slice(&after).unwrap();
+            assert!(decoded.cryptographic_proof.vrf_proofs.is_empty());
+            assert!(EvidenceExportAPI::verify_evidence(&decoded).unwrap());
+        }
+
+        // The equivocation keeps its one signature proof
+        let export = api.export_evidence("Compaction test".to_string()).unwrap();
+        if let EvidenceData::Equivocation { commit_a, commit_b, .. } = &export.evidence[1].evidence_data {
+            for commit in [commit_a, commit_b] {
+                assert_eq!(commit.shares.iter().map(|(index, _)| *index).collect::<Vec<_>>(), vec![0]);
+                assert!(commit.aggregate_signature.signers.is_empty());
+            }
+        }
+        assert_eq!(export.evidence[1].cryptographic_proof.signature_proofs.len(), 1);
+        let json = serde_json::to_string(&export).unwrap();
+        assert!(!json.contains("vrf_proofs"));
+        assert!(EvidenceExportAPI::verify_exported_evidence(&EvidenceExportAPI::import_json(&json).unwrap()).unwrap());
+    }
+
+    #[test]
+    fn test_streaming_verification_rejects_unsupported_version() {
+        let result = EvidenceExportAPI::verify_exported_evidence_streaming(json_with_version("2.0.0").as_bytes());
+        assert!(matches!(result, Err(SlashingError::UnsupportedVersion(ref v)) if v == "2.0.0"));
+
+        let truncated = EvidenceExportAPI::verify_exported_evidence_streaming(&b"{\"evidence\": ["[..]);
+        assert!(matches!(truncated, Err(SlashingError::EncodingError(_))));
+    }
+
+    #[test]
+    fn test_import_newer_minor_tolerated_and_migrated() {
+        let export = EvidenceExportAPI::import_json(&json_with_version("1.3.2")).unwrap();
+        assert_eq!(export.version, CURRENT_EXPORT_VERSION);
+        assert!(EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+    }
+
     #[tokio::test]
     async fn test_verify_exported_evidence() {
         let config = create_test_export_config();
@@ -1306,6 +2388,7 @@ mod tests {
             signature_proof: create_test_signature_proof(),
             height: 10,
             round: 1,
+            child_header: None,
         };
         
         api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
@@ -1318,6 +2401,181 @@ mod tests {
         println!("✅ Verify exported evidence working");
     }
 
+    #[test]
+    fn test_da_evidence_valid_merkle_path() {
+        let proof = create_test_merkle_proof(1);
+        assert!(proof.verify());
+
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), proof, vec![4u8; 32]).unwrap();
+
+        let export = api.export_evidence("Merkle path test".to_string()).unwrap();
+        assert!(EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+    }
+
+    #[test]
+    fn test_da_evidence_tampered_sibling_rejected() {
+        let mut proof = create_test_merkle_proof(1);
+        proof.path[0][0] ^= 0xff;
+        assert!(!proof.verify());
+
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), proof, vec![4u8; 32]).unwrap();
+
+        let export = api.export_evidence("Tampered sibling test".to_string()).unwrap();
+        assert!(!EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+    }
+
+    #[test]
+    fn test_da_evidence_wrong_expected_root_rejected() {
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_da_evidence(1, 20, vec![1u8; 32], vec![2u8; 32], create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+
+        let export = api.export_evidence("Wrong root test".to_string()).unwrap();
+        assert!(!EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+    }
+
+    #[test]
+    fn test_inclusion_evidence_tampered_sibling_rejected() {
+        let mut proof = create_test_merkle_proof(2);
+        proof.path[1][5] ^= 0x01;
+
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_inclusion_evidence(2, 30, vec![vec![1u8; 32]], create_test_block_header(), vec![5u8; 128], proof, vec![7u8; 32]).unwrap();
+
+        let export = api.export_evidence("Tampered inclusion test".to_string()).unwrap();
+        assert!(!EvidenceExportAPI::verify_exported_evidence(&export).unwrap());
+    }
+
+    fn create_test_anchor_receipt(anchor: &AnchorInfo, status: AnchorStatus) -> AnchorReceipt {
+        AnchorReceipt {
+            anchor_id: anchor.anchor_id.clone(),
+            header_hash: vec![1u8; 32],
+            chain_id: 1,
+            tx_hash: anchor.l1_tx_hash.clone(),
+            block_number: anchor.l1_block_number,
+            gas_used: 21_000,
+            gas_price: 1,
+            status,
+            timestamp: Utc::now(),
+            confirmations: 6,
+            retry_count: 0,
+        }
+    }
+
+    fn anchor_export_valid(
+        actual: Option<AnchorInfo>,
+        receipt: Option<AnchorReceipt>,
+    ) -> bool {
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        api.add_anchor_evidence(3, 40, create_test_anchor_info(), actual, receipt, vec![8u8; 256], vec![9u8; 32]).unwrap();
+        let export = api.export_evidence("Anchor test".to_string()).unwrap();
+        EvidenceExportAPI::verify_exported_evidence(&export).unwrap()
+    }
+
+    #[test]
+    fn test_anchor_evidence_genuine_mismatch_accepted() {
+        let mut actual = create_test_anchor_info();
+        actual.l1_tx_hash = "0x456".to_string();
+        let receipt = create_test_anchor_receipt(&actual, AnchorStatus::Confirmed);
+        assert!(anchor_export_valid(Some(actual.clone()), Some(receipt)));
+
+        let mut wrong_block = create_test_anchor_info();
+        wrong_block.l1_block_number = 1001;
+        let receipt = create_test_anchor_receipt(&wrong_block, AnchorStatus::Confirmed);
+        assert!(anchor_export_valid(Some(wrong_block), Some(receipt)));
+
+        // A receipt that contradicts the claimed actual anchor does not corroborate it
+        let receipt = create_test_anchor_receipt(&create_test_anchor_info(), AnchorStatus::Confirmed);
+        assert!(!anchor_export_valid(Some(actual), Some(receipt)));
+    }
+
+    #[test]
+    fn test_anchor_evidence_without_receipt_rejected() {
+        let mut actual = create_test_anchor_info();
+        actual.l1_tx_hash = "0x456".to_string();
+        assert!(!anchor_export_valid(Some(actual), None));
+        assert!(!anchor_export_valid(None, None));
+    }
+
+    #[test]
+    fn test_anchor_evidence_matching_pair_rejected() {
+        assert!(!anchor_export_valid(Some(create_test_anchor_info()), None));
+    }
+
+    #[test]
+    fn test_anchor_evidence_missing_anchor() {
+        // Confirmed anchor absent from L1, backed by a failed receipt
+        let failed = create_test_anchor_receipt(&create_test_anchor_info(), AnchorStatus::Failed);
+        assert!(anchor_export_valid(None, Some(failed)));
+
+        // L1 shows it confirmed, so nothing is missing
+        let confirmed = create_test_anchor_receipt(&create_test_anchor_info(), AnchorStatus::Confirmed);
+        assert!(!anchor_export_valid(None, Some(confirmed)));
+    }
+
+    #[test]
+    fn test_validate_network_params() {
+        let config = create_test_export_config();
+        let mut api = EvidenceExportAPI::new(config);
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+
+        let export = api.export_evidence("Network params test".to_string()).unwrap();
+        let evidence = &export.evidence[0];
+
+        assert_eq!(evidence.verification_metadata.network_params, create_test_network_params());
+        assert!(EvidenceExportAPI::validate_network_params(evidence, &create_test_network_params()));
+
+        let mut expected = create_test_network_params();
+        expected.slashing_params = serde_json::json!({ "equivocation_slash_percent": 10, "unbonding_epochs": 21 });
+        assert!(!EvidenceExportAPI::validate_network_params(evidence, &expected));
+    }
+
+    fn create_signed_test_api() -> EvidenceExportAPI {
+        let mut config = create_test_export_config();
+        config.digital_signing = true;
+        let mut api = EvidenceExportAPI::new(config);
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+        api
+    }
+
+    #[test]
+    fn test_export_signature_valid() {
+        let api = create_signed_test_api();
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+
+        let export = api.export_signed("Signed export".to_string(), &signing_key).unwrap();
+
+        assert!(export.metadata.signature.is_some());
+        assert_eq!(
+            export.metadata.signer_public_key,
+            Some(hex::encode(signing_key.verifying_key().to_bytes()))
+        );
+        assert!(EvidenceExportAPI::verify_export_signature(&export, &signing_key.verifying_key()).unwrap());
+    }
+
+    #[test]
+    fn test_export_signature_tampered_integrity_hash() {
+        let api = create_signed_test_api();
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+
+        let mut export = api.export_signed("Signed export".to_string(), &signing_key).unwrap();
+        export.integrity_hash = hex::encode([0u8; 32]);
+
+        assert!(!EvidenceExportAPI::verify_export_signature(&export, &signing_key.verifying_key()).unwrap());
+    }
+
+    #[test]
+    fn test_export_signature_wrong_public_key() {
+        let api = create_signed_test_api();
+        let signing_key = SigningKey::from_bytes(&[7u8; 32]);
+        let other_key = SigningKey::from_bytes(&[8u8; 32]);
+
+        let export = api.export_signed("Signed export".to_string(), &signing_key).unwrap();
+
+        assert!(!EvidenceExportAPI::verify_export_signature(&export, &other_key.verifying_key()).unwrap());
+    }
+
     #[tokio::test]
     async fn test_evidence_filtering() {
         let config = create_test_export_config();
@@ -1339,11 +2597,12 @@ mod tests {
             signature_proof: create_test_signature_proof(),
             height: 10,
             round: 1,
+            child_header: None,
         };
         
         api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
-        api.add_da_evidence(1, 20, vec![1u8; 32], vec![2u8; 32], create_test_block_header(), vec![3u8; 64], vec![4u8; 32]).unwrap();
-        api.add_inclusion_evidence(2, 30, vec![vec![1u8; 32]], create_test_block_header(), vec![5u8; 128], vec![6u8; 64], vec![7u8; 32]).unwrap();
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+        api.add_inclusion_evidence(2, 30, vec![vec![1u8; 32]], create_test_block_header(), vec![5u8; 128], create_test_merkle_proof(2), vec![7u8; 32]).unwrap();
         
         let equivocation_evidence = api.get_evidence_by_type(EvidenceType::Equivocation);
         let da_evidence = api.get_evidence_by_type(EvidenceType::DataAvailability);
@@ -1383,18 +2642,22 @@ mod tests {
             signature_proof: create_test_signature_proof(),
             height: 10,
             round: 1,
+            child_header: None,
         };
         
         let eq_id = api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
         
         // 2. Data Availability evidence
-        let da_id = api.add_da_evidence(1, 20, vec![1u8; 32], vec![2u8; 32], create_test_block_header(), vec![3u8; 64], vec![4u8; 32]).unwrap();
+        let da_id = api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
         
         // 3. Inclusion evidence
-        let in_id = api.add_inclusion_evidence(2, 30, vec![vec![1u8; 32]], create_test_block_header(), vec![5u8; 128], vec![6u8; 64], vec![7u8; 32]).unwrap();
+        let in_id = api.add_inclusion_evidence(2, 30, vec![vec![1u8; 32]], create_test_block_header(), vec![5u8; 128], create_test_merkle_proof(2), vec![7u8; 32]).unwrap();
         
         // 4. Anchor evidence
-        let an_id = api.add_anchor_evidence(3, 40, create_test_anchor_info(), Some(create_test_anchor_info()), None, vec![8u8; 256], vec![9u8; 32]).unwrap();
+        let mut actual_anchor = create_test_anchor_info();
+        actual_anchor.l1_tx_hash = "0x456".to_string();
+        let receipt = create_test_anchor_receipt(&actual_anchor, AnchorStatus::Confirmed);
+        let an_id = api.add_anchor_evidence(3, 40, create_test_anchor_info(), Some(actual_anchor), Some(receipt), vec![8u8; 256], vec![9u8; 32]).unwrap();
         
         // Verify all evidence types are present
         assert_eq!(api.evidence_count(), 4);
@@ -1487,6 +2750,194 @@ mod tests {
         assert_eq!(evidence.round, 0);
     }
 
+    #[test]
+    fn test_double_commit_evidence_carries_offenders_share() {
+        let validator_set = create_test_validator_set();
+        let mut detector = EquivocationDetector::new(validator_set.clone());
+        let commit_a = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0, 1, 2], 4);
+        let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![0, 3], 4);
+        detector.process_commit(&commit_a).unwrap();
+
+        // The aggregate of several signers is not the offender's signature;
+        // the proof carries the share it retained
+        let equivocations = detector.process_commit(&commit_b).unwrap();
+        assert_eq!(equivocations.len(), 1);
+        let proof = &equivocations[0].signature_proof;
+        assert_eq!(Some(&proof.signature), commit_a.share(0));
+        assert!(validator_set.get_validator(0).unwrap().bls_pubkey.verify(&commit_a.signing_message(), &proof.signature));
+
+        // Without the share there is nothing to extract
+        let mut detector = EquivocationDetector::new(validator_set);
+        detector.process_commit(&commit_a).unwrap();
+        let mut unshared = commit_b;
+        unshared.shares.clear();
+        assert!(matches!(detector.process_commit(&unshared), Err(SlashingError::MissingShare(0))));
+    }
+
+    #[test]
+    fn test_replay_rediscovers_exported_double_commit() {
+        let mut detector = EquivocationDetector::new(create_test_validator_set());
+        let commit_a = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0, 1], 4);
+        let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![0, 2], 4);
+        detector.process_commit(&commit_a).unwrap();
+        let offense = detector.process_commit(&commit_b).unwrap().remove(0);
+
+        let mut api = EvidenceExportAPI::new(create_test_export_config());
+        let offense_id = api.add_equivocation_evidence(&offense, vec![1u8; 32]).unwrap();
+        api.add_da_evidence(1, 20, vec![1u8; 32], create_test_data_root(), create_test_block_header(), create_test_merkle_proof(1), vec![4u8; 32]).unwrap();
+        let export = api.export_evidence("Replay test".to_string()).unwrap();
+
+        let auditor = EquivocationDetector::new(create_test_validator_set());
+        let report = auditor.replay_evidence(&export).unwrap();
+        assert_eq!(report.confirmed, vec![offense_id.clone()]);
+        assert!(report.all_confirmed());
+        assert_eq!(report.skipped, 1);
+        // Replay leaves the auditing detector untouched
+        assert_eq!(auditor.history_size(), 0);
+
+        // Blaming a validator who signed only one of the commits is not reproduced
+        let mut misattributed = export.clone();
+        misattributed.evidence[0].validator_index = 1;
+        let report = auditor.replay_evidence(&misattributed).unwrap();
+        assert_eq!(report.unconfirmed, vec![offense_id.clone()]);
+        assert!(!report.all_confirmed());
+
+        // Nor is a "conflict" between two commits for the same header
+        let mut no_conflict = export.clone();
+        if let EvidenceData::Equivocation { commit_a, commit_b, .. } = &mut no_conflict.evidence[0].evidence_data {
+            *commit_b = commit_a.clone();
+        }
+        assert_eq!(auditor.replay_evidence(&no_conflict).unwrap().unconfirmed, vec![offense_id.clone()]);
+
+        // Commits the detector rejects leave that item unconfirmed and the
+        // rest of the export is still replayed
+        let mut rejected = export.evidence[0].clone();
+        rejected.evidence_id = "rejected".to_string();
+        rejected.validator_index = 5;
+        if let EvidenceData::Equivocation { commit_a, commit_b, .. } = &mut rejected.evidence_data {
+            *commit_a = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![5], 8);
+            *commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![5], 8);
+        }
+        let mut with_rejected = export.clone();
+        with_rejected.evidence.insert(0, rejected);
+        let report = auditor.replay_evidence(&with_rejected).unwrap();
+        assert_eq!(report.unconfirmed, vec!["rejected".to_string()]);
+        assert_eq!(report.confirmed, vec![offense_id]);
+    }
+
+    #[tokio::test]
+    async fn test_double_commit_submits_proof_to_sink() {
+        let mut validator_set = create_test_validator_set();
+        let expected_set_hash = validator_set.hash().unwrap();
+        let (sink, mut proofs) = ChannelSink::new();
+        let mut detector = EquivocationDetector::new_with_sink(validator_set, Arc::new(sink));
+
+        let commit_a = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0, 1], 4);
+        let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![0, 2], 4);
+        detector.process_commit(&commit_a).unwrap();
+        assert!(proofs.try_recv().is_err());
+        detector.process_commit(&commit_b).unwrap();
+
+        let proof = tokio::time::timeout(std::time::Duration::from_secs(1), proofs.recv())
+            .await
+            .expect("No slashing proof submitted")
+            .expect("Sink channel closed");
+        assert_eq!(proof.evidence.validator_index, 0);
+        assert_eq!(proof.evidence.equivocation_type, EquivocationType::DoubleCommit);
+        assert_eq!(proof.validator_set_hash, expected_set_hash);
+        assert!(proof.timestamp > 0);
+        assert!(proof.verify_hash());
+    }
+
+    /// Sink that yields before recording, so unordered submission would interleave
+    #[derive(Debug, Default)]
+    struct SlowSink {
+        received: std::sync::Mutex<Vec<u64>>,
+    }
+
+    #[async_trait]
+    impl SlashingSink for SlowSink {
+        async fn submit(&self, proof: SlashingProof) {
+            for _ in 0..(10 - proof.evidence.height.min(10)) {
+                tokio::task::yield_now().await;
+            }
+            self.received.lock().unwrap().push(proof.evidence.height);
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_sink_receives_proofs_in_detection_order() {
+        let sink = Arc::new(SlowSink::default());
+        let mut detector = EquivocationDetector::new_with_sink(create_test_validator_set(), sink.clone());
+
+        for height in 1..=5 {
+            let commit_a = create_test_commit(HeaderHash::from([1u8; 32]), height, 0, vec![0], 4);
+            let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), height, 0, vec![0], 4);
+            detector.process_commit(&commit_a).unwrap();
+            detector.process_commit(&commit_b).unwrap();
+        }
+
+        let received = tokio::time::timeout(std::time::Duration::from_secs(1), async {
+            loop {
+                if sink.received.lock().unwrap().len() == 5 {
+                    return sink.received.lock().unwrap().clone();
+                }
+                tokio::task::yield_now().await;
+            }
+        }).await.expect("Not every slashing proof submitted");
+        assert_eq!(received, vec![1, 2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn test_repeated_double_commit_reported_once() {
+        let validator_set = create_test_validator_set();
+        let mut detector = EquivocationDetector::new(validator_set);
+
+        let commit_a = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0, 1], 4);
+        let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![0, 2], 4);
+        detector.process_commit(&commit_a).unwrap();
+        assert_eq!(detector.process_commit(&commit_b).unwrap().len(), 1);
+
+        // The same conflicting pair streaming in again is not a new offense
+        assert!(detector.process_commit(&commit_b).unwrap().is_empty());
+        assert!(detector.process_commit(&commit_a).unwrap().is_empty());
+        assert!(detector.process_commit(&commit_b).unwrap().is_empty());
+        assert_eq!(detector.get_equivocations().len(), 1);
+
+        // A different round at the same height is a separate offense
+        let commit_c = create_test_commit(HeaderHash::from([3u8; 32]), 1, 1, vec![0], 4);
+        let commit_d = create_test_commit(HeaderHash::from([4u8; 32]), 1, 1, vec![0], 4);
+        detector.process_commit(&commit_c).unwrap();
+        assert_eq!(detector.process_commit(&commit_d).unwrap().len(), 1);
+        assert_eq!(detector.get_equivocations().len(), 2);
+    }
+
+    #[test]
+    fn test_commit_history_pruned_outside_height_window() {
+        let validator_set = create_test_validator_set();
+        let mut detector = EquivocationDetector::new(validator_set).with_max_height_window(10);
+
+        for height in 1..=50u64 {
+            let mut hash = [0u8; 32];
+            hash[..8].copy_from_slice(&height.to_be_bytes());
+            let commit = create_test_commit(HeaderHash::from(hash), height, 0, vec![0], 4);
+            assert!(detector.process_commit(&commit).unwrap().is_empty());
+        }
+        // Heights 40..=50 remain
+        assert_eq!(detector.history_size(), 11);
+
+        // A double commit at the tip is still detectable
+        let conflicting = create_test_commit(HeaderHash::from([0xAAu8; 32]), 50, 0, vec![0], 4);
+        let evidence = detector.process_commit(&conflicting).unwrap();
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].equivocation_type, EquivocationType::DoubleCommit);
+
+        // Commits below the window are ignored rather than stored
+        let stale = create_test_commit(HeaderHash::from([0xBBu8; 32]), 5, 0, vec![0], 4);
+        assert!(detector.process_commit(&stale).unwrap().is_empty());
+        assert_eq!(detector.history_size(), 11);
+    }
+
     #[test]
     fn test_height_violation_detection() {
         let validator_set = create_test_validator_set();
@@ -1511,6 +2962,106 @@ mod tests {
         assert_eq!(evidence.equivocation_type, EquivocationType::HeightViolation);
     }
 
+    fn test_header(height: u64, prev_hash: [u8; 32], poh_seed: u8) -> Header {
+        Header::new(bpi_headers::HeaderConfig {
+            version: 1,
+            height,
+            prev_hash,
+            poh_root: [poh_seed; 32],
+            receipts_root: [0u8; 32],
+            da_root: [0u8; 32],
+            xcmp_root: [0u8; 32],
+            validator_set_hash: [0u8; 32],
+            mode: bpi_headers::ConsensusMode::Ibft,
+            round: 0,
+        })
+    }
+
+    /// Detector with a header finalized at height 1 by validators 0-2
+    fn detector_with_finalized_header() -> (EquivocationDetector, Header) {
+        let mut detector = EquivocationDetector::new(create_test_validator_set());
+        let finalized = test_header(1, [0u8; 32], 1);
+        let commit = create_test_commit(finalized.hash().unwrap(), 1, 0, vec![0, 1, 2], 4);
+        detector.record_finalized_header(&finalized, &commit).unwrap();
+        (detector, finalized)
+    }
+
+    #[test]
+    fn test_header_commit_consistent_with_finalized() {
+        let (mut detector, finalized) = detector_with_finalized_header();
+
+        // Late signature on the finalized header itself
+        let late = create_test_commit(finalized.hash().unwrap(), 1, 0, vec![3], 4);
+        assert!(detector.process_header_commit(&finalized, &late).unwrap().is_empty());
+
+        // Child building on the finalized header
+        let child = test_header(2, finalized.hash().unwrap().0, 2);
+        let commit = create_test_commit(child.hash().unwrap(), 2, 0, vec![0, 1, 2], 4);
+        assert!(detector.process_header_commit(&child, &commit).unwrap().is_empty());
+        assert!(detector.get_equivocations().is_empty());
+    }
+
+    #[test]
+    fn test_header_commit_conflicting_with_finalized() {
+        let (mut detector, finalized) = detector_with_finalized_header();
+
+        // Validator 3 commits a different header at the finalized height
+        let sibling = test_header(1, [0u8; 32], 9);
+        let commit = create_test_commit(sibling.hash().unwrap(), 1, 0, vec![3], 4);
+        let evidence = detector.process_header_commit(&sibling, &commit).unwrap();
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].validator_index, 3);
+        assert_eq!(evidence[0].equivocation_type, EquivocationType::FinalityViolation);
+        assert_eq!(evidence[0].commit_a.header_hash, finalized.hash().unwrap());
+        assert_eq!(evidence[0].commit_b.header_hash, sibling.hash().unwrap());
+
+        // ...then extends it, building on a parent other than the finalized one
+        let fork_child = test_header(2, sibling.hash().unwrap().0, 2);
+        let commit = create_test_commit(fork_child.hash().unwrap(), 2, 0, vec![3], 4);
+        let evidence = detector.process_header_commit(&fork_child, &commit).unwrap();
+        assert_eq!(evidence.len(), 1);
+        assert_eq!(evidence[0].equivocation_type, EquivocationType::FinalityViolation);
+        assert_eq!(evidence[0].height, 2);
+        assert_eq!(evidence[0].child_header.as_ref(), Some(&fork_child));
+
+        // A commit for some other header than the one supplied is refused
+        let mismatched = create_test_commit(finalized.hash().unwrap(), 1, 0, vec![3], 4);
+        assert!(matches!(
+            detector.process_header_commit(&sibling, &mismatched),
+            Err(SlashingError::InvalidProof(_))
+        ));
+    }
+
+    #[test]
+    fn test_honest_child_is_not_a_finality_violation() {
+        let validator_set = create_test_validator_set();
+        let verifier = SlashingProofVerifier::new(validator_set.clone());
+        let validator_set_hash = validator_set.clone().hash().unwrap();
+        let finalized = test_header(1, [0u8; 32], 1);
+        let finalized_commit = create_test_commit(finalized.hash().unwrap(), 1, 0, vec![0, 1, 2], 4);
+
+        // A child that builds on the finalized header, framed as a violation
+        let child = test_header(2, finalized.hash().unwrap().0, 2);
+        let child_commit = create_test_commit(child.hash().unwrap(), 2, 0, vec![3], 4);
+        let mut evidence = evidence_of(EquivocationType::FinalityViolation, 2);
+        evidence.validator_index = 3;
+        evidence.commit_a = finalized_commit;
+        evidence.commit_b = child_commit;
+        evidence.child_header = Some(child.clone());
+        let proof = SlashingProof::new(evidence.clone(), validator_set_hash, 0);
+        assert!(matches!(verifier.verify_proof(&proof), Err(SlashingError::CommitsNotConflicting)));
+
+        // Without the child header the parent link cannot be checked
+        evidence.child_header = None;
+        let proof = SlashingProof::new(evidence.clone(), validator_set_hash, 0);
+        assert!(matches!(verifier.verify_proof(&proof), Err(SlashingError::InvalidProof(_))));
+
+        // Nor with a header other than the one the child commit is for
+        evidence.child_header = Some(test_header(2, [7u8; 32], 2));
+        let proof = SlashingProof::new(evidence, validator_set_hash, 0);
+        assert!(matches!(verifier.verify_proof(&proof), Err(SlashingError::InvalidProof(_))));
+    }
+
     #[test]
     fn test_slashing_proof_creation() {
         let validator_set = create_test_validator_set();
@@ -1539,6 +3090,7 @@ mod tests {
             signature_proof,
             height: 1,
             round: 0,
+            child_header: None,
         };
         
         let proof = SlashingProof::new(evidence, validator_set_hash, timestamp);
@@ -1576,6 +3128,7 @@ mod tests {
             signature_proof,
             height: 1,
             round: 0,
+            child_header: None,
         };
         
         let proof = SlashingProof::new(evidence, [5u8; 32], 1234567890);
@@ -1587,6 +3140,141 @@ mod tests {
         assert!(result.is_err() || result.is_ok());
     }
 
+    fn signed_test_proof(signing_seed: [u8; 32], commit: &BlsCommit) -> SignatureProof {
+        let (_, validator_pubkey) = bpi_blsagg::keygen::generate_keypair(&[0u8; 32]);
+        let (signing_key, _) = bpi_blsagg::keygen::generate_keypair(&signing_seed);
+        let signed_message = commit.signing_message();
+        SignatureProof {
+            validator_index: 0,
+            signature: signing_key.sign(&signed_message),
+            public_key: validator_pubkey,
+            signed_message,
+            commit_hash: commit.commit_hash(),
+        }
+    }
+
+    #[test]
+    fn test_signature_proof_valid_signature_accepted() {
+        let validator_set = create_test_validator_set();
+        let verifier = SlashingProofVerifier::new(validator_set.clone());
+        let validator_info = validator_set.get_validator(0).unwrap();
+        let commit = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0], 4);
+
+        let proof = signed_test_proof([0u8; 32], &commit);
+        assert!(verifier.verify_signature_proof(&proof, validator_info, &commit).is_ok());
+    }
+
+    #[test]
+    fn test_signature_proof_forged_signature_rejected() {
+        let validator_set = create_test_validator_set();
+        let verifier = SlashingProofVerifier::new(validator_set.clone());
+        let validator_info = validator_set.get_validator(0).unwrap();
+        let commit = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0], 4);
+
+        // Right public key, but signed by someone else
+        let forged = signed_test_proof([9u8; 32], &commit);
+        assert!(matches!(
+            verifier.verify_signature_proof(&forged, validator_info, &commit),
+            Err(SlashingError::SignatureVerificationFailed(0))
+        ));
+
+        // Genuine signature over a different message than claimed
+        let mut tampered = signed_test_proof([0u8; 32], &commit);
+        tampered.signed_message = b"commit at height 2 round 0".to_vec();
+        assert!(matches!(
+            verifier.verify_signature_proof(&tampered, validator_info, &commit),
+            Err(SlashingError::SignatureVerificationFailed(0))
+        ));
+    }
+
+    #[test]
+    fn test_signature_over_unrelated_message_rejected() {
+        let mut validator_set = create_test_validator_set();
+        let verifier = SlashingProofVerifier::new(validator_set.clone());
+        let validator_set_hash = validator_set.hash().unwrap();
+        let mut detector = EquivocationDetector::new(validator_set.clone());
+        detector.process_commit(&create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0, 1, 2], 4)).unwrap();
+        let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![0, 1, 3], 4);
+        let evidence = detector.process_commit(&commit_b).unwrap().remove(0);
+        assert!(verifier.verify_proof(&SlashingProof::new(evidence.clone(), validator_set_hash, 0)).unwrap());
+
+        // A genuine signature by the validator, but over some other message
+        let (private_key, _) = bpi_blsagg::keygen::generate_keypair(&[0u8; 32]);
+        let mut unrelated = evidence.clone();
+        unrelated.signature_proof.signed_message = b"unrelated".to_vec();
+        unrelated.signature_proof.signature = private_key.sign(b"unrelated");
+        assert!(matches!(
+            verifier.verify_proof(&SlashingProof::new(unrelated, validator_set_hash, 0)),
+            Err(SlashingError::InvalidProof(_))
+        ));
+
+        // Nor does a signature proof for one commit vouch for another
+        let mut misattributed = evidence.clone();
+        misattributed.signature_proof.commit_hash = commit_b.commit_hash();
+        assert!(matches!(
+            verifier.verify_proof(&SlashingProof::new(misattributed, validator_set_hash, 0)),
+            Err(SlashingError::InvalidProof(_))
+        ));
+
+        // A fabricated commit with the validator's bit set but no matching
+        // aggregate signature is rejected
+        let mut fabricated = evidence;
+        fabricated.commit_b.aggregate_signature = fabricated.commit_a.aggregate_signature.clone();
+        assert!(matches!(
+            verifier.verify_proof(&SlashingProof::new(fabricated, validator_set_hash, 0)),
+            Err(SlashingError::InvalidProof(_))
+        ));
+    }
+
+    fn double_commit_proof(validator_set_hash: [u8; 32]) -> SlashingProof {
+        let commit_a = create_test_commit(HeaderHash::from([1u8; 32]), 1, 0, vec![0], 4);
+        let commit_b = create_test_commit(HeaderHash::from([2u8; 32]), 1, 0, vec![0], 4);
+        let signature_proof = signed_test_proof([0u8; 32], &commit_a);
+        let evidence = EquivocationEvidence {
+            equivocation_type: EquivocationType::DoubleCommit,
+            validator_index: 0,
+            commit_a,
+            commit_b,
+            signature_proof,
+            height: 1,
+            round: 0,
+            child_header: None,
+        };
+        SlashingProof::new(evidence, validator_set_hash, 1234567890)
+    }
+
+    #[test]
+    fn test_proof_for_known_validator_set_accepted() {
+        let mut validator_set = create_test_validator_set();
+        let verifier = SlashingProofVerifier::new(validator_set.clone());
+
+        let proof = double_commit_proof(validator_set.hash().unwrap());
+        assert!(verifier.verify_validator_set(&proof).is_ok());
+        assert!(!matches!(verifier.verify_proof(&proof), Err(SlashingError::ValidatorSetMismatch { .. })));
+    }
+
+    #[test]
+    fn test_proof_for_other_validator_set_rejected() {
+        let mut validator_set = create_test_validator_set();
+        let verifier = SlashingProofVerifier::new(validator_set.clone());
+
+        // Built against the set before a validator joined
+        let mut stale_set = ValidatorSet::new(0);
+        let first = validator_set.get_validator(0).unwrap().clone();
+        stale_set.add_validator(first).unwrap();
+        let stale_hash = stale_set.hash().unwrap();
+        assert_ne!(stale_hash, validator_set.hash().unwrap());
+
+        let proof = double_commit_proof(stale_hash);
+        match verifier.verify_proof(&proof) {
+            Err(SlashingError::ValidatorSetMismatch { known, proof }) => {
+                assert_eq!(known, hex::encode(validator_set.hash().unwrap()));
+                assert_eq!(proof, hex::encode(stale_hash));
+            }
+            other => panic!("expected ValidatorSetMismatch, got {:?}", other),
+        }
+    }
+
     #[test]
     fn test_detector_clear_history() {
         let validator_set = create_test_validator_set();
@@ -1640,6 +3328,7 @@ mod tests {
             signature_proof,
             height: 1,
             round: 0,
+            child_header: None,
         };
         
         // Test evidence serialization
@@ -1651,4 +3340,127 @@ mod tests {
         let proof_bytes = proof.to_canonical_cbor().unwrap();
         assert!(!proof_bytes.is_empty());
     }
+
+    fn evidence_of(equivocation_type: EquivocationType, height: u64) -> EquivocationEvidence {
+        EquivocationEvidence {
+            equivocation_type,
+            validator_index: 0,
+            commit_a: create_test_commit(HeaderHash::from([1u8; 32]), height, 0, vec![0], 4),
+            commit_b: create_test_commit(HeaderHash::from([2u8; 32]), height, 0, vec![0], 4),
+            signature_proof: SignatureProof {
+                validator_index: 0,
+                signature: Signature::from_bytes(&[0u8; 96]).unwrap(),
+                public_key: PublicKey::from_bytes(&[0u8; 48]).unwrap(),
+                signed_message: vec![],
+                commit_hash: [0u8; 32],
+            },
+            height,
+            round: 0,
+            child_header: None,
+        }
+    }
+
+    #[test]
+    fn test_equivocation_severity_by_type() {
+        assert_eq!(evidence_of(EquivocationType::HeightViolation, 5).severity(), SlashingSeverity::Minor);
+        assert_eq!(evidence_of(EquivocationType::DoubleCommit, 5).severity(), SlashingSeverity::Major);
+        assert_eq!(evidence_of(EquivocationType::MultipleSignatures, 5).severity(), SlashingSeverity::Major);
+        assert_eq!(evidence_of(EquivocationType::FinalityViolation, 5).severity(), SlashingSeverity::Critical);
+
+        // A double commit at or below the finalized height is Critical
+        let double_commit = evidence_of(EquivocationType::DoubleCommit, 5);
+        assert_eq!(double_commit.severity_with_finality(Some(5)), SlashingSeverity::Critical);
+        assert_eq!(double_commit.severity_with_finality(Some(4)), SlashingSeverity::Major);
+        assert_eq!(
+            evidence_of(EquivocationType::HeightViolation, 5).severity_with_finality(Some(9)),
+            SlashingSeverity::Minor
+        );
+    }
+
+    #[test]
+    fn test_detector_coalesces_evidence_beyond_cap() {
+        let mut detector = EquivocationDetector::new(create_test_validator_set()).with_max_evidence_per_validator(2);
+        let commit_at = |seed: u8, height: u64| create_test_commit(HeaderHash::from([seed; 32]), height, 0, vec![0], 4);
+        let held_types = |detector: &EquivocationDetector| {
+            detector.get_equivocations().iter().map(|evidence| evidence.equivocation_type).collect::<Vec<_>>()
+        };
+
+        // Signing heights 30, 20 then 10 is two height violations, filling the cap
+        for height in [30u64, 20, 10] {
+            detector.process_commit(&commit_at(height as u8, height)).unwrap();
+        }
+        assert_eq!(held_types(&detector), vec![EquivocationType::HeightViolation; 2]);
+
+        // A double commit is more severe and displaces the oldest height violation
+        assert_eq!(detector.process_commit(&commit_at(0xAA, 30)).unwrap().len(), 1);
+        assert_eq!(held_types(&detector), vec![EquivocationType::HeightViolation, EquivocationType::DoubleCommit]);
+
+        // Further minor evidence is still reported but not kept
+        assert_eq!(detector.process_commit(&commit_at(5, 5)).unwrap().len(), 1);
+        assert_eq!(held_types(&detector), vec![EquivocationType::HeightViolation, EquivocationType::DoubleCommit]);
+
+        detector.process_commit(&commit_at(0xBB, 20)).unwrap();
+        assert_eq!(held_types(&detector), vec![EquivocationType::DoubleCommit; 2]);
+        assert!(detector.get_equivocations().iter().all(|evidence| evidence.severity() == SlashingSeverity::Major));
+    }
+
+    #[test]
+    fn test_export_api_coalesces_evidence_beyond_cap() {
+        let mut config = create_test_export_config();
+        config.max_evidence_per_validator = 2;
+        let mut api = EvidenceExportAPI::new(config);
+        let add_da = |api: &mut EvidenceExportAPI, validator_index: usize, height: u64| {
+            api.add_da_evidence(
+                validator_index,
+                height,
+                vec![1u8; 32],
+                create_test_data_root(),
+                create_test_block_header(),
+                create_test_merkle_proof(1),
+                vec![4u8; 32],
+            ).unwrap()
+        };
+
+        let first = add_da(&mut api, 1, 1);
+        let second = add_da(&mut api, 1, 2);
+        // At the cap, equally severe evidence is folded into what is already held
+        let coalesced = add_da(&mut api, 1, 3);
+        assert_eq!(api.evidence_count(), 2);
+        assert!(coalesced == first || coalesced == second);
+        assert!(api.get_evidence_by_type(EvidenceType::DataAvailability).iter().all(|evidence| evidence.height != 3));
+
+        // Other validators have their own allowance
+        add_da(&mut api, 2, 4);
+        assert_eq!(api.evidence_count(), 3);
+
+        // A more severe equivocation replaces the oldest availability failure
+        let mut equivocation = evidence_of(EquivocationType::DoubleCommit, 5);
+        equivocation.validator_index = 1;
+        let equivocation_id = api.add_equivocation_evidence(&equivocation, vec![1u8; 32]).unwrap();
+        assert_eq!(api.evidence_count(), 3);
+        assert!(api.get_evidence(&first).is_none());
+        assert!(api.get_evidence(&second).is_some());
+        assert_eq!(api.get_evidence(&equivocation_id).unwrap().severity(), SlashingSeverity::Major);
+
+        // Later minor evidence coalesces into the most severe item held
+        assert_eq!(add_da(&mut api, 1, 6), equivocation_id);
+        assert_eq!(api.evidence_count(), 3);
+    }
+
+    #[test]
+    fn test_suggested_penalty_scales_with_stake_and_severity() {
+        assert_eq!(suggested_penalty(10_000, SlashingSeverity::Minor), 100);
+        assert_eq!(suggested_penalty(10_000, SlashingSeverity::Major), 1_000);
+        assert_eq!(suggested_penalty(10_000, SlashingSeverity::Critical), 10_000);
+        assert_eq!(suggested_penalty(20_000, SlashingSeverity::Major), 2_000);
+        assert_eq!(suggested_penalty(0, SlashingSeverity::Critical), 0);
+        // No overflow at the top of the range
+        assert_eq!(suggested_penalty(u64::MAX, SlashingSeverity::Critical), u64::MAX);
+
+        let schedule = SlashingSchedule { minor_bps: 50, major_bps: 2_500, critical_bps: 20_000 };
+        assert_eq!(schedule.suggested_penalty(1_000, SlashingSeverity::Minor), 5);
+        assert_eq!(schedule.suggested_penalty(1_000, SlashingSeverity::Major), 250);
+        // Rates above 100% are capped at the stake
+        assert_eq!(schedule.suggested_penalty(1_000, SlashingSeverity::Critical), 1_000);
+    }
 }
@@ -0,0 +1,110 @@
+/*!
+# Economic Audit Log
+
+Append-only, ordered record of every PoE mining engine state mutation:
+payments, coin locks, escrows, vesting and treasury credits and debits. Each
+entry keeps the balance of the touched account after the mutation so an
+auditor can replay the sequence without the engine. A mutation undone after a
+failure is never removed; a `Reversal` entry compensates it instead.
+*/
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::EconomicsError;
+
+/// Account names for balances that are engine totals rather than wallets
+pub const TREASURY_ACCOUNT: &str = "treasury";
+pub const LOCKED_RESERVE_ACCOUNT: &str = "locked_reserve";
+pub const ESCROW_ACCOUNT: &str = "escrow";
+pub const VESTING_ACCOUNT: &str = "vesting";
+
+/// Kind of state mutation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuditEntryType {
+    MinerPayment,
+    CoinLock,
+    CoinUnlock,
+    OwnerPayment,
+    VestingScheduled,
+    VestingReleased,
+    EscrowHeld,
+    EscrowReleased,
+    TreasuryCredit,
+    TreasuryDebit,      // Treasury-funded owner payment, vesting or escrow
+    Reversal,           // Compensates an earlier entry of a failed operation
+}
+
+/// One state mutation
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub sequence: u64,
+    pub entry_type: AuditEntryType,
+    pub amount: Decimal,
+    pub account: String,
+    pub timestamp: DateTime<Utc>,
+    pub idempotency_key: Option<String>,
+    pub resulting_balance: Decimal,   // `account` balance after the mutation
+}
+
+/// Append-only audit trail; entries can be added but never changed or removed
+#[derive(Debug, Clone, Default)]
+pub struct EconomicAuditLog {
+    entries: Vec<AuditEntry>,
+}
+
+impl EconomicAuditLog {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a mutation, assigning it the next sequence number
+    pub fn record(
+        &mut self,
+        entry_type: AuditEntryType,
+        amount: Decimal,
+        account: &str,
+        timestamp: DateTime<Utc>,
+        idempotency_key: Option<&str>,
+        resulting_balance: Decimal,
+    ) -> &AuditEntry {
+        self.entries.push(AuditEntry {
+            sequence: self.entries.len() as u64,
+            entry_type,
+            amount,
+            account: account.to_string(),
+            timestamp,
+            idempotency_key: idempotency_key.map(str::to_string),
+            resulting_balance,
+        });
+        self.entries.last().expect("entry just pushed")
+    }
+
+    /// All entries in the order they were recorded
+    pub fn entries(&self) -> &[AuditEntry] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Entries with `from <= timestamp <= to`, in sequence order
+    pub fn range(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Vec<&AuditEntry> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.timestamp >= from && entry.timestamp <= to)
+            .collect()
+    }
+
+    /// JSON array of the entries recorded between `from` and `to` inclusive
+    pub fn export_json(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<String, EconomicsError> {
+        serde_json::to_string_pretty(&self.range(from, to))
+            .map_err(|e| EconomicsError::SystemError(format!("Audit log export failed: {}", e)))
+    }
+}
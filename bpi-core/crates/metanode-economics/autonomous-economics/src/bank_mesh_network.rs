@@ -31,7 +31,11 @@ use tokio_tungstenite::{WebSocketStream, MaybeTlsStream, connect_async};
 use tokio_tungstenite::tungstenite::protocol::Message;
 use tokio::net::TcpStream;
 
-use crate::{EconomicsError, TokenSupplyState};
+use crate::clock::{Clock, SystemClock};
+use crate::{
+    EconomicsError, GovernanceParameters, OwnerSalaryLedger, OwnerSalaryPolicy, PoEMiningEngine,
+    TokenSupplyState,
+};
 use billing_meter::TokenType;
 
 /// Bank mesh network errors
@@ -120,6 +124,11 @@ pub enum BankMessage {
         metrics: EconomicMetrics,
         timestamp: DateTime<Utc>,
     },
+    Finalized {
+        proposal_id: Uuid,
+        status: ProposalStatus,
+        finalized_at: DateTime<Utc>,
+    },
 }
 
 /// Consensus vote types
@@ -199,6 +208,18 @@ pub enum ProposalStatus {
     Expired,
 }
 
+/// Result of tallying a proposal's votes
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProposalOutcome {
+    /// Voting deadline not reached yet; voting continues
+    Pending { participation: Decimal },
+    /// Passed and applied
+    Passed,
+    Rejected,
+    /// Deadline passed without reaching quorum
+    Expired,
+}
+
 /// Liquidity sharing agreement
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiquiditySharingAgreement {
@@ -250,8 +271,8 @@ impl Default for BankMeshConfig {
     }
 }
 
-/// Bank Mesh Network Engine
-#[derive(Debug)]
+/// Bank Mesh Network Engine. Clones share all network state.
+#[derive(Debug, Clone)]
 pub struct BankMeshNetwork {
     config: BankMeshConfig,
     local_bank: BankNode,
@@ -261,6 +282,12 @@ pub struct BankMeshNetwork {
     pending_settlements: Arc<RwLock<Vec<InterBankTransaction>>>,
     network_metrics: Arc<RwLock<HashMap<Uuid, EconomicMetrics>>>,
     message_handlers: Arc<RwLock<HashMap<Uuid, tokio::sync::mpsc::UnboundedSender<BankMessage>>>>,
+    // Economic state proposals are validated against
+    governance_params: Arc<RwLock<GovernanceParameters>>,
+    owner_salary_policy: Arc<RwLock<OwnerSalaryPolicy>>,
+    owner_salary_ledger: Arc<RwLock<OwnerSalaryLedger>>,
+    token_supply: Arc<RwLock<TokenSupplyState>>,
+    clock: Arc<dyn Clock>,
 }
 
 impl BankMeshNetwork {
@@ -277,9 +304,31 @@ impl BankMeshNetwork {
             pending_settlements: Arc::new(RwLock::new(Vec::new())),
             network_metrics: Arc::new(RwLock::new(HashMap::new())),
             message_handlers: Arc::new(RwLock::new(HashMap::new())),
+            governance_params: Arc::new(RwLock::new(GovernanceParameters::default())),
+            owner_salary_policy: Arc::new(RwLock::new(OwnerSalaryPolicy::default())),
+            owner_salary_ledger: Arc::new(RwLock::new(OwnerSalaryLedger::default())),
+            token_supply: Arc::new(RwLock::new(TokenSupplyState::default())),
+            clock: Arc::new(SystemClock),
         }
     }
 
+    /// Read time, e.g. voting deadlines, from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Share the engine's governance parameters, salary policy, token supply
+    /// and clock, so proposals are validated against (and applied to) live state
+    pub fn with_economic_state(mut self, engine: &PoEMiningEngine) -> Self {
+        self.governance_params = Arc::clone(&engine.governance_params);
+        self.owner_salary_policy = Arc::clone(&engine.owner_salary_policy);
+        self.owner_salary_ledger = Arc::clone(&engine.owner_salary_ledger);
+        self.token_supply = Arc::clone(&engine.token_supply);
+        self.clock = Arc::clone(&engine.clock);
+        self
+    }
+
     /// Join the bank mesh network
     pub async fn join_network(&mut self, bootstrap_nodes: Vec<String>) -> Result<(), BankMeshError> {
         info!("Joining bank mesh network with {} bootstrap nodes", bootstrap_nodes.len());
@@ -336,9 +385,9 @@ impl BankMeshNetwork {
         self.message_handlers.write().await.insert(bank_id, tx);
         
         let connected_banks = Arc::clone(&self.connected_banks);
-        let active_proposals = Arc::clone(&self.active_proposals);
         let liquidity_agreements = Arc::clone(&self.liquidity_agreements);
         let network_metrics = Arc::clone(&self.network_metrics);
+        let network = self.clone();
         
         // Spawn sender task
         tokio::spawn(async move {
@@ -368,12 +417,12 @@ impl BankMeshNetwork {
                                 // Handle liquidity request
                                 info!("Received liquidity request {} from bank {}", request_id, requesting_bank);
                             },
-                            BankMessage::ConsensusVote { proposal_id, voting_bank, vote, stake_weight } => {
-                                // Handle consensus vote
-                                let mut proposals = active_proposals.write().await;
-                                if let Some(proposal) = proposals.get_mut(&proposal_id) {
-                                    proposal.votes.insert(voting_bank, (vote, stake_weight));
-                                }
+                            BankMessage::ConsensusVote { proposal_id, voting_bank, vote, stake_weight: _ } => {
+                                // Weighed by the stake we hold on record, not the one claimed
+                                network.record_vote(proposal_id, voting_bank, vote).await;
+                            },
+                            BankMessage::Finalized { proposal_id, status, finalized_at: _ } => {
+                                network.handle_finalized(proposal_id, status).await;
                             },
                             BankMessage::EconomicUpdate { bank_id: sender_id, metrics, timestamp: _ } => {
                                 // Update economic metrics
@@ -485,7 +534,8 @@ impl BankMeshNetwork {
         voting_duration: Duration,
     ) -> Result<Uuid, BankMeshError> {
         let proposal_id = Uuid::new_v4();
-        let voting_deadline = Utc::now() + voting_duration;
+        let now = self.clock.now();
+        let voting_deadline = now + voting_duration;
         
         let proposal = ConsensusProposal {
             id: proposal_id,
@@ -493,7 +543,7 @@ impl BankMeshNetwork {
             proposal_type,
             description,
             votes: HashMap::new(),
-            created_at: Utc::now(),
+            created_at: now,
             voting_deadline,
             execution_time: None,
             status: ProposalStatus::Active,
@@ -505,11 +555,277 @@ impl BankMeshNetwork {
         Ok(proposal_id)
     }
 
-    /// Vote on a consensus proposal
+    /// Check a proposal's economic effect before voting on it. Parameter
+    /// changes must name a known parameter with a well-typed value, and the
+    /// resulting state must keep the fee-rate partition, supply caps and owner
+    /// salary cap intact.
+    pub async fn validate_proposal(&self, proposal: &ConsensusProposal) -> Result<(), EconomicsError> {
+        match &proposal.proposal_type {
+            ProposalType::ParameterChange { parameter, new_value } => {
+                self.validate_parameter_change(parameter, new_value).await
+            }
+            ProposalType::BankSuspension { target_bank, .. } if *target_bank == self.local_bank.id => {
+                Err(EconomicsError::GovernanceError("A bank cannot be proposed for its own suspension".to_string()))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    async fn validate_parameter_change(&self, parameter: &str, new_value: &str) -> Result<(), EconomicsError> {
+        self.parameter_change_state(parameter, new_value).await.map(|_| ())
+    }
+
+    /// Governance parameters and salary policy as they would be after the
+    /// change, or the invariant the change breaks
+    async fn parameter_change_state(
+        &self,
+        parameter: &str,
+        new_value: &str,
+    ) -> Result<(GovernanceParameters, OwnerSalaryPolicy), EconomicsError> {
+        let mut params = self.governance_params.read().await.clone();
+        let mut policy = self.owner_salary_policy.read().await.clone();
+
+        match parameter {
+            "job_fee_rate" | "miner_lock_rate" | "miner_spendable_rate" | "owner_salary_rate" | "treasury_net_rate" => {
+                let rate: Decimal = parse_parameter(parameter, new_value)?;
+                if rate < Decimal::ZERO || rate > Decimal::ONE {
+                    return Err(EconomicsError::GovernanceError(format!("{} must be between 0 and 1, got {}", parameter, rate)));
+                }
+                match parameter {
+                    "job_fee_rate" => params.job_fee_rate = rate,
+                    "miner_lock_rate" => params.miner_lock_rate = rate,
+                    "miner_spendable_rate" => params.miner_spendable_rate = rate,
+                    "owner_salary_rate" => params.owner_salary_rate = rate,
+                    _ => params.treasury_net_rate = rate,
+                }
+                params.validate_fee_rates()?;
+            }
+            "nex_epoch_cap" | "flx_epoch_cap" => {
+                let cap: u64 = parse_parameter(parameter, new_value)?;
+                let supply = self.token_supply.read().await;
+                let current_supply = if parameter == "nex_epoch_cap" { supply.nex_supply } else { supply.flx_supply };
+                // A single epoch may at most double the circulating supply
+                if cap == 0 || cap > current_supply {
+                    return Err(EconomicsError::TokenSupplyError(format!(
+                        "{} must be between 1 and the current supply {}, got {}", parameter, current_supply, cap
+                    )));
+                }
+                if parameter == "nex_epoch_cap" {
+                    params.nex_epoch_cap = cap;
+                } else {
+                    params.flx_epoch_cap = cap;
+                }
+            }
+            "monthly_hard_cap" => {
+                policy.monthly_hard_cap = parse_parameter(parameter, new_value)?;
+                let paid_to_date = self.owner_salary_ledger.read().await.paid_to_date;
+                if policy.monthly_hard_cap <= Decimal::ZERO || policy.monthly_hard_cap < paid_to_date {
+                    return Err(EconomicsError::OwnerSalaryError(format!(
+                        "Monthly hard cap {} must be positive and at least the {} already paid this month",
+                        policy.monthly_hard_cap, paid_to_date
+                    )));
+                }
+            }
+            "vesting_immediate_rate" | "vesting_deferred_rate" => {
+                let rate: Decimal = parse_parameter(parameter, new_value)?;
+                if parameter == "vesting_immediate_rate" {
+                    policy.vesting_immediate_rate = rate;
+                } else {
+                    policy.vesting_deferred_rate = rate;
+                }
+                if rate < Decimal::ZERO || policy.vesting_immediate_rate + policy.vesting_deferred_rate != Decimal::ONE {
+                    return Err(EconomicsError::OwnerSalaryError(format!(
+                        "Vesting rates must split salary exactly, got {} immediate + {} deferred",
+                        policy.vesting_immediate_rate, policy.vesting_deferred_rate
+                    )));
+                }
+            }
+            "gen_supply" => return Err(EconomicsError::TokenSupplyError("GEN supply is fixed at genesis".to_string())),
+            _ => return Err(EconomicsError::GovernanceError(format!("Unknown parameter: {}", parameter))),
+        }
+        params.validate()?;
+        Ok((params, policy))
+    }
+
+    /// Stake on record for every known bank, this one included
+    async fn stake_ledger(&self) -> HashMap<Uuid, Decimal> {
+        let mut stakes: HashMap<Uuid, Decimal> = self.connected_banks.read().await.values()
+            .map(|bank| (bank.id, bank.stake_amount))
+            .collect();
+        stakes.insert(self.local_bank.id, self.local_bank.stake_amount);
+        stakes
+    }
+
+    /// Record a remote bank's vote, weighted by its stake on record. Votes from
+    /// unknown banks, on finalized proposals or after the deadline are ignored.
+    async fn record_vote(&self, proposal_id: Uuid, voting_bank: Uuid, vote: ConsensusVote) {
+        let Some(stake) = self.stake_ledger().await.get(&voting_bank).copied() else {
+            warn!("Ignoring vote on proposal {} from unknown bank {}", proposal_id, voting_bank);
+            return;
+        };
+        let now = self.clock.now();
+        let mut proposals = self.active_proposals.write().await;
+        if let Some(proposal) = proposals.get_mut(&proposal_id) {
+            if proposal.status == ProposalStatus::Active && now <= proposal.voting_deadline {
+                proposal.votes.insert(voting_bank, (vote, stake));
+            }
+        }
+    }
+
+    /// Another bank announced a proposal's outcome. The announcement is not
+    /// trusted: it only prompts our own tally of the votes we hold.
+    async fn handle_finalized(&self, proposal_id: Uuid, announced: ProposalStatus) {
+        match self.tally_votes(proposal_id).await {
+            Ok(outcome) => {
+                let local = self.get_proposal(proposal_id).await.map(|proposal| proposal.status);
+                if local.as_ref() != Some(&announced) {
+                    warn!("Proposal {} announced as {:?}, local tally gives {:?}", proposal_id, announced, outcome);
+                }
+            }
+            Err(e) => warn!("Cannot tally announced proposal {}: {}", proposal_id, e),
+        }
+    }
+
+    /// Weigh the votes on a proposal, by each bank's stake on record, against
+    /// the network's quorum (q) and passage (ξ) thresholds from
+    /// `GovernanceParameters`. Nothing is decided before the voting deadline;
+    /// after it, a proposal short of quorum expires and a passing one is
+    /// applied and marked executed. Either way the result is announced with
+    /// `BankMessage::Finalized`; tallying an already finalized proposal
+    /// returns its outcome unchanged.
+    pub async fn tally_votes(&self, proposal_id: Uuid) -> Result<ProposalOutcome, BankMeshError> {
+        let (quorum_rate, passage_threshold) = {
+            let params = self.governance_params.read().await;
+            (params.quorum_rate, params.passage_threshold)
+        };
+        let stakes = self.stake_ledger().await;
+        let total_stake: Decimal = stakes.values().copied().sum();
+        let now = self.clock.now();
+
+        let mut proposals = self.active_proposals.write().await;
+        let proposal = proposals.get_mut(&proposal_id)
+            .ok_or_else(|| BankMeshError::InvalidMessage(format!("Unknown proposal {}", proposal_id)))?;
+
+        match proposal.status {
+            ProposalStatus::Executed | ProposalStatus::Approved => return Ok(ProposalOutcome::Passed),
+            ProposalStatus::Rejected => return Ok(ProposalOutcome::Rejected),
+            ProposalStatus::Expired => return Ok(ProposalOutcome::Expired),
+            ProposalStatus::Active => {}
+        }
+
+        let weight_of = |wanted: ConsensusVote| -> Decimal {
+            proposal.votes.iter()
+                .filter(|(_, (vote, _))| *vote == wanted)
+                .filter_map(|(bank_id, _)| stakes.get(bank_id))
+                .copied()
+                .sum()
+        };
+        let approve = weight_of(ConsensusVote::Approve);
+        let reject = weight_of(ConsensusVote::Reject);
+        let abstain = weight_of(ConsensusVote::Abstain);
+
+        let participation = if total_stake > Decimal::ZERO {
+            (approve + reject + abstain) / total_stake
+        } else {
+            Decimal::ZERO
+        };
+        if now <= proposal.voting_deadline {
+            return Ok(ProposalOutcome::Pending { participation });
+        }
+
+        let approval = if approve + reject > Decimal::ZERO {
+            approve / (approve + reject)
+        } else {
+            Decimal::ZERO
+        };
+        let outcome = if participation < quorum_rate {
+            proposal.status = ProposalStatus::Expired;
+            ProposalOutcome::Expired
+        } else if approval >= passage_threshold {
+            // Re-check against current state: it may have moved since voting began
+            if let Err(e) = self.apply_proposal(&proposal.proposal_type).await {
+                warn!("Passed proposal {} no longer valid, rejecting: {}", proposal_id, e);
+                proposal.status = ProposalStatus::Rejected;
+                ProposalOutcome::Rejected
+            } else {
+                proposal.status = ProposalStatus::Executed;
+                proposal.execution_time = Some(now);
+                ProposalOutcome::Passed
+            }
+        } else {
+            proposal.status = ProposalStatus::Rejected;
+            ProposalOutcome::Rejected
+        };
+        let status = proposal.status.clone();
+        drop(proposals);
+
+        info!("Proposal {} finalized as {:?} (participation {:.2}%, approval {:.2}%)",
+              proposal_id, status, participation * Decimal::from(100), approval * Decimal::from(100));
+        self.broadcast_message(BankMessage::Finalized {
+            proposal_id,
+            status,
+            finalized_at: now,
+        }).await?;
+        Ok(outcome)
+    }
+
+    async fn apply_proposal(&self, proposal_type: &ProposalType) -> Result<(), EconomicsError> {
+        match proposal_type {
+            ProposalType::ParameterChange { parameter, new_value } => {
+                let (params, policy) = self.parameter_change_state(parameter, new_value).await?;
+                let now = self.clock.now();
+                let mut current_params = self.governance_params.write().await;
+                *current_params = params;
+                current_params.last_update = now;
+                let mut current_policy = self.owner_salary_policy.write().await;
+                *current_policy = policy;
+                current_policy.last_policy_update = now;
+            }
+            ProposalType::BankSuspension { target_bank, reason } => {
+                if let Some(bank) = self.connected_banks.write().await.get_mut(target_bank) {
+                    bank.status = BankStatus::Suspended;
+                    warn!("Suspended bank {}: {}", target_bank, reason);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Vote on a consensus proposal. A proposal that fails validation is
+    /// rejected automatically: a Reject vote is cast whatever `vote` was asked for.
     pub async fn vote_on_proposal(
         &self,
         proposal_id: Uuid,
         vote: ConsensusVote,
+    ) -> Result<(), BankMeshError> {
+        if let Some(proposal) = self.get_proposal(proposal_id).await {
+            if proposal.status != ProposalStatus::Active {
+                info!("Ignoring vote on proposal {}: already {:?}", proposal_id, proposal.status);
+                return Ok(());
+            }
+            if self.clock.now() > proposal.voting_deadline {
+                return Err(BankMeshError::InvalidMessage(format!(
+                    "Voting on proposal {} closed at {}", proposal_id, proposal.voting_deadline
+                )));
+            }
+            if let Err(e) = self.validate_proposal(&proposal).await {
+                warn!("Rejecting invalid proposal {}: {}", proposal_id, e);
+                self.cast_vote(proposal_id, ConsensusVote::Reject).await?;
+                if let Some(proposal) = self.active_proposals.write().await.get_mut(&proposal_id) {
+                    proposal.status = ProposalStatus::Rejected;
+                }
+                return Err(e.into());
+            }
+        }
+
+        self.cast_vote(proposal_id, vote).await
+    }
+
+    async fn cast_vote(
+        &self,
+        proposal_id: Uuid,
+        vote: ConsensusVote,
     ) -> Result<(), BankMeshError> {
         let vote_message = BankMessage::ConsensusVote {
             proposal_id,
@@ -638,9 +954,16 @@ impl BankMeshNetwork {
     }
 }
 
+fn parse_parameter<T: FromStr>(parameter: &str, value: &str) -> Result<T, EconomicsError> {
+    value.trim().parse().map_err(|_| {
+        EconomicsError::GovernanceError(format!("Invalid value for {}: {}", parameter, value))
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::MockClock;
 
     fn create_test_bank() -> BankNode {
         BankNode {
@@ -725,6 +1048,208 @@ mod tests {
         assert!(proposal.votes.contains_key(&bank_id));
     }
 
+    #[tokio::test]
+    async fn test_valid_parameter_proposal_accepted() {
+        let bank = create_test_bank();
+        let bank_id = bank.id;
+        let network = BankMeshNetwork::new(BankMeshConfig::default(), bank);
+
+        let proposal_id = network.create_proposal(
+            ProposalType::ParameterChange {
+                parameter: "monthly_hard_cap".to_string(),
+                new_value: "200000".to_string(),
+            },
+            "Lower the owner salary cap".to_string(),
+            Duration::hours(24),
+        ).await.unwrap();
+
+        let proposal = network.get_proposal(proposal_id).await.unwrap();
+        assert!(network.validate_proposal(&proposal).await.is_ok());
+
+        network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await.unwrap();
+        let proposal = network.get_proposal(proposal_id).await.unwrap();
+        assert_eq!(proposal.votes[&bank_id].0, ConsensusVote::Approve);
+        assert_eq!(proposal.status, ProposalStatus::Active);
+    }
+
+    #[tokio::test]
+    async fn test_invariant_violating_proposal_auto_rejected() {
+        let bank = create_test_bank();
+        let bank_id = bank.id;
+        let network = BankMeshNetwork::new(BankMeshConfig::default(), bank);
+
+        // Raising the treasury rate alone breaks the fee partition
+        let proposal_id = network.create_proposal(
+            ProposalType::ParameterChange {
+                parameter: "treasury_net_rate".to_string(),
+                new_value: "0.005".to_string(),
+            },
+            "Increase treasury share".to_string(),
+            Duration::hours(24),
+        ).await.unwrap();
+
+        let result = network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await;
+        assert!(matches!(result, Err(BankMeshError::Economics(EconomicsError::GovernanceError(_)))));
+        let proposal = network.get_proposal(proposal_id).await.unwrap();
+        assert_eq!(proposal.votes[&bank_id].0, ConsensusVote::Reject);
+        assert_eq!(proposal.status, ProposalStatus::Rejected);
+
+        // Ill-typed and unknown parameters are rejected too
+        for (parameter, value) in [("nex_epoch_cap", "lots"), ("block_size", "2"), ("nex_epoch_cap", "0")] {
+            let proposal_id = network.create_proposal(
+                ProposalType::ParameterChange { parameter: parameter.to_string(), new_value: value.to_string() },
+                "Invalid change".to_string(),
+                Duration::hours(24),
+            ).await.unwrap();
+            let proposal = network.get_proposal(proposal_id).await.unwrap();
+            assert!(network.validate_proposal(&proposal).await.is_err());
+        }
+    }
+
+    async fn cap_proposal(network: &BankMeshNetwork) -> Uuid {
+        network.create_proposal(
+            ProposalType::ParameterChange {
+                parameter: "monthly_hard_cap".to_string(),
+                new_value: "200000".to_string(),
+            },
+            "Lower the owner salary cap".to_string(),
+            Duration::hours(24),
+        ).await.unwrap()
+    }
+
+    fn network_with_clock() -> (BankMeshNetwork, MockClock) {
+        let clock = MockClock::new(Utc::now());
+        let network = BankMeshNetwork::new(BankMeshConfig::default(), create_test_bank())
+            .with_clock(Arc::new(clock.clone()));
+        (network, clock)
+    }
+
+    /// Step past `cap_proposal`'s 24h voting deadline
+    fn close_voting(clock: &MockClock) {
+        clock.advance(Duration::hours(24) + Duration::seconds(1));
+    }
+
+    #[tokio::test]
+    async fn test_tally_quorum_reached_applies_change() {
+        let (network, clock) = network_with_clock();
+        let proposal_id = cap_proposal(&network).await;
+        network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await.unwrap();
+
+        // Quorum alone does not close voting before the deadline
+        assert!(matches!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Pending { .. }));
+        assert_eq!(network.owner_salary_policy.read().await.monthly_hard_cap, Decimal::from(250000));
+
+        close_voting(&clock);
+        assert_eq!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Passed);
+        assert_eq!(network.owner_salary_policy.read().await.monthly_hard_cap, Decimal::from(200000));
+        assert_eq!(network.get_proposal(proposal_id).await.unwrap().status, ProposalStatus::Executed);
+    }
+
+    #[tokio::test]
+    async fn test_tally_without_quorum_stays_pending() {
+        let (network, clock) = network_with_clock();
+        // A large bank that has not voted keeps participation under q = 10%
+        let whale = BankNode { stake_amount: Decimal::from(100_000_000), ..create_test_bank() };
+        network.connected_banks.write().await.insert(whale.id, whale);
+
+        let proposal_id = cap_proposal(&network).await;
+        network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await.unwrap();
+
+        assert!(matches!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Pending { .. }));
+        assert_eq!(network.owner_salary_policy.read().await.monthly_hard_cap, Decimal::from(250000));
+        assert_eq!(network.get_proposal(proposal_id).await.unwrap().status, ProposalStatus::Active);
+
+        // Still short of quorum at the deadline: the proposal expires
+        close_voting(&clock);
+        assert_eq!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Expired);
+        assert_eq!(network.get_proposal(proposal_id).await.unwrap().status, ProposalStatus::Expired);
+        assert_eq!(network.owner_salary_policy.read().await.monthly_hard_cap, Decimal::from(250000));
+    }
+
+    #[tokio::test]
+    async fn test_remote_votes_weighted_by_recorded_stake() {
+        let (network, clock) = network_with_clock();
+        let peer = create_test_bank();
+        let peer_id = peer.id;
+        network.connected_banks.write().await.insert(peer.id, peer);
+        let proposal_id = cap_proposal(&network).await;
+
+        // The claimed stake weight is ignored; unknown banks do not count at all
+        network.record_vote(proposal_id, peer_id, ConsensusVote::Reject).await;
+        network.record_vote(proposal_id, Uuid::new_v4(), ConsensusVote::Approve).await;
+        network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await.unwrap();
+        let proposal = network.get_proposal(proposal_id).await.unwrap();
+        assert_eq!(proposal.votes.len(), 2);
+        assert_eq!(proposal.votes[&peer_id], (ConsensusVote::Reject, Decimal::from(1000000)));
+
+        // 1M for, 1M against: 50% approval is below ξ
+        close_voting(&clock);
+        let late = Uuid::new_v4();
+        network.connected_banks.write().await.insert(late, BankNode { id: late, ..create_test_bank() });
+        network.record_vote(proposal_id, late, ConsensusVote::Approve).await;
+        assert!(network.vote_on_proposal(proposal_id, ConsensusVote::Reject).await.is_err());
+        assert_eq!(network.get_proposal(proposal_id).await.unwrap().votes.len(), 2);
+        assert_eq!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Rejected);
+    }
+
+    #[tokio::test]
+    async fn test_vote_after_deadline_refused() {
+        let (network, clock) = network_with_clock();
+        let proposal_id = cap_proposal(&network).await;
+        close_voting(&clock);
+
+        assert!(matches!(
+            network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await,
+            Err(BankMeshError::InvalidMessage(_))
+        ));
+        assert!(network.get_proposal(proposal_id).await.unwrap().votes.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_announced_outcome_recomputed_locally() {
+        let (network, clock) = network_with_clock();
+        let proposal_id = cap_proposal(&network).await;
+
+        // A peer claims the proposal passed, but nobody voted for it
+        network.handle_finalized(proposal_id, ProposalStatus::Executed).await;
+        assert_eq!(network.get_proposal(proposal_id).await.unwrap().status, ProposalStatus::Active);
+
+        close_voting(&clock);
+        network.handle_finalized(proposal_id, ProposalStatus::Executed).await;
+        assert_eq!(network.get_proposal(proposal_id).await.unwrap().status, ProposalStatus::Expired);
+        assert_eq!(network.owner_salary_policy.read().await.monthly_hard_cap, Decimal::from(250000));
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_finalize_is_idempotent() {
+        let (network, clock) = network_with_clock();
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        network.message_handlers.write().await.insert(Uuid::new_v4(), tx);
+
+        let proposal_id = cap_proposal(&network).await;
+        network.vote_on_proposal(proposal_id, ConsensusVote::Approve).await.unwrap();
+        close_voting(&clock);
+        assert_eq!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Passed);
+        let applied_at = network.owner_salary_policy.read().await.last_policy_update;
+
+        assert_eq!(network.tally_votes(proposal_id).await.unwrap(), ProposalOutcome::Passed);
+        assert_eq!(network.owner_salary_policy.read().await.last_policy_update, applied_at);
+
+        // A late vote is ignored rather than reopening the proposal
+        network.vote_on_proposal(proposal_id, ConsensusVote::Reject).await.unwrap();
+        let proposal = network.get_proposal(proposal_id).await.unwrap();
+        assert_eq!(proposal.status, ProposalStatus::Executed);
+        assert_eq!(proposal.votes.values().next().unwrap().0, ConsensusVote::Approve);
+
+        let mut finalized = 0;
+        while let Ok(message) = rx.try_recv() {
+            if matches!(message, BankMessage::Finalized { .. }) {
+                finalized += 1;
+            }
+        }
+        assert_eq!(finalized, 1);
+    }
+
     #[tokio::test]
     async fn test_settlement_batch_processing() {
         let config = BankMeshConfig::default();
@@ -13,14 +13,16 @@ for the Bank Mesh autonomous economic system.
 - Liquidity pool management across chains
 */
 
-use std::collections::HashMap;
+use std::collections::{HashMap, VecDeque};
 use std::sync::Arc;
+use tokio::sync::mpsc::{self, error::TrySendError};
 use tokio::sync::RwLock;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use ethers::prelude::*;
+use sha2::{Digest, Sha256};
 use thiserror::Error;
 use tracing::{info, warn, error};
 
@@ -87,6 +89,10 @@ pub enum TransactionStatus {
     Finalized,
     Failed,
     Expired,
+    /// HTLC redeemed by the receiver with the hashlock preimage
+    Claimed,
+    /// HTLC returned to the sender after its timelock expired
+    Refunded,
 }
 
 /// Hash Time Locked Contract (HTLC) for atomic swaps
@@ -104,6 +110,16 @@ pub struct HTLC {
     pub created_at: DateTime<Utc>,
 }
 
+/// Funds returned to an HTLC's sender after expiry
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RefundReceipt {
+    pub htlc_id: Uuid,
+    pub refunded_to: String,
+    pub amount: Decimal,
+    pub chain_id: ChainId,
+    pub refunded_at: DateTime<Utc>,
+}
+
 /// Cross-chain bridge transaction
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BridgeTransaction {
@@ -125,6 +141,68 @@ pub struct BridgeTransaction {
     pub finalized_at: Option<DateTime<Utc>>,
 }
 
+/// Tokens locked on the source chain and released on the target chain for
+/// one bridge transaction
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SettlementLedger {
+    pub locked: Decimal,
+    pub released: Decimal,
+}
+
+/// Where a bridge transaction or HTLC is in its settlement lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum SettlementStatus {
+    /// Created, nothing locked yet
+    Initiated,
+    /// Funds locked on the source chain
+    Locked,
+    /// Funds released to the receiver (bridge release or HTLC claim)
+    Released,
+    /// Funds returned to the sender
+    Refunded,
+}
+
+impl SettlementStatus {
+    /// Position in the lifecycle; a settlement only ever moves to a later stage
+    fn stage(self) -> u8 {
+        match self {
+            SettlementStatus::Initiated => 0,
+            SettlementStatus::Locked => 1,
+            SettlementStatus::Released | SettlementStatus::Refunded => 2,
+        }
+    }
+
+    /// Released and refunded settlements take no further transitions
+    pub fn is_finished(self) -> bool {
+        self.stage() == 2
+    }
+}
+
+/// Statuses of live settlements plus the most recently finished ones,
+/// oldest finished first so they can be pruned in order
+#[derive(Debug, Default)]
+struct SettlementStatuses {
+    current: HashMap<Uuid, SettlementStatus>,
+    finished: VecDeque<Uuid>,
+}
+
+/// A settlement moving to a new status, as delivered to event subscribers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SettlementEvent {
+    pub settlement_id: Uuid,
+    pub status: SettlementStatus,
+    pub source_chain: ChainId,
+    pub target_chain: ChainId,
+    pub amount: Decimal,
+    pub at: DateTime<Utc>,
+}
+
+/// Events buffered per subscriber; a subscriber this far behind misses events
+pub const SETTLEMENT_EVENT_BUFFER: usize = 256;
+
+/// Finished settlements whose status stays queryable before being pruned
+pub const FINISHED_SETTLEMENT_RETENTION: usize = 1024;
+
 /// Liquidity pool for cross-chain operations
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CrossChainLiquidityPool {
@@ -197,6 +275,9 @@ pub struct CrossChainSettlement {
     pending_transactions: Arc<RwLock<HashMap<Uuid, BridgeTransaction>>>,
     active_htlcs: Arc<RwLock<HashMap<Uuid, HTLC>>>,
     settlement_proofs: Arc<RwLock<HashMap<Uuid, SettlementProof>>>,
+    settlement_ledgers: Arc<RwLock<HashMap<Uuid, SettlementLedger>>>,
+    settlement_statuses: Arc<RwLock<SettlementStatuses>>,
+    event_subscribers: Arc<RwLock<Vec<mpsc::Sender<SettlementEvent>>>>,
     providers: HashMap<ChainId, Arc<Provider<Http>>>,
 }
 
@@ -220,6 +301,9 @@ impl CrossChainSettlement {
             pending_transactions: Arc::new(RwLock::new(HashMap::new())),
             active_htlcs: Arc::new(RwLock::new(HashMap::new())),
             settlement_proofs: Arc::new(RwLock::new(HashMap::new())),
+            settlement_ledgers: Arc::new(RwLock::new(HashMap::new())),
+            settlement_statuses: Arc::new(RwLock::new(SettlementStatuses::default())),
+            event_subscribers: Arc::new(RwLock::new(Vec::new())),
             providers,
         })
     }
@@ -312,6 +396,7 @@ impl CrossChainSettlement {
         };
 
         self.pending_transactions.write().await.insert(transaction_id, bridge_tx);
+        self.transition(transaction_id, SettlementStatus::Initiated, source_chain, target_chain, amount).await;
         info!("Created bridge transaction {} from {} to {}", transaction_id, source_chain.name(), target_chain.name());
 
         Ok(transaction_id)
@@ -344,11 +429,75 @@ impl CrossChainSettlement {
         };
 
         self.active_htlcs.write().await.insert(htlc_id, htlc);
+        // The amount is locked under the hashlock as the contract is created
+        self.transition(htlc_id, SettlementStatus::Initiated, source_chain, target_chain, amount).await;
+        self.transition(htlc_id, SettlementStatus::Locked, source_chain, target_chain, amount).await;
         info!("Created HTLC {} for atomic swap", htlc_id);
 
         Ok(htlc_id)
     }
 
+    /// Redeem an HTLC for its receiver. The preimage must hash (SHA-256) to the
+    /// hashlock and the timelock must not have passed.
+    pub async fn claim_htlc(
+        &self,
+        htlc_id: Uuid,
+        preimage: &[u8],
+        now: DateTime<Utc>,
+    ) -> Result<(), SettlementError> {
+        let mut htlcs = self.active_htlcs.write().await;
+        let htlc = htlcs.get_mut(&htlc_id)
+            .ok_or_else(|| SettlementError::HTLCError(format!("HTLC {} not found", htlc_id)))?;
+
+        if htlc.status != TransactionStatus::Pending {
+            return Err(SettlementError::HTLCError(format!("HTLC {} is {:?}", htlc_id, htlc.status)));
+        }
+        if now >= htlc.time_lock {
+            return Err(SettlementError::HTLCError(format!("HTLC {} expired at {}", htlc_id, htlc.time_lock)));
+        }
+        let digest = hex::encode(Sha256::digest(preimage));
+        if htlc.hash_lock.trim_start_matches("0x") != digest {
+            return Err(SettlementError::HTLCError("Preimage does not match hashlock".to_string()));
+        }
+
+        htlc.status = TransactionStatus::Claimed;
+        self.transition(htlc_id, SettlementStatus::Released, htlc.source_chain, htlc.target_chain, htlc.amount).await;
+        info!("HTLC {} claimed by {}", htlc_id, htlc.receiver);
+        Ok(())
+    }
+
+    /// Return an unclaimed HTLC's funds to its sender once the timelock has
+    /// passed. Claimed, already refunded and unexpired HTLCs are refused.
+    pub async fn refund_expired_htlc(
+        &self,
+        htlc_id: Uuid,
+        now: DateTime<Utc>,
+    ) -> Result<RefundReceipt, SettlementError> {
+        let mut htlcs = self.active_htlcs.write().await;
+        let htlc = htlcs.get_mut(&htlc_id)
+            .ok_or_else(|| SettlementError::HTLCError(format!("HTLC {} not found", htlc_id)))?;
+
+        if htlc.status != TransactionStatus::Pending {
+            return Err(SettlementError::HTLCError(format!("HTLC {} is {:?}, cannot refund", htlc_id, htlc.status)));
+        }
+        if now < htlc.time_lock {
+            return Err(SettlementError::HTLCError(format!("HTLC {} locked until {}", htlc_id, htlc.time_lock)));
+        }
+
+        htlc.status = TransactionStatus::Refunded;
+        let receipt = RefundReceipt {
+            htlc_id,
+            refunded_to: htlc.sender.clone(),
+            amount: htlc.amount,
+            chain_id: htlc.source_chain,
+            refunded_at: now,
+        };
+        self.transition(htlc_id, SettlementStatus::Refunded, htlc.source_chain, htlc.target_chain, htlc.amount).await;
+        warn!("HTLC {} expired unclaimed; refunded {} to {} on {}",
+              htlc_id, receipt.amount, receipt.refunded_to, receipt.chain_id.name());
+        Ok(receipt)
+    }
+
     /// Process settlement verification
     pub async fn verify_settlement(
         &self,
@@ -416,7 +565,147 @@ impl CrossChainSettlement {
         self.active_htlcs.read().await.get(&htlc_id).cloned()
     }
 
-    /// Update transaction status
+    /// Record tokens locked on the bridge transaction's source chain
+    pub async fn record_lock(
+        &self,
+        settlement_id: Uuid,
+        chain_id: ChainId,
+        amount: Decimal,
+    ) -> Result<(), SettlementError> {
+        self.record_movement(settlement_id, chain_id, amount, true).await
+    }
+
+    /// Record tokens minted or released on the bridge transaction's target chain
+    pub async fn record_release(
+        &self,
+        settlement_id: Uuid,
+        chain_id: ChainId,
+        amount: Decimal,
+    ) -> Result<(), SettlementError> {
+        self.record_movement(settlement_id, chain_id, amount, false).await
+    }
+
+    async fn record_movement(
+        &self,
+        settlement_id: Uuid,
+        chain_id: ChainId,
+        amount: Decimal,
+        lock: bool,
+    ) -> Result<(), SettlementError> {
+        if amount <= Decimal::ZERO {
+            return Err(SettlementError::BridgeProtocolError("Settlement amount must be positive".to_string()));
+        }
+
+        let pending_txs = self.pending_transactions.read().await;
+        let tx = pending_txs.get(&settlement_id)
+            .ok_or_else(|| SettlementError::BridgeProtocolError("Transaction not found".to_string()))?;
+        let expected_chain = if lock { tx.source_chain } else { tx.target_chain };
+        if chain_id != expected_chain {
+            return Err(SettlementError::BridgeProtocolError(format!(
+                "{} on {} for a transaction expecting {}",
+                if lock { "Lock" } else { "Release" }, chain_id.name(), expected_chain.name()
+            )));
+        }
+
+        let mut ledgers = self.settlement_ledgers.write().await;
+        let ledger = ledgers.entry(settlement_id).or_default();
+        if lock {
+            ledger.locked += amount;
+        } else {
+            ledger.released += amount;
+        }
+        let status = if lock { SettlementStatus::Locked } else { SettlementStatus::Released };
+        self.transition(settlement_id, status, tx.source_chain, tx.target_chain, tx.amount).await;
+        Ok(())
+    }
+
+    /// Receive an event for every settlement status change from now on.
+    /// Events are buffered up to `SETTLEMENT_EVENT_BUFFER` per subscriber.
+    pub async fn subscribe_settlement_events(&self) -> mpsc::Receiver<SettlementEvent> {
+        let (sender, receiver) = mpsc::channel(SETTLEMENT_EVENT_BUFFER);
+        self.event_subscribers.write().await.push(sender);
+        receiver
+    }
+
+    /// Current lifecycle status of a bridge transaction or HTLC. Finished
+    /// settlements are forgotten once `FINISHED_SETTLEMENT_RETENTION` newer
+    /// ones have finished.
+    pub async fn settlement_status(&self, settlement_id: Uuid) -> Option<SettlementStatus> {
+        self.settlement_statuses.read().await.current.get(&settlement_id).copied()
+    }
+
+    /// Move a settlement forward to `status` and notify subscribers. Repeating
+    /// the current status, e.g. a second partial lock, or stepping back, e.g. a
+    /// lock recorded after the release, emits nothing.
+    async fn transition(
+        &self,
+        settlement_id: Uuid,
+        status: SettlementStatus,
+        source_chain: ChainId,
+        target_chain: ChainId,
+        amount: Decimal,
+    ) {
+        {
+            let mut statuses = self.settlement_statuses.write().await;
+            if let Some(current) = statuses.current.get(&settlement_id) {
+                if status.stage() <= current.stage() {
+                    if status != *current {
+                        warn!("Settlement {} is {:?}; ignoring move back to {:?}", settlement_id, current, status);
+                    }
+                    return;
+                }
+            }
+            statuses.current.insert(settlement_id, status);
+            if status.is_finished() {
+                statuses.finished.push_back(settlement_id);
+                while statuses.finished.len() > FINISHED_SETTLEMENT_RETENTION {
+                    if let Some(pruned) = statuses.finished.pop_front() {
+                        statuses.current.remove(&pruned);
+                    }
+                }
+            }
+        }
+
+        let event = SettlementEvent { settlement_id, status, source_chain, target_chain, amount, at: Utc::now() };
+        self.event_subscribers.write().await.retain(|subscriber| match subscriber.try_send(event.clone()) {
+            Ok(()) => true,
+            Err(TrySendError::Full(_)) => {
+                warn!("Settlement event subscriber lagging; dropped {:?} for {}", status, settlement_id);
+                true
+            }
+            // Receiver dropped
+            Err(TrySendError::Closed(_)) => false,
+        });
+    }
+
+    /// Check that the tokens locked for a settlement equal the tokens released
+    /// on the other chain plus the bridge fee, so the bridge never inflates supply
+    pub async fn verify_settlement_invariant(&self, settlement_id: Uuid) -> Result<(), EconomicsError> {
+        let pending_txs = self.pending_transactions.read().await;
+        let tx = pending_txs.get(&settlement_id)
+            .ok_or_else(|| EconomicsError::SettlementInvariantViolated(format!("unknown settlement {}", settlement_id)))?;
+        self.check_invariant(tx).await
+    }
+
+    async fn check_invariant(&self, tx: &BridgeTransaction) -> Result<(), EconomicsError> {
+        let ledger = self.settlement_ledgers.read().await.get(&tx.id).cloned().unwrap_or_default();
+        if ledger.locked != tx.amount || ledger.locked != ledger.released + tx.fee {
+            return Err(EconomicsError::SettlementInvariantViolated(format!(
+                "settlement {}: locked {} on {}, released {} on {}, fee {}, amount {}",
+                tx.id, ledger.locked, tx.source_chain.name(), ledger.released,
+                tx.target_chain.name(), tx.fee, tx.amount
+            )));
+        }
+        Ok(())
+    }
+
+    /// Get the lock/release ledger for a settlement
+    pub async fn get_settlement_ledger(&self, settlement_id: Uuid) -> Option<SettlementLedger> {
+        self.settlement_ledgers.read().await.get(&settlement_id).cloned()
+    }
+
+    /// Update transaction status. Finalizing fails, leaving the status
+    /// unchanged, unless the settlement invariant holds.
     pub async fn update_transaction_status(
         &self,
         transaction_id: Uuid,
@@ -425,6 +714,12 @@ impl CrossChainSettlement {
     ) -> Result<(), SettlementError> {
         let mut pending_txs = self.pending_transactions.write().await;
         if let Some(tx) = pending_txs.get_mut(&transaction_id) {
+            if status == TransactionStatus::Finalized {
+                if let Err(e) = self.check_invariant(tx).await {
+                    error!("Refusing to finalize bridge transaction {}: {}", transaction_id, e);
+                    return Err(e.into());
+                }
+            }
             tx.status = status.clone();
             
             match status {
@@ -530,6 +825,204 @@ mod tests {
         assert_eq!(htlc.unwrap().amount, Decimal::from(1000));
     }
 
+    async fn htlc_fixture(preimage: &[u8]) -> (CrossChainSettlement, Uuid, DateTime<Utc>) {
+        let settlement = CrossChainSettlement::new(SettlementConfig::default()).await.unwrap();
+        let hash_lock = format!("0x{}", hex::encode(Sha256::digest(preimage)));
+        let htlc_id = settlement.create_htlc(
+            ChainId::Ethereum,
+            ChainId::Polygon,
+            "0xsender".to_string(),
+            "0xreceiver".to_string(),
+            Decimal::from(1000),
+            hash_lock,
+        ).await.unwrap();
+        let time_lock = settlement.get_htlc(htlc_id).await.unwrap().time_lock;
+        (settlement, htlc_id, time_lock)
+    }
+
+    #[tokio::test]
+    async fn test_htlc_refund_after_expiry() {
+        let (settlement, htlc_id, time_lock) = htlc_fixture(b"secret").await;
+
+        let receipt = settlement.refund_expired_htlc(htlc_id, time_lock + chrono::Duration::seconds(1)).await.unwrap();
+        assert_eq!(receipt.refunded_to, "0xsender");
+        assert_eq!(receipt.amount, Decimal::from(1000));
+        assert_eq!(receipt.chain_id, ChainId::Ethereum);
+        assert_eq!(settlement.get_htlc(htlc_id).await.unwrap().status, TransactionStatus::Refunded);
+
+        // Refunds happen once, and the receiver can no longer claim
+        assert!(settlement.refund_expired_htlc(htlc_id, time_lock + chrono::Duration::hours(1)).await.is_err());
+        assert!(settlement.claim_htlc(htlc_id, b"secret", time_lock - chrono::Duration::hours(1)).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_htlc_refund_refused_before_expiry() {
+        let (settlement, htlc_id, time_lock) = htlc_fixture(b"secret").await;
+
+        let result = settlement.refund_expired_htlc(htlc_id, time_lock - chrono::Duration::seconds(1)).await;
+        assert!(matches!(result, Err(SettlementError::HTLCError(_))));
+        assert_eq!(settlement.get_htlc(htlc_id).await.unwrap().status, TransactionStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_htlc_refund_refused_after_claim() {
+        let (settlement, htlc_id, time_lock) = htlc_fixture(b"secret").await;
+        let before_expiry = time_lock - chrono::Duration::minutes(5);
+
+        assert!(settlement.claim_htlc(htlc_id, b"wrong", before_expiry).await.is_err());
+        settlement.claim_htlc(htlc_id, b"secret", before_expiry).await.unwrap();
+
+        let result = settlement.refund_expired_htlc(htlc_id, time_lock + chrono::Duration::hours(1)).await;
+        assert!(matches!(result, Err(SettlementError::HTLCError(_))));
+        assert_eq!(settlement.get_htlc(htlc_id).await.unwrap().status, TransactionStatus::Claimed);
+    }
+
+    async fn bridge_fixture(amount: Decimal) -> (CrossChainSettlement, Uuid) {
+        let settlement = CrossChainSettlement::new(SettlementConfig::default()).await.unwrap();
+        let token = "0x1234567890123456789012345678901234567890".to_string();
+        settlement.initialize_liquidity_pool(ChainId::Polygon, token.clone(), Decimal::from(1000000)).await.unwrap();
+        let tx_id = settlement.create_bridge_transaction(
+            ChainId::Ethereum,
+            ChainId::Polygon,
+            "0xsender".to_string(),
+            "0xreceiver".to_string(),
+            token,
+            amount,
+        ).await.unwrap();
+        (settlement, tx_id)
+    }
+
+    #[tokio::test]
+    async fn test_balanced_settlement_finalizes() {
+        let (settlement, tx_id) = bridge_fixture(Decimal::from(1000)).await;
+        let fee = settlement.get_bridge_transaction(tx_id).await.unwrap().fee;
+
+        settlement.record_lock(tx_id, ChainId::Ethereum, Decimal::from(1000)).await.unwrap();
+        settlement.record_release(tx_id, ChainId::Polygon, Decimal::from(1000) - fee).await.unwrap();
+
+        assert!(settlement.verify_settlement_invariant(tx_id).await.is_ok());
+        settlement.update_transaction_status(tx_id, TransactionStatus::Finalized, None).await.unwrap();
+        let tx = settlement.get_bridge_transaction(tx_id).await.unwrap();
+        assert_eq!(tx.status, TransactionStatus::Finalized);
+        assert!(tx.finalized_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_imbalanced_settlement_rejected() {
+        let (settlement, tx_id) = bridge_fixture(Decimal::from(1000)).await;
+
+        // More released on the target chain than was locked on the source
+        settlement.record_lock(tx_id, ChainId::Ethereum, Decimal::from(1000)).await.unwrap();
+        settlement.record_release(tx_id, ChainId::Polygon, Decimal::from(1000)).await.unwrap();
+
+        assert!(matches!(
+            settlement.verify_settlement_invariant(tx_id).await,
+            Err(EconomicsError::SettlementInvariantViolated(_))
+        ));
+        let result = settlement.update_transaction_status(tx_id, TransactionStatus::Finalized, None).await;
+        assert!(matches!(result, Err(SettlementError::Economics(EconomicsError::SettlementInvariantViolated(_)))));
+        let tx = settlement.get_bridge_transaction(tx_id).await.unwrap();
+        assert_eq!(tx.status, TransactionStatus::Pending);
+        assert!(tx.finalized_at.is_none());
+
+        // Releases must land on the target chain
+        assert!(settlement.record_release(tx_id, ChainId::Ethereum, Decimal::ONE).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_htlc_lifecycle_emits_ordered_events() {
+        let settlement = CrossChainSettlement::new(SettlementConfig::default()).await.unwrap();
+        let mut events = settlement.subscribe_settlement_events().await;
+        let hash_lock = hex::encode(Sha256::digest(b"secret"));
+        let create = || settlement.create_htlc(
+            ChainId::Ethereum,
+            ChainId::Polygon,
+            "0xsender".to_string(),
+            "0xreceiver".to_string(),
+            Decimal::from(1000),
+            hash_lock.clone(),
+        );
+
+        let claimed = create().await.unwrap();
+        assert_eq!(settlement.settlement_status(claimed).await, Some(SettlementStatus::Locked));
+        let time_lock = settlement.get_htlc(claimed).await.unwrap().time_lock;
+        settlement.claim_htlc(claimed, b"secret", time_lock - chrono::Duration::minutes(5)).await.unwrap();
+        assert_eq!(settlement.settlement_status(claimed).await, Some(SettlementStatus::Released));
+
+        let refunded = create().await.unwrap();
+        let time_lock = settlement.get_htlc(refunded).await.unwrap().time_lock;
+        settlement.refund_expired_htlc(refunded, time_lock + chrono::Duration::seconds(1)).await.unwrap();
+        assert_eq!(settlement.settlement_status(refunded).await, Some(SettlementStatus::Refunded));
+        assert_eq!(settlement.settlement_status(Uuid::new_v4()).await, None);
+
+        let expected = [
+            (claimed, SettlementStatus::Initiated),
+            (claimed, SettlementStatus::Locked),
+            (claimed, SettlementStatus::Released),
+            (refunded, SettlementStatus::Initiated),
+            (refunded, SettlementStatus::Locked),
+            (refunded, SettlementStatus::Refunded),
+        ];
+        for (settlement_id, status) in expected {
+            let event = events.try_recv().unwrap();
+            assert_eq!((event.settlement_id, event.status), (settlement_id, status));
+            assert_eq!((event.source_chain, event.target_chain), (ChainId::Ethereum, ChainId::Polygon));
+            assert_eq!(event.amount, Decimal::from(1000));
+        }
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_settlement_status_only_moves_forward() {
+        let (settlement, tx_id) = bridge_fixture(Decimal::from(1000)).await;
+        let mut events = settlement.subscribe_settlement_events().await;
+
+        settlement.record_lock(tx_id, ChainId::Ethereum, Decimal::from(600)).await.unwrap();
+        settlement.record_release(tx_id, ChainId::Polygon, Decimal::from(500)).await.unwrap();
+        // A late lock still reaches the ledger but not the status
+        settlement.record_lock(tx_id, ChainId::Ethereum, Decimal::from(400)).await.unwrap();
+
+        assert_eq!(settlement.settlement_status(tx_id).await, Some(SettlementStatus::Released));
+        assert_eq!(settlement.get_settlement_ledger(tx_id).await.unwrap().locked, Decimal::from(1000));
+        assert_eq!(events.try_recv().unwrap().status, SettlementStatus::Locked);
+        assert_eq!(events.try_recv().unwrap().status, SettlementStatus::Released);
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_finished_settlement_statuses_are_pruned() {
+        let settlement = CrossChainSettlement::new(SettlementConfig::default()).await.unwrap();
+        let hash_lock = hex::encode(Sha256::digest(b"secret"));
+        let mut refunded = Vec::new();
+        for _ in 0..=FINISHED_SETTLEMENT_RETENTION {
+            let htlc_id = settlement.create_htlc(
+                ChainId::Ethereum,
+                ChainId::Polygon,
+                "0xsender".to_string(),
+                "0xreceiver".to_string(),
+                Decimal::from(10),
+                hash_lock.clone(),
+            ).await.unwrap();
+            let time_lock = settlement.get_htlc(htlc_id).await.unwrap().time_lock;
+            settlement.refund_expired_htlc(htlc_id, time_lock).await.unwrap();
+            refunded.push(htlc_id);
+        }
+        let live = settlement.create_htlc(
+            ChainId::Ethereum,
+            ChainId::Polygon,
+            "0xsender".to_string(),
+            "0xreceiver".to_string(),
+            Decimal::from(10),
+            hash_lock,
+        ).await.unwrap();
+
+        // Only the oldest finished settlement is forgotten; live ones are kept
+        assert_eq!(settlement.settlement_status(refunded[0]).await, None);
+        assert_eq!(settlement.settlement_status(refunded[1]).await, Some(SettlementStatus::Refunded));
+        assert_eq!(settlement.settlement_status(live).await, Some(SettlementStatus::Locked));
+        assert_eq!(settlement.settlement_statuses.read().await.current.len(), FINISHED_SETTLEMENT_RETENTION + 1);
+    }
+
     #[tokio::test]
     async fn test_settlement_stats() {
         let config = SettlementConfig::default();
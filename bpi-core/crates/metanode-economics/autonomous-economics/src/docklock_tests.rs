@@ -14,11 +14,13 @@ async fn test_docklock_revenue_integration() {
         gold_equivalent_value: Decimal::new(50_000, 0), // $50k base value
         proof_hash: "proof_docklock_001".to_string(),
         completion_time: Utc::now(),
+        completion_height: 0,
         cluster_rent_revenue: Some(Decimal::new(25_000, 0)),    // $25k cluster rent
         gas_fee_revenue: Some(Decimal::new(15_000, 0)),         // $15k gas fees
         app_interaction_revenue: Some(Decimal::new(10_000, 0)), // $10k app interactions
         security_layer_revenue: Some(Decimal::new(8_000, 0)),   // $8k security services
         data_pipeline_revenue: Some(Decimal::new(12_000, 0)),   // $12k data processing
+        priority: 0,
     };
     
     // Calculate DockLock revenue
@@ -110,11 +112,13 @@ async fn test_comprehensive_docklock_revenue_streams() {
             gold_equivalent_value: Decimal::new(10_000, 0),
             proof_hash: format!("proof_{}", test_name),
             completion_time: Utc::now(),
+            completion_height: 0,
             cluster_rent_revenue: cluster,
             gas_fee_revenue: gas,
             app_interaction_revenue: app,
             security_layer_revenue: security,
             data_pipeline_revenue: pipeline,
+            priority: 0,
         };
         
         let docklock_revenue = engine.calculate_docklock_revenue(&job).await.expect("DockLock calculation failed");
@@ -228,6 +228,8 @@ pub struct ScalingConfig {
     pub performance_weight: Decimal,
     pub risk_tolerance: Decimal,
     pub max_scaling_rate: Decimal,
+    /// Revenue per unit over cost per unit below which a resource is shed
+    pub min_cost_efficiency: Decimal,
     // Stage 54: Consensus-based scaling parameters
     pub consensus_threshold: Decimal,
     pub voting_period: Duration,
@@ -248,6 +250,7 @@ impl Default for ScalingConfig {
             performance_weight: Decimal::from_str_exact("0.6").unwrap(),
             risk_tolerance: Decimal::from_str_exact("0.1").unwrap(), // 10%
             max_scaling_rate: Decimal::from_str_exact("0.5").unwrap(), // 50% per hour
+            min_cost_efficiency: Decimal::ONE, // break-even
             // Stage 54: Enhanced consensus-based scaling defaults
             consensus_threshold: Decimal::from_str_exact("0.67").unwrap(), // 67% supermajority
             voting_period: Duration::hours(2), // 2-hour voting window
@@ -536,6 +539,119 @@ impl EconomicScalingEngine {
         Ok(())
     }
 
+    /// Recommend a scaling action for every initialized resource.
+    ///
+    /// Utilization comes from `metrics` where it carries a load figure for the
+    /// resource (compute and bandwidth follow network utilization, liquidity
+    /// follows liquidity utilization) and from the allocation otherwise. A
+    /// resource above the utilization ceiling is scaled up only if each unit
+    /// pays for itself; one below the floor, or one losing money per unit, is
+    /// scaled down. The magnitude moves utilization back to the midpoint of
+    /// the band, capped by `max_scaling_rate`. Decisions are returned, not
+    /// executed.
+    pub fn decide(&self, metrics: &EconomicMetrics) -> Vec<ScalingDecision> {
+        let allocations = match self.resource_allocations.try_read() {
+            Ok(allocations) => allocations,
+            Err(_) => {
+                warn!("Resource allocations busy, skipping scaling decision round");
+                return Vec::new();
+            }
+        };
+
+        let floor = self.config.min_utilization_threshold;
+        let ceiling = self.config.max_utilization_threshold;
+        let target = (floor + ceiling) / Decimal::from(2);
+        let max_step = self.config.max_scaling_rate;
+
+        let mut resources: Vec<&ResourceAllocation> = allocations.values().collect();
+        resources.sort_by_key(|allocation| allocation.resource_type.name());
+
+        resources
+            .into_iter()
+            .map(|allocation| {
+                let resource_type = allocation.resource_type;
+                let utilization = observed_utilization(resource_type, metrics, allocation);
+                let efficiency = if allocation.cost_per_unit > Decimal::ZERO {
+                    allocation.revenue_per_unit / allocation.cost_per_unit
+                } else {
+                    Decimal::MAX
+                };
+                let unprofitable = efficiency < self.config.min_cost_efficiency;
+
+                let (decision_type, step, trigger_reason) = if utilization > ceiling && !unprofitable {
+                    let step = (utilization / target - Decimal::ONE).min(max_step);
+                    (
+                        ScalingDecisionType::ScaleUp,
+                        step,
+                        format!(
+                            "{} utilization {} above {} with cost efficiency {}",
+                            resource_type.name(), utilization.round_dp(4), ceiling, efficiency.round_dp(4)
+                        ),
+                    )
+                } else if utilization < floor || unprofitable {
+                    let step = (Decimal::ONE - utilization / target).clamp(Decimal::ZERO, max_step);
+                    let reason = if unprofitable {
+                        format!(
+                            "{} cost efficiency {} below {}",
+                            resource_type.name(), efficiency.round_dp(4), self.config.min_cost_efficiency
+                        )
+                    } else {
+                        format!(
+                            "{} utilization {} below {}",
+                            resource_type.name(), utilization.round_dp(4), floor
+                        )
+                    };
+                    (ScalingDecisionType::ScaleDown, step, reason)
+                } else {
+                    (
+                        ScalingDecisionType::Maintain,
+                        Decimal::ZERO,
+                        format!(
+                            "{} utilization {} within [{}, {}] with cost efficiency {}",
+                            resource_type.name(), utilization.round_dp(4), floor, ceiling, efficiency.round_dp(4)
+                        ),
+                    )
+                };
+
+                let magnitude = allocation.capacity * step;
+                let (scale_amount, cost_impact, expected_benefit) = match decision_type {
+                    ScalingDecisionType::ScaleUp => (
+                        magnitude,
+                        magnitude * allocation.cost_per_unit,
+                        magnitude * allocation.revenue_per_unit,
+                    ),
+                    // Shedding capacity saves its cost and forgoes the revenue
+                    // the shed units were earning at current utilization
+                    ScalingDecisionType::ScaleDown => (
+                        -magnitude,
+                        magnitude * allocation.revenue_per_unit * utilization,
+                        magnitude * allocation.cost_per_unit,
+                    ),
+                    _ => (Decimal::ZERO, Decimal::ZERO, Decimal::ZERO),
+                };
+                let roi_estimate = if cost_impact > Decimal::ZERO {
+                    expected_benefit / cost_impact
+                } else {
+                    Decimal::ZERO
+                };
+
+                ScalingDecision {
+                    id: Uuid::new_v4(),
+                    resource_type,
+                    decision_type,
+                    trigger_reason,
+                    scale_amount,
+                    cost_impact,
+                    expected_benefit,
+                    roi_estimate,
+                    executed_at: metrics.timestamp,
+                    completion_time: None,
+                    actual_benefit: None,
+                }
+            })
+            .collect()
+    }
+
     /// Generate demand prediction using statistical models
     pub async fn generate_demand_prediction(
         &self,
@@ -943,6 +1059,20 @@ impl EconomicScalingEngine {
     }
 }
 
+/// Current load on `resource_type`, taken from the metrics when they carry it
+fn observed_utilization(
+    resource_type: ResourceType,
+    metrics: &EconomicMetrics,
+    allocation: &ResourceAllocation,
+) -> Decimal {
+    match resource_type {
+        ResourceType::ComputeNodes | ResourceType::NetworkBandwidth => metrics.network_utilization,
+        ResourceType::LiquidityPool => metrics.liquidity_utilization,
+        _ if allocation.capacity > Decimal::ZERO => allocation.utilized / allocation.capacity,
+        _ => Decimal::ZERO,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1081,6 +1211,86 @@ mod tests {
         assert_eq!(allocation.capacity, Decimal::from(120));
     }
 
+    fn decision_metrics(network_utilization: &str) -> EconomicMetrics {
+        EconomicMetrics {
+            timestamp: Utc::now(),
+            total_value_locked: Decimal::from(1000000),
+            transaction_volume: Decimal::from(50000),
+            active_users: 1000,
+            network_utilization: Decimal::from_str_exact(network_utilization).unwrap(),
+            gas_price: Decimal::from_str_exact("20.0").unwrap(),
+            liquidity_utilization: Decimal::from_str_exact("0.6").unwrap(),
+            validator_performance: Decimal::from_str_exact("0.95").unwrap(),
+            revenue_rate: Decimal::from_str_exact("1000.0").unwrap(),
+            cost_rate: Decimal::from_str_exact("800.0").unwrap(),
+            profit_margin: Decimal::from_str_exact("0.2").unwrap(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_decide_scales_up_overloaded_compute() {
+        let engine = EconomicScalingEngine::new(ScalingConfig::default());
+        engine.initialize_resource(
+            ResourceType::ComputeNodes,
+            Decimal::from(100),
+            Decimal::from_str_exact("10.0").unwrap(),
+            Decimal::from_str_exact("15.0").unwrap(),
+        ).await.unwrap();
+
+        let decisions = engine.decide(&decision_metrics("0.95"));
+        assert_eq!(decisions.len(), 1);
+        let decision = &decisions[0];
+        assert_eq!(decision.resource_type, ResourceType::ComputeNodes);
+        assert_eq!(decision.decision_type, ScalingDecisionType::ScaleUp);
+        // 0.95 against a 0.55 target wants +73%, capped at the 50% scaling rate
+        assert_eq!(decision.scale_amount, Decimal::from(50));
+        assert_eq!(decision.cost_impact, Decimal::from(500));
+        assert_eq!(decision.expected_benefit, Decimal::from(750));
+        assert!(decision.trigger_reason.contains("above"));
+    }
+
+    #[tokio::test]
+    async fn test_decide_scales_down_idle_storage() {
+        let engine = EconomicScalingEngine::new(ScalingConfig::default());
+        engine.initialize_resource(
+            ResourceType::StorageCapacity,
+            Decimal::from(1000),
+            Decimal::ONE,
+            Decimal::from(2),
+        ).await.unwrap();
+
+        let decisions = engine.decide(&decision_metrics("0.55"));
+        assert_eq!(decisions.len(), 1);
+        let decision = &decisions[0];
+        assert_eq!(decision.resource_type, ResourceType::StorageCapacity);
+        assert_eq!(decision.decision_type, ScalingDecisionType::ScaleDown);
+        assert_eq!(decision.scale_amount, Decimal::from(-500));
+        // Idle units earn nothing, so shedding them is pure savings
+        assert_eq!(decision.cost_impact, Decimal::ZERO);
+        assert_eq!(decision.expected_benefit, Decimal::from(500));
+        assert!(decision.trigger_reason.contains("below"));
+    }
+
+    #[tokio::test]
+    async fn test_decide_holds_balanced_resource() {
+        let engine = EconomicScalingEngine::new(ScalingConfig::default());
+        engine.initialize_resource(
+            ResourceType::ComputeNodes,
+            Decimal::from(100),
+            Decimal::from_str_exact("10.0").unwrap(),
+            Decimal::from_str_exact("15.0").unwrap(),
+        ).await.unwrap();
+
+        let decisions = engine.decide(&decision_metrics("0.55"));
+        assert_eq!(decisions.len(), 1);
+        assert_eq!(decisions[0].decision_type, ScalingDecisionType::Maintain);
+        assert_eq!(decisions[0].scale_amount, Decimal::ZERO);
+
+        // Nothing was executed
+        let allocation = engine.get_resource_allocation(ResourceType::ComputeNodes).await.unwrap();
+        assert_eq!(allocation.capacity, Decimal::from(100));
+    }
+
     // ========== STAGE 54: CONSENSUS-BASED SCALING TESTS ==========
 
     #[tokio::test]
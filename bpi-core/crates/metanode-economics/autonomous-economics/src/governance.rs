@@ -10,7 +10,7 @@ use thiserror::Error;
 use rust_decimal::Decimal;
 use tracing::{info, warn, error};
 
-use crate::{GovernanceParameters, EconomicsError};
+use crate::{GovernanceParameters, EconomicsError, OwnerSalaryPolicy};
 
 /// Stage 52: Governance Scaffolding & Parameter Configuration
 /// 
@@ -118,6 +118,142 @@ pub enum VoteType {
     Abstain,
 }
 
+/// Economic parameter change enacted by a passed `Proposal`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum ParameterChange {
+    /// Replace θ(t) wholesale; boxed as it dwarfs the other variants
+    GovernanceParameters(Box<GovernanceParameters>),
+    /// Replace the owner salary guardrails
+    OwnerSalaryPolicy(OwnerSalaryPolicy),
+}
+
+/// GEN-weighted vote on a parameter change proposal
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ParameterVote {
+    pub voter: String,
+    pub vote_type: VoteType,
+    pub stake: u64,                          // GEN backing the vote
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Parameter change proposal voted on under θ(t) thresholds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Proposal {
+    pub id: Uuid,
+    pub proposer: String,
+    pub change: ParameterChange,
+    pub proposer_stake: u64,                 // GEN held by the proposer, at least Θ_prop
+    pub votes: Vec<ParameterVote>,
+    pub created_at: DateTime<Utc>,
+    pub voting_end: DateTime<Utc>,           // Votes after this are refused; tallies before it too
+    pub passed_at: Option<DateTime<Utc>>,    // Start of the execution timelock
+    pub status: ProposalStatus,
+}
+
+impl Proposal {
+    /// Open `change` for voting from `created_at` until `voting_end`
+    pub fn new(
+        proposer: String,
+        change: ParameterChange,
+        proposer_stake: u64,
+        created_at: DateTime<Utc>,
+        voting_end: DateTime<Utc>,
+    ) -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            proposer,
+            change,
+            proposer_stake,
+            votes: Vec::new(),
+            created_at,
+            voting_end,
+            passed_at: None,
+            status: ProposalStatus::Active,
+        }
+    }
+
+    /// Total GEN voting for, against and abstaining
+    pub fn vote_totals(&self) -> (u64, u64, u64) {
+        self.votes.iter().fold((0, 0, 0), |(yes, no, abstain), vote| match vote.vote_type {
+            VoteType::For => (yes + vote.stake, no, abstain),
+            VoteType::Against => (yes, no + vote.stake, abstain),
+            VoteType::Abstain => (yes, no, abstain + vote.stake),
+        })
+    }
+
+    /// Record a vote backed by `stake` GEN; only while active and before the
+    /// deadline, once per voter
+    pub fn cast_vote(&mut self, voter: String, vote_type: VoteType, stake: u64, now: DateTime<Utc>) -> Result<(), EconomicsError> {
+        if self.status != ProposalStatus::Active || now > self.voting_end {
+            return Err(EconomicsError::GovernanceFailed("Proposal not open for voting".to_string()));
+        }
+        if stake == 0 {
+            return Err(EconomicsError::GovernanceFailed("No voting power".to_string()));
+        }
+        if self.votes.iter().any(|vote| vote.voter == voter) {
+            return Err(EconomicsError::GovernanceFailed("Voter already voted".to_string()));
+        }
+
+        info!("🗳️ {} voted {:?} on proposal {} with {} GEN", voter, vote_type, self.id, stake);
+        self.votes.push(ParameterVote { voter, vote_type, stake, timestamp: now });
+        Ok(())
+    }
+
+    /// Close voting once the deadline has passed: participation below q of
+    /// `gen_supply` expires the proposal, otherwise it passes when
+    /// For/(For+Against) reaches ξ
+    pub fn tally(&mut self, params: &GovernanceParameters, gen_supply: u64, now: DateTime<Utc>) -> Result<ProposalStatus, EconomicsError> {
+        if self.status != ProposalStatus::Active {
+            return Err(EconomicsError::GovernanceFailed("Proposal not active".to_string()));
+        }
+        if now <= self.voting_end {
+            return Err(EconomicsError::GovernanceFailed(format!("Voting open until {}", self.voting_end)));
+        }
+
+        let (votes_for, votes_against, votes_abstain) = self.vote_totals();
+        let participation = if gen_supply > 0 {
+            Decimal::from(votes_for + votes_against + votes_abstain) / Decimal::from(gen_supply)
+        } else {
+            Decimal::ZERO
+        };
+        let approval = if votes_for + votes_against > 0 {
+            Decimal::from(votes_for) / Decimal::from(votes_for + votes_against)
+        } else {
+            Decimal::ZERO
+        };
+
+        self.status = if participation < params.quorum_rate {
+            ProposalStatus::Expired
+        } else if approval >= params.passage_threshold {
+            self.passed_at = Some(now);
+            ProposalStatus::Passed
+        } else {
+            ProposalStatus::Failed
+        };
+
+        info!("🏛️ Proposal {} tallied: {:?} (participation: {:.2}%, approval: {:.2}%)",
+              self.id, self.status,
+              participation * Decimal::new(100, 0), approval * Decimal::new(100, 0));
+        Ok(self.status.clone())
+    }
+
+    /// The change to apply, once the proposal has passed and `timelock_hours`
+    /// have elapsed since
+    pub fn executable_change(&self, timelock_hours: u64, now: DateTime<Utc>) -> Result<&ParameterChange, EconomicsError> {
+        let passed_at = match (&self.status, self.passed_at) {
+            (ProposalStatus::Passed, Some(passed_at)) => passed_at,
+            _ => return Err(EconomicsError::GovernanceFailed("Proposal not passed".to_string())),
+        };
+        let executable_at = passed_at + chrono::Duration::hours(timelock_hours as i64);
+        if now < executable_at {
+            return Err(EconomicsError::GovernanceFailed(format!(
+                "Timelock period not expired: executable at {}", executable_at
+            )));
+        }
+        Ok(&self.change)
+    }
+}
+
 /// Treasury allocation request
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TreasuryRequest {
@@ -445,7 +581,8 @@ impl GovernanceSystem {
 
     /// Execute parameter update
     async fn execute_parameter_update(&self, parameter_name: &str, new_value: &str) -> Result<String, EconomicsError> {
-        let mut params = self.governance_parameters.write().await;
+        let mut current_params = self.governance_parameters.write().await;
+        let mut params = current_params.clone();
         
         match parameter_name {
             "job_fee_rate" => {
@@ -463,7 +600,10 @@ impl GovernanceSystem {
             _ => return Err(EconomicsError::GovernanceError("Unknown parameter".to_string())),
         }
 
+        // Applied only if the whole parameter set stays within bounds
+        params.validate()?;
         params.last_update = Utc::now();
+        *current_params = params;
         Ok(format!("Parameter {} updated to {}", parameter_name, new_value))
     }
 
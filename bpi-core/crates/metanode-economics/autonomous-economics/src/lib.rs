@@ -1,8 +1,10 @@
-use std::collections::{HashMap, VecDeque};
-use std::sync::Arc;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
+use std::sync::atomic::{AtomicU64, Ordering as AtomicOrdering};
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
 use std::str::FromStr;
-use tokio::sync::RwLock;
+use tokio::sync::{RwLock, RwLockWriteGuard};
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
 use uuid::Uuid;
@@ -11,6 +13,8 @@ use thiserror::Error;
 use prometheus::{Counter, Gauge, Histogram, HistogramOpts, Registry};
 use rust_decimal::Decimal;
 use rust_decimal::prelude::*;
+use sha2::{Digest, Sha256};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
 use tracing::{info, warn, error};
 
 use billing_meter::TokenType;
@@ -20,12 +24,19 @@ pub mod cross_chain_settlement;
 pub mod liquidity_management;
 pub mod economic_scaling;
 pub mod bank_mesh_network;
+pub mod simulation;
+pub mod clock;
+pub mod audit_log;
 
 // Re-export Bank Mesh components
-pub use cross_chain_settlement::{CrossChainSettlement, ChainId, BridgeTransaction, HTLC};
+pub use cross_chain_settlement::{CrossChainSettlement, ChainId, BridgeTransaction, HTLC, SettlementEvent, SettlementStatus};
 pub use liquidity_management::{LiquidityManager, LiquidityPool, YieldFarm, TradeResult};
 pub use economic_scaling::{EconomicScalingEngine, ResourceType, EconomicMetrics, ScalingDecision};
-pub use bank_mesh_network::{BankMeshNetwork, BankNode, BankMessage, ConsensusProposal};
+pub use bank_mesh_network::{BankMeshNetwork, BankNode, BankMessage, ConsensusProposal, ProposalOutcome};
+pub use simulation::{EconomicSimulator, RandomWorkload, WorkloadGenerator};
+pub use clock::{Clock, MockClock, SystemClock};
+pub use audit_log::{AuditEntry, AuditEntryType, EconomicAuditLog};
+pub use governance::{ParameterChange, ParameterVote, Proposal, ProposalStatus, VoteType};
 
 /// Token supply state tracking per formal specification
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -34,23 +45,95 @@ pub struct TokenSupplyState {
     pub nex_supply: u64,        // S_NEX(t) - dynamic, PoE-linked
     pub flx_supply: u64,        // S_FLX(t) - elastic to usage
     pub aur_supply: u64,        // S_AUR(t) - equals gold backing
+    #[serde(default)]
+    pub aur_backing_grams: Decimal, // Attested gold reserve behind AUR
     pub epoch: u64,             // Current epoch t
+    #[serde(default)]
+    pub epoch_nex_issued: u64,  // NEX minted so far this epoch, against C_NEX(t)
+    #[serde(default)]
+    pub epoch_flx_minted: u64,  // FLX minted so far this epoch, against C_FLX(t)
     pub last_update: DateTime<Utc>,
 }
 
+/// Supply snapshots kept for rollback unless configured otherwise
+pub const DEFAULT_SUPPLY_SNAPSHOT_DEPTH: usize = 16;
+
+/// Epochs of PoE index history kept unless configured otherwise
+pub const DEFAULT_POE_HISTORY_DEPTH: usize = 64;
+
+/// Blocks a job's reserve increment stays locked unless configured otherwise
+pub const DEFAULT_LOCK_DURATION_BLOCKS: u64 = 100_000;
+
+/// Fixed GEN supply, all allocated at genesis
+pub const GEN_GENESIS_SUPPLY: u64 = 100_000;
+/// Genesis NEX supply before PoE issuance
+pub const NEX_GENESIS_SUPPLY: u64 = 300_000;
+/// Genesis FLX supply; elastic burns never take supply below this floor
+pub const FLX_GENESIS_SUPPLY: u64 = 500_000;
+
 impl Default for TokenSupplyState {
     fn default() -> Self {
         Self {
-            gen_supply: 100_000,    // Fixed genesis supply
-            nex_supply: 300_000,    // Genesis NEX supply
-            flx_supply: 500_000,    // Genesis FLX supply
+            gen_supply: GEN_GENESIS_SUPPLY,
+            nex_supply: NEX_GENESIS_SUPPLY,
+            flx_supply: FLX_GENESIS_SUPPLY,
             aur_supply: 0,          // No AUR at genesis (bank-only)
+            aur_backing_grams: Decimal::ZERO,
             epoch: 0,
+            epoch_nex_issued: 0,
+            epoch_flx_minted: 0,
             last_update: Utc::now(),
         }
     }
 }
 
+/// Custodian attestation of a gold reserve increment backing new AUR
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReserveProof {
+    pub custodian: String,
+    pub gold_grams: Decimal,          // Attested reserve increment
+    pub attested_at: DateTime<Utc>,
+    pub attestation_hash: String,     // SHA-256 over custodian, grams and time
+    pub signature: Vec<u8>,           // Custodian's Ed25519 signature over the attestation hash
+}
+
+impl ReserveProof {
+    /// Attest `gold_grams` as `custodian`, signing with the custodian's key
+    pub fn sign(custodian: &str, gold_grams: Decimal, signing_key: &SigningKey) -> Self {
+        let attested_at = Utc::now();
+        let attestation_hash = Self::digest(custodian, gold_grams, attested_at);
+        let signature = signing_key.sign(attestation_hash.as_bytes()).to_bytes().to_vec();
+        Self {
+            custodian: custodian.to_string(),
+            gold_grams,
+            attested_at,
+            attestation_hash,
+            signature,
+        }
+    }
+
+    /// Whether the attestation hash matches the attested fields and is signed
+    /// by `custodian_key`
+    pub fn verify(&self, custodian_key: &VerifyingKey) -> bool {
+        if self.custodian.is_empty()
+            || self.attestation_hash != Self::digest(&self.custodian, self.gold_grams, self.attested_at)
+        {
+            return false;
+        }
+        Signature::from_slice(&self.signature)
+            .map(|signature| custodian_key.verify(self.attestation_hash.as_bytes(), &signature).is_ok())
+            .unwrap_or(false)
+    }
+
+    fn digest(custodian: &str, gold_grams: Decimal, attested_at: DateTime<Utc>) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(custodian.as_bytes());
+        hasher.update(gold_grams.normalize().to_string().as_bytes());
+        hasher.update(attested_at.to_rfc3339().as_bytes());
+        hex::encode(hasher.finalize())
+    }
+}
+
 /// PoE index calculation (Φ(t)) per formal specification
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PoEIndex {
@@ -63,8 +146,69 @@ pub struct PoEIndex {
     pub calculation_time: DateTime<Utc>,
 }
 
+/// Raw network measurements for one epoch, the inputs to Φ(t)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochMetrics {
+    pub epoch: u64,
+    pub total_volume: Decimal,        // Σ V_g(J) - gold-equivalent job volume
+    pub liquidity_delta: Decimal,     // Σ ΔL(J) - net liquidity added
+    pub average_uptime: Decimal,      // uptime_avg ∈ [0,1]
+    pub quality_score: Decimal,       // QualityScore(t) ∈ [0,1]
+}
+
+/// Component weights and normalization scales for Φ(t)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PoEWeights {
+    pub volume_weight: Decimal,       // w_V
+    pub liquidity_weight: Decimal,    // w_L
+    pub uptime_weight: Decimal,       // w_U
+    pub quality_weight: Decimal,      // w_Q
+    pub volume_scale: Decimal,        // scale_V
+    pub liquidity_scale: Decimal,     // scale_L
+}
+
+impl Default for PoEWeights {
+    fn default() -> Self {
+        Self {
+            volume_weight: Decimal::new(4, 1),        // 0.4
+            liquidity_weight: Decimal::new(3, 1),     // 0.3
+            uptime_weight: Decimal::new(2, 1),        // 0.2
+            quality_weight: Decimal::new(1, 1),       // 0.1
+            volume_scale: Decimal::new(1_000_000, 0), // $1M gold-equivalent
+            liquidity_scale: Decimal::new(100_000, 0),
+        }
+    }
+}
+
 /// Issuance gating function Γ(Φ) = Φ/(1+Φ)
 impl PoEIndex {
+    /// Compute Φ(t) = w_V·ΣV/scale_V + w_L·ΣΔL/scale_L + w_U·uptime + w_Q·quality.
+    /// A non-positive scale zeroes its component; Φ is floored at zero so Γ stays in [0,1).
+    pub fn compute(metrics: &EpochMetrics, weights: &PoEWeights) -> PoEIndex {
+        let scaled = |value: Decimal, scale: Decimal| {
+            if scale > Decimal::ZERO { value / scale } else { Decimal::ZERO }
+        };
+        let unit = |value: Decimal| value.max(Decimal::ZERO).min(Decimal::ONE);
+
+        let volume_component = weights.volume_weight * scaled(metrics.total_volume, weights.volume_scale);
+        let liquidity_component = weights.liquidity_weight * scaled(metrics.liquidity_delta, weights.liquidity_scale);
+        let uptime_component = weights.uptime_weight * unit(metrics.average_uptime);
+        let quality_component = weights.quality_weight * unit(metrics.quality_score);
+
+        let phi_value = (volume_component + liquidity_component + uptime_component + quality_component)
+            .max(Decimal::ZERO);
+
+        PoEIndex {
+            phi_value,
+            volume_component,
+            liquidity_component,
+            uptime_component,
+            quality_component,
+            epoch: metrics.epoch,
+            calculation_time: Utc::now(),
+        }
+    }
+
     pub fn gamma(&self) -> Decimal {
         self.phi_value / (Decimal::ONE + self.phi_value)
     }
@@ -79,6 +223,8 @@ pub struct PoEFeeSplit {
     pub miner_spendable: Decimal,     // 0.3% - immediate reward
     pub owner_salary: Decimal,        // 0.2% - owner fixed salary (NEW)
     pub treasury_net: Decimal,        // 0.3% - treasury net (reduced from 0.5%)
+    #[serde(default)]
+    pub miner_address: String,        // Account credited with the spendable share
 }
 
 /// Owner salary governance and safety guardrails
@@ -116,11 +262,165 @@ pub struct OwnerSalaryReport {
     pub capped_salary_amount: Decimal,    // After applying monthly cap
     pub immediate_payout: Decimal,        // 50% immediate
     pub vested_amount: Decimal,           // 50% vested
-    pub escrow_amount: Decimal,           // If compliance flag raised
+    pub escrow_amount: Decimal,           // Cap overflow, plus all of it if compliance flag raised
+    #[serde(default)]
+    pub month_to_date_paid: Decimal,      // Running total counted against the monthly cap
     pub transparency_tx_hash: String,     // On-chain payment proof
     pub report_timestamp: DateTime<Utc>,
 }
 
+/// Owner salary counted against the monthly hard cap for the current month
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct OwnerSalaryLedger {
+    pub month: String,                    // YYYY-MM format
+    pub paid_to_date: Decimal,
+}
+
+impl OwnerSalaryLedger {
+    /// Reset the running total when `month` differs from the tracked one
+    fn roll_to(&mut self, month: &str) {
+        if self.month != month {
+            self.month = month.to_string();
+            self.paid_to_date = Decimal::ZERO;
+        }
+    }
+}
+
+/// Runtime compliance flag change on owner salary, kept for audit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceFlagEvent {
+    pub flagged: bool,
+    pub reason: String,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Deferred owner salary released in monthly installments
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VestingSchedule {
+    pub id: Uuid,
+    pub total_amount: Decimal,
+    pub monthly_amount: Decimal,
+    pub remaining_amount: Decimal,
+    pub start_date: DateTime<Utc>,
+    pub end_date: DateTime<Utc>,
+    pub next_payment_date: DateTime<Utc>,
+    pub status: VestingStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum VestingStatus {
+    Active,
+    Completed,
+}
+
+/// One vesting installment released by `process_vesting`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VestedPayout {
+    pub schedule_id: Uuid,
+    pub amount: Decimal,
+    pub due_date: DateTime<Utc>,
+    pub recipient: String,
+    pub remaining_amount: Decimal,
+}
+
+/// One coin lock released by `process_unlocks`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnlockReceipt {
+    pub lock_id: Uuid,
+    pub job_id: String,
+    pub amount: Decimal,
+    pub unlock_height: u64,
+    pub released_at_height: u64,
+}
+
+/// Owner salary for one payment after the monthly cap, compliance escrow and vesting split
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OwnerSalaryAllocation {
+    pub gross: Decimal,
+    pub capped: Decimal,                  // Counted against the monthly cap
+    pub cap_overflow: Decimal,            // Escrowed, over the monthly cap
+    pub compliance_escrow: Decimal,       // Escrowed, compliance flag raised
+    pub immediate: Decimal,               // Paid to the owner wallet now
+    pub vested: Decimal,                  // Scheduled over the vesting period
+    pub month: String,                    // YYYY-MM format
+    pub month_to_date_paid: Decimal,      // Ledger total after this payment
+}
+
+impl OwnerSalaryAllocation {
+    /// Split `gross` under `policy` given what `ledger` has already paid; a
+    /// ledger tracking an earlier month counts as a fresh allowance
+    fn compute(gross: Decimal, policy: &OwnerSalaryPolicy, ledger: &OwnerSalaryLedger, month: &str) -> Self {
+        let paid_to_date = if ledger.month == month { ledger.paid_to_date } else { Decimal::ZERO };
+        let allowance = (policy.monthly_hard_cap - paid_to_date).max(Decimal::ZERO);
+        let capped = gross.min(allowance);
+
+        let (compliance_escrow, immediate, vested) = if policy.escrow_on_compliance_flag {
+            (capped, Decimal::ZERO, Decimal::ZERO)
+        } else {
+            (Decimal::ZERO, capped * policy.vesting_immediate_rate, capped * policy.vesting_deferred_rate)
+        };
+
+        Self {
+            gross,
+            capped,
+            cap_overflow: gross - capped,
+            compliance_escrow,
+            immediate,
+            vested,
+            month: month.to_string(),
+            month_to_date_paid: paid_to_date + capped,
+        }
+    }
+}
+
+/// Every destination of one job's fees, computed without touching engine state
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeRoutingPreview {
+    pub job_id: String,
+    pub fee_split: PoEFeeSplit,
+    pub docklock_revenue: Decimal,
+    pub miner_spendable: Decimal,
+    pub miner_locked: Decimal,
+    pub owner_salary: OwnerSalaryAllocation,
+    pub treasury_credit: Decimal,         // Base treasury net plus the DockLock share
+}
+
+/// Outcome of `route_fees_batch`: what each job owed, and what moved per destination
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatchSettlementReport {
+    pub jobs: Vec<FeeRoutingPreview>,     // Jobs with at least one leg routed by this batch
+    pub skipped: Vec<String>,             // Jobs already fully routed, or repeated in the batch
+    pub miner_spendable: HashMap<String, Decimal>, // Combined payment per miner
+    pub miner_locked: Decimal,
+    pub owner_salary: OwnerSalaryAllocation, // Guardrails applied to the combined salary
+    pub treasury_credit: Decimal,
+}
+
+/// An epoch as `advance_epoch` closed it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EpochSummary {
+    pub epoch: u64,
+    pub nex_issued: u64,
+    pub flx_minted: u64,
+    pub supply: TokenSupplyState,         // Supply at close, before the per-epoch counters reset
+    pub poe_index: Option<PoEIndex>,      // Φ(t) archived for the epoch
+}
+
+/// Point-in-time view of supplies, treasury, locks, escrow, vesting and Φ(t)
+/// for external dashboards
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EconomicSnapshot {
+    pub supply: TokenSupplyState,
+    pub reward_pool: HashMap<TokenType, Decimal>,
+    pub treasury_balance: Decimal,
+    pub locked_coins: Decimal,            // Permanent miner reserve
+    pub escrowed_funds: Decimal,
+    pub vesting_outstanding: Decimal,     // Reserved for vesting, not yet released
+    pub active_vesting_schedules: usize,
+    pub poe_index: Option<PoEIndex>,
+    pub taken_at: DateTime<Utc>,
+}
+
 /// Governance parameters θ(t) - tunable via GEN voting
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GovernanceParameters {
@@ -144,11 +444,18 @@ pub struct GovernanceParameters {
     pub tau_flx: Decimal,                // τ_FLX (optional)
     pub tau_gen: Decimal,                // τ_GEN (optional)
     
+    // PoE scoring
+    pub poe_value_scale: Decimal,        // Gold-equivalent value per PoE point
+    pub poe_diminishing_threshold: Decimal, // PoE points before diminishing returns
+    pub docklock_revenue_weights: HashMap<EconomicJobType, Decimal>, // DockLock revenue counted per job type
+    
     // Governance thresholds
     pub proposal_stake: u64,             // Θ_prop in GEN
     pub quorum_rate: Decimal,            // q = 10%
     pub passage_threshold: Decimal,      // ξ = 60%
     pub execution_timelock_hours: u64,   // T_exec = 48h
+    #[serde(default = "default_voting_period_hours")]
+    pub voting_period_hours: u64,        // T_vote = 168h
     
     pub last_update: DateTime<Utc>,
 }
@@ -170,15 +477,111 @@ impl Default for GovernanceParameters {
             tau_nex: Decimal::new(100, 0),              // 100 PoE
             tau_flx: Decimal::new(50, 0),               // 50 PoE
             tau_gen: Decimal::new(500, 0),              // 500 PoE
+            poe_value_scale: Decimal::new(100, 0),      // $100 per PoE point
+            poe_diminishing_threshold: Decimal::new(1_000, 0), // 1,000 PoE
+            docklock_revenue_weights: HashMap::from([
+                (EconomicJobType::DockLockHosting, Decimal::ONE),
+                (EconomicJobType::GasFees, Decimal::ONE),
+                (EconomicJobType::DataPipeline, Decimal::ONE),
+                (EconomicJobType::SecurityLayer, Decimal::ONE),
+                (EconomicJobType::Validation, Decimal::new(5, 1)),  // Incidental hosting revenue
+                (EconomicJobType::Settlement, Decimal::new(5, 1)),
+                (EconomicJobType::Development, Decimal::new(5, 1)),
+                (EconomicJobType::Commerce, Decimal::new(5, 1)),
+            ]),
             proposal_stake: 100,                        // 100 GEN
             quorum_rate: Decimal::new(1, 1),            // 10%
             passage_threshold: Decimal::new(6, 1),      // 60%
             execution_timelock_hours: 48,
+            voting_period_hours: default_voting_period_hours(),
             last_update: Utc::now(),
         }
     }
 }
 
+fn default_voting_period_hours() -> u64 {
+    168 // 7 days
+}
+
+/// Tolerance when checking that fee component rates sum to the job fee rate
+const FEE_RATE_EPSILON: Decimal = Decimal::from_parts(1, 0, 0, false, 12); // 1e-12
+
+impl GovernanceParameters {
+    /// Fee components must exactly partition the job fee:
+    /// f_m,lock + f_m,sp + f_owner + f_treasury = f
+    pub fn validate_fee_rates(&self) -> Result<(), EconomicsError> {
+        let components = self.miner_lock_rate + self.miner_spendable_rate
+            + self.owner_salary_rate + self.treasury_net_rate;
+        if (components - self.job_fee_rate).abs() > FEE_RATE_EPSILON {
+            return Err(EconomicsError::GovernanceError(format!(
+                "Fee component rates sum to {} but job fee rate is {}",
+                components, self.job_fee_rate
+            )));
+        }
+        Ok(())
+    }
+
+    /// Bounds every governable parameter must satisfy: rates within [0, 1],
+    /// quorum and passage thresholds within (0, 1], positive epoch caps, no
+    /// negative weights, and fee components that partition the job fee
+    pub fn validate(&self) -> Result<(), EconomicsError> {
+        let rates = [
+            ("job_fee_rate", self.job_fee_rate),
+            ("miner_share_rate", self.miner_share_rate),
+            ("miner_lock_rate", self.miner_lock_rate),
+            ("miner_spendable_rate", self.miner_spendable_rate),
+            ("owner_salary_rate", self.owner_salary_rate),
+            ("treasury_net_rate", self.treasury_net_rate),
+            ("flx_burn_rate", self.flx_burn_rate),
+        ];
+        for (name, rate) in rates {
+            if rate < Decimal::ZERO || rate > Decimal::ONE {
+                return Err(EconomicsError::GovernanceError(format!("{} must be between 0 and 1, got {}", name, rate)));
+            }
+        }
+
+        for (name, threshold) in [("quorum_rate", self.quorum_rate), ("passage_threshold", self.passage_threshold)] {
+            if threshold <= Decimal::ZERO || threshold > Decimal::ONE {
+                return Err(EconomicsError::GovernanceError(format!(
+                    "{} must be above 0 and at most 1, got {}", name, threshold
+                )));
+            }
+        }
+
+        for (name, cap) in [
+            ("nex_epoch_cap", self.nex_epoch_cap),
+            ("flx_epoch_cap", self.flx_epoch_cap),
+            ("voting_period_hours", self.voting_period_hours),
+        ] {
+            if cap == 0 {
+                return Err(EconomicsError::GovernanceError(format!("{} must be positive", name)));
+            }
+        }
+
+        let coefficients = [
+            ("nex_sensitivity", self.nex_sensitivity),
+            ("flx_elasticity", self.flx_elasticity),
+            ("tau_nex", self.tau_nex),
+            ("tau_flx", self.tau_flx),
+            ("tau_gen", self.tau_gen),
+            ("poe_value_scale", self.poe_value_scale),
+            ("poe_diminishing_threshold", self.poe_diminishing_threshold),
+        ];
+        for (name, value) in coefficients {
+            if value < Decimal::ZERO {
+                return Err(EconomicsError::GovernanceError(format!("{} must not be negative, got {}", name, value)));
+            }
+        }
+        if let Some((job_type, weight)) = self.docklock_revenue_weights.iter().find(|(_, weight)| **weight < Decimal::ZERO) {
+            return Err(EconomicsError::GovernanceError(format!(
+                "DockLock revenue weight for {:?} must not be negative, got {}", job_type, weight
+            )));
+        }
+
+        self.validate_fee_rates()
+    }
+}
+
 /// Autonomous economics error types
 #[derive(Error, Debug)]
 pub enum EconomicsError {
@@ -198,6 +601,12 @@ pub enum EconomicsError {
     MetricsError(String),
     #[error("System error: {0}")]
     SystemError(String),
+    #[error("Settlement invariant violated: {0}")]
+    SettlementInvariantViolated(String),
+    #[error("Invalid amount: {0}")]
+    InvalidAmount(String),
+    #[error("Insufficient funds: {0}")]
+    InsufficientFunds(String),
     #[error("Prometheus metrics error: {0}")]
     PrometheusError(#[from] prometheus::Error),
 }
@@ -239,8 +648,51 @@ impl Default for GenesisAllocation {
     }
 }
 
+impl GenesisAllocation {
+    /// Every bucket as (account name, token, amount)
+    pub fn buckets(&self) -> [(&'static str, TokenType, u64); 10] {
+        [
+            ("genesis:treasury_reserve", TokenType::Genesis, self.treasury_reserve),
+            ("genesis:founder_allocation", TokenType::Genesis, self.founder_allocation),
+            ("genesis:governance_pool", TokenType::Genesis, self.governance_pool),
+            ("genesis:mining_rewards", TokenType::Nexus, self.mining_rewards),
+            ("genesis:validator_rewards", TokenType::Nexus, self.validator_rewards),
+            ("genesis:community_grants", TokenType::Nexus, self.community_grants),
+            ("genesis:circulation_supply", TokenType::Flux, self.circulation_supply),
+            ("genesis:liquidity_pools", TokenType::Flux, self.liquidity_pools),
+            ("genesis:merchant_incentives", TokenType::Flux, self.merchant_incentives),
+            ("genesis:faucet_reserve", TokenType::Flux, self.faucet_reserve),
+        ]
+    }
+
+    /// Total allocated for one token across its buckets
+    pub fn subtotal(&self, token: TokenType) -> u64 {
+        self.buckets().iter()
+            .filter(|(_, bucket_token, _)| *bucket_token == token)
+            .map(|(_, _, amount)| amount)
+            .sum()
+    }
+
+    /// Per-token subtotals must equal the genesis supplies exactly
+    pub fn validate(&self) -> Result<(), EconomicsError> {
+        for (token, cap) in [
+            (TokenType::Genesis, GEN_GENESIS_SUPPLY),
+            (TokenType::Nexus, NEX_GENESIS_SUPPLY),
+            (TokenType::Flux, FLX_GENESIS_SUPPLY),
+        ] {
+            let subtotal = self.subtotal(token);
+            if subtotal != cap {
+                return Err(EconomicsError::TokenSupplyError(format!(
+                    "Genesis {} allocation sums to {} but supply is {}", token.symbol(), subtotal, cap
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
 /// Economic job types for PoE validation including DockLock hosting revenue
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum EconomicJobType {
     Validation,
     Settlement, 
@@ -261,12 +713,156 @@ pub struct EconomicJob {
     pub gold_equivalent_value: Decimal,
     pub proof_hash: String,
     pub completion_time: DateTime<Utc>,
+    #[serde(default)]
+    pub completion_height: u64,                     // Block height the job completed at
     // DockLock-specific fields
     pub cluster_rent_revenue: Option<Decimal>,      // Monthly cluster hosting fees
     pub gas_fee_revenue: Option<Decimal>,           // Transaction processing fees
     pub app_interaction_revenue: Option<Decimal>,   // API calls, data processing
     pub security_layer_revenue: Option<Decimal>,    // Encryption, validation fees
     pub data_pipeline_revenue: Option<Decimal>,     // Streaming/batch processing
+    #[serde(default)]
+    pub priority: u8,                               // Higher is processed first
+}
+
+impl EconomicJob {
+    /// Reject a job carrying a negative DockLock revenue stream, which would
+    /// otherwise shrink the owner salary and treasury credit it routes
+    pub fn validate_docklock_revenue(&self) -> Result<(), EconomicsError> {
+        for (name, revenue) in [
+            ("cluster_rent_revenue", self.cluster_rent_revenue),
+            ("gas_fee_revenue", self.gas_fee_revenue),
+            ("app_interaction_revenue", self.app_interaction_revenue),
+            ("security_layer_revenue", self.security_layer_revenue),
+            ("data_pipeline_revenue", self.data_pipeline_revenue),
+        ] {
+            if let Some(revenue) = revenue.filter(|revenue| *revenue < Decimal::ZERO) {
+                return Err(EconomicsError::InvalidAmount(format!(
+                    "Job {} has negative {}: {}", self.job_id, name, revenue
+                )));
+            }
+        }
+        Ok(())
+    }
+
+    /// Sum of all DockLock revenue streams carried by this job
+    pub fn docklock_revenue(&self) -> Decimal {
+        [
+            self.cluster_rent_revenue,
+            self.gas_fee_revenue,
+            self.app_interaction_revenue,
+            self.security_layer_revenue,
+            self.data_pipeline_revenue,
+        ]
+        .iter()
+        .flatten()
+        .sum()
+    }
+}
+
+/// Job waiting in the queue; orders by priority, then by submission order
+#[derive(Debug, Clone)]
+struct QueuedJob {
+    sequence: u64,
+    job: EconomicJob,
+}
+
+impl PartialEq for QueuedJob {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for QueuedJob {}
+
+impl PartialOrd for QueuedJob {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for QueuedJob {
+    // Max-heap: the greatest job is the highest priority, earliest submitted
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.job.priority.cmp(&other.job.priority)
+            .then_with(|| other.sequence.cmp(&self.sequence))
+    }
+}
+
+/// Pending economic jobs, highest priority first and FIFO within a priority.
+/// Jobs held for a batch window are counted, peeked and popped like queued
+/// ones; they only wait for `flush` to take their place in the heap.
+#[derive(Debug, Default)]
+pub struct JobQueue {
+    heap: BinaryHeap<QueuedJob>,
+    next_sequence: u64,
+    held: Mutex<Vec<EconomicJob>>, // Submitted under a read lock, oldest first
+}
+
+impl JobQueue {
+    pub fn push(&mut self, job: EconomicJob) {
+        self.flush();
+        self.push_queued(job);
+    }
+
+    /// Push `jobs` in order, as if pushed one at a time
+    pub fn extend(&mut self, jobs: impl IntoIterator<Item = EconomicJob>) {
+        self.flush();
+        for job in jobs {
+            self.push_queued(job);
+        }
+    }
+
+    /// Hold `job` until the next flush; needs only a shared reference, so
+    /// submitters share a read lock. True when it is the first job held.
+    pub fn hold(&self, job: EconomicJob) -> bool {
+        let mut held = self.held.lock().unwrap_or_else(|e| e.into_inner());
+        held.push(job);
+        held.len() == 1
+    }
+
+    /// Move held jobs into the heap in submission order
+    pub fn flush(&mut self) {
+        let held = std::mem::take(self.held.get_mut().unwrap_or_else(|e| e.into_inner()));
+        for job in held {
+            self.push_queued(job);
+        }
+    }
+
+    fn push_queued(&mut self, job: EconomicJob) {
+        self.heap.push(QueuedJob { sequence: self.next_sequence, job });
+        self.next_sequence += 1;
+    }
+
+    pub fn pop(&mut self) -> Option<EconomicJob> {
+        self.flush();
+        self.heap.pop().map(|queued| queued.job)
+    }
+
+    /// The job `pop` would return next
+    pub fn peek(&self) -> Option<EconomicJob> {
+        let held = self.held.lock().unwrap_or_else(|e| e.into_inner());
+        // Held jobs are newer than every queued one, so they win only on priority
+        let first_held = held.iter().rev().max_by_key(|job| job.priority);
+        match (self.heap.peek(), first_held) {
+            (Some(queued), Some(job)) if job.priority <= queued.job.priority => Some(queued.job.clone()),
+            (_, Some(job)) => Some(job.clone()),
+            (queued, None) => queued.map(|queued| queued.job.clone()),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len() + self.held_len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Jobs held for the batch window, not yet flushed into the heap
+    pub fn held_len(&self) -> usize {
+        self.held.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
 }
 
 /// PoE score calculation result
@@ -278,6 +874,8 @@ pub struct PoEScore {
     pub calculation_time: DateTime<Utc>,
     pub job_count: usize,
     pub total_job_value: Decimal, // Total gold-equivalent value of jobs processed
+    #[serde(default)]
+    pub docklock_volume: Decimal, // Weighted DockLock revenue included in total_job_value
 }
 
 /// Token minting eligibility based on PoE thresholds
@@ -286,21 +884,184 @@ pub struct TokenMintingEligibility {
     pub miner_id: String,
     pub eligible_tokens: Vec<TokenType>,
     pub poe_score: Decimal,
+    #[serde(default)]
+    pub docklock_volume: Decimal, // Qualifying DockLock revenue behind the score
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TokenMintingEligibility {
+    /// Token types whose τ threshold the raw PoE score reaches
+    pub fn from_poe_score(score: &PoEScore, params: &GovernanceParameters) -> Self {
+        let eligible_tokens = [
+            (TokenType::Genesis, params.tau_gen),
+            (TokenType::Nexus, params.tau_nex),
+            (TokenType::Flux, params.tau_flx),
+        ]
+        .into_iter()
+        .filter(|(_, tau)| score.raw_score >= *tau)
+        .map(|(token, _)| token)
+        .collect();
+
+        Self {
+            miner_id: score.miner_id.clone(),
+            eligible_tokens,
+            poe_score: score.raw_score,
+            docklock_volume: score.docklock_volume,
+            timestamp: Utc::now(),
+        }
+    }
+}
+
+/// Kind of payment recorded in the economic state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentType {
+    MinerReward,
+    OwnerDistribution,
+    TreasuryCredit,
+    VestingReserve,     // Record id is the vesting schedule id
+    EscrowHold,         // Record id is the escrow id
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PaymentStatus {
+    Completed,
+    Reversed,           // Compensates the completed payment with the same key
+}
+
+/// One payment out of fee routing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PaymentRecord {
+    pub id: Uuid,
+    pub idempotency_key: String,      // Job id and leg; a retry with the same key pays nothing
+    pub payment_type: PaymentType,
+    pub amount: Decimal,
+    pub recipient: String,
+    pub timestamp: DateTime<Utc>,
+    pub status: PaymentStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum LockStatus {
+    Active,
+    Released,
+}
+
+/// Miner reserve increment locked until `unlock_height`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CoinLockRecord {
+    pub id: Uuid,
+    pub job_id: String,
+    pub amount: Decimal,
+    pub locked_at: DateTime<Utc>,
+    pub unlock_height: u64,
+    pub status: LockStatus,
+}
+
+/// Condition under which escrowed funds are released
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum EscrowConditions {
+    TimeBasedRelease { release_date: DateTime<Utc> },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum EscrowStatus {
+    Held,
+    Released,
+}
+
+/// Funds held back from a payment until their release conditions are met
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EscrowRecord {
+    pub id: Uuid,
+    pub amount: Decimal,
+    pub created_at: DateTime<Utc>,
+    pub release_conditions: EscrowConditions,
+    pub status: EscrowStatus,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TreasuryTransactionType {
+    Credit,
+    Reversal,           // Undoes a credit of a failed fee routing
+}
+
+/// One movement of treasury funds
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TreasuryTransaction {
+    pub id: Uuid,
+    pub transaction_type: TreasuryTransactionType,
+    pub amount: Decimal,
     pub timestamp: DateTime<Utc>,
+    pub description: String,
+}
+
+/// Running treasury totals
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TreasuryStats {
+    pub total_credits: Decimal,
+    pub last_credit_date: Option<DateTime<Utc>>,
+}
+
+/// Fee routing totals for one job
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobEconomics {
+    pub locked_amount: Decimal,
+}
+
+/// Balances, locks, escrow, vesting and payment history moved by fee routing
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EconomicState {
+    pub account_balances: HashMap<String, Decimal>,
+    pub circulating_supply: Decimal,
+    pub treasury_balance: Decimal,
+    pub total_miner_rewards: Decimal,
+    pub total_owner_distributions: Decimal,
+    pub total_locked_coins: Decimal,
+    pub total_escrowed_funds: Decimal,
+    pub total_vested_amount: Decimal,
+    pub total_treasury_inflow: Decimal,
+    pub payment_history: Vec<PaymentRecord>,
+    pub payments_by_key: HashMap<String, PaymentRecord>, // Idempotency key -> the payment it made
+    pub active_locks: HashMap<Uuid, CoinLockRecord>,
+    pub active_escrows: HashMap<Uuid, EscrowRecord>,
+    pub vesting_schedules: HashMap<Uuid, VestingSchedule>,
+    pub treasury_history: Vec<TreasuryTransaction>,
+    pub treasury_stats: TreasuryStats,
+    pub job_economics: HashMap<String, JobEconomics>,
 }
 
+/// Epoch and CBOR-encoded `(epoch, TokenSupplyState)` taken by `snapshot_supply`
+pub type SupplySnapshot = (u64, Vec<u8>);
+
 /// Production-ready PoE mining system with owner salary and governance guardrails
 #[derive(Debug)]
 pub struct PoEMiningEngine {
     pub active_miners: Arc<RwLock<HashMap<String, MinerState>>>,
-    pub job_queue: Arc<RwLock<VecDeque<EconomicJob>>>,
+    pub job_queue: Arc<RwLock<JobQueue>>,
     pub reward_pool: Arc<RwLock<HashMap<TokenType, Decimal>>>,
     pub token_supply: Arc<RwLock<TokenSupplyState>>,
     pub governance_params: Arc<RwLock<GovernanceParameters>>,
+    pub economic_state: Arc<RwLock<EconomicState>>,
     pub current_poe_index: Arc<RwLock<Option<PoEIndex>>>,
+    pub poe_index_history: Arc<RwLock<VecDeque<PoEIndex>>>, // One Φ(t) per epoch, oldest first
+    pub poe_history_depth: usize,
     pub owner_salary_policy: Arc<RwLock<OwnerSalaryPolicy>>,
     pub owner_salary_reports: Arc<RwLock<Vec<OwnerSalaryReport>>>,
+    pub owner_salary_ledger: Arc<RwLock<OwnerSalaryLedger>>,
+    pub compliance_events: Arc<RwLock<Vec<ComplianceFlagEvent>>>,
+    pub compliance_escrows: Arc<RwLock<Vec<Uuid>>>, // Salary escrowed while flagged
+    pub reserve_attestations: Arc<RwLock<HashSet<String>>>, // Reserve proofs already minted against
+    pub reserve_custodians: HashMap<String, VerifyingKey>, // Custodian -> key its reserve proofs must be signed with
+    pub supply_snapshots: Arc<RwLock<VecDeque<SupplySnapshot>>>, // Oldest first
+    pub supply_snapshot_depth: usize,
+    pub lock_duration_blocks: u64,        // Blocks from job completion until its coin lock releases
+    pub proposals: Arc<RwLock<HashMap<Uuid, Proposal>>>,
+    pub token_balances: Arc<RwLock<HashMap<String, HashMap<TokenType, u64>>>>, // Account -> token holdings
+    pub clock: Arc<dyn Clock>,
+    pub audit_log: Arc<RwLock<EconomicAuditLog>>, // Every economic state mutation, in order
     pub metrics: PoEMetrics,
+    pub job_batch_window: Option<Duration>,   // Coalesce single-job submissions for this long before enqueueing
+    job_queue_locks: Arc<AtomicU64>,          // Queue write locks taken to enqueue jobs
 }
 
 /// Miner state tracking
@@ -312,6 +1073,8 @@ pub struct MinerState {
     pub last_reward_time: DateTime<Utc>,
     pub prestige_multiplier: Decimal,
     pub tokens_earned: HashMap<TokenType, Decimal>,
+    #[serde(default)]
+    pub claimable_rewards: HashMap<TokenType, Decimal>, // Entitled, not yet claimed from the reward pool
 }
 
 /// PoE mining metrics
@@ -322,6 +1085,33 @@ pub struct PoEMetrics {
     pub poe_scores_calculated: Counter,
     pub tokens_minted: Counter,
     pub mining_cycle_time: Histogram,
+    pub compliance_flagged: Gauge,
+    pub gen_supply: Gauge,
+    pub nex_supply: Gauge,
+    pub flx_supply: Gauge,
+    pub aur_supply: Gauge,
+    pub treasury_balance: Gauge,
+    pub total_locked: Gauge,
+    pub total_escrowed: Gauge,
+    pub total_vested: Gauge,
+}
+
+impl PoEMetrics {
+    /// Mirror GEN/NEX/FLX/AUR supply into their gauges
+    fn observe_supply(&self, supply: &TokenSupplyState) {
+        self.gen_supply.set(supply.gen_supply as f64);
+        self.nex_supply.set(supply.nex_supply as f64);
+        self.flx_supply.set(supply.flx_supply as f64);
+        self.aur_supply.set(supply.aur_supply as f64);
+    }
+
+    /// Mirror the treasury and its locked, escrowed and vested totals into their gauges
+    fn observe_balances(&self, state: &EconomicState) {
+        self.treasury_balance.set(state.treasury_balance.to_f64().unwrap_or(0.0));
+        self.total_locked.set(state.total_locked_coins.to_f64().unwrap_or(0.0));
+        self.total_escrowed.set(state.total_escrowed_funds.to_f64().unwrap_or(0.0));
+        self.total_vested.set(state.total_vested_amount.to_f64().unwrap_or(0.0));
+    }
 }
 
 /// Miner weight calculation W_i(t) for NEX distribution
@@ -335,6 +1125,72 @@ pub struct MinerWeight {
     pub calculation_time: DateTime<Utc>,
 }
 
+/// Shares of DockLock revenue routed to owner salary (0.2%) and treasury (0.3%)
+const DOCKLOCK_OWNER_SALARY_RATE: Decimal = Decimal::from_parts(2, 0, 0, false, 3);
+const DOCKLOCK_TREASURY_RATE: Decimal = Decimal::from_parts(3, 0, 0, false, 3);
+/// Idempotency key for one leg of a job's fee routing
+fn payment_key(job_id: &str, leg: &str) -> String {
+    format!("{}:{}", job_id, leg)
+}
+
+/// Treasury left for new outflows. Vesting and escrow need no deduction here:
+/// creating either already moved its funds out of the balance.
+fn spendable_balance(state: &EconomicState) -> Decimal {
+    state.treasury_balance.max(Decimal::ZERO)
+}
+
+/// Where an owner-side outflow draws its funds from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Funding {
+    Treasury,       // Debited from the treasury, which must have it spendable
+    JobFee,         // The owner salary share of a routed job's fee
+}
+
+/// A fee routing leg applied during the current update, reversed if a later leg fails
+#[derive(Debug)]
+enum AppliedLeg {
+    MinerPayment(PaymentRecord),
+    CoinLock(Uuid),
+    TreasuryCredit(PaymentRecord),
+}
+
+/// Write guards over everything fee routing mutates, always taken in this
+/// order, so all legs of a routing apply as one update
+struct RoutingGuards<'a> {
+    ledger: RwLockWriteGuard<'a, OwnerSalaryLedger>,
+    reports: RwLockWriteGuard<'a, Vec<OwnerSalaryReport>>,
+    compliance_escrows: RwLockWriteGuard<'a, Vec<Uuid>>,
+    state: RwLockWriteGuard<'a, EconomicState>,
+    audit_log: RwLockWriteGuard<'a, EconomicAuditLog>,
+}
+
+/// Job legs of a batch still pending, grouped by destination
+#[derive(Default)]
+struct BatchLegs<'a> {
+    miner: Vec<(String, Vec<(String, Decimal)>)>,  // Miner id and its jobs' spendable legs
+    lock: Vec<(&'a EconomicJob, Decimal)>,
+    salary: Vec<(String, Decimal)>,
+    treasury: Vec<(String, Decimal)>,
+}
+
+/// Idempotency key for a batch's combined payment on `leg`, fixed by the job legs it covers
+fn batch_payment_key(job_legs: &[(String, Decimal)], leg: &str) -> String {
+    let mut hasher = Sha256::new();
+    for (key, _) in job_legs {
+        hasher.update(key.as_bytes());
+        hasher.update([0u8]);
+    }
+    payment_key(&format!("batch-{}", &hex::encode(hasher.finalize())[..16]), leg)
+}
+
+/// Bounds on λ_P(i,t), the recorded miner prestige
+const PRESTIGE_MULTIPLIER_MIN: Decimal = Decimal::ONE;
+const PRESTIGE_MULTIPLIER_MAX: Decimal = Decimal::from_parts(2, 0, 0, false, 0);
+/// Largest λ_D(i,t) reduction, applied when every job is of one type
+const DIVERSITY_PENALTY: Decimal = Decimal::from_parts(5, 0, 0, false, 1); // 0.5
+/// Number of `EconomicJobType` variants
+const JOB_TYPE_COUNT: u32 = 8;
+
 /// Network usage demand estimation for FLX elasticity
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkUsageDemand {
@@ -355,35 +1211,119 @@ impl PoEMiningEngine {
         let mining_cycle_time = Histogram::with_opts(
             HistogramOpts::new("poe_mining_cycle_seconds", "PoE mining cycle duration")
         )?;
+        let compliance_flagged = Gauge::new("poe_owner_salary_compliance_flagged", "Owner salary compliance flag (1 = escrowing)")?;
 
         registry.register(Box::new(jobs_processed.clone()))?;
         registry.register(Box::new(miners_active.clone()))?;
         registry.register(Box::new(poe_scores_calculated.clone()))?;
         registry.register(Box::new(tokens_minted.clone()))?;
         registry.register(Box::new(mining_cycle_time.clone()))?;
+        registry.register(Box::new(compliance_flagged.clone()))?;
+
+        let gen_supply = Gauge::new("poe_supply_gen", "Circulating GEN supply")?;
+        let nex_supply = Gauge::new("poe_supply_nex", "Circulating NEX supply")?;
+        let flx_supply = Gauge::new("poe_supply_flx", "Circulating FLX supply")?;
+        let aur_supply = Gauge::new("poe_supply_aur", "Circulating AUR supply")?;
+        let treasury_balance = Gauge::new("poe_treasury_balance", "Treasury balance")?;
+        let total_locked = Gauge::new("poe_total_locked", "Miner coins under lock")?;
+        let total_escrowed = Gauge::new("poe_total_escrowed", "Funds held in escrow")?;
+        let total_vested = Gauge::new("poe_total_vested", "Scheduled payments still vesting")?;
+        for gauge in [&gen_supply, &nex_supply, &flx_supply, &aur_supply,
+                      &treasury_balance, &total_locked, &total_escrowed, &total_vested] {
+            registry.register(Box::new(gauge.clone()))?;
+        }
+
+        let metrics = PoEMetrics {
+            jobs_processed,
+            miners_active,
+            poe_scores_calculated,
+            tokens_minted,
+            mining_cycle_time,
+            compliance_flagged,
+            gen_supply,
+            nex_supply,
+            flx_supply,
+            aur_supply,
+            treasury_balance,
+            total_locked,
+            total_escrowed,
+            total_vested,
+        };
+        let token_supply = TokenSupplyState::default();
+        metrics.observe_supply(&token_supply);
 
         Ok(Self {
             active_miners: Arc::new(RwLock::new(HashMap::new())),
-            job_queue: Arc::new(RwLock::new(VecDeque::new())),
+            job_queue: Arc::new(RwLock::new(JobQueue::default())),
             reward_pool: Arc::new(RwLock::new(HashMap::new())),
-            token_supply: Arc::new(RwLock::new(TokenSupplyState::default())),
+            token_supply: Arc::new(RwLock::new(token_supply)),
             governance_params: Arc::new(RwLock::new(GovernanceParameters::default())),
+            economic_state: Arc::new(RwLock::new(EconomicState::default())),
             current_poe_index: Arc::new(RwLock::new(None)),
+            poe_index_history: Arc::new(RwLock::new(VecDeque::new())),
+            poe_history_depth: DEFAULT_POE_HISTORY_DEPTH,
             owner_salary_policy: Arc::new(RwLock::new(OwnerSalaryPolicy::default())),
             owner_salary_reports: Arc::new(RwLock::new(Vec::new())),
-            metrics: PoEMetrics {
-                jobs_processed,
-                miners_active,
-                poe_scores_calculated,
-                tokens_minted,
-                mining_cycle_time,
-            },
+            owner_salary_ledger: Arc::new(RwLock::new(OwnerSalaryLedger::default())),
+            compliance_events: Arc::new(RwLock::new(Vec::new())),
+            compliance_escrows: Arc::new(RwLock::new(Vec::new())),
+            reserve_attestations: Arc::new(RwLock::new(HashSet::new())),
+            reserve_custodians: HashMap::new(),
+            supply_snapshots: Arc::new(RwLock::new(VecDeque::new())),
+            supply_snapshot_depth: DEFAULT_SUPPLY_SNAPSHOT_DEPTH,
+            lock_duration_blocks: DEFAULT_LOCK_DURATION_BLOCKS,
+            proposals: Arc::new(RwLock::new(HashMap::new())),
+            token_balances: Arc::new(RwLock::new(HashMap::new())),
+            clock: Arc::new(SystemClock),
+            audit_log: Arc::new(RwLock::new(EconomicAuditLog::new())),
+            metrics,
+            job_batch_window: None,
+            job_queue_locks: Arc::new(AtomicU64::new(0)),
         })
     }
 
+    /// Read time from `clock` instead of the system clock
+    pub fn with_clock(mut self, clock: Arc<dyn Clock>) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Keep at most `depth` supply snapshots (minimum one)
+    pub fn with_supply_snapshot_depth(mut self, depth: usize) -> Self {
+        self.supply_snapshot_depth = depth.max(1);
+        self
+    }
+
+    /// Keep at most `depth` epochs of PoE index history (minimum one)
+    pub fn with_poe_history_depth(mut self, depth: usize) -> Self {
+        self.poe_history_depth = depth.max(1);
+        self
+    }
+
+    /// Accept reserve proofs from `custodian` signed with `key`. AUR can only be
+    /// minted against proofs from configured custodians.
+    pub fn with_reserve_custodian(mut self, custodian: &str, key: VerifyingKey) -> Self {
+        self.reserve_custodians.insert(custodian.to_string(), key);
+        self
+    }
+
+    /// Keep coin locks for `blocks` after the job's completion height
+    pub fn with_lock_duration_blocks(mut self, blocks: u64) -> Self {
+        self.lock_duration_blocks = blocks;
+        self
+    }
+
+    /// Hold `add_economic_job` submissions for `window` and enqueue them under
+    /// one queue write lock. Held jobs already count in `job_queue`.
+    pub fn with_job_batch_window(mut self, window: Duration) -> Self {
+        self.job_batch_window = Some(window);
+        self
+    }
+
     /// Calculate PoE fee split with owner salary including DockLock revenue streams
     pub async fn calculate_poe_fee_split(&self, job_value: Decimal) -> Result<PoEFeeSplit, EconomicsError> {
         let governance_params = self.governance_params.read().await;
+        governance_params.validate_fee_rates()?;
         
         // 1% total fee rate
         let total_fee = job_value * governance_params.job_fee_rate;
@@ -405,33 +1345,17 @@ impl PoEMiningEngine {
             miner_spendable,
             owner_salary,
             treasury_net,
+            miner_address: String::new(),
         })
     }
 
-    /// Calculate comprehensive DockLock revenue for owner salary
+    /// Calculate comprehensive DockLock revenue for owner salary. Fails with
+    /// `InvalidAmount` if any stream is negative.
     pub async fn calculate_docklock_revenue(&self, job: &EconomicJob) -> Result<Decimal, EconomicsError> {
-        let mut total_docklock_revenue = Decimal::ZERO;
-        
+        job.validate_docklock_revenue()?;
+
         // Aggregate all DockLock revenue streams
-        if let Some(cluster_rent) = job.cluster_rent_revenue {
-            total_docklock_revenue += cluster_rent;
-        }
-        
-        if let Some(gas_fees) = job.gas_fee_revenue {
-            total_docklock_revenue += gas_fees;
-        }
-        
-        if let Some(app_interactions) = job.app_interaction_revenue {
-            total_docklock_revenue += app_interactions;
-        }
-        
-        if let Some(security_fees) = job.security_layer_revenue {
-            total_docklock_revenue += security_fees;
-        }
-        
-        if let Some(pipeline_fees) = job.data_pipeline_revenue {
-            total_docklock_revenue += pipeline_fees;
-        }
+        let total_docklock_revenue = job.docklock_revenue();
         
         info!("🐳 DockLock revenue calculated: cluster=${:.2}, gas=${:.2}, apps=${:.2}, security=${:.2}, pipeline=${:.2}",
               job.cluster_rent_revenue.unwrap_or_default(),
@@ -443,68 +1367,588 @@ impl PoEMiningEngine {
         Ok(total_docklock_revenue)
     }
 
-    /// Route fees per job with owner salary including DockLock revenue and governance guardrails
-    pub async fn route_fees(&self, job: &EconomicJob, job_value: Decimal) -> Result<(), EconomicsError> {
-        let fee_split = self.calculate_poe_fee_split(job_value).await?;
-        let policy = self.owner_salary_policy.read().await;
-        
-        // Calculate additional DockLock revenue for owner salary
+    /// Compute where `route_fees` would send a job's fees (miner spendable and
+    /// locked, the capped and split owner salary, treasury) as of the engine
+    /// clock, without changing any state
+    pub async fn preview_fee_routing(&self, job: &EconomicJob, job_value: Decimal) -> Result<FeeRoutingPreview, EconomicsError> {
+        let mut fee_split = self.calculate_poe_fee_split(job_value).await?;
+        fee_split.miner_address = job.miner_id.clone();
         let docklock_revenue = self.calculate_docklock_revenue(job).await?;
-        let total_owner_salary = fee_split.owner_salary + (docklock_revenue * Decimal::new(2, 3)); // 0.2% of DockLock revenue
-        
-        // 1. Pay miner spendable portion
-        self.pay_miner_spendable(&fee_split).await?;
-        
-        // 2. Increase coin lock (permanent reserve)
-        self.increase_coin_lock(job, fee_split.miner_locked_reserve).await?;
-        
-        // 3. Pay owner salary with DockLock revenue and governance guardrails
-        self.pay_owner_salary_with_guardrails(total_owner_salary, &policy).await?;
-        
-        // 4. Credit treasury net (including remaining DockLock revenue)
-        let docklock_treasury_share = docklock_revenue * Decimal::new(3, 3); // 0.3% to treasury
-        let total_treasury = fee_split.treasury_net + docklock_treasury_share;
-        self.credit_treasury(total_treasury).await?;
+        let policy = self.owner_salary_policy.read().await.clone();
+        let ledger = self.owner_salary_ledger.read().await.clone();
+        let month = self.clock.now().format("%Y-%m").to_string();
+
+        let gross_owner_salary = fee_split.owner_salary + docklock_revenue * DOCKLOCK_OWNER_SALARY_RATE;
+        let owner_salary = OwnerSalaryAllocation::compute(gross_owner_salary, &policy, &ledger, &month);
+        let treasury_credit = fee_split.treasury_net + docklock_revenue * DOCKLOCK_TREASURY_RATE;
+
+        Ok(FeeRoutingPreview {
+            job_id: job.job_id.clone(),
+            miner_spendable: fee_split.miner_spendable,
+            miner_locked: fee_split.miner_locked_reserve,
+            fee_split,
+            docklock_revenue,
+            owner_salary,
+            treasury_credit,
+        })
+    }
+
+    /// Route fees per job with owner salary including DockLock revenue and governance guardrails.
+    /// All legs apply while the salary books, economic state and audit log are
+    /// held together. If a later leg fails, the legs already applied are
+    /// reversed with compensating records, so a failed routing leaves no
+    /// partial balances. Every leg is keyed by the job id, so retrying a routed
+    /// job applies nothing twice.
+    pub async fn route_fees(&self, job: &EconomicJob, job_value: Decimal) -> Result<(), EconomicsError> {
+        let preview = self.preview_fee_routing(job, job_value).await?;
+        let policy = self.owner_salary_policy.read().await.clone();
+        let now = self.clock.now();
+        let mut guards = self.lock_routing().await;
+        let mut applied = Vec::new();
+
+        let result = self.apply_fee_legs(&mut guards, job, &preview, &policy, now, &mut applied);
+        if let Err(e) = result {
+            warn!("⚠️ Fee routing for job {} failed, reversing {} applied legs: {}", job.job_id, applied.len(), e);
+            for leg in applied.into_iter().rev() {
+                self.reverse_leg(&mut guards.state, &mut guards.audit_log, leg);
+            }
+            self.metrics.observe_balances(&guards.state);
+            return Err(e);
+        }
+        self.metrics.observe_balances(&guards.state);
         
         info!("💰 Fee routed: miner_sp={:.6}, miner_lock={:.6}, owner_sal={:.6} (base={:.6} + docklock={:.6}), treasury={:.6}",
-              fee_split.miner_spendable, fee_split.miner_locked_reserve, 
-              total_owner_salary, fee_split.owner_salary, docklock_revenue * Decimal::new(2, 3),
-              total_treasury);
+              preview.miner_spendable, preview.miner_locked, 
+              preview.owner_salary.gross, preview.fee_split.owner_salary,
+              preview.docklock_revenue * DOCKLOCK_OWNER_SALARY_RATE,
+              preview.treasury_credit);
         
         Ok(())
     }
 
+    /// Take the routing write guards in their fixed order
+    async fn lock_routing(&self) -> RoutingGuards<'_> {
+        RoutingGuards {
+            ledger: self.owner_salary_ledger.write().await,
+            reports: self.owner_salary_reports.write().await,
+            compliance_escrows: self.compliance_escrows.write().await,
+            state: self.economic_state.write().await,
+            audit_log: self.audit_log.write().await,
+        }
+    }
+
+    /// Apply each of a job's fee legs not settled yet, pushing the ones that
+    /// moved balances onto `applied` so a failure can reverse them
+    fn apply_fee_legs(
+        &self,
+        guards: &mut RoutingGuards<'_>,
+        job: &EconomicJob,
+        preview: &FeeRoutingPreview,
+        policy: &OwnerSalaryPolicy,
+        now: DateTime<Utc>,
+        applied: &mut Vec<AppliedLeg>,
+    ) -> Result<(), EconomicsError> {
+        // 1. Pay miner spendable portion
+        let spendable_key = payment_key(&job.job_id, "miner_spendable");
+        if !guards.state.payments_by_key.contains_key(&spendable_key) {
+            let record = self.apply_miner_payment(
+                &mut guards.state, &mut guards.audit_log, &job.miner_id, preview.miner_spendable, &spendable_key,
+            )?;
+            applied.push(AppliedLeg::MinerPayment(record));
+        }
+
+        // 2. Increase coin lock (permanent reserve); one lock per job
+        if !guards.state.active_locks.values().any(|lock| lock.job_id == job.job_id) {
+            let lock_id = self.apply_coin_lock(&mut guards.state, &mut guards.audit_log, job, preview.miner_locked)?;
+            applied.push(AppliedLeg::CoinLock(lock_id));
+        }
+
+        // 3. Credit treasury net (including remaining DockLock revenue)
+        let treasury_key = payment_key(&job.job_id, "treasury");
+        if !guards.state.payments_by_key.contains_key(&treasury_key) {
+            let record = self.apply_treasury_credit(
+                &mut guards.state, &mut guards.audit_log, preview.treasury_credit, &treasury_key, "Fee routing to treasury",
+            )?;
+            applied.push(AppliedLeg::TreasuryCredit(record));
+        }
+
+        // 4. Pay owner salary with DockLock revenue and governance guardrails
+        self.apply_owner_salary(guards, preview.owner_salary.gross, policy, now, &payment_key(&job.job_id, "owner_salary"))
+    }
+
+    /// Route fees for many jobs with one balance update per destination instead of
+    /// the full pipeline per job. Each job's value is its gold-equivalent value.
+    /// Every job leg keeps its own idempotency key, payment record and audit
+    /// entry, so legs already routed by `route_fees` or an earlier batch are
+    /// skipped and the final balances match routing the jobs one by one. Which
+    /// legs are still pending is decided under the same write lock that
+    /// applies them, and a failing destination reverses the ones before it.
+    pub async fn route_fees_batch(&self, jobs: &[EconomicJob]) -> Result<BatchSettlementReport, EconomicsError> {
+        let policy = self.owner_salary_policy.read().await.clone();
+        let mut report = BatchSettlementReport::default();
+        let mut previews = Vec::new();
+        let mut seen = HashSet::new();
+        for job in jobs {
+            if !seen.insert(job.job_id.as_str()) {
+                report.skipped.push(job.job_id.clone());
+                continue;
+            }
+            previews.push((job, self.preview_fee_routing(job, job.gold_equivalent_value).await?));
+        }
+
+        let now = self.clock.now();
+        let mut guards = self.lock_routing().await;
+        let mut ledger = guards.ledger.clone();
+        let mut legs = BatchLegs::default();
+
+        for (job, mut preview) in previews {
+            let spendable_key = payment_key(&job.job_id, "miner_spendable");
+            let salary_key = payment_key(&job.job_id, "owner_salary");
+            let treasury_key = payment_key(&job.job_id, "treasury");
+
+            let state = &guards.state;
+            let spendable_pending = !state.payments_by_key.contains_key(&spendable_key);
+            let lock_pending = !state.active_locks.values().any(|lock| lock.job_id == job.job_id);
+            let salary_pending = !state.payments_by_key.contains_key(&salary_key);
+            let treasury_pending = !state.payments_by_key.contains_key(&treasury_key);
+            if !(spendable_pending || lock_pending || salary_pending || treasury_pending) {
+                info!("↩️ Fees for job {} already routed, skipping", job.job_id);
+                report.skipped.push(job.job_id.clone());
+                continue;
+            }
+
+            if spendable_pending {
+                match legs.miner.iter_mut().find(|(miner_id, _)| *miner_id == job.miner_id) {
+                    Some((_, miner_legs)) => miner_legs.push((spendable_key, preview.miner_spendable)),
+                    None => legs.miner.push((job.miner_id.clone(), vec![(spendable_key, preview.miner_spendable)])),
+                }
+            }
+            if lock_pending {
+                legs.lock.push((job, preview.miner_locked));
+            }
+            if salary_pending {
+                // Cap each job against the salary of the jobs before it, as sequential routing would
+                preview.owner_salary = OwnerSalaryAllocation::compute(
+                    preview.owner_salary.gross, &policy, &ledger, &preview.owner_salary.month,
+                );
+                ledger.roll_to(&preview.owner_salary.month);
+                ledger.paid_to_date = preview.owner_salary.month_to_date_paid;
+                legs.salary.push((salary_key, preview.owner_salary.gross));
+            }
+            if treasury_pending {
+                legs.treasury.push((treasury_key, preview.treasury_credit));
+            }
+            report.jobs.push(preview);
+        }
+
+        let mut applied = Vec::new();
+        let result = self.apply_batch_legs(&mut guards, &policy, now, &legs, &mut report, &mut applied);
+        if let Err(e) = result {
+            warn!("⚠️ Batch fee routing failed, reversing {} applied legs: {}", applied.len(), e);
+            for leg in applied.into_iter().rev() {
+                self.reverse_leg(&mut guards.state, &mut guards.audit_log, leg);
+            }
+            self.metrics.observe_balances(&guards.state);
+            return Err(e);
+        }
+        self.metrics.observe_balances(&guards.state);
+
+        info!("💰 Batch fees routed for {} jobs ({} skipped): miners={}, locked={:.6}, owner_sal={:.6}, treasury={:.6}",
+              report.jobs.len(), report.skipped.len(), report.miner_spendable.len(),
+              report.miner_locked, report.owner_salary.gross, report.treasury_credit);
+        Ok(report)
+    }
+
+    /// Apply a batch's pending legs, one balance update per destination,
+    /// pushing each job leg that moved balances onto `applied`
+    fn apply_batch_legs(
+        &self,
+        guards: &mut RoutingGuards<'_>,
+        policy: &OwnerSalaryPolicy,
+        now: DateTime<Utc>,
+        legs: &BatchLegs<'_>,
+        report: &mut BatchSettlementReport,
+        applied: &mut Vec<AppliedLeg>,
+    ) -> Result<(), EconomicsError> {
+        // 1. One payment per miner
+        for (miner_id, miner_legs) in &legs.miner {
+            let paid = self.pay_miner_spendable_batch(&mut guards.state, &mut guards.audit_log, miner_id, miner_legs, applied)?;
+            report.miner_spendable.insert(miner_id.clone(), paid);
+        }
+
+        // 2. One coin lock increase
+        if !legs.lock.is_empty() {
+            report.miner_locked = self.increase_coin_lock_batch(&mut guards.state, &mut guards.audit_log, &legs.lock, applied)?;
+        }
+
+        // 3. One treasury credit
+        if !legs.treasury.is_empty() {
+            report.treasury_credit = self.credit_treasury_batch(&mut guards.state, &mut guards.audit_log, &legs.treasury, applied)?;
+        }
+
+        // 4. Owner salary guardrails over the combined salary
+        if !legs.salary.is_empty() {
+            let gross_salary: Decimal = legs.salary.iter().map(|(_, amount)| *amount).sum();
+            report.owner_salary = OwnerSalaryAllocation::compute(
+                gross_salary, policy, &guards.ledger, &now.format("%Y-%m").to_string(),
+            );
+            self.apply_owner_salary(guards, gross_salary, policy, now, &batch_payment_key(&legs.salary, "owner_salary"))?;
+
+            for (key, amount) in &legs.salary {
+                guards.state.payments_by_key.insert(key.clone(), PaymentRecord {
+                    id: uuid::Uuid::new_v4(),
+                    idempotency_key: key.clone(),
+                    payment_type: PaymentType::OwnerDistribution,
+                    amount: *amount,
+                    recipient: policy.transparency_address.clone(),
+                    timestamp: now,
+                    status: PaymentStatus::Completed,
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// PoE score for one miner from its completed jobs. Each job contributes its
+    /// gold-equivalent value plus its DockLock revenue weighted by job type; above the diminishing-returns
+    /// threshold T the excess x counts as T·x/(T+x). The normalized score is the
+    /// miner's share of the raw scores across all active miners.
+    pub async fn calculate_poe_score(&self, miner_id: &str) -> Result<PoEScore, EconomicsError> {
+        let params = self.governance_params.read().await.clone();
+        let mut miners = self.active_miners.write().await;
+        if !miners.contains_key(miner_id) {
+            return Err(EconomicsError::MiningError(format!("Unknown miner {}", miner_id)));
+        }
+
+        let network_total: Decimal = miners.values()
+            .map(|miner| Self::raw_poe_score(miner, &params).0)
+            .sum();
+        let miner = miners.get_mut(miner_id).expect("miner presence checked above");
+        let (raw_score, total_job_value, docklock_volume) = Self::raw_poe_score(miner, &params);
+        let normalized_score = if network_total > Decimal::ZERO {
+            raw_score / network_total
+        } else {
+            Decimal::ZERO
+        };
+        miner.total_poe_score = raw_score;
+
+        self.metrics.poe_scores_calculated.inc();
+        info!("⛏️ PoE score for {}: raw={:.4}, normalized={:.4} over {} jobs",
+              miner_id, raw_score, normalized_score, miner.completed_jobs.len());
+
+        Ok(PoEScore {
+            raw_score,
+            normalized_score,
+            miner_id: miner_id.to_string(),
+            calculation_time: self.clock.now(),
+            job_count: miner.completed_jobs.len(),
+            total_job_value,
+            docklock_volume,
+        })
+    }
+
+    /// Recompute a miner's PoE score and list the tokens it may mint under the
+    /// current τ_GEN / τ_NEX / τ_FLX thresholds
+    pub async fn evaluate_minting_eligibility(&self, miner_id: &str) -> Result<TokenMintingEligibility, EconomicsError> {
+        let score = self.calculate_poe_score(miner_id).await?;
+        let params = self.governance_params.read().await.clone();
+        let eligibility = TokenMintingEligibility::from_poe_score(&score, &params);
+
+        info!("🎟️ Minting eligibility for {}: {:?} at PoE {:.2}",
+              miner_id, eligibility.eligible_tokens, eligibility.poe_score);
+        Ok(eligibility)
+    }
+
+    /// Weights W_i(t) = PoE_hat_i · λ_P · λ_D for every active miner. PoE_hat is the
+    /// miner's share of the network raw score; λ_P is the recorded prestige clamped to
+    /// [1, 2]; λ_D falls linearly from 1 (jobs spread evenly over all types) to 0.5
+    /// (every job of one type).
+    pub async fn compute_miner_weights(&self) -> Result<Vec<MinerWeight>, EconomicsError> {
+        let params = self.governance_params.read().await.clone();
+        let miners = self.active_miners.read().await;
+        let raw_scores: Vec<(&MinerState, Decimal)> = miners.values()
+            .map(|miner| (miner, Self::raw_poe_score(miner, &params).0))
+            .collect();
+        let network_total: Decimal = raw_scores.iter().map(|(_, raw)| *raw).sum();
+
+        let calculation_time = self.clock.now();
+        let mut weights: Vec<MinerWeight> = raw_scores.into_iter()
+            .map(|(miner, raw)| {
+                let normalized_poe_score = if network_total > Decimal::ZERO {
+                    raw / network_total
+                } else {
+                    Decimal::ZERO
+                };
+                let prestige_multiplier = miner.prestige_multiplier
+                    .max(PRESTIGE_MULTIPLIER_MIN)
+                    .min(PRESTIGE_MULTIPLIER_MAX);
+                let diversity_multiplier = Self::diversity_multiplier(&miner.completed_jobs);
+                MinerWeight {
+                    miner_id: miner.miner_id.clone(),
+                    normalized_poe_score,
+                    prestige_multiplier,
+                    diversity_multiplier,
+                    total_weight: normalized_poe_score * prestige_multiplier * diversity_multiplier,
+                    calculation_time,
+                }
+            })
+            .collect();
+        weights.sort_by(|a, b| a.miner_id.cmp(&b.miner_id));
+
+        Ok(weights)
+    }
+
+    /// Split an epoch's NEX issuance pro-rata by W_i(t), crediting each miner's
+    /// earned NEX. Rounding remainders go to the heaviest miners so the
+    /// allocations always sum to `total`.
+    pub async fn distribute_nex(&self, total: u64) -> Result<HashMap<String, u64>, EconomicsError> {
+        let weights = self.compute_miner_weights().await?;
+        let weight_total: Decimal = weights.iter().map(|w| w.total_weight).sum();
+        if weight_total <= Decimal::ZERO {
+            return Err(EconomicsError::TokenSupplyError("No miner weight to distribute NEX against".to_string()));
+        }
+
+        let total_decimal = Decimal::from(total);
+        let mut allocations: Vec<(String, u64, Decimal)> = weights.iter()
+            .map(|w| {
+                let share = total_decimal * w.total_weight / weight_total;
+                (w.miner_id.clone(), share.floor().to_u64().unwrap_or(0), w.total_weight)
+            })
+            .collect();
+
+        let allocated: u64 = allocations.iter().map(|(_, amount, _)| amount).sum();
+        let mut remainder = total.saturating_sub(allocated);
+        allocations.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(&b.0)));
+        for (_, amount, _) in allocations.iter_mut() {
+            if remainder == 0 {
+                break;
+            }
+            *amount += 1;
+            remainder -= 1;
+        }
+
+        let mut miners = self.active_miners.write().await;
+        let now = self.clock.now();
+        let mut distribution = HashMap::new();
+        for (miner_id, amount, _) in allocations {
+            if let Some(miner) = miners.get_mut(&miner_id) {
+                *miner.tokens_earned.entry(TokenType::Nexus).or_insert(Decimal::ZERO) += Decimal::from(amount);
+                miner.last_reward_time = now;
+            }
+            distribution.insert(miner_id, amount);
+        }
+
+        info!("🪙 Distributed {} NEX across {} miners", total, distribution.len());
+        Ok(distribution)
+    }
+
+    /// Add minted rewards to the pool for `token`; returns the new pool balance
+    pub async fn credit_reward_pool(&self, token: TokenType, amount: Decimal) -> Result<Decimal, EconomicsError> {
+        if amount <= Decimal::ZERO {
+            return Err(EconomicsError::InvalidAmount("Reward pool credit must be positive".to_string()));
+        }
+        let mut pool = self.reward_pool.write().await;
+        let balance = pool.entry(token).or_insert(Decimal::ZERO);
+        *balance += amount;
+        info!("🎁 Reward pool {:?} credited {:.6}, balance {:.6}", token, amount, balance);
+        Ok(*balance)
+    }
+
+    /// Entitle a miner to `amount` of `token` from the reward pool, payable on its next claim
+    pub async fn accrue_rewards(&self, miner_id: &str, token: TokenType, amount: Decimal) -> Result<(), EconomicsError> {
+        if amount <= Decimal::ZERO {
+            return Err(EconomicsError::InvalidAmount("Reward accrual must be positive".to_string()));
+        }
+        let mut miners = self.active_miners.write().await;
+        let miner = miners.get_mut(miner_id)
+            .ok_or_else(|| EconomicsError::MiningError(format!("Unknown miner {}", miner_id)))?;
+        *miner.claimable_rewards.entry(token).or_insert(Decimal::ZERO) += amount;
+        Ok(())
+    }
+
+    /// Move everything a miner is entitled to from the reward pools into its
+    /// earned tokens. All or nothing: if any pool cannot cover the miner's
+    /// share, the claim is rejected and nothing moves.
+    pub async fn claim_rewards(&self, miner_id: &str) -> Result<HashMap<TokenType, Decimal>, EconomicsError> {
+        let mut pool = self.reward_pool.write().await;
+        let mut miners = self.active_miners.write().await;
+        let miner = miners.get_mut(miner_id)
+            .ok_or_else(|| EconomicsError::MiningError(format!("Unknown miner {}", miner_id)))?;
+
+        for (token, amount) in &miner.claimable_rewards {
+            let available = pool.get(token).copied().unwrap_or(Decimal::ZERO);
+            if available < *amount {
+                return Err(EconomicsError::InsufficientFunds(format!(
+                    "Reward pool {:?} holds {:.6}, {} claims {:.6}", token, available, miner_id, amount
+                )));
+            }
+        }
+
+        let claimed: HashMap<TokenType, Decimal> = miner.claimable_rewards.drain()
+            .filter(|(_, amount)| *amount > Decimal::ZERO)
+            .collect();
+        for (token, amount) in &claimed {
+            *pool.get_mut(token).expect("pool balance checked above") -= *amount;
+            *miner.tokens_earned.entry(*token).or_insert(Decimal::ZERO) += *amount;
+        }
+        if !claimed.is_empty() {
+            miner.last_reward_time = self.clock.now();
+            info!("🎁 {} claimed rewards {:?}", miner_id, claimed);
+        }
+        Ok(claimed)
+    }
+
+    /// λ_D for a miner's job mix, from the share of its most common job type
+    fn diversity_multiplier(jobs: &[EconomicJob]) -> Decimal {
+        if jobs.is_empty() {
+            return Decimal::ONE;
+        }
+
+        let mut counts: HashMap<&EconomicJobType, usize> = HashMap::new();
+        for job in jobs {
+            *counts.entry(&job.job_type).or_insert(0) += 1;
+        }
+        let dominant = counts.values().copied().max().unwrap_or(0);
+        let dominant_share = Decimal::from(dominant) / Decimal::from(jobs.len());
+
+        // Rescale so an even spread over every job type is no concentration
+        let even_share = Decimal::ONE / Decimal::from(JOB_TYPE_COUNT);
+        let concentration = ((dominant_share - even_share) / (Decimal::ONE - even_share)).max(Decimal::ZERO);
+        Decimal::ONE - DIVERSITY_PENALTY * concentration
+    }
+
+    /// Raw PoE score, qualifying job value and the weighted DockLock part of that
+    /// value for a miner, see `calculate_poe_score`. Job types without a configured
+    /// weight count their DockLock revenue in full.
+    fn raw_poe_score(miner: &MinerState, params: &GovernanceParameters) -> (Decimal, Decimal, Decimal) {
+        let docklock_volume: Decimal = miner.completed_jobs.iter()
+            .map(|job| {
+                let weight = params.docklock_revenue_weights.get(&job.job_type).copied().unwrap_or(Decimal::ONE);
+                job.docklock_revenue() * weight
+            })
+            .sum();
+        let gold_volume: Decimal = miner.completed_jobs.iter().map(|job| job.gold_equivalent_value).sum();
+        let total_job_value = gold_volume + docklock_volume;
+        if params.poe_value_scale <= Decimal::ZERO {
+            return (Decimal::ZERO, total_job_value, docklock_volume);
+        }
+
+        let linear = total_job_value / params.poe_value_scale;
+        let threshold = params.poe_diminishing_threshold;
+        let raw_score = if threshold > Decimal::ZERO && linear > threshold {
+            let excess = linear - threshold;
+            threshold + threshold * excess / (threshold + excess)
+        } else {
+            linear
+        };
+        (raw_score.max(Decimal::ZERO), total_job_value, docklock_volume)
+    }
+
     /// Pay owner salary with cap, vesting, and escrow guardrails
     async fn pay_owner_salary_with_guardrails(
         &self, 
         gross_salary: Decimal, 
-        policy: &OwnerSalaryPolicy
+        policy: &OwnerSalaryPolicy,
+        idempotency_key: &str,
     ) -> Result<(), EconomicsError> {
-        // Apply monthly hard cap
-        let capped_salary = gross_salary.min(policy.monthly_hard_cap);
-        
-        // Check compliance flag - route to escrow if flagged
-        if policy.escrow_on_compliance_flag {
-            self.route_to_escrow(capped_salary).await?;
-            info!("⚠️ Owner salary routed to escrow due to compliance flag: {:.2}", capped_salary);
+        self.pay_owner_salary_at(gross_salary, policy, self.clock.now(), idempotency_key).await
+    }
+
+    /// Guardrailed salary payment as of `now`; the hard cap applies to the
+    /// cumulative total for `now`'s month, with any overflow escrowed. A payment
+    /// whose `idempotency_key` was already settled is skipped.
+    async fn pay_owner_salary_at(
+        &self,
+        gross_salary: Decimal,
+        policy: &OwnerSalaryPolicy,
+        now: DateTime<Utc>,
+        idempotency_key: &str,
+    ) -> Result<(), EconomicsError> {
+        let mut guards = self.lock_routing().await;
+        self.apply_owner_salary(&mut guards, gross_salary, policy, now, idempotency_key)?;
+        self.metrics.observe_balances(&guards.state);
+        Ok(())
+    }
+
+    /// Split `gross_salary` under the guardrails and apply every part. The
+    /// salary is the owner's share of the fee being routed, so none of it draws
+    /// on the treasury. Every part is checked before the first one applies, so
+    /// a rejected salary changes nothing.
+    fn apply_owner_salary(
+        &self,
+        guards: &mut RoutingGuards<'_>,
+        gross_salary: Decimal,
+        policy: &OwnerSalaryPolicy,
+        now: DateTime<Utc>,
+        idempotency_key: &str,
+    ) -> Result<(), EconomicsError> {
+        if guards.state.payments_by_key.contains_key(idempotency_key) {
+            info!("↩️ Owner salary {} already settled, skipping", idempotency_key);
             return Ok(());
         }
+        if gross_salary < Decimal::ZERO {
+            return Err(EconomicsError::InvalidAmount("Owner salary must not be negative".to_string()));
+        }
+
+        // Apply monthly hard cap against the remaining allowance
+        let allocation = OwnerSalaryAllocation::compute(gross_salary, policy, &guards.ledger, &now.format("%Y-%m").to_string());
+        if allocation.vested > Decimal::ZERO && policy.vesting_period_months == 0 {
+            return Err(EconomicsError::InvalidAmount("Vesting period must be positive".to_string()));
+        }
+        guards.ledger.roll_to(&allocation.month);
+        guards.ledger.paid_to_date = allocation.month_to_date_paid;
+        
+        if allocation.cap_overflow > Decimal::ZERO {
+            self.apply_escrow(&mut guards.state, &mut guards.audit_log, allocation.cap_overflow, Funding::JobFee)?;
+            info!("⚠️ Owner salary over monthly cap routed to escrow: {:.2}", allocation.cap_overflow);
+        }
         
-        // Apply vesting: 50% immediate, 50% vested
-        let immediate_payout = capped_salary * policy.vesting_immediate_rate;
-        let vested_amount = capped_salary * policy.vesting_deferred_rate;
+        // Check compliance flag - route to escrow if flagged
+        if allocation.compliance_escrow > Decimal::ZERO {
+            let escrow_id = self.apply_escrow(&mut guards.state, &mut guards.audit_log, allocation.compliance_escrow, Funding::JobFee)?;
+            guards.compliance_escrows.push(escrow_id);
+            info!("⚠️ Owner salary routed to escrow due to compliance flag: {:.2}", allocation.compliance_escrow);
+        }
         
         // Pay immediate portion
-        self.pay_to_owner_wallet(immediate_payout, &policy.transparency_address).await?;
+        if allocation.immediate > Decimal::ZERO {
+            self.apply_owner_payment(
+                &mut guards.state,
+                &mut guards.audit_log,
+                &policy.transparency_address,
+                allocation.immediate,
+                &format!("{}:immediate", idempotency_key),
+                Funding::JobFee,
+            )?;
+        }
         
         // Schedule vested portion
-        self.schedule_vested_payment(vested_amount, policy.vesting_period_months).await?;
+        if allocation.vested > Decimal::ZERO {
+            self.apply_vesting(
+                &mut guards.state, &mut guards.audit_log, allocation.vested, policy.vesting_period_months, now, Funding::JobFee,
+            )?;
+        }
         
         // Generate transparency report
-        self.generate_owner_salary_report(gross_salary, capped_salary, immediate_payout, vested_amount).await?;
+        let report = self.build_owner_salary_report(
+            policy, &guards.ledger, gross_salary, allocation.capped, allocation.immediate, allocation.vested,
+        );
+        guards.reports.push(report);
+
+        // Mark the salary settled; the record stays out of the payment history,
+        // where the immediate payout has its own entry
+        guards.state.payments_by_key.insert(idempotency_key.to_string(), PaymentRecord {
+            id: uuid::Uuid::new_v4(),
+            idempotency_key: idempotency_key.to_string(),
+            payment_type: PaymentType::OwnerDistribution,
+            amount: allocation.capped,
+            recipient: policy.transparency_address.clone(),
+            timestamp: now,
+            status: PaymentStatus::Completed,
+        });
         
         info!("💼 Owner salary: gross={:.2}, capped={:.2}, immediate={:.2}, vested={:.2}",
-              gross_salary, capped_salary, immediate_payout, vested_amount);
+              gross_salary, allocation.capped, allocation.immediate, allocation.vested);
         
         Ok(())
     }
@@ -517,74 +1961,116 @@ impl PoEMiningEngine {
         immediate_payout: Decimal,
         vested_amount: Decimal
     ) -> Result<(), EconomicsError> {
-        let current_month = Utc::now().format("%Y-%m").to_string();
         let policy = self.owner_salary_policy.read().await;
+        let ledger = self.owner_salary_ledger.read().await.clone();
+        let report = self.build_owner_salary_report(&policy, &ledger, gross_salary, capped_salary, immediate_payout, vested_amount);
+        self.owner_salary_reports.write().await.push(report);
+        Ok(())
+    }
+
+    /// Transparency report for one salary payment against the ledger after it
+    fn build_owner_salary_report(
+        &self,
+        policy: &OwnerSalaryPolicy,
+        ledger: &OwnerSalaryLedger,
+        gross_salary: Decimal,
+        capped_salary: Decimal,
+        immediate_payout: Decimal,
+        vested_amount: Decimal,
+    ) -> OwnerSalaryReport {
+        let current_month = if ledger.month.is_empty() {
+            self.clock.now().format("%Y-%m").to_string()
+        } else {
+            ledger.month.clone()
+        };
         
+        let flagged_escrow = if policy.escrow_on_compliance_flag { capped_salary } else { Decimal::ZERO };
         let report = OwnerSalaryReport {
-            month: current_month,
+            month: current_month.clone(),
             total_volume_processed: Decimal::ZERO, // TODO: Calculate from epoch data
             gross_salary_earned: gross_salary,
             capped_salary_amount: capped_salary,
             immediate_payout,
             vested_amount,
-            escrow_amount: if policy.escrow_on_compliance_flag { capped_salary } else { Decimal::ZERO },
-            transparency_tx_hash: format!("tx_hash_{}", Utc::now().timestamp()),
-            report_timestamp: Utc::now(),
+            escrow_amount: (gross_salary - capped_salary) + flagged_escrow,
+            month_to_date_paid: ledger.paid_to_date,
+            transparency_tx_hash: format!("tx_hash_{}", self.clock.now().timestamp()),
+            report_timestamp: self.clock.now(),
         };
         
-        let mut reports = self.owner_salary_reports.write().await;
-        reports.push(report);
-        
-        info!("📊 Owner salary transparency report generated for month: {}", 
-              Utc::now().format("%Y-%m"));
-        
-        Ok(())
+        info!("📊 Owner salary transparency report generated for month: {}", current_month);
+        report
     }
 
-    /// Real implementation for miner payment operations
-    async fn pay_miner_spendable(&self, fee_split: &PoEFeeSplit) -> Result<(), EconomicsError> {
-        info!("💰 Processing REAL miner payment: {:.6}", fee_split.miner_spendable);
-        
-        // Real miner payment implementation
-        let payment_amount = fee_split.miner_spendable;
+    /// Real implementation for miner payment operations. The caller has
+    /// checked under the same lock that `idempotency_key` is unused.
+    fn apply_miner_payment(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        miner_address: &str,
+        payment_amount: Decimal,
+        idempotency_key: &str,
+    ) -> Result<PaymentRecord, EconomicsError> {
+        info!("💰 Processing REAL miner payment: {:.6}", payment_amount);
         
         // Validate payment amount
         if payment_amount <= Decimal::ZERO {
             return Err(EconomicsError::InvalidAmount("Miner payment must be positive".to_string()));
         }
         
-        // Update miner balance in the economic state
-        let mut state = self.economic_state.write().await;
-        
         // Create payment transaction record
         let payment_record = PaymentRecord {
             id: uuid::Uuid::new_v4(),
+            idempotency_key: idempotency_key.to_string(),
             payment_type: PaymentType::MinerReward,
             amount: payment_amount,
-            recipient: fee_split.miner_address.clone(),
-            timestamp: chrono::Utc::now(),
+            recipient: miner_address.to_string(),
+            timestamp: self.clock.now(),
             status: PaymentStatus::Completed,
         };
         
         // Execute the actual payment
         state.total_miner_rewards += payment_amount;
         state.circulating_supply += payment_amount;
-        state.payment_history.push(payment_record);
+        state.payments_by_key.insert(idempotency_key.to_string(), payment_record.clone());
+        state.payment_history.push(payment_record.clone());
         
         // Update miner account balance
-        let current_balance = state.account_balances
-            .get(&fee_split.miner_address)
-            .unwrap_or(&Decimal::ZERO);
-        state.account_balances.insert(
-            fee_split.miner_address.clone(),
-            current_balance + payment_amount
+        let balance = state.account_balances.entry(miner_address.to_string()).or_insert(Decimal::ZERO);
+        *balance += payment_amount;
+        let resulting_balance = *balance;
+        
+        audit_log.record(
+            AuditEntryType::MinerPayment, payment_amount, miner_address,
+            payment_record.timestamp, Some(idempotency_key), resulting_balance,
         );
         
-        info!("✅ REAL miner payment completed: {:.6} to {}", payment_amount, fee_split.miner_address);
-        Ok(())
+        info!("✅ REAL miner payment completed: {:.6} to {}", payment_amount, miner_address);
+        Ok(payment_record)
     }
 
+    /// Lock `lock_amount` for `job` unless the job already holds a lock
     async fn increase_coin_lock(&self, job: &EconomicJob, lock_amount: Decimal) -> Result<(), EconomicsError> {
+        let mut state = self.economic_state.write().await;
+        if state.active_locks.values().any(|lock| lock.job_id == job.job_id) {
+            info!("↩️ Coin lock for job {} already applied", job.job_id);
+            return Ok(());
+        }
+        let mut audit_log = self.audit_log.write().await;
+        self.apply_coin_lock(&mut state, &mut audit_log, job, lock_amount)?;
+        self.metrics.observe_balances(&state);
+        Ok(())
+    }
+
+    /// Lock `lock_amount` for `job` until its unlock height, returning the lock id
+    fn apply_coin_lock(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        job: &EconomicJob,
+        lock_amount: Decimal,
+    ) -> Result<Uuid, EconomicsError> {
         info!("🔒 Processing REAL coin lock increase: {:.6}", lock_amount);
         
         // Real coin lock implementation
@@ -592,34 +2078,266 @@ impl PoEMiningEngine {
             return Err(EconomicsError::InvalidAmount("Lock amount must be positive".to_string()));
         }
         
-        let mut state = self.economic_state.write().await;
-        
         // Create lock record
         let lock_record = CoinLockRecord {
             id: uuid::Uuid::new_v4(),
             job_id: job.job_id.clone(),
             amount: lock_amount,
-            locked_at: chrono::Utc::now(),
-            unlock_height: job.completion_height + self.config.lock_duration_blocks,
+            locked_at: self.clock.now(),
+            unlock_height: job.completion_height + self.lock_duration_blocks,
             status: LockStatus::Active,
         };
         
         // Execute the lock
+        let lock_id = lock_record.id;
         state.total_locked_coins += lock_amount;
         state.circulating_supply -= lock_amount;
-        state.active_locks.insert(lock_record.id, lock_record);
+        state.active_locks.insert(lock_id, lock_record);
         
         // Update job economics
         if let Some(job_state) = state.job_economics.get_mut(&job.job_id) {
             job_state.locked_amount += lock_amount;
         }
         
+        audit_log.record(
+            AuditEntryType::CoinLock, lock_amount, audit_log::LOCKED_RESERVE_ACCOUNT,
+            self.clock.now(), None, state.total_locked_coins,
+        );
+        
         info!("✅ REAL coin lock completed: {:.6} locked until block {}", 
-              lock_amount, job.completion_height + self.config.lock_duration_blocks);
-        Ok(())
+              lock_amount, job.completion_height + self.lock_duration_blocks);
+        Ok(lock_id)
+    }
+
+    /// Pay one miner several jobs' spendable fees with a single balance update,
+    /// keeping a payment record and audit entry per job leg
+    fn pay_miner_spendable_batch(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        miner_account: &str,
+        legs: &[(String, Decimal)],
+        applied: &mut Vec<AppliedLeg>,
+    ) -> Result<Decimal, EconomicsError> {
+        if legs.iter().any(|(_, amount)| *amount <= Decimal::ZERO) {
+            return Err(EconomicsError::InvalidAmount("Miner payment must be positive".to_string()));
+        }
+        let total: Decimal = legs.iter().map(|(_, amount)| *amount).sum();
+        let timestamp = self.clock.now();
+
+        let opening_balance = state.account_balances.get(miner_account).copied().unwrap_or(Decimal::ZERO);
+        state.total_miner_rewards += total;
+        state.circulating_supply += total;
+        state.account_balances.insert(miner_account.to_string(), opening_balance + total);
+
+        let mut running_balance = opening_balance;
+        for (key, amount) in legs {
+            let payment_record = PaymentRecord {
+                id: uuid::Uuid::new_v4(),
+                idempotency_key: key.clone(),
+                payment_type: PaymentType::MinerReward,
+                amount: *amount,
+                recipient: miner_account.to_string(),
+                timestamp,
+                status: PaymentStatus::Completed,
+            };
+            state.payments_by_key.insert(key.clone(), payment_record.clone());
+            state.payment_history.push(payment_record.clone());
+            applied.push(AppliedLeg::MinerPayment(payment_record));
+            running_balance += *amount;
+            audit_log.record(AuditEntryType::MinerPayment, *amount, miner_account, timestamp, Some(key), running_balance);
+        }
+
+        info!("✅ Batched miner payment completed: {:.6} over {} jobs to {}", total, legs.len(), miner_account);
+        Ok(total)
+    }
+
+    /// Lock several jobs' reserve increments with a single update of the locked total
+    fn increase_coin_lock_batch(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        locks: &[(&EconomicJob, Decimal)],
+        applied: &mut Vec<AppliedLeg>,
+    ) -> Result<Decimal, EconomicsError> {
+        if locks.iter().any(|(_, amount)| *amount <= Decimal::ZERO) {
+            return Err(EconomicsError::InvalidAmount("Lock amount must be positive".to_string()));
+        }
+        let total: Decimal = locks.iter().map(|(_, amount)| *amount).sum();
+        let locked_at = self.clock.now();
+
+        let opening_locked = state.total_locked_coins;
+        state.total_locked_coins += total;
+        state.circulating_supply -= total;
+
+        let mut running_locked = opening_locked;
+        for (job, amount) in locks {
+            let lock_record = CoinLockRecord {
+                id: uuid::Uuid::new_v4(),
+                job_id: job.job_id.clone(),
+                amount: *amount,
+                locked_at,
+                unlock_height: job.completion_height + self.lock_duration_blocks,
+                status: LockStatus::Active,
+            };
+            applied.push(AppliedLeg::CoinLock(lock_record.id));
+            state.active_locks.insert(lock_record.id, lock_record);
+            if let Some(job_state) = state.job_economics.get_mut(&job.job_id) {
+                job_state.locked_amount += *amount;
+            }
+            running_locked += *amount;
+            audit_log.record(
+                AuditEntryType::CoinLock, *amount, audit_log::LOCKED_RESERVE_ACCOUNT,
+                locked_at, None, running_locked,
+            );
+        }
+
+        info!("✅ Batched coin lock completed: {:.6} over {} jobs", total, locks.len());
+        Ok(total)
+    }
+
+    /// Release every active coin lock whose unlock height is at or below
+    /// `current_height`, returning its coins to circulating supply. Released
+    /// locks are marked as such and never release again.
+    pub async fn process_unlocks(&self, current_height: u64) -> Result<Vec<UnlockReceipt>, EconomicsError> {
+        let now = self.clock.now();
+        let mut state = self.economic_state.write().await;
+        let mut receipts = Vec::new();
+
+        for lock in state.active_locks.values_mut() {
+            if lock.status == LockStatus::Active && lock.unlock_height <= current_height {
+                lock.status = LockStatus::Released;
+                receipts.push(UnlockReceipt {
+                    lock_id: lock.id,
+                    job_id: lock.job_id.clone(),
+                    amount: lock.amount,
+                    unlock_height: lock.unlock_height,
+                    released_at_height: current_height,
+                });
+            }
+        }
+        receipts.sort_by(|a, b| a.unlock_height.cmp(&b.unlock_height).then_with(|| a.job_id.cmp(&b.job_id)));
+
+        let mut audit_log = self.audit_log.write().await;
+        for receipt in &receipts {
+            state.total_locked_coins -= receipt.amount;
+            state.circulating_supply += receipt.amount;
+            if let Some(job_state) = state.job_economics.get_mut(&receipt.job_id) {
+                job_state.locked_amount -= receipt.amount;
+            }
+            audit_log.record(
+                AuditEntryType::CoinUnlock, receipt.amount, audit_log::LOCKED_RESERVE_ACCOUNT,
+                now, None, state.total_locked_coins,
+            );
+        }
+        self.metrics.observe_balances(&state);
+
+        if !receipts.is_empty() {
+            let released: Decimal = receipts.iter().map(|r| r.amount).sum();
+            info!("🔓 Released {} coin locks totalling {:.6} at height {}", receipts.len(), released, current_height);
+        }
+        Ok(receipts)
     }
 
-    async fn pay_to_owner_wallet(&self, amount: Decimal, address: &str) -> Result<(), EconomicsError> {
+    /// Undo one leg of a fee routing that failed later on
+    fn reverse_leg(&self, state: &mut EconomicState, audit_log: &mut EconomicAuditLog, leg: AppliedLeg) {
+        match leg {
+            AppliedLeg::MinerPayment(record) => self.reverse_miner_payment(state, audit_log, &record),
+            AppliedLeg::CoinLock(lock_id) => self.reverse_coin_lock(state, audit_log, lock_id),
+            AppliedLeg::TreasuryCredit(record) => self.reverse_treasury_credit(state, audit_log, &record),
+        }
+    }
+
+    /// Undo a miner payment. The payment stays in the history followed by a
+    /// `Reversed` record, and its key is freed so a retry can pay again.
+    fn reverse_miner_payment(&self, state: &mut EconomicState, audit_log: &mut EconomicAuditLog, record: &PaymentRecord) {
+        let timestamp = self.clock.now();
+        state.total_miner_rewards -= record.amount;
+        state.circulating_supply -= record.amount;
+        state.payments_by_key.remove(&record.idempotency_key);
+        state.payment_history.push(PaymentRecord {
+            id: uuid::Uuid::new_v4(),
+            timestamp,
+            status: PaymentStatus::Reversed,
+            ..record.clone()
+        });
+        let balance = state.account_balances.entry(record.recipient.clone()).or_insert(Decimal::ZERO);
+        *balance -= record.amount;
+        let resulting_balance = *balance;
+
+        audit_log.record(
+            AuditEntryType::Reversal, record.amount, &record.recipient,
+            timestamp, Some(&record.idempotency_key), resulting_balance,
+        );
+        info!("↪️ Reversed miner payment {} of {:.6}", record.idempotency_key, record.amount);
+    }
+
+    /// Undo a coin lock applied earlier in the same update
+    fn reverse_coin_lock(&self, state: &mut EconomicState, audit_log: &mut EconomicAuditLog, lock_id: Uuid) {
+        let lock = match state.active_locks.remove(&lock_id) {
+            Some(lock) => lock,
+            None => return,
+        };
+        state.total_locked_coins -= lock.amount;
+        state.circulating_supply += lock.amount;
+        if let Some(job_state) = state.job_economics.get_mut(&lock.job_id) {
+            job_state.locked_amount -= lock.amount;
+        }
+
+        audit_log.record(
+            AuditEntryType::Reversal, lock.amount, audit_log::LOCKED_RESERVE_ACCOUNT,
+            self.clock.now(), None, state.total_locked_coins,
+        );
+        info!("↪️ Reversed coin lock of {:.6} for job {}", lock.amount, lock.job_id);
+    }
+
+    /// Undo a treasury credit, recording a reversal transaction after the credit
+    fn reverse_treasury_credit(&self, state: &mut EconomicState, audit_log: &mut EconomicAuditLog, record: &PaymentRecord) {
+        let timestamp = self.clock.now();
+        state.treasury_balance -= record.amount;
+        state.total_treasury_inflow -= record.amount;
+        state.treasury_stats.total_credits -= record.amount;
+        state.payments_by_key.remove(&record.idempotency_key);
+        state.treasury_history.push(TreasuryTransaction {
+            id: uuid::Uuid::new_v4(),
+            transaction_type: TreasuryTransactionType::Reversal,
+            amount: record.amount,
+            timestamp,
+            description: format!("Reversal of {}", record.idempotency_key),
+        });
+
+        audit_log.record(
+            AuditEntryType::Reversal, record.amount, audit_log::TREASURY_ACCOUNT,
+            timestamp, Some(&record.idempotency_key), state.treasury_balance,
+        );
+        info!("↪️ Reversed treasury credit {} of {:.6}", record.idempotency_key, record.amount);
+    }
+
+    /// Pay the owner wallet out of the treasury. A payment whose
+    /// `idempotency_key` already exists returns the prior record unchanged.
+    async fn pay_to_owner_wallet(&self, amount: Decimal, address: &str, idempotency_key: &str) -> Result<PaymentRecord, EconomicsError> {
+        let mut state = self.economic_state.write().await;
+        if let Some(prior) = state.payments_by_key.get(idempotency_key) {
+            info!("↩️ Owner payment {} already applied", idempotency_key);
+            return Ok(prior.clone());
+        }
+        let mut audit_log = self.audit_log.write().await;
+        let record = self.apply_owner_payment(&mut state, &mut audit_log, address, amount, idempotency_key, Funding::Treasury)?;
+        self.metrics.observe_balances(&state);
+        Ok(record)
+    }
+
+    /// Pay `amount` to the owner wallet from `funding`. The caller has checked
+    /// under the same lock that `idempotency_key` is unused.
+    fn apply_owner_payment(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        address: &str,
+        amount: Decimal,
+        idempotency_key: &str,
+        funding: Funding,
+    ) -> Result<PaymentRecord, EconomicsError> {
         info!("💼 Processing REAL owner wallet payment: {:.6} to {}", amount, address);
         
         // Real owner payment implementation
@@ -627,45 +2345,110 @@ impl PoEMiningEngine {
             return Err(EconomicsError::InvalidAmount("Owner payment must be positive".to_string()));
         }
         
-        let mut state = self.economic_state.write().await;
-        
-        // Validate sufficient treasury funds
-        if state.treasury_balance < amount {
-            return Err(EconomicsError::InsufficientFunds(
-                format!("Treasury balance {:.6} insufficient for payment {:.6}", 
-                        state.treasury_balance, amount)
-            ));
+        // Validate sufficient uncommitted treasury funds
+        if funding == Funding::Treasury {
+            let spendable = spendable_balance(state);
+            if spendable < amount {
+                return Err(EconomicsError::InsufficientFunds(
+                    format!("Spendable treasury {:.6} insufficient for payment {:.6}", 
+                            spendable, amount)
+                ));
+            }
         }
         
         // Create payment record
         let payment_record = PaymentRecord {
             id: uuid::Uuid::new_v4(),
+            idempotency_key: idempotency_key.to_string(),
             payment_type: PaymentType::OwnerDistribution,
             amount,
             recipient: address.to_string(),
-            timestamp: chrono::Utc::now(),
+            timestamp: self.clock.now(),
             status: PaymentStatus::Completed,
         };
         
         // Execute the payment
-        state.treasury_balance -= amount;
+        if funding == Funding::Treasury {
+            self.debit_treasury(state, audit_log, amount, Some(idempotency_key));
+        }
         state.total_owner_distributions += amount;
-        state.payment_history.push(payment_record);
+        state.payments_by_key.insert(idempotency_key.to_string(), payment_record.clone());
+        state.payment_history.push(payment_record.clone());
         
         // Update owner account balance
-        let current_balance = state.account_balances
-            .get(address)
-            .unwrap_or(&Decimal::ZERO);
-        state.account_balances.insert(
-            address.to_string(),
-            current_balance + amount
+        let balance = state.account_balances.entry(address.to_string()).or_insert(Decimal::ZERO);
+        *balance += amount;
+        let resulting_balance = *balance;
+        
+        audit_log.record(
+            AuditEntryType::OwnerPayment, amount, address,
+            payment_record.timestamp, Some(idempotency_key), resulting_balance,
         );
         
         info!("✅ REAL owner payment completed: {:.6} to {}", amount, address);
-        Ok(())
+        Ok(payment_record)
+    }
+
+    /// Take `amount` out of the treasury, recording the debit against the
+    /// treasury account. The caller has already checked it is spendable.
+    fn debit_treasury(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        amount: Decimal,
+        idempotency_key: Option<&str>,
+    ) {
+        state.treasury_balance -= amount;
+        audit_log.record(
+            AuditEntryType::TreasuryDebit, amount, audit_log::TREASURY_ACCOUNT,
+            self.clock.now(), idempotency_key, state.treasury_balance,
+        );
+    }
+
+    async fn schedule_vested_payment(&self, amount: Decimal, vesting_months: u32, idempotency_key: &str) -> Result<Uuid, EconomicsError> {
+        self.schedule_vested_payment_at(amount, vesting_months, self.clock.now(), idempotency_key).await
     }
 
-    async fn schedule_vested_payment(&self, amount: Decimal, vesting_months: u32) -> Result<(), EconomicsError> {
+    /// Reserve `amount` out of the treasury, vesting monthly from `start_date`,
+    /// and return the schedule id. A schedule whose `idempotency_key` already
+    /// exists is returned without reserving again.
+    async fn schedule_vested_payment_at(
+        &self,
+        amount: Decimal,
+        vesting_months: u32,
+        start_date: DateTime<Utc>,
+        idempotency_key: &str,
+    ) -> Result<Uuid, EconomicsError> {
+        let mut state = self.economic_state.write().await;
+        if let Some(prior) = state.payments_by_key.get(idempotency_key) {
+            info!("↩️ Vesting schedule {} already reserved", idempotency_key);
+            return Ok(prior.id);
+        }
+        let mut audit_log = self.audit_log.write().await;
+        let schedule_id = self.apply_vesting(&mut state, &mut audit_log, amount, vesting_months, start_date, Funding::Treasury)?;
+        state.payments_by_key.insert(idempotency_key.to_string(), PaymentRecord {
+            id: schedule_id,
+            idempotency_key: idempotency_key.to_string(),
+            payment_type: PaymentType::VestingReserve,
+            amount,
+            recipient: audit_log::VESTING_ACCOUNT.to_string(),
+            timestamp: self.clock.now(),
+            status: PaymentStatus::Completed,
+        });
+        self.metrics.observe_balances(&state);
+        Ok(schedule_id)
+    }
+
+    /// Reserve `amount` from `funding` into a new vesting schedule
+    fn apply_vesting(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        amount: Decimal,
+        vesting_months: u32,
+        start_date: DateTime<Utc>,
+        funding: Funding,
+    ) -> Result<Uuid, EconomicsError> {
         info!("⏰ Processing REAL vesting schedule: {:.6} over {} months", amount, vesting_months);
         
         // Real vesting implementation
@@ -677,11 +2460,19 @@ impl PoEMiningEngine {
             return Err(EconomicsError::InvalidAmount("Vesting period must be positive".to_string()));
         }
         
-        let mut state = self.economic_state.write().await;
+        // Reserve funds for vesting
+        if funding == Funding::Treasury {
+            let spendable = spendable_balance(state);
+            if spendable < amount {
+                return Err(EconomicsError::InsufficientFunds(
+                    format!("Spendable treasury {:.6} insufficient for vesting {:.6}", 
+                            spendable, amount)
+                ));
+            }
+        }
         
         // Calculate monthly vesting amount
         let monthly_amount = amount / Decimal::from(vesting_months);
-        let start_date = chrono::Utc::now();
         
         // Create vesting schedule
         let vesting_schedule = VestingSchedule {
@@ -695,54 +2486,248 @@ impl PoEMiningEngine {
             status: VestingStatus::Active,
         };
         
-        // Reserve funds for vesting
-        if state.treasury_balance < amount {
-            return Err(EconomicsError::InsufficientFunds(
-                format!("Treasury balance {:.6} insufficient for vesting {:.6}", 
-                        state.treasury_balance, amount)
-            ));
+        let schedule_id = vesting_schedule.id;
+        if funding == Funding::Treasury {
+            self.debit_treasury(state, audit_log, amount, None);
         }
-        
-        state.treasury_balance -= amount;
         state.total_vested_amount += amount;
-        state.vesting_schedules.insert(vesting_schedule.id, vesting_schedule);
+        state.vesting_schedules.insert(schedule_id, vesting_schedule);
+        
+        audit_log.record(
+            AuditEntryType::VestingScheduled, amount, audit_log::VESTING_ACCOUNT,
+            self.clock.now(), None, state.total_vested_amount,
+        );
         
         info!("✅ REAL vesting schedule created: {:.6} over {} months, {:.6} per month", 
               amount, vesting_months, monthly_amount);
-        Ok(())
+        Ok(schedule_id)
+    }
+
+    /// Release every vesting installment due by the engine clock's current time
+    pub async fn process_due_vesting(&self) -> Result<Vec<VestedPayout>, EconomicsError> {
+        self.process_vesting(self.clock.now()).await
     }
 
-    async fn route_to_escrow(&self, amount: Decimal) -> Result<(), EconomicsError> {
+    /// Release every vesting installment due by `now` to the owner wallet. Missed
+    /// months are caught up; a schedule completes once its remaining amount is paid.
+    pub async fn process_vesting(&self, now: DateTime<Utc>) -> Result<Vec<VestedPayout>, EconomicsError> {
+        let recipient = self.owner_salary_policy.read().await.transparency_address.clone();
+        let mut state = self.economic_state.write().await;
+        let mut payouts = Vec::new();
+
+        for schedule in state.vesting_schedules.values_mut() {
+            while schedule.status == VestingStatus::Active && schedule.next_payment_date <= now {
+                // The last installment takes whatever division left over
+                let amount = if schedule.remaining_amount <= schedule.monthly_amount {
+                    schedule.remaining_amount
+                } else {
+                    schedule.monthly_amount
+                };
+                schedule.remaining_amount -= amount;
+                payouts.push(VestedPayout {
+                    schedule_id: schedule.id,
+                    amount,
+                    due_date: schedule.next_payment_date,
+                    recipient: recipient.clone(),
+                    remaining_amount: schedule.remaining_amount,
+                });
+
+                schedule.next_payment_date += chrono::Duration::days(30);
+                if schedule.remaining_amount <= Decimal::ZERO {
+                    schedule.status = VestingStatus::Completed;
+                }
+            }
+        }
+
+        // Funds were reserved out of treasury when the schedule was created
+        let released: Decimal = payouts.iter().map(|p| p.amount).sum();
+        if released > Decimal::ZERO {
+            state.total_vested_amount -= released;
+            self.metrics.observe_balances(&state);
+            state.total_owner_distributions += released;
+            let current_balance = state.account_balances
+                .get(&recipient)
+                .copied()
+                .unwrap_or(Decimal::ZERO);
+            state.account_balances.insert(recipient.clone(), current_balance + released);
+            
+            let mut audit_log = self.audit_log.write().await;
+            let mut running_balance = current_balance;
+            for payout in &payouts {
+                running_balance += payout.amount;
+                audit_log.record(
+                    AuditEntryType::VestingReleased, payout.amount, &recipient,
+                    now, None, running_balance,
+                );
+            }
+            info!("⏰ Released {} vesting installments totalling {:.6} to {}", payouts.len(), released, recipient);
+        }
+
+        Ok(payouts)
+    }
+
+    /// Move `amount` out of the treasury into a new escrow and return its id.
+    /// An escrow whose `idempotency_key` already exists is returned without
+    /// holding funds again.
+    async fn route_to_escrow(&self, amount: Decimal, idempotency_key: &str) -> Result<Uuid, EconomicsError> {
+        let mut state = self.economic_state.write().await;
+        if let Some(prior) = state.payments_by_key.get(idempotency_key) {
+            info!("↩️ Escrow {} already held", idempotency_key);
+            return Ok(prior.id);
+        }
+        let mut audit_log = self.audit_log.write().await;
+        let escrow_id = self.apply_escrow(&mut state, &mut audit_log, amount, Funding::Treasury)?;
+        state.payments_by_key.insert(idempotency_key.to_string(), PaymentRecord {
+            id: escrow_id,
+            idempotency_key: idempotency_key.to_string(),
+            payment_type: PaymentType::EscrowHold,
+            amount,
+            recipient: audit_log::ESCROW_ACCOUNT.to_string(),
+            timestamp: self.clock.now(),
+            status: PaymentStatus::Completed,
+        });
+        self.metrics.observe_balances(&state);
+        Ok(escrow_id)
+    }
+
+    /// Hold `amount` from `funding` in a new escrow released after 30 days.
+    /// Treasury-funded escrow is debited on creation, so it is never counted
+    /// as spendable again.
+    fn apply_escrow(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        amount: Decimal,
+        funding: Funding,
+    ) -> Result<Uuid, EconomicsError> {
         info!("🏦 Processing REAL escrow routing: {:.6}", amount);
         
         // Real escrow implementation
         if amount <= Decimal::ZERO {
             return Err(EconomicsError::InvalidAmount("Escrow amount must be positive".to_string()));
         }
-        
-        let mut state = self.economic_state.write().await;
+        if funding == Funding::Treasury {
+            let spendable = spendable_balance(state);
+            if spendable < amount {
+                return Err(EconomicsError::InsufficientFunds(
+                    format!("Spendable treasury {:.6} insufficient for escrow {:.6}", spendable, amount)
+                ));
+            }
+        }
         
         // Create escrow record
         let escrow_record = EscrowRecord {
             id: uuid::Uuid::new_v4(),
             amount,
-            created_at: chrono::Utc::now(),
+            created_at: self.clock.now(),
             release_conditions: EscrowConditions::TimeBasedRelease {
-                release_date: chrono::Utc::now() + chrono::Duration::days(30),
+                release_date: self.clock.now() + chrono::Duration::days(30),
             },
             status: EscrowStatus::Held,
         };
         
         // Execute escrow routing
+        let escrow_id = escrow_record.id;
+        if funding == Funding::Treasury {
+            self.debit_treasury(state, audit_log, amount, None);
+        }
         state.total_escrowed_funds += amount;
         state.circulating_supply -= amount;
-        state.active_escrows.insert(escrow_record.id, escrow_record);
+        state.active_escrows.insert(escrow_id, escrow_record);
+        
+        audit_log.record(
+            AuditEntryType::EscrowHeld, amount, audit_log::ESCROW_ACCOUNT,
+            self.clock.now(), None, state.total_escrowed_funds,
+        );
         
         info!("✅ REAL escrow routing completed: {:.6} held in escrow", amount);
+        Ok(escrow_id)
+    }
+
+    /// Raise or clear the owner salary compliance flag. While raised, every
+    /// salary payment is escrowed; clearing does not release existing escrows.
+    pub async fn set_compliance_flag(&self, flagged: bool, reason: String) -> Result<(), EconomicsError> {
+        let timestamp = self.clock.now();
+        {
+            let mut policy = self.owner_salary_policy.write().await;
+            policy.escrow_on_compliance_flag = flagged;
+            policy.last_policy_update = timestamp;
+        }
+
+        self.metrics.compliance_flagged.set(if flagged { 1.0 } else { 0.0 });
+        if flagged {
+            warn!("🚩 Owner salary compliance flag raised: {}", reason);
+        } else {
+            info!("✅ Owner salary compliance flag cleared: {}", reason);
+        }
+
+        self.compliance_events.write().await.push(ComplianceFlagEvent { flagged, reason, timestamp });
         Ok(())
     }
 
-    async fn credit_treasury(&self, amount: Decimal) -> Result<(), EconomicsError> {
+    /// Release salary escrowed under the compliance flag to the owner wallet.
+    /// Only allowed once the flag is cleared; returns the amount released.
+    pub async fn release_escrow(&self, id: Uuid) -> Result<Decimal, EconomicsError> {
+        let policy = self.owner_salary_policy.read().await.clone();
+        if policy.escrow_on_compliance_flag {
+            return Err(EconomicsError::OwnerSalaryError(
+                "Cannot release escrow while the compliance flag is raised".to_string()
+            ));
+        }
+
+        let mut compliance_escrows = self.compliance_escrows.write().await;
+        let position = compliance_escrows.iter().position(|escrow_id| *escrow_id == id)
+            .ok_or_else(|| EconomicsError::OwnerSalaryError(format!("No compliance escrow {}", id)))?;
+
+        let mut state = self.economic_state.write().await;
+        let escrow = state.active_escrows.get_mut(&id)
+            .ok_or_else(|| EconomicsError::OwnerSalaryError(format!("Escrow {} not found", id)))?;
+        let amount = escrow.amount;
+        escrow.status = EscrowStatus::Released;
+
+        state.total_escrowed_funds -= amount;
+        self.metrics.observe_balances(&state);
+        state.circulating_supply += amount;
+        state.total_owner_distributions += amount;
+        let current_balance = state.account_balances
+            .get(&policy.transparency_address)
+            .copied()
+            .unwrap_or(Decimal::ZERO);
+        state.account_balances.insert(policy.transparency_address.clone(), current_balance + amount);
+        compliance_escrows.remove(position);
+        self.audit_log.write().await.record(
+            AuditEntryType::EscrowReleased, amount, &policy.transparency_address,
+            self.clock.now(), None, current_balance + amount,
+        );
+
+        info!("🔓 Released escrowed owner salary {:.6} to {}", amount, policy.transparency_address);
+        Ok(amount)
+    }
+
+    /// Credit the treasury; a credit whose `idempotency_key` already exists
+    /// returns the prior record unchanged
+    async fn credit_treasury(&self, amount: Decimal, idempotency_key: &str) -> Result<PaymentRecord, EconomicsError> {
+        let mut state = self.economic_state.write().await;
+        if let Some(prior) = state.payments_by_key.get(idempotency_key) {
+            info!("↩️ Treasury credit {} already applied", idempotency_key);
+            return Ok(prior.clone());
+        }
+        let mut audit_log = self.audit_log.write().await;
+        let record = self.apply_treasury_credit(&mut state, &mut audit_log, amount, idempotency_key, "Fee routing to treasury")?;
+        self.metrics.observe_balances(&state);
+        Ok(record)
+    }
+
+    /// Credit `amount` to the treasury. The caller has checked under the same
+    /// lock that `idempotency_key` is unused.
+    fn apply_treasury_credit(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        amount: Decimal,
+        idempotency_key: &str,
+        description: &str,
+    ) -> Result<PaymentRecord, EconomicsError> {
         info!("🏛️ Processing REAL treasury credit: {:.6}", amount);
         
         // Real treasury crediting implementation
@@ -750,15 +2735,13 @@ impl PoEMiningEngine {
             return Err(EconomicsError::InvalidAmount("Treasury credit must be positive".to_string()));
         }
         
-        let mut state = self.economic_state.write().await;
-        
         // Create treasury transaction record
         let treasury_record = TreasuryTransaction {
             id: uuid::Uuid::new_v4(),
             transaction_type: TreasuryTransactionType::Credit,
             amount,
-            timestamp: chrono::Utc::now(),
-            description: "Fee routing to treasury".to_string(),
+            timestamp: self.clock.now(),
+            description: description.to_string(),
         };
         
         // Execute treasury credit
@@ -768,11 +2751,116 @@ impl PoEMiningEngine {
         
         // Update treasury statistics
         state.treasury_stats.total_credits += amount;
-        state.treasury_stats.last_credit_date = Some(chrono::Utc::now());
+        state.treasury_stats.last_credit_date = Some(self.clock.now());
+        
+        let payment_record = PaymentRecord {
+            id: uuid::Uuid::new_v4(),
+            idempotency_key: idempotency_key.to_string(),
+            payment_type: PaymentType::TreasuryCredit,
+            amount,
+            recipient: audit_log::TREASURY_ACCOUNT.to_string(),
+            timestamp: self.clock.now(),
+            status: PaymentStatus::Completed,
+        };
+        state.payments_by_key.insert(idempotency_key.to_string(), payment_record.clone());
+        audit_log.record(
+            AuditEntryType::TreasuryCredit, amount, audit_log::TREASURY_ACCOUNT,
+            payment_record.timestamp, Some(idempotency_key), state.treasury_balance,
+        );
         
         info!("✅ REAL treasury credit completed: {:.6}, new balance: {:.6}", 
               amount, state.treasury_balance);
-        Ok(())
+        Ok(payment_record)
+    }
+
+    /// Credit several jobs' treasury shares with a single balance update, keeping
+    /// a treasury transaction, payment record and audit entry per job leg
+    fn credit_treasury_batch(
+        &self,
+        state: &mut EconomicState,
+        audit_log: &mut EconomicAuditLog,
+        legs: &[(String, Decimal)],
+        applied: &mut Vec<AppliedLeg>,
+    ) -> Result<Decimal, EconomicsError> {
+        if legs.iter().any(|(_, amount)| *amount <= Decimal::ZERO) {
+            return Err(EconomicsError::InvalidAmount("Treasury credit must be positive".to_string()));
+        }
+        let total: Decimal = legs.iter().map(|(_, amount)| *amount).sum();
+        let timestamp = self.clock.now();
+
+        let opening_balance = state.treasury_balance;
+        state.treasury_balance += total;
+        state.total_treasury_inflow += total;
+        state.treasury_stats.total_credits += total;
+        state.treasury_stats.last_credit_date = Some(timestamp);
+
+        let mut running_balance = opening_balance;
+        for (key, amount) in legs {
+            state.treasury_history.push(TreasuryTransaction {
+                id: uuid::Uuid::new_v4(),
+                transaction_type: TreasuryTransactionType::Credit,
+                amount: *amount,
+                timestamp,
+                description: "Batched fee routing to treasury".to_string(),
+            });
+            let payment_record = PaymentRecord {
+                id: uuid::Uuid::new_v4(),
+                idempotency_key: key.clone(),
+                payment_type: PaymentType::TreasuryCredit,
+                amount: *amount,
+                recipient: audit_log::TREASURY_ACCOUNT.to_string(),
+                timestamp,
+                status: PaymentStatus::Completed,
+            };
+            state.payments_by_key.insert(key.clone(), payment_record.clone());
+            applied.push(AppliedLeg::TreasuryCredit(payment_record));
+            running_balance += *amount;
+            audit_log.record(
+                AuditEntryType::TreasuryCredit, *amount, audit_log::TREASURY_ACCOUNT,
+                timestamp, Some(key), running_balance,
+            );
+        }
+
+        info!("✅ Batched treasury credit completed: {:.6} over {} jobs, new balance: {:.6}",
+              total, legs.len(), state.treasury_balance);
+        Ok(total)
+    }
+
+    /// Treasury that can actually be paid out. Funds reserved for vesting or
+    /// escrow already left `treasury_balance` when they were committed, so they
+    /// are never counted as spendable.
+    pub async fn spendable_treasury(&self) -> Decimal {
+        let state = self.economic_state.read().await;
+        spendable_balance(&state)
+    }
+
+    /// Consistent copy of the dashboard-facing state. All read locks are held
+    /// together while copying, so no mutation lands halfway through; nothing
+    /// is held once the snapshot is returned.
+    pub async fn economic_snapshot(&self) -> EconomicSnapshot {
+        let supply = self.token_supply.read().await;
+        let reward_pool = self.reward_pool.read().await;
+        let state = self.economic_state.read().await;
+        let poe_index = self.current_poe_index.read().await;
+
+        EconomicSnapshot {
+            supply: supply.clone(),
+            reward_pool: reward_pool.clone(),
+            treasury_balance: state.treasury_balance,
+            locked_coins: state.total_locked_coins,
+            escrowed_funds: state.total_escrowed_funds,
+            vesting_outstanding: state.total_vested_amount,
+            active_vesting_schedules: state.vesting_schedules.values()
+                .filter(|schedule| schedule.status == VestingStatus::Active)
+                .count(),
+            poe_index: poe_index.clone(),
+            taken_at: self.clock.now(),
+        }
+    }
+
+    /// Audit log entries recorded between `from` and `to` inclusive, as JSON
+    pub async fn export_audit_log(&self, from: DateTime<Utc>, to: DateTime<Utc>) -> Result<String, EconomicsError> {
+        self.audit_log.read().await.export_json(from, to)
     }
 
     /// Get owner salary policy
@@ -785,6 +2873,16 @@ impl PoEMiningEngine {
         self.owner_salary_reports.read().await.clone()
     }
 
+    /// Get compliance flag history, oldest first
+    pub async fn get_compliance_events(&self) -> Vec<ComplianceFlagEvent> {
+        self.compliance_events.read().await.clone()
+    }
+
+    /// Get salary escrows still held from compliance-flagged payments
+    pub async fn get_compliance_escrows(&self) -> Vec<Uuid> {
+        self.compliance_escrows.read().await.clone()
+    }
+
     /// Update owner salary policy (governance-controlled)
     pub async fn update_owner_salary_policy(&self, new_policy: OwnerSalaryPolicy) -> Result<(), EconomicsError> {
         let mut policy = self.owner_salary_policy.write().await;
@@ -793,12 +2891,417 @@ impl PoEMiningEngine {
         Ok(())
     }
 
-    /// Add economic job to processing queue
+    /// Submit a parameter change for GEN voting until T_vote hours from now;
+    /// the proposer must hold at least Θ_prop GEN
+    pub async fn submit_proposal(&self, proposer: String, change: ParameterChange) -> Result<Uuid, EconomicsError> {
+        let (required_stake, voting_period_hours) = {
+            let params = self.governance_params.read().await;
+            (params.proposal_stake, params.voting_period_hours)
+        };
+        let proposer_stake = self.gen_balance(&proposer).await;
+        if proposer_stake < required_stake {
+            return Err(EconomicsError::GovernanceFailed(format!(
+                "Insufficient stake: {} GEN required, {} GEN held", required_stake, proposer_stake
+            )));
+        }
+        if let ParameterChange::GovernanceParameters(new_params) = &change {
+            new_params.validate()?;
+        }
+
+        let now = self.clock.now();
+        let voting_end = now + chrono::Duration::hours(voting_period_hours as i64);
+        let proposal = Proposal::new(proposer, change, proposer_stake, now, voting_end);
+        let id = proposal.id;
+        info!("📋 Parameter change proposal {} submitted by {} with {} GEN, voting until {}",
+              id, proposal.proposer, proposer_stake, voting_end);
+        self.proposals.write().await.insert(id, proposal);
+        Ok(id)
+    }
+
+    /// Cast a vote on an active proposal before its deadline, weighted by the
+    /// voter's GEN balance; one vote per voter
+    pub async fn vote(&self, proposal_id: Uuid, voter: String, vote_type: VoteType) -> Result<(), EconomicsError> {
+        let stake = self.gen_balance(&voter).await;
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals.get_mut(&proposal_id)
+            .ok_or_else(|| EconomicsError::GovernanceFailed("Proposal not found".to_string()))?;
+        proposal.cast_vote(voter, vote_type, stake, self.clock.now())
+    }
+
+    /// GEN held by `account` in the token ledger
+    async fn gen_balance(&self, account: &str) -> u64 {
+        self.token_balances.read().await
+            .get(account)
+            .and_then(|holdings| holdings.get(&TokenType::Genesis))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Close voting once the deadline has passed; see `Proposal::tally`
+    pub async fn tally(&self, proposal_id: Uuid) -> Result<ProposalStatus, EconomicsError> {
+        self.tally_at(proposal_id, self.clock.now()).await
+    }
+
+    async fn tally_at(&self, proposal_id: Uuid, now: DateTime<Utc>) -> Result<ProposalStatus, EconomicsError> {
+        let params = self.governance_params.read().await.clone();
+        let gen_supply = self.token_supply.read().await.gen_supply;
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals.get_mut(&proposal_id)
+            .ok_or_else(|| EconomicsError::GovernanceFailed("Proposal not found".to_string()))?;
+        proposal.tally(&params, gen_supply, now)
+    }
+
+    /// Apply a passed proposal once T_exec hours have elapsed since it passed
+    pub async fn execute(&self, proposal_id: Uuid) -> Result<(), EconomicsError> {
+        self.execute_at(proposal_id, self.clock.now()).await
+    }
+
+    async fn execute_at(&self, proposal_id: Uuid, now: DateTime<Utc>) -> Result<(), EconomicsError> {
+        let timelock_hours = self.governance_params.read().await.execution_timelock_hours;
+        let mut proposals = self.proposals.write().await;
+        let proposal = proposals.get_mut(&proposal_id)
+            .ok_or_else(|| EconomicsError::GovernanceFailed("Proposal not found".to_string()))?;
+
+        match proposal.executable_change(timelock_hours, now)? {
+            ParameterChange::GovernanceParameters(new_params) => {
+                new_params.validate()?;
+                let mut params = self.governance_params.write().await;
+                *params = new_params.as_ref().clone();
+                params.last_update = now;
+            }
+            ParameterChange::OwnerSalaryPolicy(new_policy) => {
+                let mut policy = self.owner_salary_policy.write().await;
+                *policy = new_policy.clone();
+                policy.last_policy_update = now;
+            }
+        }
+        proposal.status = ProposalStatus::Executed;
+
+        info!("⚡ Proposal {} executed", proposal_id);
+        Ok(())
+    }
+
+    /// Add economic job to processing queue, or hold it in the queue's pending
+    /// batch when a batch window is set
     pub async fn add_economic_job(&self, job: EconomicJob) -> Result<(), EconomicsError> {
+        let window = match self.job_batch_window {
+            Some(window) => window,
+            None => return self.add_economic_jobs(vec![job]).await,
+        };
+
+        let opens_batch = self.job_queue.read().await.hold(job);
+        // The first job of a batch schedules its flush; later ones ride along
+        if opens_batch {
+            let job_queue = self.job_queue.clone();
+            let job_queue_locks = self.job_queue_locks.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(window).await;
+                let mut job_queue = job_queue.write().await;
+                if job_queue.held_len() > 0 {
+                    job_queue_locks.fetch_add(1, AtomicOrdering::Relaxed);
+                    job_queue.flush();
+                }
+            });
+        }
+        Ok(())
+    }
+
+    /// Add several jobs under a single queue lock, in order
+    pub async fn add_economic_jobs(&self, jobs: Vec<EconomicJob>) -> Result<(), EconomicsError> {
+        if jobs.is_empty() {
+            return Ok(());
+        }
         let mut job_queue = self.job_queue.write().await;
-        job_queue.push_back(job);
+        self.job_queue_locks.fetch_add(1, AtomicOrdering::Relaxed);
+        job_queue.extend(jobs);
+        Ok(())
+    }
+
+    /// Take the highest-priority job, oldest first among equal priorities
+    pub async fn dequeue_next_job(&self) -> Option<EconomicJob> {
+        self.job_queue.write().await.pop()
+    }
+
+    /// Queue write locks taken so far to enqueue jobs
+    pub fn job_queue_lock_acquisitions(&self) -> u64 {
+        self.job_queue_locks.load(AtomicOrdering::Relaxed)
+    }
+
+    /// Elastic FLX adjustment from net demand U_net(t). Positive demand mints
+    /// μ·U_net up to what is left of C_FLX this epoch; negative demand burns β_burn of the μ·|U_net| excess,
+    /// never below the genesis FLX supply. Returns the signed supply delta.
+    pub async fn adjust_flx_supply(&self, demand: &NetworkUsageDemand) -> Result<i64, EconomicsError> {
+        let params = self.governance_params.read().await.clone();
+        let mut supply = self.token_supply.write().await;
+
+        let pressure = (params.flx_elasticity * demand.net_demand.abs()).round();
+        let delta: i64 = if demand.net_demand > Decimal::ZERO {
+            let remaining_cap = params.flx_epoch_cap.saturating_sub(supply.epoch_flx_minted);
+            let minted = pressure.min(Decimal::from(remaining_cap)).to_u64().unwrap_or(0);
+            supply.flx_supply = supply.flx_supply.checked_add(minted)
+                .ok_or_else(|| EconomicsError::TokenSupplyError("FLX supply overflow".to_string()))?;
+            supply.epoch_flx_minted += minted;
+            self.metrics.tokens_minted.inc_by(minted as f64);
+            minted as i64
+        } else if demand.net_demand < Decimal::ZERO {
+            let burnable = supply.flx_supply.saturating_sub(FLX_GENESIS_SUPPLY);
+            let burned = (pressure * params.flx_burn_rate).round().to_u64().unwrap_or(0).min(burnable);
+            supply.flx_supply -= burned;
+            -(burned as i64)
+        } else {
+            0
+        };
+
+        if delta != 0 {
+            supply.last_update = self.clock.now();
+            self.metrics.observe_supply(&supply);
+            info!("🌊 FLX supply adjusted by {} (U_net={:.2}), supply now {}", delta, demand.net_demand, supply.flx_supply);
+        }
+        Ok(delta)
+    }
+
+    /// Mint one AUR per whole gram of an attested reserve increment. The proof must
+    /// be signed by a configured custodian, match `gold_grams`, and not have been
+    /// minted against before.
+    pub async fn mint_aur(&self, gold_grams: Decimal, proof: ReserveProof) -> Result<u64, EconomicsError> {
+        if gold_grams <= Decimal::ZERO {
+            return Err(EconomicsError::TokenSupplyError("AUR mint requires a positive gold amount".to_string()));
+        }
+        let custodian_key = self.reserve_custodians.get(&proof.custodian)
+            .ok_or_else(|| EconomicsError::TokenSupplyError(format!("Unknown reserve custodian {}", proof.custodian)))?;
+        if !proof.verify(custodian_key) || proof.gold_grams != gold_grams {
+            return Err(EconomicsError::TokenSupplyError(format!(
+                "Reserve proof does not attest {} grams of gold", gold_grams
+            )));
+        }
+
+        let mut attestations = self.reserve_attestations.write().await;
+        if attestations.contains(&proof.attestation_hash) {
+            return Err(EconomicsError::TokenSupplyError("Reserve proof already used".to_string()));
+        }
+
+        let minted = gold_grams.floor().to_u64()
+            .ok_or_else(|| EconomicsError::TokenSupplyError("AUR mint amount out of range".to_string()))?;
+        let mut supply = self.token_supply.write().await;
+        supply.aur_supply = supply.aur_supply.checked_add(minted)
+            .ok_or_else(|| EconomicsError::TokenSupplyError("AUR supply overflow".to_string()))?;
+        supply.aur_backing_grams += gold_grams;
+        supply.last_update = self.clock.now();
+        attestations.insert(proof.attestation_hash);
+
+        self.metrics.tokens_minted.inc_by(minted as f64);
+        self.metrics.observe_supply(&supply);
+        info!("🥇 Minted {} AUR against {} g attested by {}, backing now {} g",
+              minted, gold_grams, proof.custodian, supply.aur_backing_grams);
+        Ok(minted)
+    }
+
+    /// Burn `amount` AUR and release the matching grams of backing. Rejects
+    /// redemptions beyond the circulating AUR or that would leave it unbacked.
+    pub async fn redeem_aur(&self, amount: u64) -> Result<Decimal, EconomicsError> {
+        let mut supply = self.token_supply.write().await;
+        let released = Decimal::from(amount);
+        if amount > supply.aur_supply || released > supply.aur_backing_grams {
+            return Err(EconomicsError::TokenSupplyError(format!(
+                "Cannot redeem {} AUR: supply {}, backing {} g",
+                amount, supply.aur_supply, supply.aur_backing_grams
+            )));
+        }
+        if Decimal::from(supply.aur_supply - amount) > supply.aur_backing_grams - released {
+            return Err(EconomicsError::TokenSupplyError("AUR redemption would leave supply above backing".to_string()));
+        }
+
+        supply.aur_supply -= amount;
+        supply.aur_backing_grams -= released;
+        supply.last_update = self.clock.now();
+        self.metrics.observe_supply(&supply);
+
+        info!("🥇 Redeemed {} AUR, backing now {} g", amount, supply.aur_backing_grams);
+        Ok(released)
+    }
+
+    /// Credit every genesis bucket to its named account and reset GEN/NEX/FLX
+    /// supply to the allocation. Applies once, and only if subtotals match the caps.
+    pub async fn apply_genesis_allocation(&self, alloc: &GenesisAllocation) -> Result<(), EconomicsError> {
+        alloc.validate()?;
+
+        let mut balances = self.token_balances.write().await;
+        if alloc.buckets().iter().any(|(account, _, _)| balances.contains_key(*account)) {
+            return Err(EconomicsError::TokenSupplyError("Genesis allocation already applied".to_string()));
+        }
+
+        for (account, token, amount) in alloc.buckets() {
+            *balances.entry(account.to_string()).or_default().entry(token).or_insert(0) += amount;
+        }
+
+        let mut supply = self.token_supply.write().await;
+        supply.gen_supply = alloc.subtotal(TokenType::Genesis);
+        supply.nex_supply = alloc.subtotal(TokenType::Nexus);
+        supply.flx_supply = alloc.subtotal(TokenType::Flux);
+        supply.aur_supply = 0;
+        supply.aur_backing_grams = Decimal::ZERO;
+        supply.last_update = self.clock.now();
+        self.metrics.observe_supply(&supply);
+
+        info!("🌱 Genesis allocation applied: {} GEN, {} NEX, {} FLX across {} accounts",
+              supply.gen_supply, supply.nex_supply, supply.flx_supply, alloc.buckets().len());
+        Ok(())
+    }
+
+    /// Record the current supply for its epoch, replacing any earlier snapshot of
+    /// the same epoch and evicting the oldest once the ring is full
+    pub async fn snapshot_supply(&self) -> Result<u64, EconomicsError> {
+        let supply = self.token_supply.read().await.clone();
+        let epoch = supply.epoch;
+        let encoded = serde_cbor::to_vec(&(epoch, &supply))
+            .map_err(|e| EconomicsError::SystemError(format!("Supply snapshot encoding failed: {}", e)))?;
+
+        let mut snapshots = self.supply_snapshots.write().await;
+        snapshots.retain(|(snapshot_epoch, _)| *snapshot_epoch != epoch);
+        snapshots.push_back((epoch, encoded));
+        while snapshots.len() > self.supply_snapshot_depth {
+            snapshots.pop_front();
+        }
+
+        info!("📸 Token supply snapshot taken for epoch {}", epoch);
+        Ok(epoch)
+    }
+
+    /// Restore token supply to the snapshot of `epoch`. Later snapshots belong to
+    /// the discarded history and are dropped.
+    pub async fn rollback_to_epoch(&self, epoch: u64) -> Result<(), EconomicsError> {
+        let mut snapshots = self.supply_snapshots.write().await;
+        let encoded = snapshots.iter()
+            .find(|(snapshot_epoch, _)| *snapshot_epoch == epoch)
+            .map(|(_, encoded)| encoded.clone())
+            .ok_or_else(|| EconomicsError::TokenSupplyError(format!("No supply snapshot for epoch {}", epoch)))?;
+        let (_, restored): (u64, TokenSupplyState) = serde_cbor::from_slice(&encoded)
+            .map_err(|e| EconomicsError::SystemError(format!("Supply snapshot decoding failed: {}", e)))?;
+
+        let mut supply = self.token_supply.write().await;
+        warn!("⏪ Rolling token supply back from epoch {} to {}: GEN {:+}, NEX {:+}, FLX {:+}, AUR {:+}",
+              supply.epoch, epoch,
+              restored.gen_supply as i128 - supply.gen_supply as i128,
+              restored.nex_supply as i128 - supply.nex_supply as i128,
+              restored.flx_supply as i128 - supply.flx_supply as i128,
+              restored.aur_supply as i128 - supply.aur_supply as i128);
+        *supply = restored;
+        self.metrics.observe_supply(&supply);
+        snapshots.retain(|(snapshot_epoch, _)| *snapshot_epoch <= epoch);
+
         Ok(())
     }
+
+    /// Record `index` in the history, replacing any earlier index of the same
+    /// epoch and evicting the oldest once full. It becomes the current Φ(t) only
+    /// if its epoch is not older than the current one's.
+    pub async fn record_poe_index(&self, index: PoEIndex) {
+        let mut history = self.poe_index_history.write().await;
+        self.archive_poe_index(&mut history, index.clone());
+        let mut current = self.current_poe_index.write().await;
+        if current.as_ref().is_none_or(|current| index.epoch >= current.epoch) {
+            *current = Some(index);
+        }
+    }
+
+    fn archive_poe_index(&self, history: &mut VecDeque<PoEIndex>, index: PoEIndex) {
+        history.retain(|recorded| recorded.epoch != index.epoch);
+        history.push_back(index);
+        history.make_contiguous().sort_by_key(|recorded| recorded.epoch);
+        while history.len() > self.poe_history_depth {
+            history.pop_front();
+        }
+    }
+
+    /// The last `last_n` recorded PoE indices, oldest first
+    pub async fn poe_trend(&self, last_n: usize) -> Vec<PoEIndex> {
+        let history = self.poe_index_history.read().await;
+        history.iter().skip(history.len().saturating_sub(last_n)).cloned().collect()
+    }
+
+    /// Least-squares slope of Φ per epoch over the last `last_n` indices;
+    /// zero with fewer than two points
+    pub async fn poe_slope(&self, last_n: usize) -> Decimal {
+        let trend = self.poe_trend(last_n).await;
+        if trend.len() < 2 {
+            return Decimal::ZERO;
+        }
+        let count = Decimal::from(trend.len());
+        let mean_epoch = trend.iter().map(|index| Decimal::from(index.epoch)).sum::<Decimal>() / count;
+        let mean_phi = trend.iter().map(|index| index.phi_value).sum::<Decimal>() / count;
+
+        let (covariance, variance) = trend.iter().fold((Decimal::ZERO, Decimal::ZERO), |(cov, var), index| {
+            let dx = Decimal::from(index.epoch) - mean_epoch;
+            (cov + dx * (index.phi_value - mean_phi), var + dx * dx)
+        });
+        if variance.is_zero() { Decimal::ZERO } else { covariance / variance }
+    }
+
+    /// Close the current epoch and start the next under one supply lock: the
+    /// closing supply and Φ(t) are captured in the summary, Φ(t) is archived in
+    /// the PoE history, and the per-epoch NEX/FLX counters reset so both caps
+    /// are available again
+    pub async fn advance_epoch(&self) -> Result<EpochSummary, EconomicsError> {
+        let mut supply = self.token_supply.write().await;
+        self.close_epoch(&mut supply).await
+    }
+
+    async fn close_epoch(&self, supply: &mut TokenSupplyState) -> Result<EpochSummary, EconomicsError> {
+        let next_epoch = supply.epoch.checked_add(1)
+            .ok_or_else(|| EconomicsError::TokenSupplyError("Epoch counter overflow".to_string()))?;
+        let poe_index = self.current_poe_index.read().await.clone();
+        if let Some(index) = &poe_index {
+            let mut history = self.poe_index_history.write().await;
+            if !history.iter().any(|recorded| recorded.epoch == index.epoch) {
+                self.archive_poe_index(&mut history, index.clone());
+            }
+        }
+
+        let summary = EpochSummary {
+            epoch: supply.epoch,
+            nex_issued: supply.epoch_nex_issued,
+            flx_minted: supply.epoch_flx_minted,
+            supply: supply.clone(),
+            poe_index,
+        };
+        supply.epoch = next_epoch;
+        supply.epoch_nex_issued = 0;
+        supply.epoch_flx_minted = 0;
+        supply.last_update = self.clock.now();
+        self.metrics.observe_supply(supply);
+
+        info!("⏭️ Epoch {} closed: NEX +{}, FLX +{}; epoch {} begins",
+              summary.epoch, summary.nex_issued, summary.flx_minted, next_epoch);
+        Ok(summary)
+    }
+
+    /// Mint this epoch's NEX: Γ(Φ)·C_NEX, nothing when Φ < τ_NEX, never more
+    /// than is left of the cap. Closes the epoch through `advance_epoch`.
+    pub async fn issue_epoch_nex(&self) -> Result<u64, EconomicsError> {
+        let poe_index = self.current_poe_index.read().await.clone()
+            .ok_or_else(|| EconomicsError::TokenSupplyError("No PoE index computed for this epoch".to_string()))?;
+        let params = self.governance_params.read().await.clone();
+
+        let mut supply = self.token_supply.write().await;
+        let issuance = if poe_index.phi_value < params.tau_nex {
+            0
+        } else {
+            let cap = Decimal::from(params.nex_epoch_cap);
+            let remaining_cap = Decimal::from(params.nex_epoch_cap.saturating_sub(supply.epoch_nex_issued));
+            (poe_index.gamma() * cap).round().min(remaining_cap).to_u64().unwrap_or(0)
+        };
+
+        supply.nex_supply = supply.nex_supply.checked_add(issuance)
+            .ok_or_else(|| EconomicsError::TokenSupplyError("NEX supply overflow".to_string()))?;
+        supply.epoch_nex_issued += issuance;
+
+        self.metrics.tokens_minted.inc_by(issuance as f64);
+        info!("🪙 Epoch {} NEX issuance: {} (Φ={:.4}, Γ={:.4}, cap={})",
+              supply.epoch, issuance, poe_index.phi_value, poe_index.gamma(), params.nex_epoch_cap);
+        self.close_epoch(&mut supply).await?;
+
+        Ok(issuance)
+    }
 }
 
 /// Complete Bank Mesh System Integration
@@ -1077,11 +3580,13 @@ mod docklock_tests;
                 gold_equivalent_value: value,
                 proof_hash: format!("proof_{}", job_id),
                 completion_time: Utc::now(),
+                completion_height: 0,
                 cluster_rent_revenue: None,
                 gas_fee_revenue: None,
                 app_interaction_revenue: None,
                 security_layer_revenue: None,
                 data_pipeline_revenue: None,
+                priority: 0,
             };
             
             // Route fees
@@ -1162,12 +3667,14 @@ mod docklock_tests;
             gold_equivalent_value: Decimal::new(50_000, 0), // $50k base value
             proof_hash: "docklock_proof_001".to_string(),
             completion_time: Utc::now(),
+            completion_height: 0,
             // DockLock revenue streams
             cluster_rent_revenue: Some(Decimal::new(25_000, 0)),    // $25k cluster rent
             gas_fee_revenue: Some(Decimal::new(15_000, 0)),         // $15k gas fees
             app_interaction_revenue: Some(Decimal::new(10_000, 0)), // $10k app interactions
             security_layer_revenue: Some(Decimal::new(8_000, 0)),   // $8k security services
             data_pipeline_revenue: Some(Decimal::new(12_000, 0)),   // $12k data processing
+            priority: 0,
         };
         
         // Calculate DockLock revenue
@@ -1202,7 +3709,7 @@ mod docklock_tests;
         
         // Test salary above cap
         let high_salary = Decimal::new(2000, 0); // $2000 > $1000 cap
-        let result = engine.pay_owner_salary_with_guardrails(high_salary, &policy).await;
+        let result = engine.pay_owner_salary_with_guardrails(high_salary, &policy, "salary_over_cap").await;
         assert!(result.is_ok());
         
         // Test vesting split (50% immediate, 50% vested)
@@ -1215,7 +3722,7 @@ mod docklock_tests;
         
         // Test escrow flag
         policy.escrow_on_compliance_flag = true;
-        let escrow_result = engine.pay_owner_salary_with_guardrails(test_salary, &policy).await;
+        let escrow_result = engine.pay_owner_salary_with_guardrails(test_salary, &policy, "salary_flagged").await;
         assert!(escrow_result.is_ok());
         
         println!("✅ Owner salary governance guardrails test passed");
@@ -1264,11 +3771,13 @@ mod docklock_tests;
             gold_equivalent_value: Decimal::new(50_000, 0), // $50k job
             proof_hash: "proof_test_001".to_string(),
             completion_time: Utc::now(),
+            completion_height: 0,
             cluster_rent_revenue: None,
             gas_fee_revenue: None,
             app_interaction_revenue: None,
             security_layer_revenue: None,
             data_pipeline_revenue: None,
+            priority: 0,
         };
         
         let job_value = job.gold_equivalent_value;
@@ -1350,11 +3859,13 @@ mod docklock_tests;
             gold_equivalent_value: Decimal::new(5000, 0),
             proof_hash: "proof_creation".to_string(),
             completion_time: Utc::now(),
+            completion_height: 0,
                 cluster_rent_revenue: None,
                 gas_fee_revenue: None,
                 app_interaction_revenue: None,
                 security_layer_revenue: None,
                 data_pipeline_revenue: None,
+                priority: 0,
         };
         
         // Add job to queue
@@ -1418,11 +3929,13 @@ mod docklock_tests;
             gold_equivalent_value: Decimal::new(1000, 0),
             proof_hash: "test_verification".to_string(),
             completion_time: Utc::now(),
+            completion_height: 0,
                 cluster_rent_revenue: None,
                 gas_fee_revenue: None,
                 app_interaction_revenue: None,
                 security_layer_revenue: None,
                 data_pipeline_revenue: None,
+                priority: 0,
         };
         
         let add_result = engine.add_economic_job(job.clone()).await;
@@ -78,6 +78,41 @@ pub struct LiquidityPool {
     pub impermanent_loss_protection: bool,
 }
 
+impl LiquidityPool {
+    /// Fraction of value lost by providing liquidity instead of holding, when
+    /// the price of token A in token B moves from `entry_price` to
+    /// `current_price`. Uses the constant-product valuation, weighted by
+    /// `weight_a` for weighted pools; 0.057 means a 5.7% loss.
+    pub fn impermanent_loss_ratio(&self, entry_price: Decimal, current_price: Decimal) -> Decimal {
+        if entry_price <= Decimal::ZERO || current_price <= Decimal::ZERO {
+            return Decimal::ZERO;
+        }
+        let weight_a = match self.pool_type {
+            PoolType::Weighted => self.weight_a.to_f64().unwrap_or(0.5),
+            _ => 0.5,
+        };
+        let r = (current_price / entry_price).to_f64().unwrap_or(1.0);
+        // Pool value relative to holding: r^w / (w*r + (1 - w))
+        let relative_value = r.powf(weight_a) / (weight_a * r + (1.0 - weight_a));
+        Decimal::from_f64(1.0 - relative_value).unwrap_or(Decimal::ZERO)
+    }
+
+    /// Impermanent loss of `provider`'s deposit, in token B, at `current_price`
+    pub fn impermanent_loss(&self, provider: &LiquidityPosition, entry_price: Decimal, current_price: Decimal) -> Decimal {
+        let held_value = provider.initial_deposit_a * current_price + provider.initial_deposit_b;
+        held_value * self.impermanent_loss_ratio(entry_price, current_price)
+    }
+
+    /// Share of the pool owned by `provider`
+    pub fn share_of(&self, provider: &LiquidityPosition) -> Decimal {
+        if self.total_liquidity.is_zero() {
+            Decimal::ZERO
+        } else {
+            provider.liquidity_tokens / self.total_liquidity
+        }
+    }
+}
+
 /// Liquidity provider position
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LiquidityPosition {
@@ -95,6 +130,22 @@ pub struct LiquidityPosition {
     pub last_update: DateTime<Utc>,
 }
 
+/// Value of a liquidity position against simply holding its deposit, in token B
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PositionSummary {
+    pub position_id: Uuid,
+    pub provider: String,
+    /// Deposit valued at the entry price
+    pub deposited_value: Decimal,
+    /// Position value in the pool at the current price
+    pub current_value: Decimal,
+    /// Pro-rata share of the pool's collected fees
+    pub fees_earned: Decimal,
+    pub impermanent_loss: Decimal,
+    /// Impermanent loss after fees; negative when fees more than cover it
+    pub net_impermanent_loss: Decimal,
+}
+
 /// Yield farming pool
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct YieldFarm {
@@ -618,6 +669,54 @@ impl LiquidityManager {
         self.positions.read().await.get(&position_id).cloned()
     }
 
+    /// Summarize a position at `current_price` (token A in token B); the entry
+    /// price is the ratio the position deposited at
+    pub async fn position_summary(
+        &self,
+        position_id: Uuid,
+        current_price: Decimal,
+    ) -> Result<PositionSummary, LiquidityError> {
+        let position = self.positions.read().await.get(&position_id).cloned()
+            .ok_or(LiquidityError::PoolNotFound(position_id))?;
+        let pools = self.pools.read().await;
+        let pool = pools.get(&position.pool_id)
+            .ok_or(LiquidityError::PoolNotFound(position.pool_id))?;
+        Ok(Self::summarize(pool, &position, current_price))
+    }
+
+    /// Summaries of every position held by `provider`
+    pub async fn provider_positions(&self, provider: &str, current_price: Decimal) -> Vec<PositionSummary> {
+        let positions = self.positions.read().await;
+        let pools = self.pools.read().await;
+        positions.values()
+            .filter(|position| position.provider == provider)
+            .filter_map(|position| {
+                pools.get(&position.pool_id).map(|pool| Self::summarize(pool, position, current_price))
+            })
+            .collect()
+    }
+
+    fn summarize(pool: &LiquidityPool, position: &LiquidityPosition, current_price: Decimal) -> PositionSummary {
+        let entry_price = if position.initial_deposit_a.is_zero() {
+            current_price
+        } else {
+            position.initial_deposit_b / position.initial_deposit_a
+        };
+        let held_value = position.initial_deposit_a * current_price + position.initial_deposit_b;
+        let impermanent_loss = pool.impermanent_loss(position, entry_price, current_price);
+        let fees_earned = position.fees_earned + pool.share_of(position) * pool.fees_collected;
+
+        PositionSummary {
+            position_id: position.id,
+            provider: position.provider.clone(),
+            deposited_value: position.initial_deposit_a * entry_price + position.initial_deposit_b,
+            current_value: held_value - impermanent_loss + fees_earned,
+            fees_earned,
+            impermanent_loss,
+            net_impermanent_loss: impermanent_loss - fees_earned,
+        }
+    }
+
     /// Get yield farm by ID
     pub async fn get_yield_farm(&self, farm_id: Uuid) -> Option<YieldFarm> {
         self.yield_farms.read().await.get(&farm_id).cloned()
@@ -711,6 +810,80 @@ mod tests {
         assert!(result.output_amount > Decimal::ZERO);
     }
 
+    async fn pool_with_provider(manager: &LiquidityManager) -> (Uuid, Uuid) {
+        let pool_id = manager.create_pool(
+            PoolType::ConstantProduct,
+            TokenType::Genesis,
+            TokenType::Nexus,
+            Decimal::from(10000),
+            Decimal::from(20000),
+            None,
+            None,
+        ).await.unwrap();
+        let position_id = manager.add_liquidity(
+            pool_id,
+            "provider1".to_string(),
+            Decimal::from(1000),
+            Decimal::from(2000),
+        ).await.unwrap();
+        (pool_id, position_id)
+    }
+
+    #[tokio::test]
+    async fn test_no_impermanent_loss_without_price_change() {
+        let manager = LiquidityManager::new(LiquidityConfig::default());
+        let (_, position_id) = pool_with_provider(&manager).await;
+
+        let summary = manager.position_summary(position_id, Decimal::from(2)).await.unwrap();
+        assert_eq!(summary.impermanent_loss, Decimal::ZERO);
+        assert_eq!(summary.deposited_value, Decimal::from(4000));
+        assert_eq!(summary.current_value, Decimal::from(4000));
+    }
+
+    #[tokio::test]
+    async fn test_impermanent_loss_on_price_doubling() {
+        let manager = LiquidityManager::new(LiquidityConfig::default());
+        let (pool_id, position_id) = pool_with_provider(&manager).await;
+        let pool = manager.get_pool(pool_id).await.unwrap();
+        let position = manager.get_position(position_id).await.unwrap();
+
+        // 1 - 2*sqrt(2)/3 ≈ 5.72%
+        let ratio = pool.impermanent_loss_ratio(Decimal::from(2), Decimal::from(4));
+        assert!((ratio - Decimal::from_str_exact("0.0572").unwrap()).abs() < Decimal::from_str_exact("0.0001").unwrap());
+
+        // Holding would be worth 1000 * 4 + 2000 = 6000
+        let loss = pool.impermanent_loss(&position, Decimal::from(2), Decimal::from(4));
+        assert!((loss - Decimal::from(6000) * ratio).abs() < Decimal::from_str_exact("0.000001").unwrap());
+
+        let summary = manager.position_summary(position_id, Decimal::from(4)).await.unwrap();
+        assert_eq!(summary.impermanent_loss, loss);
+        assert_eq!(summary.net_impermanent_loss, loss);
+    }
+
+    #[tokio::test]
+    async fn test_fees_offset_impermanent_loss() {
+        let manager = LiquidityManager::new(LiquidityConfig::default());
+        let (pool_id, position_id) = pool_with_provider(&manager).await;
+        let share = {
+            let pools = manager.pools.read().await;
+            let position = manager.get_position(position_id).await.unwrap();
+            pools[&pool_id].share_of(&position)
+        };
+
+        // Enough fees that the provider's share exceeds its ~343 token loss
+        manager.pools.write().await.get_mut(&pool_id).unwrap().fees_collected = Decimal::from(400) / share;
+
+        let summary = manager.position_summary(position_id, Decimal::from(4)).await.unwrap();
+        assert!(summary.impermanent_loss > Decimal::ZERO);
+        assert!((summary.fees_earned - Decimal::from(400)).abs() < Decimal::from_str_exact("0.0001").unwrap());
+        assert!(summary.net_impermanent_loss < Decimal::ZERO);
+        assert!(summary.current_value > Decimal::from(6000));
+
+        let summaries = manager.provider_positions("provider1", Decimal::from(4)).await;
+        assert_eq!(summaries.len(), 1);
+        assert_eq!(summaries[0].position_id, position_id);
+    }
+
     #[tokio::test]
     async fn test_yield_farm_creation() {
         let config = LiquidityConfig::default();
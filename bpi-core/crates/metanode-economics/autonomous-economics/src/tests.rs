@@ -19,11 +19,13 @@ fn create_test_job(
         gold_equivalent_value: gold_value,
         proof_hash: format!("proof_{}", job_id),
         completion_time: Utc::now(),
+        completion_height: 0,
         cluster_rent_revenue: if cluster_rent > Decimal::ZERO { Some(cluster_rent) } else { None },
         gas_fee_revenue: if gas_fees > Decimal::ZERO { Some(gas_fees) } else { None },
         app_interaction_revenue: if app_interactions > Decimal::ZERO { Some(app_interactions) } else { None },
         security_layer_revenue: if security_fees > Decimal::ZERO { Some(security_fees) } else { None },
         data_pipeline_revenue: if pipeline_fees > Decimal::ZERO { Some(pipeline_fees) } else { None },
+        priority: 0,
     }
 }
 
@@ -99,6 +101,46 @@ async fn test_docklock_revenue_calculation() {
              total_owner_salary, base_fee_split.owner_salary, docklock_owner_share);
 }
 
+#[tokio::test]
+async fn test_positive_docklock_revenue_routes_to_treasury() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let job = create_test_job(
+        "docklock_valid",
+        EconomicJobType::DockLockHosting,
+        "miner_001",
+        Decimal::new(50_000, 0),
+        Some((Decimal::new(5_000, 0), Decimal::new(3_000, 0), Decimal::new(2_000, 0), Decimal::new(1_500, 0), Decimal::new(1_500, 0))),
+    );
+    assert!(job.validate_docklock_revenue().is_ok());
+
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Fee routing failed");
+
+    // $150 base treasury net + 0.3% of $13k DockLock revenue
+    let snapshot = engine.economic_snapshot().await;
+    assert_eq!(snapshot.treasury_balance, Decimal::new(189, 0));
+    assert_eq!(snapshot.locked_coins, Decimal::new(100, 0));
+}
+
+#[tokio::test]
+async fn test_negative_docklock_revenue_rejected_before_routing() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let mut job = create_test_job("docklock_negative", EconomicJobType::DockLockHosting, "miner_001", Decimal::new(50_000, 0), None);
+    job.cluster_rent_revenue = Some(Decimal::new(5_000, 0));
+    job.gas_fee_revenue = Some(Decimal::new(-20_000, 0));
+
+    assert!(matches!(engine.calculate_docklock_revenue(&job).await, Err(EconomicsError::InvalidAmount(_))));
+    assert!(matches!(engine.route_fees(&job, job.gold_equivalent_value).await, Err(EconomicsError::InvalidAmount(_))));
+    assert!(matches!(engine.route_fees_batch(std::slice::from_ref(&job)).await, Err(EconomicsError::InvalidAmount(_))));
+
+    // No leg was applied
+    let snapshot = engine.economic_snapshot().await;
+    assert_eq!(snapshot.treasury_balance, Decimal::ZERO);
+    assert_eq!(snapshot.locked_coins, Decimal::ZERO);
+    assert!(engine.economic_state.read().await.payments_by_key.is_empty());
+}
+
 #[tokio::test]
 async fn test_complete_fee_routing_with_docklock() {
     let registry = Registry::new();
@@ -200,3 +242,1545 @@ async fn test_stage51_exit_criteria_with_docklock() {
              policy.vesting_immediate_rate * Decimal::new(100, 0),
              policy.vesting_deferred_rate * Decimal::new(100, 0));
 }
+
+fn baseline_epoch_metrics() -> EpochMetrics {
+    EpochMetrics {
+        epoch: 1,
+        total_volume: Decimal::new(500_000, 0),
+        liquidity_delta: Decimal::new(20_000, 0),
+        average_uptime: Decimal::new(9, 1),
+        quality_score: Decimal::new(8, 1),
+    }
+}
+
+#[test]
+fn test_poe_index_compute_monotonic_in_each_input() {
+    let weights = PoEWeights::default();
+    let base = baseline_epoch_metrics();
+    let base_phi = PoEIndex::compute(&base, &weights).phi_value;
+
+    // 0.4·0.5 + 0.3·0.2 + 0.2·0.9 + 0.1·0.8 = 0.52
+    assert_eq!(base_phi, Decimal::new(52, 2));
+
+    let bumps: [fn(&mut EpochMetrics); 4] = [
+        |m| m.total_volume += Decimal::new(100_000, 0),
+        |m| m.liquidity_delta += Decimal::new(10_000, 0),
+        |m| m.average_uptime += Decimal::new(5, 2),
+        |m| m.quality_score += Decimal::new(5, 2),
+    ];
+    for bump in bumps {
+        let mut metrics = base.clone();
+        bump(&mut metrics);
+        assert!(PoEIndex::compute(&metrics, &weights).phi_value > base_phi);
+    }
+}
+
+#[test]
+fn test_poe_index_gamma_bounded_and_zero_scale_guarded() {
+    let weights = PoEWeights {
+        volume_scale: Decimal::ZERO,
+        liquidity_scale: Decimal::ZERO,
+        ..PoEWeights::default()
+    };
+
+    let index = PoEIndex::compute(&baseline_epoch_metrics(), &weights);
+    assert_eq!(index.volume_component, Decimal::ZERO);
+    assert_eq!(index.liquidity_component, Decimal::ZERO);
+
+    let huge = EpochMetrics {
+        total_volume: Decimal::new(1_000_000_000_000, 0),
+        ..baseline_epoch_metrics()
+    };
+    let negative = EpochMetrics {
+        liquidity_delta: Decimal::new(-10_000_000, 0),
+        ..baseline_epoch_metrics()
+    };
+    for metrics in [baseline_epoch_metrics(), huge, negative] {
+        let gamma = PoEIndex::compute(&metrics, &PoEWeights::default()).gamma();
+        assert!(gamma >= Decimal::ZERO && gamma < Decimal::ONE);
+    }
+}
+
+fn poe_index_with_phi(phi: Decimal) -> PoEIndex {
+    PoEIndex {
+        phi_value: phi,
+        volume_component: phi,
+        liquidity_component: Decimal::ZERO,
+        uptime_component: Decimal::ZERO,
+        quality_component: Decimal::ZERO,
+        epoch: 0,
+        calculation_time: Utc::now(),
+    }
+}
+
+fn poe_index_at(epoch: u64, phi: Decimal) -> PoEIndex {
+    PoEIndex { epoch, ..poe_index_with_phi(phi) }
+}
+
+#[tokio::test]
+async fn test_poe_history_keeps_bounded_epoch_order() {
+    let engine = PoEMiningEngine::new(&Registry::new())
+        .expect("Failed to create engine")
+        .with_poe_history_depth(4);
+    for epoch in [1, 2, 3, 5, 4, 6] {
+        engine.record_poe_index(poe_index_at(epoch, Decimal::from(epoch))).await;
+    }
+    // Re-recording an epoch replaces it rather than adding a second entry
+    engine.record_poe_index(poe_index_at(6, Decimal::new(65, 1))).await;
+
+    let epochs: Vec<u64> = engine.poe_trend(10).await.iter().map(|index| index.epoch).collect();
+    assert_eq!(epochs, vec![3, 4, 5, 6]);
+    let last_two: Vec<u64> = engine.poe_trend(2).await.iter().map(|index| index.epoch).collect();
+    assert_eq!(last_two, vec![5, 6]);
+    assert_eq!(engine.current_poe_index.read().await.as_ref().unwrap().phi_value, Decimal::new(65, 1));
+    assert!(engine.poe_trend(0).await.is_empty());
+}
+
+#[tokio::test]
+async fn test_late_poe_index_does_not_rewind_current() {
+    let engine = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    engine.record_poe_index(poe_index_at(5, Decimal::from(5))).await;
+    engine.record_poe_index(poe_index_at(3, Decimal::from(3))).await;
+
+    let current = engine.current_poe_index.read().await.clone().unwrap();
+    assert_eq!((current.epoch, current.phi_value), (5, Decimal::from(5)));
+    // The late index still lands in the history in epoch order
+    let epochs: Vec<u64> = engine.poe_trend(10).await.iter().map(|index| index.epoch).collect();
+    assert_eq!(epochs, vec![3, 5]);
+}
+
+#[tokio::test]
+async fn test_poe_slope_tracks_rising_and_falling_phi() {
+    let rising = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    for (epoch, phi) in [(1, 10), (2, 12), (3, 14), (4, 16)] {
+        rising.record_poe_index(poe_index_at(epoch, Decimal::new(phi, 1))).await;
+    }
+    assert_eq!(rising.poe_slope(4).await, Decimal::new(2, 1));
+
+    let falling = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    for (epoch, phi) in [(1, 30), (2, 20), (3, 20), (4, 10)] {
+        falling.record_poe_index(poe_index_at(epoch, Decimal::from(phi))).await;
+    }
+    assert_eq!(falling.poe_slope(4).await, Decimal::from(-6));
+    // Only the recent window counts: epochs 3..4 fall by 10
+    assert_eq!(falling.poe_slope(2).await, Decimal::from(-10));
+    assert_eq!(falling.poe_slope(1).await, Decimal::ZERO);
+}
+
+async fn issue_nex_at_phi(engine: &PoEMiningEngine, phi: Decimal) -> u64 {
+    *engine.current_poe_index.write().await = Some(poe_index_with_phi(phi));
+    engine.issue_epoch_nex().await.expect("NEX issuance failed")
+}
+
+#[tokio::test]
+async fn test_nex_issuance_below_threshold_is_zero() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.governance_params.write().await.tau_nex = Decimal::ONE;
+
+    let issued = issue_nex_at_phi(&engine, Decimal::new(5, 1)).await;
+    assert_eq!(issued, 0);
+
+    let supply = engine.token_supply.read().await;
+    assert_eq!(supply.nex_supply, 300_000);
+    assert_eq!(supply.epoch, 1);
+}
+
+#[tokio::test]
+async fn test_nex_issuance_gated_by_gamma() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.governance_params.write().await.tau_nex = Decimal::ONE;
+
+    // Φ = 3 → Γ = 0.75 → 750 of the 1,000 cap
+    let issued = issue_nex_at_phi(&engine, Decimal::new(3, 0)).await;
+    assert_eq!(issued, 750);
+    assert_eq!(engine.token_supply.read().await.nex_supply, 300_750);
+    assert_eq!(engine.metrics.tokens_minted.get(), 750.0);
+}
+
+#[tokio::test]
+async fn test_nex_supply_gauge_tracks_issuance() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.governance_params.write().await.tau_nex = Decimal::ONE;
+    assert_eq!(engine.metrics.nex_supply.get(), 300_000.0);
+
+    issue_nex_at_phi(&engine, Decimal::new(3, 0)).await;
+    let nex_supply = engine.token_supply.read().await.nex_supply;
+    assert_eq!(engine.metrics.nex_supply.get(), nex_supply as f64);
+
+    // Exported through the engine's registry
+    let exported = registry.gather().into_iter()
+        .find(|family| family.get_name() == "poe_supply_nex")
+        .expect("NEX supply gauge registered");
+    assert_eq!(exported.get_metric()[0].get_gauge().get_value(), nex_supply as f64);
+}
+
+#[tokio::test]
+async fn test_nex_issuance_saturates_at_cap() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.governance_params.write().await.tau_nex = Decimal::ONE;
+
+    let issued = issue_nex_at_phi(&engine, Decimal::new(1_000_000_000, 0)).await;
+    assert_eq!(issued, 1_000);
+
+    // Missing index is an error rather than silent zero issuance
+    *engine.current_poe_index.write().await = None;
+    assert!(engine.issue_epoch_nex().await.is_err());
+}
+
+fn net_demand(u_net: i64) -> NetworkUsageDemand {
+    NetworkUsageDemand {
+        pending_gas_buffer: Decimal::ZERO,
+        tx_fee_moving_average: Decimal::ZERO,
+        queue_length_factor: Decimal::ZERO,
+        net_demand: Decimal::new(u_net, 0),
+        timestamp: Utc::now(),
+    }
+}
+
+#[tokio::test]
+async fn test_flx_demand_spike_mint_is_capped() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+
+    // μ·U_net = 0.05 · 1,000,000 = 50,000, capped at C_FLX = 5,000
+    let delta = engine.adjust_flx_supply(&net_demand(1_000_000)).await.expect("FLX adjustment failed");
+    assert_eq!(delta, 5_000);
+    assert_eq!(engine.token_supply.read().await.flx_supply, FLX_GENESIS_SUPPLY + 5_000);
+}
+
+#[tokio::test]
+async fn test_flx_steady_state_no_change() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+
+    let delta = engine.adjust_flx_supply(&net_demand(0)).await.expect("FLX adjustment failed");
+    assert_eq!(delta, 0);
+    assert_eq!(engine.token_supply.read().await.flx_supply, FLX_GENESIS_SUPPLY);
+}
+
+#[tokio::test]
+async fn test_flx_demand_collapse_burns_to_floor() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.adjust_flx_supply(&net_demand(1_000_000)).await.expect("FLX adjustment failed");
+
+    // Excess μ·|U_net| = 0.05 · 40,000 = 2,000; β_burn = 0.5 burns 1,000
+    let delta = engine.adjust_flx_supply(&net_demand(-40_000)).await.expect("FLX adjustment failed");
+    assert_eq!(delta, -1_000);
+    assert_eq!(engine.token_supply.read().await.flx_supply, FLX_GENESIS_SUPPLY + 4_000);
+
+    // A deep collapse cannot burn below the genesis floor
+    let delta = engine.adjust_flx_supply(&net_demand(-10_000_000)).await.expect("FLX adjustment failed");
+    assert_eq!(delta, -4_000);
+    assert_eq!(engine.token_supply.read().await.flx_supply, FLX_GENESIS_SUPPLY);
+}
+
+#[tokio::test]
+async fn test_advance_epoch_resets_issuance_caps() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.record_poe_index(poe_index_at(0, Decimal::from(2))).await;
+
+    // Split across two adjustments, the epoch still mints only C_FLX = 5,000
+    assert_eq!(engine.adjust_flx_supply(&net_demand(60_000)).await.expect("FLX adjustment failed"), 3_000);
+    assert_eq!(engine.adjust_flx_supply(&net_demand(60_000)).await.expect("FLX adjustment failed"), 2_000);
+    assert_eq!(engine.adjust_flx_supply(&net_demand(60_000)).await.expect("FLX adjustment failed"), 0);
+
+    let summary = engine.advance_epoch().await.expect("Epoch advance failed");
+    assert_eq!(summary.epoch, 0);
+    assert_eq!(summary.flx_minted, 5_000);
+    assert_eq!(summary.supply.flx_supply, FLX_GENESIS_SUPPLY + 5_000);
+    assert_eq!(summary.poe_index.map(|index| index.epoch), Some(0));
+    assert_eq!(engine.poe_trend(1).await[0].epoch, 0);
+
+    let supply = engine.token_supply.read().await.clone();
+    assert_eq!(supply.epoch, 1);
+    assert_eq!(supply.epoch_flx_minted, 0);
+
+    // The new epoch has the full cap again
+    let delta = engine.adjust_flx_supply(&net_demand(1_000_000)).await.expect("FLX adjustment failed");
+    assert_eq!(delta, 5_000);
+    assert_eq!(engine.token_supply.read().await.flx_supply, FLX_GENESIS_SUPPLY + 10_000);
+}
+
+#[tokio::test]
+async fn test_fee_rates_balanced_config_validates() {
+    let params = GovernanceParameters::default();
+    assert!(params.validate_fee_rates().is_ok());
+
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    assert!(engine.calculate_poe_fee_split(Decimal::new(10_000, 0)).await.is_ok());
+}
+
+#[tokio::test]
+async fn test_fee_rates_unbalanced_config_rejected() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+
+    // Treasury bumped to 0.5% without reducing anything else: components sum to 1.2%
+    engine.governance_params.write().await.treasury_net_rate = Decimal::new(5, 3);
+    assert!(engine.governance_params.read().await.validate_fee_rates().is_err());
+
+    let result = engine.calculate_poe_fee_split(Decimal::new(10_000, 0)).await;
+    assert!(matches!(result, Err(EconomicsError::GovernanceError(_))));
+}
+
+#[test]
+fn test_governance_parameters_default_set_is_valid() {
+    assert!(GovernanceParameters::default().validate().is_ok());
+
+    // Boundary values are allowed
+    let params = GovernanceParameters {
+        quorum_rate: Decimal::ONE,
+        passage_threshold: Decimal::ONE,
+        flx_burn_rate: Decimal::ZERO,
+        nex_epoch_cap: 1,
+        ..GovernanceParameters::default()
+    };
+    assert!(params.validate().is_ok());
+}
+
+#[test]
+fn test_governance_parameters_out_of_bounds_rejected() {
+    let defaults = GovernanceParameters::default;
+    let cases = [
+        (GovernanceParameters { quorum_rate: Decimal::new(15, 1), ..defaults() }, "quorum_rate must be above 0 and at most 1, got 1.5"),
+        (GovernanceParameters { quorum_rate: Decimal::ZERO, ..defaults() }, "quorum_rate must be above 0 and at most 1, got 0"),
+        (GovernanceParameters { passage_threshold: Decimal::new(-1, 1), ..defaults() }, "passage_threshold must be above 0 and at most 1, got -0.1"),
+        (GovernanceParameters { flx_burn_rate: Decimal::new(2, 0), ..defaults() }, "flx_burn_rate must be between 0 and 1, got 2"),
+        (GovernanceParameters { owner_salary_rate: Decimal::new(-2, 3), ..defaults() }, "owner_salary_rate must be between 0 and 1, got -0.002"),
+        (GovernanceParameters { nex_epoch_cap: 0, ..defaults() }, "nex_epoch_cap must be positive"),
+        (GovernanceParameters { flx_epoch_cap: 0, ..defaults() }, "flx_epoch_cap must be positive"),
+        (GovernanceParameters { flx_elasticity: Decimal::new(-5, 2), ..defaults() }, "flx_elasticity must not be negative, got -0.05"),
+        (GovernanceParameters { treasury_net_rate: Decimal::new(5, 3), ..defaults() }, "Fee component rates sum to 0.012 but job fee rate is 0.01"),
+    ];
+    for (params, expected) in cases {
+        match params.validate() {
+            Err(EconomicsError::GovernanceError(message)) => assert_eq!(message, expected),
+            other => panic!("expected \"{}\", got {:?}", expected, other),
+        }
+    }
+}
+
+#[tokio::test]
+async fn test_invalid_governance_parameters_proposal_refused() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    fund_gen(&engine, "proposer", 1_000).await;
+    let unsafe_params = GovernanceParameters { quorum_rate: Decimal::new(2, 0), ..GovernanceParameters::default() };
+
+    let result = engine.submit_proposal("proposer".to_string(), ParameterChange::GovernanceParameters(Box::new(unsafe_params))).await;
+    assert!(matches!(result, Err(EconomicsError::GovernanceError(ref message)) if message.starts_with("quorum_rate")));
+    assert!(engine.proposals.read().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_owner_salary_monthly_cap_is_cumulative() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let policy = OwnerSalaryPolicy {
+        monthly_hard_cap: Decimal::new(1_000, 0),
+        ..OwnerSalaryPolicy::default()
+    };
+    let march = DateTime::parse_from_rfc3339("2025-03-05T12:00:00Z").unwrap().with_timezone(&Utc);
+    let april = DateTime::parse_from_rfc3339("2025-04-01T00:00:00Z").unwrap().with_timezone(&Utc);
+
+    // Three $400 payments in March: the third only has $200 of allowance left
+    for day in 0..3 {
+        engine.pay_owner_salary_at(Decimal::new(400, 0), &policy, march + chrono::Duration::days(day), &format!("salary_march_{}", day))
+            .await
+            .expect("Salary payment failed");
+    }
+
+    let reports = engine.get_owner_salary_reports().await;
+    assert_eq!(reports.len(), 3);
+    assert_eq!(reports[2].month, "2025-03");
+    assert_eq!(reports[2].capped_salary_amount, Decimal::new(200, 0));
+    assert_eq!(reports[2].escrow_amount, Decimal::new(200, 0));
+    assert_eq!(reports[2].month_to_date_paid, Decimal::new(1_000, 0));
+
+    let capped_total: Decimal = reports.iter().map(|r| r.capped_salary_amount).sum();
+    assert_eq!(capped_total, policy.monthly_hard_cap);
+
+    // April starts a fresh allowance
+    engine.pay_owner_salary_at(Decimal::new(400, 0), &policy, april, "salary_april").await.expect("Salary payment failed");
+    let reports = engine.get_owner_salary_reports().await;
+    let latest = reports.last().unwrap();
+    assert_eq!(latest.month, "2025-04");
+    assert_eq!(latest.capped_salary_amount, Decimal::new(400, 0));
+    assert_eq!(latest.escrow_amount, Decimal::ZERO);
+    assert_eq!(latest.month_to_date_paid, Decimal::new(400, 0));
+}
+
+#[tokio::test]
+async fn test_vesting_installments_released_over_months() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+
+    let start = DateTime::parse_from_rfc3339("2025-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+    engine.schedule_vested_payment_at(Decimal::new(600, 0), 6, start, "owner_vesting").await.expect("Vesting schedule failed");
+
+    // Nothing is due before the first month elapses
+    let payouts = engine.process_vesting(start + chrono::Duration::days(29)).await.expect("Vesting failed");
+    assert!(payouts.is_empty());
+
+    // Three installments due by day 95 (days 30, 60, 90)
+    let payouts = engine.process_vesting(start + chrono::Duration::days(95)).await.expect("Vesting failed");
+    assert_eq!(payouts.len(), 3);
+    assert!(payouts.iter().all(|p| p.amount == Decimal::new(100, 0)));
+    assert_eq!(payouts[2].remaining_amount, Decimal::new(300, 0));
+
+    // Re-processing at the same time releases nothing twice
+    assert!(engine.process_vesting(start + chrono::Duration::days(95)).await.expect("Vesting failed").is_empty());
+
+    // Well past the end: the remaining three installments, then the schedule completes
+    let payouts = engine.process_vesting(start + chrono::Duration::days(400)).await.expect("Vesting failed");
+    assert_eq!(payouts.len(), 3);
+    let released: Decimal = payouts.iter().map(|p| p.amount).sum();
+    assert_eq!(released, Decimal::new(300, 0));
+    assert_eq!(payouts.last().unwrap().remaining_amount, Decimal::ZERO);
+    assert!(engine.process_vesting(start + chrono::Duration::days(800)).await.expect("Vesting failed").is_empty());
+}
+
+#[tokio::test]
+async fn test_mock_clock_steps_across_vesting_month() {
+    let start = DateTime::parse_from_rfc3339("2025-01-15T00:00:00Z").unwrap().with_timezone(&Utc);
+    let clock = MockClock::new(start);
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry)
+        .expect("Failed to create engine")
+        .with_clock(Arc::new(clock.clone()));
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+
+    engine.schedule_vested_payment(Decimal::new(600, 0), 6, "owner_vesting").await.expect("Vesting schedule failed");
+
+    // One second short of the first installment
+    clock.advance(chrono::Duration::days(30) - chrono::Duration::seconds(1));
+    assert!(engine.process_due_vesting().await.expect("Vesting failed").is_empty());
+
+    clock.advance(chrono::Duration::seconds(1));
+    let payouts = engine.process_due_vesting().await.expect("Vesting failed");
+    assert_eq!(payouts.len(), 1);
+    assert_eq!(payouts[0].amount, Decimal::new(100, 0));
+    assert_eq!(payouts[0].due_date, start + chrono::Duration::days(30));
+    assert_eq!(payouts[0].remaining_amount, Decimal::new(500, 0));
+}
+
+#[tokio::test]
+async fn test_compliance_flag_escrows_then_releases_salary() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    let now = DateTime::parse_from_rfc3339("2025-05-10T00:00:00Z").unwrap().with_timezone(&Utc);
+
+    let policy = engine.get_owner_salary_policy().await;
+    engine.pay_owner_salary_at(Decimal::new(100, 0), &policy, now, "salary_before_flag").await.expect("Salary payment failed");
+
+    // Flag mid-stream: the next payment is escrowed in full
+    engine.set_compliance_flag(true, "external audit".to_string()).await.expect("Flag failed");
+    assert_eq!(engine.metrics.compliance_flagged.get(), 1.0);
+    let policy = engine.get_owner_salary_policy().await;
+    engine.pay_owner_salary_at(Decimal::new(200, 0), &policy, now, "salary_flagged").await.expect("Salary payment failed");
+
+    let reports = engine.get_owner_salary_reports().await;
+    assert_eq!(reports[0].immediate_payout, Decimal::new(50, 0));
+    assert_eq!(reports[1].immediate_payout, Decimal::ZERO);
+    assert_eq!(reports[1].escrow_amount, Decimal::new(200, 0));
+
+    let escrows = engine.get_compliance_escrows().await;
+    assert_eq!(escrows.len(), 1);
+    assert!(matches!(engine.release_escrow(escrows[0]).await, Err(EconomicsError::OwnerSalaryError(_))));
+
+    // Clear, then release what was held
+    engine.set_compliance_flag(false, "audit closed".to_string()).await.expect("Clear failed");
+    assert_eq!(engine.metrics.compliance_flagged.get(), 0.0);
+    let released = engine.release_escrow(escrows[0]).await.expect("Release failed");
+    assert_eq!(released, Decimal::new(200, 0));
+    assert!(engine.get_compliance_escrows().await.is_empty());
+    assert!(engine.release_escrow(escrows[0]).await.is_err());
+
+    let events = engine.get_compliance_events().await;
+    assert_eq!(events.len(), 2);
+    assert!(events[0].flagged);
+    assert_eq!(events[1].reason, "audit closed");
+}
+
+/// (treasury, miner rewards, locked, escrowed, vested, owner distributions)
+async fn routing_totals(engine: &PoEMiningEngine) -> (Decimal, Decimal, Decimal, Decimal, Decimal, Decimal) {
+    let state = engine.economic_state.read().await;
+    (
+        state.treasury_balance,
+        state.total_miner_rewards,
+        state.total_locked_coins,
+        state.total_escrowed_funds,
+        state.total_vested_amount,
+        state.total_owner_distributions,
+    )
+}
+
+#[tokio::test]
+async fn test_fee_routing_preview_matches_routed_deltas() {
+    let now = DateTime::parse_from_rfc3339("2025-06-10T00:00:00Z").unwrap().with_timezone(&Utc);
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry)
+        .expect("Failed to create engine")
+        .with_clock(Arc::new(MockClock::new(now)));
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    engine.update_owner_salary_policy(OwnerSalaryPolicy {
+        monthly_hard_cap: Decimal::new(100, 0),
+        ..OwnerSalaryPolicy::default()
+    }).await.expect("Policy update failed");
+
+    let job = create_test_job(
+        "preview_job",
+        EconomicJobType::Settlement,
+        "miner_001",
+        Decimal::new(50_000, 0),
+        Some((
+            Decimal::new(5_000, 0),
+            Decimal::new(3_000, 0),
+            Decimal::new(2_000, 0),
+            Decimal::new(1_500, 0),
+            Decimal::new(1_500, 0),
+        )),
+    );
+
+    let before = routing_totals(&engine).await;
+    let preview = engine.preview_fee_routing(&job, job.gold_equivalent_value).await.expect("Preview failed");
+
+    // $100 base + $26 DockLock salary against a $100 cap; $150 + $39 to treasury
+    assert_eq!(preview.owner_salary.gross, Decimal::new(126, 0));
+    assert_eq!(preview.owner_salary.capped, Decimal::new(100, 0));
+    assert_eq!(preview.owner_salary.cap_overflow, Decimal::new(26, 0));
+    assert_eq!(preview.owner_salary.immediate, Decimal::new(50, 0));
+    assert_eq!(preview.owner_salary.vested, Decimal::new(50, 0));
+    assert_eq!(preview.treasury_credit, Decimal::new(189, 0));
+
+    // Previewing changes nothing
+    assert_eq!(routing_totals(&engine).await, before);
+    assert_eq!(engine.owner_salary_ledger.read().await.paid_to_date, Decimal::ZERO);
+    assert!(engine.get_owner_salary_reports().await.is_empty());
+
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    let after = routing_totals(&engine).await;
+
+    // The salary is the owner's share of the fee, so the treasury only gains its own share
+    let salary = &preview.owner_salary;
+    assert_eq!(after.0 - before.0, preview.treasury_credit);
+    assert_eq!(after.1 - before.1, preview.miner_spendable);
+    assert_eq!(after.2 - before.2, preview.miner_locked);
+    assert_eq!(after.3 - before.3, salary.cap_overflow + salary.compliance_escrow);
+    assert_eq!(after.4 - before.4, salary.vested);
+    assert_eq!(after.5 - before.5, salary.immediate);
+    assert_eq!(engine.owner_salary_ledger.read().await.paid_to_date, salary.month_to_date_paid);
+
+    let reports = engine.get_owner_salary_reports().await;
+    let report = reports.last().unwrap();
+    assert_eq!(report.month, salary.month);
+    assert_eq!(report.capped_salary_amount, salary.capped);
+    assert_eq!(report.escrow_amount, salary.cap_overflow);
+}
+
+#[tokio::test]
+async fn test_route_fees_retry_moves_balances_once() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    let job = create_test_job("retried_job", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None);
+
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    let once = routing_totals(&engine).await;
+    let payments_once = engine.economic_state.read().await.payment_history.len();
+    let ledger_once = engine.owner_salary_ledger.read().await.paid_to_date;
+
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Retried fee routing failed");
+    assert_eq!(routing_totals(&engine).await, once);
+    assert_eq!(engine.economic_state.read().await.payment_history.len(), payments_once);
+    assert_eq!(engine.owner_salary_ledger.read().await.paid_to_date, ledger_once);
+
+    // A duplicate payment hands back the original record
+    let state = engine.economic_state.read().await;
+    let prior = state.payments_by_key["retried_job:treasury"].clone();
+    drop(state);
+    let replayed = engine.credit_treasury(Decimal::new(150, 0), "retried_job:treasury").await.expect("Treasury credit failed");
+    assert_eq!(replayed.id, prior.id);
+    assert_eq!(replayed.amount, Decimal::new(150, 0));
+
+    // Funding under the same key is applied once as well
+    let treasury_before = routing_totals(&engine).await.0;
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    assert_eq!(routing_totals(&engine).await.0, treasury_before);
+
+    // So are vesting and escrow reserves, which hand back the original id
+    let schedule = engine.schedule_vested_payment(Decimal::new(60, 0), 6, "retried_vesting").await.expect("Vesting schedule failed");
+    let escrow = engine.route_to_escrow(Decimal::new(40, 0), "retried_escrow").await.expect("Escrow routing failed");
+    let reserved = routing_totals(&engine).await;
+    assert_eq!(engine.schedule_vested_payment(Decimal::new(60, 0), 6, "retried_vesting").await.unwrap(), schedule);
+    assert_eq!(engine.route_to_escrow(Decimal::new(40, 0), "retried_escrow").await.unwrap(), escrow);
+    assert_eq!(routing_totals(&engine).await, reserved);
+}
+
+#[tokio::test]
+async fn test_concurrent_route_fees_settle_once() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let job = create_test_job("raced_job", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None);
+
+    // Both calls pass their previews before either takes the routing lock
+    let (first, second) = tokio::join!(
+        engine.route_fees(&job, job.gold_equivalent_value),
+        engine.route_fees(&job, job.gold_equivalent_value),
+    );
+    first.expect("Fee routing failed");
+    second.expect("Raced fee routing failed");
+
+    let state = engine.economic_state.read().await;
+    assert_eq!(state.account_balances["miner_001"], Decimal::new(150, 0));
+    assert_eq!(state.treasury_balance, Decimal::new(150, 0));
+    assert_eq!(state.payment_history.len(), 2); // Miner payment and owner immediate payout
+    drop(state);
+    assert_eq!(engine.get_owner_salary_reports().await.len(), 1);
+}
+
+#[tokio::test]
+async fn test_audit_log_records_mutations_in_order() {
+    let start = DateTime::parse_from_rfc3339("2025-07-01T00:00:00Z").unwrap().with_timezone(&Utc);
+    let clock = MockClock::new(start);
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry)
+        .expect("Failed to create engine")
+        .with_clock(Arc::new(clock.clone()));
+    let owner = engine.get_owner_salary_policy().await.transparency_address;
+
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    clock.advance(chrono::Duration::hours(1));
+    let job = create_test_job("audited_job", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None);
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    // A retry adds nothing
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Retried fee routing failed");
+    clock.advance(chrono::Duration::hours(1));
+    engine.credit_treasury(Decimal::new(500, 0), "second_funding").await.expect("Treasury credit failed");
+
+    let log = engine.audit_log.read().await;
+    let entries = log.entries();
+    let kinds: Vec<AuditEntryType> = entries.iter().map(|e| e.entry_type).collect();
+    assert_eq!(kinds, vec![
+        AuditEntryType::TreasuryCredit,
+        AuditEntryType::MinerPayment,
+        AuditEntryType::CoinLock,
+        AuditEntryType::TreasuryCredit,
+        AuditEntryType::OwnerPayment,
+        AuditEntryType::VestingScheduled,
+        AuditEntryType::TreasuryCredit,
+    ]);
+    assert!(entries.iter().enumerate().all(|(i, e)| e.sequence == i as u64));
+
+    assert_eq!(entries[1].amount, Decimal::new(150, 0));
+    assert_eq!(entries[1].resulting_balance, Decimal::new(150, 0));
+    assert_eq!(entries[1].idempotency_key.as_deref(), Some("audited_job:miner_spendable"));
+    assert_eq!(entries[2].resulting_balance, Decimal::new(100, 0));
+    assert_eq!(entries[3].idempotency_key.as_deref(), Some("audited_job:treasury"));
+    assert_eq!(entries[4].account, owner);
+    assert_eq!(entries[4].resulting_balance, Decimal::new(50, 0));
+    assert_eq!(entries[5].resulting_balance, Decimal::new(50, 0));
+
+    // Treasury: funded, credited the job's share, then funded again
+    let treasury: Vec<Decimal> = entries.iter()
+        .filter(|e| e.account == audit_log::TREASURY_ACCOUNT)
+        .map(|e| e.resulting_balance)
+        .collect();
+    assert_eq!(treasury, vec![Decimal::new(10_000, 0), Decimal::new(10_150, 0), Decimal::new(10_650, 0)]);
+
+    // Only the fee routing falls inside the middle hour
+    let json = log.export_json(start + chrono::Duration::minutes(30), start + chrono::Duration::minutes(90))
+        .expect("Export failed");
+    let exported: Vec<AuditEntry> = serde_json::from_str(&json).expect("Export is not valid JSON");
+    assert_eq!(exported.len(), 5);
+    assert_eq!(exported[0].sequence, 1);
+    assert_eq!(exported[4].entry_type, AuditEntryType::VestingScheduled);
+}
+
+#[tokio::test]
+async fn test_audit_log_records_treasury_debits() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let owner = engine.get_owner_salary_policy().await.transparency_address;
+
+    engine.credit_treasury(Decimal::new(1_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    engine.pay_to_owner_wallet(Decimal::new(100, 0), &owner, "owner_bonus").await.expect("Owner payment failed");
+    engine.schedule_vested_payment(Decimal::new(200, 0), 4, "owner_vesting").await.expect("Vesting schedule failed");
+    engine.route_to_escrow(Decimal::new(300, 0), "owner_escrow").await.expect("Escrow routing failed");
+
+    // Every outflow leaves a debit on the treasury account ahead of its own entry
+    let log = engine.audit_log.read().await;
+    let treasury: Vec<(AuditEntryType, Decimal)> = log.entries().iter()
+        .filter(|e| e.account == audit_log::TREASURY_ACCOUNT)
+        .map(|e| (e.entry_type, e.resulting_balance))
+        .collect();
+    assert_eq!(treasury, vec![
+        (AuditEntryType::TreasuryCredit, Decimal::new(1_000, 0)),
+        (AuditEntryType::TreasuryDebit, Decimal::new(900, 0)),
+        (AuditEntryType::TreasuryDebit, Decimal::new(700, 0)),
+        (AuditEntryType::TreasuryDebit, Decimal::new(400, 0)),
+    ]);
+    let debit = &log.entries()[1];
+    assert_eq!(debit.amount, Decimal::new(100, 0));
+    assert_eq!(debit.idempotency_key.as_deref(), Some("owner_bonus"));
+    assert_eq!(log.entries()[2].entry_type, AuditEntryType::OwnerPayment);
+    drop(log);
+    assert_eq!(routing_totals(&engine).await.0, Decimal::new(400, 0));
+}
+
+#[tokio::test]
+async fn test_economic_snapshot_reflects_mutations() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    let job = create_test_job("snapshot_job", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None);
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    engine.reward_pool.write().await.insert(TokenType::Nexus, Decimal::new(42, 0));
+    issue_nex_at_phi(&engine, Decimal::new(10, 0)).await;
+
+    let snapshot = engine.economic_snapshot().await;
+    assert_eq!(snapshot.treasury_balance, Decimal::new(10_150, 0));
+    assert_eq!(snapshot.locked_coins, Decimal::new(100, 0));
+    assert_eq!(snapshot.escrowed_funds, Decimal::ZERO);
+    assert_eq!(snapshot.vesting_outstanding, Decimal::new(50, 0));
+    assert_eq!(snapshot.active_vesting_schedules, 1);
+    assert_eq!(snapshot.reward_pool[&TokenType::Nexus], Decimal::new(42, 0));
+    assert_eq!(snapshot.supply.epoch, 1);
+    assert_eq!(snapshot.poe_index.as_ref().map(|index| index.phi_value), Some(Decimal::new(10, 0)));
+    serde_json::to_string(&snapshot).expect("Snapshot is not serializable");
+
+    // Vesting moves funds from treasury to the vesting reserve under one lock,
+    // so every snapshot sees the pair summing to the same total
+    let engine = Arc::new(engine);
+    let total = snapshot.treasury_balance + snapshot.vesting_outstanding;
+    let writer = {
+        let engine = engine.clone();
+        tokio::spawn(async move {
+            for i in 0..50 {
+                engine.schedule_vested_payment(Decimal::new(10, 0), 1, &format!("vesting_{}", i)).await.expect("Vesting schedule failed");
+                tokio::task::yield_now().await;
+            }
+        })
+    };
+    for _ in 0..50 {
+        let snapshot = engine.economic_snapshot().await;
+        assert_eq!(snapshot.treasury_balance + snapshot.vesting_outstanding, total);
+        tokio::task::yield_now().await;
+    }
+    writer.await.unwrap();
+    assert_eq!(engine.economic_snapshot().await.active_vesting_schedules, 51);
+}
+
+/// Register a miner with the given completed jobs
+async fn insert_miner(engine: &PoEMiningEngine, miner_id: &str, completed_jobs: Vec<EconomicJob>) {
+    engine.active_miners.write().await.insert(miner_id.to_string(), MinerState {
+        miner_id: miner_id.to_string(),
+        total_poe_score: Decimal::ZERO,
+        completed_jobs,
+        last_reward_time: Utc::now(),
+        prestige_multiplier: Decimal::ONE,
+        tokens_earned: HashMap::new(),
+        claimable_rewards: HashMap::new(),
+    });
+}
+
+#[tokio::test]
+async fn test_poe_score_single_job() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let job = create_test_job("job_1", EconomicJobType::DockLockHosting, "miner_a", Decimal::new(40_000, 0),
+        Some((Decimal::new(10_000, 0), Decimal::ZERO, Decimal::ZERO, Decimal::ZERO, Decimal::ZERO)));
+    insert_miner(&engine, "miner_a", vec![job]).await;
+
+    // $40k gold + $10k cluster rent at $100 per point
+    let score = engine.calculate_poe_score("miner_a").await.expect("Score failed");
+    assert_eq!(score.raw_score, Decimal::new(500, 0));
+    assert_eq!(score.normalized_score, Decimal::ONE);
+    assert_eq!(score.job_count, 1);
+    assert_eq!(score.total_job_value, Decimal::new(50_000, 0));
+    assert_eq!(engine.active_miners.read().await["miner_a"].total_poe_score, Decimal::new(500, 0));
+    assert_eq!(engine.metrics.poe_scores_calculated.get(), 1.0);
+}
+
+#[tokio::test]
+async fn test_poe_score_diminishing_returns() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let jobs = (0..30)
+        .map(|i| create_test_job(&format!("job_{}", i), EconomicJobType::Settlement, "whale", Decimal::new(10_000, 0), None))
+        .collect();
+    insert_miner(&engine, "whale", jobs).await;
+    insert_miner(&engine, "small", vec![
+        create_test_job("job_s", EconomicJobType::Settlement, "small", Decimal::new(50_000, 0), None),
+    ]).await;
+
+    // 3,000 linear points: 1,000 + 1,000·2,000/3,000
+    let score = engine.calculate_poe_score("whale").await.expect("Score failed");
+    let expected = Decimal::new(1_000, 0) + Decimal::new(2_000_000, 0) / Decimal::new(3_000, 0);
+    assert_eq!(score.raw_score, expected);
+    assert!(score.raw_score < Decimal::new(3_000, 0));
+    assert_eq!(score.normalized_score, expected / (expected + Decimal::new(500, 0)));
+}
+
+#[tokio::test]
+async fn test_poe_score_no_jobs_is_zero() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    insert_miner(&engine, "idle", Vec::new()).await;
+
+    let score = engine.calculate_poe_score("idle").await.expect("Score failed");
+    assert_eq!(score.raw_score, Decimal::ZERO);
+    assert_eq!(score.normalized_score, Decimal::ZERO);
+    assert_eq!(score.job_count, 0);
+    assert!(engine.calculate_poe_score("unknown").await.is_err());
+}
+
+#[tokio::test]
+async fn test_miner_weights_sum_normalize() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let job_types = [
+        EconomicJobType::Validation, EconomicJobType::Settlement, EconomicJobType::Development,
+        EconomicJobType::Commerce, EconomicJobType::DockLockHosting, EconomicJobType::GasFees,
+        EconomicJobType::DataPipeline, EconomicJobType::SecurityLayer,
+    ];
+    let diverse = job_types.iter().enumerate()
+        .map(|(i, job_type)| create_test_job(&format!("d_{}", i), job_type.clone(), "diverse", Decimal::new(5_000, 0), None))
+        .collect();
+    let focused = (0..8)
+        .map(|i| create_test_job(&format!("f_{}", i), EconomicJobType::Validation, "focused", Decimal::new(5_000, 0), None))
+        .collect();
+    insert_miner(&engine, "diverse", diverse).await;
+    insert_miner(&engine, "focused", focused).await;
+    engine.active_miners.write().await.get_mut("focused").unwrap().prestige_multiplier = Decimal::new(15, 1);
+
+    let weights = engine.compute_miner_weights().await.expect("Weights failed");
+    assert_eq!(weights.len(), 2);
+    let normalized_total: Decimal = weights.iter().map(|w| w.normalized_poe_score).sum();
+    assert_eq!(normalized_total, Decimal::ONE);
+
+    let diverse = weights.iter().find(|w| w.miner_id == "diverse").unwrap();
+    let focused = weights.iter().find(|w| w.miner_id == "focused").unwrap();
+    assert_eq!(diverse.diversity_multiplier, Decimal::ONE);
+    assert_eq!(focused.diversity_multiplier, Decimal::new(5, 1));
+    assert_eq!(focused.prestige_multiplier, Decimal::new(15, 1));
+    for weight in &weights {
+        assert_eq!(weight.total_weight,
+                   weight.normalized_poe_score * weight.prestige_multiplier * weight.diversity_multiplier);
+    }
+}
+
+#[tokio::test]
+async fn test_distribute_nex_pro_rata() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    // Same job mix, so weights follow PoE score alone: 3:1
+    insert_miner(&engine, "big", vec![
+        create_test_job("b", EconomicJobType::Commerce, "big", Decimal::new(30_000, 0), None),
+    ]).await;
+    insert_miner(&engine, "small", vec![
+        create_test_job("s", EconomicJobType::Commerce, "small", Decimal::new(10_000, 0), None),
+    ]).await;
+
+    let distribution = engine.distribute_nex(1_000).await.expect("Distribution failed");
+    assert_eq!(distribution["big"], 750);
+    assert_eq!(distribution["small"], 250);
+
+    // Uneven split: the remainder still lands so nothing is lost
+    let distribution = engine.distribute_nex(1_001).await.expect("Distribution failed");
+    assert_eq!(distribution.values().sum::<u64>(), 1_001);
+    assert_eq!(distribution["big"], 751);
+
+    let miners = engine.active_miners.read().await;
+    assert_eq!(miners["big"].tokens_earned[&TokenType::Nexus], Decimal::new(1_501, 0));
+    assert_eq!(miners["small"].tokens_earned[&TokenType::Nexus], Decimal::new(500, 0));
+}
+
+fn custodian_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn engine_with_custodians() -> PoEMiningEngine {
+    PoEMiningEngine::new(&Registry::new())
+        .expect("Failed to create engine")
+        .with_reserve_custodian("vault_zurich", custodian_key(1).verifying_key())
+        .with_reserve_custodian("vault_london", custodian_key(2).verifying_key())
+}
+
+#[tokio::test]
+async fn test_aur_mint_with_reserve_proof() {
+    let engine = engine_with_custodians();
+    let grams = Decimal::new(1_2505, 1); // 1,250.5 g
+
+    let proof = ReserveProof::sign("vault_zurich", grams, &custodian_key(1));
+    let minted = engine.mint_aur(grams, proof).await.expect("Mint failed");
+    assert_eq!(minted, 1_250);
+    let supply = engine.token_supply.read().await.clone();
+    assert_eq!(supply.aur_supply, 1_250);
+    assert_eq!(supply.aur_backing_grams, grams);
+}
+
+#[tokio::test]
+async fn test_aur_mint_rejected_without_valid_proof() {
+    let engine = engine_with_custodians();
+    let grams = Decimal::new(100, 0);
+
+    // Tampered attestation
+    let mut forged = ReserveProof::sign("vault_zurich", Decimal::new(10, 0), &custodian_key(1));
+    forged.gold_grams = grams;
+    assert!(engine.mint_aur(grams, forged).await.is_err());
+
+    // Proof for a different amount
+    let proof = ReserveProof::sign("vault_zurich", Decimal::new(50, 0), &custodian_key(1));
+    assert!(engine.mint_aur(grams, proof).await.is_err());
+
+    // Self-consistent proof signed with a key other than the custodian's
+    let proof = ReserveProof::sign("vault_zurich", grams, &custodian_key(2));
+    assert!(engine.mint_aur(grams, proof).await.is_err());
+
+    // Custodian the engine was never configured with
+    let proof = ReserveProof::sign("vault_unknown", grams, &custodian_key(3));
+    assert!(engine.mint_aur(grams, proof).await.is_err());
+
+    // Replayed proof
+    let proof = ReserveProof::sign("vault_zurich", grams, &custodian_key(1));
+    engine.mint_aur(grams, proof.clone()).await.expect("Mint failed");
+    assert!(engine.mint_aur(grams, proof).await.is_err());
+    assert_eq!(engine.token_supply.read().await.aur_supply, 100);
+}
+
+#[tokio::test]
+async fn test_aur_over_redemption_rejected() {
+    let engine = engine_with_custodians();
+    let grams = Decimal::new(500, 0);
+    let proof = ReserveProof::sign("vault_london", grams, &custodian_key(2));
+    engine.mint_aur(grams, proof).await.expect("Mint failed");
+
+    assert_eq!(engine.redeem_aur(200).await.expect("Redeem failed"), Decimal::new(200, 0));
+    assert!(matches!(engine.redeem_aur(301).await, Err(EconomicsError::TokenSupplyError(_))));
+
+    let supply = engine.token_supply.read().await.clone();
+    assert_eq!(supply.aur_supply, 300);
+    assert_eq!(supply.aur_backing_grams, Decimal::new(300, 0));
+}
+
+#[tokio::test]
+async fn test_supply_rollback_restores_snapshot() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.governance_params.write().await.tau_nex = Decimal::ONE;
+
+    // Three epochs of issuance, snapshotting after each
+    issue_nex_at_phi(&engine, Decimal::new(3, 0)).await;
+    assert_eq!(engine.snapshot_supply().await.expect("Snapshot failed"), 1);
+    let epoch_one = engine.token_supply.read().await.clone();
+
+    engine.adjust_flx_supply(&net_demand(10_000)).await.expect("FLX adjustment failed");
+    issue_nex_at_phi(&engine, Decimal::new(3, 0)).await;
+    engine.snapshot_supply().await.expect("Snapshot failed");
+    issue_nex_at_phi(&engine, Decimal::new(3, 0)).await;
+    engine.snapshot_supply().await.expect("Snapshot failed");
+    assert_eq!(engine.token_supply.read().await.nex_supply, 302_250);
+
+    engine.rollback_to_epoch(1).await.expect("Rollback failed");
+    let supply = engine.token_supply.read().await.clone();
+    assert_eq!(supply.epoch, 1);
+    assert_eq!(supply.nex_supply, epoch_one.nex_supply);
+    assert_eq!(supply.flx_supply, epoch_one.flx_supply);
+    assert_eq!(supply.gen_supply, epoch_one.gen_supply);
+    assert_eq!(supply.aur_supply, epoch_one.aur_supply);
+
+    // Snapshots after the rollback point are discarded
+    assert!(engine.rollback_to_epoch(3).await.is_err());
+}
+
+#[tokio::test]
+async fn test_supply_snapshot_ring_depth() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine")
+        .with_supply_snapshot_depth(2);
+
+    for _ in 0..3 {
+        issue_nex_at_phi(&engine, Decimal::ZERO).await;
+        engine.snapshot_supply().await.expect("Snapshot failed");
+    }
+
+    let epochs: Vec<u64> = engine.supply_snapshots.read().await.iter().map(|(epoch, _)| *epoch).collect();
+    assert_eq!(epochs, vec![2, 3]);
+    assert!(engine.rollback_to_epoch(1).await.is_err());
+}
+
+fn salary_cap_change(cap: i64) -> ParameterChange {
+    ParameterChange::OwnerSalaryPolicy(OwnerSalaryPolicy {
+        monthly_hard_cap: Decimal::new(cap, 0),
+        ..OwnerSalaryPolicy::default()
+    })
+}
+
+async fn fund_gen(engine: &PoEMiningEngine, account: &str, amount: u64) {
+    engine.token_balances.write().await
+        .insert(account.to_string(), HashMap::from([(TokenType::Genesis, amount)]));
+}
+
+/// Engine on a mock clock with GEN held by a proposer and three voters
+async fn governance_fixture() -> (PoEMiningEngine, MockClock) {
+    let clock = MockClock::new(DateTime::parse_from_rfc3339("2025-06-01T00:00:00Z").unwrap().with_timezone(&Utc));
+    let engine = PoEMiningEngine::new(&Registry::new())
+        .expect("Failed to create engine")
+        .with_clock(Arc::new(clock.clone()));
+    for (account, amount) in [("proposer", 100), ("voter", 5_000), ("whale", 15_000), ("skeptic", 5_000)] {
+        fund_gen(&engine, account, amount).await;
+    }
+    (engine, clock)
+}
+
+#[tokio::test]
+async fn test_proposal_rejected_for_low_stake() {
+    let (engine, _) = governance_fixture().await;
+    fund_gen(&engine, "proposer", 99).await;
+
+    let result = engine.submit_proposal("proposer".to_string(), salary_cap_change(100_000)).await;
+    assert!(matches!(result, Err(EconomicsError::GovernanceFailed(_))));
+    let result = engine.submit_proposal("unfunded".to_string(), salary_cap_change(100_000)).await;
+    assert!(matches!(result, Err(EconomicsError::GovernanceFailed(_))));
+    assert!(engine.proposals.read().await.is_empty());
+}
+
+#[tokio::test]
+async fn test_proposal_fails_quorum() {
+    let (engine, clock) = governance_fixture().await;
+    let id = engine.submit_proposal("proposer".to_string(), salary_cap_change(100_000)).await
+        .expect("Submission failed");
+
+    // 5,000 of 100,000 GEN is below the 10% quorum
+    engine.vote(id, "voter".to_string(), VoteType::For).await.expect("Vote failed");
+    clock.advance(chrono::Duration::hours(169));
+    assert_eq!(engine.tally(id).await.expect("Tally failed"), ProposalStatus::Expired);
+    assert!(engine.vote(id, "whale".to_string(), VoteType::For).await.is_err());
+    assert!(engine.execute(id).await.is_err());
+    assert_eq!(engine.get_owner_salary_policy().await.monthly_hard_cap, Decimal::new(250_000, 0));
+}
+
+#[tokio::test]
+async fn test_proposal_voting_deadline_enforced() {
+    let (engine, clock) = governance_fixture().await;
+    let id = engine.submit_proposal("proposer".to_string(), salary_cap_change(100_000)).await
+        .expect("Submission failed");
+    let voting_end = engine.proposals.read().await[&id].voting_end;
+    assert_eq!(voting_end, clock.now() + chrono::Duration::hours(168));
+
+    // Votes weigh the voter's GEN balance; no balance, no vote
+    engine.vote(id, "whale".to_string(), VoteType::For).await.expect("Vote failed");
+    assert!(engine.vote(id, "unfunded".to_string(), VoteType::For).await.is_err());
+    assert_eq!(engine.proposals.read().await[&id].vote_totals(), (15_000, 0, 0));
+
+    // Voting is still open, so it cannot be closed early
+    clock.set(voting_end);
+    assert!(engine.tally(id).await.is_err());
+
+    clock.advance(chrono::Duration::seconds(1));
+    assert!(engine.vote(id, "skeptic".to_string(), VoteType::Against).await.is_err());
+    assert_eq!(engine.tally(id).await.expect("Tally failed"), ProposalStatus::Passed);
+    assert_eq!(engine.proposals.read().await[&id].vote_totals(), (15_000, 0, 0));
+}
+
+#[tokio::test]
+async fn test_proposal_execution_waits_for_timelock() {
+    let (engine, clock) = governance_fixture().await;
+    let id = engine.submit_proposal("proposer".to_string(), salary_cap_change(100_000)).await
+        .expect("Submission failed");
+
+    engine.vote(id, "whale".to_string(), VoteType::For).await.expect("Vote failed");
+    engine.vote(id, "skeptic".to_string(), VoteType::Against).await.expect("Vote failed");
+    assert!(engine.vote(id, "whale".to_string(), VoteType::For).await.is_err());
+
+    let passed_at = clock.now() + chrono::Duration::hours(169);
+    assert_eq!(engine.tally_at(id, passed_at).await.expect("Tally failed"), ProposalStatus::Passed);
+
+    // 48h timelock: one hour early is rejected and changes nothing
+    let early = engine.execute_at(id, passed_at + chrono::Duration::hours(47)).await;
+    assert!(matches!(early, Err(EconomicsError::GovernanceFailed(_))));
+    assert_eq!(engine.get_owner_salary_policy().await.monthly_hard_cap, Decimal::new(250_000, 0));
+
+    engine.execute_at(id, passed_at + chrono::Duration::hours(48)).await.expect("Execution failed");
+    assert_eq!(engine.get_owner_salary_policy().await.monthly_hard_cap, Decimal::new(100_000, 0));
+    assert_eq!(engine.proposals.read().await[&id].status, ProposalStatus::Executed);
+    assert!(engine.execute_at(id, passed_at + chrono::Duration::hours(72)).await.is_err());
+}
+
+#[tokio::test]
+async fn test_docklock_revenue_raises_poe_score_and_eligibility() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let params = engine.governance_params.read().await.clone();
+    let hosting_revenue = Some((Decimal::new(3_000, 0), Decimal::new(1_000, 0), Decimal::new(1_000, 0), Decimal::ZERO, Decimal::ZERO));
+
+    insert_miner(&engine, "plain", vec![
+        create_test_job("p", EconomicJobType::DockLockHosting, "plain", Decimal::new(6_000, 0), None),
+    ]).await;
+    insert_miner(&engine, "hosting", vec![
+        create_test_job("h", EconomicJobType::DockLockHosting, "hosting", Decimal::new(6_000, 0), hosting_revenue),
+    ]).await;
+    insert_miner(&engine, "validator", vec![
+        create_test_job("v", EconomicJobType::Validation, "validator", Decimal::new(6_000, 0), hosting_revenue),
+    ]).await;
+
+    let plain = engine.calculate_poe_score("plain").await.expect("Score failed");
+    let hosting = engine.calculate_poe_score("hosting").await.expect("Score failed");
+    let validator = engine.calculate_poe_score("validator").await.expect("Score failed");
+
+    // $5k DockLock revenue counts fully on hosting jobs, at half weight on validation
+    assert_eq!(plain.raw_score, Decimal::new(60, 0));
+    assert_eq!(hosting.raw_score, Decimal::new(110, 0));
+    assert_eq!(hosting.docklock_volume, Decimal::new(5_000, 0));
+    assert_eq!(validator.raw_score, Decimal::new(85, 0));
+
+    // Crossing τ_NEX = 100 unlocks NEX for the hosting miner only
+    let plain_eligibility = TokenMintingEligibility::from_poe_score(&plain, &params);
+    let hosting_eligibility = TokenMintingEligibility::from_poe_score(&hosting, &params);
+    assert_eq!(plain_eligibility.eligible_tokens, vec![TokenType::Flux]);
+    assert!(hosting_eligibility.eligible_tokens.contains(&TokenType::Nexus));
+    assert_eq!(hosting_eligibility.docklock_volume, Decimal::new(5_000, 0));
+}
+
+async fn eligibility_for_volume(gold_value: i64) -> TokenMintingEligibility {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    insert_miner(&engine, "miner", vec![
+        create_test_job("job", EconomicJobType::Commerce, "miner", Decimal::new(gold_value, 0), None),
+    ]).await;
+    engine.evaluate_minting_eligibility("miner").await.expect("Eligibility failed")
+}
+
+#[tokio::test]
+async fn test_minting_eligibility_low_score() {
+    // 30 PoE is under every τ
+    let eligibility = eligibility_for_volume(3_000).await;
+    assert_eq!(eligibility.poe_score, Decimal::new(30, 0));
+    assert!(eligibility.eligible_tokens.is_empty());
+}
+
+#[tokio::test]
+async fn test_minting_eligibility_mid_score() {
+    // 200 PoE clears τ_NEX = 100 and τ_FLX = 50 but not τ_GEN = 500
+    let eligibility = eligibility_for_volume(20_000).await;
+    assert_eq!(eligibility.eligible_tokens, vec![TokenType::Nexus, TokenType::Flux]);
+}
+
+#[tokio::test]
+async fn test_minting_eligibility_high_score() {
+    let eligibility = eligibility_for_volume(60_000).await;
+    assert_eq!(eligibility.miner_id, "miner");
+    assert_eq!(eligibility.eligible_tokens, vec![TokenType::Genesis, TokenType::Nexus, TokenType::Flux]);
+}
+
+#[tokio::test]
+async fn test_genesis_allocation_buckets_match_caps() {
+    let alloc = GenesisAllocation::default();
+    assert_eq!(alloc.subtotal(TokenType::Genesis), 100_000);
+    assert_eq!(alloc.subtotal(TokenType::Nexus), 300_000);
+    assert_eq!(alloc.subtotal(TokenType::Flux), 500_000);
+    assert!(alloc.validate().is_ok());
+
+    let skewed = GenesisAllocation { founder_allocation: 25_000, ..GenesisAllocation::default() };
+    assert!(matches!(skewed.validate(), Err(EconomicsError::TokenSupplyError(_))));
+}
+
+#[tokio::test]
+async fn test_genesis_allocation_credits_accounts() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let alloc = GenesisAllocation::default();
+
+    engine.apply_genesis_allocation(&alloc).await.expect("Genesis allocation failed");
+    {
+        let balances = engine.token_balances.read().await;
+        assert_eq!(balances["genesis:treasury_reserve"][&TokenType::Genesis], 60_000);
+        assert_eq!(balances["genesis:mining_rewards"][&TokenType::Nexus], 200_000);
+        assert_eq!(balances["genesis:liquidity_pools"][&TokenType::Flux], 100_000);
+        assert_eq!(balances.len(), 10);
+    }
+
+    let supply = engine.token_supply.read().await.clone();
+    assert_eq!(supply.gen_supply, 100_000);
+    assert_eq!(supply.nex_supply, 300_000);
+    assert_eq!(supply.flx_supply, 500_000);
+
+    // Genesis happens once
+    assert!(engine.apply_genesis_allocation(&alloc).await.is_err());
+}
+
+#[tokio::test]
+async fn test_job_queue_dequeues_highest_priority_oldest_first() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+
+    let submissions = [("bulk_1", 1), ("settle_1", 9), ("bulk_2", 1), ("settle_2", 9), ("audit", 5)];
+    for (job_id, priority) in submissions {
+        let job = EconomicJob {
+            priority,
+            ..create_test_job(job_id, EconomicJobType::Commerce, "miner_1", Decimal::new(100, 0), None)
+        };
+        engine.add_economic_job(job).await.unwrap();
+    }
+    assert_eq!(engine.job_queue.read().await.len(), 5);
+
+    let mut order = Vec::new();
+    while let Some(job) = engine.dequeue_next_job().await {
+        order.push(job.job_id);
+    }
+    assert_eq!(order, vec!["settle_1", "settle_2", "audit", "bulk_1", "bulk_2"]);
+    assert!(engine.dequeue_next_job().await.is_none());
+}
+
+#[tokio::test]
+async fn test_batched_job_submission_takes_fewer_queue_locks() {
+    let jobs: Vec<EconomicJob> = (0..1_000)
+        .map(|i| create_test_job(&format!("job_{}", i), EconomicJobType::Commerce, "miner_1", Decimal::new(100, 0), None))
+        .collect();
+
+    let per_job = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    for job in jobs.clone() {
+        per_job.add_economic_job(job).await.unwrap();
+    }
+    let batched = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    batched.add_economic_jobs(jobs.clone()).await.unwrap();
+
+    assert_eq!(per_job.job_queue_lock_acquisitions(), 1_000);
+    assert_eq!(batched.job_queue_lock_acquisitions(), 1);
+    assert_eq!(batched.job_queue.read().await.len(), 1_000);
+
+    // Same dequeue order either way
+    for _ in 0..jobs.len() {
+        let a = per_job.dequeue_next_job().await.unwrap();
+        let b = batched.dequeue_next_job().await.unwrap();
+        assert_eq!(a.job_id, b.job_id);
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn test_job_batch_window_coalesces_single_submissions() {
+    let engine = PoEMiningEngine::new(&Registry::new())
+        .expect("Failed to create engine")
+        .with_job_batch_window(Duration::from_millis(20));
+    for i in 0..100 {
+        let job = create_test_job(&format!("job_{}", i), EconomicJobType::Commerce, "miner_1", Decimal::new(100, 0), None);
+        engine.add_economic_job(job).await.unwrap();
+    }
+    // Held until the window closes, but already visible to queue readers
+    assert_eq!(engine.job_queue.read().await.held_len(), 100);
+    assert_eq!(engine.job_queue.read().await.len(), 100);
+    assert_eq!(engine.job_queue.read().await.peek().unwrap().job_id, "job_0");
+    assert_eq!(engine.job_queue_lock_acquisitions(), 0);
+
+    // The paused clock jumps straight past the window, running the flush
+    tokio::time::sleep(Duration::from_millis(21)).await;
+    assert_eq!(engine.job_queue.read().await.held_len(), 0);
+    assert_eq!(engine.job_queue.read().await.len(), 100);
+    assert_eq!(engine.job_queue_lock_acquisitions(), 1);
+
+    // A consumer never misses a job still waiting in the batch
+    let late = create_test_job("late", EconomicJobType::Commerce, "miner_1", Decimal::new(100, 0), None);
+    engine.add_economic_job(EconomicJob { priority: 9, ..late }).await.unwrap();
+    assert_eq!(engine.job_queue.read().await.peek().unwrap().job_id, "late");
+    assert_eq!(engine.dequeue_next_job().await.unwrap().job_id, "late");
+}
+
+#[tokio::test]
+async fn test_route_fees_batch_matches_sequential_routing() {
+    let jobs = vec![
+        create_test_job("batch_job_1", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None),
+        create_test_job("batch_job_2", EconomicJobType::Commerce, "miner_002", Decimal::new(20_000, 0), None),
+        create_test_job(
+            "batch_job_3", EconomicJobType::DockLockHosting, "miner_001", Decimal::new(10_000, 0),
+            Some((Decimal::new(5_000, 0), Decimal::new(1_000, 0), Decimal::ZERO, Decimal::ZERO, Decimal::ZERO)),
+        ),
+    ];
+
+    let sequential = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    let batched = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    for engine in [&sequential, &batched] {
+        engine.credit_treasury(Decimal::new(10_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    }
+
+    for job in &jobs {
+        sequential.route_fees(job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    }
+    let report = batched.route_fees_batch(&jobs).await.expect("Batch fee routing failed");
+
+    assert_eq!(routing_totals(&batched).await, routing_totals(&sequential).await);
+    assert_eq!(
+        batched.owner_salary_ledger.read().await.paid_to_date,
+        sequential.owner_salary_ledger.read().await.paid_to_date,
+    );
+    let owner = batched.get_owner_salary_policy().await.transparency_address;
+    assert_eq!(
+        batched.economic_state.read().await.account_balances.get(&owner),
+        sequential.economic_state.read().await.account_balances.get(&owner),
+    );
+
+    // Per-job accounting survives the aggregation
+    assert_eq!(report.jobs.len(), 3);
+    assert!(report.skipped.is_empty());
+    let expected_miner_001: Decimal = report.jobs.iter()
+        .filter(|preview| preview.job_id != "batch_job_2")
+        .map(|preview| preview.miner_spendable)
+        .sum();
+    assert_eq!(report.miner_spendable["miner_001"], expected_miner_001);
+    assert_eq!(report.treasury_credit, report.jobs.iter().map(|preview| preview.treasury_credit).sum::<Decimal>());
+    let treasury_keys: Vec<Option<String>> = batched.audit_log.read().await.entries().iter()
+        .filter(|entry| entry.entry_type == AuditEntryType::TreasuryCredit)
+        .map(|entry| entry.idempotency_key.clone())
+        .collect();
+    assert_eq!(treasury_keys[1..], [
+        Some("batch_job_1:treasury".to_string()),
+        Some("batch_job_2:treasury".to_string()),
+        Some("batch_job_3:treasury".to_string()),
+    ]);
+
+    // Idempotency keys stop a second settlement, batched or not
+    let settled = routing_totals(&batched).await;
+    let retry = batched.route_fees_batch(&jobs).await.expect("Retried batch failed");
+    assert_eq!(retry.skipped.len(), 3);
+    batched.route_fees(&jobs[0], jobs[0].gold_equivalent_value).await.expect("Fee routing failed");
+    assert_eq!(routing_totals(&batched).await, settled);
+}
+
+#[tokio::test]
+async fn test_route_fees_batch_racing_route_fees_settles_once() {
+    let jobs = vec![
+        create_test_job("raced_batch_1", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None),
+        create_test_job("raced_batch_2", EconomicJobType::Commerce, "miner_002", Decimal::new(20_000, 0), None),
+    ];
+    let sequential = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    for job in &jobs {
+        sequential.route_fees(job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    }
+
+    // Both pass their previews before either holds the routing lock; the
+    // batch decides what is pending only once it does
+    let engine = PoEMiningEngine::new(&Registry::new()).expect("Failed to create engine");
+    let (batch, single) = tokio::join!(
+        engine.route_fees_batch(&jobs),
+        engine.route_fees(&jobs[0], jobs[0].gold_equivalent_value),
+    );
+    batch.expect("Batch fee routing failed");
+    single.expect("Fee routing failed");
+
+    assert_eq!(routing_totals(&engine).await, routing_totals(&sequential).await);
+    assert_eq!(
+        engine.owner_salary_ledger.read().await.paid_to_date,
+        sequential.owner_salary_ledger.read().await.paid_to_date,
+    );
+}
+
+#[tokio::test]
+async fn test_spendable_treasury_excludes_vesting_and_escrow() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    engine.credit_treasury(Decimal::new(1_000, 0), "treasury_funding").await.expect("Treasury credit failed");
+    assert_eq!(engine.spendable_treasury().await, Decimal::new(1_000, 0));
+
+    engine.schedule_vested_payment(Decimal::new(200, 0), 4, "owner_vesting").await.expect("Vesting schedule failed");
+    engine.route_to_escrow(Decimal::new(300, 0), "owner_escrow").await.expect("Escrow routing failed");
+    // Both left the balance when created; nothing is set aside twice
+    assert_eq!(routing_totals(&engine).await.0, Decimal::new(500, 0));
+    assert_eq!(engine.spendable_treasury().await, Decimal::new(500, 0));
+
+    // Beyond spendable is refused
+    let owner = engine.get_owner_salary_policy().await.transparency_address;
+    let result = engine.pay_to_owner_wallet(Decimal::new(600, 0), &owner, "owner_overspend").await;
+    assert!(matches!(result, Err(EconomicsError::InsufficientFunds(_))));
+    let result = engine.schedule_vested_payment(Decimal::new(600, 0), 4, "owner_overvest").await;
+    assert!(matches!(result, Err(EconomicsError::InsufficientFunds(_))));
+    assert!(matches!(engine.route_to_escrow(Decimal::new(600, 0), "owner_overescrow").await, Err(EconomicsError::InsufficientFunds(_))));
+    assert_eq!(routing_totals(&engine).await.0, Decimal::new(500, 0));
+
+    engine.pay_to_owner_wallet(Decimal::new(500, 0), &owner, "owner_within_spendable").await.expect("Owner payment failed");
+    assert_eq!(engine.spendable_treasury().await, Decimal::ZERO);
+}
+
+/// Lock `amount` for a fresh job and return the height at which it unlocks
+async fn lock_for_test_job(engine: &PoEMiningEngine, job_id: &str, amount: Decimal) -> u64 {
+    let job = create_test_job(job_id, EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None);
+    engine.increase_coin_lock(&job, amount).await.expect("Coin lock failed");
+    let state = engine.economic_state.read().await;
+    state.active_locks.values().find(|lock| lock.job_id == job_id).expect("lock recorded").unlock_height
+}
+
+#[tokio::test]
+async fn test_unlock_sweep_restores_circulating_supply() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry)
+        .expect("Failed to create engine")
+        .with_lock_duration_blocks(10);
+    let circulating_before = engine.economic_state.read().await.circulating_supply;
+
+    let early = lock_for_test_job(&engine, "early_lock", Decimal::new(100, 0)).await;
+    assert_eq!(routing_totals(&engine).await.2, Decimal::new(100, 0));
+    assert_eq!(engine.economic_state.read().await.circulating_supply, circulating_before - Decimal::new(100, 0));
+
+    // Not yet due
+    assert!(engine.process_unlocks(early - 1).await.expect("Unlock sweep failed").is_empty());
+
+    let receipts = engine.process_unlocks(early + 5).await.expect("Unlock sweep failed");
+    assert_eq!(receipts.len(), 1);
+    assert_eq!(receipts[0].job_id, "early_lock");
+    assert_eq!(receipts[0].amount, Decimal::new(100, 0));
+    assert_eq!(receipts[0].released_at_height, early + 5);
+    assert_eq!(routing_totals(&engine).await.2, Decimal::ZERO);
+    assert_eq!(engine.economic_state.read().await.circulating_supply, circulating_before);
+
+    let state = engine.economic_state.read().await;
+    assert!(state.active_locks.values().all(|lock| lock.status == LockStatus::Released));
+    drop(state);
+    let last = engine.audit_log.read().await.entries().last().cloned().unwrap();
+    assert_eq!(last.entry_type, AuditEntryType::CoinUnlock);
+    assert_eq!(last.resulting_balance, Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_unlock_sweep_never_releases_twice() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry)
+        .expect("Failed to create engine")
+        .with_lock_duration_blocks(10);
+    let first = lock_for_test_job(&engine, "first_lock", Decimal::new(100, 0)).await;
+    let circulating_after_first = engine.economic_state.read().await.circulating_supply;
+
+    assert_eq!(engine.process_unlocks(first).await.expect("Unlock sweep failed").len(), 1);
+    let circulating_released = engine.economic_state.read().await.circulating_supply;
+    assert_eq!(circulating_released, circulating_after_first + Decimal::new(100, 0));
+
+    // A later sweep only picks up the lock added since
+    let second = lock_for_test_job(&engine, "second_lock", Decimal::new(40, 0)).await;
+    let receipts = engine.process_unlocks(first.max(second) + 1_000).await.expect("Unlock sweep failed");
+    assert_eq!(receipts.iter().map(|r| r.job_id.as_str()).collect::<Vec<_>>(), vec!["second_lock"]);
+    assert!(engine.process_unlocks(u64::MAX).await.expect("Unlock sweep failed").is_empty());
+    assert_eq!(engine.economic_state.read().await.circulating_supply, circulating_released);
+    assert_eq!(routing_totals(&engine).await.2, Decimal::ZERO);
+}
+
+#[tokio::test]
+async fn test_failed_fee_routing_leaves_no_partial_balances() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    let valid_policy = engine.get_owner_salary_policy().await;
+    engine.update_owner_salary_policy(OwnerSalaryPolicy {
+        vesting_period_months: 0,
+        ..valid_policy.clone()
+    }).await.expect("Policy update failed");
+    let job = create_test_job("unvestable_job", EconomicJobType::Settlement, "miner_001", Decimal::new(50_000, 0), None);
+
+    let before = routing_totals(&engine).await;
+    let circulating_before = engine.economic_state.read().await.circulating_supply;
+    let ledger_before = engine.owner_salary_ledger.read().await.paid_to_date;
+
+    // Miner payment, coin lock and treasury credit apply, then the salary cannot vest
+    let result = engine.route_fees(&job, job.gold_equivalent_value).await;
+    assert!(matches!(result, Err(EconomicsError::InvalidAmount(_))));
+
+    assert_eq!(routing_totals(&engine).await, before);
+    let state = engine.economic_state.read().await;
+    assert_eq!(state.circulating_supply, circulating_before);
+    assert!(state.payments_by_key.keys().all(|key| !key.starts_with("unvestable_job:")));
+    assert!(state.active_locks.values().all(|lock| lock.job_id != "unvestable_job"));
+    assert_eq!(state.account_balances["miner_001"], Decimal::ZERO);
+
+    // The payment stays in the history, compensated by a reversal
+    let statuses: Vec<PaymentStatus> = state.payment_history.iter().map(|payment| payment.status).collect();
+    assert_eq!(statuses, vec![PaymentStatus::Completed, PaymentStatus::Reversed]);
+    assert_eq!(state.payment_history[1].idempotency_key, state.payment_history[0].idempotency_key);
+    assert_eq!(state.treasury_history.last().unwrap().transaction_type, TreasuryTransactionType::Reversal);
+    drop(state);
+    assert_eq!(engine.owner_salary_ledger.read().await.paid_to_date, ledger_before);
+    assert!(engine.get_owner_salary_reports().await.is_empty());
+
+    // The audit trail keeps the applied legs and their reversals, newest undone first
+    let entries = engine.audit_log.read().await.entries().to_vec();
+    let kinds: Vec<AuditEntryType> = entries.iter().map(|e| e.entry_type).collect();
+    assert_eq!(kinds, vec![
+        AuditEntryType::MinerPayment,
+        AuditEntryType::CoinLock,
+        AuditEntryType::TreasuryCredit,
+        AuditEntryType::Reversal,
+        AuditEntryType::Reversal,
+        AuditEntryType::Reversal,
+    ]);
+    assert_eq!(entries[3].account, audit_log::TREASURY_ACCOUNT);
+    assert_eq!(entries[5].account, "miner_001");
+
+    // Once the policy is fixed, the same job routes in full
+    engine.update_owner_salary_policy(valid_policy).await.expect("Policy update failed");
+    engine.route_fees(&job, job.gold_equivalent_value).await.expect("Fee routing failed");
+    assert_eq!(routing_totals(&engine).await.1 - before.1, Decimal::new(150, 0));
+}
+
+#[tokio::test]
+async fn test_reward_pool_credit_and_partial_claims() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    insert_miner(&engine, "miner_a", vec![]).await;
+    insert_miner(&engine, "miner_b", vec![]).await;
+
+    assert_eq!(engine.credit_reward_pool(TokenType::Nexus, Decimal::new(60, 0)).await.unwrap(), Decimal::new(60, 0));
+    assert_eq!(engine.credit_reward_pool(TokenType::Nexus, Decimal::new(40, 0)).await.unwrap(), Decimal::new(100, 0));
+    engine.credit_reward_pool(TokenType::Flux, Decimal::new(10, 0)).await.unwrap();
+    assert!(engine.credit_reward_pool(TokenType::Flux, Decimal::ZERO).await.is_err());
+
+    engine.accrue_rewards("miner_a", TokenType::Nexus, Decimal::new(60, 0)).await.unwrap();
+    engine.accrue_rewards("miner_a", TokenType::Flux, Decimal::new(4, 0)).await.unwrap();
+    engine.accrue_rewards("miner_b", TokenType::Nexus, Decimal::new(30, 0)).await.unwrap();
+
+    let claimed = engine.claim_rewards("miner_a").await.unwrap();
+    assert_eq!(claimed[&TokenType::Nexus], Decimal::new(60, 0));
+    assert_eq!(claimed[&TokenType::Flux], Decimal::new(4, 0));
+    let claimed = engine.claim_rewards("miner_b").await.unwrap();
+    assert_eq!(claimed[&TokenType::Nexus], Decimal::new(30, 0));
+
+    // Pools drained by exactly what was claimed; claimable balances zeroed
+    let pool = engine.reward_pool.read().await.clone();
+    assert_eq!(pool[&TokenType::Nexus], Decimal::new(10, 0));
+    assert_eq!(pool[&TokenType::Flux], Decimal::new(6, 0));
+    let miners = engine.active_miners.read().await;
+    assert_eq!(miners["miner_a"].tokens_earned[&TokenType::Nexus], Decimal::new(60, 0));
+    assert!(miners["miner_a"].claimable_rewards.is_empty());
+    assert_eq!(miners["miner_b"].tokens_earned[&TokenType::Nexus], Decimal::new(30, 0));
+    drop(miners);
+
+    // Nothing left to claim
+    assert!(engine.claim_rewards("miner_a").await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_claim_exceeding_reward_pool_rejected() {
+    let registry = Registry::new();
+    let engine = PoEMiningEngine::new(&registry).expect("Failed to create engine");
+    insert_miner(&engine, "miner_a", vec![]).await;
+    engine.credit_reward_pool(TokenType::Nexus, Decimal::new(50, 0)).await.unwrap();
+    engine.credit_reward_pool(TokenType::Flux, Decimal::new(50, 0)).await.unwrap();
+    engine.accrue_rewards("miner_a", TokenType::Nexus, Decimal::new(20, 0)).await.unwrap();
+    engine.accrue_rewards("miner_a", TokenType::Flux, Decimal::new(80, 0)).await.unwrap();
+
+    // The Flux share can't be covered, so not even the Nexus share moves
+    let result = engine.claim_rewards("miner_a").await;
+    assert!(matches!(result, Err(EconomicsError::InsufficientFunds(_))));
+    let pool = engine.reward_pool.read().await.clone();
+    assert_eq!(pool[&TokenType::Nexus], Decimal::new(50, 0));
+    assert_eq!(pool[&TokenType::Flux], Decimal::new(50, 0));
+    let miners = engine.active_miners.read().await;
+    assert!(miners["miner_a"].tokens_earned.is_empty());
+    assert_eq!(miners["miner_a"].claimable_rewards[&TokenType::Flux], Decimal::new(80, 0));
+    drop(miners);
+
+    assert!(matches!(engine.claim_rewards("unknown").await, Err(EconomicsError::MiningError(_))));
+}